@@ -0,0 +1,14 @@
+/// Generate kode gRPC (tonic/prost) dari proto/. protoc diambil dari crate vendored
+/// agar build tidak bergantung pada protoc yang terpasang di sistem.
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    if std::env::var_os("PROTOC").is_none() {
+        std::env::set_var("PROTOC", protoc_bin_vendored::protoc_bin_path()?);
+    }
+
+    tonic_build::configure().compile_protos(
+        &["proto/mindmate/v1/moods.proto"],
+        &["proto".into(), protoc_bin_vendored::include_path()?],
+    )?;
+    println!("cargo:rerun-if-changed=proto");
+    Ok(())
+}
@@ -0,0 +1,2 @@
+ALTER TABLE moods DROP CONSTRAINT IF EXISTS moods_user_id_date_key;
+ALTER TABLE users DROP CONSTRAINT IF EXISTS users_username_key;
\ No newline at end of file
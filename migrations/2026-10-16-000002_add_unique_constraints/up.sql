@@ -0,0 +1,17 @@
+-- Username duplikat (jika ada) diberi suffix id agar constraint bisa dibuat
+UPDATE users u
+SET username = u.username || '_' || u.id
+WHERE EXISTS (
+    SELECT 1 FROM users other
+    WHERE other.username = u.username AND other.id < u.id
+);
+
+-- Mood ganda di tanggal yang sama hanya bisa terjadi karena race; simpan yang terbaru
+DELETE FROM moods m
+WHERE EXISTS (
+    SELECT 1 FROM moods other
+    WHERE other.user_id = m.user_id AND other.date = m.date AND other.id > m.id
+);
+
+ALTER TABLE users ADD CONSTRAINT users_username_key UNIQUE (username);
+ALTER TABLE moods ADD CONSTRAINT moods_user_id_date_key UNIQUE (user_id, date);
\ No newline at end of file
@@ -0,0 +1,13 @@
+-- Tanpa foreign key agar log tetap ada setelah akun dihapus
+CREATE TABLE audit_logs (
+    id SERIAL PRIMARY KEY,
+    actor_user_id INTEGER,
+    action VARCHAR(64) NOT NULL,
+    target_user_id INTEGER,
+    details TEXT,
+    ip_address VARCHAR(64),
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_audit_logs_actor_user_id ON audit_logs (actor_user_id, created_at);
+CREATE INDEX idx_audit_logs_target_user_id ON audit_logs (target_user_id, created_at);
\ No newline at end of file
@@ -0,0 +1,19 @@
+-- Waktu penyelesaian tiap langkah onboarding; NULL berarti belum selesai
+CREATE TABLE user_onboarding (
+    user_id INTEGER PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    profile_completed_at TIMESTAMP,
+    first_mood_at TIMESTAMP,
+    first_journal_at TIMESTAMP,
+    reminder_set_at TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Isi dari data yang sudah ada agar pengguna lama tidak mengulang checklist
+INSERT INTO user_onboarding (user_id, profile_completed_at, first_mood_at, first_journal_at)
+SELECT
+    u.id,
+    CASE WHEN u.age IS NOT NULL AND u.gender IS NOT NULL AND u.avatar IS NOT NULL AND u.avatar <> ''
+        THEN u.updated_at END,
+    (SELECT MIN(m.created_at) FROM moods m WHERE m.user_id = u.id),
+    (SELECT MIN(j.created_at) FROM journals j WHERE j.user_id = u.id)
+FROM users u;
\ No newline at end of file
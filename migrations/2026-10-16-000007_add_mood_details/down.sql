@@ -0,0 +1 @@
+ALTER TABLE moods DROP COLUMN details;
\ No newline at end of file
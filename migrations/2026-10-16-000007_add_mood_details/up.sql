@@ -0,0 +1,2 @@
+-- Data terstruktur opsional per entri mood (energi, kecemasan, jam tidur)
+ALTER TABLE moods ADD COLUMN details JSONB;
\ No newline at end of file
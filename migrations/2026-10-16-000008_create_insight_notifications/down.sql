@@ -0,0 +1 @@
+DROP TABLE IF EXISTS insight_notifications;
\ No newline at end of file
@@ -0,0 +1,9 @@
+-- Alert insight yang sudah dikirim ke pengguna, agar satu penurunan mood hanya dinotifikasi sekali
+CREATE TABLE insight_notifications (
+    id SERIAL PRIMARY KEY,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    kind VARCHAR(50) NOT NULL,
+    start_date DATE NOT NULL,
+    notified_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (user_id, kind, start_date)
+);
\ No newline at end of file
@@ -0,0 +1 @@
+DROP TABLE IF EXISTS journal_drafts;
\ No newline at end of file
@@ -0,0 +1,7 @@
+-- Satu draft jurnal per pengguna untuk autosave, terpisah dari jurnal yang sudah dibuat
+CREATE TABLE journal_drafts (
+    user_id INTEGER PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    title VARCHAR(500) NOT NULL DEFAULT '',
+    content TEXT NOT NULL DEFAULT '',
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
\ No newline at end of file
@@ -0,0 +1,4 @@
+DROP INDEX IF EXISTS idx_moods_user_pinned;
+DROP INDEX IF EXISTS idx_journals_user_pinned;
+ALTER TABLE moods DROP COLUMN IF EXISTS is_pinned;
+ALTER TABLE journals DROP COLUMN IF EXISTS is_pinned;
\ No newline at end of file
@@ -0,0 +1,6 @@
+-- Penanda jurnal dan hari mood penting yang disematkan pengguna
+ALTER TABLE journals ADD COLUMN is_pinned BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE moods ADD COLUMN is_pinned BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE INDEX idx_journals_user_pinned ON journals (user_id) WHERE is_pinned;
+CREATE INDEX idx_moods_user_pinned ON moods (user_id) WHERE is_pinned;
\ No newline at end of file
@@ -0,0 +1,2 @@
+ALTER TABLE users DROP COLUMN IF EXISTS guest_device_id;
+ALTER TABLE users DROP COLUMN IF EXISTS is_guest;
\ No newline at end of file
@@ -0,0 +1,3 @@
+-- Akun tamu tanpa email yang terikat ke satu perangkat; bisa di-upgrade menjadi akun penuh
+ALTER TABLE users ADD COLUMN is_guest BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE users ADD COLUMN guest_device_id VARCHAR(255) UNIQUE;
\ No newline at end of file
@@ -0,0 +1 @@
+DROP TABLE IF EXISTS journal_retention_states;
\ No newline at end of file
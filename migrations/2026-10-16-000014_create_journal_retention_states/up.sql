@@ -0,0 +1,6 @@
+-- Status retensi jurnal per pengguna; penghapusan pertama baru dilakukan setelah email pemberitahuan
+CREATE TABLE journal_retention_states (
+    user_id INTEGER PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    notice_sent_at TIMESTAMP NOT NULL,
+    last_purged_at TIMESTAMP
+);
\ No newline at end of file
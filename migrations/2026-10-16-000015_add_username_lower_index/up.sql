@@ -0,0 +1,10 @@
+-- Username yang hanya berbeda huruf besar/kecil diberi suffix id agar index bisa dibuat
+UPDATE users u
+SET username = u.username || '_' || u.id
+WHERE EXISTS (
+    SELECT 1 FROM users other
+    WHERE LOWER(other.username) = LOWER(u.username) AND other.id < u.id
+);
+
+-- Login dengan username tidak membedakan huruf besar/kecil
+CREATE UNIQUE INDEX users_username_lower_key ON users (LOWER(username));
\ No newline at end of file
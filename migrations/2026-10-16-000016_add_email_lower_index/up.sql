@@ -0,0 +1,12 @@
+-- Email yang hanya berbeda huruf besar/kecil atau spasi adalah akun ganda; akun yang lebih
+-- baru ditandai agar index bisa dibuat (masih bisa login dengan username)
+UPDATE users u
+SET email = u.email || '.duplicate-' || u.id
+WHERE EXISTS (
+    SELECT 1 FROM users other
+    WHERE LOWER(TRIM(other.email)) = LOWER(TRIM(u.email)) AND other.id < u.id
+);
+
+UPDATE users SET email = LOWER(TRIM(email)) WHERE email <> LOWER(TRIM(email));
+
+CREATE UNIQUE INDEX users_email_lower_key ON users (LOWER(email));
\ No newline at end of file
@@ -0,0 +1 @@
+DROP TABLE deleted_moods;
\ No newline at end of file
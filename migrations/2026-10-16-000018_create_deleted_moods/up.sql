@@ -0,0 +1,16 @@
+-- Tempat sampah mood: mood yang dihapus disimpan sementara agar bisa di-undo
+CREATE TABLE deleted_moods (
+    id INTEGER PRIMARY KEY,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    date DATE NOT NULL,
+    mood VARCHAR(50) NOT NULL,
+    emoji VARCHAR(10) NOT NULL,
+    notes TEXT,
+    created_at TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP,
+    details JSONB,
+    is_pinned BOOLEAN NOT NULL DEFAULT FALSE,
+    deleted_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_deleted_moods_deleted_at ON deleted_moods (deleted_at);
\ No newline at end of file
@@ -0,0 +1,8 @@
+DROP TABLE help_request_comments;
+
+DROP INDEX idx_help_requests_user_id;
+DROP INDEX idx_help_requests_status;
+
+ALTER TABLE help_requests
+    DROP COLUMN updated_at,
+    DROP COLUMN status;
\ No newline at end of file
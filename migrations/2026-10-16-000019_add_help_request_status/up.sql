@@ -0,0 +1,18 @@
+-- Status penanganan permintaan bantuan dan percakapan antara pengguna dan admin
+ALTER TABLE help_requests
+    ADD COLUMN status VARCHAR(20) NOT NULL DEFAULT 'open',
+    ADD COLUMN updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP;
+
+CREATE INDEX idx_help_requests_status ON help_requests (status, created_at);
+CREATE INDEX idx_help_requests_user_id ON help_requests (user_id);
+
+CREATE TABLE help_request_comments (
+    id SERIAL PRIMARY KEY,
+    help_request_id INTEGER NOT NULL REFERENCES help_requests(id) ON DELETE CASCADE,
+    author_id INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    is_staff BOOLEAN NOT NULL DEFAULT FALSE,
+    body TEXT NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_help_request_comments_request ON help_request_comments (help_request_id, created_at);
\ No newline at end of file
@@ -0,0 +1,9 @@
+DROP INDEX idx_psychologist_requests_user_id;
+DROP INDEX psychologist_requests_slot_id_key;
+
+ALTER TABLE psychologist_requests
+    DROP COLUMN slot_id,
+    DROP COLUMN psychologist_id;
+
+DROP TABLE psychologist_slots;
+DROP TABLE psychologists;
\ No newline at end of file
@@ -0,0 +1,30 @@
+-- Direktori psikolog beserta jadwal yang bisa dipesan lewat psychologist_requests
+CREATE TABLE psychologists (
+    id SERIAL PRIMARY KEY,
+    name VARCHAR(255) NOT NULL,
+    specialization VARCHAR(100) NOT NULL,
+    languages TEXT[] NOT NULL DEFAULT '{}',
+    bio TEXT,
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_psychologists_specialization ON psychologists (lower(specialization));
+
+CREATE TABLE psychologist_slots (
+    id SERIAL PRIMARY KEY,
+    psychologist_id INTEGER NOT NULL REFERENCES psychologists(id) ON DELETE CASCADE,
+    starts_at TIMESTAMP NOT NULL,
+    ends_at TIMESTAMP NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT psychologist_slots_psychologist_id_starts_at_key UNIQUE (psychologist_id, starts_at),
+    CHECK (ends_at > starts_at)
+);
+
+ALTER TABLE psychologist_requests
+    ADD COLUMN psychologist_id INTEGER REFERENCES psychologists(id) ON DELETE SET NULL,
+    ADD COLUMN slot_id INTEGER REFERENCES psychologist_slots(id) ON DELETE SET NULL;
+
+-- Satu jadwal hanya bisa dipesan satu kali
+CREATE UNIQUE INDEX psychologist_requests_slot_id_key ON psychologist_requests (slot_id) WHERE slot_id IS NOT NULL;
+CREATE INDEX idx_psychologist_requests_user_id ON psychologist_requests (user_id);
\ No newline at end of file
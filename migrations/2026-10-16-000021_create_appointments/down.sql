@@ -0,0 +1 @@
+DROP TABLE appointments;
\ No newline at end of file
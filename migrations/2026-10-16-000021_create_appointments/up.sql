@@ -0,0 +1,20 @@
+-- Janji temu yang sudah dikonfirmasi dari pengajuan psikolog; waktu dalam UTC
+CREATE TABLE appointments (
+    id SERIAL PRIMARY KEY,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    psychologist_id INTEGER NOT NULL REFERENCES psychologists(id) ON DELETE CASCADE,
+    request_id INTEGER REFERENCES psychologist_requests(id) ON DELETE SET NULL,
+    starts_at TIMESTAMP NOT NULL,
+    ends_at TIMESTAMP NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'scheduled',
+    meeting_link TEXT,
+    reminder_24h_sent_at TIMESTAMP,
+    reminder_1h_sent_at TIMESTAMP,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT appointments_request_id_key UNIQUE (request_id),
+    CHECK (ends_at > starts_at)
+);
+
+CREATE INDEX idx_appointments_user_id ON appointments (user_id, starts_at);
+CREATE INDEX idx_appointments_scheduled ON appointments (starts_at) WHERE status = 'scheduled';
\ No newline at end of file
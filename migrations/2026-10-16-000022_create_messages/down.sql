@@ -0,0 +1,2 @@
+DROP TABLE messages;
+ALTER TABLE psychologists DROP COLUMN user_id;
\ No newline at end of file
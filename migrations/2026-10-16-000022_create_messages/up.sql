@@ -0,0 +1,17 @@
+-- Akun pengguna milik psikolog, dipakai untuk membalas pesan klien
+ALTER TABLE psychologists ADD COLUMN user_id INTEGER REFERENCES users(id) ON DELETE SET NULL;
+ALTER TABLE psychologists ADD CONSTRAINT psychologists_user_id_key UNIQUE (user_id);
+
+-- Pesan antara pengguna dan psikolog yang menanganinya
+CREATE TABLE messages (
+    id SERIAL PRIMARY KEY,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    psychologist_id INTEGER NOT NULL REFERENCES psychologists(id) ON DELETE CASCADE,
+    from_psychologist BOOLEAN NOT NULL,
+    body TEXT NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    read_at TIMESTAMP
+);
+
+CREATE INDEX idx_messages_conversation ON messages (user_id, psychologist_id, id);
+CREATE INDEX idx_messages_unread ON messages (psychologist_id, user_id) WHERE read_at IS NULL;
\ No newline at end of file
@@ -0,0 +1,4 @@
+DROP TABLE moderation_verdicts;
+ALTER TABLE help_request_comments DROP COLUMN held;
+ALTER TABLE help_requests DROP COLUMN held;
+ALTER TABLE messages DROP COLUMN held;
\ No newline at end of file
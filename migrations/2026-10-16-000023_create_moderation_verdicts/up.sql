@@ -0,0 +1,22 @@
+-- Konten yang ditahan tidak ditampilkan ke penerima sampai disetujui admin
+ALTER TABLE messages ADD COLUMN held BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE help_requests ADD COLUMN held BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE help_request_comments ADD COLUMN held BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- Hasil moderasi (flag/hold) untuk ditinjau admin dan sebagai jejak audit
+CREATE TABLE moderation_verdicts (
+    id SERIAL PRIMARY KEY,
+    content_type VARCHAR(30) NOT NULL,
+    content_id INTEGER NOT NULL,
+    author_id INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    action VARCHAR(10) NOT NULL,
+    source VARCHAR(20) NOT NULL,
+    reasons TEXT[] NOT NULL DEFAULT '{}',
+    status VARCHAR(20) NOT NULL DEFAULT 'pending',
+    reviewed_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    reviewed_at TIMESTAMP,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT moderation_verdicts_content_key UNIQUE (content_type, content_id)
+);
+
+CREATE INDEX idx_moderation_verdicts_status ON moderation_verdicts (status, created_at);
\ No newline at end of file
@@ -0,0 +1,4 @@
+DROP TABLE community_reactions;
+DROP TABLE community_posts;
+DROP TABLE community_memberships;
+DROP TABLE community_groups;
\ No newline at end of file
@@ -0,0 +1,40 @@
+-- Grup dukungan komunitas per topik; pengguna bergabung secara sukarela
+CREATE TABLE community_groups (
+    id SERIAL PRIMARY KEY,
+    slug VARCHAR(50) NOT NULL,
+    name VARCHAR(100) NOT NULL,
+    description TEXT,
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT community_groups_slug_key UNIQUE (slug)
+);
+
+CREATE TABLE community_memberships (
+    group_id INTEGER NOT NULL REFERENCES community_groups(id) ON DELETE CASCADE,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (group_id, user_id)
+);
+
+-- Penulis disimpan untuk batas kiriman dan moderasi, tetapi tidak pernah ditampilkan
+CREATE TABLE community_posts (
+    id SERIAL PRIMARY KEY,
+    group_id INTEGER NOT NULL REFERENCES community_groups(id) ON DELETE CASCADE,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    body TEXT NOT NULL,
+    held BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_community_posts_group ON community_posts (group_id, id);
+CREATE INDEX idx_community_posts_user ON community_posts (user_id, created_at);
+
+CREATE TABLE community_reactions (
+    post_id INTEGER NOT NULL REFERENCES community_posts(id) ON DELETE CASCADE,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    kind VARCHAR(20) NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (post_id, user_id, kind)
+);
+
+CREATE INDEX idx_community_reactions_user ON community_reactions (user_id, created_at);
\ No newline at end of file
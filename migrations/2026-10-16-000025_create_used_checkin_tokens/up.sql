@@ -0,0 +1,6 @@
+-- Token check-in mood dari email yang sudah dipakai, agar setiap link hanya berlaku sekali
+CREATE TABLE used_checkin_tokens (
+    jti VARCHAR(64) PRIMARY KEY,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    used_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
\ No newline at end of file
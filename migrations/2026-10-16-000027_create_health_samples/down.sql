@@ -0,0 +1 @@
+DROP TABLE health_samples;
\ No newline at end of file
@@ -0,0 +1,14 @@
+-- Data langkah dan tidur dari Google Fit / Apple Health yang dikirim aplikasi mobile
+CREATE TABLE health_samples (
+    id SERIAL PRIMARY KEY,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    kind VARCHAR(16) NOT NULL,
+    source VARCHAR(32) NOT NULL,
+    start_at TIMESTAMP NOT NULL,
+    end_at TIMESTAMP NOT NULL,
+    value DOUBLE PRECISION,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT health_samples_sample_key UNIQUE (user_id, kind, source, start_at, end_at)
+);
+
+CREATE INDEX idx_health_samples_user_end_at ON health_samples (user_id, end_at);
\ No newline at end of file
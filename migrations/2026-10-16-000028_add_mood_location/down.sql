@@ -0,0 +1,9 @@
+ALTER TABLE deleted_moods
+    DROP COLUMN place_label,
+    DROP COLUMN latitude,
+    DROP COLUMN longitude;
+
+ALTER TABLE moods
+    DROP COLUMN place_label,
+    DROP COLUMN latitude,
+    DROP COLUMN longitude;
\ No newline at end of file
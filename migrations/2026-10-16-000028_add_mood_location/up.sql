@@ -0,0 +1,11 @@
+-- Lokasi kasar opsional pada mood: label tempat pilihan pengguna dan/atau koordinat
+-- yang sudah dibulatkan; deleted_moods ikut agar lokasi tidak hilang saat undo
+ALTER TABLE moods
+    ADD COLUMN place_label VARCHAR(100),
+    ADD COLUMN latitude DOUBLE PRECISION,
+    ADD COLUMN longitude DOUBLE PRECISION;
+
+ALTER TABLE deleted_moods
+    ADD COLUMN place_label VARCHAR(100),
+    ADD COLUMN latitude DOUBLE PRECISION,
+    ADD COLUMN longitude DOUBLE PRECISION;
\ No newline at end of file
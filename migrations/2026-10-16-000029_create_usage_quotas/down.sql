@@ -0,0 +1 @@
+DROP TABLE usage_quotas;
\ No newline at end of file
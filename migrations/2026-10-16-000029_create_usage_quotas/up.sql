@@ -0,0 +1,9 @@
+-- Pemakaian harian (UTC) operasi berat per pengguna: export, PDF laporan, import
+CREATE TABLE usage_quotas (
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    operation VARCHAR(32) NOT NULL,
+    period_start DATE NOT NULL,
+    used INTEGER NOT NULL DEFAULT 0,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (user_id, operation, period_start)
+);
\ No newline at end of file
@@ -0,0 +1,18 @@
+-- Antrian operasi panjang (import, export, PDF, hapus akun) yang dikerjakan worker di latar belakang.
+-- File input dan hasil disimpan di storage; baris job tetap ada setelah akun dihapus sampai dibersihkan.
+CREATE TABLE jobs (
+    id SERIAL PRIMARY KEY,
+    user_id INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    kind VARCHAR(32) NOT NULL,
+    status VARCHAR(16) NOT NULL DEFAULT 'queued' CHECK (status IN ('queued', 'running', 'succeeded', 'failed')),
+    params TEXT NOT NULL DEFAULT '{}',
+    result_content_type VARCHAR(100),
+    result_filename VARCHAR(255),
+    error TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    started_at TIMESTAMP,
+    finished_at TIMESTAMP
+);
+
+CREATE INDEX idx_jobs_queued ON jobs (id) WHERE status = 'queued';
+CREATE INDEX idx_jobs_user_id ON jobs (user_id);
\ No newline at end of file
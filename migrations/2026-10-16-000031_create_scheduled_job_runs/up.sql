@@ -0,0 +1,7 @@
+-- Jadwal terakhir yang sudah diklaim untuk setiap job terjadwal. Bersama advisory lock,
+-- tabel ini memastikan satu jadwal hanya dijalankan satu replika walaupun jam antar replika sedikit berbeda.
+CREATE TABLE scheduled_job_runs (
+    job_name VARCHAR(64) PRIMARY KEY,
+    last_scheduled_at TIMESTAMP NOT NULL,
+    claimed_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
\ No newline at end of file
@@ -0,0 +1 @@
+DROP TABLE auth_exchange_codes;
\ No newline at end of file
@@ -0,0 +1,11 @@
+-- Kode sekali pakai berumur pendek dari callback Google OAuth. Frontend menukarnya dengan JWT
+-- lewat POST /auth/exchange, jadi token login tidak pernah muncul di URL.
+CREATE TABLE auth_exchange_codes (
+    id SERIAL PRIMARY KEY,
+    code VARCHAR(64) NOT NULL UNIQUE,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    expires_at TIMESTAMP NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_auth_exchange_codes_expires_at ON auth_exchange_codes (expires_at);
\ No newline at end of file
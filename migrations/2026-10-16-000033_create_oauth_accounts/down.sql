@@ -0,0 +1 @@
+DROP TABLE oauth_accounts;
\ No newline at end of file
@@ -0,0 +1,16 @@
+-- Akun penyedia OAuth (Google) yang terhubung ke pengguna. Token disimpan terenkripsi (AES-256-GCM)
+-- agar integrasi berikutnya (Calendar, Fit) bisa memakai refresh token tanpa meminta izin ulang.
+CREATE TABLE oauth_accounts (
+    id SERIAL PRIMARY KEY,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    provider VARCHAR(32) NOT NULL,
+    provider_user_id VARCHAR(255) NOT NULL,
+    refresh_token_encrypted TEXT,
+    access_token_encrypted TEXT,
+    access_token_expires_at TIMESTAMP,
+    scope TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (provider, provider_user_id),
+    UNIQUE (user_id, provider)
+);
\ No newline at end of file
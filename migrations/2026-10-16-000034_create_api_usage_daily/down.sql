@@ -0,0 +1 @@
+DROP TABLE api_usage_daily;
\ No newline at end of file
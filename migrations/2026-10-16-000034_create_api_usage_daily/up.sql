@@ -0,0 +1,8 @@
+-- Jumlah request API per pengguna per hari (UTC), diakumulasi di memori lalu disimpan berkala
+CREATE TABLE api_usage_daily (
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    day DATE NOT NULL,
+    request_count BIGINT NOT NULL DEFAULT 0,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (user_id, day)
+);
\ No newline at end of file
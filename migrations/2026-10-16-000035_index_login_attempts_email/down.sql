@@ -0,0 +1 @@
+DROP INDEX idx_login_attempts_email_created_at;
\ No newline at end of file
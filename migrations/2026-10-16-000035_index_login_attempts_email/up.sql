@@ -0,0 +1,2 @@
+-- Lockout untuk identifier yang tidak terdaftar dihitung per email/username
+CREATE INDEX idx_login_attempts_email_created_at ON login_attempts (email, created_at) WHERE user_id IS NULL;
\ No newline at end of file
@@ -0,0 +1,95 @@
+use chrono::{Datelike, Duration, NaiveDate};
+
+/// Jumlah titik data minimum agar regresi bisa dipakai
+pub const MIN_POINTS: usize = 5;
+/// Minimal residual per hari agar penyesuaian musiman hari itu dipakai
+const MIN_SEASONAL_POINTS: usize = 2;
+/// z-score untuk interval prediksi 95%
+const Z_95: f64 = 1.96;
+const MIN_SCORE: f64 = 1.0;
+const MAX_SCORE: f64 = 5.0;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Prediction {
+    pub date: NaiveDate,
+    pub value: f64,
+    pub lower: f64,
+    pub upper: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct Forecast {
+    pub slope_per_day: f64,
+    /// Apakah penyesuaian per hari dalam minggu dipakai
+    pub seasonal: bool,
+    pub predictions: Vec<Prediction>,
+}
+
+/// Regresi linear skor terhadap tanggal, opsional ditambah rata-rata residual per
+/// hari dalam minggu, untuk `horizon` hari setelah `last_date`. Interval prediksi
+/// memakai galat standar residual; semua nilai dibatasi ke skala skor 1-5.
+pub fn forecast(history: &[(NaiveDate, f64)], last_date: NaiveDate, horizon: usize, seasonal: bool) -> Option<Forecast> {
+    if history.len() < MIN_POINTS {
+        return None;
+    }
+
+    let origin = history.iter().map(|(date, _)| *date).min()?;
+    let x = |date: NaiveDate| (date - origin).num_days() as f64;
+
+    let n = history.len() as f64;
+    let mean_x = history.iter().map(|(date, _)| x(*date)).sum::<f64>() / n;
+    let mean_y = history.iter().map(|(_, score)| score).sum::<f64>() / n;
+    let sxx: f64 = history.iter().map(|(date, _)| (x(*date) - mean_x).powi(2)).sum();
+    let sxy: f64 = history
+        .iter()
+        .map(|(date, score)| (x(*date) - mean_x) * (score - mean_y))
+        .sum();
+
+    // Semua data di hari yang sama: tidak ada tren, pakai rata-rata saja
+    let slope = if sxx > 0.0 { sxy / sxx } else { 0.0 };
+    let intercept = mean_y - slope * mean_x;
+    let trend = |date: NaiveDate| intercept + slope * x(date);
+
+    let mut weekday_offsets = [0.0; 7];
+    let mut use_seasonal = false;
+    if seasonal {
+        let mut sums = [(0.0, 0usize); 7];
+        for (date, score) in history {
+            let slot = &mut sums[date.weekday().num_days_from_monday() as usize];
+            slot.0 += score - trend(*date);
+            slot.1 += 1;
+        }
+        for (offset, (sum, count)) in weekday_offsets.iter_mut().zip(sums) {
+            if count >= MIN_SEASONAL_POINTS {
+                *offset = sum / count as f64;
+                use_seasonal = true;
+            }
+        }
+    }
+    let fitted = |date: NaiveDate| trend(date) + weekday_offsets[date.weekday().num_days_from_monday() as usize];
+
+    // Derajat bebas: 2 parameter regresi, minimal 1 agar tidak membagi nol
+    let residual_ss: f64 = history.iter().map(|(date, score)| (score - fitted(*date)).powi(2)).sum();
+    let std_error = (residual_ss / (n - 2.0).max(1.0)).sqrt();
+
+    let predictions = (1..=horizon as i64)
+        .map(|offset| {
+            let date = last_date + Duration::days(offset);
+            let leverage = if sxx > 0.0 { (x(date) - mean_x).powi(2) / sxx } else { 0.0 };
+            let margin = Z_95 * std_error * (1.0 + 1.0 / n + leverage).sqrt();
+            let value = fitted(date);
+            Prediction {
+                date,
+                value: value.clamp(MIN_SCORE, MAX_SCORE),
+                lower: (value - margin).clamp(MIN_SCORE, MAX_SCORE),
+                upper: (value + margin).clamp(MIN_SCORE, MAX_SCORE),
+            }
+        })
+        .collect();
+
+    Some(Forecast {
+        slope_per_day: slope,
+        seasonal: use_seasonal,
+        predictions,
+    })
+}
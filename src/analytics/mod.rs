@@ -0,0 +1,2 @@
+pub mod forecast;
+pub mod trend;
\ No newline at end of file
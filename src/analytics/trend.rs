@@ -0,0 +1,211 @@
+use chrono::{Datelike, Duration, NaiveDate};
+
+/// Posisi jendela rolling average terhadap titik yang dihaluskan
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Alignment {
+    /// Jendela berakhir di titik itu, hanya memakai data sebelumnya
+    Right,
+    /// Jendela berpusat di titik itu
+    Center,
+}
+
+impl Alignment {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "right" => Some(Alignment::Right),
+            "center" | "centre" => Some(Alignment::Center),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Alignment::Right => "right",
+            Alignment::Center => "center",
+        }
+    }
+
+    /// Jumlah hari sebelum dan sesudah titik yang masuk jendela `window` hari
+    pub fn span(self, window: u32) -> (i64, i64) {
+        let window = i64::from(window.max(1));
+        match self {
+            Alignment::Right => (window - 1, 0),
+            Alignment::Center => (window / 2, window - 1 - window / 2),
+        }
+    }
+}
+
+/// Satuan periode untuk mengelompokkan titik tren
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Grouping {
+    Day,
+    /// Minggu ISO, Senin sampai Minggu
+    Week,
+    Month,
+    Quarter,
+}
+
+impl Grouping {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "day" => Some(Grouping::Day),
+            "week" => Some(Grouping::Week),
+            "month" => Some(Grouping::Month),
+            "quarter" => Some(Grouping::Quarter),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Grouping::Day => "day",
+            Grouping::Week => "week",
+            Grouping::Month => "month",
+            Grouping::Quarter => "quarter",
+        }
+    }
+
+    /// Tanggal awal dan akhir (inklusif) periode yang memuat `date`
+    pub fn period(self, date: NaiveDate) -> (NaiveDate, NaiveDate) {
+        match self {
+            Grouping::Day => (date, date),
+            Grouping::Week => {
+                let start = date - Duration::days(i64::from(date.weekday().num_days_from_monday()));
+                (start, start + Duration::days(6))
+            }
+            Grouping::Month => month_span(date.year(), date.month(), 1),
+            Grouping::Quarter => month_span(date.year(), (date.month() - 1) / 3 * 3 + 1, 3),
+        }
+    }
+}
+
+/// `months` bulan mulai dari tanggal 1 bulan `month`
+fn month_span(year: i32, month: u32, months: u32) -> (NaiveDate, NaiveDate) {
+    let start = NaiveDate::from_ymd_opt(year, month, 1).expect("valid month");
+    let next_month = month + months;
+    let end = NaiveDate::from_ymd_opt(year + (next_month as i32 - 1) / 12, (next_month - 1) % 12 + 1, 1)
+        .and_then(|next| next.pred_opt())
+        .expect("valid month");
+    (start, end)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PeriodAverage {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    pub average: f64,
+    pub entries: usize,
+}
+
+/// Rata-rata skor per periode; hanya periode yang punya catatan yang dikembalikan.
+/// `points` harus terurut berdasarkan tanggal.
+pub fn group_averages(points: &[(NaiveDate, f64)], grouping: Grouping) -> Vec<PeriodAverage> {
+    let mut periods: Vec<PeriodAverage> = Vec::new();
+    for (date, score) in points {
+        let (start, end) = grouping.period(*date);
+        match periods.last_mut() {
+            Some(period) if period.start == start => {
+                period.average += score;
+                period.entries += 1;
+            }
+            _ => periods.push(PeriodAverage { start, end, average: *score, entries: 1 }),
+        }
+    }
+    for period in &mut periods {
+        period.average /= period.entries as f64;
+    }
+    periods
+}
+
+/// Rolling average per hari kalender: setiap titik diganti rata-rata semua titik yang
+/// tanggalnya berada dalam jendela `window` hari. Hari tanpa catatan tidak dihitung
+/// sebagai nol. `points` harus terurut berdasarkan tanggal.
+pub fn rolling_average(points: &[(NaiveDate, f64)], window: u32, alignment: Alignment) -> Vec<(NaiveDate, f64)> {
+    let (before, after) = alignment.span(window);
+    let mut prefix = Vec::with_capacity(points.len() + 1);
+    prefix.push(0.0);
+    for (_, score) in points {
+        prefix.push(prefix[prefix.len() - 1] + score);
+    }
+
+    points
+        .iter()
+        .map(|(date, _)| {
+            let first = points.partition_point(|(other, _)| *other < *date - Duration::days(before));
+            let end = points.partition_point(|(other, _)| *other <= *date + Duration::days(after));
+            (*date, (prefix[end] - prefix[first]) / (end - first) as f64)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 7, day).unwrap()
+    }
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn weeks_start_on_iso_monday_across_year_boundaries() {
+        // 1 Januari 2025 jatuh pada hari Rabu, minggu ISO-nya dimulai 30 Desember 2024
+        assert_eq!(Grouping::Week.period(date(2025, 1, 1)), (date(2024, 12, 30), date(2025, 1, 5)));
+        // 3 Januari 2021 (Minggu) masih termasuk minggu ISO 53 tahun 2020
+        assert_eq!(Grouping::Week.period(date(2021, 1, 3)), (date(2020, 12, 28), date(2021, 1, 3)));
+        assert_eq!(Grouping::Week.period(date(2021, 1, 4)), (date(2021, 1, 4), date(2021, 1, 10)));
+        assert_eq!(Grouping::Week.period(date(2024, 12, 31)), (date(2024, 12, 30), date(2025, 1, 5)));
+    }
+
+    #[test]
+    fn months_and_quarters_cover_whole_calendar_periods() {
+        assert_eq!(Grouping::Month.period(date(2024, 2, 10)), (date(2024, 2, 1), date(2024, 2, 29)));
+        assert_eq!(Grouping::Month.period(date(2025, 12, 31)), (date(2025, 12, 1), date(2025, 12, 31)));
+        assert_eq!(Grouping::Quarter.period(date(2025, 5, 20)), (date(2025, 4, 1), date(2025, 6, 30)));
+        assert_eq!(Grouping::Quarter.period(date(2025, 11, 2)), (date(2025, 10, 1), date(2025, 12, 31)));
+        assert_eq!(Grouping::Quarter.period(date(2026, 1, 1)), (date(2026, 1, 1), date(2026, 3, 31)));
+    }
+
+    #[test]
+    fn groups_a_week_that_spans_two_years() {
+        let points = vec![
+            (date(2024, 12, 29), 1.0),
+            (date(2024, 12, 30), 2.0),
+            (date(2025, 1, 2), 4.0),
+            (date(2025, 1, 6), 5.0),
+        ];
+        let weeks = group_averages(&points, Grouping::Week);
+        assert_eq!(
+            weeks,
+            vec![
+                PeriodAverage { start: date(2024, 12, 23), end: date(2024, 12, 29), average: 1.0, entries: 1 },
+                PeriodAverage { start: date(2024, 12, 30), end: date(2025, 1, 5), average: 3.0, entries: 2 },
+                PeriodAverage { start: date(2025, 1, 6), end: date(2025, 1, 12), average: 5.0, entries: 1 },
+            ]
+        );
+    }
+
+    #[test]
+    fn right_aligned_uses_only_earlier_days() {
+        let points = vec![(day(1), 1.0), (day(2), 3.0), (day(3), 5.0)];
+        let smoothed = rolling_average(&points, 2, Alignment::Right);
+        assert_eq!(smoothed, vec![(day(1), 1.0), (day(2), 2.0), (day(3), 4.0)]);
+    }
+
+    #[test]
+    fn centered_window_looks_both_ways() {
+        let points = vec![(day(1), 1.0), (day(2), 3.0), (day(3), 5.0)];
+        let smoothed = rolling_average(&points, 3, Alignment::Center);
+        assert_eq!(smoothed, vec![(day(1), 2.0), (day(2), 3.0), (day(3), 4.0)]);
+    }
+
+    #[test]
+    fn missing_days_are_not_counted_as_zero() {
+        let points = vec![(day(1), 4.0), (day(7), 2.0), (day(20), 5.0)];
+        let smoothed = rolling_average(&points, 7, Alignment::Right);
+        assert_eq!(smoothed, vec![(day(1), 4.0), (day(7), 3.0), (day(20), 5.0)]);
+    }
+}
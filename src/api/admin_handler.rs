@@ -0,0 +1,615 @@
+use axum::{
+    extract::{State, Path, Query},
+    response::IntoResponse,
+};
+use crate::middleware::json_body::Json;
+
+use crate::{
+    errors::app_error::AppError,
+    middleware::admin_middleware::AdminUser,
+    middleware::client_info::ClientInfo,
+    models::admin_analytics::AnalyticsQuery,
+    models::appointment::ConfirmAppointmentRequest,
+    models::audit::AuditLogQuery,
+    models::auth::{ImpersonateRequest, TokenCleanupResponse},
+    models::backup::{CreateBackupRequest, RestoreBackupRequest},
+    models::community::CreateCommunityGroupRequest,
+    models::help::{CreateHelpCommentRequest, HelpRequestListQuery, UpdateHelpRequestStatus},
+    models::moderation::ModerationListQuery,
+    models::psychologist::{CreatePsychologistRequest, CreateSlotsRequest},
+    models::status::UpdateMaintenanceRequest,
+    service::admin_analytics_service::{get_retention_cohorts, get_weekly_activity},
+    service::appointment_service::confirm_appointment,
+    service::audit_service::get_audit_logs,
+    service::auth_service::{cleanup_expired_tokens_by_admin, impersonate_user},
+    service::backup_service::{create_backup_by_admin, restore_backup},
+    service::community_service::create_group,
+    service::help_service::{add_staff_comment, get_help_request_for_admin, list_help_requests, update_help_request_status},
+    service::moderation_service::{list_verdicts, review_verdict},
+    service::psychologist_service::{create_psychologist, create_slots},
+    service::status_service::set_maintenance_by_admin,
+    state::AppState,
+};
+
+/// Handler untuk menjalankan cleanup token blacklist secara manual
+#[utoipa::path(
+    post,
+    path = "/admin/cleanup-tokens",
+    tag = "admin",
+    responses(
+        (status = 200, description = "OK", body = TokenCleanupResponse),
+        (status = 403, description = "Admin access required", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn cleanup_tokens_handler(
+    State(state): State<AppState>,
+    admin: AdminUser,
+    client: ClientInfo,
+) -> Result<impl IntoResponse, AppError> {
+    let admin_id: i32 = admin
+        .0
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let deleted = cleanup_expired_tokens_by_admin(&state.pool, admin_id, client.ip_address.as_deref())?;
+    Ok(Json(TokenCleanupResponse { deleted }))
+}
+
+/// Handler untuk melihat hit/miss cache statistik
+#[utoipa::path(
+    get,
+    path = "/admin/cache-stats",
+    tag = "admin",
+    responses(
+        (status = 200, description = "OK", body = CacheMetrics),
+        (status = 403, description = "Admin access required", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn cache_stats_handler(
+    State(state): State<AppState>,
+    _admin: AdminUser,
+) -> Result<impl IntoResponse, AppError> {
+    Ok(Json(state.stats_cache.metrics()))
+}
+
+/// Handler untuk melihat audit log semua pengguna
+#[utoipa::path(
+    get,
+    path = "/admin/audit-logs",
+    tag = "admin",
+    params(AuditLogQuery),
+    responses(
+        (status = 200, description = "OK", body = Vec<AuditLogResponse>),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 403, description = "Admin access required", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn audit_logs_handler(
+    State(state): State<AppState>,
+    _admin: AdminUser,
+    Query(query): Query<AuditLogQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let logs = get_audit_logs(&state.pool, query.user_id, query.limit)?;
+    Ok(Json(logs))
+}
+
+/// Handler untuk backup tabel database ke storage
+#[utoipa::path(
+    post,
+    path = "/admin/backup",
+    tag = "admin",
+    request_body(content = CreateBackupRequest, description = "Optional; all tables when omitted"),
+    responses(
+        (status = 200, description = "Backup created", body = BackupResponse),
+        (status = 400, description = "Invalid table selection", body = ErrorResponse),
+        (status = 403, description = "Admin access required", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn create_backup_handler(
+    State(state): State<AppState>,
+    admin: AdminUser,
+    client: ClientInfo,
+    data: Option<Json<CreateBackupRequest>>,
+) -> Result<impl IntoResponse, AppError> {
+    let admin_id: i32 = admin
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let backup = create_backup_by_admin(
+        &state.pool,
+        state.storage.as_ref(),
+        admin_id,
+        data.map(|Json(data)| data).unwrap_or_default(),
+        client.ip_address.as_deref(),
+    )?;
+    Ok(Json(backup))
+}
+
+/// Handler untuk memulihkan tabel dari file backup; `confirm` harus sama dengan nama backup
+#[utoipa::path(
+    post,
+    path = "/admin/restore",
+    tag = "admin",
+    request_body = RestoreBackupRequest,
+    responses(
+        (status = 200, description = "Backup restored", body = RestoreBackupResponse),
+        (status = 400, description = "Missing confirmation or invalid backup", body = ErrorResponse),
+        (status = 403, description = "Admin access required", body = ErrorResponse),
+        (status = 404, description = "Backup not found", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn restore_backup_handler(
+    State(state): State<AppState>,
+    admin: AdminUser,
+    client: ClientInfo,
+    Json(data): Json<RestoreBackupRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let admin_id: i32 = admin
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let restored = restore_backup(
+        &state.pool,
+        state.storage.as_ref(),
+        &state.stats_cache,
+        admin_id,
+        data,
+        client.ip_address.as_deref(),
+    )?;
+    Ok(Json(restored))
+}
+
+/// Handler untuk menerbitkan token impersonasi hanya-baca bagi admin support
+#[utoipa::path(
+    post,
+    path = "/admin/impersonate/{user_id}",
+    tag = "admin",
+    params(("user_id" = i32, Path, description = "ID pengguna yang diimpersonasi")),
+    request_body = ImpersonateRequest,
+    responses(
+        (status = 200, description = "Impersonation token issued", body = ImpersonationResponse),
+        (status = 400, description = "Missing reason", body = ErrorResponse),
+        (status = 403, description = "Admin access required or target is an admin", body = ErrorResponse),
+        (status = 404, description = "User not found", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn impersonate_user_handler(
+    State(state): State<AppState>,
+    admin: AdminUser,
+    client: ClientInfo,
+    Path(user_id): Path<i32>,
+    Json(data): Json<ImpersonateRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let admin_id: i32 = admin
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let impersonation = impersonate_user(
+        &state.pool,
+        admin_id,
+        user_id,
+        &data.reason,
+        client.ip_address.as_deref(),
+    )?;
+    Ok(Json(impersonation))
+}
+
+/// Handler untuk pengguna aktif mingguan dan rata-rata entri per pengguna aktif
+#[utoipa::path(
+    get,
+    path = "/admin/analytics/active-users",
+    tag = "admin",
+    params(AnalyticsQuery),
+    responses(
+        (status = 200, description = "OK", body = Vec<WeeklyActivity>),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 403, description = "Admin access required", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn weekly_activity_handler(
+    State(state): State<AppState>,
+    _admin: AdminUser,
+    Query(query): Query<AnalyticsQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let activity = get_weekly_activity(&state.pool, query.weeks)?;
+    Ok(Json(activity))
+}
+
+/// Handler untuk retensi D1/D7/D30 per kohort minggu pendaftaran
+#[utoipa::path(
+    get,
+    path = "/admin/analytics/retention",
+    tag = "admin",
+    params(AnalyticsQuery),
+    responses(
+        (status = 200, description = "OK", body = Vec<RetentionCohort>),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 403, description = "Admin access required", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn retention_cohorts_handler(
+    State(state): State<AppState>,
+    _admin: AdminUser,
+    Query(query): Query<AnalyticsQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let cohorts = get_retention_cohorts(&state.pool, query.weeks)?;
+    Ok(Json(cohorts))
+}
+
+/// Handler untuk antrian permintaan bantuan, yang paling lama menunggu lebih dulu
+#[utoipa::path(
+    get,
+    path = "/admin/help-requests",
+    tag = "admin",
+    params(HelpRequestListQuery),
+    responses(
+        (status = 200, description = "OK", body = Vec<HelpRequestResponse>),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 403, description = "Admin access required", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_help_requests_handler(
+    State(state): State<AppState>,
+    _admin: AdminUser,
+    Query(query): Query<HelpRequestListQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let requests = list_help_requests(&state.pool, query.status.as_deref(), query.limit)?;
+    Ok(Json(requests))
+}
+
+/// Handler untuk detail permintaan bantuan beserta percakapannya
+#[utoipa::path(
+    get,
+    path = "/admin/help-requests/{id}",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Help request id")),
+    responses(
+        (status = 200, description = "OK", body = HelpRequestDetail),
+        (status = 403, description = "Admin access required", body = ErrorResponse),
+        (status = 404, description = "Help request not found", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_help_request_handler(
+    State(state): State<AppState>,
+    _admin: AdminUser,
+    Path(request_id): Path<i32>,
+) -> Result<impl IntoResponse, AppError> {
+    let request = get_help_request_for_admin(&state.pool, request_id)?;
+    Ok(Json(request))
+}
+
+/// Handler untuk memajukan status permintaan bantuan; pengguna diberi tahu lewat push
+#[utoipa::path(
+    put,
+    path = "/admin/help-requests/{id}/status",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Help request id")),
+    request_body = UpdateHelpRequestStatus,
+    responses(
+        (status = 200, description = "Status updated", body = HelpRequestDetail),
+        (status = 400, description = "Unknown status", body = ErrorResponse),
+        (status = 403, description = "Admin access required", body = ErrorResponse),
+        (status = 404, description = "Help request not found", body = ErrorResponse),
+        (status = 409, description = "Transition not allowed from the current status", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update_help_request_status_handler(
+    State(state): State<AppState>,
+    admin: AdminUser,
+    client: ClientInfo,
+    Path(request_id): Path<i32>,
+    Json(data): Json<UpdateHelpRequestStatus>,
+) -> Result<impl IntoResponse, AppError> {
+    let admin_id: i32 = admin
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let request = update_help_request_status(
+        &state.pool,
+        state.mailer.as_ref(),
+        admin_id,
+        request_id,
+        data,
+        client.ip_address.as_deref(),
+    )?;
+    Ok(Json(request))
+}
+
+/// Handler untuk balasan admin di percakapan permintaan bantuan
+#[utoipa::path(
+    post,
+    path = "/admin/help-requests/{id}/comments",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Help request id")),
+    request_body = CreateHelpCommentRequest,
+    responses(
+        (status = 200, description = "Comment added", body = HelpCommentResponse),
+        (status = 403, description = "Admin access required", body = ErrorResponse),
+        (status = 404, description = "Help request not found", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn add_help_comment_handler(
+    State(state): State<AppState>,
+    admin: AdminUser,
+    Path(request_id): Path<i32>,
+    Json(data): Json<CreateHelpCommentRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let admin_id: i32 = admin
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let comment = add_staff_comment(&state.pool, admin_id, request_id, data)?;
+    Ok(Json(comment))
+}
+
+/// Handler untuk menambah psikolog ke direktori
+#[utoipa::path(
+    post,
+    path = "/admin/psychologists",
+    tag = "admin",
+    request_body = CreatePsychologistRequest,
+    responses(
+        (status = 200, description = "Psychologist created", body = PsychologistResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 403, description = "Admin access required", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn create_psychologist_handler(
+    State(state): State<AppState>,
+    _admin: AdminUser,
+    Json(data): Json<CreatePsychologistRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let psychologist = create_psychologist(&state.pool, data)?;
+    Ok(Json(psychologist))
+}
+
+/// Handler untuk menambah jadwal praktik psikolog (UTC)
+#[utoipa::path(
+    post,
+    path = "/admin/psychologists/{id}/slots",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Psychologist id")),
+    request_body = CreateSlotsRequest,
+    responses(
+        (status = 200, description = "Slots created", body = Vec<SlotResponse>),
+        (status = 400, description = "Invalid or duplicate slot", body = ErrorResponse),
+        (status = 403, description = "Admin access required", body = ErrorResponse),
+        (status = 404, description = "Psychologist not found", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn create_psychologist_slots_handler(
+    State(state): State<AppState>,
+    _admin: AdminUser,
+    Path(psychologist_id): Path<i32>,
+    Json(data): Json<CreateSlotsRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let slots = create_slots(&state.pool, psychologist_id, data)?;
+    Ok(Json(slots))
+}
+
+/// Handler untuk mengonfirmasi pengajuan psikolog menjadi janji temu
+#[utoipa::path(
+    post,
+    path = "/admin/psychologist-requests/{id}/confirm",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Psychologist request id")),
+    request_body = ConfirmAppointmentRequest,
+    responses(
+        (status = 200, description = "Appointment created", body = AppointmentResponse),
+        (status = 400, description = "Invalid time or meeting link", body = ErrorResponse),
+        (status = 403, description = "Admin access required", body = ErrorResponse),
+        (status = 404, description = "Psychologist request not found", body = ErrorResponse),
+        (status = 409, description = "Request already has an appointment", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn confirm_appointment_handler(
+    State(state): State<AppState>,
+    admin: AdminUser,
+    client: ClientInfo,
+    Path(request_id): Path<i32>,
+    Json(data): Json<ConfirmAppointmentRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let admin_id: i32 = admin
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let appointment = confirm_appointment(&state.pool, admin_id, request_id, data, client.ip_address.as_deref())?;
+    Ok(Json(appointment))
+}
+
+/// Handler untuk membuat grup komunitas baru
+#[utoipa::path(
+    post,
+    path = "/admin/community/groups",
+    tag = "admin",
+    request_body = CreateCommunityGroupRequest,
+    responses(
+        (status = 200, description = "Group created", body = CommunityGroupResponse),
+        (status = 400, description = "Invalid slug or name", body = ErrorResponse),
+        (status = 403, description = "Admin access required", body = ErrorResponse),
+        (status = 409, description = "Slug already exists", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn create_community_group_handler(
+    State(state): State<AppState>,
+    admin: AdminUser,
+    client: ClientInfo,
+    Json(data): Json<CreateCommunityGroupRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let admin_id: i32 = admin
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let group = create_group(&state.pool, admin_id, data, client.ip_address.as_deref())?;
+    Ok(Json(group))
+}
+
+/// Handler untuk antrian tinjauan moderasi pesan dan permintaan bantuan
+#[utoipa::path(
+    get,
+    path = "/admin/moderation",
+    tag = "admin",
+    params(ModerationListQuery),
+    responses(
+        (status = 200, description = "OK", body = Vec<ModerationVerdictResponse>),
+        (status = 400, description = "Unknown status or invalid limit", body = ErrorResponse),
+        (status = 403, description = "Admin access required", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_moderation_handler(
+    State(state): State<AppState>,
+    _admin: AdminUser,
+    Query(query): Query<ModerationListQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let verdicts = list_verdicts(&state.pool, query.status.as_deref(), query.limit)?;
+    Ok(Json(verdicts))
+}
+
+/// Handler untuk menyetujui konten; konten yang ditahan ditampilkan ke penerima
+#[utoipa::path(
+    post,
+    path = "/admin/moderation/{id}/approve",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Moderation verdict id")),
+    responses(
+        (status = 200, description = "Content approved", body = ModerationVerdictResponse),
+        (status = 403, description = "Admin access required", body = ErrorResponse),
+        (status = 404, description = "Moderation verdict not found", body = ErrorResponse),
+        (status = 409, description = "Already reviewed", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn approve_moderation_handler(
+    State(state): State<AppState>,
+    admin: AdminUser,
+    client: ClientInfo,
+    Path(verdict_id): Path<i32>,
+) -> Result<impl IntoResponse, AppError> {
+    let admin_id: i32 = admin
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let verdict = review_verdict(
+        &state.pool,
+        &state.event_bus,
+        admin_id,
+        verdict_id,
+        true,
+        client.ip_address.as_deref(),
+    )?;
+    Ok(Json(verdict))
+}
+
+/// Handler untuk menolak konten; konten disembunyikan dari penerima
+#[utoipa::path(
+    post,
+    path = "/admin/moderation/{id}/reject",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Moderation verdict id")),
+    responses(
+        (status = 200, description = "Content rejected", body = ModerationVerdictResponse),
+        (status = 403, description = "Admin access required", body = ErrorResponse),
+        (status = 404, description = "Moderation verdict not found", body = ErrorResponse),
+        (status = 409, description = "Already reviewed", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn reject_moderation_handler(
+    State(state): State<AppState>,
+    admin: AdminUser,
+    client: ClientInfo,
+    Path(verdict_id): Path<i32>,
+) -> Result<impl IntoResponse, AppError> {
+    let admin_id: i32 = admin
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let verdict = review_verdict(
+        &state.pool,
+        &state.event_bus,
+        admin_id,
+        verdict_id,
+        false,
+        client.ip_address.as_deref(),
+    )?;
+    Ok(Json(verdict))
+}
+
+/// Handler untuk melihat status mode maintenance baca-saja
+#[utoipa::path(
+    get,
+    path = "/admin/maintenance",
+    tag = "admin",
+    responses(
+        (status = 200, description = "OK", body = MaintenanceStatus),
+        (status = 403, description = "Admin access required", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_maintenance_handler(
+    State(state): State<AppState>,
+    _admin: AdminUser,
+) -> Result<impl IntoResponse, AppError> {
+    Ok(Json(state.maintenance.status()))
+}
+
+/// Handler untuk menyalakan atau mematikan mode maintenance di instance ini.
+/// Selama aktif, request tulis lain ditolak dengan 503 dan `Retry-After`.
+#[utoipa::path(
+    put,
+    path = "/admin/maintenance",
+    tag = "admin",
+    request_body = UpdateMaintenanceRequest,
+    responses(
+        (status = 200, description = "Maintenance mode updated", body = MaintenanceStatus),
+        (status = 403, description = "Admin access required", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update_maintenance_handler(
+    State(state): State<AppState>,
+    admin: AdminUser,
+    client: ClientInfo,
+    Json(data): Json<UpdateMaintenanceRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let admin_id: i32 = admin
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let status = set_maintenance_by_admin(
+        &state.pool,
+        &state.maintenance,
+        admin_id,
+        data,
+        client.ip_address.as_deref(),
+    );
+    Ok(Json(status))
+}
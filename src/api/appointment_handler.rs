@@ -0,0 +1,93 @@
+use axum::{
+    extract::{Path, State},
+    response::IntoResponse,
+};
+use crate::middleware::json_body::Json;
+
+use crate::{
+    errors::app_error::AppError,
+    middleware::auth_middleware::AuthenticatedUser,
+    models::appointment::RescheduleAppointmentRequest,
+    service::appointment_service::{cancel_appointment, get_appointments, reschedule_appointment},
+    state::AppState,
+};
+
+/// Handler untuk daftar janji temu pengguna dengan psikolog
+#[utoipa::path(
+    get,
+    path = "/appointments",
+    tag = "appointments",
+    responses(
+        (status = 200, description = "OK", body = Vec<AppointmentResponse>)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_appointments_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let appointments = get_appointments(&state.pool, user_id)?;
+    Ok(Json(appointments))
+}
+
+/// Handler untuk memindahkan janji temu ke jadwal lain milik psikolog yang sama
+#[utoipa::path(
+    put,
+    path = "/appointments/{id}/reschedule",
+    tag = "appointments",
+    params(("id" = i32, Path, description = "Appointment id")),
+    request_body = RescheduleAppointmentRequest,
+    responses(
+        (status = 200, description = "Appointment rescheduled", body = AppointmentResponse),
+        (status = 400, description = "Slot has already started", body = ErrorResponse),
+        (status = 404, description = "Appointment or slot not found", body = ErrorResponse),
+        (status = 409, description = "Appointment can no longer be changed or slot is already booked", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn reschedule_appointment_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(appointment_id): Path<i32>,
+    Json(data): Json<RescheduleAppointmentRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let appointment = reschedule_appointment(&state.pool, user_id, appointment_id, data)?;
+    Ok(Json(appointment))
+}
+
+/// Handler untuk membatalkan janji temu; jadwalnya bisa dipesan kembali
+#[utoipa::path(
+    post,
+    path = "/appointments/{id}/cancel",
+    tag = "appointments",
+    params(("id" = i32, Path, description = "Appointment id")),
+    responses(
+        (status = 200, description = "Appointment cancelled", body = AppointmentResponse),
+        (status = 404, description = "Appointment not found", body = ErrorResponse),
+        (status = 409, description = "Appointment can no longer be changed", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn cancel_appointment_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(appointment_id): Path<i32>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let appointment = cancel_appointment(&state.pool, user_id, appointment_id)?;
+    Ok(Json(appointment))
+}
@@ -1,31 +1,57 @@
 use axum::{
-    extract::{State, Json, Query},
-    response::{IntoResponse, Redirect},
-    http::HeaderMap,
+    extract::{State, Query},
+    response::IntoResponse,
+    http::{header, HeaderMap, Method, StatusCode},
 };
+use crate::middleware::json_body::Json;
 use crate::service::{
-    auth_service::{register_user, login_user, logout_user},
-    google_auth_service::{google_login, get_google_auth_url}
+    auth_service::{register_user, login_user, login_guest, logout_user, issue_scoped_token, upgrade_guest},
+    google_auth_service::{google_login, get_google_auth_url, frontend_redirect, redeem_exchange_code}
 };
 use crate::errors::app_error::AppError;
+use crate::i18n::t;
+use crate::middleware::auth_middleware::{request_token, AuthenticatedUser};
+use crate::middleware::captcha::CaptchaVerified;
+use crate::middleware::client_info::ClientInfo;
+use crate::state::AppState;
+use crate::utils::auth_cookie::{clear_cookies, csrf_cookie_name, login_cookies, read_cookie};
+use crate::utils::event_bus::AppEvent;
 use crate::models::auth::{
     RegisterRequest, 
     LoginRequest, 
     LoginResponse, 
     GoogleCallbackRequest,
-    GoogleAuthUrlResponse
+    GoogleAuthUrlRequest,
+    GoogleAuthUrlResponse,
+    ScopedTokenRequest,
+    GuestLoginRequest,
+    UpgradeAccountRequest,
+    CsrfTokenResponse,
+    ExchangeCodeRequest,
 };
-use diesel::r2d2;
-use diesel::pg::PgConnection;
 use serde_json::json;
 // ✅ Removed unused import
 
+#[utoipa::path(
+    post,
+    path = "/auth/register",
+    tag = "auth",
+    request_body = RegisterRequest,
+    params(("X-Captcha-Token" = Option<String>, Header, description = "Token CAPTCHA, wajib setelah terlalu banyak percobaan dari satu IP")),
+    responses(
+        (status = 200, description = "User registered"),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 403, description = "CAPTCHA verification required or failed", body = ErrorResponse),
+        (status = 409, description = "Email or username already exists", body = ErrorResponse)
+    )
+)]
 pub async fn register(
-    State(pool): State<r2d2::Pool<diesel::r2d2::ConnectionManager<PgConnection>>>,
+    State(state): State<AppState>,
+    _captcha: CaptchaVerified,
     Json(data): Json<RegisterRequest>,
 ) -> Result<impl IntoResponse, AppError> {
     let user = register_user(
-        &pool, 
+        &state.pool, 
         &data.username,    
         &data.email,       
         &data.password,    
@@ -33,9 +59,11 @@ pub async fn register(
         data.gender,     
         None              
     )?;
+
+    state.event_bus.publish(AppEvent::UserRegistered { user_id: user.id });
     
     Ok(Json(json!({
-        "message": "User registered successfully",
+        "message": t("message.user_registered"),
         "user": {
             "id": user.id,
             "username": user.username,
@@ -47,62 +75,239 @@ pub async fn register(
     })))
 }
 
+#[utoipa::path(
+    post,
+    path = "/auth/login",
+    tag = "auth",
+    request_body = LoginRequest,
+    responses(
+        (status = 200, description = "OK; also sets the session and CSRF cookies when cookie auth is enabled", body = LoginResponse),
+        (status = 401, description = "Invalid username/email or password", body = ErrorResponse),
+        (status = 429, description = "Too many failed attempts, account or IP temporarily locked", body = ErrorResponse)
+    )
+)]
 pub async fn login(
-    State(pool): State<r2d2::Pool<diesel::r2d2::ConnectionManager<PgConnection>>>,
+    State(state): State<AppState>,
+    client: ClientInfo,
     Json(data): Json<LoginRequest>,
 ) -> Result<impl IntoResponse, AppError> {
-    let login_response = login_user(&pool, &data.email, &data.password)?;
-    
-    Ok(Json(LoginResponse {
-        token: login_response.token,
-        user: login_response.user,
-    }))
+    let identifier = data
+        .identifier()
+        .ok_or_else(|| AppError::BadRequest("Username or email must be provided".to_string()))?;
+    let login_response = login_user(
+        &state.pool,
+        identifier,
+        &data.password,
+        client.ip_address.as_deref(),
+        client.user_agent.as_deref(),
+    )?;
+
+    Ok((
+        login_cookies(state.config, &login_response.token),
+        Json(LoginResponse {
+            token: login_response.token,
+            user: login_response.user,
+        }),
+    ))
 }
 
+#[utoipa::path(
+    post,
+    path = "/auth/logout",
+    tag = "auth",
+    responses(
+        (status = 200, description = "Token blacklisted and session cookies cleared"),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Missing or invalid CSRF token (cookie auth)", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn logout(
-    State(pool): State<r2d2::Pool<diesel::r2d2::ConnectionManager<PgConnection>>>,
+    State(state): State<AppState>,
     headers: HeaderMap,
 ) -> Result<impl IntoResponse, AppError> {
-    let auth_header = headers
-        .get("Authorization")
-        .ok_or_else(|| AppError::Unauthorized("Authorization header missing".to_string()))?;
+    let token = request_token(&headers, &Method::POST, state.config)?;
 
-    let auth_str = auth_header
-        .to_str()
-        .map_err(|_| AppError::Unauthorized("Invalid Authorization header".to_string()))?;
+    logout_user(&state.pool, token)?;
 
-    if !auth_str.starts_with("Bearer ") {
-        return Err(AppError::Unauthorized("Invalid Authorization scheme".to_string()));
-    }
+    Ok((
+        clear_cookies(state.config),
+        Json(json!({
+            "message": t("message.logout")
+        })),
+    ))
+}
 
-    let token = &auth_str[7..];
+/// Token CSRF untuk sesi cookie. Frontend di domain lain tidak bisa membaca cookie CSRF
+/// secara langsung, jadi nilainya diambil lewat endpoint ini lalu dikirim di header X-CSRF-Token.
+#[utoipa::path(
+    get,
+    path = "/auth/csrf",
+    tag = "auth",
+    responses(
+        (status = 200, description = "OK", body = CsrfTokenResponse),
+        (status = 401, description = "Not signed in", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn csrf_token_handler(
+    State(state): State<AppState>,
+    _user: AuthenticatedUser,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    let csrf_token = read_cookie(&headers, &csrf_cookie_name(state.config))
+        .ok_or_else(|| AppError::Unauthorized("Not signed in with a session cookie".to_string()))?;
 
-    logout_user(&pool, token)?;
+    Ok(Json(CsrfTokenResponse {
+        csrf_token: csrf_token.to_string(),
+    }))
+}
 
-    Ok(Json(json!({
-        "message": "Successfully logged out"
-    })))
+/// Buat token terbatas (misalnya `share:read`) untuk share link atau integrasi
+#[utoipa::path(
+    post,
+    path = "/auth/scoped-token",
+    tag = "auth",
+    request_body = ScopedTokenRequest,
+    responses(
+        (status = 200, description = "OK", body = ScopedTokenResponse),
+        (status = 400, description = "Unknown scope or invalid expiry", body = ErrorResponse),
+        (status = 403, description = "Scoped tokens cannot issue other tokens", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn issue_scoped_token_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    client: ClientInfo,
+    Json(data): Json<ScopedTokenRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let response = issue_scoped_token(
+        &state.pool,
+        user_id,
+        &data.scopes,
+        data.expires_in_hours,
+        client.ip_address.as_deref(),
+    )?;
+    Ok(Json(response))
 }
 
-pub async fn google_auth_url() -> Result<impl IntoResponse, AppError> {
-    let auth_url = get_google_auth_url()?;
+/// Masuk sebagai tamu tanpa email; akun terikat ke perangkat
+#[utoipa::path(
+    post,
+    path = "/auth/guest",
+    tag = "auth",
+    request_body = GuestLoginRequest,
+    responses(
+        (status = 200, description = "OK; device_secret is only returned for a new guest account", body = GuestLoginResponse),
+        (status = 400, description = "Invalid device id", body = ErrorResponse),
+        (status = 401, description = "Wrong device secret for an existing guest account", body = ErrorResponse)
+    )
+)]
+pub async fn guest_login_handler(
+    State(state): State<AppState>,
+    Json(data): Json<GuestLoginRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = login_guest(&state.pool, &data.device_id, data.device_secret.as_deref())?;
+    Ok((login_cookies(state.config, &response.token), Json(response)))
+}
+
+/// Ubah akun tamu menjadi akun penuh tanpa kehilangan data
+#[utoipa::path(
+    post,
+    path = "/auth/upgrade",
+    tag = "auth",
+    request_body = UpgradeAccountRequest,
+    responses(
+        (status = 200, description = "Account upgraded", body = UserResponse),
+        (status = 400, description = "Not a guest account, invalid email/username, or weak password", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 409, description = "Email or username already exists", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn upgrade_account_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Json(data): Json<UpgradeAccountRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let user = upgrade_guest(&state.pool, user_id, data)?;
+    Ok(Json(user))
+}
+
+#[utoipa::path(
+    get,
+    path = "/auth/google",
+    tag = "auth",
+    params(GoogleAuthUrlRequest),
+    responses(
+        (status = 200, description = "OK", body = GoogleAuthUrlResponse),
+        (status = 400, description = "`redirect_to` is not in the redirect whitelist", body = ErrorResponse)
+    )
+)]
+pub async fn google_auth_url(
+    Query(params): Query<GoogleAuthUrlRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let auth_url = get_google_auth_url(params.redirect_to.as_deref())?;
     
     Ok(Json(GoogleAuthUrlResponse {
         auth_url,
     }))
 }
 
+#[utoipa::path(
+    get,
+    path = "/auth/google/callback",
+    tag = "auth",
+    params(GoogleCallbackRequest),
+    responses(
+        (status = 302, description = "Redirect to the frontend (or the whitelisted `redirect_to` from /auth/google) with a one-time `code`; exchange it via POST /auth/exchange")
+    )
+)]
 pub async fn google_callback(
-    State(pool): State<r2d2::Pool<diesel::r2d2::ConnectionManager<PgConnection>>>,
+    State(state): State<AppState>,
     Query(params): Query<GoogleCallbackRequest>,
 ) -> Result<impl IntoResponse, AppError> {
-    let login_response = google_login(&pool, &params.code, params.state.as_deref()).await?;
-    
-    let redirect_url = if login_response.is_new_user {
-        format!("https://mind-mate-fe.vercel.app/dashboard?welcome=1&token={}", login_response.token)
-    } else {
-        format!("https://mind-mate-fe.vercel.app/dashboard?token={}", login_response.token)
-    };
-    
-    Ok(Redirect::permanent(&redirect_url))
+    let mut redirect_url = frontend_redirect(params.state.as_deref())?;
+    let login_response = google_login(&state.pool, &state.http_client, &params.code, params.state.as_deref()).await?;
+
+    // Hanya kode sekali pakai yang masuk URL, supaya JWT tidak tersimpan di history atau log
+    redirect_url
+        .query_pairs_mut()
+        .append_pair("code", &login_response.exchange_code);
+    if login_response.is_new_user {
+        redirect_url.query_pairs_mut().append_pair("welcome", "1");
+    }
+
+    // 302 agar browser tidak meng-cache redirect yang berisi kode sekali pakai
+    Ok((StatusCode::FOUND, [(header::LOCATION, redirect_url.to_string())]))
+}
+
+#[utoipa::path(
+    post,
+    path = "/auth/exchange",
+    tag = "auth",
+    request_body = ExchangeCodeRequest,
+    responses(
+        (status = 200, description = "OK; also sets the session and CSRF cookies when cookie auth is enabled", body = LoginResponse),
+        (status = 400, description = "Invalid, expired, or already used code", body = ErrorResponse)
+    )
+)]
+pub async fn exchange_code_handler(
+    State(state): State<AppState>,
+    Json(data): Json<ExchangeCodeRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let login_response = redeem_exchange_code(&state.pool, &data.code)?;
+
+    Ok((login_cookies(state.config, &login_response.token), Json(login_response)))
 }
\ No newline at end of file
@@ -0,0 +1,95 @@
+use axum::{
+    extract::{State, Query},
+    http::header,
+    response::IntoResponse,
+};
+use crate::middleware::json_body::Json;
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+use crate::{
+    errors::app_error::AppError,
+    middleware::auth_middleware::AuthenticatedUser,
+    service::calendar_service::{build_feed, get_or_create_feed_token, regenerate_feed_token},
+    state::AppState,
+};
+
+#[derive(Deserialize, IntoParams)]
+pub struct CalendarFeedQuery {
+    /// Token feed kalender milik user
+    pub token: String,
+}
+
+/// Handler untuk mengambil token dan URL feed kalender
+#[utoipa::path(
+    get,
+    path = "/calendar/token",
+    tag = "calendar",
+    responses(
+        (status = 200, description = "OK", body = CalendarTokenResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_calendar_token_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let token = get_or_create_feed_token(&state.pool, user_id)?;
+    Ok(Json(token))
+}
+
+/// Handler untuk membuat ulang token feed kalender (link lama tidak berlaku)
+#[utoipa::path(
+    post,
+    path = "/calendar/token",
+    tag = "calendar",
+    responses(
+        (status = 200, description = "OK", body = CalendarTokenResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn regenerate_calendar_token_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let token = regenerate_feed_token(&state.pool, user_id)?;
+    Ok(Json(token))
+}
+
+/// Handler untuk feed iCalendar, diakses aplikasi kalender dengan token feed
+#[utoipa::path(
+    get,
+    path = "/calendar.ics",
+    tag = "calendar",
+    params(CalendarFeedQuery),
+    responses(
+        (status = 200, description = "iCalendar feed", content_type = "text/calendar", body = String),
+        (status = 401, description = "Invalid feed token", body = ErrorResponse)
+    )
+)]
+pub async fn calendar_feed_handler(
+    State(state): State<AppState>,
+    Query(query): Query<CalendarFeedQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let feed = build_feed(&state.pool, &query.token)?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/calendar; charset=utf-8"),
+            (header::CONTENT_DISPOSITION, "inline; filename=\"mindmate.ics\""),
+        ],
+        feed,
+    ))
+}
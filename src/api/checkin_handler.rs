@@ -0,0 +1,44 @@
+use axum::{
+    extract::{Query, State},
+    response::{Html, IntoResponse},
+};
+
+use crate::{
+    i18n::{current_locale, localize_message, t_in, t_in_args},
+    models::checkin::CheckinQuery,
+    service::checkin_service::{check_in, render_page},
+    state::AppState,
+};
+
+/// Handler untuk tombol check-in mood di email pengingat; tidak perlu login.
+/// Respons berupa halaman HTML karena link dibuka langsung di browser.
+#[utoipa::path(
+    get,
+    path = "/checkin",
+    tag = "moods",
+    params(CheckinQuery),
+    responses(
+        (status = 200, description = "Mood recorded", content_type = "text/html", body = String),
+        (status = 400, description = "Invalid mood or mood already recorded for the day", content_type = "text/html", body = String),
+        (status = 401, description = "Invalid or expired link", content_type = "text/html", body = String),
+        (status = 409, description = "Link already used", content_type = "text/html", body = String)
+    )
+)]
+pub async fn checkin_handler(
+    State(state): State<AppState>,
+    Query(query): Query<CheckinQuery>,
+) -> impl IntoResponse {
+    match check_in(&state.pool, &state.stats_cache, &query.token, &query.mood) {
+        Ok((mood, locale)) => {
+            let saved = format!("{} {}", mood.emoji, mood.mood);
+            let message = t_in_args(locale, "checkin.page.saved", &[&mood.date.to_string(), &saved]);
+            Html(render_page(locale, &t_in(locale, "checkin.page.thanks"), &message)).into_response()
+        }
+        Err(e) => {
+            let locale = current_locale();
+            let (status, message) = e.into_parts();
+            let page = render_page(locale, &t_in(locale, "checkin.page.failed"), &localize_message(&message));
+            (status, Html(page)).into_response()
+        }
+    }
+}
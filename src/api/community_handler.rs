@@ -0,0 +1,219 @@
+use axum::{
+    extract::{Path, Query, State},
+    response::IntoResponse,
+};
+use crate::middleware::json_body::Json;
+
+use crate::{
+    errors::app_error::AppError,
+    i18n::t,
+    middleware::auth_middleware::AuthenticatedUser,
+    models::community::{CommunityPostListQuery, CreateCommunityPostRequest},
+    service::community_service::{
+        add_reaction, create_post, get_groups, get_posts, join_group, leave_group, remove_reaction,
+    },
+    state::AppState,
+};
+
+/// Handler untuk daftar grup komunitas beserta jumlah anggota
+#[utoipa::path(
+    get,
+    path = "/community/groups",
+    tag = "community",
+    responses(
+        (status = 200, description = "OK", body = Vec<CommunityGroupResponse>)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_groups_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let groups = get_groups(&state.pool, user_id)?;
+    Ok(Json(groups))
+}
+
+/// Handler untuk bergabung dengan grup komunitas
+#[utoipa::path(
+    post,
+    path = "/community/groups/{id}/join",
+    tag = "community",
+    params(("id" = i32, Path, description = "Community group id")),
+    responses(
+        (status = 200, description = "Joined", body = CommunityGroupResponse),
+        (status = 404, description = "Community group not found", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn join_group_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(group_id): Path<i32>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let group = join_group(&state.pool, user_id, group_id)?;
+    Ok(Json(group))
+}
+
+/// Handler untuk keluar dari grup komunitas
+#[utoipa::path(
+    delete,
+    path = "/community/groups/{id}/join",
+    tag = "community",
+    params(("id" = i32, Path, description = "Community group id")),
+    responses(
+        (status = 200, description = "Left the group"),
+        (status = 404, description = "Not a member", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn leave_group_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(group_id): Path<i32>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    leave_group(&state.pool, user_id, group_id)?;
+    Ok(Json(t("message.community_left")))
+}
+
+/// Handler untuk kiriman anonim dalam grup, terbaru lebih dulu; hanya untuk anggota
+#[utoipa::path(
+    get,
+    path = "/community/groups/{id}/posts",
+    tag = "community",
+    params(
+        ("id" = i32, Path, description = "Community group id"),
+        CommunityPostListQuery
+    ),
+    responses(
+        (status = 200, description = "OK", body = Vec<CommunityPostResponse>),
+        (status = 400, description = "Invalid limit", body = ErrorResponse),
+        (status = 403, description = "Not a member", body = ErrorResponse),
+        (status = 404, description = "Community group not found", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_posts_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(group_id): Path<i32>,
+    Query(query): Query<CommunityPostListQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let posts = get_posts(&state.pool, user_id, group_id, &query)?;
+    Ok(Json(posts))
+}
+
+/// Handler untuk membuat kiriman anonim; kiriman melewati moderasi sebelum tampil
+#[utoipa::path(
+    post,
+    path = "/community/groups/{id}/posts",
+    tag = "community",
+    params(("id" = i32, Path, description = "Community group id")),
+    request_body = CreateCommunityPostRequest,
+    responses(
+        (status = 200, description = "Post created", body = CommunityPostResponse),
+        (status = 400, description = "Empty post", body = ErrorResponse),
+        (status = 403, description = "Not a member", body = ErrorResponse),
+        (status = 404, description = "Community group not found", body = ErrorResponse),
+        (status = 413, description = "Post too long", body = ErrorResponse),
+        (status = 429, description = "Posting limit reached", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn create_post_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(group_id): Path<i32>,
+    Json(data): Json<CreateCommunityPostRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let verdict = state.moderation.review(&data.body).await;
+    let post = create_post(&state.pool, user_id, group_id, data, &verdict)?;
+    Ok(Json(post))
+}
+
+/// Handler untuk memberi reaksi dukungan (`support`, `hug`, `relate`) pada kiriman
+#[utoipa::path(
+    put,
+    path = "/community/posts/{id}/reactions/{kind}",
+    tag = "community",
+    params(
+        ("id" = i32, Path, description = "Community post id"),
+        ("kind" = String, Path, description = "Reaction kind: support, hug or relate")
+    ),
+    responses(
+        (status = 200, description = "Reaction added", body = CommunityPostResponse),
+        (status = 400, description = "Unknown reaction", body = ErrorResponse),
+        (status = 403, description = "Not a member", body = ErrorResponse),
+        (status = 404, description = "Community post not found", body = ErrorResponse),
+        (status = 429, description = "Reaction limit reached", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn add_reaction_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path((post_id, kind)): Path<(i32, String)>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let post = add_reaction(&state.pool, user_id, post_id, &kind)?;
+    Ok(Json(post))
+}
+
+/// Handler untuk membatalkan reaksi pada kiriman
+#[utoipa::path(
+    delete,
+    path = "/community/posts/{id}/reactions/{kind}",
+    tag = "community",
+    params(
+        ("id" = i32, Path, description = "Community post id"),
+        ("kind" = String, Path, description = "Reaction kind: support, hug or relate")
+    ),
+    responses(
+        (status = 200, description = "Reaction removed", body = CommunityPostResponse),
+        (status = 400, description = "Unknown reaction", body = ErrorResponse),
+        (status = 403, description = "Not a member", body = ErrorResponse),
+        (status = 404, description = "Community post not found", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn remove_reaction_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path((post_id, kind)): Path<(i32, String)>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let post = remove_reaction(&state.pool, user_id, post_id, &kind)?;
+    Ok(Json(post))
+}
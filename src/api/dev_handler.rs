@@ -0,0 +1,43 @@
+use axum::{
+    extract::State,
+    response::IntoResponse,
+};
+use crate::middleware::json_body::Json;
+
+use crate::{
+    errors::app_error::AppError,
+    middleware::auth_middleware::AuthenticatedUser,
+    middleware::timezone_middleware::UserTimezone,
+    models::dev::GenerateHistoryRequest,
+    service::dev_service::generate_user_history,
+    state::AppState,
+};
+
+/// Handler untuk membuat riwayat mood dan jurnal buatan; hanya tersedia jika DEV_TOOLS aktif
+#[utoipa::path(
+    post,
+    path = "/dev/generate",
+    tag = "dev",
+    request_body = GenerateHistoryRequest,
+    responses(
+        (status = 200, description = "History generated", body = GenerateHistoryResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 404, description = "DEV_TOOLS is disabled")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn generate_history_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    tz: UserTimezone,
+    Json(data): Json<GenerateHistoryRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let generated = generate_user_history(&state.pool, &state.stats_cache, user_id, data, tz.tz())?;
+    Ok(Json(generated))
+}
@@ -0,0 +1,89 @@
+use axum::{
+    extract::{State, Path},
+    response::IntoResponse,
+};
+use crate::middleware::json_body::Json;
+
+use crate::{
+    errors::app_error::AppError,
+    i18n::t,
+    middleware::auth_middleware::AuthenticatedUser,
+    models::device::RegisterDeviceRequest,
+    service::push_service::{delete_device, get_devices, register_device},
+    state::AppState,
+};
+
+/// Handler untuk mendaftarkan token push perangkat
+#[utoipa::path(
+    post,
+    path = "/devices",
+    tag = "devices",
+    request_body = RegisterDeviceRequest,
+    responses(
+        (status = 200, description = "Device registered", body = DeviceResponse),
+        (status = 400, description = "Invalid platform or token", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn register_device_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Json(data): Json<RegisterDeviceRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let device = register_device(&state.pool, user_id, data)?;
+    Ok(Json(device))
+}
+
+/// Handler untuk daftar perangkat yang menerima push notification
+#[utoipa::path(
+    get,
+    path = "/devices",
+    tag = "devices",
+    responses(
+        (status = 200, description = "OK", body = Vec<DeviceResponse>)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_devices_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let devices = get_devices(&state.pool, user_id)?;
+    Ok(Json(devices))
+}
+
+/// Handler untuk berhenti menerima push notification di sebuah perangkat
+#[utoipa::path(
+    delete,
+    path = "/devices/{id}",
+    tag = "devices",
+    params(("id" = i32, Path, description = "Device id")),
+    responses(
+        (status = 200, description = "Device unregistered"),
+        (status = 404, description = "Not found", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn delete_device_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(device_id): Path<i32>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    delete_device(&state.pool, user_id, device_id)?;
+    Ok(Json(t("message.device_deleted")))
+}
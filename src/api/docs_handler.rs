@@ -0,0 +1,481 @@
+use axum::{
+    response::{Html, IntoResponse},
+};
+use crate::middleware::json_body::Json;
+use chrono::NaiveDateTime;
+use serde::Serialize;
+use utoipa::{
+    openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme},
+    Modify, OpenApi, ToSchema,
+};
+
+use crate::api::{admin_handler, appointment_handler, auth_handler, calendar_handler, checkin_handler, community_handler, dev_handler, device_handler, export_handler, health_handler, help_handler, import_handler, insight_handler, job_handler, journal_handler, message_handler, mood_handler, organization_handler, psychologist_handler, report_handler, security_handler, status_handler, user_handler, webhook_handler};
+use crate::models::{
+    auth::{
+        CsrfTokenResponse, ExchangeCodeRequest, GoogleAuthUrlResponse, GuestLoginRequest, GuestLoginResponse, ImpersonateRequest, ImpersonationResponse, LoginRequest, LoginResponse, RegisterRequest,
+        ScopedTokenRequest, ScopedTokenResponse, TokenCleanupResponse, UpgradeAccountRequest,
+    },
+    journal::{CreateJournalRequest, JournalDraftResponse, JournalListItem, JournalResponse, SaveJournalDraftRequest, SparseJournalResponse, UpdateJournalRequest},
+    mood::{CreateMoodRequest, MoodCount, MoodDetails, MoodLocation, MoodResponse, ScoreInterpretation, UpdateMoodRequest},
+    user::{AvatarResponse, AvatarUploadForm, EditProfileRequest, PatchProfileRequest, UserResponse, UserSettings},
+};
+use crate::service::user_service::UsernameCheckResponse;
+use crate::models::password_reset::{CheckEmailRequest, PasswordResetRequestedResponse, ResetPasswordRequest};
+use crate::models::email_change::{ConfirmEmailChangeRequest, PendingEmailChangeResponse, RequestEmailChangeRequest};
+use crate::models::security::LoginAttemptResponse;
+use crate::models::api_usage::{ApiUsageResponse, DailyUsage, QuotaUsage};
+use crate::models::status::{DatabaseStatus, MaintenanceStatus, PoolStats, ReadinessResponse, UpdateMaintenanceRequest};
+use crate::models::job::{DeleteAccountRequest, JobResponse};
+use crate::models::health::{HealthSamplesRequest, HealthSamplesResponse, SleepSegment, StepSample};
+use crate::models::webhook::{CreateWebhookRequest, WebhookDeliveryResponse, WebhookResponse};
+use crate::models::community::{
+    CommunityGroupResponse, CommunityPostResponse, CreateCommunityGroupRequest, CreateCommunityPostRequest, ReactionSummary,
+};
+use crate::models::moderation::ModerationVerdictResponse;
+use crate::models::message::{ConversationResponse, MarkReadResponse, MessageResponse, SendMessageRequest};
+use crate::models::appointment::{AppointmentResponse, ConfirmAppointmentRequest, RescheduleAppointmentRequest};
+use crate::models::psychologist::{
+    CreatePsychologistBooking, CreatePsychologistRequest, CreateSlotsRequest, PsychologistDetail,
+    PsychologistRequestResponse, PsychologistResponse, SlotInput, SlotResponse,
+};
+use crate::models::help::{
+    CreateHelpCommentRequest, CreateHelpRequest, HelpCommentResponse, HelpRequestDetail, HelpRequestResponse,
+    UpdateHelpRequestStatus,
+};
+use crate::models::notification::{NotificationPreferences, QuietHours};
+use crate::models::dev::{GenerateHistoryRequest, GenerateHistoryResponse};
+use crate::models::device::{DeviceResponse, RegisterDeviceRequest};
+use crate::models::organization::{
+    AcceptInvitationRequest, CreateOrganizationRequest, InvitationResponse, InviteMemberRequest, MemberMoodSummary,
+    OrganizationMemberResponse, OrganizationResponse, OrganizationSummary, UpdateConsentRequest,
+};
+use crate::models::import::{DaylioImportForm, ImportSummary, ImportedMood, SkippedImportRow};
+use crate::models::insight::{
+    DayOfWeekInsight, ForecastPoint, ImprovedDay, InsightSnapshot, MoodAlert, MoodCountChange, MoodForecast, MoodTrend, SmoothedPoint, TrendComparison, TrendPoint, WeekOverWeekInsight,
+    PlaceAverage, PlacesInsight, WeekSummary, WeekdayAverage,
+};
+use crate::models::onboarding::{OnboardingStatus, OnboardingStepStatus};
+use crate::models::admin_analytics::{RetentionCohort, WeeklyActivity};
+use crate::models::audit::AuditLogResponse;
+use crate::models::backup::{BackupResponse, CreateBackupRequest, RestoreBackupRequest, RestoreBackupResponse};
+use crate::utils::stats_cache::CacheMetrics;
+use crate::errors::app_error::FieldError;
+use crate::models::calendar::CalendarTokenResponse;
+use crate::models::report::{DailyScore, DetailPoint, DetailSeries, MonthlyAverage, MonthlyReport, MoodRangeStats, StreakSummary, WeeklyAverage, YearlyReport};
+
+/// Bentuk body error yang dikembalikan oleh `AppError`
+#[derive(Serialize, ToSchema)]
+pub struct ErrorResponse {
+    pub error: String,
+    /// Hanya pada error 5xx: `internal_error` atau `database_error`; detailnya hanya ada di log server
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    /// Hanya pada error 5xx: sama dengan header X-Request-Id, untuk mencari log terkait
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
+
+/// Body 429 saat kuota harian habis; disertai header `Retry-After` (detik)
+#[derive(Serialize, ToSchema)]
+pub struct QuotaErrorResponse {
+    pub error: String,
+    /// Batas harian operasi ini
+    pub limit: i32,
+    /// Saat kuota terisi kembali (tengah malam UTC)
+    pub reset_at: NaiveDateTime,
+}
+
+/// Body 422 saat body JSON rusak atau tidak cocok dengan bentuk request
+#[derive(Serialize, ToSchema)]
+pub struct ValidationErrorResponse {
+    pub error: String,
+    pub fields: Vec<FieldError>,
+}
+
+/// Menambahkan skema autentikasi Bearer JWT ke dokumen OpenAPI
+struct SecurityAddon;
+
+impl Modify for SecurityAddon {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "bearer_auth",
+            SecurityScheme::Http(
+                HttpBuilder::new()
+                    .scheme(HttpAuthScheme::Bearer)
+                    .bearer_format("JWT")
+                    .build(),
+            ),
+        );
+    }
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "MindMate API", description = "Backend API untuk aplikasi MindMate"),
+    servers(
+        (url = "/api/v1", description = "API v1"),
+        (url = "/api", description = "Tanpa versi; v1 kecuali header X-Api-Version menentukan lain")
+    ),
+    paths(
+        auth_handler::register,
+        auth_handler::login,
+        auth_handler::logout,
+        auth_handler::issue_scoped_token_handler,
+        auth_handler::guest_login_handler,
+        auth_handler::upgrade_account_handler,
+        auth_handler::csrf_token_handler,
+        auth_handler::google_auth_url,
+        auth_handler::google_callback,
+        auth_handler::exchange_code_handler,
+        user_handler::get_profile,
+        user_handler::edit_profile_handler,
+        user_handler::patch_profile_handler,
+        user_handler::get_settings_handler,
+        user_handler::get_usage_handler,
+        user_handler::update_settings_handler,
+        user_handler::change_password_handler,
+        user_handler::get_all_users_handler,
+        user_handler::check_email_handler_post,
+        user_handler::reset_password_handler,
+        mood_handler::create_mood_handler,
+        mood_handler::get_mood_by_id_handler,
+        mood_handler::get_user_moods_handler,
+        mood_handler::get_mood_by_date_handler,
+        mood_handler::get_moods_by_date_range_handler,
+        mood_handler::search_moods_handler,
+        mood_handler::update_mood_handler,
+        mood_handler::delete_mood_handler,
+        mood_handler::get_recent_moods_handler,
+        mood_handler::get_mood_stats_handler,
+        mood_handler::get_mood_streak_handler,
+        mood_handler::get_all_moods_handler,
+        mood_handler::get_advanced_mood_stats_handler,
+        journal_handler::create_journal_handler,
+        journal_handler::get_journal_by_id_handler,
+        journal_handler::get_user_journals_handler,
+        journal_handler::get_journal_by_date_handler,
+        journal_handler::get_journals_by_date_range_handler,
+        journal_handler::update_journal_handler,
+        journal_handler::delete_journal_handler,
+        journal_handler::get_recent_journals_handler,
+        journal_handler::get_journal_stats_handler,
+        journal_handler::get_journal_streak_handler,
+        journal_handler::get_all_journals_handler,
+        journal_handler::search_journals_handler,
+        report_handler::get_monthly_report_handler,
+        report_handler::get_yearly_report_handler,
+        report_handler::get_monthly_report_pdf_handler,
+        report_handler::monthly_report_pdf_job_handler,
+        calendar_handler::get_calendar_token_handler,
+        calendar_handler::regenerate_calendar_token_handler,
+        calendar_handler::calendar_feed_handler,
+        admin_handler::cleanup_tokens_handler,
+        admin_handler::cache_stats_handler,
+        admin_handler::audit_logs_handler,
+        admin_handler::create_backup_handler,
+        admin_handler::restore_backup_handler,
+        admin_handler::impersonate_user_handler,
+        admin_handler::weekly_activity_handler,
+        admin_handler::retention_cohorts_handler,
+        security_handler::get_login_history_handler,
+        security_handler::get_audit_log_handler,
+        user_handler::request_email_change_handler,
+        user_handler::get_pending_email_change_handler,
+        user_handler::cancel_email_change_handler,
+        user_handler::confirm_email_change_handler_get,
+        user_handler::confirm_email_change_handler_post,
+        user_handler::check_username_handler,
+        user_handler::upload_avatar_handler,
+        user_handler::get_avatar_handler,
+        user_handler::delete_account_handler,
+        user_handler::get_onboarding_handler,
+        insight_handler::get_alerts_handler,
+        insight_handler::get_week_over_week_handler,
+        insight_handler::get_day_of_week_handler,
+        insight_handler::get_places_handler,
+        insight_handler::get_forecast_handler,
+        insight_handler::get_trend_handler,
+        insight_handler::get_snapshot_handler,
+        journal_handler::get_journal_draft_handler,
+        journal_handler::save_journal_draft_handler,
+        journal_handler::delete_journal_draft_handler,
+        journal_handler::pin_journal_handler,
+        journal_handler::unpin_journal_handler,
+        journal_handler::get_user_journal_previews_handler,
+        journal_handler::search_journal_previews_handler,
+        journal_handler::get_journal_previews_by_date_range_handler,
+        journal_handler::get_recent_journal_previews_handler,
+        mood_handler::restore_mood_handler,
+        mood_handler::pin_mood_handler,
+        mood_handler::unpin_mood_handler,
+        device_handler::register_device_handler,
+        device_handler::get_devices_handler,
+        device_handler::delete_device_handler,
+        export_handler::export_markdown_handler,
+        export_handler::export_markdown_job_handler,
+        import_handler::import_daylio_handler,
+        import_handler::import_daylio_job_handler,
+        organization_handler::create_organization_handler,
+        organization_handler::get_organizations_handler,
+        organization_handler::get_members_handler,
+        organization_handler::remove_member_handler,
+        organization_handler::invite_member_handler,
+        organization_handler::accept_invitation_handler,
+        organization_handler::update_consent_handler,
+        organization_handler::get_summary_handler,
+        dev_handler::generate_history_handler,
+        help_handler::create_help_request_handler,
+        help_handler::get_help_requests_handler,
+        help_handler::get_help_request_handler,
+        help_handler::add_help_comment_handler,
+        admin_handler::list_help_requests_handler,
+        admin_handler::get_help_request_handler,
+        admin_handler::update_help_request_status_handler,
+        admin_handler::add_help_comment_handler,
+        psychologist_handler::get_psychologists_handler,
+        psychologist_handler::get_psychologist_handler,
+        psychologist_handler::create_psychologist_request_handler,
+        psychologist_handler::get_psychologist_requests_handler,
+        admin_handler::create_psychologist_handler,
+        admin_handler::create_psychologist_slots_handler,
+        appointment_handler::get_appointments_handler,
+        appointment_handler::reschedule_appointment_handler,
+        appointment_handler::cancel_appointment_handler,
+        admin_handler::confirm_appointment_handler,
+        message_handler::get_conversations_handler,
+        message_handler::get_messages_handler,
+        message_handler::send_message_handler,
+        message_handler::mark_read_handler,
+        message_handler::conversation_events_handler,
+        admin_handler::list_moderation_handler,
+        admin_handler::approve_moderation_handler,
+        admin_handler::reject_moderation_handler,
+        community_handler::get_groups_handler,
+        community_handler::join_group_handler,
+        community_handler::leave_group_handler,
+        community_handler::get_posts_handler,
+        community_handler::create_post_handler,
+        community_handler::add_reaction_handler,
+        community_handler::remove_reaction_handler,
+        admin_handler::create_community_group_handler,
+        checkin_handler::checkin_handler,
+        webhook_handler::get_webhooks_handler,
+        webhook_handler::register_webhook_handler,
+        webhook_handler::delete_webhook_handler,
+        health_handler::save_samples_handler,
+        job_handler::get_job_handler,
+        job_handler::get_job_result_handler,
+        status_handler::ready_handler,
+        status_handler::metrics_handler,
+        admin_handler::get_maintenance_handler,
+        admin_handler::update_maintenance_handler,
+    ),
+    components(schemas(
+        ErrorResponse,
+        QuotaErrorResponse,
+        ValidationErrorResponse,
+        FieldError,
+        RegisterRequest,
+        LoginRequest,
+        LoginResponse,
+        GoogleAuthUrlResponse,
+        ScopedTokenRequest,
+        ScopedTokenResponse,
+        GuestLoginRequest,
+        GuestLoginResponse,
+        CsrfTokenResponse,
+        ExchangeCodeRequest,
+        UpgradeAccountRequest,
+        UserResponse,
+        UserSettings,
+        ApiUsageResponse,
+        DailyUsage,
+        QuotaUsage,
+        NotificationPreferences,
+        QuietHours,
+        PasswordResetRequestedResponse,
+        EditProfileRequest,
+        PatchProfileRequest,
+        user_handler::ChangePasswordRequest,
+        CheckEmailRequest,
+        ResetPasswordRequest,
+        MoodResponse,
+        CreateMoodRequest,
+        UpdateMoodRequest,
+        JournalResponse,
+        SparseJournalResponse,
+        JournalListItem,
+        CreateJournalRequest,
+        UpdateJournalRequest,
+        MonthlyReport,
+        MoodRangeStats,
+        YearlyReport,
+        DailyScore,
+        WeeklyAverage,
+        MonthlyAverage,
+        StreakSummary,
+        MoodCount,
+        MoodDetails,
+        MoodLocation,
+        DetailPoint,
+        DetailSeries,
+        ScoreInterpretation,
+        CalendarTokenResponse,
+        TokenCleanupResponse,
+        ImpersonateRequest,
+        ImpersonationResponse,
+        CacheMetrics,
+        LoginAttemptResponse,
+        AuditLogResponse,
+        RequestEmailChangeRequest,
+        ConfirmEmailChangeRequest,
+        PendingEmailChangeResponse,
+        UsernameCheckResponse,
+        AvatarResponse,
+        AvatarUploadForm,
+        OnboardingStatus,
+        OnboardingStepStatus,
+        MoodAlert,
+        WeekOverWeekInsight,
+        WeekSummary,
+        ImprovedDay,
+        MoodCountChange,
+        DayOfWeekInsight,
+        WeekdayAverage,
+        PlacesInsight,
+        PlaceAverage,
+        MoodForecast,
+        MoodTrend,
+        InsightSnapshot,
+        TrendPoint,
+        SmoothedPoint,
+        TrendComparison,
+        ForecastPoint,
+        JournalDraftResponse,
+        SaveJournalDraftRequest,
+        DeviceResponse,
+        RegisterDeviceRequest,
+        DaylioImportForm,
+        ImportSummary,
+        ImportedMood,
+        SkippedImportRow,
+        CreateBackupRequest,
+        BackupResponse,
+        RestoreBackupRequest,
+        RestoreBackupResponse,
+        CreateOrganizationRequest,
+        InviteMemberRequest,
+        AcceptInvitationRequest,
+        UpdateConsentRequest,
+        OrganizationResponse,
+        OrganizationMemberResponse,
+        InvitationResponse,
+        OrganizationSummary,
+        MemberMoodSummary,
+        WeeklyActivity,
+        RetentionCohort,
+        GenerateHistoryRequest,
+        GenerateHistoryResponse,
+        CreateHelpRequest,
+        UpdateHelpRequestStatus,
+        CreateHelpCommentRequest,
+        HelpRequestResponse,
+        HelpCommentResponse,
+        HelpRequestDetail,
+        CreatePsychologistRequest,
+        SlotInput,
+        CreateSlotsRequest,
+        CreatePsychologistBooking,
+        PsychologistResponse,
+        SlotResponse,
+        PsychologistDetail,
+        PsychologistRequestResponse,
+        ConfirmAppointmentRequest,
+        RescheduleAppointmentRequest,
+        AppointmentResponse,
+        SendMessageRequest,
+        MessageResponse,
+        ConversationResponse,
+        MarkReadResponse,
+        ModerationVerdictResponse,
+        CreateCommunityGroupRequest,
+        CreateCommunityPostRequest,
+        CommunityGroupResponse,
+        ReactionSummary,
+        CommunityPostResponse,
+        CreateWebhookRequest,
+        WebhookResponse,
+        WebhookDeliveryResponse,
+        StepSample,
+        SleepSegment,
+        HealthSamplesRequest,
+        HealthSamplesResponse,
+        JobResponse,
+        DeleteAccountRequest,
+        ReadinessResponse,
+        DatabaseStatus,
+        PoolStats,
+        MaintenanceStatus,
+        UpdateMaintenanceRequest,
+    )),
+    modifiers(&SecurityAddon),
+    tags(
+        (name = "auth", description = "Registrasi, login dan Google OAuth"),
+        (name = "user", description = "Profil dan akun pengguna"),
+        (name = "moods", description = "Pencatatan mood harian"),
+        (name = "journals", description = "Jurnal pengguna"),
+        (name = "reports", description = "Laporan bulanan dan tahunan"),
+        (name = "calendar", description = "Feed iCalendar aktivitas pengguna"),
+        (name = "admin", description = "Operasi administrasi"),
+        (name = "security", description = "Riwayat login, audit log dan keamanan akun"),
+        (name = "insights", description = "Deteksi pola penurunan mood"),
+        (name = "devices", description = "Registrasi perangkat untuk push notification"),
+        (name = "export", description = "Export data pengguna"),
+        (name = "import", description = "Import data dari aplikasi lain"),
+        (name = "organizations", description = "Organisasi (klinik), undangan anggota dan persetujuan berbagi data"),
+        (name = "dev", description = "Alat development, hanya tersedia jika DEV_TOOLS aktif"),
+        (name = "help", description = "Permintaan bantuan dan percakapan dengan admin"),
+        (name = "psychologists", description = "Direktori psikolog dan pemesanan jadwal"),
+        (name = "appointments", description = "Janji temu dengan psikolog"),
+        (name = "conversations", description = "Pesan antara pengguna dan psikolognya"),
+        (name = "community", description = "Grup dukungan komunitas dengan kiriman anonim"),
+        (name = "integrations", description = "Webhook Slack/Discord dan data Google Fit / Apple Health"),
+        (name = "jobs", description = "Status dan hasil operasi panjang (import, export, PDF, hapus akun) yang dikerjakan di latar belakang"),
+        (name = "status", description = "Readiness probe dan metrics untuk operator"),
+    )
+)]
+pub struct ApiDoc;
+
+/// Handler untuk dokumen OpenAPI dalam format JSON
+/// GET /openapi.json
+pub async fn openapi_json_handler() -> impl IntoResponse {
+    Json(ApiDoc::openapi())
+}
+
+/// Handler untuk Swagger UI yang membaca /api/openapi.json
+/// GET /docs
+pub async fn swagger_ui_handler() -> impl IntoResponse {
+    Html(SWAGGER_UI_HTML)
+}
+
+const SWAGGER_UI_HTML: &str = r##"<!DOCTYPE html>
+<html lang="en">
+<head>
+    <meta charset="utf-8" />
+    <title>MindMate API Docs</title>
+    <link rel="stylesheet" href="https://unpkg.com/swagger-ui-dist@5/swagger-ui.css" />
+</head>
+<body>
+    <div id="swagger-ui"></div>
+    <script src="https://unpkg.com/swagger-ui-dist@5/swagger-ui-bundle.js" crossorigin></script>
+    <script>
+        window.onload = () => {
+            window.ui = SwaggerUIBundle({
+                url: "/api/openapi.json",
+                dom_id: "#swagger-ui",
+            });
+        };
+    </script>
+</body>
+</html>"##;
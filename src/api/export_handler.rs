@@ -0,0 +1,88 @@
+use axum::{
+    extract::State,
+    http::header,
+    response::IntoResponse,
+};
+
+use crate::{
+    errors::app_error::AppError,
+    middleware::auth_middleware::{AuthenticatedUser, ExportScope},
+    middleware::timezone_middleware::UserTimezone,
+    middleware::client_info::ClientInfo,
+    service::export_service::export_journals_markdown,
+    service::job_service::enqueue_journals_markdown,
+    api::job_handler::accepted,
+    utils::timezone::today_in,
+    state::AppState,
+};
+
+/// Handler untuk export semua jurnal sebagai arsip ZIP berisi file Markdown
+#[utoipa::path(
+    get,
+    path = "/export/markdown",
+    tag = "export",
+    params(
+        ("X-Timezone" = Option<String>, Header, description = "IANA timezone, e.g. Asia/Jakarta; defaults to the user setting")
+    ),
+    responses(
+        (status = 200, description = "ZIP archive, one Markdown file per journal", content_type = "application/zip", body = Vec<u8>),
+        (status = 403, description = "Token lacks the export:read scope", body = ErrorResponse),
+        (status = 429, description = "Daily export quota reached", body = QuotaErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn export_markdown_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser<ExportScope>,
+    tz: UserTimezone,
+    client: ClientInfo,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let archive = export_journals_markdown(&state.pool, user_id, tz.tz(), client.ip_address.as_deref())?;
+    let disposition = format!(
+        "attachment; filename=\"mindmate-journals-{}.zip\"",
+        today_in(tz.tz())
+    );
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/zip".to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        archive,
+    ))
+}
+
+/// Handler v2: antrikan export jurnal Markdown sebagai job; hasil ZIP diunduh lewat `/jobs/{id}/result`
+#[utoipa::path(
+    post,
+    path = "/v2/export/markdown",
+    tag = "export",
+    params(
+        ("X-Timezone" = Option<String>, Header, description = "IANA timezone, e.g. Asia/Jakarta; defaults to the user setting")
+    ),
+    responses(
+        (status = 202, description = "Export queued", body = JobResponse),
+        (status = 403, description = "Token lacks the export:read scope", body = ErrorResponse),
+        (status = 429, description = "Daily export quota reached", body = QuotaErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn export_markdown_job_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser<ExportScope>,
+    tz: UserTimezone,
+    client: ClientInfo,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let job = enqueue_journals_markdown(&state.pool, user_id, tz.tz(), client.ip_address.as_deref())?;
+    Ok(accepted(job))
+}
@@ -0,0 +1,40 @@
+use axum::{
+    extract::State,
+    response::IntoResponse,
+};
+use crate::middleware::json_body::Json;
+
+use crate::{
+    errors::app_error::AppError,
+    middleware::auth_middleware::AuthenticatedUser,
+    models::health::HealthSamplesRequest,
+    service::health_service::save_samples,
+    state::AppState,
+};
+
+/// Handler untuk menerima langkah dan segmen tidur dari Google Fit / Apple Health.
+/// Data ini dipakai laporan bulanan dan tahunan untuk korelasi dengan mood.
+#[utoipa::path(
+    post,
+    path = "/integrations/health/samples",
+    tag = "integrations",
+    request_body = HealthSamplesRequest,
+    responses(
+        (status = 200, description = "Samples saved", body = HealthSamplesResponse),
+        (status = 400, description = "Unsupported source or invalid sample", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn save_samples_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Json(data): Json<HealthSamplesRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let saved = save_samples(&state.pool, user_id, data)?;
+    Ok(Json(saved))
+}
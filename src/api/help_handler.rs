@@ -0,0 +1,123 @@
+use axum::{
+    extract::{Path, State},
+    response::IntoResponse,
+};
+use crate::middleware::json_body::Json;
+
+use crate::{
+    errors::app_error::AppError,
+    middleware::auth_middleware::AuthenticatedUser,
+    middleware::captcha::CaptchaVerified,
+    models::help::{CreateHelpCommentRequest, CreateHelpRequest},
+    service::help_service::{add_user_comment, create_help_request, get_help_request, get_help_requests},
+    state::AppState,
+};
+
+/// Handler untuk mengirim permintaan bantuan
+#[utoipa::path(
+    post,
+    path = "/help-requests",
+    tag = "help",
+    request_body = CreateHelpRequest,
+    params(("X-Captcha-Token" = Option<String>, Header, description = "Token CAPTCHA, wajib setelah terlalu banyak percobaan dari satu IP")),
+    responses(
+        (status = 200, description = "Help request created", body = HelpRequestResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 403, description = "CAPTCHA verification required or failed", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn create_help_request_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    _captcha: CaptchaVerified,
+    Json(data): Json<CreateHelpRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let verdict = state.moderation.review(&data.message).await;
+    let request = create_help_request(&state.pool, user_id, data, &verdict)?;
+    Ok(Json(request))
+}
+
+/// Handler untuk daftar permintaan bantuan milik pengguna
+#[utoipa::path(
+    get,
+    path = "/help-requests",
+    tag = "help",
+    responses(
+        (status = 200, description = "OK", body = Vec<HelpRequestResponse>)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_help_requests_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let requests = get_help_requests(&state.pool, user_id)?;
+    Ok(Json(requests))
+}
+
+/// Handler untuk detail permintaan bantuan beserta percakapannya
+#[utoipa::path(
+    get,
+    path = "/help-requests/{id}",
+    tag = "help",
+    params(("id" = i32, Path, description = "Help request id")),
+    responses(
+        (status = 200, description = "OK", body = HelpRequestDetail),
+        (status = 404, description = "Help request not found", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_help_request_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(request_id): Path<i32>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let request = get_help_request(&state.pool, user_id, request_id)?;
+    Ok(Json(request))
+}
+
+/// Handler untuk membalas percakapan permintaan bantuan
+#[utoipa::path(
+    post,
+    path = "/help-requests/{id}/comments",
+    tag = "help",
+    params(("id" = i32, Path, description = "Help request id")),
+    request_body = CreateHelpCommentRequest,
+    responses(
+        (status = 200, description = "Comment added", body = HelpCommentResponse),
+        (status = 404, description = "Help request not found", body = ErrorResponse),
+        (status = 409, description = "Help request is already resolved", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn add_help_comment_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(request_id): Path<i32>,
+    Json(data): Json<CreateHelpCommentRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let verdict = state.moderation.review(&data.body).await;
+    let comment = add_user_comment(&state.pool, user_id, request_id, data, &verdict)?;
+    Ok(Json(comment))
+}
@@ -0,0 +1,117 @@
+use axum::{
+    body::Bytes,
+    extract::{Multipart, Query, State},
+    response::IntoResponse,
+};
+use crate::middleware::json_body::Json;
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+use crate::{
+    errors::app_error::AppError,
+    middleware::auth_middleware::AuthenticatedUser,
+    service::import_service::import_daylio,
+    service::job_service::enqueue_daylio_import,
+    api::job_handler::accepted,
+    state::AppState,
+};
+
+#[derive(Deserialize, IntoParams)]
+pub struct ImportQuery {
+    /// Hanya hitung apa yang akan dibuat, tanpa menyimpan
+    pub dry_run: Option<bool>,
+}
+
+/// Isi field multipart `file`
+async fn read_file_field(multipart: &mut Multipart) -> Result<Bytes, AppError> {
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| AppError::BadRequest(format!("Invalid multipart body: {}", e)))?
+    {
+        if field.name() == Some("file") {
+            return field
+                .bytes()
+                .await
+                .map_err(|e| AppError::BadRequest(format!("Failed to read file: {}", e)));
+        }
+    }
+
+    Err(AppError::BadRequest("Missing 'file' field".to_string()))
+}
+
+/// Handler untuk import mood dari CSV export Daylio (multipart, field `file`)
+#[utoipa::path(
+    post,
+    path = "/import/daylio",
+    tag = "import",
+    params(ImportQuery),
+    request_body(content = DaylioImportForm, content_type = "multipart/form-data"),
+    responses(
+        (status = 200, description = "Moods created, or that would be created on a dry run", body = ImportSummary),
+        (status = 400, description = "Not a Daylio CSV export", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 429, description = "Daily import quota reached", body = QuotaErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn import_daylio_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Query(query): Query<ImportQuery>,
+    mut multipart: Multipart,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let file = read_file_field(&mut multipart).await?;
+
+    let summary = import_daylio(
+        &state.pool,
+        &state.stats_cache,
+        user_id,
+        &file,
+        query.dry_run.unwrap_or(false),
+    )?;
+    Ok(Json(summary))
+}
+
+/// Handler v2: antrikan import Daylio sebagai job; ringkasan import diunduh lewat `/jobs/{id}/result`
+#[utoipa::path(
+    post,
+    path = "/v2/import/daylio",
+    tag = "import",
+    params(ImportQuery),
+    request_body(content = DaylioImportForm, content_type = "multipart/form-data"),
+    responses(
+        (status = 202, description = "Import queued", body = JobResponse),
+        (status = 400, description = "Missing file", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 429, description = "Daily import quota reached", body = QuotaErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn import_daylio_job_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Query(query): Query<ImportQuery>,
+    mut multipart: Multipart,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let file = read_file_field(&mut multipart).await?;
+
+    let job = enqueue_daylio_import(
+        &state.pool,
+        state.storage.as_ref(),
+        user_id,
+        &file,
+        query.dry_run.unwrap_or(false),
+    )?;
+    Ok(accepted(job))
+}
@@ -0,0 +1,237 @@
+use axum::{
+    extract::{State, Query},
+    response::IntoResponse,
+};
+use crate::middleware::json_body::Json;
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+use crate::{
+    errors::app_error::AppError,
+    middleware::auth_middleware::AuthenticatedUser,
+    middleware::timezone_middleware::UserTimezone,
+    models::insight::TrendQuery,
+    service::insight_service::{get_alerts, get_day_of_week, get_forecast, get_mood_trend, get_places, get_snapshot, get_week_over_week},
+    state::AppState,
+};
+
+#[derive(Deserialize, IntoParams)]
+pub struct DayOfWeekQuery {
+    /// Jumlah hari terakhir yang dianalisis (1-365, default 90)
+    #[param(example = 90)]
+    pub days: Option<i32>,
+}
+
+#[derive(Deserialize, IntoParams)]
+pub struct PlacesQuery {
+    /// Jumlah hari terakhir yang dianalisis (1-365, default 90)
+    #[param(example = 90)]
+    pub days: Option<i32>,
+}
+
+#[derive(Deserialize, IntoParams)]
+pub struct ForecastQuery {
+    /// Sesuaikan prediksi dengan pola per hari dalam minggu (default true)
+    pub seasonal: Option<bool>,
+}
+
+/// Handler untuk alert penurunan mood (hari rendah berturut-turut atau turun tajam dari baseline)
+#[utoipa::path(
+    get,
+    path = "/insights/alerts",
+    tag = "insights",
+    params(
+        ("X-Timezone" = Option<String>, Header, description = "IANA timezone, e.g. Asia/Jakarta; defaults to the user setting")
+    ),
+    responses(
+        (status = 200, description = "OK", body = Vec<MoodAlert>),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_alerts_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    tz: UserTimezone,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let alerts = get_alerts(&state.pool, user_id, tz.tz())?;
+    Ok(Json(alerts))
+}
+
+/// Handler untuk perbandingan mood minggu ini dengan minggu lalu (kartu beranda)
+#[utoipa::path(
+    get,
+    path = "/insights/week-over-week",
+    tag = "insights",
+    params(
+        ("X-Timezone" = Option<String>, Header, description = "IANA timezone, e.g. Asia/Jakarta; defaults to the user setting")
+    ),
+    responses(
+        (status = 200, description = "OK", body = WeekOverWeekInsight),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_week_over_week_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    tz: UserTimezone,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let insight = get_week_over_week(&state.pool, user_id, tz.tz())?;
+    Ok(Json(insight))
+}
+
+/// Handler untuk rata-rata mood per hari dalam minggu
+#[utoipa::path(
+    get,
+    path = "/insights/day-of-week",
+    tag = "insights",
+    params(
+        DayOfWeekQuery,
+        ("X-Timezone" = Option<String>, Header, description = "IANA timezone, e.g. Asia/Jakarta; defaults to the user setting")
+    ),
+    responses(
+        (status = 200, description = "OK", body = DayOfWeekInsight),
+        (status = 400, description = "Invalid request", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_day_of_week_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    tz: UserTimezone,
+    Query(query): Query<DayOfWeekQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let insight = get_day_of_week(&state.pool, user_id, query.days, tz.tz())?;
+    Ok(Json(insight))
+}
+
+/// Handler untuk rata-rata mood per tempat dari lokasi yang dicatat bersama mood
+#[utoipa::path(
+    get,
+    path = "/insights/places",
+    tag = "insights",
+    params(
+        PlacesQuery,
+        ("X-Timezone" = Option<String>, Header, description = "IANA timezone, e.g. Asia/Jakarta; defaults to the user setting")
+    ),
+    responses(
+        (status = 200, description = "OK", body = PlacesInsight),
+        (status = 400, description = "Invalid request", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_places_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    tz: UserTimezone,
+    Query(query): Query<PlacesQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let insight = get_places(&state.pool, user_id, query.days, tz.tz())?;
+    Ok(Json(insight))
+}
+
+/// Handler untuk prediksi skor mood 7 hari ke depan
+#[utoipa::path(
+    get,
+    path = "/insights/forecast",
+    tag = "insights",
+    params(
+        ForecastQuery,
+        ("X-Timezone" = Option<String>, Header, description = "IANA timezone, e.g. Asia/Jakarta; defaults to the user setting")
+    ),
+    responses(
+        (status = 200, description = "OK", body = MoodForecast),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_forecast_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    tz: UserTimezone,
+    Query(query): Query<ForecastQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let forecast = get_forecast(&state.pool, user_id, query.seasonal.unwrap_or(true), tz.tz())?;
+    Ok(Json(forecast))
+}
+
+/// Handler untuk grafik tren skor mood per hari/minggu/bulan/kuartal, opsional dengan rolling average
+#[utoipa::path(
+    get,
+    path = "/insights/trend",
+    tag = "insights",
+    params(
+        TrendQuery,
+        ("X-Timezone" = Option<String>, Header, description = "IANA timezone, e.g. Asia/Jakarta; defaults to the user setting")
+    ),
+    responses(
+        (status = 200, description = "OK", body = MoodTrend),
+        (status = 400, description = "Invalid request", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_trend_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    tz: UserTimezone,
+    Query(query): Query<TrendQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let trend = get_mood_trend(&state.pool, user_id, &query, tz.tz())?;
+    Ok(Json(trend))
+}
+
+/// Handler untuk ringkasan insight dalam satu dokumen; dihitung ulang paling sering sekali per jam
+#[utoipa::path(
+    get,
+    path = "/insights/snapshot",
+    tag = "insights",
+    params(("X-Timezone" = Option<String>, Header, description = "IANA timezone, e.g. Asia/Jakarta; defaults to the user setting")),
+    responses(
+        (status = 200, description = "OK", body = InsightSnapshot)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_snapshot_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    tz: UserTimezone,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let snapshot = get_snapshot(&state.pool, state.moods.as_ref(), &state.stats_cache, user_id, tz.tz())?;
+    Ok(Json(snapshot))
+}
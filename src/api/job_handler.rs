@@ -0,0 +1,81 @@
+use axum::{
+    extract::{Path, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+};
+use crate::middleware::json_body::Json;
+
+use crate::{
+    errors::app_error::AppError,
+    middleware::auth_middleware::AuthenticatedUser,
+    models::job::JobResponse,
+    service::job_service::{get_job, get_job_result},
+    state::AppState,
+};
+
+/// Respons 202 untuk job yang baru diantrikan, dengan `Location` menunjuk ke status job
+pub fn accepted(job: JobResponse) -> Response {
+    let location = format!("/api/jobs/{}", job.id);
+    (StatusCode::ACCEPTED, [(header::LOCATION, location)], Json(job)).into_response()
+}
+
+/// Handler untuk status job latar belakang milik pengguna
+#[utoipa::path(
+    get,
+    path = "/jobs/{id}",
+    tag = "jobs",
+    params(("id" = i32, Path, description = "Job id")),
+    responses(
+        (status = 200, description = "OK", body = JobResponse),
+        (status = 404, description = "Job not found", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_job_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(job_id): Path<i32>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let job = get_job(&state.pool, user_id, job_id)?;
+    Ok(Json(job))
+}
+
+/// Handler untuk mengunduh hasil job yang sudah selesai (ZIP, PDF, atau ringkasan import JSON)
+#[utoipa::path(
+    get,
+    path = "/jobs/{id}/result",
+    tag = "jobs",
+    params(("id" = i32, Path, description = "Job id")),
+    responses(
+        (status = 200, description = "Job result; content type depends on the job kind", body = Vec<u8>),
+        (status = 404, description = "Job or result not found", body = ErrorResponse),
+        (status = 409, description = "Job has not finished yet", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_job_result_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(job_id): Path<i32>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let (content_type, filename, data) = get_job_result(&state.pool, state.storage.as_ref(), user_id, job_id)?;
+
+    let mut response = ([(header::CONTENT_TYPE, content_type)], data).into_response();
+    if let Some(filename) = filename {
+        let disposition = format!("attachment; filename=\"{}\"", filename);
+        if let Ok(value) = disposition.parse() {
+            response.headers_mut().insert(header::CONTENT_DISPOSITION, value);
+        }
+    }
+    Ok(response)
+}
@@ -1,53 +1,96 @@
 use axum::{
-    extract::{State, Json, Path, Query},
+    extract::{State, Path, Query},
     response::IntoResponse,
 };
-use diesel::{r2d2, PgConnection};
+use crate::middleware::json_body::Json;
 use serde::Deserialize;
+use utoipa::IntoParams;
 use chrono::NaiveDate;
 
 use crate::{
     errors::app_error::AppError,
-    middleware::auth_middleware::AuthenticatedUser,
-    models::journal::{CreateJournalRequest, UpdateJournalRequest},
+    models::list_sort::ListSort,
+    i18n::t,
+    middleware::auth_middleware::{AuthenticatedUser, ExportScope},
+    middleware::timezone_middleware::UserTimezone,
+    utils::api_dates,
+    models::journal::{includes_content, CreateJournalRequest, JournalFields, JournalListItem, JournalResponse, SaveJournalDraftRequest, UpdateJournalRequest},
     service::journal_service::{
-        create_journal, get_journal_by_id, get_user_journals, get_journal_by_date,
+        create_journal, get_journal_by_id, get_user_journals, set_journal_pinned, get_journal_by_date,
         get_journals_by_date_range, update_journal, delete_journal, get_recent_journals,
-        get_journal_stats_count, get_all_user_journals, search_journals
+        get_journal_stats_count, get_journal_streak, get_all_user_journals, get_user_journal_fields, search_journal_fields, search_journals,
+        get_journal_draft, save_journal_draft, delete_journal_draft
     },
+    state::AppState,
+    utils::event_bus::AppEvent,
 };
 
 // Type alias agar lebih singkat
-type DbPool = r2d2::Pool<diesel::r2d2::ConnectionManager<PgConnection>>;
-
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
 pub struct PaginationQuery {
     pub limit: Option<i32>,
     pub offset: Option<i32>,
+    /// Hanya entri yang disematkan (true) atau yang tidak (false)
+    pub pinned: Option<bool>,
+    /// Urutan hasil: `date_asc`, `date_desc` (default), `updated_desc` atau `title`
+    pub sort: Option<String>,
+    /// Hanya field ini, dipisah koma: `id`, `title`, `content`, `is_pinned`, `created_at`, `updated_at`, `word_count`.
+    /// Tanpa `content`, isi jurnal tidak dimuat sehingga respons jauh lebih kecil.
+    pub fields: Option<String>,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
 pub struct DateRangeQuery {
-    pub start_date: String, // Changed from NaiveDate to String for MM-DD-YYYY parsing
-    pub end_date: String,   // Changed from NaiveDate to String for MM-DD-YYYY parsing
+    /// Start date (YYYY-MM-DD)
+    #[serde(with = "api_dates")]
+    #[param(value_type = String, format = Date, example = "2025-07-01")]
+    pub start_date: NaiveDate,
+    /// End date (YYYY-MM-DD)
+    #[serde(with = "api_dates")]
+    #[param(value_type = String, format = Date, example = "2025-07-31")]
+    pub end_date: NaiveDate,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
 pub struct RecentQuery {
     pub days: Option<i32>,
 }
 
-#[derive(Deserialize)]
+/// Parameter `include` pada list jurnal v2
+#[derive(Deserialize, IntoParams)]
+pub struct IncludeQuery {
+    /// `content` untuk menyertakan isi lengkap selain `preview`
+    pub include: Option<String>,
+}
+
+#[derive(Deserialize, IntoParams)]
 pub struct SearchQuery {
     pub query: String,
     pub limit: Option<i32>,
     pub offset: Option<i32>,
+    /// Hanya field ini, dipisah koma: `id`, `title`, `content`, `is_pinned`, `created_at`, `updated_at`, `word_count`.
+    /// Tanpa `content`, isi jurnal tidak dimuat sehingga respons jauh lebih kecil.
+    pub fields: Option<String>,
 }
 
 /// Handler untuk membuat journal baru
+#[utoipa::path(
+    post,
+    path = "/journals",
+    tag = "journals",
+    params(("X-Timezone" = Option<String>, Header, description = "IANA timezone, e.g. Asia/Jakarta; defaults to the user setting")),
+    request_body = CreateJournalRequest,
+    responses(
+        (status = 200, description = "OK", body = JournalResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn create_journal_handler(
-    State(pool): State<DbPool>,
+    State(state): State<AppState>,
     user: AuthenticatedUser,
+    tz: UserTimezone,
     Json(data): Json<CreateJournalRequest>,
 ) -> Result<impl IntoResponse, AppError> {
     let user_id: i32 = user
@@ -56,19 +99,34 @@ pub async fn create_journal_handler(
         .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
 
     let journal_response = create_journal(
-        &pool,
+        state.journals.as_ref(),
+        &state.stats_cache,
         user_id,
         &data.title,
         &data.content,
         data.created_at,
+        tz.tz(),
     )?;
 
+    state.event_bus.publish(AppEvent::JournalCreated { user_id, journal_id: journal_response.id });
+
     Ok(Json(journal_response))
 }
 
 /// Handler untuk mengambil journal berdasarkan ID
+#[utoipa::path(
+    get,
+    path = "/journals/{id}",
+    tag = "journals",
+    params(("id" = i32, Path, description = "Journal id")),
+    responses(
+        (status = 200, description = "OK", body = JournalResponse),
+        (status = 404, description = "Not found", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn get_journal_by_id_handler(
-    State(pool): State<DbPool>,
+    State(state): State<AppState>,
     user: AuthenticatedUser,
     Path(journal_id): Path<i32>,
 ) -> Result<impl IntoResponse, AppError> {
@@ -77,13 +135,23 @@ pub async fn get_journal_by_id_handler(
         .parse()
         .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
 
-    let journal_response = get_journal_by_id(&pool, journal_id, user_id)?;
+    let journal_response = get_journal_by_id(state.journals.as_ref(), journal_id, user_id)?;
     Ok(Json(journal_response))
 }
 
 /// Handler untuk mengambil semua journal user dengan pagination
+#[utoipa::path(
+    get,
+    path = "/journals",
+    tag = "journals",
+    params(PaginationQuery),
+    responses(
+        (status = 200, description = "OK", body = Vec<JournalResponse>)
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn get_user_journals_handler(
-    State(pool): State<DbPool>,
+    State(state): State<AppState>,
     user: AuthenticatedUser,
     Query(pagination): Query<PaginationQuery>,
 ) -> Result<impl IntoResponse, AppError> {
@@ -92,14 +160,37 @@ pub async fn get_user_journals_handler(
         .parse()
         .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
 
-    let journals = get_user_journals(&pool, user_id, pagination.limit, pagination.offset)?;
-    Ok(Json(journals))
+    let sort = ListSort::parse(pagination.sort.as_deref(), &ListSort::JOURNALS)?;
+    let fields = JournalFields::parse(pagination.fields.as_deref()).map_err(AppError::BadRequest)?;
+    if let Some(fields) = fields {
+        let journals = get_user_journal_fields(state.journals.as_ref(), user_id, pagination.limit, pagination.offset, pagination.pinned, sort, &fields)?;
+        return Ok(Json(journals).into_response());
+    }
+
+    let journals = get_user_journals(state.journals.as_ref(), user_id, pagination.limit, pagination.offset, pagination.pinned, sort)?;
+    Ok(Json(journals).into_response())
 }
 
 /// Handler untuk mengambil journal berdasarkan tanggal
+#[utoipa::path(
+    get,
+    path = "/journals/date/{date}",
+    tag = "journals",
+    params(
+        ("date" = String, Path, description = "Journal date (YYYY-MM-DD)"),
+        ("X-Timezone" = Option<String>, Header, description = "IANA timezone, e.g. Asia/Jakarta; defaults to the user setting")
+    ),
+    responses(
+        (status = 200, description = "OK", body = JournalResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 404, description = "Not found", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn get_journal_by_date_handler(
-    State(pool): State<DbPool>,
+    State(state): State<AppState>,
     user: AuthenticatedUser,
+    tz: UserTimezone,
     Path(date): Path<String>,
 ) -> Result<impl IntoResponse, AppError> {
     let user_id: i32 = user
@@ -107,17 +198,31 @@ pub async fn get_journal_by_date_handler(
         .parse()
         .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
 
-    let parsed_date = NaiveDate::parse_from_str(&date, "%m-%d-%Y")
-        .map_err(|_| AppError::BadRequest("Invalid date format. Use MM-DD-YYYY".to_string()))?;
+    let parsed_date = api_dates::parse_date(&date)?;
 
-    let journal_response = get_journal_by_date(&pool, user_id, parsed_date)?;
+    let journal_response = get_journal_by_date(state.journals.as_ref(), user_id, parsed_date, tz.tz())?;
     Ok(Json(journal_response))
 }
 
 /// Handler untuk mengambil journal dalam rentang tanggal
+#[utoipa::path(
+    get,
+    path = "/journals/range",
+    tag = "journals",
+    params(
+        DateRangeQuery,
+        ("X-Timezone" = Option<String>, Header, description = "IANA timezone, e.g. Asia/Jakarta; defaults to the user setting")
+    ),
+    responses(
+        (status = 200, description = "OK", body = Vec<JournalResponse>),
+        (status = 400, description = "Invalid request", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn get_journals_by_date_range_handler(
-    State(pool): State<DbPool>,
+    State(state): State<AppState>,
     user: AuthenticatedUser,
+    tz: UserTimezone,
     Query(range): Query<DateRangeQuery>,
 ) -> Result<impl IntoResponse, AppError> {
     let user_id: i32 = user
@@ -125,21 +230,31 @@ pub async fn get_journals_by_date_range_handler(
         .parse()
         .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
 
-    // Parse dates from MM-DD-YYYY format
-    let start_date = NaiveDate::parse_from_str(&range.start_date, "%m-%d-%Y")
-        .map_err(|_| AppError::BadRequest("Invalid start_date format. Use MM-DD-YYYY".to_string()))?;
-    
-    let end_date = NaiveDate::parse_from_str(&range.end_date, "%m-%d-%Y")
-        .map_err(|_| AppError::BadRequest("Invalid end_date format. Use MM-DD-YYYY".to_string()))?;
-
-    let journals = get_journals_by_date_range(&pool, user_id, start_date, end_date)?;
+    let journals = get_journals_by_date_range(state.journals.as_ref(), user_id, range.start_date, range.end_date, tz.tz())?;
     Ok(Json(journals))
 }
 
 /// Handler untuk mengupdate journal
+#[utoipa::path(
+    put,
+    path = "/journals/{id}",
+    tag = "journals",
+    params(
+        ("id" = i32, Path, description = "Journal id"),
+        ("X-Timezone" = Option<String>, Header, description = "IANA timezone, e.g. Asia/Jakarta; defaults to the user setting")
+    ),
+    request_body = UpdateJournalRequest,
+    responses(
+        (status = 200, description = "OK", body = JournalResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 404, description = "Not found", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn update_journal_handler(
-    State(pool): State<DbPool>,
+    State(state): State<AppState>,
     user: AuthenticatedUser,
+    tz: UserTimezone,
     Path(journal_id): Path<i32>,
     Json(data): Json<UpdateJournalRequest>,
 ) -> Result<impl IntoResponse, AppError> {
@@ -149,19 +264,30 @@ pub async fn update_journal_handler(
         .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
 
     let updated_journal = update_journal(
-        &pool, 
-        journal_id, 
-        user_id, 
-        data.title, 
-        data.content,
-        data.created_at 
+        state.journals.as_ref(),
+        &state.stats_cache,
+        journal_id,
+        user_id,
+        data,
+        tz.tz(),
     )?;
     Ok(Json(updated_journal))
 }
 
 /// Handler untuk menghapus journal
+#[utoipa::path(
+    delete,
+    path = "/journals/{id}",
+    tag = "journals",
+    params(("id" = i32, Path, description = "Journal id")),
+    responses(
+        (status = 200, description = "Journal deleted"),
+        (status = 404, description = "Not found", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn delete_journal_handler(
-    State(pool): State<DbPool>,
+    State(state): State<AppState>,
     user: AuthenticatedUser,
     Path(journal_id): Path<i32>,
 ) -> Result<impl IntoResponse, AppError> {
@@ -170,14 +296,29 @@ pub async fn delete_journal_handler(
         .parse()
         .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
 
-    delete_journal(&pool, journal_id, user_id)?;
-    Ok(Json("Journal deleted successfully"))
+    delete_journal(state.journals.as_ref(), &state.stats_cache, journal_id, user_id)?;
+    Ok(Json(t("message.journal_deleted")))
 }
 
 /// Handler untuk mengambil journal terbaru
+#[utoipa::path(
+    get,
+    path = "/journals/recent",
+    tag = "journals",
+    params(
+        RecentQuery,
+        ("X-Timezone" = Option<String>, Header, description = "IANA timezone, e.g. Asia/Jakarta; defaults to the user setting")
+    ),
+    responses(
+        (status = 200, description = "OK", body = Vec<JournalResponse>),
+        (status = 400, description = "Invalid request", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn get_recent_journals_handler(
-    State(pool): State<DbPool>,
+    State(state): State<AppState>,
     user: AuthenticatedUser,
+    tz: UserTimezone,
     Query(query): Query<RecentQuery>,
 ) -> Result<impl IntoResponse, AppError> {
     let user_id: i32 = user
@@ -185,13 +326,22 @@ pub async fn get_recent_journals_handler(
         .parse()
         .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
 
-    let journals = get_recent_journals(&pool, user_id, query.days)?;
+    let journals = get_recent_journals(state.journals.as_ref(), user_id, query.days, tz.tz())?;
     Ok(Json(journals))
 }
 
 /// Handler untuk mendapatkan statistik journal sederhana
+#[utoipa::path(
+    get,
+    path = "/journals/stats",
+    tag = "journals",
+    responses(
+        (status = 200, description = "OK", body = Object)
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn get_journal_stats_handler(
-    State(pool): State<DbPool>,
+    State(state): State<AppState>,
     user: AuthenticatedUser,
 ) -> Result<impl IntoResponse, AppError> {
     let user_id: i32 = user
@@ -199,29 +349,77 @@ pub async fn get_journal_stats_handler(
         .parse()
         .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
 
-    let count = get_journal_stats_count(&pool, user_id)?;
+    let count = get_journal_stats_count(state.journals.as_ref(), &state.stats_cache, user_id)?;
     Ok(Json(serde_json::json!({
         "total_entries": count
     })))
 }
 
+#[utoipa::path(
+    get,
+    path = "/journals/streak",
+    tag = "journals",
+    params(("X-Timezone" = Option<String>, Header, description = "IANA timezone, e.g. Asia/Jakarta; defaults to the user setting")),
+    responses(
+        (status = 200, description = "OK", body = Object)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_journal_streak_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    tz: UserTimezone,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let stats = get_journal_streak(state.journals.as_ref(), user_id, tz.tz())?;
+    Ok(Json(serde_json::json!({
+        "streak": stats.current_length(),
+        "longest_streak": stats.longest_length()
+    })))
+}
+
 /// Handler untuk mendapatkan SEMUA journal user tanpa pagination
+#[utoipa::path(
+    get,
+    path = "/journals/all",
+    tag = "journals",
+    responses(
+        (status = 200, description = "OK", body = Vec<JournalResponse>),
+        (status = 403, description = "Token lacks the export:read scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn get_all_journals_handler(
-    State(pool): State<DbPool>,
-    user: AuthenticatedUser,
+    State(state): State<AppState>,
+    user: AuthenticatedUser<ExportScope>,
 ) -> Result<impl IntoResponse, AppError> {
     let user_id: i32 = user
         .user_id()
         .parse()
         .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
 
-    let journals = get_all_user_journals(&pool, user_id)?;
-    Ok(Json(journals))
+    let journals = get_all_user_journals(state.journals.clone(), user_id)?;
+    Ok(journals)
 }
 
 /// Handler untuk mencari journal berdasarkan title atau content
+#[utoipa::path(
+    get,
+    path = "/journals/search",
+    tag = "journals",
+    params(SearchQuery),
+    responses(
+        (status = 200, description = "OK", body = Vec<JournalResponse>),
+        (status = 400, description = "Invalid request", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn search_journals_handler(
-    State(pool): State<DbPool>,
+    State(state): State<AppState>,
     user: AuthenticatedUser,
     Query(search): Query<SearchQuery>,
 ) -> Result<impl IntoResponse, AppError> {
@@ -230,6 +428,284 @@ pub async fn search_journals_handler(
         .parse()
         .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
 
-    let journals = search_journals(&pool, user_id, &search.query, search.limit, search.offset)?;
-    Ok(Json(journals))
-}
\ No newline at end of file
+    let fields = JournalFields::parse(search.fields.as_deref()).map_err(AppError::BadRequest)?;
+    if let Some(fields) = fields {
+        let journals = search_journal_fields(&state.pool, user_id, &search.query, search.limit, search.offset, &fields)?;
+        return Ok(Json(journals).into_response());
+    }
+
+    let journals = search_journals(&state.pool, user_id, &search.query, search.limit, search.offset)?;
+    Ok(Json(journals).into_response())
+}
+
+/// Handler untuk mengambil draft jurnal yang sedang ditulis
+#[utoipa::path(
+    get,
+    path = "/journals/draft",
+    tag = "journals",
+    responses(
+        (status = 200, description = "OK", body = JournalDraftResponse),
+        (status = 404, description = "No draft saved", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_journal_draft_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let draft = get_journal_draft(&state.pool, user_id)?;
+    Ok(Json(draft))
+}
+
+/// Handler untuk autosave draft jurnal; menimpa draft sebelumnya
+#[utoipa::path(
+    put,
+    path = "/journals/draft",
+    tag = "journals",
+    request_body = SaveJournalDraftRequest,
+    responses(
+        (status = 200, description = "OK", body = JournalDraftResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn save_journal_draft_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Json(data): Json<SaveJournalDraftRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let draft = save_journal_draft(&state.pool, user_id, data)?;
+    Ok(Json(draft))
+}
+
+/// Handler untuk membuang draft jurnal, misalnya setelah jurnal dipublikasikan
+#[utoipa::path(
+    delete,
+    path = "/journals/draft",
+    tag = "journals",
+    responses(
+        (status = 200, description = "Draft deleted"),
+        (status = 404, description = "No draft saved", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn delete_journal_draft_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    delete_journal_draft(&state.pool, user_id)?;
+    Ok(Json(t("message.journal_draft_deleted")))
+}
+
+/// Handler untuk menyematkan jurnal penting
+#[utoipa::path(
+    post,
+    path = "/journals/{id}/pin",
+    tag = "journals",
+    params(("id" = i32, Path, description = "Journal id")),
+    responses(
+        (status = 200, description = "OK", body = JournalResponse),
+        (status = 404, description = "Not found", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn pin_journal_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(journal_id): Path<i32>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let journal_response = set_journal_pinned(state.journals.as_ref(), journal_id, user_id, true)?;
+    Ok(Json(journal_response))
+}
+
+/// Handler untuk melepas sematan jurnal
+#[utoipa::path(
+    delete,
+    path = "/journals/{id}/pin",
+    tag = "journals",
+    params(("id" = i32, Path, description = "Journal id")),
+    responses(
+        (status = 200, description = "OK", body = JournalResponse),
+        (status = 404, description = "Not found", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn unpin_journal_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(journal_id): Path<i32>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let journal_response = set_journal_pinned(state.journals.as_ref(), journal_id, user_id, false)?;
+    Ok(Json(journal_response))
+}
+
+fn include_content(include: &IncludeQuery) -> Result<bool, AppError> {
+    includes_content(include.include.as_deref()).map_err(AppError::BadRequest)
+}
+
+fn list_items(journals: Vec<JournalResponse>, include_content: bool) -> Vec<JournalListItem> {
+    journals
+        .into_iter()
+        .map(|journal| JournalListItem::from_response(journal, include_content))
+        .collect()
+}
+
+/// Handler v2: list journal dengan `preview` sebagai pengganti isi lengkap
+#[utoipa::path(
+    get,
+    path = "/v2/journals",
+    tag = "journals",
+    params(PaginationQuery, IncludeQuery),
+    responses(
+        (status = 200, description = "OK; with `fields`, only the requested fields", body = Vec<JournalListItem>),
+        (status = 400, description = "Invalid request", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_user_journal_previews_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Query(pagination): Query<PaginationQuery>,
+    Query(include): Query<IncludeQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let include_content = include_content(&include)?;
+    let sort = ListSort::parse(pagination.sort.as_deref(), &ListSort::JOURNALS)?;
+    let fields = JournalFields::parse(pagination.fields.as_deref()).map_err(AppError::BadRequest)?;
+    if let Some(fields) = fields {
+        let journals = get_user_journal_fields(state.journals.as_ref(), user_id, pagination.limit, pagination.offset, pagination.pinned, sort, &fields)?;
+        return Ok(Json(journals).into_response());
+    }
+
+    let journals = get_user_journals(state.journals.as_ref(), user_id, pagination.limit, pagination.offset, pagination.pinned, sort)?;
+    Ok(Json(list_items(journals, include_content)).into_response())
+}
+
+/// Handler v2: pencarian journal dengan `preview` sebagai pengganti isi lengkap
+#[utoipa::path(
+    get,
+    path = "/v2/journals/search",
+    tag = "journals",
+    params(SearchQuery, IncludeQuery),
+    responses(
+        (status = 200, description = "OK; with `fields`, only the requested fields", body = Vec<JournalListItem>),
+        (status = 400, description = "Invalid request", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn search_journal_previews_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Query(search): Query<SearchQuery>,
+    Query(include): Query<IncludeQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let include_content = include_content(&include)?;
+    let fields = JournalFields::parse(search.fields.as_deref()).map_err(AppError::BadRequest)?;
+    if let Some(fields) = fields {
+        let journals = search_journal_fields(&state.pool, user_id, &search.query, search.limit, search.offset, &fields)?;
+        return Ok(Json(journals).into_response());
+    }
+
+    let journals = search_journals(&state.pool, user_id, &search.query, search.limit, search.offset)?;
+    Ok(Json(list_items(journals, include_content)).into_response())
+}
+
+/// Handler v2: journal dalam rentang tanggal dengan `preview` sebagai pengganti isi lengkap
+#[utoipa::path(
+    get,
+    path = "/v2/journals/range",
+    tag = "journals",
+    params(
+        DateRangeQuery,
+        IncludeQuery,
+        ("X-Timezone" = Option<String>, Header, description = "IANA timezone, e.g. Asia/Jakarta; defaults to the user setting")
+    ),
+    responses(
+        (status = 200, description = "OK", body = Vec<JournalListItem>),
+        (status = 400, description = "Invalid request", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_journal_previews_by_date_range_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    tz: UserTimezone,
+    Query(range): Query<DateRangeQuery>,
+    Query(include): Query<IncludeQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let include_content = include_content(&include)?;
+    let journals = get_journals_by_date_range(state.journals.as_ref(), user_id, range.start_date, range.end_date, tz.tz())?;
+    Ok(Json(list_items(journals, include_content)))
+}
+
+/// Handler v2: journal terbaru dengan `preview` sebagai pengganti isi lengkap
+#[utoipa::path(
+    get,
+    path = "/v2/journals/recent",
+    tag = "journals",
+    params(
+        RecentQuery,
+        IncludeQuery,
+        ("X-Timezone" = Option<String>, Header, description = "IANA timezone, e.g. Asia/Jakarta; defaults to the user setting")
+    ),
+    responses(
+        (status = 200, description = "OK", body = Vec<JournalListItem>),
+        (status = 400, description = "Invalid request", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_recent_journal_previews_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    tz: UserTimezone,
+    Query(query): Query<RecentQuery>,
+    Query(include): Query<IncludeQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let include_content = include_content(&include)?;
+    let journals = get_recent_journals(state.journals.as_ref(), user_id, query.days, tz.tz())?;
+    Ok(Json(list_items(journals, include_content)))
+}
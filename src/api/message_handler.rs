@@ -0,0 +1,194 @@
+use std::convert::Infallible;
+use axum::{
+    extract::{Path, Query, State},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse,
+    },
+};
+use crate::middleware::json_body::Json;
+use futures_util::Stream;
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::{
+    errors::app_error::AppError,
+    middleware::auth_middleware::AuthenticatedUser,
+    models::message::{MessageListQuery, SendMessageRequest},
+    service::message_service::{
+        find_caller_psychologist_id, get_conversations, get_messages, is_recipient, mark_read, send_message,
+    },
+    state::AppState,
+    utils::event_bus::AppEvent,
+};
+
+/// Handler untuk daftar percakapan pemanggil beserta jumlah pesan belum dibaca
+#[utoipa::path(
+    get,
+    path = "/conversations",
+    tag = "conversations",
+    responses(
+        (status = 200, description = "OK", body = Vec<ConversationResponse>)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_conversations_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> Result<impl IntoResponse, AppError> {
+    let caller_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let conversations = get_conversations(&state.pool, caller_id)?;
+    Ok(Json(conversations))
+}
+
+/// Handler untuk riwayat pesan, terbaru lebih dulu; gunakan `before_id` untuk halaman berikutnya
+#[utoipa::path(
+    get,
+    path = "/conversations/{psychologist_id}/{user_id}/messages",
+    tag = "conversations",
+    params(
+        ("psychologist_id" = i32, Path, description = "Psychologist id"),
+        ("user_id" = i32, Path, description = "Client user id"),
+        MessageListQuery
+    ),
+    responses(
+        (status = 200, description = "OK", body = Vec<MessageResponse>),
+        (status = 400, description = "Invalid limit", body = ErrorResponse),
+        (status = 404, description = "Conversation not found", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_messages_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path((psychologist_id, user_id)): Path<(i32, i32)>,
+    Query(query): Query<MessageListQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let caller_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let messages = get_messages(&state.pool, caller_id, user_id, psychologist_id, &query)?;
+    Ok(Json(messages))
+}
+
+/// Handler untuk mengirim pesan ke lawan bicara
+#[utoipa::path(
+    post,
+    path = "/conversations/{psychologist_id}/{user_id}/messages",
+    tag = "conversations",
+    params(
+        ("psychologist_id" = i32, Path, description = "Psychologist id"),
+        ("user_id" = i32, Path, description = "Client user id")
+    ),
+    request_body = SendMessageRequest,
+    responses(
+        (status = 200, description = "Message sent", body = MessageResponse),
+        (status = 400, description = "Empty message", body = ErrorResponse),
+        (status = 404, description = "Conversation not found", body = ErrorResponse),
+        (status = 413, description = "Message too long", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn send_message_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path((psychologist_id, user_id)): Path<(i32, i32)>,
+    Json(data): Json<SendMessageRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let caller_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let verdict = state.moderation.review(&data.body).await;
+    let message = send_message(&state.pool, &state.event_bus, caller_id, user_id, psychologist_id, data, &verdict)?;
+    Ok(Json(message))
+}
+
+/// Handler untuk menandai dibaca semua pesan dari lawan bicara
+#[utoipa::path(
+    post,
+    path = "/conversations/{psychologist_id}/{user_id}/read",
+    tag = "conversations",
+    params(
+        ("psychologist_id" = i32, Path, description = "Psychologist id"),
+        ("user_id" = i32, Path, description = "Client user id")
+    ),
+    responses(
+        (status = 200, description = "Messages marked as read", body = MarkReadResponse),
+        (status = 404, description = "Conversation not found", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn mark_read_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path((psychologist_id, user_id)): Path<(i32, i32)>,
+) -> Result<impl IntoResponse, AppError> {
+    let caller_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let result = mark_read(&state.pool, &state.event_bus, caller_id, user_id, psychologist_id)?;
+    Ok(Json(result))
+}
+
+/// Handler untuk stream Server-Sent Events percakapan pemanggil: event `message` berisi
+/// pesan baru, event `read` dikirim saat lawan bicara membaca pesan
+#[utoipa::path(
+    get,
+    path = "/conversations/events",
+    tag = "conversations",
+    responses(
+        (status = 200, description = "Event stream", content_type = "text/event-stream", body = String)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn conversation_events_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, AppError> {
+    let caller_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+    let caller_psychologist_id = find_caller_psychologist_id(&state.pool, caller_id)?;
+
+    let receiver = state.event_bus.subscribe();
+    let stream = futures_util::stream::unfold(receiver, move |mut receiver| async move {
+        loop {
+            let event = match receiver.recv().await {
+                Ok(AppEvent::MessageCreated { message })
+                    if is_recipient(caller_id, caller_psychologist_id, message.user_id, message.psychologist_id) =>
+                {
+                    Event::default().event("message").json_data(&message)
+                }
+                Ok(AppEvent::MessagesRead { user_id, psychologist_id, from_psychologist })
+                    if is_recipient(caller_id, caller_psychologist_id, user_id, psychologist_id) =>
+                {
+                    Event::default().event("read").json_data(serde_json::json!({
+                        "user_id": user_id,
+                        "psychologist_id": psychologist_id,
+                        "from_psychologist": from_psychologist,
+                    }))
+                }
+                Ok(_) => continue,
+                // Event yang terlewat tidak dikirim ulang; klien memuat ulang lewat endpoint pesan
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => return None,
+            };
+            match event {
+                Ok(event) => return Some((Ok(event), receiver)),
+                Err(e) => eprintln!("❌ Failed to encode conversation event: {}", e),
+            }
+        }
+    });
+
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+}
@@ -1,4 +1,25 @@
 pub mod auth_handler;
 pub mod user_handler;
 pub mod mood_handler;
-pub mod journal_handler;
\ No newline at end of file
+pub mod journal_handler;
+pub mod docs_handler;
+pub mod report_handler;
+pub mod calendar_handler;
+pub mod admin_handler;
+pub mod security_handler;
+pub mod insight_handler;
+pub mod device_handler;
+pub mod export_handler;
+pub mod import_handler;
+pub mod organization_handler;
+pub mod dev_handler;
+pub mod help_handler;
+pub mod psychologist_handler;
+pub mod appointment_handler;
+pub mod message_handler;
+pub mod community_handler;
+pub mod checkin_handler;
+pub mod webhook_handler;
+pub mod health_handler;
+pub mod job_handler;
+pub mod status_handler;
\ No newline at end of file
@@ -1,68 +1,93 @@
 use axum::{
-    extract::{State, Json, Path, Query},
+    extract::{State, Path, Query},
     response::IntoResponse,
 };
-use diesel::{r2d2, PgConnection};
+use crate::middleware::json_body::Json;
 use serde::Deserialize;
+use utoipa::IntoParams;
 use chrono::NaiveDate;
 
 use crate::{
     errors::app_error::AppError,
-    middleware::auth_middleware::AuthenticatedUser,
-    models::mood::{CreateMoodRequest, UpdateMoodRequest},
+    models::list_sort::ListSort,
+    i18n::t,
+    middleware::auth_middleware::{AuthenticatedUser, ExportScope},
+    middleware::timezone_middleware::UserTimezone,
+    utils::api_dates,
+    models::mood::{CreateMoodRequest, MoodSearchFilter, MoodType, UpdateMoodRequest},
     service::mood_service::{
-        create_mood, get_mood_by_id, get_user_moods, get_mood_by_date,
-        get_moods_by_date_range, update_mood_with_date, delete_mood, get_recent_moods, // ✅ Fixed import
-        get_mood_stats_count, get_mood_streak,
+        create_mood, get_mood_by_id, get_user_moods, set_mood_pinned, get_mood_by_date,
+        get_moods_by_date_range, search_moods, update_mood_with_date, delete_mood, restore_mood, get_recent_moods, // ✅ Fixed import
+        get_mood_stats_count, get_mood_stats_for_range, get_mood_streak,
         get_all_user_moods, get_mood_stats_with_scores
     },
+    state::AppState,
+    utils::event_bus::AppEvent,
 };
 
-type DbPool = r2d2::Pool<diesel::r2d2::ConnectionManager<PgConnection>>;
-
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
 pub struct PaginationQuery {
     pub limit: Option<i32>,
     pub offset: Option<i32>,
+    /// Hanya entri yang disematkan (true) atau yang tidak (false)
+    pub pinned: Option<bool>,
+    /// Urutan hasil: `date_asc`, `date_desc` (default) atau `updated_desc`
+    pub sort: Option<String>,
 }
 
-#[derive(Deserialize)]
-#[serde(try_from = "DateRangeQueryRaw")]
+#[derive(Deserialize, IntoParams)]
 pub struct DateRangeQuery {
+    /// Start date (YYYY-MM-DD)
+    #[serde(with = "api_dates")]
+    #[param(value_type = String, format = Date, example = "2025-07-01")]
     pub start_date: NaiveDate,
+    /// End date (YYYY-MM-DD)
+    #[serde(with = "api_dates")]
+    #[param(value_type = String, format = Date, example = "2025-07-31")]
     pub end_date: NaiveDate,
 }
 
-#[derive(Deserialize)]
-struct DateRangeQueryRaw {
-    pub start_date: String,
-    pub end_date: String,
-}
-
-impl TryFrom<DateRangeQueryRaw> for DateRangeQuery {
-    type Error = AppError;
-
-    fn try_from(raw: DateRangeQueryRaw) -> Result<Self, Self::Error> {
-        let start_date = NaiveDate::parse_from_str(&raw.start_date, "%m-%d-%Y")
-            .map_err(|_| AppError::BadRequest("Invalid start_date format. Use MM-DD-YYYY".to_string()))?;
-        let end_date = NaiveDate::parse_from_str(&raw.end_date, "%m-%d-%Y")
-            .map_err(|_| AppError::BadRequest("Invalid end_date format. Use MM-DD-YYYY".to_string()))?;
-        
-        Ok(DateRangeQuery {
-            start_date,
-            end_date,
-        })
-    }
+#[derive(Deserialize, IntoParams)]
+pub struct MoodSearchQuery {
+    /// Teks yang dicari di catatan mood
+    pub query: String,
+    /// Hanya jenis mood ini, misalnya `happy` atau `very sad`
+    pub mood: Option<String>,
+    /// Start date (YYYY-MM-DD), inklusif
+    #[serde(default, with = "api_dates::option")]
+    #[param(value_type = Option<String>, format = Date, example = "2025-07-01")]
+    pub start_date: Option<NaiveDate>,
+    /// End date (YYYY-MM-DD), inklusif
+    #[serde(default, with = "api_dates::option")]
+    #[param(value_type = Option<String>, format = Date, example = "2025-07-31")]
+    pub end_date: Option<NaiveDate>,
+    pub limit: Option<i32>,
+    pub offset: Option<i32>,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
 pub struct RecentQuery {
     pub days: Option<i32>,
 }
 
+#[utoipa::path(
+    post,
+    path = "/moods",
+    tag = "moods",
+    params(("X-Timezone" = Option<String>, Header, description = "IANA timezone, e.g. Asia/Jakarta; defaults to the user setting")),
+    request_body = CreateMoodRequest,
+    responses(
+        (status = 200, description = "OK", body = MoodResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 409, description = "Mood already exists for this date", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn create_mood_handler(
-    State(pool): State<DbPool>,
+    State(state): State<AppState>,
     user: AuthenticatedUser,
+    tz: UserTimezone,
     Json(data): Json<CreateMoodRequest>,
 ) -> Result<impl IntoResponse, AppError> {
     let user_id: i32 = user
@@ -70,27 +95,32 @@ pub async fn create_mood_handler(
         .parse()
         .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
 
-    let mood_date = if let Some(date_str) = &data.date { // ✅ Fixed borrowing
-        NaiveDate::parse_from_str(date_str, "%Y-%m-%d")
-            .map_err(|_| AppError::BadRequest("Invalid date format. Use YYYY-MM-DD".to_string()))?
-    } else {
-        chrono::Utc::now().date_naive()
-    };
-
     let mood_response = create_mood(
-        &pool,
+        state.moods.as_ref(),
+        &state.stats_cache,
         user_id,
-        &data.mood,
-        &data.emoji,
-        data.notes,
-        Some(mood_date), 
+        data,
+        tz.tz(),
     )?;
 
+    state.event_bus.publish(AppEvent::MoodCreated { user_id, mood_id: mood_response.id });
+
     Ok(Json(mood_response))
 }
 
+#[utoipa::path(
+    get,
+    path = "/moods/{id}",
+    tag = "moods",
+    params(("id" = i32, Path, description = "Mood id")),
+    responses(
+        (status = 200, description = "OK", body = MoodResponse),
+        (status = 404, description = "Not found", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn get_mood_by_id_handler(
-    State(pool): State<DbPool>,
+    State(state): State<AppState>,
     user: AuthenticatedUser,
     Path(mood_id): Path<i32>,
 ) -> Result<impl IntoResponse, AppError> {
@@ -99,12 +129,22 @@ pub async fn get_mood_by_id_handler(
         .parse()
         .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
 
-    let mood_response = get_mood_by_id(&pool, mood_id, user_id)?;
+    let mood_response = get_mood_by_id(state.moods.as_ref(), mood_id, user_id)?;
     Ok(Json(mood_response))
 }
 
+#[utoipa::path(
+    get,
+    path = "/moods",
+    tag = "moods",
+    params(PaginationQuery),
+    responses(
+        (status = 200, description = "OK", body = Vec<MoodResponse>)
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn get_user_moods_handler(
-    State(pool): State<DbPool>,
+    State(state): State<AppState>,
     user: AuthenticatedUser,
     Query(pagination): Query<PaginationQuery>,
 ) -> Result<impl IntoResponse, AppError> {
@@ -113,12 +153,25 @@ pub async fn get_user_moods_handler(
         .parse()
         .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
 
-    let moods = get_user_moods(&pool, user_id, pagination.limit, pagination.offset)?;
+    let sort = ListSort::parse(pagination.sort.as_deref(), &ListSort::MOODS)?;
+    let moods = get_user_moods(state.moods.as_ref(), user_id, pagination.limit, pagination.offset, pagination.pinned, sort)?;
     Ok(Json(moods))
 }
 
+#[utoipa::path(
+    get,
+    path = "/moods/date/{date}",
+    tag = "moods",
+    params(("date" = String, Path, description = "Mood date (YYYY-MM-DD)")),
+    responses(
+        (status = 200, description = "OK", body = MoodResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 404, description = "Not found", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn get_mood_by_date_handler(
-    State(pool): State<DbPool>,
+    State(state): State<AppState>,
     user: AuthenticatedUser,
     Path(date): Path<String>,
 ) -> Result<impl IntoResponse, AppError> {
@@ -127,15 +180,25 @@ pub async fn get_mood_by_date_handler(
         .parse()
         .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
 
-    let parsed_date = NaiveDate::parse_from_str(&date, "%m-%d-%Y")
-        .map_err(|_| AppError::BadRequest("Invalid date format. Use MM-DD-YYYY".to_string()))?;
+    let parsed_date = api_dates::parse_date(&date)?;
 
-    let mood_response = get_mood_by_date(&pool, user_id, parsed_date)?;
+    let mood_response = get_mood_by_date(state.moods.as_ref(), user_id, parsed_date)?;
     Ok(Json(mood_response))
 }
 
+#[utoipa::path(
+    get,
+    path = "/moods/range",
+    tag = "moods",
+    params(DateRangeQuery),
+    responses(
+        (status = 200, description = "OK", body = Vec<MoodResponse>),
+        (status = 400, description = "Invalid request", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn get_moods_by_date_range_handler(
-    State(pool): State<DbPool>,
+    State(state): State<AppState>,
     user: AuthenticatedUser,
     Query(range): Query<DateRangeQuery>,
 ) -> Result<impl IntoResponse, AppError> {
@@ -144,12 +207,67 @@ pub async fn get_moods_by_date_range_handler(
         .parse()
         .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
 
-    let moods = get_moods_by_date_range(&pool, user_id, range.start_date, range.end_date)?;
+    let moods = get_moods_by_date_range(state.moods.as_ref(), user_id, range.start_date, range.end_date)?;
     Ok(Json(moods))
 }
 
+/// Handler untuk mencari mood berdasarkan isi catatan
+#[utoipa::path(
+    get,
+    path = "/moods/search",
+    tag = "moods",
+    params(MoodSearchQuery),
+    responses(
+        (status = 200, description = "OK", body = Vec<MoodResponse>),
+        (status = 400, description = "Invalid request", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn search_moods_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Query(search): Query<MoodSearchQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let mood = search
+        .mood
+        .as_deref()
+        .map(str::parse::<MoodType>)
+        .transpose()
+        .map_err(AppError::BadRequest)?;
+    let filter = MoodSearchFilter {
+        query: search.query,
+        mood,
+        start_date: search.start_date,
+        end_date: search.end_date,
+        limit: search.limit,
+        offset: search.offset,
+    };
+
+    let moods = search_moods(state.moods.as_ref(), user_id, &filter)?;
+    Ok(Json(moods))
+}
+
+#[utoipa::path(
+    put,
+    path = "/moods/{id}",
+    tag = "moods",
+    params(("id" = i32, Path, description = "Mood id")),
+    request_body = UpdateMoodRequest,
+    responses(
+        (status = 200, description = "OK", body = MoodResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 404, description = "Not found", body = ErrorResponse),
+        (status = 409, description = "Another mood already exists for this date", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn update_mood_handler(
-    State(pool): State<DbPool>,
+    State(state): State<AppState>,
     user: AuthenticatedUser,
     Path(mood_id): Path<i32>,
     Json(data): Json<UpdateMoodRequest>,
@@ -159,27 +277,29 @@ pub async fn update_mood_handler(
         .parse()
         .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
 
-    let mood_date = if let Some(ref date_str) = data.date { // ✅ Fixed borrowing
-        Some(NaiveDate::parse_from_str(date_str, "%Y-%m-%d")
-            .map_err(|_| AppError::BadRequest("Invalid date format. Use YYYY-MM-DD".to_string()))?)
-    } else {
-        None
-    };
-
     let updated_mood = update_mood_with_date(
-        &pool, 
-        mood_id, 
-        user_id, 
-        data.mood, 
-        data.emoji, 
-        data.notes,
-        mood_date 
+        state.moods.as_ref(),
+        &state.stats_cache,
+        mood_id,
+        user_id,
+        data,
     )?;
     Ok(Json(updated_mood))
 }
 
+#[utoipa::path(
+    delete,
+    path = "/moods/{id}",
+    tag = "moods",
+    params(("id" = i32, Path, description = "Mood id")),
+    responses(
+        (status = 200, description = "Mood moved to trash; restorable via POST /moods/{id}/restore for MOOD_UNDO_WINDOW_MINUTES"),
+        (status = 404, description = "Not found", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn delete_mood_handler(
-    State(pool): State<DbPool>,
+    State(state): State<AppState>,
     user: AuthenticatedUser,
     Path(mood_id): Path<i32>,
 ) -> Result<impl IntoResponse, AppError> {
@@ -188,13 +308,28 @@ pub async fn delete_mood_handler(
         .parse()
         .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
 
-    delete_mood(&pool, mood_id, user_id)?;
-    Ok(Json("Mood deleted successfully"))
+    delete_mood(state.moods.as_ref(), &state.stats_cache, mood_id, user_id)?;
+    Ok(Json(t("message.mood_deleted")))
 }
 
+#[utoipa::path(
+    get,
+    path = "/moods/recent",
+    tag = "moods",
+    params(
+        RecentQuery,
+        ("X-Timezone" = Option<String>, Header, description = "IANA timezone, e.g. Asia/Jakarta; defaults to the user setting")
+    ),
+    responses(
+        (status = 200, description = "OK", body = Vec<MoodResponse>),
+        (status = 400, description = "Invalid request", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn get_recent_moods_handler(
-    State(pool): State<DbPool>,
+    State(state): State<AppState>,
     user: AuthenticatedUser,
+    tz: UserTimezone,
     Query(query): Query<RecentQuery>,
 ) -> Result<impl IntoResponse, AppError> {
     let user_id: i32 = user
@@ -202,55 +337,122 @@ pub async fn get_recent_moods_handler(
         .parse()
         .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
 
-    let moods = get_recent_moods(&pool, user_id, query.days)?;
+    let moods = get_recent_moods(state.moods.as_ref(), user_id, query.days, tz.tz())?;
     Ok(Json(moods))
 }
 
+#[derive(Deserialize, IntoParams)]
+pub struct StatsRangeQuery {
+    /// Start date (YYYY-MM-DD); wajib bersama end_date
+    #[serde(default, with = "api_dates::option")]
+    #[param(value_type = Option<String>, format = Date, example = "2025-07-01")]
+    pub start_date: Option<NaiveDate>,
+    /// End date (YYYY-MM-DD), inklusif
+    #[serde(default, with = "api_dates::option")]
+    #[param(value_type = Option<String>, format = Date, example = "2025-07-31")]
+    pub end_date: Option<NaiveDate>,
+}
+
+/// Tanpa rentang tanggal hanya mengembalikan jumlah seluruh mood; dengan `start_date` dan
+/// `end_date` mengembalikan statistik lengkap untuk rentang tersebut
+#[utoipa::path(
+    get,
+    path = "/moods/stats",
+    tag = "moods",
+    params(StatsRangeQuery),
+    responses(
+        (status = 200, description = "`{\"total_entries\"}` without a range, MoodRangeStats with one", body = MoodRangeStats),
+        (status = 400, description = "Invalid request", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn get_mood_stats_handler(
-    State(pool): State<DbPool>,
+    State(state): State<AppState>,
     user: AuthenticatedUser,
+    Query(range): Query<StatsRangeQuery>,
 ) -> Result<impl IntoResponse, AppError> {
     let user_id: i32 = user
         .user_id()
         .parse()
         .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
 
-    let count = get_mood_stats_count(&pool, user_id)?;
-    Ok(Json(serde_json::json!({
-        "total_entries": count
-    })))
+    match (range.start_date, range.end_date) {
+        (Some(start_date), Some(end_date)) => {
+            let stats = get_mood_stats_for_range(&state.pool, user_id, start_date, end_date)?;
+            Ok(Json(stats).into_response())
+        }
+        (None, None) => {
+            let count = get_mood_stats_count(state.moods.as_ref(), &state.stats_cache, user_id)?;
+            Ok(Json(serde_json::json!({
+                "total_entries": count
+            }))
+            .into_response())
+        }
+        _ => Err(AppError::BadRequest("start_date and end_date must be provided together".to_string())),
+    }
 }
 
+#[utoipa::path(
+    get,
+    path = "/moods/streak",
+    tag = "moods",
+    params(("X-Timezone" = Option<String>, Header, description = "IANA timezone, e.g. Asia/Jakarta; defaults to the user setting")),
+    responses(
+        (status = 200, description = "OK", body = Object)
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn get_mood_streak_handler(
-    State(pool): State<DbPool>,
+    State(state): State<AppState>,
     user: AuthenticatedUser,
+    tz: UserTimezone,
 ) -> Result<impl IntoResponse, AppError> {
     let user_id: i32 = user
         .user_id()
         .parse()
         .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
 
-    let streak = get_mood_streak(&pool, user_id)?;
+    let stats = get_mood_streak(state.moods.as_ref(), user_id, tz.tz())?;
     Ok(Json(serde_json::json!({
-        "streak": streak
+        "streak": stats.current_length(),
+        "longest_streak": stats.longest_length()
     })))
 }
 
+#[utoipa::path(
+    get,
+    path = "/moods/all",
+    tag = "moods",
+    responses(
+        (status = 200, description = "OK", body = Vec<MoodResponse>),
+        (status = 403, description = "Token lacks the export:read scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn get_all_moods_handler(
-    State(pool): State<DbPool>,
-    user: AuthenticatedUser,
+    State(state): State<AppState>,
+    user: AuthenticatedUser<ExportScope>,
 ) -> Result<impl IntoResponse, AppError> {
     let user_id: i32 = user
         .user_id()
         .parse()
         .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
 
-    let moods = get_all_user_moods(&pool, user_id)?;
-    Ok(Json(moods))
+    let moods = get_all_user_moods(state.moods.clone(), user_id)?;
+    Ok(moods)
 }
 
+#[utoipa::path(
+    get,
+    path = "/moods/stats/advanced",
+    tag = "moods",
+    responses(
+        (status = 200, description = "OK", body = Object)
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn get_advanced_mood_stats_handler(
-    State(pool): State<DbPool>,
+    State(state): State<AppState>,
     user: AuthenticatedUser,
 ) -> Result<impl IntoResponse, AppError> {
     let user_id: i32 = user
@@ -258,6 +460,85 @@ pub async fn get_advanced_mood_stats_handler(
         .parse()
         .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
 
-    let stats = get_mood_stats_with_scores(&pool, user_id)?;
+    let stats = get_mood_stats_with_scores(state.moods.as_ref(), &state.stats_cache, user_id)?;
     Ok(Json(stats))
-}
\ No newline at end of file
+}
+
+/// Handler untuk membatalkan penghapusan mood selama jendela undo
+#[utoipa::path(
+    post,
+    path = "/moods/{id}/restore",
+    tag = "moods",
+    params(("id" = i32, Path, description = "Id of the deleted mood")),
+    responses(
+        (status = 200, description = "Mood restored", body = MoodResponse),
+        (status = 404, description = "Not in trash or undo window has passed", body = ErrorResponse),
+        (status = 409, description = "Another mood was logged for the same date", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn restore_mood_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(mood_id): Path<i32>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let mood_response = restore_mood(state.moods.as_ref(), &state.stats_cache, mood_id, user_id)?;
+    Ok(Json(mood_response))
+}
+
+/// Handler untuk menyematkan hari mood penting
+#[utoipa::path(
+    post,
+    path = "/moods/{id}/pin",
+    tag = "moods",
+    params(("id" = i32, Path, description = "Mood id")),
+    responses(
+        (status = 200, description = "OK", body = MoodResponse),
+        (status = 404, description = "Not found", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn pin_mood_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(mood_id): Path<i32>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let mood_response = set_mood_pinned(state.moods.as_ref(), mood_id, user_id, true)?;
+    Ok(Json(mood_response))
+}
+
+/// Handler untuk melepas sematan hari mood
+#[utoipa::path(
+    delete,
+    path = "/moods/{id}/pin",
+    tag = "moods",
+    params(("id" = i32, Path, description = "Mood id")),
+    responses(
+        (status = 200, description = "OK", body = MoodResponse),
+        (status = 404, description = "Not found", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn unpin_mood_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(mood_id): Path<i32>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let mood_response = set_mood_pinned(state.moods.as_ref(), mood_id, user_id, false)?;
+    Ok(Json(mood_response))
+}
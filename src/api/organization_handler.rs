@@ -0,0 +1,248 @@
+use axum::{
+    extract::{Path, Query, State},
+    response::IntoResponse,
+};
+use crate::middleware::json_body::Json;
+
+use crate::{
+    errors::app_error::AppError,
+    i18n::t,
+    middleware::auth_middleware::AuthenticatedUser,
+    middleware::client_info::ClientInfo,
+    models::organization::{
+        AcceptInvitationRequest, CreateOrganizationRequest, InviteMemberRequest, OrganizationSummaryQuery,
+        UpdateConsentRequest,
+    },
+    service::organization_service::{
+        accept_invitation, create_organization, get_members, get_organizations, get_summary, invite_member,
+        remove_member, update_consent,
+    },
+    state::AppState,
+};
+
+/// Handler untuk membuat organisasi; pembuatnya menjadi admin
+#[utoipa::path(
+    post,
+    path = "/organizations",
+    tag = "organizations",
+    request_body = CreateOrganizationRequest,
+    responses(
+        (status = 200, description = "Organization created", body = OrganizationResponse),
+        (status = 400, description = "Invalid name", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn create_organization_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Json(data): Json<CreateOrganizationRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let organization = create_organization(&state.pool, user_id, data)?;
+    Ok(Json(organization))
+}
+
+/// Handler untuk daftar organisasi yang diikuti pengguna
+#[utoipa::path(
+    get,
+    path = "/organizations",
+    tag = "organizations",
+    responses(
+        (status = 200, description = "OK", body = Vec<OrganizationResponse>)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_organizations_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let organizations = get_organizations(&state.pool, user_id)?;
+    Ok(Json(organizations))
+}
+
+/// Handler untuk daftar anggota organisasi (admin organisasi)
+#[utoipa::path(
+    get,
+    path = "/organizations/{id}/members",
+    tag = "organizations",
+    params(("id" = i32, Path, description = "Organization id")),
+    responses(
+        (status = 200, description = "OK", body = Vec<OrganizationMemberResponse>),
+        (status = 403, description = "Organization admin access required", body = ErrorResponse),
+        (status = 404, description = "Not a member", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_members_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(organization_id): Path<i32>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let members = get_members(&state.pool, user_id, organization_id)?;
+    Ok(Json(members))
+}
+
+/// Handler untuk mengeluarkan anggota (admin) atau keluar dari organisasi (diri sendiri)
+#[utoipa::path(
+    delete,
+    path = "/organizations/{id}/members/{user_id}",
+    tag = "organizations",
+    params(
+        ("id" = i32, Path, description = "Organization id"),
+        ("user_id" = i32, Path, description = "Member user id")
+    ),
+    responses(
+        (status = 200, description = "Member removed"),
+        (status = 400, description = "Last admin cannot be removed", body = ErrorResponse),
+        (status = 403, description = "Organization admin access required", body = ErrorResponse),
+        (status = 404, description = "Not found", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn remove_member_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path((organization_id, member_user_id)): Path<(i32, i32)>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    remove_member(&state.pool, user_id, organization_id, member_user_id)?;
+    Ok(Json(t("message.organization_member_removed")))
+}
+
+/// Handler untuk mengundang pengguna lewat email (admin organisasi)
+#[utoipa::path(
+    post,
+    path = "/organizations/{id}/invitations",
+    tag = "organizations",
+    params(("id" = i32, Path, description = "Organization id")),
+    request_body = InviteMemberRequest,
+    responses(
+        (status = 200, description = "Invitation sent", body = InvitationResponse),
+        (status = 400, description = "Invalid email or role", body = ErrorResponse),
+        (status = 403, description = "Organization admin access required", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn invite_member_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(organization_id): Path<i32>,
+    Json(data): Json<InviteMemberRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let invitation = invite_member(&state.pool, state.mailer.as_ref(), user_id, organization_id, data)?;
+    Ok(Json(invitation))
+}
+
+/// Handler untuk menerima undangan organisasi dengan kode dari email
+#[utoipa::path(
+    post,
+    path = "/organizations/invitations/accept",
+    tag = "organizations",
+    request_body = AcceptInvitationRequest,
+    responses(
+        (status = 200, description = "Joined the organization", body = OrganizationResponse),
+        (status = 400, description = "Invalid or expired invitation", body = ErrorResponse),
+        (status = 403, description = "Invitation belongs to another email", body = ErrorResponse),
+        (status = 409, description = "Already a member", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn accept_invitation_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Json(data): Json<AcceptInvitationRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let organization = accept_invitation(&state.pool, user_id, data)?;
+    Ok(Json(organization))
+}
+
+/// Handler untuk memberi atau mencabut persetujuan berbagi data dengan organisasi
+#[utoipa::path(
+    put,
+    path = "/organizations/{id}/consent",
+    tag = "organizations",
+    params(("id" = i32, Path, description = "Organization id")),
+    request_body = UpdateConsentRequest,
+    responses(
+        (status = 200, description = "Consent updated", body = OrganizationMemberResponse),
+        (status = 404, description = "Not a member", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update_consent_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    client: ClientInfo,
+    Path(organization_id): Path<i32>,
+    Json(data): Json<UpdateConsentRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let member = update_consent(
+        &state.pool,
+        user_id,
+        organization_id,
+        data.data_sharing_consent,
+        client.ip_address.as_deref(),
+    )?;
+    Ok(Json(member))
+}
+
+/// Handler untuk ringkasan mood anggota yang memberi persetujuan (admin organisasi)
+#[utoipa::path(
+    get,
+    path = "/organizations/{id}/summary",
+    tag = "organizations",
+    params(("id" = i32, Path, description = "Organization id"), OrganizationSummaryQuery),
+    responses(
+        (status = 200, description = "OK", body = OrganizationSummary),
+        (status = 400, description = "Invalid date range", body = ErrorResponse),
+        (status = 403, description = "Organization admin access required", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_summary_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(organization_id): Path<i32>,
+    Query(query): Query<OrganizationSummaryQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let summary = get_summary(&state.pool, user_id, organization_id, query.start_date, query.end_date)?;
+    Ok(Json(summary))
+}
@@ -0,0 +1,103 @@
+use axum::{
+    extract::{Path, Query, State},
+    response::IntoResponse,
+};
+use crate::middleware::json_body::Json;
+
+use crate::{
+    errors::app_error::AppError,
+    middleware::auth_middleware::AuthenticatedUser,
+    models::psychologist::{CreatePsychologistBooking, PsychologistQuery},
+    service::psychologist_service::{
+        book_psychologist, get_psychologist, get_psychologist_requests, get_psychologists,
+    },
+    state::AppState,
+};
+
+/// Handler untuk direktori psikolog (publik)
+#[utoipa::path(
+    get,
+    path = "/psychologists",
+    tag = "psychologists",
+    params(PsychologistQuery),
+    responses(
+        (status = 200, description = "OK", body = Vec<PsychologistResponse>)
+    )
+)]
+pub async fn get_psychologists_handler(
+    State(state): State<AppState>,
+    Query(query): Query<PsychologistQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let psychologists = get_psychologists(&state.pool, &query)?;
+    Ok(Json(psychologists))
+}
+
+/// Handler untuk profil psikolog beserta jadwal yang masih kosong (publik)
+#[utoipa::path(
+    get,
+    path = "/psychologists/{id}",
+    tag = "psychologists",
+    params(("id" = i32, Path, description = "Psychologist id")),
+    responses(
+        (status = 200, description = "OK", body = PsychologistDetail),
+        (status = 404, description = "Psychologist not found", body = ErrorResponse)
+    )
+)]
+pub async fn get_psychologist_handler(
+    State(state): State<AppState>,
+    Path(psychologist_id): Path<i32>,
+) -> Result<impl IntoResponse, AppError> {
+    let psychologist = get_psychologist(&state.pool, psychologist_id)?;
+    Ok(Json(psychologist))
+}
+
+/// Handler untuk mengajukan sesi dengan psikolog, opsional memesan jadwal
+#[utoipa::path(
+    post,
+    path = "/psychologist-requests",
+    tag = "psychologists",
+    request_body = CreatePsychologistBooking,
+    responses(
+        (status = 200, description = "Request created", body = PsychologistRequestResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 404, description = "Psychologist or slot not found", body = ErrorResponse),
+        (status = 409, description = "Slot is already booked", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn create_psychologist_request_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Json(data): Json<CreatePsychologistBooking>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let request = book_psychologist(&state.pool, user_id, data)?;
+    Ok(Json(request))
+}
+
+/// Handler untuk daftar pengajuan sesi milik pengguna
+#[utoipa::path(
+    get,
+    path = "/psychologist-requests",
+    tag = "psychologists",
+    responses(
+        (status = 200, description = "OK", body = Vec<PsychologistRequestResponse>)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_psychologist_requests_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let requests = get_psychologist_requests(&state.pool, user_id)?;
+    Ok(Json(requests))
+}
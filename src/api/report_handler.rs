@@ -0,0 +1,178 @@
+use axum::{
+    extract::{State, Query},
+    http::header,
+    response::IntoResponse,
+};
+use crate::middleware::json_body::Json;
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+use crate::{
+    errors::app_error::AppError,
+    middleware::auth_middleware::{AuthenticatedUser, ExportScope, ShareScope},
+    middleware::timezone_middleware::UserTimezone,
+    middleware::client_info::ClientInfo,
+    service::report_service::{get_monthly_report, get_monthly_report_pdf, get_yearly_report},
+    service::job_service::enqueue_monthly_report_pdf,
+    api::job_handler::accepted,
+    utils::api_dates,
+    state::AppState,
+};
+
+#[derive(Deserialize, IntoParams)]
+pub struct MonthlyReportQuery {
+    /// Bulan laporan (YYYY-MM)
+    #[param(example = "2025-03")]
+    pub month: String,
+}
+
+#[derive(Deserialize, IntoParams)]
+pub struct YearlyReportQuery {
+    #[param(example = 2025)]
+    pub year: i32,
+}
+
+/// Handler untuk laporan bulanan ("month in review")
+#[utoipa::path(
+    get,
+    path = "/reports/monthly",
+    tag = "reports",
+    params(
+        MonthlyReportQuery,
+        ("X-Timezone" = Option<String>, Header, description = "IANA timezone, e.g. Asia/Jakarta; defaults to the user setting")
+    ),
+    responses(
+        (status = 200, description = "OK", body = MonthlyReport),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 403, description = "Token lacks the share:read scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_monthly_report_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser<ShareScope>,
+    tz: UserTimezone,
+    Query(query): Query<MonthlyReportQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let month_start = api_dates::parse_month(&query.month)?;
+
+    let report = get_monthly_report(&state.pool, user_id, month_start, tz.tz())?;
+    Ok(Json(report))
+}
+
+/// Handler untuk laporan bulanan dalam format PDF
+#[utoipa::path(
+    get,
+    path = "/reports/monthly.pdf",
+    tag = "reports",
+    params(
+        MonthlyReportQuery,
+        ("X-Timezone" = Option<String>, Header, description = "IANA timezone, e.g. Asia/Jakarta; defaults to the user setting")
+    ),
+    responses(
+        (status = 200, description = "PDF document", content_type = "application/pdf", body = Vec<u8>),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 403, description = "Token lacks the export:read scope", body = ErrorResponse),
+        (status = 429, description = "Daily PDF report quota reached", body = QuotaErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_monthly_report_pdf_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser<ExportScope>,
+    tz: UserTimezone,
+    client: ClientInfo,
+    Query(query): Query<MonthlyReportQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let month_start = api_dates::parse_month(&query.month)?;
+
+    let pdf = get_monthly_report_pdf(&state.pool, user_id, month_start, tz.tz(), client.ip_address.as_deref())?;
+    let disposition = format!(
+        "attachment; filename=\"mindmate-report-{}.pdf\"",
+        month_start.format("%Y-%m")
+    );
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/pdf".to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        pdf,
+    ))
+}
+
+/// Handler v2: antrikan PDF laporan bulanan sebagai job; PDF diunduh lewat `/jobs/{id}/result`
+#[utoipa::path(
+    post,
+    path = "/v2/reports/monthly.pdf",
+    tag = "reports",
+    params(
+        MonthlyReportQuery,
+        ("X-Timezone" = Option<String>, Header, description = "IANA timezone, e.g. Asia/Jakarta; defaults to the user setting")
+    ),
+    responses(
+        (status = 202, description = "PDF queued", body = JobResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 403, description = "Token lacks the export:read scope", body = ErrorResponse),
+        (status = 429, description = "Daily PDF report quota reached", body = QuotaErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn monthly_report_pdf_job_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser<ExportScope>,
+    tz: UserTimezone,
+    client: ClientInfo,
+    Query(query): Query<MonthlyReportQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let month_start = api_dates::parse_month(&query.month)?;
+
+    let job = enqueue_monthly_report_pdf(&state.pool, user_id, month_start, tz.tz(), client.ip_address.as_deref())?;
+    Ok(accepted(job))
+}
+
+/// Handler untuk laporan tahunan ("year in review")
+#[utoipa::path(
+    get,
+    path = "/reports/yearly",
+    tag = "reports",
+    params(
+        YearlyReportQuery,
+        ("X-Timezone" = Option<String>, Header, description = "IANA timezone, e.g. Asia/Jakarta; defaults to the user setting")
+    ),
+    responses(
+        (status = 200, description = "OK", body = YearlyReport),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 403, description = "Token lacks the share:read scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_yearly_report_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser<ShareScope>,
+    tz: UserTimezone,
+    Query(query): Query<YearlyReportQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let report = get_yearly_report(&state.pool, user_id, query.year, tz.tz())?;
+    Ok(Json(report))
+}
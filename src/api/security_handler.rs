@@ -0,0 +1,61 @@
+use axum::{
+    extract::State,
+    response::IntoResponse,
+};
+use crate::middleware::json_body::Json;
+
+use crate::{
+    errors::app_error::AppError,
+    middleware::auth_middleware::AuthenticatedUser,
+    service::audit_service::get_user_audit_log,
+    service::security_service::get_login_history,
+    state::AppState,
+};
+
+/// Handler untuk riwayat login pengguna
+#[utoipa::path(
+    get,
+    path = "/user/security/logins",
+    tag = "security",
+    responses(
+        (status = 200, description = "OK", body = Vec<LoginAttemptResponse>),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_login_history_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let history = get_login_history(&state.pool, user_id)?;
+    Ok(Json(history))
+}
+
+/// Handler untuk audit log aksi sensitif pada akun pengguna
+#[utoipa::path(
+    get,
+    path = "/user/security/audit",
+    tag = "security",
+    responses(
+        (status = 200, description = "OK", body = Vec<AuditLogResponse>),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_audit_log_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let logs = get_user_audit_log(&state.pool, user_id)?;
+    Ok(Json(logs))
+}
@@ -0,0 +1,52 @@
+use axum::{
+    extract::State,
+    http::{header, StatusCode},
+    response::IntoResponse,
+};
+use crate::middleware::json_body::Json;
+
+use crate::{
+    service::status_service::{metrics, readiness},
+    state::AppState,
+};
+
+/// Handler readiness probe: 200 jika database primary bisa dihubungi, 503 jika tidak.
+/// Statistik pool disertakan untuk memantau kehabisan koneksi.
+#[utoipa::path(
+    get,
+    path = "/ready",
+    tag = "status",
+    responses(
+        (status = 200, description = "Ready to serve traffic", body = ReadinessResponse),
+        (status = 503, description = "Database unavailable", body = ReadinessResponse)
+    )
+)]
+pub async fn ready_handler(
+    State(state): State<AppState>,
+) -> impl IntoResponse {
+    let response = readiness(&state.pool);
+    let status = if response.database.primary_available {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(response))
+}
+
+/// Handler metrics dalam format teks Prometheus (statistik pool database)
+#[utoipa::path(
+    get,
+    path = "/metrics",
+    tag = "status",
+    responses(
+        (status = 200, description = "Prometheus metrics", body = String, content_type = "text/plain")
+    )
+)]
+pub async fn metrics_handler(
+    State(state): State<AppState>,
+) -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        metrics(&state.pool),
+    )
+}
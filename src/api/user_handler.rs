@@ -1,21 +1,37 @@
 use axum::{
-    extract::{State, Json, Query},
+    extract::{Multipart, State, Query},
+    http::{header, HeaderMap, StatusCode},
     response::IntoResponse,
 };
-use diesel::{r2d2, PgConnection};
+use crate::middleware::json_body::Json;
 use serde::Deserialize;
+use utoipa::ToSchema;
 use std::collections::HashMap;
 use base64::{Engine as _, engine::general_purpose};
 
 use crate::{
     errors::app_error::AppError,
+    i18n::t,
     middleware::auth_middleware::AuthenticatedUser,
-    service::user_service::{get_user_by_id, edit_profile, change_password, get_all_users, check_email_exists, reset_password},
+    middleware::captcha::CaptchaVerified,
+    middleware::client_info::ClientInfo,
+    models::email_change::{ConfirmEmailChangeRequest, RequestEmailChangeRequest},
+    models::password_reset::{CheckEmailRequest, ResetPasswordRequest},
+    models::user::{EditProfileRequest, PatchProfileRequest, UserSettings},
+    service::user_service::{get_user_by_id, get_user_settings, update_user_settings, edit_profile, patch_profile, change_password, get_all_users, check_username_available},
+    service::password_reset_service::{request_password_reset, reset_password},
+    service::avatar_service::{get_avatar, upload_avatar},
+    service::job_service::request_account_deletion,
+    models::job::DeleteAccountRequest,
+    api::job_handler::accepted,
+    service::onboarding_service::get_onboarding_status,
+    service::usage_service::get_usage,
+    models::api_usage::UsageQuery,
+    service::email_change_service::{cancel_email_change, confirm_email_change, get_pending_email_change, request_email_change},
+    state::AppState,
 };
 
 // Type alias agar lebih singkat
-type DbPool = r2d2::Pool<diesel::r2d2::ConnectionManager<PgConnection>>;
-
 /// Fungsi untuk validasi avatar (base64 image atau URL)
 pub fn validate_avatar(avatar_data: &str) -> Result<(), String> {
     // Check jika ini base64 image
@@ -49,8 +65,18 @@ pub fn validate_avatar(avatar_data: &str) -> Result<(), String> {
 }
 
 /// Handler untuk mengambil profil pengguna
+#[utoipa::path(
+    get,
+    path = "/user/profile",
+    tag = "user",
+    responses(
+        (status = 200, description = "OK", body = UserResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn get_profile(
-    State(pool): State<DbPool>,
+    State(state): State<AppState>,
     user: AuthenticatedUser,
 ) -> Result<impl IntoResponse, AppError> {
     let user_id: i32 = user
@@ -58,23 +84,26 @@ pub async fn get_profile(
         .parse()
         .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
 
-    let user_data = get_user_by_id(&pool, user_id)?;
+    let user_data = get_user_by_id(state.users.as_ref(), user_id)?;
     Ok(Json(user_data))
 }
 
-/// Request body untuk edit profil - ditambahkan avatar
-#[derive(Deserialize)]
-pub struct EditProfileRequest {
-    pub username: String,
-    pub email: String,
-    pub age: Option<i32>,
-    pub gender: Option<String>,
-    pub avatar: Option<String>, // Tambahan field avatar
-}
-
 /// Handler untuk mengedit profil pengguna dengan validasi avatar
+#[utoipa::path(
+    put,
+    path = "/user/profile",
+    tag = "user",
+    request_body = EditProfileRequest,
+    responses(
+        (status = 200, description = "Profile updated"),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 409, description = "Username already exists", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn edit_profile_handler(
-    State(pool): State<DbPool>,
+    State(state): State<AppState>,
     user: AuthenticatedUser,
     Json(data): Json<EditProfileRequest>,
 ) -> Result<impl IntoResponse, AppError> {
@@ -91,21 +120,171 @@ pub async fn edit_profile_handler(
         }
     }
 
-    edit_profile(&pool, user_id, &data.username, &data.email, data.age, data.gender, data.avatar)?;
-    Ok(Json("Profile updated successfully"))
+    edit_profile(&state.pool, user_id, data)?;
+    Ok(Json(t("message.profile_updated")))
+}
+
+/// Handler untuk mengubah sebagian profil; field yang tidak dikirim tidak diubah
+#[utoipa::path(
+    patch,
+    path = "/user/profile",
+    tag = "user",
+    request_body = PatchProfileRequest,
+    responses(
+        (status = 200, description = "Profile updated"),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 409, description = "Username already exists", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn patch_profile_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Json(data): Json<PatchProfileRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    if let Some(avatar) = data.avatar.value() {
+        if !avatar.is_empty() {
+            validate_avatar(avatar)
+                .map_err(|e| AppError::BadRequest(format!("Avatar validation error: {}", e)))?;
+        }
+    }
+
+    patch_profile(&state.pool, user_id, data)?;
+    Ok(Json(t("message.profile_updated")))
+}
+
+/// Handler untuk checklist onboarding pengguna
+#[utoipa::path(
+    get,
+    path = "/user/onboarding",
+    tag = "user",
+    responses(
+        (status = 200, description = "OK", body = OnboardingStatus),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_onboarding_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let status = get_onboarding_status(&state.pool, user_id)?;
+    Ok(Json(status))
+}
+
+/// Handler untuk mengambil pengaturan pengguna
+#[utoipa::path(
+    get,
+    path = "/user/settings",
+    tag = "user",
+    responses(
+        (status = 200, description = "OK", body = UserSettings),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_settings_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let settings = get_user_settings(state.users.as_ref(), user_id)?;
+    Ok(Json(settings))
+}
+
+/// Handler untuk statistik request API pengguna per hari dan pemakaian kuota hari ini
+#[utoipa::path(
+    get,
+    path = "/user/usage",
+    tag = "user",
+    params(UsageQuery),
+    responses(
+        (status = 200, description = "OK", body = ApiUsageResponse),
+        (status = 400, description = "Invalid days", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_usage_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Query(query): Query<UsageQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let usage = get_usage(&state.pool, &state.api_usage, user_id, query.days)?;
+    Ok(Json(usage))
+}
+
+/// Handler untuk menyimpan pengaturan pengguna (misalnya zona waktu)
+#[utoipa::path(
+    put,
+    path = "/user/settings",
+    tag = "user",
+    request_body = UserSettings,
+    responses(
+        (status = 200, description = "OK", body = UserSettings),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update_settings_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Json(data): Json<UserSettings>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let settings = update_user_settings(&state.pool, user_id, data)?;
+    Ok(Json(settings))
 }
 
 /// Request body untuk ganti password
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct ChangePasswordRequest {
     pub old_password: String,
     pub new_password: String,
 }
 
 /// Handler untuk mengganti password pengguna
+#[utoipa::path(
+    put,
+    path = "/user/password",
+    tag = "user",
+    request_body = ChangePasswordRequest,
+    responses(
+        (status = 200, description = "Password changed"),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn change_password_handler(
-    State(pool): State<DbPool>,
+    State(state): State<AppState>,
     user: AuthenticatedUser,
+    client: ClientInfo,
     Json(data): Json<ChangePasswordRequest>,
 ) -> Result<impl IntoResponse, AppError> {
     let user_id: i32 = user
@@ -113,104 +292,367 @@ pub async fn change_password_handler(
         .parse()
         .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
 
-    change_password(&pool, user_id, &data.old_password, &data.new_password)?;
-    Ok(Json("Password changed successfully"))
+    change_password(&state.pool, user_id, &data.old_password, &data.new_password, client.ip_address.as_deref())?;
+    Ok(Json(t("message.password_changed")))
 }
 
 /// Handler untuk mendapatkan semua pengguna
+#[utoipa::path(
+    get,
+    path = "/users",
+    tag = "user",
+    responses(
+        (status = 200, description = "OK", body = Vec<UserResponse>)
+    )
+)]
 pub async fn get_all_users_handler(
-    State(pool): State<DbPool>,
+    State(state): State<AppState>,
 ) -> Result<impl IntoResponse, AppError> {
-    let users = get_all_users(&pool)?;
+    let users = get_all_users(state.users.as_ref())?;
     Ok(Json(users))
 }
 
-/// Request body untuk check email
-#[derive(Deserialize)]
-pub struct CheckEmailRequest {
-    pub email: String,
-}
-
-/// Handler untuk mengecek ketersediaan email via GET query parameter
-/// GET /user/check-email?email=example@email.com
-pub async fn check_email_handler_get(
-    State(pool): State<DbPool>,
+/// Handler untuk mengecek ketersediaan username saat mengisi form registrasi
+/// GET /user/check-username?username=budi
+#[utoipa::path(
+    get,
+    path = "/user/check-username",
+    tag = "user",
+    params(("username" = String, Query, description = "Username to check")),
+    responses(
+        (status = 200, description = "OK", body = UsernameCheckResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse)
+    )
+)]
+pub async fn check_username_handler(
+    State(state): State<AppState>,
     Query(params): Query<HashMap<String, String>>,
 ) -> Result<impl IntoResponse, AppError> {
-    let email = params
-        .get("email")
-        .ok_or_else(|| AppError::BadRequest("Email parameter is required".to_string()))?;
+    let username = params
+        .get("username")
+        .ok_or_else(|| AppError::BadRequest("Username parameter is required".to_string()))?;
 
-    // Basic email format validation (optional)
-    if !email.contains('@') || !email.contains('.') {
-        return Err(AppError::BadRequest("Invalid email format".to_string()));
-    }
-
-    let result = check_email_exists(&pool, email)?;
+    let result = check_username_available(state.users.as_ref(), username)?;
     Ok(Json(result))
 }
 
-/// Handler untuk mengecek ketersediaan email via POST body
+/// Handler lupa password via POST body: kirim token reset ke email jika terdaftar
 /// POST /user/check-email dengan body: {"email": "example@email.com"}
+#[utoipa::path(
+    post,
+    path = "/user/check-email",
+    tag = "user",
+    request_body = CheckEmailRequest,
+    params(("X-Captcha-Token" = Option<String>, Header, description = "Token CAPTCHA, wajib setelah terlalu banyak percobaan dari satu IP")),
+    responses(
+        (status = 200, description = "Same response whether or not the email is registered", body = PasswordResetRequestedResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 403, description = "CAPTCHA verification required or failed", body = ErrorResponse),
+        (status = 429, description = "Too many requests from this IP", body = ErrorResponse)
+    )
+)]
 pub async fn check_email_handler_post(
-    State(pool): State<DbPool>,
+    State(state): State<AppState>,
+    client: ClientInfo,
+    _captcha: CaptchaVerified,
     Json(data): Json<CheckEmailRequest>,
 ) -> Result<impl IntoResponse, AppError> {
-    let email = data.email.trim();
+    state.password_reset_limiter.check(client.ip_address.as_deref())?;
 
-    // Basic email format validation
-    if email.is_empty() {
-        return Err(AppError::BadRequest("Email cannot be empty".to_string()));
-    }
-    
-    if !email.contains('@') || !email.contains('.') {
-        return Err(AppError::BadRequest("Invalid email format".to_string()));
-    }
-
-    let result = check_email_exists(&pool, email)?;
+    let result = request_password_reset(&state.pool, state.mailer.clone(), &data.email)?;
     Ok(Json(result))
 }
 
-/// Request body untuk reset password (lupa password)
-#[derive(Deserialize)]
-pub struct ResetPasswordRequest {
-    pub email: String,
-    pub new_password: String,
-    pub confirm_password: String,
-}
-
-/// Handler untuk reset password setelah verifikasi email
-/// POST /user/reset-password dengan body: {"email": "example@email.com", "new_password": "newpass123", "confirm_password": "newpass123"}
+/// Handler untuk reset password dengan token yang dikirim ke email
+/// POST /user/reset-password dengan body: {"token": "...", "new_password": "newpass123", "confirm_password": "newpass123"}
+#[utoipa::path(
+    post,
+    path = "/user/reset-password",
+    tag = "user",
+    request_body = ResetPasswordRequest,
+    params(("X-Captcha-Token" = Option<String>, Header, description = "Token CAPTCHA, wajib setelah terlalu banyak percobaan dari satu IP")),
+    responses(
+        (status = 200, description = "Password reset"),
+        (status = 400, description = "Invalid request or expired token", body = ErrorResponse),
+        (status = 403, description = "CAPTCHA verification required or failed", body = ErrorResponse),
+        (status = 429, description = "Too many requests from this IP", body = ErrorResponse)
+    )
+)]
 pub async fn reset_password_handler(
-    State(pool): State<DbPool>,
+    State(state): State<AppState>,
+    client: ClientInfo,
+    _captcha: CaptchaVerified,
     Json(data): Json<ResetPasswordRequest>,
 ) -> Result<impl IntoResponse, AppError> {
-    let email = data.email.trim();
+    state.password_reset_limiter.check(client.ip_address.as_deref())?;
+
+    let token = data.token.trim();
     let new_password = data.new_password.trim();
     let confirm_password = data.confirm_password.trim();
 
-    // Basic validation
-    if email.is_empty() {
-        return Err(AppError::BadRequest("Email cannot be empty".to_string()));
-    }
-    
-    if !email.contains('@') || !email.contains('.') {
-        return Err(AppError::BadRequest("Invalid email format".to_string()));
+    if token.is_empty() {
+        return Err(AppError::BadRequest("Invalid or expired password reset token".to_string()));
     }
 
     if new_password.is_empty() {
         return Err(AppError::BadRequest("New password cannot be empty".to_string()));
     }
 
-    if new_password.len() < 6 {
-        return Err(AppError::BadRequest("Password must be at least 6 characters long".to_string()));
-    }
-
     if new_password != confirm_password {
         return Err(AppError::BadRequest("Passwords do not match".to_string()));
     }
 
-    // Reset password
-    reset_password(&pool, email, new_password)?;
-    Ok(Json("Password reset successfully"))
-}
\ No newline at end of file
+    reset_password(&state.pool, token, new_password, client.ip_address.as_deref())?;
+    Ok(Json(t("message.password_reset")))
+}
+
+/// Handler untuk meminta ganti email; link konfirmasi dikirim ke alamat baru
+#[utoipa::path(
+    post,
+    path = "/user/email/change",
+    tag = "user",
+    request_body = RequestEmailChangeRequest,
+    responses(
+        (status = 200, description = "Confirmation email sent", body = PendingEmailChangeResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 409, description = "Email already exists", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn request_email_change_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Json(data): Json<RequestEmailChangeRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let pending = request_email_change(&state.pool, state.mailer.as_ref(), user_id, data)?;
+    Ok(Json(pending))
+}
+
+/// Handler untuk melihat permintaan ganti email yang belum dikonfirmasi
+#[utoipa::path(
+    get,
+    path = "/user/email/change",
+    tag = "user",
+    responses(
+        (status = 200, description = "Pending change, or null if none", body = Option<PendingEmailChangeResponse>),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_pending_email_change_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let pending = get_pending_email_change(&state.pool, user_id)?;
+    Ok(Json(pending))
+}
+
+/// Handler untuk membatalkan permintaan ganti email
+#[utoipa::path(
+    delete,
+    path = "/user/email/change",
+    tag = "user",
+    responses(
+        (status = 200, description = "Pending change cancelled"),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn cancel_email_change_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    cancel_email_change(&state.pool, user_id)?;
+    Ok(Json(t("message.email_change_cancelled")))
+}
+
+/// Handler untuk konfirmasi ganti email dari link di email
+/// GET /user/email/confirm?token=...
+#[utoipa::path(
+    get,
+    path = "/user/email/confirm",
+    tag = "user",
+    params(ConfirmEmailChangeRequest),
+    responses(
+        (status = 200, description = "Email changed"),
+        (status = 400, description = "Invalid or expired token", body = ErrorResponse),
+        (status = 409, description = "Email already exists", body = ErrorResponse)
+    )
+)]
+pub async fn confirm_email_change_handler_get(
+    State(state): State<AppState>,
+    client: ClientInfo,
+    Query(query): Query<ConfirmEmailChangeRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    confirm_email_change(&state.pool, &query.token, client.ip_address.as_deref())?;
+    Ok(Json(t("message.email_changed")))
+}
+
+/// Handler untuk konfirmasi ganti email via POST body
+/// POST /user/email/confirm dengan body: {"token": "..."}
+#[utoipa::path(
+    post,
+    path = "/user/email/confirm",
+    tag = "user",
+    request_body = ConfirmEmailChangeRequest,
+    responses(
+        (status = 200, description = "Email changed"),
+        (status = 400, description = "Invalid or expired token", body = ErrorResponse),
+        (status = 409, description = "Email already exists", body = ErrorResponse)
+    )
+)]
+pub async fn confirm_email_change_handler_post(
+    State(state): State<AppState>,
+    client: ClientInfo,
+    Json(data): Json<ConfirmEmailChangeRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    confirm_email_change(&state.pool, &data.token, client.ip_address.as_deref())?;
+    Ok(Json(t("message.email_changed")))
+}
+
+/// Handler untuk upload avatar (multipart, field `avatar`); gambar di-resize di server
+#[utoipa::path(
+    post,
+    path = "/user/avatar",
+    tag = "user",
+    request_body(content = AvatarUploadForm, content_type = "multipart/form-data"),
+    responses(
+        (status = 200, description = "Avatar uploaded", body = AvatarResponse),
+        (status = 400, description = "Invalid image", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn upload_avatar_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    mut multipart: Multipart,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let mut image_data = None;
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| AppError::BadRequest(format!("Invalid multipart body: {}", e)))?
+    {
+        if field.name() == Some("avatar") {
+            let bytes = field
+                .bytes()
+                .await
+                .map_err(|e| AppError::BadRequest(format!("Failed to read avatar: {}", e)))?;
+            image_data = Some(bytes);
+            break;
+        }
+    }
+
+    let image_data = image_data
+        .ok_or_else(|| AppError::BadRequest("Missing 'avatar' file field".to_string()))?;
+
+    let avatar = upload_avatar(&state.pool, state.storage.as_ref(), user_id, image_data).await?;
+    Ok(Json(avatar))
+}
+
+/// Handler untuk mengambil avatar hasil upload milik pengguna
+#[utoipa::path(
+    get,
+    path = "/user/avatar",
+    tag = "user",
+    responses(
+        (status = 200, description = "JPEG image", content_type = "image/jpeg", body = Vec<u8>),
+        (status = 304, description = "Not modified"),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 404, description = "No uploaded avatar", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_avatar_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let (data, version) = get_avatar(&state.pool, state.storage.as_ref(), user_id)?;
+    let etag = format!("\"{}\"", version);
+    // URL avatar berubah setiap upload, jadi isi untuk satu versi tidak pernah berubah
+    let cache_control = "private, max-age=31536000, immutable".to_string();
+
+    let not_modified = headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value == etag);
+    if not_modified {
+        return Ok((
+            StatusCode::NOT_MODIFIED,
+            [(header::ETAG, etag), (header::CACHE_CONTROL, cache_control)],
+        )
+            .into_response());
+    }
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "image/jpeg".to_string()),
+            (header::ETAG, etag),
+            (header::CACHE_CONTROL, cache_control),
+        ],
+        data,
+    )
+        .into_response())
+}
+
+/// Handler untuk menghapus akun beserta semua datanya. Penghapusan dikerjakan sebagai job;
+/// setelah akun terhapus, status job tersebut juga tidak bisa dilihat lagi.
+#[utoipa::path(
+    delete,
+    path = "/user/account",
+    tag = "user",
+    request_body = DeleteAccountRequest,
+    responses(
+        (status = 202, description = "Account deletion queued", body = JobResponse),
+        (status = 400, description = "Missing or invalid password", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn delete_account_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    client: ClientInfo,
+    Json(data): Json<DeleteAccountRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let job = request_account_deletion(
+        &state.pool,
+        user_id,
+        data.password.as_deref(),
+        client.ip_address.as_deref(),
+    )?;
+    Ok(accepted(job))
+}
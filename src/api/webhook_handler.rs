@@ -0,0 +1,90 @@
+use axum::{
+    extract::{Path, State},
+    response::IntoResponse,
+};
+use crate::middleware::json_body::Json;
+
+use crate::{
+    errors::app_error::AppError,
+    i18n::t,
+    middleware::auth_middleware::AuthenticatedUser,
+    models::webhook::CreateWebhookRequest,
+    service::webhook_service::{delete_webhook, get_webhooks, register_webhook},
+    state::AppState,
+};
+
+/// Handler untuk daftar webhook ringkasan mingguan beserta status pengiriman terakhir
+#[utoipa::path(
+    get,
+    path = "/user/settings/webhooks",
+    tag = "integrations",
+    responses(
+        (status = 200, description = "OK", body = Vec<WebhookResponse>)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_webhooks_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let webhooks = get_webhooks(&state.pool, user_id)?;
+    Ok(Json(webhooks))
+}
+
+/// Handler untuk mendaftarkan webhook Slack atau Discord penerima ringkasan mingguan
+#[utoipa::path(
+    post,
+    path = "/user/settings/webhooks",
+    tag = "integrations",
+    request_body = CreateWebhookRequest,
+    responses(
+        (status = 200, description = "Webhook registered", body = WebhookResponse),
+        (status = 400, description = "Unsupported platform, invalid URL or too many webhooks", body = ErrorResponse),
+        (status = 409, description = "Webhook is already registered", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn register_webhook_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Json(data): Json<CreateWebhookRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    let webhook = register_webhook(&state.pool, user_id, data)?;
+    Ok(Json(webhook))
+}
+
+/// Handler untuk menghapus webhook; antrian pengiriman yang belum terkirim ikut dihapus
+#[utoipa::path(
+    delete,
+    path = "/user/settings/webhooks/{id}",
+    tag = "integrations",
+    params(("id" = i32, Path, description = "Webhook id")),
+    responses(
+        (status = 200, description = "Webhook deleted"),
+        (status = 404, description = "Webhook not found", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn delete_webhook_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(webhook_id): Path<i32>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id: i32 = user
+        .user_id()
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+    delete_webhook(&state.pool, user_id, webhook_id)?;
+    Ok(Json(t("message.webhook_deleted")))
+}
@@ -0,0 +1,54 @@
+use std::sync::Arc;
+use axum::Router;
+use axum::http::{HeaderName, HeaderValue, Method};
+use axum::http::header::{AUTHORIZATION, CONTENT_TYPE, ACCEPT};
+use tower_http::catch_panic::CatchPanicLayer;
+use tower_http::cors::CorsLayer;
+use crate::config::app_config::app_config;
+use crate::middleware::{api_version, body_limit, captcha, compression, locale_middleware, maintenance, panic, request_id, request_limits, request_log, timezone_middleware};
+use crate::path;
+use crate::state::AppState;
+use crate::utils::auth_cookie;
+
+/// Router lengkap aplikasi (prefix /api, versi API, middleware dan CORS) tanpa background job.
+/// Dipakai oleh server dan oleh test integrasi.
+pub fn build_router(state: AppState) -> Router {
+    let limits = Arc::new(request_limits::RequestLimits::from_config(app_config()));
+
+    // Create API routes dengan prefix /api
+    let api_routes = Router::new()
+        .merge(path::init_routes())
+        .layer(axum::middleware::map_response(body_limit::payload_too_large_as_json))
+        .layer(axum::middleware::from_fn_with_state(state.clone(), maintenance::reject_writes))
+        .layer(axum::middleware::from_fn_with_state(state.clone(), locale_middleware::resolve_locale))
+        .with_state(state);
+
+    // CORS configuration untuk development
+    let local_origin = "http://localhost:5173".parse::<HeaderValue>().unwrap();
+    let vercel_origin = "https://mindmate-project.vercel.app".parse::<HeaderValue>().unwrap();
+    let cors = CorsLayer::new()
+        .allow_origin([local_origin, vercel_origin])
+        .allow_methods([Method::GET, Method::POST, Method::PUT, Method::PATCH, Method::DELETE])
+        .allow_headers([
+            AUTHORIZATION,
+            CONTENT_TYPE,
+            ACCEPT,
+            api_version::API_VERSION_HEADER.clone(),
+            HeaderName::from_static(timezone_middleware::TIMEZONE_HEADER),
+            HeaderName::from_static(captcha::CAPTCHA_TOKEN_HEADER),
+            HeaderName::from_static(auth_cookie::CSRF_HEADER),
+        ])
+        .expose_headers([api_version::API_VERSION_HEADER.clone(), request_id::REQUEST_ID_HEADER.clone()])
+        .allow_credentials(true);
+
+    // Create the main app dengan prefix /api
+    Router::new()
+        .nest(api_version::API_PREFIX, api_routes)
+        .layer(CatchPanicLayer::custom(panic::panic_response))
+        .layer(axum::middleware::from_fn_with_state(limits, request_limits::enforce))
+        .layer(axum::middleware::from_fn(api_version::negotiate))
+        .layer(compression::compression_layer())
+        .layer(cors)
+        .layer(axum::middleware::from_fn(request_log::log_request))
+        .layer(axum::middleware::from_fn(request_id::assign_request_id))
+}
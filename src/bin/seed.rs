@@ -0,0 +1,127 @@
+//! Isi database development dengan pengguna, mood dan jurnal buatan.
+//!
+//! cargo run --bin seed -- --users 50 --months 6 --journals-per-week 2 --seed 42
+//!
+//! Pengguna dibuat dengan email `seed<N>@mindmate.test` dan password SEED_PASSWORD;
+//! pengguna yang sudah ada dilewati sehingga seed aman dijalankan ulang.
+use std::env;
+use std::process;
+use bcrypt::{hash, DEFAULT_COST};
+use chrono::{Duration, Utc};
+use dotenv::dotenv;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use mindmate_be::db::pool::DbPools;
+use mindmate_be::db::{journal_query, mood_query, user_query};
+use mindmate_be::models::user::NewUser;
+use mindmate_be::utils::synthetic_data::generate_history;
+
+const SEED_PASSWORD: &str = "SeedPassword#2025";
+const SEED_EMAIL_DOMAIN: &str = "mindmate.test";
+const GENDERS: &[&str] = &["male", "female"];
+
+struct Options {
+    users: usize,
+    months: i64,
+    journals_per_week: f64,
+    seed: Option<u64>,
+}
+
+fn usage() -> ! {
+    eprintln!(
+        "Usage: seed [--users N] [--months N] [--journals-per-week N] [--seed N]\n\n\
+         --users              jumlah pengguna (default 20)\n\
+         --months             panjang riwayat maksimum per pengguna dalam bulan (default 6)\n\
+         --journals-per-week  rata-rata jurnal per minggu per pengguna (default 2)\n\
+         --seed               seed RNG agar hasil bisa diulang"
+    );
+    process::exit(2);
+}
+
+fn parse_options() -> Options {
+    let mut options = Options {
+        users: 20,
+        months: 6,
+        journals_per_week: 2.0,
+        seed: None,
+    };
+
+    let mut args = env::args().skip(1);
+    while let Some(flag) = args.next() {
+        if flag == "--help" || flag == "-h" {
+            usage();
+        }
+        let Some(value) = args.next() else {
+            usage();
+        };
+        let parsed = match flag.as_str() {
+            "--users" => value.parse().map(|users| options.users = users).is_ok(),
+            "--months" => value.parse().map(|months| options.months = months).is_ok(),
+            "--journals-per-week" => value.parse().map(|count| options.journals_per_week = count).is_ok(),
+            "--seed" => value.parse().map(|seed| options.seed = Some(seed)).is_ok(),
+            _ => false,
+        };
+        if !parsed {
+            eprintln!("❌ Invalid argument: {} {}", flag, value);
+            usage();
+        }
+    }
+
+    if options.months < 1 || options.journals_per_week < 0.0 {
+        usage();
+    }
+    options
+}
+
+fn main() {
+    dotenv().ok();
+    let options = parse_options();
+
+    let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = DbPools::from_urls(database_url, None);
+    let mut conn = pool.conn_write().expect("Failed to connect to database");
+
+    let mut rng = match options.seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+    let password = hash(SEED_PASSWORD, DEFAULT_COST).expect("Failed to hash password");
+    let now = Utc::now().naive_utc();
+    let today = now.date();
+
+    let (mut users, mut moods, mut journals) = (0, 0, 0);
+    for index in 1..=options.users {
+        let email = format!("seed{}@{}", index, SEED_EMAIL_DOMAIN);
+        if user_query::find_user_by_email(&mut conn, &email).is_ok() {
+            continue;
+        }
+
+        // Tanggal daftar tersebar agar kohort retensi dan grafik punya variasi
+        let signup = now - Duration::days(rng.gen_range(0..=options.months * 30));
+        let user = user_query::insert_user(
+            &mut conn,
+            &NewUser {
+                username: format!("seed_user_{}", index),
+                email,
+                password: password.clone(),
+                settings: Some(r#"{"timezone":"Asia/Jakarta"}"#.to_string()),
+                age: Some(rng.gen_range(17..=45)),
+                gender: Some(GENDERS[rng.gen_range(0..GENDERS.len())].to_string()),
+                avatar: None,
+                created_at: signup,
+                updated_at: signup,
+            },
+        )
+        .unwrap_or_else(|e| panic!("Failed to create seed user {}: {}", index, e));
+
+        let history = generate_history(&mut rng, user.id, signup.date(), today, options.journals_per_week);
+        moods += mood_query::insert_moods(&mut conn, &history.moods).expect("Failed to insert moods");
+        journals += journal_query::insert_journals(&mut conn, &history.journals).expect("Failed to insert journals");
+        users += 1;
+    }
+
+    println!(
+        "✅ Seeded {} users, {} moods and {} journals (password: {})",
+        users, moods, journals, SEED_PASSWORD
+    );
+}
@@ -0,0 +1,489 @@
+use std::env;
+use std::sync::OnceLock;
+use ipnet::IpNet;
+
+/// Konfigurasi aplikasi yang dibaca dari environment variable
+#[derive(Debug, Clone)]
+pub struct AppConfig {
+    /// URL database primary (DATABASE_URL), wajib
+    pub database_url: Option<String>,
+    /// Secret penandatangan JWT, wajib dan minimal 32 byte
+    pub jwt_secret: Option<String>,
+    /// `kid` untuk JWT_SECRET, ditulis di header setiap token baru
+    pub jwt_key_id: String,
+    /// Kunci lama yang masih diterima untuk verifikasi saat rotasi, format `kid:secret` dipisah koma
+    pub jwt_previous_keys: Option<String>,
+    /// File berisi kunci lama, satu `kid:secret` per baris; untuk secret yang mengandung koma
+    pub jwt_keys_file: Option<String>,
+    /// Aktifkan login lewat cookie HttpOnly (selain header Bearer); request tulis dengan cookie wajib membawa X-CSRF-Token
+    pub auth_cookie_enabled: bool,
+    /// Nama cookie sesi; cookie CSRF memakai nama ini dengan akhiran `_csrf`
+    pub auth_cookie_name: String,
+    /// Atribut SameSite: `Strict`, `Lax`, atau `None` (wajib jika frontend beda domain, dan butuh Secure)
+    pub auth_cookie_same_site: String,
+    /// Kirim cookie hanya lewat HTTPS; matikan hanya untuk development lokal
+    pub auth_cookie_secure: bool,
+    /// Atribut Domain cookie; kosong berarti hanya host API
+    pub auth_cookie_domain: Option<String>,
+    /// Umur kode sekali pakai dari callback Google sebelum harus ditukar lewat `POST /auth/exchange`
+    pub auth_exchange_code_ttl_secs: i64,
+    /// Kredensial Google OAuth; login Google aktif jika salah satunya diisi, dan saat itu ketiganya wajib
+    pub google_client_id: Option<String>,
+    pub google_client_secret: Option<String>,
+    pub google_redirect_uri: Option<String>,
+    /// Kunci AES-256 (32 byte, base64) untuk mengenkripsi token Google yang disimpan; kosong berarti token tidak disimpan
+    pub oauth_token_encryption_key: Option<String>,
+    /// Base URL frontend, tujuan redirect setelah login Google
+    pub frontend_url: String,
+    /// Origin lain (selain FRONTEND_URL) yang boleh menjadi tujuan `redirect_to`, dipisah koma
+    pub frontend_redirect_whitelist: Vec<String>,
+    /// Terima format tanggal lama (MM-DD-YYYY) selain ISO-8601 (YYYY-MM-DD)
+    pub allow_legacy_date_format: bool,
+    /// URL publik API (tanpa /api), dipakai untuk membuat link seperti feed kalender
+    pub public_api_url: String,
+    /// Jadwal cleanup token blacklist (format cron dengan detik, waktu UTC)
+    pub token_cleanup_schedule: String,
+    /// Jadwal penghapusan permanen mood di tempat sampah (format cron dengan detik, waktu UTC)
+    pub mood_trash_purge_schedule: String,
+    /// Lama mood yang dihapus masih bisa dikembalikan (menit)
+    pub mood_undo_window_minutes: i64,
+    /// Email pengguna yang boleh mengakses endpoint /admin
+    pub admin_emails: Vec<String>,
+    /// Timeout total untuk request HTTP keluar (detik)
+    pub http_timeout_secs: u64,
+    /// Timeout koneksi untuk request HTTP keluar (detik)
+    pub http_connect_timeout_secs: u64,
+    /// Jumlah retry untuk request HTTP keluar yang gagal sementara (hanya method idempoten)
+    pub http_max_retries: u32,
+    /// Proxy untuk semua request HTTP keluar, misalnya http://proxy:3128
+    pub http_proxy_url: Option<String>,
+    /// URL database replica untuk query baca yang berat; kosong berarti semua ke primary
+    pub database_replica_url: Option<String>,
+    /// Jumlah maksimum koneksi per pool database (primary dan replica masing-masing)
+    pub db_pool_max_size: u32,
+    /// Koneksi idle minimum yang dijaga pool; kosong berarti sama dengan ukuran maksimum
+    pub db_pool_min_idle: Option<u32>,
+    /// Batas waktu menunggu koneksi dari pool, dalam detik
+    pub db_connection_timeout_secs: u64,
+    /// `statement_timeout` untuk setiap koneksi, dalam milidetik; 0 berarti tanpa batas
+    pub db_statement_timeout_ms: u64,
+    /// Berapa kali koneksi ke database dicoba saat startup sebelum server tetap jalan tanpa database
+    pub db_startup_retries: u32,
+    /// Lama satu percobaan koneksi saat startup, dalam detik
+    pub db_startup_retry_secs: u64,
+    /// Masa berlaku cache statistik per pengguna (detik)
+    pub stats_cache_ttl_secs: u64,
+    /// Jumlah maksimum entri cache statistik
+    pub stats_cache_capacity: u64,
+    /// Jumlah login gagal berturut-turut sebelum akun dikunci sementara
+    pub login_max_failures: i64,
+    /// Jumlah login gagal dari satu IP (semua akun) sebelum IP tersebut dibatasi
+    pub login_max_failures_per_ip: i64,
+    /// Jendela waktu penghitungan login gagal sekaligus lama penguncian (menit)
+    pub login_lockout_minutes: i64,
+    /// Panjang minimum password
+    pub password_min_length: usize,
+    /// Password wajib mengandung huruf kecil
+    pub password_require_lowercase: bool,
+    /// Password wajib mengandung huruf besar
+    pub password_require_uppercase: bool,
+    /// Password wajib mengandung angka
+    pub password_require_digit: bool,
+    /// Password wajib mengandung simbol
+    pub password_require_symbol: bool,
+    /// Skor kekuatan password minimum (0-4); 0 menonaktifkan pengecekan
+    pub password_min_strength: u8,
+    /// Direktori penyimpanan file lampiran (avatar) untuk storage lokal
+    pub storage_dir: String,
+    /// Ukuran sisi avatar setelah di-resize (piksel)
+    pub avatar_size: u32,
+    /// Ukuran maksimum file avatar yang di-upload (byte)
+    pub avatar_max_upload_bytes: usize,
+    /// Ukuran maksimum file CSV yang di-import (byte)
+    pub import_max_upload_bytes: usize,
+    /// Skor rata-rata (1-5) di bawah nilai ini diinterpretasikan sebagai `low`
+    pub mood_score_low_threshold: f64,
+    /// Skor rata-rata (1-5) mulai nilai ini diinterpretasikan sebagai `good`
+    pub mood_score_good_threshold: f64,
+    /// Jadwal job deteksi penurunan mood (format cron dengan detik, waktu UTC)
+    pub insight_schedule: String,
+    /// Hari tanpa catatan sebelum streak berjalan dianggap putus; 1 = hari ini belum dicatat
+    pub streak_grace_days: i64,
+    /// Jumlah hari berturut-turut di bawah MOOD_SCORE_LOW_THRESHOLD sebelum muncul alert
+    pub insight_low_streak_days: i64,
+    /// Panjang periode terbaru yang dibandingkan dengan baseline (hari)
+    pub insight_recent_days: i64,
+    /// Panjang periode baseline sebelum periode terbaru (hari)
+    pub insight_baseline_days: i64,
+    /// Selisih rata-rata skor baseline dan terbaru yang dianggap penurunan tajam
+    pub insight_drop_threshold: f64,
+    /// Ukuran maksimum body request JSON (byte); upload avatar dan import punya batas sendiri
+    pub max_request_body_bytes: usize,
+    /// Panjang maksimum judul jurnal (karakter, paling banyak 500 sesuai kolom database)
+    pub journal_title_max_length: usize,
+    /// Panjang maksimum isi jurnal (karakter)
+    pub journal_content_max_length: usize,
+    /// Panjang maksimum catatan mood (karakter)
+    pub mood_notes_max_length: usize,
+    /// Emoji yang boleh dipakai pada mood; emoji bawaan tiap jenis mood selalu diterima
+    pub mood_emoji_whitelist: Vec<String>,
+    /// Jadwal pengiriman antrian push notification (format cron dengan detik, waktu UTC)
+    pub push_delivery_schedule: String,
+    /// Jumlah percobaan kirim push sebelum entri antrian dibuang
+    pub push_max_attempts: i32,
+    /// Jadwal pengecekan pengingat harian (format cron dengan detik, waktu UTC)
+    pub reminder_schedule: String,
+    /// Masa berlaku link check-in mood di email pengingat (jam)
+    pub checkin_token_hours: i64,
+    /// Jadwal pembuatan ringkasan mood mingguan untuk webhook Slack/Discord (UTC)
+    pub webhook_summary_schedule: String,
+    /// Jadwal pengiriman antrian webhook
+    pub webhook_delivery_schedule: String,
+    /// Jumlah percobaan kirim webhook sebelum ditandai gagal
+    pub webhook_max_attempts: i32,
+    /// Backend koordinasi job terjadwal: `postgres` (advisory lock, aman untuk beberapa replika),
+    /// `tokio` (satu instance, tanpa koordinasi) atau `redis`
+    pub job_backend: String,
+    /// Berapa lama klaim satu jadwal disimpan di Redis, dalam detik; harus lebih lama dari selisih jam antar replika
+    pub job_claim_ttl_secs: u64,
+    /// URL Redis (`redis://[[user]:password@]host[:port][/db]`), wajib jika JOB_BACKEND=redis
+    pub redis_url: Option<String>,
+    /// Prefix semua key Redis, agar beberapa lingkungan bisa berbagi satu server
+    pub redis_key_prefix: String,
+    /// Batas waktu satu perintah Redis, termasuk membuka koneksi, dalam detik
+    pub redis_timeout_secs: u64,
+    /// Jadwal cron worker job latar belakang (import, export, PDF, hapus akun)
+    pub job_worker_schedule: String,
+    /// Berapa lama job yang selesai dan file hasilnya disimpan, dalam jam
+    pub job_result_ttl_hours: i64,
+    /// Job yang `running` lebih lama dari ini dianggap terputus dan ditandai gagal, dalam menit
+    pub job_timeout_minutes: i64,
+    /// Jadwal pengecekan pengingat janji temu 24 jam dan 1 jam sebelumnya
+    pub appointment_reminder_schedule: String,
+    /// Project ID Firebase; FCM nonaktif jika salah satu kredensial FCM kosong
+    pub fcm_project_id: Option<String>,
+    /// Email service account Firebase
+    pub fcm_client_email: Option<String>,
+    /// Private key service account Firebase (PEM, `\n` boleh ditulis literal)
+    pub fcm_private_key: Option<String>,
+    /// Key ID auth key APNs; APNs nonaktif jika salah satu kredensial APNs kosong
+    pub apns_key_id: Option<String>,
+    /// Team ID akun Apple Developer
+    pub apns_team_id: Option<String>,
+    /// Auth key APNs (.p8, PEM)
+    pub apns_private_key: Option<String>,
+    /// Bundle ID aplikasi iOS, dikirim sebagai header apns-topic
+    pub apns_topic: Option<String>,
+    /// Kirim ke server sandbox APNs (build development)
+    pub apns_sandbox: bool,
+    /// Jadwal backup otomatis semua tabel ke storage (format cron dengan detik, waktu UTC);
+    /// kosong berarti backup otomatis nonaktif
+    pub backup_schedule: Option<String>,
+    /// Masa berlaku token impersonasi admin (menit)
+    pub impersonation_token_minutes: i64,
+    /// Provider CAPTCHA (`hcaptcha` atau `turnstile`); CAPTCHA nonaktif jika provider atau secret kosong
+    pub captcha_provider: Option<String>,
+    /// Secret key dari provider CAPTCHA
+    pub captcha_secret: Option<String>,
+    /// Jumlah request ke endpoint publik dari satu IP sebelum CAPTCHA diwajibkan
+    pub captcha_threshold: u32,
+    /// Jendela waktu penghitungan request per IP untuk CAPTCHA (menit)
+    pub captcha_window_minutes: u64,
+    /// Istilah terlarang untuk moderasi pesan dan permintaan bantuan (dipisah koma)
+    pub moderation_banned_terms: Vec<String>,
+    /// Tindakan jika istilah terlarang ditemukan: `flag` (tinjau) atau `hold` (tahan sampai disetujui)
+    pub moderation_banned_terms_action: String,
+    /// Endpoint API moderasi eksternal; nonaktif jika kosong
+    pub moderation_api_url: Option<String>,
+    pub moderation_api_key: Option<String>,
+    /// Tahan semua kiriman komunitas sampai disetujui admin
+    pub community_premoderation: bool,
+    /// Jumlah kiriman komunitas maksimum per pengguna per jam
+    pub community_posts_per_hour: i64,
+    /// Jumlah kiriman komunitas maksimum per pengguna per hari
+    pub community_posts_per_day: i64,
+    /// Jumlah reaksi komunitas maksimum per pengguna per jam
+    pub community_reactions_per_hour: i64,
+    /// Jumlah export data maksimum per pengguna per hari (UTC); 0 berarti tanpa batas
+    pub quota_exports_per_day: i32,
+    /// Jumlah PDF laporan maksimum per pengguna per hari (UTC); 0 berarti tanpa batas
+    pub quota_report_pdfs_per_day: i32,
+    /// Jumlah import maksimum per pengguna per hari (UTC), termasuk dry run; 0 berarti tanpa batas
+    pub quota_imports_per_day: i32,
+    /// Jumlah request lupa/reset password dari satu IP per jendela waktu
+    pub password_reset_rate_limit: u32,
+    /// Jendela waktu rate limit lupa/reset password (menit)
+    pub password_reset_window_minutes: u64,
+    /// Jaringan reverse proxy (CIDR) yang header `Forwarded`/`X-Forwarded-For`-nya dipercaya
+    pub trusted_proxies: Vec<IpNet>,
+    /// Jadwal job retensi jurnal (format cron dengan detik, waktu UTC)
+    pub retention_schedule: String,
+    /// Jeda antara email pemberitahuan dan penghapusan jurnal pertama (hari)
+    pub retention_notice_days: i64,
+    /// Aktifkan endpoint development seperti `/dev/generate`; jangan aktifkan di production
+    pub dev_tools: bool,
+    /// Mulai dalam mode maintenance baca-saja; admin bisa mengubahnya saat server berjalan
+    pub maintenance_mode: bool,
+    /// Nilai `Retry-After` (detik) untuk request tulis yang ditolak saat maintenance
+    pub maintenance_retry_after_secs: u64,
+    /// Batas waktu handler menghasilkan respons, dalam detik; 0 berarti tanpa batas
+    pub request_timeout_secs: u64,
+    /// Jumlah request yang boleh diproses bersamaan; request di atasnya langsung ditolak 503. 0 berarti tanpa batas
+    pub max_concurrent_requests: usize,
+    /// Interval penyimpanan hitungan request API dari memori ke database, dalam detik
+    pub api_usage_flush_secs: u64,
+    /// Berapa lama statistik request API harian disimpan, dalam hari
+    pub api_usage_retention_days: i64,
+    /// Port server gRPC untuk konsumen internal; kosong berarti server gRPC tidak dijalankan
+    pub grpc_port: Option<u16>,
+    /// Token yang wajib dikirim klien gRPC sebagai `authorization: Bearer <token>`
+    pub grpc_auth_token: Option<String>,
+    /// Lingkungan deployment: `development` atau `production`; production mewajibkan SMTP
+    pub app_env: String,
+    /// Host server SMTP; kosong berarti email hanya dicatat ke log (tanpa isi)
+    pub smtp_host: Option<String>,
+    /// Port SMTP; kosong berarti port bawaan mode TLS (587, 465 atau 25)
+    pub smtp_port: Option<u16>,
+    /// Mode koneksi SMTP: `starttls`, `tls` (implicit TLS) atau `none` untuk relay lokal
+    pub smtp_tls: String,
+    pub smtp_username: Option<String>,
+    pub smtp_password: Option<String>,
+    /// Alamat pengirim, misalnya `MindMate <no-reply@mindmate.app>`
+    pub smtp_from: Option<String>,
+    /// Batas waktu koneksi dan perintah SMTP, dalam detik
+    pub smtp_timeout_secs: u64,
+}
+
+impl AppConfig {
+    pub fn from_env() -> Self {
+        AppConfig {
+            database_url: env_opt("DATABASE_URL"),
+            jwt_secret: env_opt("JWT_SECRET"),
+            jwt_key_id: env::var("JWT_KEY_ID").unwrap_or_else(|_| "primary".to_string()),
+            jwt_previous_keys: env_opt("JWT_PREVIOUS_KEYS"),
+            jwt_keys_file: env_opt("JWT_KEYS_FILE"),
+            auth_cookie_enabled: env_flag("AUTH_COOKIE_ENABLED", false),
+            auth_cookie_name: env::var("AUTH_COOKIE_NAME").unwrap_or_else(|_| "mindmate_session".to_string()),
+            auth_cookie_same_site: env::var("AUTH_COOKIE_SAME_SITE").unwrap_or_else(|_| "Lax".to_string()),
+            auth_cookie_secure: env_flag("AUTH_COOKIE_SECURE", true),
+            auth_cookie_domain: env_opt("AUTH_COOKIE_DOMAIN"),
+            auth_exchange_code_ttl_secs: env_parse("AUTH_EXCHANGE_CODE_TTL_SECS", 60),
+            google_client_id: env_opt("GOOGLE_CLIENT_ID"),
+            google_client_secret: env_opt("GOOGLE_CLIENT_SECRET"),
+            google_redirect_uri: env_opt("GOOGLE_REDIRECT_URI"),
+            oauth_token_encryption_key: env_opt("OAUTH_TOKEN_ENCRYPTION_KEY"),
+            frontend_url: env::var("FRONTEND_URL").unwrap_or_else(|_| "https://mind-mate-fe.vercel.app".to_string()),
+            frontend_redirect_whitelist: env::var("FRONTEND_REDIRECT_WHITELIST")
+                .map(|origins| {
+                    origins
+                        .split(',')
+                        .map(|origin| origin.trim().to_string())
+                        .filter(|origin| !origin.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default(),
+            allow_legacy_date_format: env_flag("ALLOW_LEGACY_DATE_FORMAT", false),
+            public_api_url: env::var("PUBLIC_API_URL")
+                .map(|url| url.trim_end_matches('/').to_string())
+                .unwrap_or_default(),
+            token_cleanup_schedule: env::var("TOKEN_CLEANUP_SCHEDULE")
+                .unwrap_or_else(|_| "0 0 3 * * *".to_string()),
+            mood_trash_purge_schedule: env::var("MOOD_TRASH_PURGE_SCHEDULE")
+                .unwrap_or_else(|_| "0 */15 * * * *".to_string()),
+            mood_undo_window_minutes: env_parse("MOOD_UNDO_WINDOW_MINUTES", 10),
+            admin_emails: env::var("ADMIN_EMAILS")
+                .map(|emails| {
+                    emails
+                        .split(',')
+                        .map(|email| email.trim().to_lowercase())
+                        .filter(|email| !email.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default(),
+            http_timeout_secs: env_parse("HTTP_TIMEOUT_SECS", 10),
+            http_connect_timeout_secs: env_parse("HTTP_CONNECT_TIMEOUT_SECS", 5),
+            http_max_retries: env_parse("HTTP_MAX_RETRIES", 2),
+            http_proxy_url: env::var("HTTP_PROXY_URL").ok().filter(|url| !url.trim().is_empty()),
+            database_replica_url: env::var("DATABASE_REPLICA_URL").ok().filter(|url| !url.trim().is_empty()),
+            db_pool_max_size: env_parse("DB_POOL_MAX_SIZE", 10),
+            db_pool_min_idle: env_opt("DB_POOL_MIN_IDLE").and_then(|value| value.trim().parse().ok()),
+            db_connection_timeout_secs: env_parse("DB_CONNECTION_TIMEOUT_SECS", 30),
+            db_statement_timeout_ms: env_parse("DB_STATEMENT_TIMEOUT_MS", 0),
+            db_startup_retries: env_parse("DB_STARTUP_RETRIES", 5),
+            db_startup_retry_secs: env_parse("DB_STARTUP_RETRY_SECS", 2),
+            stats_cache_ttl_secs: env_parse("STATS_CACHE_TTL_SECS", 300),
+            stats_cache_capacity: env_parse("STATS_CACHE_CAPACITY", 10_000),
+            login_max_failures: env_parse("LOGIN_MAX_FAILURES", 5),
+            login_max_failures_per_ip: env_parse("LOGIN_MAX_FAILURES_PER_IP", 20),
+            login_lockout_minutes: env_parse("LOGIN_LOCKOUT_MINUTES", 15),
+            password_min_length: env_parse("PASSWORD_MIN_LENGTH", 8),
+            password_require_lowercase: env_flag("PASSWORD_REQUIRE_LOWERCASE", false),
+            password_require_uppercase: env_flag("PASSWORD_REQUIRE_UPPERCASE", false),
+            password_require_digit: env_flag("PASSWORD_REQUIRE_DIGIT", true),
+            password_require_symbol: env_flag("PASSWORD_REQUIRE_SYMBOL", false),
+            password_min_strength: env_parse::<u8>("PASSWORD_MIN_STRENGTH", 0).min(4),
+            storage_dir: env::var("STORAGE_DIR").unwrap_or_else(|_| "uploads".to_string()),
+            avatar_size: env_parse("AVATAR_SIZE", 256),
+            avatar_max_upload_bytes: env_parse("AVATAR_MAX_UPLOAD_BYTES", 10 * 1024 * 1024),
+            import_max_upload_bytes: env_parse("IMPORT_MAX_UPLOAD_BYTES", 5 * 1024 * 1024),
+            mood_score_low_threshold: env_parse("MOOD_SCORE_LOW_THRESHOLD", 2.5),
+            mood_score_good_threshold: env_parse("MOOD_SCORE_GOOD_THRESHOLD", 3.5),
+            insight_schedule: env::var("INSIGHT_SCHEDULE")
+                .unwrap_or_else(|_| "0 0 9 * * *".to_string()),
+            streak_grace_days: env_parse("STREAK_GRACE_DAYS", 1),
+            insight_low_streak_days: env_parse("INSIGHT_LOW_STREAK_DAYS", 5),
+            insight_recent_days: env_parse("INSIGHT_RECENT_DAYS", 7),
+            insight_baseline_days: env_parse("INSIGHT_BASELINE_DAYS", 28),
+            insight_drop_threshold: env_parse("INSIGHT_DROP_THRESHOLD", 1.0),
+            max_request_body_bytes: env_parse("MAX_REQUEST_BODY_BYTES", 1024 * 1024),
+            journal_title_max_length: env_parse::<usize>("JOURNAL_TITLE_MAX_LENGTH", 500).min(500),
+            journal_content_max_length: env_parse("JOURNAL_CONTENT_MAX_LENGTH", 50_000),
+            mood_notes_max_length: env_parse("MOOD_NOTES_MAX_LENGTH", 2_000),
+            mood_emoji_whitelist: env::var("MOOD_EMOJI_WHITELIST")
+                .unwrap_or_else(|_| DEFAULT_MOOD_EMOJIS.to_string())
+                .split(',')
+                .map(|emoji| emoji.trim().to_string())
+                .filter(|emoji| !emoji.is_empty())
+                .collect(),
+            push_delivery_schedule: env::var("PUSH_DELIVERY_SCHEDULE")
+                .unwrap_or_else(|_| "*/30 * * * * *".to_string()),
+            push_max_attempts: env_parse("PUSH_MAX_ATTEMPTS", 5),
+            reminder_schedule: env::var("REMINDER_SCHEDULE")
+                .unwrap_or_else(|_| "0 * * * * *".to_string()),
+            checkin_token_hours: env_parse("CHECKIN_TOKEN_HOURS", 48),
+            webhook_summary_schedule: env::var("WEBHOOK_SUMMARY_SCHEDULE")
+                .unwrap_or_else(|_| "0 0 8 * * Mon".to_string()),
+            webhook_delivery_schedule: env::var("WEBHOOK_DELIVERY_SCHEDULE")
+                .unwrap_or_else(|_| "30 * * * * *".to_string()),
+            webhook_max_attempts: env_parse("WEBHOOK_MAX_ATTEMPTS", 5),
+            job_backend: env::var("JOB_BACKEND").unwrap_or_else(|_| "postgres".to_string()),
+            job_claim_ttl_secs: env_parse("JOB_CLAIM_TTL_SECS", 600),
+            redis_url: env_opt("REDIS_URL"),
+            redis_key_prefix: env::var("REDIS_KEY_PREFIX").unwrap_or_else(|_| "mindmate".to_string()),
+            redis_timeout_secs: env_parse("REDIS_TIMEOUT_SECS", 2),
+            job_worker_schedule: env::var("JOB_WORKER_SCHEDULE")
+                .unwrap_or_else(|_| "*/5 * * * * *".to_string()),
+            job_result_ttl_hours: env_parse("JOB_RESULT_TTL_HOURS", 24),
+            job_timeout_minutes: env_parse("JOB_TIMEOUT_MINUTES", 30),
+            appointment_reminder_schedule: env::var("APPOINTMENT_REMINDER_SCHEDULE")
+                .unwrap_or_else(|_| "0 */5 * * * *".to_string()),
+            fcm_project_id: env_opt("FCM_PROJECT_ID"),
+            fcm_client_email: env_opt("FCM_CLIENT_EMAIL"),
+            fcm_private_key: env_opt("FCM_PRIVATE_KEY").map(|key| key.replace("\\n", "\n")),
+            apns_key_id: env_opt("APNS_KEY_ID"),
+            apns_team_id: env_opt("APNS_TEAM_ID"),
+            apns_private_key: env_opt("APNS_PRIVATE_KEY").map(|key| key.replace("\\n", "\n")),
+            apns_topic: env_opt("APNS_TOPIC"),
+            apns_sandbox: env_flag("APNS_SANDBOX", false),
+            backup_schedule: env_opt("BACKUP_SCHEDULE"),
+            impersonation_token_minutes: env_parse("IMPERSONATION_TOKEN_MINUTES", 15),
+            captcha_provider: env_opt("CAPTCHA_PROVIDER"),
+            captcha_secret: env_opt("CAPTCHA_SECRET"),
+            captcha_threshold: env_parse("CAPTCHA_THRESHOLD", 3),
+            captcha_window_minutes: env_parse("CAPTCHA_WINDOW_MINUTES", 60),
+            moderation_banned_terms: env::var("MODERATION_BANNED_TERMS")
+                .map(|terms| {
+                    terms
+                        .split(',')
+                        .map(|term| term.trim().to_string())
+                        .filter(|term| !term.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default(),
+            moderation_banned_terms_action: env::var("MODERATION_BANNED_TERMS_ACTION")
+                .unwrap_or_else(|_| "flag".to_string()),
+            moderation_api_url: env_opt("MODERATION_API_URL"),
+            moderation_api_key: env_opt("MODERATION_API_KEY"),
+            community_premoderation: env_flag("COMMUNITY_PREMODERATION", true),
+            community_posts_per_hour: env_parse("COMMUNITY_POSTS_PER_HOUR", 3),
+            community_posts_per_day: env_parse("COMMUNITY_POSTS_PER_DAY", 10),
+            community_reactions_per_hour: env_parse("COMMUNITY_REACTIONS_PER_HOUR", 60),
+            quota_exports_per_day: env_parse("QUOTA_EXPORTS_PER_DAY", 10),
+            quota_report_pdfs_per_day: env_parse("QUOTA_REPORT_PDFS_PER_DAY", 20),
+            quota_imports_per_day: env_parse("QUOTA_IMPORTS_PER_DAY", 5),
+            password_reset_rate_limit: env_parse("PASSWORD_RESET_RATE_LIMIT", 5),
+            password_reset_window_minutes: env_parse("PASSWORD_RESET_WINDOW_MINUTES", 15),
+            trusted_proxies: parse_trusted_proxies(
+                &env::var("TRUSTED_PROXIES").unwrap_or_else(|_| DEFAULT_TRUSTED_PROXIES.to_string()),
+            ),
+            retention_schedule: env::var("RETENTION_SCHEDULE")
+                .unwrap_or_else(|_| "0 30 2 * * *".to_string()),
+            retention_notice_days: env_parse("RETENTION_NOTICE_DAYS", 7),
+            dev_tools: env_flag("DEV_TOOLS", false),
+            maintenance_mode: env_flag("MAINTENANCE_MODE", false),
+            maintenance_retry_after_secs: env_parse("MAINTENANCE_RETRY_AFTER_SECS", 300),
+            request_timeout_secs: env_parse("REQUEST_TIMEOUT_SECS", 30),
+            max_concurrent_requests: env_parse("MAX_CONCURRENT_REQUESTS", 256),
+            api_usage_flush_secs: env_parse("API_USAGE_FLUSH_SECS", 60),
+            api_usage_retention_days: env_parse("API_USAGE_RETENTION_DAYS", 90),
+            grpc_port: env_opt("GRPC_PORT").and_then(|value| value.trim().parse().ok()),
+            grpc_auth_token: env_opt("GRPC_AUTH_TOKEN"),
+            app_env: env::var("APP_ENV").unwrap_or_else(|_| "development".to_string()),
+            smtp_host: env_opt("SMTP_HOST"),
+            smtp_port: env_opt("SMTP_PORT").and_then(|value| value.trim().parse().ok()),
+            smtp_tls: env::var("SMTP_TLS").unwrap_or_else(|_| "starttls".to_string()),
+            smtp_username: env_opt("SMTP_USERNAME"),
+            smtp_password: env_opt("SMTP_PASSWORD"),
+            smtp_from: env_opt("SMTP_FROM"),
+            smtp_timeout_secs: env_parse("SMTP_TIMEOUT_SECS", 10),
+        }
+    }
+}
+
+impl AppConfig {
+    pub fn is_admin_email(&self, email: &str) -> bool {
+        let email = email.to_lowercase();
+        self.admin_emails.contains(&email)
+    }
+
+    pub fn is_production(&self) -> bool {
+        self.app_env == "production"
+    }
+}
+
+/// Konfigurasi global, dibaca sekali dari environment saat pertama kali dipakai
+pub fn app_config() -> &'static AppConfig {
+    static CONFIG: OnceLock<AppConfig> = OnceLock::new();
+    CONFIG.get_or_init(AppConfig::from_env)
+}
+
+fn env_flag(name: &str, default: bool) -> bool {
+    match env::var(name) {
+        Ok(value) => matches!(value.trim().to_lowercase().as_str(), "1" | "true" | "yes" | "on"),
+        Err(_) => default,
+    }
+}
+
+fn env_parse<T: std::str::FromStr>(name: &str, default: T) -> T {
+    env::var(name)
+        .ok()
+        .and_then(|value| value.trim().parse().ok())
+        .unwrap_or(default)
+}
+
+const DEFAULT_MOOD_EMOJIS: &str = "😭,😢,😞,😔,😟,😰,😡,😴,😐,😶,🙂,😊,😌,😄,😁,🥰,🤩";
+
+/// Loopback dan jaringan privat, tempat reverse proxy (Railway) berada
+const DEFAULT_TRUSTED_PROXIES: &str = "127.0.0.0/8,::1/128,10.0.0.0/8,172.16.0.0/12,192.168.0.0/16,fc00::/7";
+
+/// Daftar CIDR dipisah koma; alamat tanpa prefix dianggap satu host
+fn parse_trusted_proxies(value: &str) -> Vec<IpNet> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .filter_map(|entry| {
+            let parsed = entry
+                .parse::<IpNet>()
+                .or_else(|_| entry.parse::<std::net::IpAddr>().map(IpNet::from));
+            if parsed.is_err() {
+                eprintln!("⚠️ Ignoring invalid TRUSTED_PROXIES entry: {}", entry);
+            }
+            parsed.ok()
+        })
+        .collect()
+}
+
+fn env_opt(name: &str) -> Option<String> {
+    env::var(name).ok().filter(|value| !value.trim().is_empty())
+}
\ No newline at end of file
@@ -1 +1,2 @@
-pub mod app_config;
\ No newline at end of file
+pub mod app_config;
+pub mod startup_check;
\ No newline at end of file
@@ -0,0 +1,206 @@
+use lettre::message::Mailbox;
+use url::Url;
+use crate::config::app_config::AppConfig;
+use crate::utils::jwt::JwtKeyring;
+use crate::utils::redirect::RedirectWhitelist;
+use crate::utils::token_crypto::TokenCipher;
+
+/// Panjang minimum JWT_SECRET; HS256 butuh kunci minimal 256 bit
+pub const MIN_JWT_SECRET_LENGTH: usize = 32;
+
+/// Periksa konfigurasi sebelum server start. Hasilnya daftar masalah yang bisa dibaca operator;
+/// kosong berarti konfigurasi lengkap.
+pub fn check(config: &AppConfig) -> Vec<String> {
+    let mut problems = Vec::new();
+
+    if config.database_url.is_none() {
+        problems.push("DATABASE_URL is not set".to_string());
+    }
+
+    match config.jwt_secret {
+        None => problems.push("JWT_SECRET is not set".to_string()),
+        Some(ref secret) if secret.len() < MIN_JWT_SECRET_LENGTH => problems.push(format!(
+            "JWT_SECRET must be at least {} bytes long (got {})",
+            MIN_JWT_SECRET_LENGTH,
+            secret.len()
+        )),
+        Some(_) => {}
+    }
+    if let Err(e) = JwtKeyring::from_config(config) {
+        problems.push(e);
+    }
+
+    check_all_or_none(
+        &mut problems,
+        "Google login",
+        &[
+            ("GOOGLE_CLIENT_ID", &config.google_client_id),
+            ("GOOGLE_CLIENT_SECRET", &config.google_client_secret),
+            ("GOOGLE_REDIRECT_URI", &config.google_redirect_uri),
+        ],
+    );
+    if let Some(ref redirect_uri) = config.google_redirect_uri {
+        if !Url::parse(redirect_uri).is_ok_and(|url| matches!(url.scheme(), "http" | "https")) {
+            problems.push(format!("GOOGLE_REDIRECT_URI is not a valid http(s) URL: {}", redirect_uri));
+        }
+    }
+
+    if let Err(e) = RedirectWhitelist::from_config(config) {
+        problems.push(e);
+    }
+    if let Err(e) = TokenCipher::from_config(config) {
+        problems.push(e);
+    }
+
+    check_all_or_none(
+        &mut problems,
+        "FCM push",
+        &[
+            ("FCM_PROJECT_ID", &config.fcm_project_id),
+            ("FCM_CLIENT_EMAIL", &config.fcm_client_email),
+            ("FCM_PRIVATE_KEY", &config.fcm_private_key),
+        ],
+    );
+    check_all_or_none(
+        &mut problems,
+        "APNs push",
+        &[
+            ("APNS_KEY_ID", &config.apns_key_id),
+            ("APNS_TEAM_ID", &config.apns_team_id),
+            ("APNS_PRIVATE_KEY", &config.apns_private_key),
+            ("APNS_TOPIC", &config.apns_topic),
+        ],
+    );
+    check_all_or_none(
+        &mut problems,
+        "CAPTCHA",
+        &[
+            ("CAPTCHA_PROVIDER", &config.captcha_provider),
+            ("CAPTCHA_SECRET", &config.captcha_secret),
+        ],
+    );
+
+    if config.auth_cookie_enabled {
+        match config.auth_cookie_same_site.as_str() {
+            "Strict" | "Lax" => {}
+            "None" if !config.auth_cookie_secure => {
+                problems.push("AUTH_COOKIE_SAME_SITE=None requires AUTH_COOKIE_SECURE".to_string())
+            }
+            "None" => {}
+            other => problems.push(format!("AUTH_COOKIE_SAME_SITE must be Strict, Lax or None (got {})", other)),
+        }
+    }
+
+    match config.app_env.as_str() {
+        "development" | "production" => {}
+        other => problems.push(format!("APP_ENV must be development or production (got {})", other)),
+    }
+    check_all_or_none(
+        &mut problems,
+        "SMTP",
+        &[("SMTP_HOST", &config.smtp_host), ("SMTP_FROM", &config.smtp_from)],
+    );
+    check_all_or_none(
+        &mut problems,
+        "SMTP authentication",
+        &[("SMTP_USERNAME", &config.smtp_username), ("SMTP_PASSWORD", &config.smtp_password)],
+    );
+    if let Some(ref from) = config.smtp_from {
+        if from.parse::<Mailbox>().is_err() {
+            problems.push(format!("SMTP_FROM is not a valid email address: {}", from));
+        }
+    }
+    if !matches!(config.smtp_tls.as_str(), "starttls" | "tls" | "none") {
+        problems.push(format!("SMTP_TLS must be starttls, tls or none (got {})", config.smtp_tls));
+    }
+    if config.is_production() && config.smtp_host.is_none() {
+        problems.push("APP_ENV=production requires SMTP_HOST; emails would not be delivered".to_string());
+    }
+
+    if config.grpc_port.is_some() && config.grpc_auth_token.is_none() {
+        problems.push("GRPC_PORT requires GRPC_AUTH_TOKEN".to_string());
+    }
+
+    match config.job_backend.as_str() {
+        "postgres" | "tokio" => {}
+        "redis" if config.redis_url.is_none() => problems.push("JOB_BACKEND=redis requires REDIS_URL".to_string()),
+        "redis" => {}
+        other => problems.push(format!("JOB_BACKEND must be postgres, tokio or redis (got {})", other)),
+    }
+
+    problems
+}
+
+/// Fitur opsional aktif jika salah satu variabelnya diisi; saat itu semua variabelnya wajib
+fn check_all_or_none(problems: &mut Vec<String>, feature: &str, vars: &[(&str, &Option<String>)]) {
+    let missing: Vec<&str> = vars
+        .iter()
+        .filter(|(_, value)| value.is_none())
+        .map(|(name, _)| *name)
+        .collect();
+
+    if !missing.is_empty() && missing.len() < vars.len() {
+        problems.push(format!("{} is partially configured, missing {}", feature, missing.join(", ")));
+    }
+}
+
+/// Laporan untuk ditampilkan sebelum proses berhenti
+pub fn report(problems: &[String]) -> String {
+    let mut out = String::from("❌ Invalid configuration, refusing to start:");
+    for problem in problems {
+        out.push_str("\n   - ");
+        out.push_str(problem);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_missing_and_partial_settings() {
+        let mut config = AppConfig::from_env();
+        config.database_url = Some("postgres://localhost/mindmate".to_string());
+        config.jwt_secret = Some("x".repeat(MIN_JWT_SECRET_LENGTH));
+        config.google_client_id = None;
+        config.google_client_secret = None;
+        config.google_redirect_uri = None;
+        config.fcm_project_id = None;
+        config.fcm_client_email = None;
+        config.fcm_private_key = None;
+        config.apns_key_id = None;
+        config.apns_team_id = None;
+        config.apns_private_key = None;
+        config.apns_topic = None;
+        config.captcha_provider = None;
+        config.captcha_secret = None;
+        config.job_backend = "postgres".to_string();
+        config.grpc_port = None;
+        config.app_env = "development".to_string();
+        config.smtp_host = None;
+        config.smtp_from = None;
+        config.smtp_username = None;
+        config.smtp_password = None;
+        config.smtp_tls = "starttls".to_string();
+        assert!(check(&config).is_empty());
+
+        config.app_env = "production".to_string();
+        assert_eq!(check(&config), vec!["APP_ENV=production requires SMTP_HOST; emails would not be delivered".to_string()]);
+        config.smtp_host = Some("smtp.example.com".to_string());
+        config.smtp_from = Some("MindMate <no-reply@example.com>".to_string());
+        assert!(check(&config).is_empty());
+
+        config.jwt_secret = Some("short".to_string());
+        config.google_client_id = Some("client".to_string());
+        config.google_redirect_uri = Some("not a url".to_string());
+        assert_eq!(
+            check(&config),
+            vec![
+                "JWT_SECRET must be at least 32 bytes long (got 5)".to_string(),
+                "Google login is partially configured, missing GOOGLE_CLIENT_SECRET".to_string(),
+                "GOOGLE_REDIRECT_URI is not a valid http(s) URL: not a url".to_string(),
+            ]
+        );
+    }
+}
@@ -0,0 +1,103 @@
+use diesel::prelude::*;
+use diesel::pg::PgConnection;
+use diesel::sql_types::{BigInt, Date};
+use chrono::NaiveDate;
+use crate::errors::app_error::AppError;
+
+// Aktivitas pengguna = mood atau jurnal yang dibuat, dikelompokkan per hari UTC
+const ACTIVITY_DAYS: &str = "
+    SELECT user_id, created_at::date AS day FROM moods
+    UNION
+    SELECT user_id, created_at::date AS day FROM journals";
+
+#[derive(Debug, QueryableByName)]
+pub struct WeeklyActivityRow {
+    #[diesel(sql_type = Date)]
+    pub week_start: NaiveDate,
+    #[diesel(sql_type = BigInt)]
+    pub active_users: i64,
+    #[diesel(sql_type = BigInt)]
+    pub entries: i64,
+}
+
+#[derive(Debug, QueryableByName)]
+pub struct RetentionCohortRow {
+    #[diesel(sql_type = Date)]
+    pub signup_week: NaiveDate,
+    #[diesel(sql_type = BigInt)]
+    pub users: i64,
+    #[diesel(sql_type = BigInt)]
+    pub d1_eligible: i64,
+    #[diesel(sql_type = BigInt)]
+    pub d1_retained: i64,
+    #[diesel(sql_type = BigInt)]
+    pub d7_eligible: i64,
+    #[diesel(sql_type = BigInt)]
+    pub d7_retained: i64,
+    #[diesel(sql_type = BigInt)]
+    pub d30_eligible: i64,
+    #[diesel(sql_type = BigInt)]
+    pub d30_retained: i64,
+}
+
+/// Pengguna aktif dan jumlah entri per minggu mulai `since`; minggu tanpa aktivitas tidak muncul
+pub fn weekly_activity(
+    conn: &mut PgConnection,
+    since: NaiveDate,
+) -> Result<Vec<WeeklyActivityRow>, AppError> {
+    diesel::sql_query(
+        "SELECT date_trunc('week', created_at)::date AS week_start,
+                COUNT(DISTINCT user_id) AS active_users,
+                COUNT(*) AS entries
+         FROM (
+             SELECT user_id, created_at FROM moods WHERE created_at >= $1
+             UNION ALL
+             SELECT user_id, created_at FROM journals WHERE created_at >= $1
+         ) activity
+         GROUP BY 1
+         ORDER BY 1",
+    )
+    .bind::<Date, _>(since)
+    .load(conn)
+    .map_err(AppError::from)
+}
+
+/// Kohort pendaftaran per minggu mulai `since` dengan jumlah pengguna yang kembali aktif
+/// pada hari ke-1, 7 dan 30. Pengguna yang belum mencapai hari ke-N per `today` tidak dihitung.
+pub fn retention_cohorts(
+    conn: &mut PgConnection,
+    since: NaiveDate,
+    today: NaiveDate,
+) -> Result<Vec<RetentionCohortRow>, AppError> {
+    diesel::sql_query(format!(
+        "WITH activity AS ({activity}),
+         cohort AS (
+             SELECT id AS user_id, created_at::date AS signup_day FROM users WHERE created_at >= $1
+         )
+         SELECT date_trunc('week', c.signup_day)::date AS signup_week,
+                COUNT(*) AS users,
+                {d1},
+                {d7},
+                {d30}
+         FROM cohort c
+         GROUP BY 1
+         ORDER BY 1",
+        activity = ACTIVITY_DAYS,
+        d1 = retention_columns(1),
+        d7 = retention_columns(7),
+        d30 = retention_columns(30),
+    ))
+    .bind::<Date, _>(since)
+    .bind::<Date, _>(today)
+    .load(conn)
+    .map_err(AppError::from)
+}
+
+fn retention_columns(day: u32) -> String {
+    format!(
+        "COUNT(*) FILTER (WHERE c.signup_day + {day} <= $2) AS d{day}_eligible,
+         COUNT(*) FILTER (WHERE c.signup_day + {day} <= $2 AND EXISTS (
+             SELECT 1 FROM activity a WHERE a.user_id = c.user_id AND a.day = c.signup_day + {day}
+         )) AS d{day}_retained"
+    )
+}
@@ -0,0 +1,47 @@
+use chrono::{NaiveDate, Utc};
+use diesel::prelude::*;
+use diesel::pg::PgConnection;
+use diesel::upsert::excluded;
+use crate::errors::app_error::AppError;
+use crate::models::api_usage::NewApiUsageDay;
+use crate::schema::api_usage_daily;
+
+/// Tambahkan hitungan request ke baris harian yang sudah ada, atau buat barisnya
+pub fn add_counts(
+    conn: &mut PgConnection,
+    rows: &[NewApiUsageDay],
+) -> Result<usize, AppError> {
+    diesel::insert_into(api_usage_daily::table)
+        .values(rows)
+        .on_conflict((api_usage_daily::user_id, api_usage_daily::day))
+        .do_update()
+        .set((
+            api_usage_daily::request_count.eq(api_usage_daily::request_count + excluded(api_usage_daily::request_count)),
+            api_usage_daily::updated_at.eq(Utc::now().naive_utc()),
+        ))
+        .execute(conn)
+        .map_err(AppError::from)
+}
+
+/// Jumlah request per hari milik `user_id` sejak `since` (inklusif)
+pub fn find_user_days(
+    conn: &mut PgConnection,
+    user_id: i32,
+    since: NaiveDate,
+) -> Result<Vec<(NaiveDate, i64)>, AppError> {
+    api_usage_daily::table
+        .filter(api_usage_daily::user_id.eq(user_id))
+        .filter(api_usage_daily::day.ge(since))
+        .select((api_usage_daily::day, api_usage_daily::request_count))
+        .load(conn)
+        .map_err(AppError::from)
+}
+
+pub fn delete_before(
+    conn: &mut PgConnection,
+    before: NaiveDate,
+) -> Result<usize, AppError> {
+    diesel::delete(api_usage_daily::table.filter(api_usage_daily::day.lt(before)))
+        .execute(conn)
+        .map_err(AppError::from)
+}
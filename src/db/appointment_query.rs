@@ -0,0 +1,199 @@
+use diesel::dsl::exists;
+use diesel::prelude::*;
+use diesel::pg::PgConnection;
+use chrono::{Duration, NaiveDateTime};
+use crate::errors::app_error::AppError;
+use crate::models::appointment::{Appointment, AppointmentReminder, AppointmentStatus, NewAppointment};
+use crate::schema::{appointments, psychologists, users};
+
+pub fn create_appointment(
+    conn: &mut PgConnection,
+    appointment: &NewAppointment,
+) -> Result<Appointment, AppError> {
+    diesel::insert_into(appointments::table)
+        .values(appointment)
+        .returning(Appointment::as_returning())
+        .get_result(conn)
+        .map_err(AppError::from)
+}
+
+/// Janji temu milik pengguna dengan kunci baris sampai transaksi selesai
+pub fn find_user_appointment_for_update(
+    conn: &mut PgConnection,
+    user_id: i32,
+    appointment_id: i32,
+) -> Result<Option<Appointment>, AppError> {
+    appointments::table
+        .find(appointment_id)
+        .filter(appointments::user_id.eq(user_id))
+        .select(Appointment::as_select())
+        .for_update()
+        .first(conn)
+        .optional()
+        .map_err(AppError::from)
+}
+
+/// Semua janji temu pengguna beserta nama psikolognya, yang terbaru lebih dulu
+pub fn find_appointments_by_user(
+    conn: &mut PgConnection,
+    user_id: i32,
+) -> Result<Vec<(Appointment, String)>, AppError> {
+    appointments::table
+        .inner_join(psychologists::table)
+        .filter(appointments::user_id.eq(user_id))
+        .order(appointments::starts_at.desc())
+        .select((Appointment::as_select(), psychologists::name))
+        .load(conn)
+        .map_err(AppError::from)
+}
+
+pub fn reschedule_appointment(
+    conn: &mut PgConnection,
+    appointment_id: i32,
+    starts_at: NaiveDateTime,
+    ends_at: NaiveDateTime,
+    now: NaiveDateTime,
+) -> Result<Appointment, AppError> {
+    // Pengingat dihitung ulang dari waktu yang baru
+    diesel::update(appointments::table.find(appointment_id))
+        .set((
+            appointments::starts_at.eq(starts_at),
+            appointments::ends_at.eq(ends_at),
+            appointments::reminder_24h_sent_at.eq(None::<NaiveDateTime>),
+            appointments::reminder_1h_sent_at.eq(None::<NaiveDateTime>),
+            appointments::updated_at.eq(now),
+        ))
+        .returning(Appointment::as_returning())
+        .get_result(conn)
+        .map_err(AppError::from)
+}
+
+pub fn cancel_appointment(
+    conn: &mut PgConnection,
+    appointment_id: i32,
+    now: NaiveDateTime,
+) -> Result<Appointment, AppError> {
+    diesel::update(appointments::table.find(appointment_id))
+        .set((
+            appointments::status.eq(AppointmentStatus::Cancelled.as_str()),
+            appointments::updated_at.eq(now),
+        ))
+        .returning(Appointment::as_returning())
+        .get_result(conn)
+        .map_err(AppError::from)
+}
+
+/// Janji temu terjadwal yang sudah masuk jendela pengingat dan belum diingatkan.
+/// Pengingat 24 jam dilewati jika janji sudah kurang dari satu jam lagi.
+pub fn find_due_reminders(
+    conn: &mut PgConnection,
+    reminder: AppointmentReminder,
+    now: NaiveDateTime,
+) -> Result<Vec<(Appointment, String)>, AppError> {
+    let query = appointments::table
+        .inner_join(psychologists::table)
+        .filter(appointments::status.eq(AppointmentStatus::Scheduled.as_str()))
+        .filter(appointments::starts_at.gt(now))
+        .select((Appointment::as_select(), psychologists::name))
+        .into_boxed();
+
+    let query = match reminder {
+        AppointmentReminder::DayBefore => query
+            .filter(appointments::reminder_24h_sent_at.is_null())
+            .filter(appointments::starts_at.gt(now + Duration::hours(1)))
+            .filter(appointments::starts_at.le(now + Duration::hours(24))),
+        AppointmentReminder::HourBefore => query
+            .filter(appointments::reminder_1h_sent_at.is_null())
+            .filter(appointments::starts_at.le(now + Duration::hours(1))),
+    };
+
+    query
+        .load(conn)
+        .map_err(AppError::from)
+}
+
+/// Tandai pengingat terkirim; `false` jika instance lain sudah lebih dulu menandainya
+pub fn claim_reminder(
+    conn: &mut PgConnection,
+    appointment_id: i32,
+    reminder: AppointmentReminder,
+    now: NaiveDateTime,
+) -> Result<bool, AppError> {
+    let updated = match reminder {
+        AppointmentReminder::DayBefore => diesel::update(
+            appointments::table
+                .find(appointment_id)
+                .filter(appointments::reminder_24h_sent_at.is_null()),
+        )
+        .set(appointments::reminder_24h_sent_at.eq(now))
+        .execute(conn),
+        AppointmentReminder::HourBefore => diesel::update(
+            appointments::table
+                .find(appointment_id)
+                .filter(appointments::reminder_1h_sent_at.is_null()),
+        )
+        .set(appointments::reminder_1h_sent_at.eq(now))
+        .execute(conn),
+    }
+    .map_err(AppError::from)?;
+
+    // Pengingat 24 jam yang belum terkirim tidak perlu dikirim lagi setelah pengingat 1 jam
+    if updated > 0 && reminder == AppointmentReminder::HourBefore {
+        diesel::update(
+            appointments::table
+                .find(appointment_id)
+                .filter(appointments::reminder_24h_sent_at.is_null()),
+        )
+        .set(appointments::reminder_24h_sent_at.eq(now))
+        .execute(conn)
+        .map_err(AppError::from)?;
+    }
+
+    Ok(updated > 0)
+}
+
+/// Pengguna dan psikolog punya janji temu yang tidak dibatalkan
+pub fn has_confirmed_relationship(
+    conn: &mut PgConnection,
+    user_id: i32,
+    psychologist_id: i32,
+) -> Result<bool, AppError> {
+    diesel::select(exists(
+        appointments::table
+            .filter(appointments::user_id.eq(user_id))
+            .filter(appointments::psychologist_id.eq(psychologist_id))
+            .filter(appointments::status.ne(AppointmentStatus::Cancelled.as_str())),
+    ))
+    .get_result(conn)
+    .map_err(AppError::from)
+}
+
+/// Psikolog (id, nama) yang pernah dikonfirmasi untuk pengguna
+pub fn find_confirmed_psychologists(
+    conn: &mut PgConnection,
+    user_id: i32,
+) -> Result<Vec<(i32, String)>, AppError> {
+    appointments::table
+        .inner_join(psychologists::table)
+        .filter(appointments::user_id.eq(user_id))
+        .filter(appointments::status.ne(AppointmentStatus::Cancelled.as_str()))
+        .select((psychologists::id, psychologists::name))
+        .distinct()
+        .load(conn)
+        .map_err(AppError::from)
+}
+
+/// Klien (id, username) yang punya janji temu terkonfirmasi dengan psikolog
+pub fn find_confirmed_clients(
+    conn: &mut PgConnection,
+    psychologist_id: i32,
+) -> Result<Vec<(i32, String)>, AppError> {
+    appointments::table
+        .inner_join(users::table)
+        .filter(appointments::psychologist_id.eq(psychologist_id))
+        .filter(appointments::status.ne(AppointmentStatus::Cancelled.as_str()))
+        .select((users::id, users::username))
+        .distinct()
+        .load(conn)
+        .map_err(AppError::from)
+}
@@ -0,0 +1,41 @@
+use diesel::prelude::*;
+use diesel::pg::PgConnection;
+use crate::models::audit::{AuditLog, NewAuditLog};
+use crate::errors::app_error::AppError;
+use crate::schema::audit_logs;
+
+pub fn insert_audit_log(
+    conn: &mut PgConnection,
+    entry: &NewAuditLog,
+) -> Result<(), AppError> {
+    diesel::insert_into(audit_logs::table)
+        .values(entry)
+        .execute(conn)
+        .map_err(AppError::from)?;
+
+    Ok(())
+}
+
+/// Entri audit terbaru, opsional hanya yang melibatkan satu pengguna (pelaku atau target)
+pub fn find_audit_logs(
+    conn: &mut PgConnection,
+    user_id: Option<i32>,
+    limit: i64,
+) -> Result<Vec<AuditLog>, AppError> {
+    let mut query = audit_logs::table
+        .order(audit_logs::created_at.desc())
+        .limit(limit)
+        .select(AuditLog::as_select())
+        .into_boxed();
+
+    if let Some(user_id) = user_id {
+        query = query.filter(
+            audit_logs::actor_user_id.eq(user_id)
+                .or(audit_logs::target_user_id.eq(user_id)),
+        );
+    }
+
+    query
+        .load::<AuditLog>(conn)
+        .map_err(AppError::from)
+}
@@ -0,0 +1,38 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use diesel::pg::PgConnection;
+use crate::errors::app_error::AppError;
+use crate::models::google_auth::{AuthExchangeCode, NewAuthExchangeCode};
+use crate::schema::auth_exchange_codes;
+
+pub fn insert_code(
+    conn: &mut PgConnection,
+    code: &NewAuthExchangeCode,
+) -> Result<AuthExchangeCode, AppError> {
+    diesel::insert_into(auth_exchange_codes::table)
+        .values(code)
+        .returning(AuthExchangeCode::as_returning())
+        .get_result(conn)
+        .map_err(AppError::from)
+}
+
+/// Ambil dan hapus kode sekaligus, agar kode hanya bisa ditukar sekali walaupun dipakai bersamaan
+pub fn take_code(
+    conn: &mut PgConnection,
+    code: &str,
+) -> Result<Option<AuthExchangeCode>, AppError> {
+    diesel::delete(auth_exchange_codes::table.filter(auth_exchange_codes::code.eq(code)))
+        .returning(AuthExchangeCode::as_returning())
+        .get_result(conn)
+        .optional()
+        .map_err(AppError::from)
+}
+
+pub fn delete_expired(
+    conn: &mut PgConnection,
+    now: NaiveDateTime,
+) -> Result<usize, AppError> {
+    diesel::delete(auth_exchange_codes::table.filter(auth_exchange_codes::expires_at.lt(now)))
+        .execute(conn)
+        .map_err(AppError::from)
+}
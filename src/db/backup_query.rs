@@ -0,0 +1,56 @@
+use diesel::prelude::*;
+use diesel::pg::PgConnection;
+use diesel::sql_types::{BigInt, Text};
+use crate::errors::app_error::AppError;
+
+// Nama tabel disisipkan langsung ke SQL; pemanggil wajib memakai nama dari
+// daftar tetap `backup_service::BACKUP_TABLES`, bukan input pengguna.
+
+#[derive(QueryableByName)]
+struct TableDump {
+    #[diesel(sql_type = BigInt)]
+    count: i64,
+    #[diesel(sql_type = Text)]
+    rows: String,
+}
+
+/// Semua baris tabel sebagai array JSON, beserta jumlah barisnya
+pub fn dump_table(conn: &mut PgConnection, table: &str) -> Result<(i64, String), AppError> {
+    let dump = diesel::sql_query(format!(
+        "SELECT COUNT(*) AS count, COALESCE(json_agg(t), '[]'::json)::text AS rows FROM {} t",
+        table
+    ))
+    .get_result::<TableDump>(conn)
+    .map_err(AppError::from)?;
+    Ok((dump.count, dump.rows))
+}
+
+/// Kosongkan beberapa tabel sekaligus; gagal jika tabel lain masih mereferensikannya
+pub fn truncate_tables(conn: &mut PgConnection, tables: &[&str]) -> Result<(), AppError> {
+    diesel::sql_query(format!("TRUNCATE {}", tables.join(", ")))
+        .execute(conn)
+        .map(|_| ())
+        .map_err(AppError::from)
+}
+
+/// Masukkan baris dari array JSON hasil `dump_table`
+pub fn restore_table(conn: &mut PgConnection, table: &str, rows: &str) -> Result<usize, AppError> {
+    diesel::sql_query(format!(
+        "INSERT INTO {0} SELECT * FROM json_populate_recordset(NULL::{0}, $1::json)",
+        table
+    ))
+    .bind::<Text, _>(rows)
+    .execute(conn)
+    .map_err(AppError::from)
+}
+
+/// Samakan sequence kolom `id` dengan data yang baru dipulihkan
+pub fn reset_id_sequence(conn: &mut PgConnection, table: &str) -> Result<(), AppError> {
+    diesel::sql_query(format!(
+        "SELECT setval(pg_get_serial_sequence('{0}', 'id'), COALESCE(MAX(id), 0) + 1, false) FROM {0}",
+        table
+    ))
+    .execute(conn)
+    .map(|_| ())
+    .map_err(AppError::from)
+}
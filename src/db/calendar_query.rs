@@ -0,0 +1,57 @@
+use diesel::prelude::*;
+use diesel::pg::PgConnection;
+use crate::errors::app_error::AppError;
+use crate::models::calendar::{CalendarFeedToken, NewCalendarFeedToken};
+use crate::schema::calendar_feed_tokens;
+use chrono::Utc;
+
+pub fn find_token_by_user(
+    conn: &mut PgConnection,
+    user_id: i32,
+) -> Result<Option<CalendarFeedToken>, AppError> {
+    calendar_feed_tokens::table
+        .filter(calendar_feed_tokens::user_id.eq(user_id))
+        .select(CalendarFeedToken::as_select())
+        .first(conn)
+        .optional()
+        .map_err(AppError::from)
+}
+
+pub fn find_token(
+    conn: &mut PgConnection,
+    token_str: &str,
+) -> Result<CalendarFeedToken, AppError> {
+    calendar_feed_tokens::table
+        .filter(calendar_feed_tokens::token.eq(token_str))
+        .select(CalendarFeedToken::as_select())
+        .first(conn)
+        .map_err(|e| match e {
+            diesel::result::Error::NotFound => AppError::Unauthorized("Invalid calendar token".to_string()),
+            _ => AppError::from(e),
+        })
+}
+
+/// Simpan token baru untuk user, menggantikan token lama jika ada
+pub fn upsert_token(
+    conn: &mut PgConnection,
+    user_id: i32,
+    token_str: &str,
+) -> Result<CalendarFeedToken, AppError> {
+    let new_token = NewCalendarFeedToken {
+        user_id,
+        token: token_str.to_string(),
+        created_at: Utc::now().naive_utc(),
+    };
+
+    diesel::insert_into(calendar_feed_tokens::table)
+        .values(&new_token)
+        .on_conflict(calendar_feed_tokens::user_id)
+        .do_update()
+        .set((
+            calendar_feed_tokens::token.eq(&new_token.token),
+            calendar_feed_tokens::created_at.eq(new_token.created_at),
+        ))
+        .returning(CalendarFeedToken::as_returning())
+        .get_result(conn)
+        .map_err(AppError::from)
+}
@@ -0,0 +1,32 @@
+use diesel::prelude::*;
+use diesel::pg::PgConnection;
+use chrono::NaiveDateTime;
+use crate::errors::app_error::AppError;
+use crate::schema::used_checkin_tokens;
+
+/// Tandai token check-in sudah dipakai; `false` jika token ini pernah dipakai sebelumnya
+pub fn claim_token(
+    conn: &mut PgConnection,
+    jti: &str,
+    user_id: i32,
+) -> Result<bool, AppError> {
+    diesel::insert_into(used_checkin_tokens::table)
+        .values((
+            used_checkin_tokens::jti.eq(jti),
+            used_checkin_tokens::user_id.eq(user_id),
+        ))
+        .on_conflict_do_nothing()
+        .execute(conn)
+        .map(|inserted| inserted == 1)
+        .map_err(AppError::from)
+}
+
+/// Hapus catatan token yang sudah pasti kedaluwarsa
+pub fn delete_used_before(
+    conn: &mut PgConnection,
+    cutoff: NaiveDateTime,
+) -> Result<usize, AppError> {
+    diesel::delete(used_checkin_tokens::table.filter(used_checkin_tokens::used_at.lt(cutoff)))
+        .execute(conn)
+        .map_err(AppError::from)
+}
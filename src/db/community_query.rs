@@ -0,0 +1,255 @@
+use diesel::dsl::count_star;
+use diesel::prelude::*;
+use diesel::pg::PgConnection;
+use chrono::NaiveDateTime;
+use crate::errors::app_error::AppError;
+use crate::models::community::{CommunityGroup, CommunityPost, NewCommunityGroup, NewCommunityPost};
+use crate::schema::{community_groups, community_memberships, community_posts, community_reactions};
+
+pub fn insert_group(
+    conn: &mut PgConnection,
+    group: &NewCommunityGroup,
+) -> Result<CommunityGroup, AppError> {
+    diesel::insert_into(community_groups::table)
+        .values(group)
+        .returning(CommunityGroup::as_returning())
+        .get_result(conn)
+        .map_err(AppError::from)
+}
+
+pub fn find_active_groups(conn: &mut PgConnection) -> Result<Vec<CommunityGroup>, AppError> {
+    community_groups::table
+        .filter(community_groups::is_active.eq(true))
+        .order(community_groups::name.asc())
+        .select(CommunityGroup::as_select())
+        .load(conn)
+        .map_err(AppError::from)
+}
+
+pub fn find_active_group(
+    conn: &mut PgConnection,
+    group_id: i32,
+) -> Result<Option<CommunityGroup>, AppError> {
+    community_groups::table
+        .find(group_id)
+        .filter(community_groups::is_active.eq(true))
+        .select(CommunityGroup::as_select())
+        .first(conn)
+        .optional()
+        .map_err(AppError::from)
+}
+
+/// Jumlah anggota per grup
+pub fn count_members(conn: &mut PgConnection) -> Result<Vec<(i32, i64)>, AppError> {
+    community_memberships::table
+        .group_by(community_memberships::group_id)
+        .select((community_memberships::group_id, count_star()))
+        .load(conn)
+        .map_err(AppError::from)
+}
+
+pub fn find_joined_group_ids(
+    conn: &mut PgConnection,
+    user_id: i32,
+) -> Result<Vec<i32>, AppError> {
+    community_memberships::table
+        .filter(community_memberships::user_id.eq(user_id))
+        .select(community_memberships::group_id)
+        .load(conn)
+        .map_err(AppError::from)
+}
+
+pub fn is_member(
+    conn: &mut PgConnection,
+    group_id: i32,
+    user_id: i32,
+) -> Result<bool, AppError> {
+    diesel::select(diesel::dsl::exists(
+        community_memberships::table
+            .filter(community_memberships::group_id.eq(group_id))
+            .filter(community_memberships::user_id.eq(user_id)),
+    ))
+    .get_result(conn)
+    .map_err(AppError::from)
+}
+
+/// Bergabung dengan grup; bergabung dua kali tidak mengubah apa pun
+pub fn insert_membership(
+    conn: &mut PgConnection,
+    group_id: i32,
+    user_id: i32,
+) -> Result<usize, AppError> {
+    diesel::insert_into(community_memberships::table)
+        .values((
+            community_memberships::group_id.eq(group_id),
+            community_memberships::user_id.eq(user_id),
+        ))
+        .on_conflict_do_nothing()
+        .execute(conn)
+        .map_err(AppError::from)
+}
+
+pub fn delete_membership(
+    conn: &mut PgConnection,
+    group_id: i32,
+    user_id: i32,
+) -> Result<usize, AppError> {
+    diesel::delete(
+        community_memberships::table
+            .filter(community_memberships::group_id.eq(group_id))
+            .filter(community_memberships::user_id.eq(user_id)),
+    )
+    .execute(conn)
+    .map_err(AppError::from)
+}
+
+pub fn insert_post(
+    conn: &mut PgConnection,
+    post: &NewCommunityPost,
+) -> Result<CommunityPost, AppError> {
+    diesel::insert_into(community_posts::table)
+        .values(post)
+        .returning(CommunityPost::as_returning())
+        .get_result(conn)
+        .map_err(AppError::from)
+}
+
+/// Jumlah kiriman pengguna sejak waktu tertentu, untuk batas kiriman
+pub fn count_posts_since(
+    conn: &mut PgConnection,
+    user_id: i32,
+    since: NaiveDateTime,
+) -> Result<i64, AppError> {
+    community_posts::table
+        .filter(community_posts::user_id.eq(user_id))
+        .filter(community_posts::created_at.ge(since))
+        .count()
+        .get_result(conn)
+        .map_err(AppError::from)
+}
+
+pub fn find_post(
+    conn: &mut PgConnection,
+    post_id: i32,
+) -> Result<Option<CommunityPost>, AppError> {
+    community_posts::table
+        .find(post_id)
+        .select(CommunityPost::as_select())
+        .first(conn)
+        .optional()
+        .map_err(AppError::from)
+}
+
+/// Kiriman dalam satu grup, terbaru lebih dulu, sebelum `before_id` jika diisi.
+/// Kiriman yang ditahan moderasi hanya terlihat oleh penulisnya.
+pub fn find_posts(
+    conn: &mut PgConnection,
+    group_id: i32,
+    viewer_id: i32,
+    before_id: Option<i32>,
+    limit: i64,
+) -> Result<Vec<CommunityPost>, AppError> {
+    let mut query = community_posts::table
+        .filter(community_posts::group_id.eq(group_id))
+        .filter(community_posts::held.eq(false).or(community_posts::user_id.eq(viewer_id)))
+        .order(community_posts::id.desc())
+        .limit(limit)
+        .select(CommunityPost::as_select())
+        .into_boxed();
+
+    if let Some(before_id) = before_id {
+        query = query.filter(community_posts::id.lt(before_id));
+    }
+
+    query
+        .load(conn)
+        .map_err(AppError::from)
+}
+
+pub fn set_post_held(
+    conn: &mut PgConnection,
+    post_id: i32,
+    held: bool,
+) -> Result<Option<CommunityPost>, AppError> {
+    diesel::update(community_posts::table.find(post_id))
+        .set(community_posts::held.eq(held))
+        .returning(CommunityPost::as_returning())
+        .get_result(conn)
+        .optional()
+        .map_err(AppError::from)
+}
+
+/// Jumlah reaksi per kiriman dan jenis
+pub fn count_reactions(
+    conn: &mut PgConnection,
+    post_ids: &[i32],
+) -> Result<Vec<(i32, String, i64)>, AppError> {
+    community_reactions::table
+        .filter(community_reactions::post_id.eq_any(post_ids))
+        .group_by((community_reactions::post_id, community_reactions::kind))
+        .select((community_reactions::post_id, community_reactions::kind, count_star()))
+        .load(conn)
+        .map_err(AppError::from)
+}
+
+/// Reaksi yang diberikan pengguna pada kiriman-kiriman tertentu
+pub fn find_user_reactions(
+    conn: &mut PgConnection,
+    user_id: i32,
+    post_ids: &[i32],
+) -> Result<Vec<(i32, String)>, AppError> {
+    community_reactions::table
+        .filter(community_reactions::user_id.eq(user_id))
+        .filter(community_reactions::post_id.eq_any(post_ids))
+        .select((community_reactions::post_id, community_reactions::kind))
+        .load(conn)
+        .map_err(AppError::from)
+}
+
+/// Tambah reaksi; reaksi yang sama dua kali tidak mengubah apa pun
+pub fn insert_reaction(
+    conn: &mut PgConnection,
+    post_id: i32,
+    user_id: i32,
+    kind: &str,
+) -> Result<usize, AppError> {
+    diesel::insert_into(community_reactions::table)
+        .values((
+            community_reactions::post_id.eq(post_id),
+            community_reactions::user_id.eq(user_id),
+            community_reactions::kind.eq(kind),
+        ))
+        .on_conflict_do_nothing()
+        .execute(conn)
+        .map_err(AppError::from)
+}
+
+pub fn delete_reaction(
+    conn: &mut PgConnection,
+    post_id: i32,
+    user_id: i32,
+    kind: &str,
+) -> Result<usize, AppError> {
+    diesel::delete(
+        community_reactions::table
+            .filter(community_reactions::post_id.eq(post_id))
+            .filter(community_reactions::user_id.eq(user_id))
+            .filter(community_reactions::kind.eq(kind)),
+    )
+    .execute(conn)
+    .map_err(AppError::from)
+}
+
+/// Jumlah reaksi yang diberikan pengguna sejak waktu tertentu, untuk batas reaksi
+pub fn count_reactions_since(
+    conn: &mut PgConnection,
+    user_id: i32,
+    since: NaiveDateTime,
+) -> Result<i64, AppError> {
+    community_reactions::table
+        .filter(community_reactions::user_id.eq(user_id))
+        .filter(community_reactions::created_at.ge(since))
+        .count()
+        .get_result(conn)
+        .map_err(AppError::from)
+}
@@ -0,0 +1,48 @@
+use crate::errors::app_error::AppError;
+
+pub const USERS_EMAIL_KEY: &str = "users_email_key";
+pub const USERS_USERNAME_KEY: &str = "users_username_key";
+pub const MOODS_USER_ID_DATE_KEY: &str = "moods_user_id_date_key";
+pub const PSYCHOLOGIST_SLOTS_START_KEY: &str = "psychologist_slots_psychologist_id_starts_at_key";
+pub const PSYCHOLOGIST_REQUESTS_SLOT_ID_KEY: &str = "psychologist_requests_slot_id_key";
+pub const APPOINTMENTS_REQUEST_ID_KEY: &str = "appointments_request_id_key";
+pub const PSYCHOLOGISTS_USER_ID_KEY: &str = "psychologists_user_id_key";
+pub const COMMUNITY_GROUPS_SLUG_KEY: &str = "community_groups_slug_key";
+pub const USER_WEBHOOKS_USER_ID_URL_KEY: &str = "user_webhooks_user_id_url_key";
+
+/// Ubah pelanggaran unique constraint menjadi 409 dengan pesan yang sama seperti pengecekan di service,
+/// sehingga request yang balapan tetap mendapat pesan yang jelas. Constraint lain menyebut namanya.
+pub fn unique_violation_error(constraint_name: Option<&str>) -> AppError {
+    let message = match constraint_name {
+        Some(USERS_EMAIL_KEY) => "Email already exists".to_string(),
+        Some(USERS_USERNAME_KEY) => "Username already exists".to_string(),
+        Some(MOODS_USER_ID_DATE_KEY) => "Mood already exists for this date".to_string(),
+        Some(PSYCHOLOGIST_SLOTS_START_KEY) => "Slot already exists".to_string(),
+        Some(PSYCHOLOGIST_REQUESTS_SLOT_ID_KEY) => "Slot is already booked".to_string(),
+        Some(PSYCHOLOGISTS_USER_ID_KEY) => "User is already linked to a psychologist".to_string(),
+        Some(APPOINTMENTS_REQUEST_ID_KEY) => "Request already has an appointment".to_string(),
+        Some(COMMUNITY_GROUPS_SLUG_KEY) => "Group slug already exists".to_string(),
+        Some(USER_WEBHOOKS_USER_ID_URL_KEY) => "Webhook is already registered".to_string(),
+        Some(constraint) => format!("Duplicate value violates {}", constraint),
+        None => "Duplicate value".to_string(),
+    };
+    AppError::Conflict(message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unique_violations_are_conflicts() {
+        let AppError::Conflict(message) = unique_violation_error(Some(USERS_EMAIL_KEY)) else {
+            panic!("expected a conflict");
+        };
+        assert_eq!(message, "Email already exists");
+
+        let AppError::Conflict(message) = unique_violation_error(Some("tags_name_key")) else {
+            panic!("expected a conflict");
+        };
+        assert_eq!(message, "Duplicate value violates tags_name_key");
+    }
+}
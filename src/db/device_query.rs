@@ -0,0 +1,72 @@
+use diesel::prelude::*;
+use diesel::pg::PgConnection;
+use diesel::upsert::excluded;
+use crate::errors::app_error::AppError;
+use crate::models::device::{Device, NewDevice};
+use crate::schema::devices;
+
+/// Daftarkan token perangkat. Token yang sudah ada dipindahkan ke pengguna ini,
+/// misalnya saat perangkat yang sama login dengan akun lain.
+pub fn upsert_device(
+    conn: &mut PgConnection,
+    device: &NewDevice,
+) -> Result<Device, AppError> {
+    diesel::insert_into(devices::table)
+        .values(device)
+        .on_conflict(devices::push_token)
+        .do_update()
+        .set((
+            devices::user_id.eq(excluded(devices::user_id)),
+            devices::platform.eq(excluded(devices::platform)),
+            devices::updated_at.eq(excluded(devices::updated_at)),
+        ))
+        .returning(Device::as_returning())
+        .get_result(conn)
+        .map_err(AppError::from)
+}
+
+pub fn find_devices_by_user(
+    conn: &mut PgConnection,
+    user_id: i32,
+) -> Result<Vec<Device>, AppError> {
+    devices::table
+        .filter(devices::user_id.eq(user_id))
+        .order(devices::updated_at.desc())
+        .select(Device::as_select())
+        .load(conn)
+        .map_err(AppError::from)
+}
+
+pub fn find_user_ids_with_devices(
+    conn: &mut PgConnection,
+) -> Result<Vec<i32>, AppError> {
+    devices::table
+        .select(devices::user_id)
+        .distinct()
+        .load(conn)
+        .map_err(AppError::from)
+}
+
+pub fn delete_device(
+    conn: &mut PgConnection,
+    device_id: i32,
+    user_id: i32,
+) -> Result<usize, AppError> {
+    diesel::delete(
+        devices::table
+            .filter(devices::id.eq(device_id))
+            .filter(devices::user_id.eq(user_id)),
+    )
+    .execute(conn)
+    .map_err(AppError::from)
+}
+
+/// Hapus perangkat yang token-nya ditolak provider push
+pub fn delete_device_by_id(
+    conn: &mut PgConnection,
+    device_id: i32,
+) -> Result<usize, AppError> {
+    diesel::delete(devices::table.filter(devices::id.eq(device_id)))
+        .execute(conn)
+        .map_err(AppError::from)
+}
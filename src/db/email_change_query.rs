@@ -0,0 +1,60 @@
+use diesel::prelude::*;
+use diesel::pg::PgConnection;
+use crate::errors::app_error::AppError;
+use crate::models::email_change::{EmailChangeRequest, NewEmailChangeRequest};
+use crate::schema::email_change_requests;
+
+/// Simpan permintaan ganti email, menggantikan permintaan lama milik user yang sama
+pub fn upsert_request(
+    conn: &mut PgConnection,
+    request: &NewEmailChangeRequest,
+) -> Result<EmailChangeRequest, AppError> {
+    diesel::insert_into(email_change_requests::table)
+        .values(request)
+        .on_conflict(email_change_requests::user_id)
+        .do_update()
+        .set((
+            email_change_requests::new_email.eq(&request.new_email),
+            email_change_requests::token.eq(&request.token),
+            email_change_requests::expires_at.eq(request.expires_at),
+            email_change_requests::created_at.eq(request.created_at),
+        ))
+        .returning(EmailChangeRequest::as_returning())
+        .get_result(conn)
+        .map_err(AppError::from)
+}
+
+pub fn find_request_by_token(
+    conn: &mut PgConnection,
+    token_str: &str,
+) -> Result<EmailChangeRequest, AppError> {
+    email_change_requests::table
+        .filter(email_change_requests::token.eq(token_str))
+        .select(EmailChangeRequest::as_select())
+        .first(conn)
+        .map_err(|e| match e {
+            diesel::result::Error::NotFound => AppError::BadRequest("Invalid or expired email change token".to_string()),
+            _ => AppError::from(e),
+        })
+}
+
+pub fn find_request_by_user(
+    conn: &mut PgConnection,
+    user_id: i32,
+) -> Result<Option<EmailChangeRequest>, AppError> {
+    email_change_requests::table
+        .filter(email_change_requests::user_id.eq(user_id))
+        .select(EmailChangeRequest::as_select())
+        .first(conn)
+        .optional()
+        .map_err(AppError::from)
+}
+
+pub fn delete_request_by_user(
+    conn: &mut PgConnection,
+    user_id: i32,
+) -> Result<usize, AppError> {
+    diesel::delete(email_change_requests::table.filter(email_change_requests::user_id.eq(user_id)))
+        .execute(conn)
+        .map_err(AppError::from)
+}
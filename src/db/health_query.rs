@@ -0,0 +1,49 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use diesel::pg::PgConnection;
+use diesel::upsert::excluded;
+use crate::errors::app_error::AppError;
+use crate::models::health::{HealthSample, NewHealthSample};
+use crate::schema::health_samples;
+
+/// Simpan sampel; interval yang sudah ada dari sumber yang sama diperbarui nilainya
+pub fn upsert_samples(
+    conn: &mut PgConnection,
+    samples: &[NewHealthSample],
+) -> Result<usize, AppError> {
+    let mut saved = 0;
+    // Batas parameter bind PostgreSQL adalah 65535 per query
+    for chunk in samples.chunks(1000) {
+        saved += diesel::insert_into(health_samples::table)
+            .values(chunk)
+            .on_conflict((
+                health_samples::user_id,
+                health_samples::kind,
+                health_samples::source,
+                health_samples::start_at,
+                health_samples::end_at,
+            ))
+            .do_update()
+            .set(health_samples::value.eq(excluded(health_samples::value)))
+            .execute(conn)
+            .map_err(AppError::from)?;
+    }
+    Ok(saved)
+}
+
+/// Sampel yang berakhir dalam rentang `[from, to)`
+pub fn find_samples_ending_between(
+    conn: &mut PgConnection,
+    user_id: i32,
+    from: NaiveDateTime,
+    to: NaiveDateTime,
+) -> Result<Vec<HealthSample>, AppError> {
+    health_samples::table
+        .filter(health_samples::user_id.eq(user_id))
+        .filter(health_samples::end_at.ge(from))
+        .filter(health_samples::end_at.lt(to))
+        .order(health_samples::start_at.asc())
+        .select(HealthSample::as_select())
+        .load(conn)
+        .map_err(AppError::from)
+}
@@ -0,0 +1,131 @@
+use diesel::prelude::*;
+use diesel::pg::PgConnection;
+use chrono::NaiveDateTime;
+use crate::errors::app_error::AppError;
+use crate::models::help::{HelpRequest, HelpRequestComment, NewHelpRequest, NewHelpRequestComment};
+use crate::schema::{help_request_comments, help_requests};
+
+pub fn create_help_request(
+    conn: &mut PgConnection,
+    request: &NewHelpRequest,
+) -> Result<HelpRequest, AppError> {
+    diesel::insert_into(help_requests::table)
+        .values(request)
+        .returning(HelpRequest::as_returning())
+        .get_result(conn)
+        .map_err(AppError::from)
+}
+
+pub fn find_help_request(
+    conn: &mut PgConnection,
+    request_id: i32,
+) -> Result<Option<HelpRequest>, AppError> {
+    help_requests::table
+        .find(request_id)
+        .select(HelpRequest::as_select())
+        .first(conn)
+        .optional()
+        .map_err(AppError::from)
+}
+
+/// Sama seperti `find_help_request` tetapi mengunci baris sampai transaksi selesai,
+/// agar dua admin tidak mengubah status yang sama bersamaan
+pub fn find_help_request_for_update(
+    conn: &mut PgConnection,
+    request_id: i32,
+) -> Result<Option<HelpRequest>, AppError> {
+    help_requests::table
+        .find(request_id)
+        .select(HelpRequest::as_select())
+        .for_update()
+        .first(conn)
+        .optional()
+        .map_err(AppError::from)
+}
+
+pub fn find_help_requests_by_user(
+    conn: &mut PgConnection,
+    user_id: i32,
+) -> Result<Vec<HelpRequest>, AppError> {
+    help_requests::table
+        .filter(help_requests::user_id.eq(user_id))
+        .order(help_requests::created_at.desc())
+        .select(HelpRequest::as_select())
+        .load(conn)
+        .map_err(AppError::from)
+}
+
+/// Antrian untuk admin: yang paling lama menunggu lebih dulu, tanpa yang ditahan moderasi
+pub fn find_help_requests(
+    conn: &mut PgConnection,
+    status: Option<&str>,
+    limit: i64,
+) -> Result<Vec<HelpRequest>, AppError> {
+    let mut query = help_requests::table
+        .filter(help_requests::held.eq(false))
+        .order(help_requests::created_at.asc())
+        .limit(limit)
+        .select(HelpRequest::as_select())
+        .into_boxed();
+
+    if let Some(status) = status {
+        query = query.filter(help_requests::status.eq(status));
+    }
+
+    query
+        .load(conn)
+        .map_err(AppError::from)
+}
+
+pub fn update_status(
+    conn: &mut PgConnection,
+    request_id: i32,
+    status: &str,
+    now: NaiveDateTime,
+) -> Result<HelpRequest, AppError> {
+    diesel::update(help_requests::table.find(request_id))
+        .set((
+            help_requests::status.eq(status),
+            help_requests::updated_at.eq(now),
+        ))
+        .returning(HelpRequest::as_returning())
+        .get_result(conn)
+        .map_err(AppError::from)
+}
+
+/// Tandai permintaan berubah tanpa mengganti status, misalnya saat ada komentar baru
+pub fn touch(
+    conn: &mut PgConnection,
+    request_id: i32,
+    now: NaiveDateTime,
+) -> Result<(), AppError> {
+    diesel::update(help_requests::table.find(request_id))
+        .set(help_requests::updated_at.eq(now))
+        .execute(conn)
+        .map_err(AppError::from)?;
+
+    Ok(())
+}
+
+pub fn insert_comment(
+    conn: &mut PgConnection,
+    comment: &NewHelpRequestComment,
+) -> Result<HelpRequestComment, AppError> {
+    diesel::insert_into(help_request_comments::table)
+        .values(comment)
+        .returning(HelpRequestComment::as_returning())
+        .get_result(conn)
+        .map_err(AppError::from)
+}
+
+pub fn find_comments(
+    conn: &mut PgConnection,
+    request_id: i32,
+) -> Result<Vec<HelpRequestComment>, AppError> {
+    help_request_comments::table
+        .filter(help_request_comments::help_request_id.eq(request_id))
+        .order((help_request_comments::created_at.asc(), help_request_comments::id.asc()))
+        .select(HelpRequestComment::as_select())
+        .load(conn)
+        .map_err(AppError::from)
+}
@@ -0,0 +1,23 @@
+use diesel::prelude::*;
+use diesel::pg::PgConnection;
+use crate::errors::app_error::AppError;
+use crate::models::insight::NewInsightNotification;
+use crate::schema::insight_notifications;
+
+/// Catat notifikasi alert; mengembalikan `false` jika alert yang sama sudah pernah dikirim
+pub fn record_notification(
+    conn: &mut PgConnection,
+    notification: &NewInsightNotification,
+) -> Result<bool, AppError> {
+    diesel::insert_into(insight_notifications::table)
+        .values(notification)
+        .on_conflict((
+            insight_notifications::user_id,
+            insight_notifications::kind,
+            insight_notifications::start_date,
+        ))
+        .do_nothing()
+        .execute(conn)
+        .map(|inserted| inserted > 0)
+        .map_err(AppError::from)
+}
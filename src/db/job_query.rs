@@ -0,0 +1,167 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use diesel::pg::PgConnection;
+use crate::errors::app_error::AppError;
+use crate::models::job::{Job, JobStatus, NewJob};
+use crate::schema::jobs;
+
+pub fn insert_job(
+    conn: &mut PgConnection,
+    job: &NewJob,
+) -> Result<Job, AppError> {
+    diesel::insert_into(jobs::table)
+        .values(job)
+        .returning(Job::as_returning())
+        .get_result(conn)
+        .map_err(AppError::from)
+}
+
+pub fn find_user_job(
+    conn: &mut PgConnection,
+    job_id: i32,
+    user_id: i32,
+) -> Result<Option<Job>, AppError> {
+    jobs::table
+        .filter(jobs::id.eq(job_id))
+        .filter(jobs::user_id.eq(user_id))
+        .select(Job::as_select())
+        .first(conn)
+        .optional()
+        .map_err(AppError::from)
+}
+
+/// Job pengguna dengan jenis `kind` yang belum selesai, jika ada
+pub fn find_active_job(
+    conn: &mut PgConnection,
+    user_id: i32,
+    kind: &str,
+) -> Result<Option<Job>, AppError> {
+    jobs::table
+        .filter(jobs::user_id.eq(user_id))
+        .filter(jobs::kind.eq(kind))
+        .filter(jobs::status.eq_any([JobStatus::Queued.as_str(), JobStatus::Running.as_str()]))
+        .select(Job::as_select())
+        .first(conn)
+        .optional()
+        .map_err(AppError::from)
+}
+
+/// Ambil job antrian tertua dan tandai `running`. Baris yang sedang diambil worker lain
+/// dilewati (`SKIP LOCKED`), jadi beberapa instance bisa berjalan bersamaan.
+pub fn claim_next_job(
+    conn: &mut PgConnection,
+    now: NaiveDateTime,
+) -> Result<Option<Job>, AppError> {
+    conn.transaction(|conn| {
+        let Some(job_id) = jobs::table
+            .filter(jobs::status.eq(JobStatus::Queued.as_str()))
+            .order(jobs::id.asc())
+            .select(jobs::id)
+            .for_update()
+            .skip_locked()
+            .first::<i32>(conn)
+            .optional()?
+        else {
+            return Ok(None);
+        };
+
+        diesel::update(jobs::table.filter(jobs::id.eq(job_id)))
+            .set((
+                jobs::status.eq(JobStatus::Running.as_str()),
+                jobs::started_at.eq(Some(now)),
+            ))
+            .returning(Job::as_returning())
+            .get_result(conn)
+            .map(Some)
+    })
+    .map_err(AppError::from)
+}
+
+pub fn mark_succeeded(
+    conn: &mut PgConnection,
+    job_id: i32,
+    content_type: Option<&str>,
+    filename: Option<&str>,
+    finished_at: NaiveDateTime,
+) -> Result<(), AppError> {
+    diesel::update(jobs::table.filter(jobs::id.eq(job_id)))
+        .set((
+            jobs::status.eq(JobStatus::Succeeded.as_str()),
+            jobs::result_content_type.eq(content_type),
+            jobs::result_filename.eq(filename),
+            jobs::finished_at.eq(Some(finished_at)),
+        ))
+        .execute(conn)
+        .map(|_| ())
+        .map_err(AppError::from)
+}
+
+pub fn mark_failed(
+    conn: &mut PgConnection,
+    job_id: i32,
+    error: &str,
+    finished_at: NaiveDateTime,
+) -> Result<(), AppError> {
+    diesel::update(jobs::table.filter(jobs::id.eq(job_id)))
+        .set((
+            jobs::status.eq(JobStatus::Failed.as_str()),
+            jobs::error.eq(Some(error)),
+            jobs::finished_at.eq(Some(finished_at)),
+        ))
+        .execute(conn)
+        .map(|_| ())
+        .map_err(AppError::from)
+}
+
+/// Job `running` yang mulai sebelum `before` dianggap terputus (misalnya server restart)
+pub fn fail_stale_jobs(
+    conn: &mut PgConnection,
+    before: NaiveDateTime,
+    error: &str,
+    finished_at: NaiveDateTime,
+) -> Result<usize, AppError> {
+    diesel::update(
+        jobs::table
+            .filter(jobs::status.eq(JobStatus::Running.as_str()))
+            .filter(jobs::started_at.lt(before)),
+    )
+    .set((
+        jobs::status.eq(JobStatus::Failed.as_str()),
+        jobs::error.eq(Some(error)),
+        jobs::finished_at.eq(Some(finished_at)),
+    ))
+    .execute(conn)
+    .map_err(AppError::from)
+}
+
+/// Id job yang sudah selesai sebelum `before`, untuk dibersihkan beserta file-nya
+pub fn find_finished_before(
+    conn: &mut PgConnection,
+    before: NaiveDateTime,
+) -> Result<Vec<i32>, AppError> {
+    jobs::table
+        .filter(jobs::finished_at.lt(before))
+        .select(jobs::id)
+        .load(conn)
+        .map_err(AppError::from)
+}
+
+pub fn find_job_ids_by_user(
+    conn: &mut PgConnection,
+    user_id: i32,
+) -> Result<Vec<i32>, AppError> {
+    jobs::table
+        .filter(jobs::user_id.eq(user_id))
+        .select(jobs::id)
+        .load(conn)
+        .map_err(AppError::from)
+}
+
+pub fn delete_jobs(
+    conn: &mut PgConnection,
+    job_ids: &[i32],
+) -> Result<usize, AppError> {
+    diesel::delete(jobs::table.filter(jobs::id.eq_any(job_ids)))
+        .execute(conn)
+        .map_err(AppError::from)
+}
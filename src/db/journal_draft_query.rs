@@ -0,0 +1,46 @@
+use diesel::prelude::*;
+use diesel::pg::PgConnection;
+use diesel::upsert::excluded;
+use crate::errors::app_error::AppError;
+use crate::models::journal::JournalDraft;
+use crate::schema::journal_drafts;
+
+pub fn find_draft_by_user(
+    conn: &mut PgConnection,
+    user_id: i32,
+) -> Result<Option<JournalDraft>, AppError> {
+    journal_drafts::table
+        .filter(journal_drafts::user_id.eq(user_id))
+        .select(JournalDraft::as_select())
+        .first(conn)
+        .optional()
+        .map_err(AppError::from)
+}
+
+/// Simpan draft; draft sebelumnya milik pengguna yang sama ditimpa
+pub fn upsert_draft(
+    conn: &mut PgConnection,
+    draft: &JournalDraft,
+) -> Result<JournalDraft, AppError> {
+    diesel::insert_into(journal_drafts::table)
+        .values(draft)
+        .on_conflict(journal_drafts::user_id)
+        .do_update()
+        .set((
+            journal_drafts::title.eq(excluded(journal_drafts::title)),
+            journal_drafts::content.eq(excluded(journal_drafts::content)),
+            journal_drafts::updated_at.eq(excluded(journal_drafts::updated_at)),
+        ))
+        .returning(JournalDraft::as_returning())
+        .get_result(conn)
+        .map_err(AppError::from)
+}
+
+pub fn delete_draft(
+    conn: &mut PgConnection,
+    user_id: i32,
+) -> Result<usize, AppError> {
+    diesel::delete(journal_drafts::table.filter(journal_drafts::user_id.eq(user_id)))
+        .execute(conn)
+        .map_err(AppError::from)
+}
@@ -1,27 +1,30 @@
 use diesel::prelude::*;
-use diesel::pg::PgConnection;
-use chrono::{NaiveDate, Utc};
-use crate::models::journal::{Journal, NewJournal};
+use diesel::dsl::sql;
+use diesel::expression::SqlLiteral;
+use diesel::pg::{Pg, PgConnection, PgRowByRowLoadingMode};
+use diesel::sql_types::BigInt;
+use chrono::{NaiveDate, NaiveDateTime, Utc};
+use chrono_tz::Tz;
+use crate::utils::timezone::{day_range_utc, day_start_utc, today_in};
+use crate::models::journal::{Journal, JournalSummary, NewJournal};
+use crate::models::list_sort::ListSort;
 use crate::errors::app_error::AppError;
 use crate::schema::journals;
 
+diesel::define_sql_function!(fn lower(x: diesel::sql_types::Text) -> diesel::sql_types::Text);
+
 pub fn create_journal(
     conn: &mut PgConnection,
     user_id: i32,
     title: &str,
     content: &str,
     created_at: Option<NaiveDate>,
+    tz: Tz,
 ) -> Result<Journal, AppError> {
-    let now = Utc::now().naive_utc();
-    
-    // Convert NaiveDate to NaiveDateTime
-    let created_datetime = if let Some(date) = created_at {
-        // Use the provided date at midnight
-        date.and_hms_opt(0, 0, 0)
-            .ok_or_else(|| AppError::BadRequest("Invalid date provided".to_string()))?
-    } else {
-        // Use current timestamp if no date provided
-        now
+    // Tanggal yang diberikan disimpan sebagai tengah malam waktu lokal pengguna (dalam UTC)
+    let created_datetime = match created_at {
+        Some(date) => day_start_utc(date, tz),
+        None => Utc::now().naive_utc(),
     };
     
     let new_journal = NewJournal {
@@ -34,16 +37,9 @@ pub fn create_journal(
 
     diesel::insert_into(journals::table)
         .values(&new_journal)
-        .execute(conn)
-        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
-
-    // Get the created journal by ordering by id desc to get the latest
-    journals::table
-        .filter(journals::user_id.eq(user_id))
-        .order(journals::id.desc())
-        .select(Journal::as_select())
-        .first(conn)
-        .map_err(|e| AppError::DatabaseError(e.to_string()))
+        .returning(Journal::as_returning())
+        .get_result(conn)
+        .map_err(AppError::from)
 }
 
 pub fn find_journal_by_id(
@@ -56,46 +52,107 @@ pub fn find_journal_by_id(
         .first(conn)
         .map_err(|e| match e {
             diesel::result::Error::NotFound => AppError::NotFound("Journal not found".to_string()),
-            _ => AppError::DatabaseError(e.to_string()),
+            _ => AppError::from(e),
         })
 }
 
-pub fn find_journals_by_user(
-    conn: &mut PgConnection,
+/// Jumlah kata dihitung di database agar isi jurnal tidak perlu dikirim untuk list ringkas
+const WORD_COUNT_SQL: &str = "(SELECT count(*) FROM regexp_matches(journals.content, '\\S+', 'g'))";
+
+type JournalSummaryColumns = (
+    journals::id,
+    journals::title,
+    journals::is_pinned,
+    journals::created_at,
+    journals::updated_at,
+    SqlLiteral<BigInt>,
+);
+
+/// Proyeksi ringkas: semua metadata jurnal tanpa `content`
+fn journal_summary_columns() -> JournalSummaryColumns {
+    (
+        journals::id,
+        journals::title,
+        journals::is_pinned,
+        journals::created_at,
+        journals::updated_at,
+        sql::<BigInt>(WORD_COUNT_SQL),
+    )
+}
+
+/// Filter, urutan dan pagination list jurnal pengguna, dipakai proyeksi lengkap maupun ringkas
+fn user_journals_query<'a>(
     user_id: i32,
     limit: Option<i32>,
     offset: Option<i32>,
-) -> Result<Vec<Journal>, AppError> {
+    pinned: Option<bool>,
+    sort: ListSort,
+) -> journals::BoxedQuery<'a, Pg> {
     let limit = limit.unwrap_or(50);
     let offset = offset.unwrap_or(0);
 
-    journals::table
+    let mut query = journals::table
         .filter(journals::user_id.eq(user_id))
-        .order(journals::created_at.desc())
-        .limit(limit as i64)
-        .offset(offset as i64)
+        .into_boxed();
+    if let Some(pinned) = pinned {
+        query = query.filter(journals::is_pinned.eq(pinned));
+    }
+    // `id` sebagai penentu terakhir agar pagination stabil untuk nilai yang sama
+    query = match sort {
+        ListSort::DateAsc => query.order((journals::created_at.asc(), journals::id.asc())),
+        ListSort::DateDesc => query.order((journals::created_at.desc(), journals::id.desc())),
+        ListSort::UpdatedDesc => query.order((journals::updated_at.desc().nulls_last(), journals::id.desc())),
+        ListSort::Title => query.order((lower(journals::title).asc(), journals::id.asc())),
+    };
+
+    query.limit(limit as i64).offset(offset as i64)
+}
+
+pub fn find_journals_by_user(
+    conn: &mut PgConnection,
+    user_id: i32,
+    limit: Option<i32>,
+    offset: Option<i32>,
+    pinned: Option<bool>,
+    sort: ListSort,
+) -> Result<Vec<Journal>, AppError> {
+    user_journals_query(user_id, limit, offset, pinned, sort)
         .select(Journal::as_select())
         .load::<Journal>(conn)
-        .map_err(|e| AppError::DatabaseError(e.to_string()))
+        .map_err(AppError::from)
+}
+
+pub fn find_journal_summaries_by_user(
+    conn: &mut PgConnection,
+    user_id: i32,
+    limit: Option<i32>,
+    offset: Option<i32>,
+    pinned: Option<bool>,
+    sort: ListSort,
+) -> Result<Vec<JournalSummary>, AppError> {
+    user_journals_query(user_id, limit, offset, pinned, sort)
+        .select(journal_summary_columns())
+        .load::<JournalSummary>(conn)
+        .map_err(AppError::from)
 }
 
 pub fn find_journal_by_user_and_date(
     conn: &mut PgConnection,
     user_id: i32,
     date: NaiveDate,
+    tz: Tz,
 ) -> Result<Journal, AppError> {
-    let start_of_day = date.and_hms_opt(0, 0, 0).unwrap_or_default();
-    let end_of_day = date.and_hms_opt(23, 59, 59).unwrap_or_default();
+    let (start_of_day, next_day) = day_range_utc(date, date, tz);
 
     journals::table
         .filter(journals::user_id.eq(user_id))
         .filter(journals::created_at.ge(start_of_day))
-        .filter(journals::created_at.le(end_of_day))
+        .filter(journals::created_at.lt(next_day))
         .select(Journal::as_select())
         .first(conn)
         .map_err(|e| match e {
             diesel::result::Error::NotFound => AppError::NotFound("Journal not found for this date".to_string()),
-            _ => AppError::DatabaseError(e.to_string()),
+            _ => AppError::from(e),
         })
 }
 
@@ -104,17 +161,18 @@ pub fn find_journals_by_date_range(
     user_id: i32,
     start_date: NaiveDate,
     end_date: NaiveDate,
+    tz: Tz,
 ) -> Result<Vec<Journal>, AppError> {
-    let start_datetime = start_date.and_hms_opt(0, 0, 0).unwrap_or_default();
-    let end_datetime = end_date.and_hms_opt(23, 59, 59).unwrap_or_default();
+    let (start_datetime, end_datetime) = day_range_utc(start_date, end_date, tz);
 
     journals::table
         .filter(journals::user_id.eq(user_id))
-        .filter(journals::created_at.between(start_datetime, end_datetime))
+        .filter(journals::created_at.ge(start_datetime))
+        .filter(journals::created_at.lt(end_datetime))
         .order(journals::created_at.asc())
         .select(Journal::as_select())
         .load::<Journal>(conn)
-        .map_err(|e| AppError::DatabaseError(e.to_string()))
+        .map_err(AppError::from)
 }
 
 pub fn update_journal(
@@ -123,7 +181,8 @@ pub fn update_journal(
     user_id: i32,
     new_title: Option<String>,
     new_content: Option<String>,
-    new_created_at: Option<NaiveDate>, // Added this parameter
+    new_created_at: Option<NaiveDate>,
+    tz: Tz,
 ) -> Result<Journal, AppError> {
     // Check if journal exists and belongs to user
     let existing_journal = journals::table
@@ -133,17 +192,15 @@ pub fn update_journal(
         .first::<Journal>(conn)
         .map_err(|e| match e {
             diesel::result::Error::NotFound => AppError::NotFound("Journal not found".to_string()),
-            _ => AppError::DatabaseError(e.to_string()),
+            _ => AppError::from(e),
         })?;
 
     // Build update values
     let title_to_update = new_title.unwrap_or(existing_journal.title);
     let content_to_update = new_content.unwrap_or(existing_journal.content);
-    let created_at_to_update = if let Some(date) = new_created_at {
-        date.and_hms_opt(0, 0, 0)
-            .ok_or_else(|| AppError::BadRequest("Invalid date provided".to_string()))?
-    } else {
-        existing_journal.created_at
+    let created_at_to_update: NaiveDateTime = match new_created_at {
+        Some(date) => day_start_utc(date, tz),
+        None => existing_journal.created_at,
     };
 
     diesel::update(journals::table.filter(journals::id.eq(journal_id)))
@@ -153,10 +210,9 @@ pub fn update_journal(
             journals::created_at.eq(created_at_to_update), 
             journals::updated_at.eq(Some(Utc::now().naive_utc())),
         ))
-        .execute(conn)
-        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
-
-    find_journal_by_id(conn, journal_id)
+        .returning(Journal::as_returning())
+        .get_result(conn)
+        .map_err(AppError::from)
 }
 
 pub fn delete_journal(
@@ -170,18 +226,40 @@ pub fn delete_journal(
             .filter(journals::user_id.eq(user_id))
     )
     .execute(conn)
-    .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    .map_err(AppError::from)?;
 
     Ok(result > 0)
 }
 
+/// Sematkan atau lepas sematan jurnal milik pengguna
+pub fn set_journal_pinned(
+    conn: &mut PgConnection,
+    journal_id: i32,
+    user_id: i32,
+    pinned: bool,
+) -> Result<Journal, AppError> {
+    diesel::update(
+        journals::table
+            .filter(journals::id.eq(journal_id))
+            .filter(journals::user_id.eq(user_id))
+    )
+    .set(journals::is_pinned.eq(pinned))
+    .returning(Journal::as_returning())
+    .get_result(conn)
+    .map_err(|e| match e {
+        diesel::result::Error::NotFound => AppError::NotFound("Journal not found".to_string()),
+        _ => AppError::from(e),
+    })
+}
+
 pub fn get_recent_journals(
     conn: &mut PgConnection,
     user_id: i32,
     days: i32,
+    tz: Tz,
 ) -> Result<Vec<Journal>, AppError> {
-    let cutoff_date = Utc::now().date_naive() - chrono::Duration::days(days as i64);
-    let cutoff_datetime = cutoff_date.and_hms_opt(0, 0, 0).unwrap_or_default();
+    let cutoff_date = today_in(tz) - chrono::Duration::days(days as i64);
+    let cutoff_datetime = day_start_utc(cutoff_date, tz);
     
     journals::table
         .filter(journals::user_id.eq(user_id))
@@ -189,7 +267,19 @@ pub fn get_recent_journals(
         .order(journals::created_at.desc())
         .select(Journal::as_select())
         .load::<Journal>(conn)
-        .map_err(|e| AppError::DatabaseError(e.to_string()))
+        .map_err(AppError::from)
+}
+
+/// Waktu pembuatan (UTC) semua journal pengguna, untuk menghitung streak
+pub fn find_journal_timestamps(
+    conn: &mut PgConnection,
+    user_id: i32,
+) -> Result<Vec<NaiveDateTime>, AppError> {
+    journals::table
+        .filter(journals::user_id.eq(user_id))
+        .select(journals::created_at)
+        .load::<NaiveDateTime>(conn)
+        .map_err(AppError::from)
 }
 
 pub fn get_journal_stats_simple(
@@ -202,7 +292,19 @@ pub fn get_journal_stats_simple(
         .filter(journals::user_id.eq(user_id))
         .select(count(journals::id))
         .first(conn)
-        .map_err(|e| AppError::DatabaseError(e.to_string()))
+        .map_err(AppError::from)
+}
+
+/// `updated_at` terbaru dari journal pengguna, dipakai sebagai versi cache statistik
+pub fn latest_journal_update(
+    conn: &mut PgConnection,
+    user_id: i32,
+) -> Result<Option<NaiveDateTime>, AppError> {
+    journals::table
+        .filter(journals::user_id.eq(user_id))
+        .select(diesel::dsl::max(journals::updated_at))
+        .first(conn)
+        .map_err(AppError::from)
 }
 
 pub fn get_all_journals_by_user(
@@ -214,16 +316,37 @@ pub fn get_all_journals_by_user(
         .order(journals::created_at.desc())
         .select(Journal::as_select())
         .load::<Journal>(conn)
-        .map_err(|e| AppError::DatabaseError(e.to_string()))
+        .map_err(AppError::from)
 }
 
-pub fn search_journals(
+/// Sama dengan `get_all_journals_by_user`, tetapi baris dibaca satu per satu dari
+/// database dan diteruskan ke `f` tanpa menampung seluruh hasil
+pub fn for_each_journal_by_user<F>(
     conn: &mut PgConnection,
+    user_id: i32,
+    mut f: F,
+) -> Result<(), AppError>
+where
+    F: FnMut(Journal) -> Result<(), AppError>,
+{
+    let rows = journals::table
+        .filter(journals::user_id.eq(user_id))
+        .order(journals::created_at.desc())
+        .select(Journal::as_select())
+        .load_iter::<Journal, PgRowByRowLoadingMode>(conn)?;
+
+    for journal in rows {
+        f(journal?)?;
+    }
+    Ok(())
+}
+
+fn search_journals_query<'a>(
     user_id: i32,
     search_query: &str,
     limit: Option<i32>,
     offset: Option<i32>,
-) -> Result<Vec<Journal>, AppError> {
+) -> journals::BoxedQuery<'a, Pg> {
     let limit = limit.unwrap_or(50);
     let offset = offset.unwrap_or(0);
     let search_pattern = format!("%{}%", search_query);
@@ -231,13 +354,104 @@ pub fn search_journals(
     journals::table
         .filter(journals::user_id.eq(user_id))
         .filter(
-            journals::title.like(&search_pattern)
-                .or(journals::content.like(&search_pattern))
+            journals::title.like(search_pattern.clone())
+                .or(journals::content.like(search_pattern))
         )
         .order(journals::created_at.desc())
         .limit(limit as i64)
         .offset(offset as i64)
+        .into_boxed()
+}
+
+pub fn search_journals(
+    conn: &mut PgConnection,
+    user_id: i32,
+    search_query: &str,
+    limit: Option<i32>,
+    offset: Option<i32>,
+) -> Result<Vec<Journal>, AppError> {
+    search_journals_query(user_id, search_query, limit, offset)
         .select(Journal::as_select())
         .load::<Journal>(conn)
-        .map_err(|e| AppError::DatabaseError(e.to_string()))
-}
\ No newline at end of file
+        .map_err(AppError::from)
+}
+
+pub fn search_journal_summaries(
+    conn: &mut PgConnection,
+    user_id: i32,
+    search_query: &str,
+    limit: Option<i32>,
+    offset: Option<i32>,
+) -> Result<Vec<JournalSummary>, AppError> {
+    search_journals_query(user_id, search_query, limit, offset)
+        .select(journal_summary_columns())
+        .load::<JournalSummary>(conn)
+        .map_err(AppError::from)
+}
+
+pub fn count_journals_by_date_range(
+    conn: &mut PgConnection,
+    user_id: i32,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+    tz: Tz,
+) -> Result<i64, AppError> {
+    use diesel::dsl::count;
+
+    let (start_datetime, end_datetime) = day_range_utc(start_date, end_date, tz);
+
+    journals::table
+        .filter(journals::user_id.eq(user_id))
+        .filter(journals::created_at.ge(start_datetime))
+        .filter(journals::created_at.lt(end_datetime))
+        .select(count(journals::id))
+        .first(conn)
+        .map_err(AppError::from)
+}
+
+/// Jumlah jurnal yang tidak disematkan dan dibuat sebelum `cutoff`
+pub fn count_unpinned_journals_before(
+    conn: &mut PgConnection,
+    user_id: i32,
+    cutoff: NaiveDateTime,
+) -> Result<i64, AppError> {
+    journals::table
+        .filter(journals::user_id.eq(user_id))
+        .filter(journals::is_pinned.eq(false))
+        .filter(journals::created_at.lt(cutoff))
+        .count()
+        .get_result(conn)
+        .map_err(AppError::from)
+}
+
+/// Hapus jurnal yang tidak disematkan dan dibuat sebelum `cutoff` (retensi data)
+pub fn delete_unpinned_journals_before(
+    conn: &mut PgConnection,
+    user_id: i32,
+    cutoff: NaiveDateTime,
+) -> Result<usize, AppError> {
+    diesel::delete(
+        journals::table
+            .filter(journals::user_id.eq(user_id))
+            .filter(journals::is_pinned.eq(false))
+            .filter(journals::created_at.lt(cutoff))
+    )
+    .execute(conn)
+    .map_err(AppError::from)
+}
+
+/// Simpan banyak jurnal sekaligus
+pub fn insert_journals(
+    conn: &mut PgConnection,
+    new_journals: &[NewJournal],
+) -> Result<usize, AppError> {
+    let mut inserted = 0;
+    // Batas parameter bind PostgreSQL adalah 65535 per query
+    for chunk in new_journals.chunks(1000) {
+        inserted += diesel::insert_into(journals::table)
+            .values(chunk)
+            .execute(conn)
+            .map_err(AppError::from)?;
+    }
+    Ok(inserted)
+}
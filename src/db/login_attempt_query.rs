@@ -0,0 +1,89 @@
+use diesel::prelude::*;
+use diesel::pg::PgConnection;
+use chrono::NaiveDateTime;
+use crate::models::security::{LoginAttempt, NewLoginAttempt};
+use crate::errors::app_error::AppError;
+use crate::schema::login_attempts;
+
+pub fn insert_login_attempt(
+    conn: &mut PgConnection,
+    attempt: &NewLoginAttempt,
+) -> Result<(), AppError> {
+    diesel::insert_into(login_attempts::table)
+        .values(attempt)
+        .execute(conn)
+        .map_err(AppError::from)?;
+
+    Ok(())
+}
+
+/// Waktu login berhasil terakhir, kegagalan sebelum ini tidak dihitung untuk lockout
+pub fn last_successful_login(
+    conn: &mut PgConnection,
+    user_id: i32,
+) -> Result<Option<NaiveDateTime>, AppError> {
+    login_attempts::table
+        .filter(login_attempts::user_id.eq(user_id))
+        .filter(login_attempts::success.eq(true))
+        .select(diesel::dsl::max(login_attempts::created_at))
+        .first(conn)
+        .map_err(AppError::from)
+}
+
+pub fn count_failures_for_user_since(
+    conn: &mut PgConnection,
+    user_id: i32,
+    since: NaiveDateTime,
+) -> Result<i64, AppError> {
+    login_attempts::table
+        .filter(login_attempts::user_id.eq(user_id))
+        .filter(login_attempts::success.eq(false))
+        .filter(login_attempts::created_at.gt(since))
+        .count()
+        .get_result(conn)
+        .map_err(AppError::from)
+}
+
+/// Kegagalan login dengan identifier yang tidak cocok dengan akun mana pun
+pub fn count_unknown_identifier_failures_since(
+    conn: &mut PgConnection,
+    identifier: &str,
+    since: NaiveDateTime,
+) -> Result<i64, AppError> {
+    login_attempts::table
+        .filter(login_attempts::user_id.is_null())
+        .filter(login_attempts::email.eq(identifier))
+        .filter(login_attempts::success.eq(false))
+        .filter(login_attempts::created_at.gt(since))
+        .count()
+        .get_result(conn)
+        .map_err(AppError::from)
+}
+
+pub fn count_failures_for_ip_since(
+    conn: &mut PgConnection,
+    ip_address: &str,
+    since: NaiveDateTime,
+) -> Result<i64, AppError> {
+    login_attempts::table
+        .filter(login_attempts::ip_address.eq(ip_address))
+        .filter(login_attempts::success.eq(false))
+        .filter(login_attempts::created_at.gt(since))
+        .count()
+        .get_result(conn)
+        .map_err(AppError::from)
+}
+
+pub fn find_recent_attempts_by_user(
+    conn: &mut PgConnection,
+    user_id: i32,
+    limit: i64,
+) -> Result<Vec<LoginAttempt>, AppError> {
+    login_attempts::table
+        .filter(login_attempts::user_id.eq(user_id))
+        .order(login_attempts::created_at.desc())
+        .limit(limit)
+        .select(LoginAttempt::as_select())
+        .load::<LoginAttempt>(conn)
+        .map_err(AppError::from)
+}
@@ -0,0 +1,129 @@
+use diesel::dsl::count_star;
+use diesel::prelude::*;
+use diesel::pg::PgConnection;
+use chrono::NaiveDateTime;
+use crate::errors::app_error::AppError;
+use crate::models::message::{Message, NewMessage};
+use crate::schema::messages;
+
+pub fn insert_message(
+    conn: &mut PgConnection,
+    message: &NewMessage,
+) -> Result<Message, AppError> {
+    diesel::insert_into(messages::table)
+        .values(message)
+        .returning(Message::as_returning())
+        .get_result(conn)
+        .map_err(AppError::from)
+}
+
+/// Pesan dalam satu percakapan, terbaru lebih dulu, sebelum `before_id` jika diisi.
+/// Pesan yang ditahan moderasi hanya terlihat oleh pengirimnya.
+pub fn find_messages(
+    conn: &mut PgConnection,
+    user_id: i32,
+    psychologist_id: i32,
+    viewer_is_psychologist: bool,
+    before_id: Option<i32>,
+    limit: i64,
+) -> Result<Vec<Message>, AppError> {
+    let mut query = messages::table
+        .filter(messages::user_id.eq(user_id))
+        .filter(messages::psychologist_id.eq(psychologist_id))
+        .filter(messages::held.eq(false).or(messages::from_psychologist.eq(viewer_is_psychologist)))
+        .order(messages::id.desc())
+        .limit(limit)
+        .select(Message::as_select())
+        .into_boxed();
+
+    if let Some(before_id) = before_id {
+        query = query.filter(messages::id.lt(before_id));
+    }
+
+    query
+        .load(conn)
+        .map_err(AppError::from)
+}
+
+/// Tandai dibaca semua pesan dari satu sisi percakapan; mengembalikan jumlah pesan yang berubah
+pub fn mark_read(
+    conn: &mut PgConnection,
+    user_id: i32,
+    psychologist_id: i32,
+    from_psychologist: bool,
+    now: NaiveDateTime,
+) -> Result<usize, AppError> {
+    diesel::update(
+        messages::table
+            .filter(messages::user_id.eq(user_id))
+            .filter(messages::psychologist_id.eq(psychologist_id))
+            .filter(messages::from_psychologist.eq(from_psychologist))
+            .filter(messages::held.eq(false))
+            .filter(messages::read_at.is_null()),
+    )
+    .set(messages::read_at.eq(now))
+    .execute(conn)
+    .map_err(AppError::from)
+}
+
+/// Pesan terakhir yang terlihat oleh pengguna di setiap percakapannya
+pub fn find_last_messages_for_user(
+    conn: &mut PgConnection,
+    user_id: i32,
+) -> Result<Vec<Message>, AppError> {
+    messages::table
+        .filter(messages::user_id.eq(user_id))
+        .filter(messages::held.eq(false).or(messages::from_psychologist.eq(false)))
+        .distinct_on(messages::psychologist_id)
+        .order((messages::psychologist_id, messages::id.desc()))
+        .select(Message::as_select())
+        .load(conn)
+        .map_err(AppError::from)
+}
+
+/// Pesan terakhir yang terlihat oleh psikolog di setiap percakapannya
+pub fn find_last_messages_for_psychologist(
+    conn: &mut PgConnection,
+    psychologist_id: i32,
+) -> Result<Vec<Message>, AppError> {
+    messages::table
+        .filter(messages::psychologist_id.eq(psychologist_id))
+        .filter(messages::held.eq(false).or(messages::from_psychologist.eq(true)))
+        .distinct_on(messages::user_id)
+        .order((messages::user_id, messages::id.desc()))
+        .select(Message::as_select())
+        .load(conn)
+        .map_err(AppError::from)
+}
+
+/// Jumlah pesan psikolog yang belum dibaca pengguna, per psikolog
+pub fn count_unread_for_user(
+    conn: &mut PgConnection,
+    user_id: i32,
+) -> Result<Vec<(i32, i64)>, AppError> {
+    messages::table
+        .filter(messages::user_id.eq(user_id))
+        .filter(messages::from_psychologist.eq(true))
+        .filter(messages::held.eq(false))
+        .filter(messages::read_at.is_null())
+        .group_by(messages::psychologist_id)
+        .select((messages::psychologist_id, count_star()))
+        .load(conn)
+        .map_err(AppError::from)
+}
+
+/// Jumlah pesan klien yang belum dibaca psikolog, per klien
+pub fn count_unread_for_psychologist(
+    conn: &mut PgConnection,
+    psychologist_id: i32,
+) -> Result<Vec<(i32, i64)>, AppError> {
+    messages::table
+        .filter(messages::psychologist_id.eq(psychologist_id))
+        .filter(messages::from_psychologist.eq(false))
+        .filter(messages::held.eq(false))
+        .filter(messages::read_at.is_null())
+        .group_by(messages::user_id)
+        .select((messages::user_id, count_star()))
+        .load(conn)
+        .map_err(AppError::from)
+}
@@ -2,4 +2,35 @@ pub mod pool;
 pub mod user_query;
 pub mod token_blacklist_query;
 pub mod mood_query;
-pub mod journal_query;
\ No newline at end of file
+pub mod journal_query;
+pub mod calendar_query;
+pub mod transaction;
+pub mod constraints;
+pub mod login_attempt_query;
+pub mod audit_log_query;
+pub mod email_change_query;
+pub mod onboarding_query;
+pub mod insight_query;
+pub mod journal_draft_query;
+pub mod device_query;
+pub mod push_outbox_query;
+pub mod backup_query;
+pub mod organization_query;
+pub mod retention_query;
+pub mod admin_analytics_query;
+pub mod password_reset_query;
+pub mod help_query;
+pub mod psychologist_query;
+pub mod appointment_query;
+pub mod message_query;
+pub mod moderation_query;
+pub mod community_query;
+pub mod checkin_query;
+pub mod webhook_query;
+pub mod health_query;
+pub mod quota_query;
+pub mod job_query;
+pub mod scheduled_job_query;
+pub mod auth_exchange_query;
+pub mod oauth_account_query;
+pub mod api_usage_query;
\ No newline at end of file
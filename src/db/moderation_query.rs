@@ -0,0 +1,128 @@
+use diesel::prelude::*;
+use diesel::pg::PgConnection;
+use chrono::NaiveDateTime;
+use crate::errors::app_error::AppError;
+use crate::models::help::{HelpRequest, HelpRequestComment};
+use crate::models::message::Message;
+use crate::models::moderation::{ModerationVerdictRecord, NewModerationVerdict};
+use crate::schema::{help_request_comments, help_requests, messages, moderation_verdicts};
+
+pub fn insert_verdict(
+    conn: &mut PgConnection,
+    verdict: &NewModerationVerdict,
+) -> Result<ModerationVerdictRecord, AppError> {
+    diesel::insert_into(moderation_verdicts::table)
+        .values(verdict)
+        .returning(ModerationVerdictRecord::as_returning())
+        .get_result(conn)
+        .map_err(AppError::from)
+}
+
+/// Antrian tinjauan: yang paling lama menunggu lebih dulu
+pub fn find_verdicts(
+    conn: &mut PgConnection,
+    status: &str,
+    limit: i64,
+) -> Result<Vec<ModerationVerdictRecord>, AppError> {
+    moderation_verdicts::table
+        .filter(moderation_verdicts::status.eq(status))
+        .order(moderation_verdicts::created_at.asc())
+        .limit(limit)
+        .select(ModerationVerdictRecord::as_select())
+        .load(conn)
+        .map_err(AppError::from)
+}
+
+pub fn find_verdict_for_update(
+    conn: &mut PgConnection,
+    verdict_id: i32,
+) -> Result<Option<ModerationVerdictRecord>, AppError> {
+    moderation_verdicts::table
+        .find(verdict_id)
+        .select(ModerationVerdictRecord::as_select())
+        .for_update()
+        .first(conn)
+        .optional()
+        .map_err(AppError::from)
+}
+
+pub fn update_verdict_status(
+    conn: &mut PgConnection,
+    verdict_id: i32,
+    status: &str,
+    reviewed_by: i32,
+    now: NaiveDateTime,
+) -> Result<ModerationVerdictRecord, AppError> {
+    diesel::update(moderation_verdicts::table.find(verdict_id))
+        .set((
+            moderation_verdicts::status.eq(status),
+            moderation_verdicts::reviewed_by.eq(reviewed_by),
+            moderation_verdicts::reviewed_at.eq(now),
+        ))
+        .returning(ModerationVerdictRecord::as_returning())
+        .get_result(conn)
+        .map_err(AppError::from)
+}
+
+pub fn find_message(
+    conn: &mut PgConnection,
+    message_id: i32,
+) -> Result<Option<Message>, AppError> {
+    messages::table
+        .find(message_id)
+        .select(Message::as_select())
+        .first(conn)
+        .optional()
+        .map_err(AppError::from)
+}
+
+pub fn set_message_held(
+    conn: &mut PgConnection,
+    message_id: i32,
+    held: bool,
+) -> Result<Option<Message>, AppError> {
+    diesel::update(messages::table.find(message_id))
+        .set(messages::held.eq(held))
+        .returning(Message::as_returning())
+        .get_result(conn)
+        .optional()
+        .map_err(AppError::from)
+}
+
+pub fn set_help_request_held(
+    conn: &mut PgConnection,
+    request_id: i32,
+    held: bool,
+) -> Result<Option<HelpRequest>, AppError> {
+    diesel::update(help_requests::table.find(request_id))
+        .set(help_requests::held.eq(held))
+        .returning(HelpRequest::as_returning())
+        .get_result(conn)
+        .optional()
+        .map_err(AppError::from)
+}
+
+pub fn find_help_comment(
+    conn: &mut PgConnection,
+    comment_id: i32,
+) -> Result<Option<HelpRequestComment>, AppError> {
+    help_request_comments::table
+        .find(comment_id)
+        .select(HelpRequestComment::as_select())
+        .first(conn)
+        .optional()
+        .map_err(AppError::from)
+}
+
+pub fn set_help_comment_held(
+    conn: &mut PgConnection,
+    comment_id: i32,
+    held: bool,
+) -> Result<Option<HelpRequestComment>, AppError> {
+    diesel::update(help_request_comments::table.find(comment_id))
+        .set(help_request_comments::held.eq(held))
+        .returning(HelpRequestComment::as_returning())
+        .get_result(conn)
+        .optional()
+        .map_err(AppError::from)
+}
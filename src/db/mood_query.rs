@@ -1,42 +1,21 @@
 use diesel::prelude::*;
-use diesel::pg::PgConnection;
-use chrono::{NaiveDate, Utc};
-use crate::models::mood::{Mood, NewMood};
+use diesel::pg::{Pg, PgConnection, PgRowByRowLoadingMode};
+use chrono::{NaiveDate, NaiveDateTime, Utc};
+use crate::models::list_sort::ListSort;
+use crate::models::mood::{DeletedMood, Mood, MoodSearchFilter, MoodType, NewMood, UpdateMoodRequest};
+use crate::utils::patch::Patch;
 use crate::errors::app_error::AppError;
-use crate::schema::moods;
+use crate::schema::{deleted_moods, moods};
 
 pub fn create_mood(
     conn: &mut PgConnection,
-    user_id: i32,
-    mood: &str,
-    emoji: &str,
-    notes: Option<String>,
-    date: Option<NaiveDate>,
+    new_mood: &NewMood,
 ) -> Result<Mood, AppError> {
-    let mood_date = date.unwrap_or_else(|| Utc::now().date_naive());
-    let now = Utc::now().naive_utc();
-    
-    let new_mood = NewMood {
-        user_id,
-        date: mood_date,
-        mood: mood.to_string(),
-        emoji: emoji.to_string(),
-        notes,
-        created_at: now,
-        updated_at: Some(now),
-    };
-
     diesel::insert_into(moods::table)
-        .values(&new_mood)
-        .execute(conn)
-        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
-
-    moods::table
-        .filter(moods::user_id.eq(user_id))
-        .filter(moods::date.eq(mood_date))
-        .select(Mood::as_select())
-        .first(conn)
-        .map_err(|e| AppError::DatabaseError(e.to_string()))
+        .values(new_mood)
+        .returning(Mood::as_returning())
+        .get_result(conn)
+        .map_err(AppError::from)
 }
 
 pub fn find_mood_by_id(
@@ -49,7 +28,7 @@ pub fn find_mood_by_id(
         .first(conn)
         .map_err(|e| match e {
             diesel::result::Error::NotFound => AppError::NotFound("Mood not found".to_string()),
-            _ => AppError::DatabaseError(e.to_string()),
+            _ => AppError::from(e),
         })
 }
 
@@ -58,18 +37,77 @@ pub fn find_moods_by_user(
     user_id: i32,
     limit: Option<i32>,
     offset: Option<i32>,
+    pinned: Option<bool>,
+    sort: ListSort,
 ) -> Result<Vec<Mood>, AppError> {
     let limit = limit.unwrap_or(50);
     let offset = offset.unwrap_or(0);
 
-    moods::table
+    let mut query = moods::table
+        .filter(moods::user_id.eq(user_id))
+        .into_boxed();
+    if let Some(pinned) = pinned {
+        query = query.filter(moods::is_pinned.eq(pinned));
+    }
+    // `id` sebagai penentu terakhir agar pagination stabil untuk nilai yang sama
+    query = match sort {
+        ListSort::DateAsc => query.order((moods::date.asc(), moods::id.asc())),
+        ListSort::UpdatedDesc => query.order((moods::updated_at.desc().nulls_last(), moods::id.desc())),
+        ListSort::DateDesc => query.order((moods::date.desc(), moods::id.desc())),
+        ListSort::Title => return Err(sort.unsupported(&ListSort::MOODS)),
+    };
+
+    query
+        .limit(limit as i64)
+        .offset(offset as i64)
+        .select(Mood::as_select())
+        .load::<Mood>(conn)
+        .map_err(AppError::from)
+}
+
+/// Escape `%`, `_` dan `\` agar teks pencarian dicocokkan apa adanya oleh LIKE/ILIKE
+fn escape_like(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Mood yang catatannya mengandung `filter.query` (tidak peka huruf besar/kecil), terbaru lebih dulu
+pub fn search_moods(
+    conn: &mut PgConnection,
+    user_id: i32,
+    filter: &MoodSearchFilter,
+) -> Result<Vec<Mood>, AppError> {
+    let limit = filter.limit.unwrap_or(50);
+    let offset = filter.offset.unwrap_or(0);
+    let search_pattern = format!("%{}%", escape_like(&filter.query));
+
+    let mut query = moods::table
         .filter(moods::user_id.eq(user_id))
+        .filter(moods::notes.ilike(search_pattern))
+        .into_boxed();
+    if let Some(ref mood) = filter.mood {
+        query = query.filter(moods::mood.eq(mood.as_str()));
+    }
+    if let Some(start_date) = filter.start_date {
+        query = query.filter(moods::date.ge(start_date));
+    }
+    if let Some(end_date) = filter.end_date {
+        query = query.filter(moods::date.le(end_date));
+    }
+
+    query
         .order(moods::date.desc())
         .limit(limit as i64)
         .offset(offset as i64)
         .select(Mood::as_select())
         .load::<Mood>(conn)
-        .map_err(|e| AppError::DatabaseError(e.to_string()))
+        .map_err(AppError::from)
 }
 
 pub fn find_mood_by_user_and_date(
@@ -84,7 +122,7 @@ pub fn find_mood_by_user_and_date(
         .first(conn)
         .map_err(|e| match e {
             diesel::result::Error::NotFound => AppError::NotFound("Mood not found for this date".to_string()),
-            _ => AppError::DatabaseError(e.to_string()),
+            _ => AppError::from(e),
         })
 }
 
@@ -100,18 +138,24 @@ pub fn find_moods_by_date_range(
         .order(moods::date.asc())
         .select(Mood::as_select())
         .load::<Mood>(conn)
-        .map_err(|e| AppError::DatabaseError(e.to_string()))
+        .map_err(AppError::from)
 }
 
 pub fn update_mood_with_date(
     conn: &mut PgConnection,
     mood_id: i32,
     user_id: i32,
-    new_mood: Option<String>,
-    new_emoji: Option<String>,
-    new_notes: Option<String>,
-    new_date: Option<NaiveDate>,
+    changes: UpdateMoodRequest,
 ) -> Result<Mood, AppError> {
+    let UpdateMoodRequest {
+        mood: new_mood,
+        emoji: new_emoji,
+        notes: new_notes,
+        details: new_details,
+        location: new_location,
+        date: new_date,
+    } = changes;
+
     let existing_mood = moods::table
         .filter(moods::id.eq(mood_id))
         .filter(moods::user_id.eq(user_id))
@@ -119,13 +163,23 @@ pub fn update_mood_with_date(
         .first::<Mood>(conn)
         .map_err(|e| match e {
             diesel::result::Error::NotFound => AppError::NotFound("Mood not found".to_string()),
-            _ => AppError::DatabaseError(e.to_string()),
+            _ => AppError::from(e),
         })?;
 
     let mood_to_update = new_mood.unwrap_or(existing_mood.mood);
     let emoji_to_update = new_emoji.unwrap_or(existing_mood.emoji);
-    let notes_to_update = if new_notes.is_some() { new_notes } else { existing_mood.notes };
+    let notes_to_update = new_notes.apply(existing_mood.notes);
     let date_to_update = new_date.unwrap_or(existing_mood.date); 
+    let details_to_update = match new_details {
+        Patch::Absent => existing_mood.details,
+        Patch::Null => None,
+        Patch::Value(details) => details.to_json(),
+    };
+    let (place_label, latitude, longitude) = match new_location {
+        Patch::Absent => (existing_mood.place_label, existing_mood.latitude, existing_mood.longitude),
+        Patch::Null => (None, None, None),
+        Patch::Value(location) => (location.label, location.latitude, location.longitude),
+    };
 
     diesel::update(moods::table.filter(moods::id.eq(mood_id)))
         .set((
@@ -133,36 +187,117 @@ pub fn update_mood_with_date(
             moods::emoji.eq(emoji_to_update),
             moods::notes.eq(notes_to_update),
             moods::date.eq(date_to_update), 
+            moods::details.eq(details_to_update),
+            moods::place_label.eq(place_label),
+            moods::latitude.eq(latitude),
+            moods::longitude.eq(longitude),
             moods::updated_at.eq(Some(Utc::now().naive_utc())),
         ))
-        .execute(conn)
-        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
-
-    find_mood_by_id(conn, mood_id)
+        .returning(Mood::as_returning())
+        .get_result(conn)
+        .map_err(AppError::from)
 }
 
-pub fn delete_mood(
+/// Pindahkan mood ke tempat sampah. Jalankan di dalam transaksi.
+pub fn move_mood_to_trash(
     conn: &mut PgConnection,
     mood_id: i32,
     user_id: i32,
+    deleted_at: NaiveDateTime,
 ) -> Result<bool, AppError> {
-    let result = diesel::delete(
+    let mood = diesel::delete(
         moods::table
             .filter(moods::id.eq(mood_id))
             .filter(moods::user_id.eq(user_id))
     )
-    .execute(conn)
-    .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    .returning(Mood::as_returning())
+    .get_result(conn)
+    .optional()
+    .map_err(AppError::from)?;
+
+    let Some(mood) = mood else {
+        return Ok(false);
+    };
+
+    diesel::insert_into(deleted_moods::table)
+        .values(&DeletedMood::from_mood(mood, deleted_at))
+        .execute(conn)
+        .map_err(AppError::from)?;
 
-    Ok(result > 0)
+    Ok(true)
+}
+
+/// Mood di tempat sampah milik user yang dihapus setelah `deleted_after`
+pub fn find_trashed_mood(
+    conn: &mut PgConnection,
+    mood_id: i32,
+    user_id: i32,
+    deleted_after: NaiveDateTime,
+) -> Result<Option<DeletedMood>, AppError> {
+    deleted_moods::table
+        .filter(deleted_moods::id.eq(mood_id))
+        .filter(deleted_moods::user_id.eq(user_id))
+        .filter(deleted_moods::deleted_at.ge(deleted_after))
+        .select(DeletedMood::as_select())
+        .first(conn)
+        .optional()
+        .map_err(AppError::from)
+}
+
+/// Kembalikan mood dari tempat sampah dengan id aslinya. Jalankan di dalam transaksi.
+pub fn restore_trashed_mood(
+    conn: &mut PgConnection,
+    trashed: DeletedMood,
+) -> Result<Mood, AppError> {
+    diesel::delete(deleted_moods::table.filter(deleted_moods::id.eq(trashed.id)))
+        .execute(conn)
+        .map_err(AppError::from)?;
+
+    diesel::insert_into(moods::table)
+        .values(&trashed.into_mood())
+        .returning(Mood::as_returning())
+        .get_result(conn)
+        .map_err(AppError::from)
+}
+
+/// Hapus permanen mood di tempat sampah yang jendela undo-nya sudah lewat
+pub fn purge_trashed_moods(
+    conn: &mut PgConnection,
+    deleted_before: NaiveDateTime,
+) -> Result<usize, AppError> {
+    diesel::delete(deleted_moods::table.filter(deleted_moods::deleted_at.lt(deleted_before)))
+        .execute(conn)
+        .map_err(AppError::from)
+}
+
+/// Tandai atau lepas tanda hari mood penting milik pengguna
+pub fn set_mood_pinned(
+    conn: &mut PgConnection,
+    mood_id: i32,
+    user_id: i32,
+    pinned: bool,
+) -> Result<Mood, AppError> {
+    diesel::update(
+        moods::table
+            .filter(moods::id.eq(mood_id))
+            .filter(moods::user_id.eq(user_id))
+    )
+    .set(moods::is_pinned.eq(pinned))
+    .returning(Mood::as_returning())
+    .get_result(conn)
+    .map_err(|e| match e {
+        diesel::result::Error::NotFound => AppError::NotFound("Mood not found".to_string()),
+        _ => AppError::from(e),
+    })
 }
 
 pub fn get_recent_moods(
     conn: &mut PgConnection,
     user_id: i32,
     days: i32,
+    today: NaiveDate,
 ) -> Result<Vec<Mood>, AppError> {
-    let cutoff_date = Utc::now().date_naive() - chrono::Duration::days(days as i64);
+    let cutoff_date = today - chrono::Duration::days(days as i64);
     
     moods::table
         .filter(moods::user_id.eq(user_id))
@@ -170,7 +305,7 @@ pub fn get_recent_moods(
         .order(moods::date.desc())
         .select(Mood::as_select())
         .load::<Mood>(conn)
-        .map_err(|e| AppError::DatabaseError(e.to_string()))
+        .map_err(AppError::from)
 }
 
 pub fn get_mood_stats_simple(
@@ -183,7 +318,96 @@ pub fn get_mood_stats_simple(
         .filter(moods::user_id.eq(user_id))
         .select(count(moods::id))
         .first(conn)
-        .map_err(|e| AppError::DatabaseError(e.to_string()))
+        .map_err(AppError::from)
+}
+
+/// Mood pengguna, dibatasi ke rentang tanggal (inklusif) jika ada
+fn moods_in_range(user_id: i32, range: Option<(NaiveDate, NaiveDate)>) -> moods::BoxedQuery<'static, Pg> {
+    let mut query = moods::table.filter(moods::user_id.eq(user_id)).into_boxed();
+    if let Some((start_date, end_date)) = range {
+        query = query.filter(moods::date.between(start_date, end_date));
+    }
+    query
+}
+
+/// Jumlah mood pengguna per jenis mood (GROUP BY mood), seluruh riwayat atau dalam rentang tanggal
+pub fn count_moods_by_type(
+    conn: &mut PgConnection,
+    user_id: i32,
+    range: Option<(NaiveDate, NaiveDate)>,
+) -> Result<Vec<(String, i64)>, AppError> {
+    use diesel::dsl::count_star;
+
+    let mut query = moods::table
+        .filter(moods::user_id.eq(user_id))
+        .group_by(moods::mood)
+        .select((moods::mood, count_star()))
+        .into_boxed();
+    if let Some((start_date, end_date)) = range {
+        query = query.filter(moods::date.between(start_date, end_date));
+    }
+    query.load::<(String, i64)>(conn).map_err(AppError::from)
+}
+
+/// Rata-rata skor mood (1-5) dihitung langsung di database
+pub fn average_mood_score(
+    conn: &mut PgConnection,
+    user_id: i32,
+    range: Option<(NaiveDate, NaiveDate)>,
+) -> Result<Option<f64>, AppError> {
+    use diesel::dsl::sql;
+    use diesel::sql_types::{Double, Nullable};
+
+    moods_in_range(user_id, range)
+        .select(sql::<Nullable<Double>>(&mood_score_average_sql()))
+        .first(conn)
+        .map_err(AppError::from)
+}
+
+/// Hari dengan skor mood tertinggi (`best`) atau terendah; jika seri, tanggal paling awal
+pub fn find_extreme_mood_day(
+    conn: &mut PgConnection,
+    user_id: i32,
+    range: Option<(NaiveDate, NaiveDate)>,
+    best: bool,
+) -> Result<Option<(NaiveDate, String)>, AppError> {
+    use diesel::dsl::sql;
+    use diesel::sql_types::Integer;
+
+    let direction = if best { "DESC" } else { "ASC" };
+    moods_in_range(user_id, range)
+        .filter(moods::mood.eq_any(MoodType::ALL.iter().map(|mood_type| mood_type.as_str())))
+        .order((sql::<Integer>(&format!("{} {}", mood_score_sql(), direction)), moods::date.asc()))
+        .select((moods::date, moods::mood))
+        .first(conn)
+        .optional()
+        .map_err(AppError::from)
+}
+
+/// `AVG(CASE mood WHEN 'very sad' THEN 1 ... END)`
+fn mood_score_average_sql() -> String {
+    format!("AVG({})::float8", mood_score_sql())
+}
+
+/// `CASE mood WHEN 'very sad' THEN 1 ... END`, dibangun dari `MoodType::score()`
+fn mood_score_sql() -> String {
+    let cases: String = MoodType::ALL
+        .iter()
+        .map(|mood_type| format!(" WHEN '{}' THEN {}", mood_type.as_str(), mood_type.score()))
+        .collect();
+    format!("CASE mood{} END", cases)
+}
+
+/// `updated_at` terbaru dari mood pengguna, dipakai sebagai versi cache statistik
+pub fn latest_mood_update(
+    conn: &mut PgConnection,
+    user_id: i32,
+) -> Result<Option<NaiveDateTime>, AppError> {
+    moods::table
+        .filter(moods::user_id.eq(user_id))
+        .select(diesel::dsl::max(moods::updated_at))
+        .first(conn)
+        .map_err(AppError::from)
 }
 
 // ✅ Added missing function
@@ -201,7 +425,52 @@ pub fn check_mood_exists_for_date(
             .filter(moods::date.eq(date))
     ))
     .get_result(conn)
-    .map_err(|e| AppError::DatabaseError(e.to_string()))
+    .map_err(AppError::from)
+}
+
+/// Tanggal-tanggal dalam rentang yang sudah punya mood
+pub fn find_mood_dates_in_range(
+    conn: &mut PgConnection,
+    user_id: i32,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+) -> Result<Vec<NaiveDate>, AppError> {
+    moods::table
+        .filter(moods::user_id.eq(user_id))
+        .filter(moods::date.between(start_date, end_date))
+        .select(moods::date)
+        .load::<NaiveDate>(conn)
+        .map_err(AppError::from)
+}
+
+/// Semua tanggal yang punya mood sampai `end_date`, untuk menghitung streak
+pub fn find_mood_dates_until(
+    conn: &mut PgConnection,
+    user_id: i32,
+    end_date: NaiveDate,
+) -> Result<Vec<NaiveDate>, AppError> {
+    moods::table
+        .filter(moods::user_id.eq(user_id))
+        .filter(moods::date.le(end_date))
+        .select(moods::date)
+        .load::<NaiveDate>(conn)
+        .map_err(AppError::from)
+}
+
+/// Mood beberapa pengguna sekaligus dalam rentang tanggal, misalnya anggota organisasi
+pub fn find_moods_by_users_in_range(
+    conn: &mut PgConnection,
+    user_ids: &[i32],
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+) -> Result<Vec<Mood>, AppError> {
+    moods::table
+        .filter(moods::user_id.eq_any(user_ids))
+        .filter(moods::date.between(start_date, end_date))
+        .order(moods::date.asc())
+        .select(Mood::as_select())
+        .load::<Mood>(conn)
+        .map_err(AppError::from)
 }
 
 pub fn check_mood_exists_for_date_excluding(
@@ -220,7 +489,7 @@ pub fn check_mood_exists_for_date_excluding(
             .filter(moods::id.ne(excluding_mood_id))
     ))
     .get_result(conn)
-    .map_err(|e| AppError::DatabaseError(e.to_string()))
+    .map_err(AppError::from)
 }
 
 pub fn get_all_moods_by_user(
@@ -232,5 +501,124 @@ pub fn get_all_moods_by_user(
         .order(moods::date.desc())
         .select(Mood::as_select())
         .load::<Mood>(conn)
-        .map_err(|e| AppError::DatabaseError(e.to_string()))
-}
\ No newline at end of file
+        .map_err(AppError::from)
+}
+/// Sama dengan `get_all_moods_by_user`, tetapi baris dibaca satu per satu dari
+/// database dan diteruskan ke `f` tanpa menampung seluruh hasil
+pub fn for_each_mood_by_user<F>(
+    conn: &mut PgConnection,
+    user_id: i32,
+    mut f: F,
+) -> Result<(), AppError>
+where
+    F: FnMut(Mood) -> Result<(), AppError>,
+{
+    let rows = moods::table
+        .filter(moods::user_id.eq(user_id))
+        .order(moods::date.desc())
+        .select(Mood::as_select())
+        .load_iter::<Mood, PgRowByRowLoadingMode>(conn)?;
+
+    for mood in rows {
+        f(mood?)?;
+    }
+    Ok(())
+}
+
+/// ID pengguna yang punya catatan mood sejak `since`, dipakai oleh job insight
+pub fn find_user_ids_with_moods_since(
+    conn: &mut PgConnection,
+    since: NaiveDate,
+) -> Result<Vec<i32>, AppError> {
+    moods::table
+        .filter(moods::date.ge(since))
+        .select(moods::user_id)
+        .distinct()
+        .load::<i32>(conn)
+        .map_err(AppError::from)
+}
+
+/// Jumlah catatan dan rata-rata skor per hari dalam minggu (`EXTRACT(DOW)`, 0 = Minggu)
+pub fn average_score_by_weekday(
+    conn: &mut PgConnection,
+    user_id: i32,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+) -> Result<Vec<(i32, i64, Option<f64>)>, AppError> {
+    use diesel::dsl::{count_star, sql};
+    use diesel::sql_types::{Double, Integer, Nullable};
+
+    let day_of_week = || sql::<Integer>("EXTRACT(DOW FROM date)::int4");
+
+    moods::table
+        .filter(moods::user_id.eq(user_id))
+        .filter(moods::date.between(start_date, end_date))
+        .group_by(day_of_week())
+        .select((day_of_week(), count_star(), sql::<Nullable<Double>>(&mood_score_average_sql())))
+        .load::<(i32, i64, Option<f64>)>(conn)
+        .map_err(AppError::from)
+}
+
+/// `(label, latitude, longitude, jumlah catatan, rata-rata skor)`
+pub type PlaceScoreRow = (Option<String>, Option<f64>, Option<f64>, i64, Option<f64>);
+
+/// Jumlah catatan dan rata-rata skor per tempat. Mood berlabel dikelompokkan menurut label;
+/// koordinat hanya dipakai untuk mood tanpa label.
+pub fn average_score_by_place(
+    conn: &mut PgConnection,
+    user_id: i32,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+) -> Result<Vec<PlaceScoreRow>, AppError> {
+    use diesel::dsl::{count_star, sql};
+    use diesel::sql_types::{Double, Nullable, Text};
+
+    // Kolom label juga lewat `sql` agar bisa dikelompokkan bersama ekspresi CASE
+    let label = || sql::<Nullable<Text>>("place_label");
+    let latitude = || sql::<Nullable<Double>>("CASE WHEN place_label IS NULL THEN latitude END");
+    let longitude = || sql::<Nullable<Double>>("CASE WHEN place_label IS NULL THEN longitude END");
+
+    moods::table
+        .filter(moods::user_id.eq(user_id))
+        .filter(moods::date.between(start_date, end_date))
+        .filter(moods::place_label.is_not_null().or(moods::latitude.is_not_null()))
+        .group_by((label(), latitude(), longitude()))
+        .select((
+            label(),
+            latitude(),
+            longitude(),
+            count_star(),
+            sql::<Nullable<Double>>(&mood_score_average_sql()),
+        ))
+        .load::<PlaceScoreRow>(conn)
+        .map_err(AppError::from)
+}
+
+/// Simpan banyak mood sekaligus; tanggal yang sudah terisi dilewati
+pub fn insert_moods(
+    conn: &mut PgConnection,
+    new_moods: &[NewMood],
+) -> Result<usize, AppError> {
+    let mut inserted = 0;
+    // Batas parameter bind PostgreSQL adalah 65535 per query
+    for chunk in new_moods.chunks(1000) {
+        inserted += diesel::insert_into(moods::table)
+            .values(chunk)
+            .on_conflict((moods::user_id, moods::date))
+            .do_nothing()
+            .execute(conn)
+            .map_err(AppError::from)?;
+    }
+    Ok(inserted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn like_wildcards_are_escaped() {
+        assert_eq!(escape_like("100%_done\\"), "100\\%\\_done\\\\");
+        assert_eq!(escape_like("biasa"), "biasa");
+    }
+}
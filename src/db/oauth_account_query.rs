@@ -0,0 +1,65 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use diesel::pg::PgConnection;
+use crate::errors::app_error::AppError;
+use crate::models::oauth_account::{NewOAuthAccount, OAuthAccount, OAuthTokenUpdate};
+use crate::schema::oauth_accounts;
+
+/// Simpan akun penyedia saat login; akun yang sudah ada diperbarui tanpa menghapus refresh token lama
+pub fn upsert_account(
+    conn: &mut PgConnection,
+    account: &NewOAuthAccount,
+) -> Result<OAuthAccount, AppError> {
+    diesel::insert_into(oauth_accounts::table)
+        .values(account)
+        .on_conflict((oauth_accounts::provider, oauth_accounts::provider_user_id))
+        .do_update()
+        .set(account)
+        .returning(OAuthAccount::as_returning())
+        .get_result(conn)
+        .map_err(AppError::from)
+}
+
+pub fn find_user_account(
+    conn: &mut PgConnection,
+    user_id: i32,
+    provider: &str,
+) -> Result<Option<OAuthAccount>, AppError> {
+    oauth_accounts::table
+        .filter(oauth_accounts::user_id.eq(user_id))
+        .filter(oauth_accounts::provider.eq(provider))
+        .select(OAuthAccount::as_select())
+        .first(conn)
+        .optional()
+        .map_err(AppError::from)
+}
+
+pub fn update_tokens(
+    conn: &mut PgConnection,
+    account_id: i32,
+    update: &OAuthTokenUpdate,
+) -> Result<(), AppError> {
+    diesel::update(oauth_accounts::table.filter(oauth_accounts::id.eq(account_id)))
+        .set(update)
+        .execute(conn)
+        .map(|_| ())
+        .map_err(AppError::from)
+}
+
+/// Refresh token yang dicabut pengguna tidak bisa dipakai lagi; pengguna harus login Google ulang
+pub fn clear_tokens(
+    conn: &mut PgConnection,
+    account_id: i32,
+    now: NaiveDateTime,
+) -> Result<(), AppError> {
+    diesel::update(oauth_accounts::table.filter(oauth_accounts::id.eq(account_id)))
+        .set((
+            oauth_accounts::refresh_token_encrypted.eq(None::<String>),
+            oauth_accounts::access_token_encrypted.eq(None::<String>),
+            oauth_accounts::access_token_expires_at.eq(None::<NaiveDateTime>),
+            oauth_accounts::updated_at.eq(now),
+        ))
+        .execute(conn)
+        .map(|_| ())
+        .map_err(AppError::from)
+}
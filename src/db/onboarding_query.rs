@@ -0,0 +1,106 @@
+use diesel::prelude::*;
+use diesel::pg::PgConnection;
+use diesel::upsert::excluded;
+use crate::errors::app_error::AppError;
+use crate::models::onboarding::{OnboardingStep, UserOnboarding};
+use crate::schema::user_onboarding;
+use chrono::Utc;
+
+diesel::define_sql_function!(
+    fn coalesce(
+        x: diesel::sql_types::Nullable<diesel::sql_types::Timestamp>,
+        y: diesel::sql_types::Nullable<diesel::sql_types::Timestamp>
+    ) -> diesel::sql_types::Nullable<diesel::sql_types::Timestamp>
+);
+
+pub fn find_onboarding_by_user(
+    conn: &mut PgConnection,
+    user_id: i32,
+) -> Result<Option<UserOnboarding>, AppError> {
+    user_onboarding::table
+        .filter(user_onboarding::user_id.eq(user_id))
+        .select(UserOnboarding::as_select())
+        .first(conn)
+        .optional()
+        .map_err(AppError::from)
+}
+
+/// Tandai langkah selesai; waktu penyelesaian pertama dipertahankan jika sudah ada
+pub fn mark_step_completed(
+    conn: &mut PgConnection,
+    user_id: i32,
+    step: OnboardingStep,
+) -> Result<(), AppError> {
+    let now = Utc::now().naive_utc();
+    let mut row = UserOnboarding {
+        user_id,
+        updated_at: now,
+        ..Default::default()
+    };
+
+    let insert = diesel::insert_into(user_onboarding::table);
+    let result = match step {
+        OnboardingStep::ProfileFilled => {
+            row.profile_completed_at = Some(now);
+            insert
+                .values(&row)
+                .on_conflict(user_onboarding::user_id)
+                .do_update()
+                .set((
+                    user_onboarding::profile_completed_at.eq(coalesce(
+                        user_onboarding::profile_completed_at,
+                        excluded(user_onboarding::profile_completed_at),
+                    )),
+                    user_onboarding::updated_at.eq(now),
+                ))
+                .execute(conn)
+        }
+        OnboardingStep::FirstMood => {
+            row.first_mood_at = Some(now);
+            insert
+                .values(&row)
+                .on_conflict(user_onboarding::user_id)
+                .do_update()
+                .set((
+                    user_onboarding::first_mood_at.eq(coalesce(
+                        user_onboarding::first_mood_at,
+                        excluded(user_onboarding::first_mood_at),
+                    )),
+                    user_onboarding::updated_at.eq(now),
+                ))
+                .execute(conn)
+        }
+        OnboardingStep::FirstJournal => {
+            row.first_journal_at = Some(now);
+            insert
+                .values(&row)
+                .on_conflict(user_onboarding::user_id)
+                .do_update()
+                .set((
+                    user_onboarding::first_journal_at.eq(coalesce(
+                        user_onboarding::first_journal_at,
+                        excluded(user_onboarding::first_journal_at),
+                    )),
+                    user_onboarding::updated_at.eq(now),
+                ))
+                .execute(conn)
+        }
+        OnboardingStep::ReminderSet => {
+            row.reminder_set_at = Some(now);
+            insert
+                .values(&row)
+                .on_conflict(user_onboarding::user_id)
+                .do_update()
+                .set((
+                    user_onboarding::reminder_set_at.eq(coalesce(
+                        user_onboarding::reminder_set_at,
+                        excluded(user_onboarding::reminder_set_at),
+                    )),
+                    user_onboarding::updated_at.eq(now),
+                ))
+                .execute(conn)
+        }
+    };
+
+    result.map(|_| ()).map_err(AppError::from)
+}
@@ -0,0 +1,178 @@
+use diesel::prelude::*;
+use diesel::pg::PgConnection;
+use chrono::{NaiveDateTime, Utc};
+use crate::errors::app_error::AppError;
+use crate::models::organization::{
+    NewOrganization, NewOrganizationInvitation, NewOrganizationMember, Organization, OrganizationInvitation,
+    OrganizationMember,
+};
+use crate::schema::{organization_invitations, organization_members, organizations, users};
+
+pub fn create_organization(
+    conn: &mut PgConnection,
+    organization: &NewOrganization,
+) -> Result<Organization, AppError> {
+    diesel::insert_into(organizations::table)
+        .values(organization)
+        .returning(Organization::as_returning())
+        .get_result(conn)
+        .map_err(AppError::from)
+}
+
+pub fn find_organization_by_id(
+    conn: &mut PgConnection,
+    organization_id: i32,
+) -> Result<Organization, AppError> {
+    organizations::table
+        .find(organization_id)
+        .select(Organization::as_select())
+        .first(conn)
+        .map_err(AppError::from)
+}
+
+pub fn add_member(
+    conn: &mut PgConnection,
+    member: &NewOrganizationMember,
+) -> Result<OrganizationMember, AppError> {
+    diesel::insert_into(organization_members::table)
+        .values(member)
+        .returning(OrganizationMember::as_returning())
+        .get_result(conn)
+        .map_err(AppError::from)
+}
+
+/// Keanggotaan pengguna di organisasi, `None` jika bukan anggota
+pub fn find_membership(
+    conn: &mut PgConnection,
+    organization_id: i32,
+    user_id: i32,
+) -> Result<Option<OrganizationMember>, AppError> {
+    organization_members::table
+        .filter(organization_members::organization_id.eq(organization_id))
+        .filter(organization_members::user_id.eq(user_id))
+        .select(OrganizationMember::as_select())
+        .first(conn)
+        .optional()
+        .map_err(AppError::from)
+}
+
+/// Organisasi tempat pengguna menjadi anggota, beserta keanggotaannya
+pub fn find_organizations_by_user(
+    conn: &mut PgConnection,
+    user_id: i32,
+) -> Result<Vec<(Organization, OrganizationMember)>, AppError> {
+    organizations::table
+        .inner_join(organization_members::table)
+        .filter(organization_members::user_id.eq(user_id))
+        .order(organizations::name.asc())
+        .select((Organization::as_select(), OrganizationMember::as_select()))
+        .load(conn)
+        .map_err(AppError::from)
+}
+
+/// Semua anggota organisasi beserta username
+pub fn find_members(
+    conn: &mut PgConnection,
+    organization_id: i32,
+) -> Result<Vec<(OrganizationMember, String)>, AppError> {
+    organization_members::table
+        .inner_join(users::table)
+        .filter(organization_members::organization_id.eq(organization_id))
+        .order(organization_members::joined_at.asc())
+        .select((OrganizationMember::as_select(), users::username))
+        .load(conn)
+        .map_err(AppError::from)
+}
+
+pub fn count_admins(
+    conn: &mut PgConnection,
+    organization_id: i32,
+) -> Result<i64, AppError> {
+    organization_members::table
+        .filter(organization_members::organization_id.eq(organization_id))
+        .filter(organization_members::role.eq("admin"))
+        .count()
+        .get_result(conn)
+        .map_err(AppError::from)
+}
+
+pub fn remove_member(
+    conn: &mut PgConnection,
+    organization_id: i32,
+    user_id: i32,
+) -> Result<usize, AppError> {
+    diesel::delete(
+        organization_members::table
+            .filter(organization_members::organization_id.eq(organization_id))
+            .filter(organization_members::user_id.eq(user_id)),
+    )
+    .execute(conn)
+    .map_err(AppError::from)
+}
+
+pub fn set_consent(
+    conn: &mut PgConnection,
+    organization_id: i32,
+    user_id: i32,
+    consent: bool,
+) -> Result<OrganizationMember, AppError> {
+    diesel::update(
+        organization_members::table
+            .filter(organization_members::organization_id.eq(organization_id))
+            .filter(organization_members::user_id.eq(user_id)),
+    )
+    .set((
+        organization_members::data_sharing_consent.eq(consent),
+        organization_members::consent_updated_at.eq(Some(Utc::now().naive_utc())),
+    ))
+    .returning(OrganizationMember::as_returning())
+    .get_result(conn)
+    .map_err(AppError::from)
+}
+
+pub fn create_invitation(
+    conn: &mut PgConnection,
+    invitation: &NewOrganizationInvitation,
+) -> Result<OrganizationInvitation, AppError> {
+    diesel::insert_into(organization_invitations::table)
+        .values(invitation)
+        .returning(OrganizationInvitation::as_returning())
+        .get_result(conn)
+        .map_err(AppError::from)
+}
+
+pub fn find_invitation_by_token(
+    conn: &mut PgConnection,
+    token: &str,
+) -> Result<Option<OrganizationInvitation>, AppError> {
+    organization_invitations::table
+        .filter(organization_invitations::token.eq(token))
+        .select(OrganizationInvitation::as_select())
+        .first(conn)
+        .optional()
+        .map_err(AppError::from)
+}
+
+pub fn delete_invitation(
+    conn: &mut PgConnection,
+    invitation_id: i32,
+) -> Result<usize, AppError> {
+    diesel::delete(organization_invitations::table.filter(organization_invitations::id.eq(invitation_id)))
+        .execute(conn)
+        .map_err(AppError::from)
+}
+
+/// Hapus undangan kedaluwarsa milik organisasi agar tabel tidak terus bertambah
+pub fn delete_expired_invitations(
+    conn: &mut PgConnection,
+    organization_id: i32,
+    now: NaiveDateTime,
+) -> Result<usize, AppError> {
+    diesel::delete(
+        organization_invitations::table
+            .filter(organization_invitations::organization_id.eq(organization_id))
+            .filter(organization_invitations::expires_at.lt(now)),
+    )
+    .execute(conn)
+    .map_err(AppError::from)
+}
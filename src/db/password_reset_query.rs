@@ -0,0 +1,47 @@
+use diesel::prelude::*;
+use diesel::pg::PgConnection;
+use crate::errors::app_error::AppError;
+use crate::models::password_reset::{NewPasswordResetToken, PasswordResetToken};
+use crate::schema::password_reset_tokens;
+
+/// Simpan token reset, menggantikan token lama milik user yang sama
+pub fn upsert_token(
+    conn: &mut PgConnection,
+    token: &NewPasswordResetToken,
+) -> Result<PasswordResetToken, AppError> {
+    diesel::insert_into(password_reset_tokens::table)
+        .values(token)
+        .on_conflict(password_reset_tokens::user_id)
+        .do_update()
+        .set((
+            password_reset_tokens::token.eq(&token.token),
+            password_reset_tokens::expires_at.eq(token.expires_at),
+            password_reset_tokens::created_at.eq(token.created_at),
+        ))
+        .returning(PasswordResetToken::as_returning())
+        .get_result(conn)
+        .map_err(AppError::from)
+}
+
+pub fn find_token(
+    conn: &mut PgConnection,
+    token_str: &str,
+) -> Result<PasswordResetToken, AppError> {
+    password_reset_tokens::table
+        .filter(password_reset_tokens::token.eq(token_str))
+        .select(PasswordResetToken::as_select())
+        .first(conn)
+        .map_err(|e| match e {
+            diesel::result::Error::NotFound => AppError::BadRequest("Invalid or expired password reset token".to_string()),
+            _ => AppError::from(e),
+        })
+}
+
+pub fn delete_token_by_user(
+    conn: &mut PgConnection,
+    user_id: i32,
+) -> Result<usize, AppError> {
+    diesel::delete(password_reset_tokens::table.filter(password_reset_tokens::user_id.eq(user_id)))
+        .execute(conn)
+        .map_err(AppError::from)
+}
@@ -1,9 +1,119 @@
-use diesel::r2d2::{self, ConnectionManager};
+use std::time::Duration;
+use diesel::connection::SimpleConnection;
+use diesel::r2d2::{self, ConnectionManager, CustomizeConnection};
 use diesel::pg::PgConnection;
+use crate::config::app_config::{app_config, AppConfig};
+use crate::errors::app_error::AppError;
+use crate::models::status::PoolStats;
 
-pub fn create_pool(database_url: String) -> r2d2::Pool<ConnectionManager<PgConnection>> {
+pub type DbPool = r2d2::Pool<ConnectionManager<PgConnection>>;
+pub type DbConnection = r2d2::PooledConnection<ConnectionManager<PgConnection>>;
+
+/// Pengaturan sesi yang dipasang setiap kali pool membuka koneksi baru
+#[derive(Debug)]
+struct SessionSettings {
+    statement_timeout_ms: u64,
+}
+
+impl CustomizeConnection<PgConnection, r2d2::Error> for SessionSettings {
+    fn on_acquire(&self, conn: &mut PgConnection) -> Result<(), r2d2::Error> {
+        if self.statement_timeout_ms == 0 {
+            return Ok(());
+        }
+        conn.batch_execute(&format!("SET statement_timeout = {}", self.statement_timeout_ms))
+            .map_err(r2d2::Error::QueryError)
+    }
+}
+
+/// Pool dibuat tanpa langsung membuka koneksi, jadi server tetap bisa start walaupun
+/// database belum siap; koneksi dibuka saat pertama kali dibutuhkan.
+pub fn create_pool(database_url: String, config: &AppConfig) -> DbPool {
     let manager = ConnectionManager::<PgConnection>::new(database_url);
     r2d2::Pool::builder()
-        .build(manager)
-        .expect("Failed to create pool.")
-}
\ No newline at end of file
+        .max_size(config.db_pool_max_size)
+        .min_idle(config.db_pool_min_idle)
+        .connection_timeout(Duration::from_secs(config.db_connection_timeout_secs))
+        .connection_customizer(Box::new(SessionSettings {
+            statement_timeout_ms: config.db_statement_timeout_ms,
+        }))
+        .build_unchecked(manager)
+}
+
+fn pool_stats(pool: &DbPool) -> PoolStats {
+    let state = pool.state();
+    PoolStats {
+        max_size: pool.max_size(),
+        connections: state.connections,
+        idle_connections: state.idle_connections,
+    }
+}
+
+/// Pool database primary (baca/tulis) dan replica opsional (hanya baca).
+/// Tanpa replica, semua koneksi diambil dari primary.
+#[derive(Clone)]
+pub struct DbPools {
+    primary: DbPool,
+    replica: Option<DbPool>,
+}
+
+impl DbPools {
+    pub fn new(primary: DbPool, replica: Option<DbPool>) -> Self {
+        DbPools { primary, replica }
+    }
+
+    pub fn from_urls(primary_url: String, replica_url: Option<String>) -> Self {
+        let config = app_config();
+        DbPools::new(
+            create_pool(primary_url, config),
+            replica_url.map(|url| create_pool(url, config)),
+        )
+    }
+
+    /// Tunggu sampai primary bisa dihubungi, maksimal `attempts` kali dan masing-masing `per_attempt`.
+    /// `false` jika tetap gagal; pemanggil boleh lanjut karena pool akan mencoba lagi sendiri.
+    pub fn wait_for_primary(&self, attempts: u32, per_attempt: Duration) -> bool {
+        let attempts = attempts.max(1);
+        for attempt in 1..=attempts {
+            match self.primary.get_timeout(per_attempt) {
+                Ok(_) => return true,
+                Err(e) => eprintln!("⚠️ Database not reachable (attempt {}/{}): {}", attempt, attempts, e),
+            }
+        }
+        false
+    }
+
+    /// Cek cepat apakah primary bisa memberikan koneksi dalam `timeout`
+    pub fn primary_available(&self, timeout: Duration) -> bool {
+        self.primary.get_timeout(timeout).is_ok()
+    }
+
+    pub fn primary_stats(&self) -> PoolStats {
+        pool_stats(&self.primary)
+    }
+
+    pub fn replica_stats(&self) -> Option<PoolStats> {
+        self.replica.as_ref().map(pool_stats)
+    }
+
+    /// Koneksi ke primary, untuk tulis dan baca yang harus selalu terbaru
+    pub fn conn_write(&self) -> Result<DbConnection, AppError> {
+        self.primary
+            .get()
+            .map_err(|e| AppError::internal("Failed to get DB connection", e))
+    }
+
+    /// Koneksi ke replica untuk query berat yang boleh sedikit tertinggal (stats, list, search).
+    /// Jatuh ke primary jika replica tidak dikonfigurasi atau sedang tidak tersedia.
+    pub fn conn_read(&self) -> Result<DbConnection, AppError> {
+        match self.replica {
+            Some(ref replica) => match replica.get() {
+                Ok(conn) => Ok(conn),
+                Err(e) => {
+                    eprintln!("⚠️ Replica unavailable, reading from primary: {}", e);
+                    self.conn_write()
+                }
+            },
+            None => self.conn_write(),
+        }
+    }
+}
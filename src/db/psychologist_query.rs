@@ -0,0 +1,205 @@
+use diesel::dsl::{exists, not};
+use diesel::prelude::*;
+use diesel::pg::PgConnection;
+use chrono::NaiveDateTime;
+use crate::errors::app_error::AppError;
+use crate::models::psychologist::{
+    NewPsychologist, NewPsychologistRequest, NewPsychologistSlot, Psychologist, PsychologistRequest,
+    PsychologistSlot,
+};
+use crate::schema::{psychologist_requests, psychologist_slots, psychologists};
+
+diesel::define_sql_function!(fn lower(x: diesel::sql_types::Text) -> diesel::sql_types::Text);
+
+pub fn create_psychologist(
+    conn: &mut PgConnection,
+    psychologist: &NewPsychologist,
+) -> Result<Psychologist, AppError> {
+    diesel::insert_into(psychologists::table)
+        .values(psychologist)
+        .returning(Psychologist::as_returning())
+        .get_result(conn)
+        .map_err(AppError::from)
+}
+
+/// Psikolog aktif, opsional difilter spesialisasi (huruf kecil) dan bahasa
+pub fn find_active_psychologists(
+    conn: &mut PgConnection,
+    specialization: Option<&str>,
+    language: Option<&str>,
+) -> Result<Vec<Psychologist>, AppError> {
+    let mut query = psychologists::table
+        .filter(psychologists::is_active.eq(true))
+        .order(psychologists::name.asc())
+        .select(Psychologist::as_select())
+        .into_boxed();
+
+    if let Some(specialization) = specialization {
+        query = query.filter(lower(psychologists::specialization).eq(specialization));
+    }
+    if let Some(language) = language {
+        query = query.filter(psychologists::languages.contains(vec![language]));
+    }
+
+    query
+        .load(conn)
+        .map_err(AppError::from)
+}
+
+pub fn find_active_psychologist(
+    conn: &mut PgConnection,
+    psychologist_id: i32,
+) -> Result<Option<Psychologist>, AppError> {
+    psychologists::table
+        .find(psychologist_id)
+        .filter(psychologists::is_active.eq(true))
+        .select(Psychologist::as_select())
+        .first(conn)
+        .optional()
+        .map_err(AppError::from)
+}
+
+pub fn insert_slots(
+    conn: &mut PgConnection,
+    slots: &[NewPsychologistSlot],
+) -> Result<Vec<PsychologistSlot>, AppError> {
+    diesel::insert_into(psychologist_slots::table)
+        .values(slots)
+        .returning(PsychologistSlot::as_returning())
+        .get_results(conn)
+        .map_err(AppError::from)
+}
+
+/// Jadwal setelah `after` yang belum dipesan, urut waktu
+pub fn find_available_slots(
+    conn: &mut PgConnection,
+    psychologist_id: i32,
+    after: NaiveDateTime,
+) -> Result<Vec<PsychologistSlot>, AppError> {
+    psychologist_slots::table
+        .filter(psychologist_slots::psychologist_id.eq(psychologist_id))
+        .filter(psychologist_slots::starts_at.gt(after))
+        .filter(not(exists(
+            psychologist_requests::table
+                .filter(psychologist_requests::slot_id.eq(psychologist_slots::id.nullable())),
+        )))
+        .order(psychologist_slots::starts_at.asc())
+        .select(PsychologistSlot::as_select())
+        .load(conn)
+        .map_err(AppError::from)
+}
+
+/// Jadwal dengan kunci baris sampai transaksi selesai, agar dua pemesanan
+/// untuk jadwal yang sama diproses bergantian
+pub fn find_slot_for_update(
+    conn: &mut PgConnection,
+    slot_id: i32,
+) -> Result<Option<PsychologistSlot>, AppError> {
+    psychologist_slots::table
+        .find(slot_id)
+        .select(PsychologistSlot::as_select())
+        .for_update()
+        .first(conn)
+        .optional()
+        .map_err(AppError::from)
+}
+
+pub fn is_slot_booked(
+    conn: &mut PgConnection,
+    slot_id: i32,
+) -> Result<bool, AppError> {
+    diesel::select(exists(
+        psychologist_requests::table.filter(psychologist_requests::slot_id.eq(slot_id)),
+    ))
+    .get_result(conn)
+    .map_err(AppError::from)
+}
+
+pub fn create_request(
+    conn: &mut PgConnection,
+    request: &NewPsychologistRequest,
+) -> Result<PsychologistRequest, AppError> {
+    diesel::insert_into(psychologist_requests::table)
+        .values(request)
+        .returning(PsychologistRequest::as_returning())
+        .get_result(conn)
+        .map_err(AppError::from)
+}
+
+/// Permintaan milik pengguna beserta jadwal yang dipesan
+pub fn find_requests_by_user(
+    conn: &mut PgConnection,
+    user_id: i32,
+) -> Result<Vec<(PsychologistRequest, Option<PsychologistSlot>)>, AppError> {
+    psychologist_requests::table
+        .left_join(psychologist_slots::table)
+        .filter(psychologist_requests::user_id.eq(user_id))
+        .order(psychologist_requests::created_at.desc())
+        .select((PsychologistRequest::as_select(), Option::<PsychologistSlot>::as_select()))
+        .load(conn)
+        .map_err(AppError::from)
+}
+
+/// Pengajuan dengan kunci baris sampai transaksi selesai
+pub fn find_request_for_update(
+    conn: &mut PgConnection,
+    request_id: i32,
+) -> Result<Option<PsychologistRequest>, AppError> {
+    psychologist_requests::table
+        .find(request_id)
+        .select(PsychologistRequest::as_select())
+        .for_update()
+        .first(conn)
+        .optional()
+        .map_err(AppError::from)
+}
+
+/// Ganti atau lepaskan (`None`) jadwal yang dipesan sebuah pengajuan
+pub fn update_request_slot(
+    conn: &mut PgConnection,
+    request_id: i32,
+    slot_id: Option<i32>,
+) -> Result<(), AppError> {
+    diesel::update(psychologist_requests::table.find(request_id))
+        .set(psychologist_requests::slot_id.eq(slot_id))
+        .execute(conn)
+        .map_err(AppError::from)?;
+
+    Ok(())
+}
+
+pub fn find_slot(
+    conn: &mut PgConnection,
+    slot_id: i32,
+) -> Result<Option<PsychologistSlot>, AppError> {
+    psychologist_slots::table
+        .find(slot_id)
+        .select(PsychologistSlot::as_select())
+        .first(conn)
+        .optional()
+        .map_err(AppError::from)
+}
+
+pub fn find_psychologist_name(
+    conn: &mut PgConnection,
+    psychologist_id: i32,
+) -> Result<String, AppError> {
+    psychologists::table
+        .find(psychologist_id)
+        .select(psychologists::name)
+        .first(conn)
+        .map_err(AppError::from)
+}
+
+/// Psikolog yang ditautkan ke akun pengguna ini, jika ada
+pub fn find_psychologist_id_by_user(
+    conn: &mut PgConnection,
+    user_id: i32,
+) -> Result<Option<i32>, AppError> {
+    psychologists::table
+        .filter(psychologists::user_id.eq(user_id))
+        .select(psychologists::id)
+        .first(conn)
+        .optional()
+        .map_err(AppError::from)
+}
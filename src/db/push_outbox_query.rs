@@ -0,0 +1,72 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use diesel::pg::PgConnection;
+use crate::errors::app_error::AppError;
+use crate::models::device::{Device, NewPushOutboxEntry, PushOutboxEntry};
+use crate::schema::{devices, push_outbox};
+
+pub fn insert_entries(
+    conn: &mut PgConnection,
+    entries: &[NewPushOutboxEntry],
+) -> Result<usize, AppError> {
+    diesel::insert_into(push_outbox::table)
+        .values(entries)
+        .execute(conn)
+        .map_err(AppError::from)
+}
+
+/// Entri yang sudah waktunya dikirim beserta perangkat tujuannya, yang terlama dulu
+pub fn find_due_entries(
+    conn: &mut PgConnection,
+    now: NaiveDateTime,
+    limit: i64,
+) -> Result<Vec<(PushOutboxEntry, Device)>, AppError> {
+    push_outbox::table
+        .inner_join(devices::table)
+        .filter(push_outbox::next_attempt_at.le(now))
+        .order(push_outbox::next_attempt_at.asc())
+        .limit(limit)
+        .select((PushOutboxEntry::as_select(), Device::as_select()))
+        .load(conn)
+        .map_err(AppError::from)
+}
+
+/// Jumlah entri yang sudah waktunya dikirim
+pub fn count_due_entries(
+    conn: &mut PgConnection,
+    now: NaiveDateTime,
+) -> Result<i64, AppError> {
+    push_outbox::table
+        .inner_join(devices::table)
+        .filter(push_outbox::next_attempt_at.le(now))
+        .count()
+        .get_result(conn)
+        .map_err(AppError::from)
+}
+
+pub fn delete_entry(
+    conn: &mut PgConnection,
+    entry_id: i32,
+) -> Result<usize, AppError> {
+    diesel::delete(push_outbox::table.filter(push_outbox::id.eq(entry_id)))
+        .execute(conn)
+        .map_err(AppError::from)
+}
+
+/// Catat percobaan yang gagal dan jadwalkan percobaan berikutnya
+pub fn reschedule_entry(
+    conn: &mut PgConnection,
+    entry_id: i32,
+    attempts: i32,
+    next_attempt_at: NaiveDateTime,
+    last_error: &str,
+) -> Result<usize, AppError> {
+    diesel::update(push_outbox::table.filter(push_outbox::id.eq(entry_id)))
+        .set((
+            push_outbox::attempts.eq(attempts),
+            push_outbox::next_attempt_at.eq(next_attempt_at),
+            push_outbox::last_error.eq(last_error),
+        ))
+        .execute(conn)
+        .map_err(AppError::from)
+}
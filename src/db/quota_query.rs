@@ -0,0 +1,62 @@
+use chrono::{NaiveDate, Utc};
+use diesel::prelude::*;
+use diesel::pg::PgConnection;
+use crate::errors::app_error::AppError;
+use crate::models::quota::NewUsageQuota;
+use crate::schema::usage_quotas;
+
+/// Tambah pemakaian satu kali jika masih di bawah `limit`, dalam satu statement agar aman
+/// dari request bersamaan. Mengembalikan pemakaian baru, atau `None` jika kuota sudah habis.
+pub fn try_consume(
+    conn: &mut PgConnection,
+    user_id: i32,
+    operation: &str,
+    period_start: NaiveDate,
+    limit: i32,
+) -> Result<Option<i32>, AppError> {
+    let new_quota = NewUsageQuota {
+        user_id,
+        operation,
+        period_start,
+        used: 1,
+    };
+    let upsert = diesel::insert_into(usage_quotas::table)
+        .values(&new_quota)
+        .on_conflict((usage_quotas::user_id, usage_quotas::operation, usage_quotas::period_start))
+        .do_update()
+        .set((
+            usage_quotas::used.eq(usage_quotas::used + 1),
+            usage_quotas::updated_at.eq(Utc::now().naive_utc()),
+        ));
+
+    // `ON CONFLICT ... DO UPDATE ... WHERE`; filter untuk upsert tidak tersedia lewat QueryDsl
+    diesel::query_dsl::methods::FilterDsl::filter(upsert, usage_quotas::used.lt(limit))
+        .returning(usage_quotas::used)
+        .get_result(conn)
+        .optional()
+        .map_err(AppError::from)
+}
+
+/// Hapus catatan pemakaian periode sebelum `before`
+pub fn delete_before(
+    conn: &mut PgConnection,
+    before: NaiveDate,
+) -> Result<usize, AppError> {
+    diesel::delete(usage_quotas::table.filter(usage_quotas::period_start.lt(before)))
+        .execute(conn)
+        .map_err(AppError::from)
+}
+
+/// Pemakaian setiap operasi milik `user_id` pada periode `period_start`
+pub fn find_user_period(
+    conn: &mut PgConnection,
+    user_id: i32,
+    period_start: NaiveDate,
+) -> Result<Vec<(String, i32)>, AppError> {
+    usage_quotas::table
+        .filter(usage_quotas::user_id.eq(user_id))
+        .filter(usage_quotas::period_start.eq(period_start))
+        .select((usage_quotas::operation, usage_quotas::used))
+        .load(conn)
+        .map_err(AppError::from)
+}
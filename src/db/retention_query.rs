@@ -0,0 +1,53 @@
+use diesel::prelude::*;
+use diesel::pg::PgConnection;
+use chrono::NaiveDateTime;
+use crate::errors::app_error::AppError;
+use crate::models::retention::{JournalRetentionState, NewJournalRetentionState};
+use crate::schema::journal_retention_states;
+
+pub fn find_state(
+    conn: &mut PgConnection,
+    user_id: i32,
+) -> Result<Option<JournalRetentionState>, AppError> {
+    journal_retention_states::table
+        .find(user_id)
+        .select(JournalRetentionState::as_select())
+        .first(conn)
+        .optional()
+        .map_err(AppError::from)
+}
+
+pub fn create_state(
+    conn: &mut PgConnection,
+    state: &NewJournalRetentionState,
+) -> Result<(), AppError> {
+    diesel::insert_into(journal_retention_states::table)
+        .values(state)
+        .on_conflict_do_nothing()
+        .execute(conn)
+        .map(|_| ())
+        .map_err(AppError::from)
+}
+
+pub fn mark_purged(
+    conn: &mut PgConnection,
+    user_id: i32,
+    purged_at: NaiveDateTime,
+) -> Result<(), AppError> {
+    diesel::update(journal_retention_states::table.find(user_id))
+        .set(journal_retention_states::last_purged_at.eq(Some(purged_at)))
+        .execute(conn)
+        .map(|_| ())
+        .map_err(AppError::from)
+}
+
+/// Hapus status retensi agar pemberitahuan dikirim ulang sebelum penghapusan berikutnya
+pub fn delete_state(
+    conn: &mut PgConnection,
+    user_id: i32,
+) -> Result<(), AppError> {
+    diesel::delete(journal_retention_states::table.find(user_id))
+        .execute(conn)
+        .map(|_| ())
+        .map_err(AppError::from)
+}
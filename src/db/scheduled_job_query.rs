@@ -0,0 +1,53 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use diesel::pg::PgConnection;
+use diesel::sql_types::{Bool, Text};
+use crate::errors::app_error::AppError;
+use crate::schema::scheduled_job_runs;
+
+#[derive(QueryableByName)]
+struct AdvisoryLock {
+    #[diesel(sql_type = Bool)]
+    locked: bool,
+}
+
+/// Klaim jadwal `scheduled_at` milik job `job_name`. Advisory lock per job membuat replika lain
+/// yang mengklaim bersamaan langsung mundur, dan jadwal yang sudah tercatat tidak bisa diklaim ulang.
+/// Lock dilepas otomatis saat transaksi selesai.
+pub fn claim_schedule(
+    conn: &mut PgConnection,
+    job_name: &str,
+    scheduled_at: NaiveDateTime,
+    now: NaiveDateTime,
+) -> Result<bool, AppError> {
+    conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        let lock = diesel::sql_query("SELECT pg_try_advisory_xact_lock(hashtext($1)) AS locked")
+            .bind::<Text, _>(format!("scheduled_job:{}", job_name))
+            .get_result::<AdvisoryLock>(conn)?;
+        if !lock.locked {
+            return Ok(false);
+        }
+
+        let upsert = diesel::insert_into(scheduled_job_runs::table)
+            .values((
+                scheduled_job_runs::job_name.eq(job_name),
+                scheduled_job_runs::last_scheduled_at.eq(scheduled_at),
+                scheduled_job_runs::claimed_at.eq(now),
+            ))
+            .on_conflict(scheduled_job_runs::job_name)
+            .do_update()
+            .set((
+                scheduled_job_runs::last_scheduled_at.eq(scheduled_at),
+                scheduled_job_runs::claimed_at.eq(now),
+            ));
+
+        // `ON CONFLICT ... DO UPDATE ... WHERE`: jadwal yang sama atau lebih lama tidak mengubah baris
+        let claimed = diesel::query_dsl::methods::FilterDsl::filter(
+            upsert,
+            scheduled_job_runs::last_scheduled_at.lt(scheduled_at),
+        )
+        .execute(conn)?;
+        Ok(claimed > 0)
+    })
+    .map_err(AppError::from)
+}
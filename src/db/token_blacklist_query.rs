@@ -23,7 +23,7 @@ pub fn insert_blacklisted_token(
     diesel::insert_into(token_blacklist::table)
         .values(&blacklisted_token)
         .execute(conn)
-        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        .map_err(AppError::from)?;
 
     Ok(())
 }
@@ -41,7 +41,7 @@ pub fn is_token_blacklisted(
             .filter(token_blacklist::token.eq(token_str))
     ))
     .get_result(conn)
-    .map_err(|e| AppError::DatabaseError(e.to_string()))
+    .map_err(AppError::from)
 }
 
 pub fn cleanup_expired_tokens(conn: &mut PgConnection, cutoff_date: NaiveDateTime) -> QueryResult<usize> {
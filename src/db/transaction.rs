@@ -0,0 +1,11 @@
+use diesel::pg::PgConnection;
+use crate::errors::app_error::AppError;
+
+/// Jalankan beberapa query sebagai satu transaksi SERIALIZABLE.
+/// Jika bentrok dengan transaksi lain, hasilnya `AppError::Conflict` dan request boleh diulang.
+pub fn run_in_transaction<T, F>(conn: &mut PgConnection, f: F) -> Result<T, AppError>
+where
+    F: FnOnce(&mut PgConnection) -> Result<T, AppError>,
+{
+    conn.build_transaction().serializable().run(f)
+}
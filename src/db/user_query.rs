@@ -1,10 +1,21 @@
 use diesel::prelude::*;
 use diesel::pg::PgConnection;
-use crate::models::user::{User, NewUser};
+use crate::models::user::{User, NewGuestUser, NewUser, UserProfileChanges};
 use crate::errors::app_error::AppError;
 use crate::schema::users;
+use crate::utils::email::normalize_email;
 use chrono::Utc;
 
+/// Simpan pengguna apa adanya, termasuk `created_at` dan avatar; dipakai untuk data seed
+/// dan akun dari login Google
+pub fn insert_user(conn: &mut PgConnection, new_user: &NewUser) -> Result<User, AppError> {
+    diesel::insert_into(users::table)
+        .values(new_user)
+        .returning(User::as_returning())
+        .get_result(conn)
+        .map_err(AppError::from)
+}
+
 // Function utama yang support semua parameter
 pub fn create_user(
     conn: &mut PgConnection,
@@ -22,21 +33,63 @@ pub fn create_user(
         age,
         gender,
         settings,
+        avatar: None,
         created_at: Utc::now().naive_utc(),
         updated_at: Utc::now().naive_utc(),
     };
 
     diesel::insert_into(users::table)
         .values(&new_user)
-        .execute(conn)
-        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        .returning(User::as_returning())
+        .get_result(conn)
+        .map_err(AppError::from)
+}
+
+pub fn create_guest_user(
+    conn: &mut PgConnection,
+    guest: &NewGuestUser,
+) -> Result<User, AppError> {
+    diesel::insert_into(users::table)
+        .values(guest)
+        .returning(User::as_returning())
+        .get_result(conn)
+        .map_err(AppError::from)
+}
 
-    // Get the created user
+/// Akun tamu yang terikat ke perangkat ini, jika ada
+pub fn find_guest_by_device(
+    conn: &mut PgConnection,
+    device_id: &str,
+) -> Result<Option<User>, AppError> {
     users::table
-        .filter(users::email.eq(email))
+        .filter(users::guest_device_id.eq(device_id))
+        .filter(users::is_guest.eq(true))
         .select(User::as_select())
         .first(conn)
-        .map_err(|e| AppError::DatabaseError(e.to_string()))
+        .optional()
+        .map_err(AppError::from)
+}
+
+/// Jadikan akun tamu akun penuh; ikatan ke perangkat dilepas
+pub fn upgrade_guest_user(
+    conn: &mut PgConnection,
+    user_id: i32,
+    username: &str,
+    email: &str,
+    password: &str,
+) -> Result<User, AppError> {
+    diesel::update(users::table.filter(users::id.eq(user_id)))
+        .set((
+            users::username.eq(username),
+            users::email.eq(email),
+            users::password.eq(password),
+            users::is_guest.eq(false),
+            users::guest_device_id.eq(None::<String>),
+            users::updated_at.eq(Utc::now().naive_utc()),
+        ))
+        .returning(User::as_returning())
+        .get_result(conn)
+        .map_err(AppError::from)
 }
 
 pub fn find_user_by_id(
@@ -49,21 +102,40 @@ pub fn find_user_by_id(
         .first(conn)
         .map_err(|e| match e {
             diesel::result::Error::NotFound => AppError::NotFound("User not found".to_string()),
-            _ => AppError::DatabaseError(e.to_string()),
+            _ => AppError::from(e),
+        })
+}
+
+/// Kunci baris pengguna sampai transaksi selesai, untuk membatasi aksi per pengguna
+/// yang dihitung dari database
+pub fn lock_user(
+    conn: &mut PgConnection,
+    user_id: i32,
+) -> Result<(), AppError> {
+    users::table
+        .filter(users::id.eq(user_id))
+        .select(users::id)
+        .for_update()
+        .first::<i32>(conn)
+        .map(|_| ())
+        .map_err(|e| match e {
+            diesel::result::Error::NotFound => AppError::NotFound("User not found".to_string()),
+            _ => AppError::from(e),
         })
 }
 
+/// Cari pengguna berdasarkan email tanpa membedakan huruf besar/kecil
 pub fn find_user_by_email(
     conn: &mut PgConnection,
     email: &str,
 ) -> Result<User, AppError> {
     users::table
-        .filter(users::email.eq(email))
+        .filter(lower(users::email).eq(normalize_email(email)))
         .select(User::as_select())
         .first(conn)
         .map_err(|e| match e {
             diesel::result::Error::NotFound => AppError::NotFound("User not found".to_string()),
-            _ => AppError::DatabaseError(e.to_string()),
+            _ => AppError::from(e),
         })
 }
 
@@ -77,55 +149,149 @@ pub fn find_user_by_username(
         .first(conn)
         .map_err(|e| match e {
             diesel::result::Error::NotFound => AppError::NotFound("User not found".to_string()),
-            _ => AppError::DatabaseError(e.to_string()),
+            _ => AppError::from(e),
         })
 }
 
-// Modifikasi function untuk include avatar parameter
+diesel::define_sql_function!(fn lower(x: diesel::sql_types::Text) -> diesel::sql_types::Text);
+
+/// Cek username tanpa membedakan huruf besar/kecil, termasuk username lama yang belum dinormalisasi
+/// Pengguna untuk login: `identifier` berisi "@" dicocokkan ke email, selain itu ke
+/// username (username tidak boleh mengandung "@"); keduanya tanpa membedakan huruf besar/kecil
+pub fn find_user_by_login_identifier(
+    conn: &mut PgConnection,
+    identifier: &str,
+) -> Result<User, AppError> {
+    let identifier = identifier.trim().to_lowercase();
+    let query = users::table.select(User::as_select()).into_boxed();
+    let query = if identifier.contains('@') {
+        query.filter(lower(users::email).eq(normalize_email(&identifier)))
+    } else {
+        query.filter(lower(users::username).eq(identifier))
+    };
+
+    query.first(conn).map_err(|e| match e {
+        diesel::result::Error::NotFound => AppError::NotFound("User not found".to_string()),
+        _ => AppError::from(e),
+    })
+}
+
+pub fn username_exists(
+    conn: &mut PgConnection,
+    username: &str,
+) -> Result<bool, AppError> {
+    diesel::select(diesel::dsl::exists(
+        users::table.filter(lower(users::username).eq(username.to_lowercase())),
+    ))
+    .get_result(conn)
+    .map_err(AppError::from)
+}
+
+/// Ubah hanya kolom profil yang ada di `changes`
 pub fn update_user_profile(
     conn: &mut PgConnection,
     user_id: i32,
-    new_username: &str,
-    new_email: &str,
-    new_age: Option<i32>,
-    new_gender: Option<String>,
-    new_avatar: Option<String>, // Tambahan parameter avatar
+    changes: &UserProfileChanges,
 ) -> Result<User, AppError> {
+    diesel::update(users::table.filter(users::id.eq(user_id)))
+        .set((changes, users::updated_at.eq(Utc::now().naive_utc())))
+        .returning(User::as_returning())
+        .get_result(conn)
+        .map_err(|e| match e {
+            diesel::result::Error::NotFound => AppError::NotFound("User not found".to_string()),
+            _ => AppError::from(e),
+        })
+}
+
+pub fn update_user_password(
+    conn: &mut PgConnection,
+    user_id: i32,
+    new_password: &str,
+) -> Result<(), AppError> {
+    diesel::update(users::table.filter(users::id.eq(user_id)))
+        .set((
+            users::password.eq(new_password),
+            users::updated_at.eq(Utc::now().naive_utc()),
+        ))
+        .execute(conn)
+        .map_err(AppError::from)?;
+
+    Ok(())
+}
+
+pub fn update_user_email(
+    conn: &mut PgConnection,
+    user_id: i32,
+    new_email: &str,
+) -> Result<(), AppError> {
     diesel::update(users::table.filter(users::id.eq(user_id)))
         .set((
-            users::username.eq(new_username),
             users::email.eq(new_email),
-            users::age.eq(new_age),
-            users::gender.eq(new_gender),
-            users::avatar.eq(new_avatar), // Update avatar field
             users::updated_at.eq(Utc::now().naive_utc()),
         ))
         .execute(conn)
-        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        .map_err(AppError::from)?;
 
-    find_user_by_id(conn, user_id)
+    Ok(())
 }
 
-pub fn update_user_password(
+pub fn update_user_avatar(
     conn: &mut PgConnection,
     user_id: i32,
-    new_password: &str,
+    new_avatar: Option<&str>,
 ) -> Result<(), AppError> {
     diesel::update(users::table.filter(users::id.eq(user_id)))
         .set((
-            users::password.eq(new_password),
+            users::avatar.eq(new_avatar),
             users::updated_at.eq(Utc::now().naive_utc()),
         ))
         .execute(conn)
-        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        .map_err(AppError::from)?;
 
     Ok(())
 }
 
+pub fn update_user_settings(
+    conn: &mut PgConnection,
+    user_id: i32,
+    new_settings: &str,
+) -> Result<User, AppError> {
+    diesel::update(users::table.filter(users::id.eq(user_id)))
+        .set((
+            users::settings.eq(Some(new_settings)),
+            users::updated_at.eq(Utc::now().naive_utc()),
+        ))
+        .returning(User::as_returning())
+        .get_result(conn)
+        .map_err(|e| match e {
+            diesel::result::Error::NotFound => AppError::NotFound("User not found".to_string()),
+            _ => AppError::from(e),
+        })
+}
+
+/// Hapus pengguna; data miliknya ikut terhapus lewat foreign key `ON DELETE CASCADE`
+pub fn delete_user(
+    conn: &mut PgConnection,
+    user_id: i32,
+) -> Result<usize, AppError> {
+    diesel::delete(users::table.filter(users::id.eq(user_id)))
+        .execute(conn)
+        .map_err(AppError::from)
+}
+
 // New function to get all users
 pub fn get_all_users(conn: &mut PgConnection) -> Result<Vec<User>, AppError> {
     users::table
         .select(User::as_select())
         .load::<User>(conn)
-        .map_err(|e| AppError::DatabaseError(e.to_string()))
+        .map_err(AppError::from)
+}
+
+/// Pengguna yang settings-nya memuat `key`; nilai settings tetap perlu di-parse
+pub fn find_users_with_setting(conn: &mut PgConnection, key: &str) -> Result<Vec<User>, AppError> {
+    users::table
+        .filter(users::settings.like(format!("%\"{}\"%", key)))
+        .select(User::as_select())
+        .load::<User>(conn)
+        .map_err(AppError::from)
 }
\ No newline at end of file
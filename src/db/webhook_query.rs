@@ -0,0 +1,146 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use diesel::pg::PgConnection;
+use crate::errors::app_error::AppError;
+use crate::models::webhook::{DeliveryStatus, NewUserWebhook, NewWebhookDelivery, UserWebhook, WebhookDelivery};
+use crate::schema::{user_webhooks, webhook_deliveries};
+
+pub fn insert_webhook(
+    conn: &mut PgConnection,
+    webhook: &NewUserWebhook,
+) -> Result<UserWebhook, AppError> {
+    diesel::insert_into(user_webhooks::table)
+        .values(webhook)
+        .returning(UserWebhook::as_returning())
+        .get_result(conn)
+        .map_err(AppError::from)
+}
+
+pub fn find_webhooks_by_user(
+    conn: &mut PgConnection,
+    user_id: i32,
+) -> Result<Vec<UserWebhook>, AppError> {
+    user_webhooks::table
+        .filter(user_webhooks::user_id.eq(user_id))
+        .order(user_webhooks::created_at.asc())
+        .select(UserWebhook::as_select())
+        .load(conn)
+        .map_err(AppError::from)
+}
+
+pub fn count_webhooks_by_user(
+    conn: &mut PgConnection,
+    user_id: i32,
+) -> Result<i64, AppError> {
+    user_webhooks::table
+        .filter(user_webhooks::user_id.eq(user_id))
+        .count()
+        .get_result(conn)
+        .map_err(AppError::from)
+}
+
+/// Semua webhook, dikelompokkan per pengguna, untuk job ringkasan mingguan
+pub fn find_all_webhooks(conn: &mut PgConnection) -> Result<Vec<UserWebhook>, AppError> {
+    user_webhooks::table
+        .order((user_webhooks::user_id.asc(), user_webhooks::id.asc()))
+        .select(UserWebhook::as_select())
+        .load(conn)
+        .map_err(AppError::from)
+}
+
+pub fn delete_webhook(
+    conn: &mut PgConnection,
+    webhook_id: i32,
+    user_id: i32,
+) -> Result<usize, AppError> {
+    diesel::delete(
+        user_webhooks::table
+            .filter(user_webhooks::id.eq(webhook_id))
+            .filter(user_webhooks::user_id.eq(user_id)),
+    )
+    .execute(conn)
+    .map_err(AppError::from)
+}
+
+/// Masukkan ke antrian; ringkasan yang sama tidak diantrikan dua kali
+pub fn insert_delivery(
+    conn: &mut PgConnection,
+    delivery: &NewWebhookDelivery,
+) -> Result<usize, AppError> {
+    diesel::insert_into(webhook_deliveries::table)
+        .values(delivery)
+        .on_conflict_do_nothing()
+        .execute(conn)
+        .map_err(AppError::from)
+}
+
+/// Pengiriman terakhir untuk setiap webhook
+pub fn find_last_deliveries(
+    conn: &mut PgConnection,
+    webhook_ids: &[i32],
+) -> Result<Vec<WebhookDelivery>, AppError> {
+    webhook_deliveries::table
+        .filter(webhook_deliveries::webhook_id.eq_any(webhook_ids))
+        .distinct_on(webhook_deliveries::webhook_id)
+        .order((webhook_deliveries::webhook_id, webhook_deliveries::period_start.desc()))
+        .select(WebhookDelivery::as_select())
+        .load(conn)
+        .map_err(AppError::from)
+}
+
+/// Entri yang sudah waktunya dikirim beserta webhook tujuannya, yang terlama dulu
+pub fn find_due_deliveries(
+    conn: &mut PgConnection,
+    now: NaiveDateTime,
+    limit: i64,
+) -> Result<Vec<(WebhookDelivery, UserWebhook)>, AppError> {
+    webhook_deliveries::table
+        .inner_join(user_webhooks::table)
+        .filter(webhook_deliveries::status.eq(DeliveryStatus::Pending.as_str()))
+        .filter(webhook_deliveries::next_attempt_at.le(now))
+        .order(webhook_deliveries::next_attempt_at.asc())
+        .limit(limit)
+        .select((WebhookDelivery::as_select(), UserWebhook::as_select()))
+        .load(conn)
+        .map_err(AppError::from)
+}
+
+pub fn mark_delivered(
+    conn: &mut PgConnection,
+    delivery_id: i32,
+    attempts: i32,
+    now: NaiveDateTime,
+) -> Result<usize, AppError> {
+    diesel::update(webhook_deliveries::table.filter(webhook_deliveries::id.eq(delivery_id)))
+        .set((
+            webhook_deliveries::status.eq(DeliveryStatus::Delivered.as_str()),
+            webhook_deliveries::attempts.eq(attempts),
+            webhook_deliveries::last_error.eq(None::<String>),
+            webhook_deliveries::delivered_at.eq(now),
+        ))
+        .execute(conn)
+        .map_err(AppError::from)
+}
+
+/// Catat percobaan yang gagal; tanpa `next_attempt_at` entri ditandai gagal permanen
+pub fn record_failure(
+    conn: &mut PgConnection,
+    delivery_id: i32,
+    attempts: i32,
+    next_attempt_at: Option<NaiveDateTime>,
+    last_error: &str,
+) -> Result<usize, AppError> {
+    let status = match next_attempt_at {
+        Some(_) => DeliveryStatus::Pending,
+        None => DeliveryStatus::Failed,
+    };
+    diesel::update(webhook_deliveries::table.filter(webhook_deliveries::id.eq(delivery_id)))
+        .set((
+            webhook_deliveries::status.eq(status.as_str()),
+            webhook_deliveries::attempts.eq(attempts),
+            webhook_deliveries::last_error.eq(last_error),
+            next_attempt_at.map(|next| webhook_deliveries::next_attempt_at.eq(next)),
+        ))
+        .execute(conn)
+        .map_err(AppError::from)
+}
@@ -1,47 +1,211 @@
 use axum::{
-    http::StatusCode,
+    http::{header, StatusCode},
     response::{IntoResponse, Response},
     Json,
 };
+use chrono::{NaiveDateTime, Utc};
+use diesel::result::{DatabaseErrorKind, Error as DieselError};
+use serde::Serialize;
 use serde_json::json;
+use utoipa::ToSchema;
+use crate::db::constraints::unique_violation_error;
+use crate::i18n::localize_message;
+use crate::middleware::request_id;
 
-#[derive(Debug)]
+/// Lokasi kesalahan pada body request
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FieldError {
+    /// Path field, misalnya `details.sleep_hours`; kosong jika JSON-nya sendiri rusak
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub field: Option<String>,
+    pub message: String,
+}
+
+/// Pengganti pesan error 5xx di respons; detail aslinya hanya ditulis ke log server
+const INTERNAL_ERROR_MESSAGE: &str = "Internal server error";
+
+#[derive(Debug, thiserror::Error)]
 pub enum AppError {
+    #[error("Bad Request: {0}")]
     BadRequest(String),
+    #[error("Unauthorized: {0}")]
     Unauthorized(String),
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+    #[error("Not Found: {0}")]
     NotFound(String),
+    /// Konflik dengan request lain yang berjalan bersamaan; aman untuk diulang
+    #[error("Conflict: {0}")]
+    Conflict(String),
+    #[error("Too Many Requests: {0}")]
+    TooManyRequests(String),
+    /// Kuota harian pengguna habis; `reset_at` (UTC) adalah saat kuota terisi kembali
+    #[error("Too Many Requests: {message}")]
+    QuotaExceeded {
+        message: String,
+        limit: i32,
+        reset_at: NaiveDateTime,
+    },
+    /// Body JSON rusak atau tidak cocok dengan bentuk yang diharapkan; `fields` menunjukkan lokasinya
+    #[error("Validation Error: {message}")]
+    ValidationError {
+        message: String,
+        fields: Vec<FieldError>,
+    },
+    /// Body request atau isi field melebihi batas ukuran
+    #[error("Payload Too Large: {0}")]
+    PayloadTooLarge(String),
+    /// Sementara tidak bisa dilayani (misalnya mode maintenance); coba lagi setelah `retry_after_secs`
+    #[error("Service Unavailable: {message}")]
+    ServiceUnavailable {
+        message: String,
+        retry_after_secs: u64,
+    },
+    /// Handler tidak selesai dalam batas waktu request
+    #[error("Request Timeout: {0}")]
+    RequestTimeout(String),
+    #[error("Internal Server Error: {0}")]
     InternalServerError(String),
-    DatabaseError(String),
+    /// Seperti `InternalServerError`, dengan error asal yang tetap bisa dibaca lewat `source()`
+    #[error("Internal Server Error: {message}: {source}")]
+    Internal {
+        message: String,
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    #[error("Database Error: {0}")]
+    DatabaseError(#[source] DieselError),
 }
 
-impl IntoResponse for AppError {
-    fn into_response(self) -> Response {
-        let (status, error_message) = match self {
+impl AppError {
+    pub fn internal(message: &str, source: impl std::error::Error + Send + Sync + 'static) -> Self {
+        AppError::Internal {
+            message: message.to_string(),
+            source: Box::new(source),
+        }
+    }
+
+    /// Kode stabil untuk error 5xx, agar klien bisa membedakan jenisnya tanpa melihat detail
+    fn internal_code(&self) -> Option<&'static str> {
+        match self {
+            AppError::InternalServerError(_) | AppError::Internal { .. } => Some("internal_error"),
+            AppError::DatabaseError(_) => Some("database_error"),
+            _ => None,
+        }
+    }
+
+    /// Tulis detail error internal ke log beserta request id; error lain tidak dicatat
+    fn log_internal(&self) {
+        if self.internal_code().is_some() {
+            log::error!("[{}] {}", request_id::current().as_deref().unwrap_or("-"), self);
+        }
+    }
+
+    /// Status HTTP dan pesan (belum diterjemahkan), untuk respons selain JSON.
+    /// Detail error 5xx dicatat di log dan diganti pesan generik.
+    pub fn into_parts(self) -> (StatusCode, String) {
+        self.log_internal();
+        match self {
             AppError::BadRequest(message) => (StatusCode::BAD_REQUEST, message),
             AppError::Unauthorized(message) => (StatusCode::UNAUTHORIZED, message),
+            AppError::Forbidden(message) => (StatusCode::FORBIDDEN, message),
             AppError::NotFound(message) => (StatusCode::NOT_FOUND, message),
-            AppError::InternalServerError(message) => (StatusCode::INTERNAL_SERVER_ERROR, message),
-            AppError::DatabaseError(message) => (StatusCode::INTERNAL_SERVER_ERROR, format!("Database error: {}", message)),
-        };
+            AppError::Conflict(message) => (StatusCode::CONFLICT, message),
+            AppError::TooManyRequests(message) => (StatusCode::TOO_MANY_REQUESTS, message),
+            AppError::ValidationError { message, .. } => (StatusCode::UNPROCESSABLE_ENTITY, message),
+            AppError::QuotaExceeded { message, .. } => (StatusCode::TOO_MANY_REQUESTS, message),
+            AppError::PayloadTooLarge(message) => (StatusCode::PAYLOAD_TOO_LARGE, message),
+            AppError::ServiceUnavailable { message, .. } => (StatusCode::SERVICE_UNAVAILABLE, message),
+            AppError::RequestTimeout(message) => (StatusCode::REQUEST_TIMEOUT, message),
+            AppError::InternalServerError(_) | AppError::Internal { .. } | AppError::DatabaseError(_) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, INTERNAL_ERROR_MESSAGE.to_string())
+            }
+        }
+    }
+}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        if let AppError::QuotaExceeded { message, limit, reset_at } = self {
+            let retry_after = (reset_at - Utc::now().naive_utc()).num_seconds().max(1);
+            let body = Json(json!({
+                "error": localize_message(&message),
+                "limit": limit,
+                "reset_at": reset_at,
+            }));
+            return (
+                StatusCode::TOO_MANY_REQUESTS,
+                [(header::RETRY_AFTER, retry_after.to_string())],
+                body,
+            )
+                .into_response();
+        }
+        if let AppError::ValidationError { message, fields } = self {
+            let body = Json(json!({
+                "error": localize_message(&message),
+                "fields": fields,
+            }));
+            return (StatusCode::UNPROCESSABLE_ENTITY, body).into_response();
+        }
+        if let AppError::ServiceUnavailable { message, retry_after_secs } = self {
+            return (
+                StatusCode::SERVICE_UNAVAILABLE,
+                [(header::RETRY_AFTER, retry_after_secs.to_string())],
+                Json(json!({ "error": localize_message(&message) })),
+            )
+                .into_response();
+        }
+
+        if let Some(code) = self.internal_code() {
+            let (status, error_message) = self.into_parts();
+            let body = Json(json!({
+                "error": localize_message(&error_message),
+                "code": code,
+                "request_id": request_id::current(),
+            }));
+            return (status, body).into_response();
+        }
+
+        let (status, error_message) = self.into_parts();
 
         let body = Json(json!({
-            "error": error_message,
+            "error": localize_message(&error_message),
         }));
 
         (status, body).into_response()
     }
 }
 
-impl std::fmt::Display for AppError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            AppError::BadRequest(msg) => write!(f, "Bad Request: {}", msg),
-            AppError::Unauthorized(msg) => write!(f, "Unauthorized: {}", msg),
-            AppError::NotFound(msg) => write!(f, "Not Found: {}", msg),
-            AppError::InternalServerError(msg) => write!(f, "Internal Server Error: {}", msg),
-            AppError::DatabaseError(msg) => write!(f, "Database Error: {}", msg),
+impl From<DieselError> for AppError {
+    fn from(e: DieselError) -> Self {
+        match e {
+            DieselError::DatabaseError(DatabaseErrorKind::SerializationFailure, _) => {
+                AppError::Conflict("Request conflicted with a concurrent update, please retry".to_string())
+            }
+            DieselError::DatabaseError(DatabaseErrorKind::UniqueViolation, info) => {
+                unique_violation_error(info.constraint_name())
+            }
+            _ => AppError::DatabaseError(e),
         }
     }
 }
 
-impl std::error::Error for AppError {}
\ No newline at end of file
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error as _;
+
+    #[test]
+    fn internal_errors_keep_source_but_hide_details() {
+        let error = AppError::from(DieselError::QueryBuilderError("relation \"users\" does not exist".into()));
+        assert!(error.source().is_some());
+        assert!(error.to_string().contains("relation \"users\" does not exist"));
+
+        let (status, message) = error.into_parts();
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(message, INTERNAL_ERROR_MESSAGE);
+
+        let (status, message) = AppError::NotFound("Mood not found".to_string()).into_parts();
+        assert_eq!((status, message.as_str()), (StatusCode::NOT_FOUND, "Mood not found"));
+    }
+}
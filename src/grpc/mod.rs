@@ -0,0 +1,96 @@
+use std::net::SocketAddr;
+use axum::http::StatusCode;
+use tokio_util::sync::CancellationToken;
+use tonic::service::Interceptor;
+use tonic::{Request, Status};
+use crate::errors::app_error::AppError;
+use crate::state::AppState;
+
+pub mod mood_service;
+
+/// Kode hasil generate dari `proto/mindmate/v1` (lihat build.rs)
+pub mod proto {
+    tonic::include_proto!("mindmate.v1");
+}
+
+/// Jalankan server gRPC untuk konsumen internal di `addr` sampai `shutdown` dibatalkan.
+/// Setiap panggilan wajib membawa metadata `authorization: Bearer <GRPC_AUTH_TOKEN>`.
+pub async fn serve(
+    state: AppState,
+    addr: SocketAddr,
+    auth_token: String,
+    shutdown: CancellationToken,
+) -> Result<(), tonic::transport::Error> {
+    let moods = proto::mood_service_server::MoodServiceServer::with_interceptor(
+        mood_service::MoodGrpcService::new(state),
+        BearerAuth { token: auth_token },
+    );
+
+    tonic::transport::Server::builder()
+        .add_service(moods)
+        .serve_with_shutdown(addr, shutdown.cancelled_owned())
+        .await
+}
+
+/// Tolak panggilan tanpa `authorization: Bearer <token>` yang cocok
+#[derive(Clone)]
+struct BearerAuth {
+    token: String,
+}
+
+impl Interceptor for BearerAuth {
+    fn call(&mut self, request: Request<()>) -> Result<Request<()>, Status> {
+        let provided = request
+            .metadata()
+            .get("authorization")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .unwrap_or_default();
+
+        if constant_time_eq(provided.as_bytes(), self.token.as_bytes()) {
+            Ok(request)
+        } else {
+            Err(Status::unauthenticated("Missing or invalid token"))
+        }
+    }
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+/// Ubah `AppError` menjadi status gRPC; detail error internal hanya dicatat di log
+pub fn to_status(error: AppError) -> Status {
+    let (status, message) = error.into_parts();
+    match status {
+        StatusCode::BAD_REQUEST | StatusCode::UNPROCESSABLE_ENTITY => Status::invalid_argument(message),
+        StatusCode::UNAUTHORIZED => Status::unauthenticated(message),
+        StatusCode::FORBIDDEN => Status::permission_denied(message),
+        StatusCode::NOT_FOUND => Status::not_found(message),
+        StatusCode::CONFLICT => Status::already_exists(message),
+        StatusCode::TOO_MANY_REQUESTS => Status::resource_exhausted(message),
+        StatusCode::SERVICE_UNAVAILABLE => Status::unavailable(message),
+        StatusCode::REQUEST_TIMEOUT => Status::deadline_exceeded(message),
+        _ => Status::internal(message),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn requires_the_configured_bearer_token() {
+        let mut auth = BearerAuth { token: "secret".to_string() };
+        let mut request = Request::new(());
+        request.metadata_mut().insert("authorization", "Bearer secret".parse().unwrap());
+        assert!(auth.call(request).is_ok());
+
+        let mut request = Request::new(());
+        request.metadata_mut().insert("authorization", "Bearer other".parse().unwrap());
+        assert_eq!(auth.call(request).unwrap_err().code(), tonic::Code::Unauthenticated);
+        assert!(auth.call(Request::new(())).is_err());
+
+        assert_eq!(to_status(AppError::Conflict("Mood already exists".to_string())).code(), tonic::Code::AlreadyExists);
+    }
+}
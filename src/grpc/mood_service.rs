@@ -0,0 +1,149 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use chrono_tz::Tz;
+use tonic::{Request, Response, Status};
+use crate::errors::app_error::AppError;
+use crate::grpc::proto::{self, mood_service_server::MoodService};
+use crate::grpc::to_status;
+use crate::models::list_sort::ListSort;
+use crate::models::mood::{CreateMoodRequest, MoodDetails, MoodResponse};
+use crate::service::{mood_service, user_service};
+use crate::state::AppState;
+use crate::utils::event_bus::AppEvent;
+use crate::utils::timezone::parse_timezone;
+
+/// Implementasi `mindmate.v1.MoodService`, memakai service yang sama dengan endpoint REST /moods
+pub struct MoodGrpcService {
+    state: AppState,
+}
+
+impl MoodGrpcService {
+    pub fn new(state: AppState) -> Self {
+        MoodGrpcService { state }
+    }
+
+    /// Zona waktu dari settings pengguna, sekaligus memastikan pengguna ada
+    fn user_timezone(&self, user_id: i32) -> Result<Tz, AppError> {
+        let settings = user_service::get_user_settings(self.state.users.as_ref(), user_id)?;
+        Ok(settings.timezone.as_deref().and_then(parse_timezone).unwrap_or(Tz::UTC))
+    }
+}
+
+#[tonic::async_trait]
+impl MoodService for MoodGrpcService {
+    async fn create_mood(&self, request: Request<proto::CreateMoodRequest>) -> Result<Response<proto::Mood>, Status> {
+        let request = request.into_inner();
+        if self.state.maintenance.is_enabled() {
+            return Err(Status::unavailable("The service is in read-only maintenance mode, please try again later"));
+        }
+
+        let date = parse_date(request.date).map_err(to_status)?;
+        let data = CreateMoodRequest {
+            mood: request.mood,
+            emoji: Some(request.emoji).filter(|emoji| !emoji.is_empty()),
+            notes: request.notes,
+            details: request.details.map(MoodDetails::from),
+            location: None,
+            date,
+        };
+
+        let user_id = request.user_id;
+        let tz = self.user_timezone(user_id).map_err(to_status)?;
+        let mood = mood_service::create_mood(self.state.moods.as_ref(), &self.state.stats_cache, user_id, data, tz)
+            .map_err(to_status)?;
+        self.state.event_bus.publish(AppEvent::MoodCreated { user_id, mood_id: mood.id });
+
+        Ok(Response::new(mood.into()))
+    }
+
+    async fn list_moods(&self, request: Request<proto::ListMoodsRequest>) -> Result<Response<proto::ListMoodsResponse>, Status> {
+        let request = request.into_inner();
+        let moods = mood_service::get_user_moods(
+            self.state.moods.as_ref(),
+            request.user_id,
+            request.limit,
+            request.offset,
+            request.pinned,
+            ListSort::DateDesc,
+        )
+        .map_err(to_status)?;
+
+        Ok(Response::new(proto::ListMoodsResponse {
+            moods: moods.into_iter().map(proto::Mood::from).collect(),
+        }))
+    }
+
+    async fn get_mood_stats(&self, request: Request<proto::GetMoodStatsRequest>) -> Result<Response<proto::MoodStats>, Status> {
+        let summary = mood_service::get_mood_summary(self.state.moods.as_ref(), request.into_inner().user_id).map_err(to_status)?;
+
+        Ok(Response::new(proto::MoodStats {
+            total_entries: summary.total_entries,
+            mood_distribution: summary
+                .mood_distribution
+                .into_iter()
+                .map(|count| proto::MoodCount {
+                    mood: count.mood,
+                    count: count.count,
+                    percentage: count.percentage,
+                })
+                .collect(),
+            average_mood_score: summary.average_score,
+        }))
+    }
+}
+
+/// Tanggal `YYYY-MM-DD`; kosong berarti hari ini menurut zona waktu pengguna
+fn parse_date(date: Option<String>) -> Result<Option<NaiveDate>, AppError> {
+    date.filter(|date| !date.is_empty())
+        .map(|date| {
+            NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+                .map_err(|_| AppError::BadRequest(format!("Invalid date: {}. Use YYYY-MM-DD", date)))
+        })
+        .transpose()
+}
+
+fn timestamp(value: NaiveDateTime) -> prost_types::Timestamp {
+    let value = value.and_utc();
+    prost_types::Timestamp {
+        seconds: value.timestamp(),
+        nanos: value.timestamp_subsec_nanos() as i32,
+    }
+}
+
+impl From<MoodResponse> for proto::Mood {
+    fn from(mood: MoodResponse) -> Self {
+        proto::Mood {
+            id: mood.id,
+            user_id: mood.user_id,
+            date: mood.date.format("%Y-%m-%d").to_string(),
+            mood: mood.mood,
+            emoji: mood.emoji,
+            notes: mood.notes,
+            details: mood.details.map(proto::MoodDetails::from),
+            is_pinned: mood.is_pinned,
+            created_at: Some(timestamp(mood.created_at)),
+            updated_at: mood.updated_at.map(timestamp),
+        }
+    }
+}
+
+impl From<MoodDetails> for proto::MoodDetails {
+    fn from(details: MoodDetails) -> Self {
+        proto::MoodDetails {
+            energy: details.energy,
+            anxiety: details.anxiety,
+            sleep_hours: details.sleep_hours,
+            tags: details.tags.unwrap_or_default(),
+        }
+    }
+}
+
+impl From<proto::MoodDetails> for MoodDetails {
+    fn from(details: proto::MoodDetails) -> Self {
+        MoodDetails {
+            energy: details.energy,
+            anxiety: details.anxiety,
+            sleep_hours: details.sleep_hours,
+            tags: Some(details.tags).filter(|tags| !tags.is_empty()),
+        }
+    }
+}
@@ -0,0 +1,158 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::OnceLock;
+
+/// Bahasa yang didukung untuk pesan API
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Locale {
+    #[default]
+    En,
+    Id,
+}
+
+impl Locale {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::Id => "id",
+        }
+    }
+
+    /// Terima kode bahasa seperti `id`, `id-ID` atau `en_US`
+    pub fn parse(code: &str) -> Option<Locale> {
+        let primary = code.trim().split(['-', '_']).next()?.to_lowercase();
+        match primary.as_str() {
+            "en" => Some(Locale::En),
+            "id" | "in" => Some(Locale::Id),
+            _ => None,
+        }
+    }
+
+    /// Pilih bahasa dari header Accept-Language berdasarkan bobot `q`
+    pub fn from_accept_language(header: &str) -> Option<Locale> {
+        header
+            .split(',')
+            .filter_map(|item| {
+                let mut parts = item.split(';');
+                let locale = Locale::parse(parts.next()?)?;
+                let quality = parts
+                    .find_map(|param| param.trim().strip_prefix("q="))
+                    .and_then(|q| q.parse::<f32>().ok())
+                    .unwrap_or(1.0);
+                (quality > 0.0).then_some((locale, quality))
+            })
+            .fold(None, |best: Option<(Locale, f32)>, (locale, quality)| match best {
+                Some((_, best_quality)) if best_quality >= quality => best,
+                _ => Some((locale, quality)),
+            })
+            .map(|(locale, _)| locale)
+    }
+}
+
+tokio::task_local! {
+    static CURRENT_LOCALE: Locale;
+}
+
+/// Jalankan future dengan bahasa tertentu; dipakai oleh middleware locale
+pub async fn with_locale<F: Future>(locale: Locale, future: F) -> F::Output {
+    CURRENT_LOCALE.scope(locale, future).await
+}
+
+/// Bahasa untuk request yang sedang diproses, default `en` di luar request
+pub fn current_locale() -> Locale {
+    CURRENT_LOCALE.try_with(|locale| *locale).unwrap_or_default()
+}
+
+type Catalog = HashMap<String, String>;
+
+fn load_catalog(raw: &str) -> Catalog {
+    serde_json::from_str(raw).expect("i18n catalog must be valid JSON")
+}
+
+fn catalog(locale: Locale) -> &'static Catalog {
+    static EN: OnceLock<Catalog> = OnceLock::new();
+    static ID: OnceLock<Catalog> = OnceLock::new();
+    match locale {
+        Locale::En => EN.get_or_init(|| load_catalog(include_str!("en.json"))),
+        Locale::Id => ID.get_or_init(|| load_catalog(include_str!("id.json"))),
+    }
+}
+
+/// Pesan untuk `key` dalam bahasa request saat ini; jatuh ke `en` lalu ke key itu sendiri
+pub fn t(key: &str) -> String {
+    t_in(current_locale(), key)
+}
+
+pub fn t_in(locale: Locale, key: &str) -> String {
+    catalog(locale)
+        .get(key)
+        .or_else(|| catalog(Locale::En).get(key))
+        .cloned()
+        .unwrap_or_else(|| key.to_string())
+}
+
+/// Seperti `t_in`, lalu isi setiap `{}` pada pesan dengan `args` secara berurutan
+pub fn t_in_args(locale: Locale, key: &str, args: &[&str]) -> String {
+    fill_template(&t_in(locale, key), args)
+}
+
+/// Terjemahkan pesan bahasa Inggris yang sudah jadi (misalnya isi `AppError`) dengan
+/// mencocokkannya ke template di katalog `en`; `{}` pada template menangkap bagian dinamis.
+/// Pesan yang tidak dikenal dikembalikan apa adanya.
+pub fn localize_message(message: &str) -> String {
+    localize_message_in(current_locale(), message)
+}
+
+pub fn localize_message_in(locale: Locale, message: &str) -> String {
+    if locale == Locale::En {
+        return message.to_string();
+    }
+
+    catalog(Locale::En)
+        .iter()
+        .find_map(|(key, template)| {
+            let args = match_template(template, message)?;
+            let target = catalog(locale).get(key)?;
+            Some(fill_template(target, &args))
+        })
+        .unwrap_or_else(|| message.to_string())
+}
+
+fn match_template<'a>(template: &str, message: &'a str) -> Option<Vec<&'a str>> {
+    let mut parts = template.split("{}");
+    let first = parts.next().unwrap_or_default();
+    let mut rest = message.strip_prefix(first)?;
+    let literals: Vec<&str> = parts.collect();
+
+    if literals.is_empty() {
+        return rest.is_empty().then(Vec::new);
+    }
+
+    let mut args = Vec::with_capacity(literals.len());
+    for (index, literal) in literals.iter().enumerate() {
+        if index == literals.len() - 1 {
+            let arg = rest.strip_suffix(literal)?;
+            args.push(arg);
+            rest = "";
+        } else {
+            let end = rest.find(literal)?;
+            args.push(&rest[..end]);
+            rest = &rest[end + literal.len()..];
+        }
+    }
+
+    rest.is_empty().then_some(args)
+}
+
+fn fill_template(template: &str, args: &[&str]) -> String {
+    let mut output = String::with_capacity(template.len());
+    let mut args = args.iter();
+    let mut parts = template.split("{}").peekable();
+    while let Some(part) = parts.next() {
+        output.push_str(part);
+        if parts.peek().is_some() {
+            output.push_str(args.next().copied().unwrap_or_default());
+        }
+    }
+    output
+}
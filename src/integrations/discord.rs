@@ -0,0 +1,25 @@
+use serde_json::{json, Value};
+use super::SummaryMessage;
+
+/// Warna garis embed (ungu MindMate)
+const EMBED_COLOR: u32 = 0x7C5CFF;
+
+/// Satu embed dengan field sebaris; mention dimatikan agar isi ringkasan tidak bisa memanggil orang
+pub fn format(message: &SummaryMessage) -> Value {
+    let fields: Vec<Value> = message
+        .fields
+        .iter()
+        .map(|field| json!({ "name": field.name, "value": field.value, "inline": true }))
+        .collect();
+
+    json!({
+        "allowed_mentions": { "parse": [] },
+        "embeds": [{
+            "title": message.title,
+            "description": message.description,
+            "color": EMBED_COLOR,
+            "fields": fields,
+            "footer": { "text": message.footer },
+        }],
+    })
+}
@@ -0,0 +1,135 @@
+pub mod discord;
+pub mod slack;
+
+use serde_json::Value;
+use url::Url;
+use crate::errors::app_error::AppError;
+use crate::utils::http_client::HttpClient;
+
+/// Platform webhook keluar yang didukung
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WebhookPlatform {
+    Slack,
+    Discord,
+}
+
+impl WebhookPlatform {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WebhookPlatform::Slack => "slack",
+            WebhookPlatform::Discord => "discord",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "slack" => Some(WebhookPlatform::Slack),
+            "discord" => Some(WebhookPlatform::Discord),
+            _ => None,
+        }
+    }
+
+    /// Host dan awalan path resmi; URL lain ditolak agar server tidak bisa dipakai
+    /// untuk mengirim request ke alamat sembarang
+    fn allowed_targets(&self) -> (&'static [&'static str], &'static str) {
+        match self {
+            WebhookPlatform::Slack => (&["hooks.slack.com"], "/services/"),
+            WebhookPlatform::Discord => (
+                &["discord.com", "discordapp.com", "ptb.discord.com", "canary.discord.com"],
+                "/api/webhooks/",
+            ),
+        }
+    }
+
+    pub fn validate_url(&self, value: &str) -> Result<String, AppError> {
+        let invalid = || AppError::BadRequest(format!("Invalid {} webhook URL", self.as_str()));
+        let url = Url::parse(value.trim()).map_err(|_| invalid())?;
+        let (hosts, path_prefix) = self.allowed_targets();
+
+        let valid = url.scheme() == "https"
+            && url.port().is_none()
+            && url.username().is_empty()
+            && url.host_str().is_some_and(|host| hosts.contains(&host))
+            && url.path().starts_with(path_prefix)
+            && url.path().len() > path_prefix.len();
+        if !valid {
+            return Err(invalid());
+        }
+        Ok(url.to_string())
+    }
+
+    /// Body JSON yang dikirim ke webhook
+    pub fn format(&self, message: &SummaryMessage) -> Value {
+        match self {
+            WebhookPlatform::Slack => slack::format(message),
+            WebhookPlatform::Discord => discord::format(message),
+        }
+    }
+}
+
+/// Ringkasan yang sudah diterjemahkan, siap diformat sesuai platform
+#[derive(Debug, Clone)]
+pub struct SummaryMessage {
+    pub title: String,
+    pub description: String,
+    pub fields: Vec<SummaryField>,
+    pub footer: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct SummaryField {
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(Debug)]
+pub enum WebhookError {
+    /// Webhook menolak permanen (misalnya sudah dihapus); tidak perlu dicoba lagi
+    Rejected(String),
+    /// Gagal sementara (koneksi, 5xx, rate limit); boleh dicoba lagi
+    Failed(String),
+}
+
+/// Kirim body JSON yang sudah diformat ke URL webhook
+pub async fn send(http_client: &HttpClient, url: &str, payload: &str) -> Result<(), WebhookError> {
+    let request = http_client
+        .post(url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .body(payload.to_string());
+
+    let response = http_client
+        .send(request)
+        .await
+        .map_err(|e| WebhookError::Failed(format!("Request failed: {}", e)))?;
+    let status = response.status();
+    if status.is_success() {
+        return Ok(());
+    }
+
+    let reason = format!("Webhook returned {}", status);
+    if status.is_client_error() && status != reqwest::StatusCode::TOO_MANY_REQUESTS {
+        Err(WebhookError::Rejected(reason))
+    } else {
+        Err(WebhookError::Failed(reason))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_official_webhook_urls_are_accepted() {
+        let slack = WebhookPlatform::Slack;
+        assert!(slack.validate_url("https://hooks.slack.com/services/T000/B000/XXXX").is_ok());
+        assert!(slack.validate_url("http://hooks.slack.com/services/T000/B000/XXXX").is_err());
+        assert!(slack.validate_url("https://hooks.slack.com.evil.test/services/T000").is_err());
+        assert!(slack.validate_url("https://hooks.slack.com:8443/services/T000").is_err());
+        assert!(slack.validate_url("https://hooks.slack.com/services/").is_err());
+
+        let discord = WebhookPlatform::Discord;
+        assert!(discord.validate_url("https://discord.com/api/webhooks/123/abc").is_ok());
+        assert!(discord.validate_url("https://hooks.slack.com/services/T000/B000/XXXX").is_err());
+        assert!(discord.validate_url("https://user@discord.com/api/webhooks/123/abc").is_err());
+    }
+}
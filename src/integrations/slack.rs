@@ -0,0 +1,47 @@
+use serde_json::{json, Value};
+use super::SummaryMessage;
+
+/// Block Kit: header, deskripsi, field dua kolom dan footer kecil.
+/// `text` dipakai Slack untuk notifikasi dan klien yang tidak menampilkan block.
+pub fn format(message: &SummaryMessage) -> Value {
+    let fields: Vec<Value> = message
+        .fields
+        .iter()
+        .map(|field| json!({ "type": "mrkdwn", "text": format!("*{}*\n{}", escape(&field.name), escape(&field.value)) }))
+        .collect();
+
+    json!({
+        "text": format!("{}: {}", message.title, message.description),
+        "blocks": [
+            { "type": "header", "text": { "type": "plain_text", "text": message.title } },
+            { "type": "section", "text": { "type": "mrkdwn", "text": escape(&message.description) } },
+            { "type": "section", "fields": fields },
+            { "type": "context", "elements": [{ "type": "mrkdwn", "text": escape(&message.footer) }] },
+        ],
+    })
+}
+
+/// Karakter kontrol mrkdwn Slack
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integrations::SummaryField;
+
+    #[test]
+    fn fields_are_escaped_mrkdwn() {
+        let message = SummaryMessage {
+            title: "Weekly mood".to_string(),
+            description: "Better than <last> week".to_string(),
+            fields: vec![SummaryField { name: "Entries".to_string(), value: "5 of 7 days".to_string() }],
+            footer: "MindMate".to_string(),
+        };
+
+        let payload = format(&message);
+        assert_eq!(payload["blocks"][1]["text"]["text"], "Better than &lt;last&gt; week");
+        assert_eq!(payload["blocks"][2]["fields"][0]["text"], "*Entries*\n5 of 7 days");
+    }
+}
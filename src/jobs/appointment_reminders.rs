@@ -0,0 +1,35 @@
+use std::sync::Arc;
+use crate::config::app_config::app_config;
+use crate::db::pool::DbPools;
+use crate::jobs::{scheduler, JobContext};
+use crate::service::appointment_service;
+use crate::utils::mailer::Mailer;
+
+/// Kirim pengingat janji temu sesuai APPOINTMENT_REMINDER_SCHEDULE
+pub async fn run(
+    pool: DbPools,
+    mailer: Arc<dyn Mailer>,
+    ctx: JobContext,
+) {
+    let schedule = match scheduler::parse_schedule(&app_config().appointment_reminder_schedule) {
+        Ok(schedule) => schedule,
+        Err(e) => {
+            eprintln!("❌ Appointment reminders disabled: {}", e);
+            return;
+        }
+    };
+
+    scheduler::run_on_schedule(&schedule, &ctx, || async { send(&pool, mailer.as_ref()) }).await;
+}
+
+fn send(pool: &DbPools, mailer: &dyn Mailer) {
+    match appointment_service::send_appointment_reminders(pool, mailer) {
+        Ok(0) => {}
+        Ok(sent) => {
+            println!("✅ Sent {} appointment reminders", sent);
+        }
+        Err(e) => {
+            eprintln!("❌ Failed to send appointment reminders: {}", e);
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use std::sync::Arc;
+use std::time::Duration;
+use axum::async_trait;
+use chrono::{DateTime, Utc};
+use redis::aio::{ConnectionManager, ConnectionManagerConfig};
+use tokio::sync::OnceCell;
+use crate::config::app_config::AppConfig;
+use crate::db::pool::DbPools;
+use crate::db::scheduled_job_query;
+use crate::errors::app_error::AppError;
+
+/// Koordinasi eksekusi job terjadwal. Setiap replika menjalankan scheduler yang sama,
+/// dan sebelum satu jadwal dijalankan, replika harus berhasil mengklaimnya lewat backend.
+#[async_trait]
+pub trait JobBackend: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    /// Klaim eksekusi `job` untuk jadwal `scheduled_at`. `false` berarti replika lain
+    /// sudah mengklaimnya, jadi jadwal ini dilewati.
+    async fn claim(&self, job: &str, scheduled_at: DateTime<Utc>) -> Result<bool, AppError>;
+}
+
+/// Backend default: semua jadwal dijalankan di proses ini oleh Tokio. Cocok untuk satu
+/// instance; dengan beberapa replika setiap jadwal akan berjalan di semua replika.
+pub struct TokioBackend;
+
+#[async_trait]
+impl JobBackend for TokioBackend {
+    fn name(&self) -> &'static str {
+        "tokio"
+    }
+
+    async fn claim(&self, _job: &str, _scheduled_at: DateTime<Utc>) -> Result<bool, AppError> {
+        Ok(true)
+    }
+}
+
+/// Backend untuk beberapa replika tanpa infrastruktur tambahan: klaim memakai advisory lock
+/// Postgres dan dicatat di `scheduled_job_runs`, jadi setiap jadwal berjalan tepat sekali di seluruh cluster.
+pub struct PostgresBackend {
+    pool: DbPools,
+}
+
+impl PostgresBackend {
+    pub fn new(pool: DbPools) -> Self {
+        PostgresBackend { pool }
+    }
+}
+
+#[async_trait]
+impl JobBackend for PostgresBackend {
+    fn name(&self) -> &'static str {
+        "postgres"
+    }
+
+    async fn claim(&self, job: &str, scheduled_at: DateTime<Utc>) -> Result<bool, AppError> {
+        let mut conn = self.pool.conn_write()?;
+        scheduled_job_query::claim_schedule(&mut conn, job, scheduled_at.naive_utc(), Utc::now().naive_utc())
+    }
+}
+
+/// Backend untuk beberapa replika: klaim disimpan di Redis dengan `SET NX`, jadi hanya
+/// replika pertama yang menjalankan jadwal tersebut. Key kedaluwarsa sendiri setelah `claim_ttl`.
+pub struct RedisBackend {
+    client: redis::Client,
+    timeout: Duration,
+    /// Dibuka saat klaim pertama; `ConnectionManager` menyambung ulang sendiri jika koneksi putus
+    connection: OnceCell<ConnectionManager>,
+    key_prefix: String,
+    claim_ttl: Duration,
+}
+
+impl RedisBackend {
+    /// URL berbentuk `redis://[[user]:password@]host[:port][/db]`
+    pub fn new(url: &str, timeout: Duration, key_prefix: &str, claim_ttl: Duration) -> Result<Self, AppError> {
+        let client = redis::Client::open(url)
+            .map_err(|e| AppError::InternalServerError(format!("Invalid REDIS_URL: {}", e)))?;
+
+        Ok(RedisBackend {
+            client,
+            timeout,
+            connection: OnceCell::new(),
+            key_prefix: key_prefix.to_string(),
+            claim_ttl,
+        })
+    }
+
+    async fn connection(&self) -> Result<ConnectionManager, AppError> {
+        let connection = self
+            .connection
+            .get_or_try_init(|| {
+                let config = ConnectionManagerConfig::new()
+                    .set_connection_timeout(self.timeout)
+                    .set_response_timeout(self.timeout);
+                ConnectionManager::new_with_config(self.client.clone(), config)
+            })
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("Failed to connect to Redis: {}", e)))?;
+        Ok(connection.clone())
+    }
+
+    fn claim_key(&self, job: &str, scheduled_at: DateTime<Utc>) -> String {
+        format!("{}:jobs:{}:{}", self.key_prefix, job, scheduled_at.timestamp())
+    }
+}
+
+#[async_trait]
+impl JobBackend for RedisBackend {
+    fn name(&self) -> &'static str {
+        "redis"
+    }
+
+    async fn claim(&self, job: &str, scheduled_at: DateTime<Utc>) -> Result<bool, AppError> {
+        let key = self.claim_key(job, scheduled_at);
+        // Batas waktu mencakup membuka koneksi, yang di `ConnectionManager` dicoba ulang dengan backoff
+        tokio::time::timeout(self.timeout, async {
+            let mut connection = self.connection().await?;
+            let reply: Option<String> = redis::cmd("SET")
+                .arg(key)
+                .arg(1)
+                .arg("NX")
+                .arg("PX")
+                .arg(self.claim_ttl.as_millis() as u64)
+                .query_async(&mut connection)
+                .await
+                .map_err(|e| AppError::InternalServerError(format!("Redis error: {}", e)))?;
+            // `SET NX` membalas nil jika key sudah ada
+            Ok(reply.is_some())
+        })
+        .await
+        .unwrap_or_else(|_| Err(AppError::InternalServerError("Redis command timed out".to_string())))
+    }
+}
+
+/// Backend sesuai JOB_BACKEND (`postgres`, `tokio` atau `redis`)
+pub fn from_config(config: &AppConfig, pool: &DbPools) -> Result<Arc<dyn JobBackend>, AppError> {
+    match config.job_backend.as_str() {
+        "postgres" => Ok(Arc::new(PostgresBackend::new(pool.clone()))),
+        "tokio" => Ok(Arc::new(TokioBackend)),
+        "redis" => {
+            let url = config
+                .redis_url
+                .as_deref()
+                .ok_or_else(|| AppError::InternalServerError("JOB_BACKEND=redis requires REDIS_URL".to_string()))?;
+            Ok(Arc::new(RedisBackend::new(
+                url,
+                Duration::from_secs(config.redis_timeout_secs),
+                &config.redis_key_prefix,
+                Duration::from_secs(config.job_claim_ttl_secs),
+            )?))
+        }
+        other => Err(AppError::InternalServerError(format!("Unknown JOB_BACKEND: {}", other))),
+    }
+}
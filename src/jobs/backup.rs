@@ -0,0 +1,38 @@
+use std::sync::Arc;
+use crate::config::app_config::app_config;
+use crate::db::pool::DbPools;
+use crate::jobs::{scheduler, JobContext};
+use crate::models::backup::CreateBackupRequest;
+use crate::service::backup_service;
+use crate::utils::storage::Storage;
+
+/// Backup semua tabel sesuai BACKUP_SCHEDULE; tidak berjalan jika jadwal kosong
+pub async fn run(
+    pool: DbPools,
+    storage: Arc<dyn Storage>,
+    ctx: JobContext,
+) {
+    let Some(ref schedule) = app_config().backup_schedule else {
+        return;
+    };
+    let schedule = match scheduler::parse_schedule(schedule) {
+        Ok(schedule) => schedule,
+        Err(e) => {
+            eprintln!("❌ Scheduled backup disabled: {}", e);
+            return;
+        }
+    };
+
+    scheduler::run_on_schedule(&schedule, &ctx, || async { backup(&pool, storage.as_ref()) }).await;
+}
+
+fn backup(pool: &DbPools, storage: &dyn Storage) {
+    match backup_service::create_backup(pool, storage, CreateBackupRequest::default()) {
+        Ok(backup) => {
+            println!("✅ Created backup {} ({} rows)", backup.name, backup.rows);
+        }
+        Err(e) => {
+            eprintln!("❌ Failed to create backup: {}", e);
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use std::sync::Arc;
+use crate::config::app_config::app_config;
+use crate::db::pool::DbPools;
+use crate::jobs::{scheduler, JobContext};
+use crate::service::insight_service;
+use crate::utils::mailer::Mailer;
+
+/// Deteksi penurunan mood dan kirim notifikasi sesuai INSIGHT_SCHEDULE
+pub async fn run(
+    pool: DbPools,
+    mailer: Arc<dyn Mailer>,
+    ctx: JobContext,
+) {
+    let schedule = match scheduler::parse_schedule(&app_config().insight_schedule) {
+        Ok(schedule) => schedule,
+        Err(e) => {
+            eprintln!("❌ Insight alerts disabled: {}", e);
+            return;
+        }
+    };
+
+    scheduler::run_on_schedule(&schedule, &ctx, || async { notify(&pool, mailer.as_ref()) }).await;
+}
+
+fn notify(pool: &DbPools, mailer: &dyn Mailer) {
+    match insight_service::notify_alerts(pool, mailer) {
+        Ok(sent) => {
+            println!("✅ Sent {} mood insight notifications", sent);
+        }
+        Err(e) => {
+            eprintln!("❌ Failed to send mood insight notifications: {}", e);
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use std::sync::Arc;
+use crate::config::app_config::app_config;
+use crate::db::pool::DbPools;
+use crate::jobs::{scheduler, JobContext};
+use crate::service::job_service;
+use crate::utils::stats_cache::StatsCache;
+use crate::utils::storage::Storage;
+
+/// Kerjakan antrian job (import, export, PDF, hapus akun) dan bersihkan hasil lama sesuai JOB_WORKER_SCHEDULE
+pub async fn run(
+    pool: DbPools,
+    storage: Arc<dyn Storage>,
+    cache: Arc<StatsCache>,
+    ctx: JobContext,
+) {
+    let schedule = match scheduler::parse_schedule(&app_config().job_worker_schedule) {
+        Ok(schedule) => schedule,
+        Err(e) => {
+            eprintln!("❌ Job worker disabled: {}", e);
+            return;
+        }
+    };
+
+    scheduler::run_on_schedule(&schedule, &ctx, || work(&pool, storage.as_ref(), &cache)).await;
+}
+
+async fn work(pool: &DbPools, storage: &dyn Storage, cache: &StatsCache) {
+    match job_service::run_queued(pool, storage, cache).await {
+        Ok((0, 0)) => {}
+        Ok((succeeded, failed)) => {
+            println!("✅ Processed background jobs: {} succeeded, {} failed", succeeded, failed);
+        }
+        Err(e) => {
+            eprintln!("❌ Failed to process background jobs: {}", e);
+        }
+    }
+    if let Err(e) = job_service::cleanup_finished_jobs(pool, storage) {
+        eprintln!("❌ Failed to cleanup finished jobs: {}", e);
+    }
+}
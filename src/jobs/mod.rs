@@ -0,0 +1,111 @@
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use crate::jobs::backend::JobBackend;
+
+pub mod backend;
+pub mod scheduler;
+pub mod appointment_reminders;
+pub mod backup;
+pub mod insight_alerts;
+pub mod job_worker;
+pub mod mood_trash;
+pub mod push_delivery;
+pub mod reminders;
+pub mod retention;
+pub mod token_cleanup;
+pub mod usage_flush;
+pub mod webhook_delivery;
+pub mod webhook_summaries;
+
+/// Jeda sebelum job yang panic dijalankan ulang
+const RESTART_BACKOFF: Duration = Duration::from_secs(5);
+
+/// Yang diterima setiap job saat dijalankan supervisor
+#[derive(Clone)]
+pub struct JobContext {
+    pub name: &'static str,
+    /// Dibatalkan saat shutdown; job harus berhenti sendiri
+    pub token: CancellationToken,
+    pub backend: Arc<dyn JobBackend>,
+}
+
+/// Menjalankan background job, me-restart job yang panic,
+/// dan menghentikan semuanya lewat satu CancellationToken saat shutdown
+pub struct JobSupervisor {
+    token: CancellationToken,
+    backend: Arc<dyn JobBackend>,
+    handles: Vec<JoinHandle<()>>,
+}
+
+impl JobSupervisor {
+    pub fn new(token: CancellationToken, backend: Arc<dyn JobBackend>) -> Self {
+        JobSupervisor {
+            token,
+            backend,
+            handles: Vec::new(),
+        }
+    }
+
+    pub fn token(&self) -> CancellationToken {
+        self.token.clone()
+    }
+
+    /// Jalankan job dengan supervisi. Job harus berhenti sendiri ketika token dibatalkan.
+    pub fn spawn<F, Fut>(&mut self, name: &'static str, job: F)
+    where
+        F: Fn(JobContext) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let token = self.token.clone();
+        let ctx = JobContext {
+            name,
+            token: token.clone(),
+            backend: self.backend.clone(),
+        };
+
+        let handle = tokio::spawn(async move {
+            loop {
+                let result = tokio::spawn(job(ctx.clone())).await;
+
+                match result {
+                    Ok(()) => break,
+                    Err(e) if e.is_panic() => {
+                        eprintln!("❌ Job '{}' panicked, restarting in {:?}", name, RESTART_BACKOFF);
+                    }
+                    Err(e) => {
+                        eprintln!("❌ Job '{}' was aborted: {}", name, e);
+                        break;
+                    }
+                }
+
+                tokio::select! {
+                    _ = token.cancelled() => break,
+                    _ = tokio::time::sleep(RESTART_BACKOFF) => {}
+                }
+            }
+
+            println!("🛑 Job '{}' stopped", name);
+        });
+
+        self.handles.push(handle);
+    }
+
+    /// Batalkan semua job dan tunggu sampai selesai, paling lama `timeout`. Job boleh
+    /// menyelesaikan pekerjaan terakhirnya (misalnya mengosongkan antrian push) dalam batas waktu ini.
+    pub async fn shutdown(self, timeout: Duration) {
+        self.token.cancel();
+
+        let wait_all = async {
+            for handle in self.handles {
+                let _ = handle.await;
+            }
+        };
+
+        if tokio::time::timeout(timeout, wait_all).await.is_err() {
+            eprintln!("⚠️ Background jobs did not stop within {:?}", timeout);
+        }
+    }
+}
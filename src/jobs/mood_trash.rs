@@ -0,0 +1,33 @@
+use crate::config::app_config::app_config;
+use crate::db::pool::DbPools;
+use crate::jobs::{scheduler, JobContext};
+use crate::service::mood_service;
+
+/// Hapus permanen mood di tempat sampah yang sudah lewat jendela undo, sesuai MOOD_TRASH_PURGE_SCHEDULE
+pub async fn run(
+    pool: DbPools,
+    ctx: JobContext,
+) {
+    let schedule = match scheduler::parse_schedule(&app_config().mood_trash_purge_schedule) {
+        Ok(schedule) => schedule,
+        Err(e) => {
+            eprintln!("❌ Mood trash purge disabled: {}", e);
+            return;
+        }
+    };
+
+    scheduler::run_on_schedule(&schedule, &ctx, || async { purge(&pool) }).await;
+}
+
+fn purge(pool: &DbPools) {
+    match mood_service::purge_expired_trash(pool) {
+        Ok(deleted_count) => {
+            if deleted_count > 0 {
+                println!("✅ Purged {} deleted moods", deleted_count);
+            }
+        }
+        Err(e) => {
+            eprintln!("❌ Failed to purge deleted moods: {}", e);
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use std::sync::Arc;
+use crate::config::app_config::app_config;
+use crate::db::pool::DbPools;
+use crate::jobs::{scheduler, JobContext};
+use crate::service::push_service;
+use crate::utils::push::PushSender;
+
+/// Kirim antrian push notification sesuai PUSH_DELIVERY_SCHEDULE, dan sekali lagi saat shutdown
+/// sampai antrian yang jatuh tempo habis (dibatasi timeout shutdown supervisor).
+pub async fn run(
+    pool: DbPools,
+    sender: Arc<PushSender>,
+    ctx: JobContext,
+) {
+    let schedule = match scheduler::parse_schedule(&app_config().push_delivery_schedule) {
+        Ok(schedule) => schedule,
+        Err(e) => {
+            eprintln!("❌ Push delivery disabled: {}", e);
+            return;
+        }
+    };
+
+    scheduler::run_on_schedule(&schedule, &ctx, || deliver(&pool, sender.as_ref())).await;
+
+    match push_service::deliver_all_due(&pool, sender.as_ref()).await {
+        Ok(0) => {}
+        Ok(sent) => {
+            println!("✅ Sent {} queued push notifications before shutdown", sent);
+        }
+        Err(e) => {
+            eprintln!("❌ Failed to flush push notifications on shutdown: {}", e);
+        }
+    }
+}
+
+async fn deliver(pool: &DbPools, sender: &PushSender) {
+    match push_service::deliver_due(pool, sender).await {
+        Ok(0) => {}
+        Ok(sent) => {
+            println!("✅ Sent {} push notifications", sent);
+        }
+        Err(e) => {
+            eprintln!("❌ Failed to deliver push notifications: {}", e);
+        }
+    }
+}
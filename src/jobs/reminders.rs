@@ -0,0 +1,35 @@
+use std::sync::Arc;
+use crate::config::app_config::app_config;
+use crate::db::pool::DbPools;
+use crate::jobs::{scheduler, JobContext};
+use crate::service::notification_service;
+use crate::utils::mailer::Mailer;
+
+/// Kirim pengingat harian sesuai REMINDER_SCHEDULE; push dikirim oleh job push_delivery
+pub async fn run(
+    pool: DbPools,
+    mailer: Arc<dyn Mailer>,
+    ctx: JobContext,
+) {
+    let schedule = match scheduler::parse_schedule(&app_config().reminder_schedule) {
+        Ok(schedule) => schedule,
+        Err(e) => {
+            eprintln!("❌ Reminders disabled: {}", e);
+            return;
+        }
+    };
+
+    scheduler::run_on_schedule(&schedule, &ctx, || async { send(&pool, mailer.as_ref()) }).await;
+}
+
+fn send(pool: &DbPools, mailer: &dyn Mailer) {
+    match notification_service::send_due_reminders(pool, mailer) {
+        Ok(0) => {}
+        Ok(sent) => {
+            println!("✅ Sent {} daily reminders", sent);
+        }
+        Err(e) => {
+            eprintln!("❌ Failed to send daily reminders: {}", e);
+        }
+    }
+}
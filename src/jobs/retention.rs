@@ -0,0 +1,44 @@
+use std::sync::Arc;
+use crate::config::app_config::app_config;
+use crate::db::pool::DbPools;
+use crate::jobs::{scheduler, JobContext};
+use crate::service::retention_service;
+use crate::utils::mailer::Mailer;
+use crate::utils::stats_cache::StatsCache;
+
+/// Hapus jurnal lama sesuai pengaturan retensi pengguna, dijalankan sesuai RETENTION_SCHEDULE
+pub async fn run(
+    pool: DbPools,
+    cache: Arc<StatsCache>,
+    mailer: Arc<dyn Mailer>,
+    ctx: JobContext,
+) {
+    let schedule = match scheduler::parse_schedule(&app_config().retention_schedule) {
+        Ok(schedule) => schedule,
+        Err(e) => {
+            eprintln!("❌ Journal retention disabled: {}", e);
+            return;
+        }
+    };
+
+    scheduler::run_on_schedule(&schedule, &ctx, || async {
+        apply(&pool, &cache, mailer.as_ref())
+    })
+    .await;
+}
+
+fn apply(pool: &DbPools, cache: &StatsCache, mailer: &dyn Mailer) {
+    match retention_service::apply_retention(pool, cache, mailer) {
+        Ok(run) => {
+            if run.notified > 0 || run.purged > 0 {
+                println!(
+                    "✅ Journal retention: {} notices sent, {} journals deleted",
+                    run.notified, run.purged
+                );
+            }
+        }
+        Err(e) => {
+            eprintln!("❌ Journal retention failed: {}", e);
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use chrono::Utc;
+use cron::Schedule;
+use std::future::Future;
+use std::str::FromStr;
+use crate::jobs::JobContext;
+
+/// Parse ekspresi cron (detik menit jam hari bulan hari-minggu)
+pub fn parse_schedule(expression: &str) -> Result<Schedule, String> {
+    Schedule::from_str(expression)
+        .map_err(|e| format!("Invalid cron expression '{}': {}", expression, e))
+}
+
+/// Jalankan `task` setiap kali jadwal tercapai (UTC) sampai token dibatalkan. Setiap jadwal
+/// diklaim lewat backend job lebih dulu, sehingga dengan beberapa replika hanya satu yang menjalankannya.
+pub async fn run_on_schedule<F, Fut>(schedule: &Schedule, ctx: &JobContext, mut task: F)
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = ()>,
+{
+    for next in schedule.upcoming(Utc) {
+        let wait = (next - Utc::now()).to_std().unwrap_or_default();
+
+        tokio::select! {
+            _ = ctx.token.cancelled() => return,
+            _ = tokio::time::sleep(wait) => {}
+        }
+
+        match ctx.backend.claim(ctx.name, next).await {
+            Ok(true) => task().await,
+            Ok(false) => {}
+            Err(e) => {
+                eprintln!("❌ Job '{}' skipped, failed to claim schedule on {} backend: {}", ctx.name, ctx.backend.name(), e);
+            }
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use crate::config::app_config::app_config;
+use crate::db::pool::DbPools;
+use crate::jobs::{scheduler, JobContext};
+use crate::service::{auth_service, checkin_service, google_auth_service, quota_service, usage_service};
+
+/// Cleanup token blacklist, token check-in yang sudah dipakai, kode tukar Google yang kedaluwarsa, catatan kuota lama, dan statistik request API lama sekali saat startup, lalu sesuai TOKEN_CLEANUP_SCHEDULE
+pub async fn run(
+    pool: DbPools,
+    ctx: JobContext,
+) {
+    let schedule = match scheduler::parse_schedule(&app_config().token_cleanup_schedule) {
+        Ok(schedule) => schedule,
+        Err(e) => {
+            eprintln!("❌ Token cleanup disabled: {}", e);
+            return;
+        }
+    };
+
+    cleanup(&pool);
+
+    scheduler::run_on_schedule(&schedule, &ctx, || async { cleanup(&pool) }).await;
+}
+
+fn cleanup(pool: &DbPools) {
+    match auth_service::cleanup_expired_tokens(pool) {
+        Ok(deleted_count) => {
+            println!("✅ Cleaned up {} expired tokens", deleted_count);
+        }
+        Err(e) => {
+            eprintln!("❌ Failed to cleanup expired tokens: {}", e);
+        }
+    }
+    if let Err(e) = checkin_service::cleanup_used_tokens(pool) {
+        eprintln!("❌ Failed to cleanup used check-in tokens: {}", e);
+    }
+    if let Err(e) = quota_service::cleanup_old_usage(pool) {
+        eprintln!("❌ Failed to cleanup usage quotas: {}", e);
+    }
+    if let Err(e) = usage_service::cleanup_old_usage(pool) {
+        eprintln!("❌ Failed to cleanup API usage statistics: {}", e);
+    }
+    if let Err(e) = google_auth_service::cleanup_expired_exchange_codes(pool) {
+        eprintln!("❌ Failed to cleanup expired exchange codes: {}", e);
+    }
+}
@@ -0,0 +1,34 @@
+use std::sync::Arc;
+use std::time::Duration;
+use crate::config::app_config::app_config;
+use crate::db::pool::DbPools;
+use crate::jobs::JobContext;
+use crate::service::usage_service;
+use crate::utils::api_usage::ApiUsageCounter;
+
+/// Simpan hitungan request API setiap API_USAGE_FLUSH_SECS, dan sekali lagi saat shutdown.
+/// Hitungan ada di memori tiap replika, jadi job ini tidak diklaim lewat backend job.
+pub async fn run(
+    pool: DbPools,
+    counter: Arc<ApiUsageCounter>,
+    ctx: JobContext,
+) {
+    let mut interval = tokio::time::interval(Duration::from_secs(app_config().api_usage_flush_secs.max(1)));
+    interval.tick().await;
+
+    loop {
+        tokio::select! {
+            _ = ctx.token.cancelled() => {
+                flush(&pool, &counter);
+                return;
+            }
+            _ = interval.tick() => flush(&pool, &counter),
+        }
+    }
+}
+
+fn flush(pool: &DbPools, counter: &ApiUsageCounter) {
+    if let Err(e) = usage_service::flush(pool, counter) {
+        eprintln!("❌ Failed to save API usage counts: {}", e);
+    }
+}
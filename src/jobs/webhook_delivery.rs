@@ -0,0 +1,34 @@
+use crate::config::app_config::app_config;
+use crate::db::pool::DbPools;
+use crate::jobs::{scheduler, JobContext};
+use crate::service::webhook_service;
+use crate::utils::http_client::HttpClient;
+
+/// Kirim antrian webhook sesuai WEBHOOK_DELIVERY_SCHEDULE
+pub async fn run(
+    pool: DbPools,
+    http_client: HttpClient,
+    ctx: JobContext,
+) {
+    let schedule = match scheduler::parse_schedule(&app_config().webhook_delivery_schedule) {
+        Ok(schedule) => schedule,
+        Err(e) => {
+            eprintln!("❌ Webhook delivery disabled: {}", e);
+            return;
+        }
+    };
+
+    scheduler::run_on_schedule(&schedule, &ctx, || deliver(&pool, &http_client)).await;
+}
+
+async fn deliver(pool: &DbPools, http_client: &HttpClient) {
+    match webhook_service::deliver_due(pool, http_client).await {
+        Ok(0) => {}
+        Ok(sent) => {
+            println!("✅ Delivered {} webhook summaries", sent);
+        }
+        Err(e) => {
+            eprintln!("❌ Failed to deliver webhook summaries: {}", e);
+        }
+    }
+}
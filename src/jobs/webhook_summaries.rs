@@ -0,0 +1,32 @@
+use crate::config::app_config::app_config;
+use crate::db::pool::DbPools;
+use crate::jobs::{scheduler, JobContext};
+use crate::service::webhook_service;
+
+/// Antrikan ringkasan mood mingguan ke webhook pengguna sesuai WEBHOOK_SUMMARY_SCHEDULE
+pub async fn run(
+    pool: DbPools,
+    ctx: JobContext,
+) {
+    let schedule = match scheduler::parse_schedule(&app_config().webhook_summary_schedule) {
+        Ok(schedule) => schedule,
+        Err(e) => {
+            eprintln!("❌ Webhook summaries disabled: {}", e);
+            return;
+        }
+    };
+
+    scheduler::run_on_schedule(&schedule, &ctx, || async { enqueue(&pool) }).await;
+}
+
+fn enqueue(pool: &DbPools) {
+    match webhook_service::enqueue_weekly_summaries(pool) {
+        Ok(0) => {}
+        Ok(queued) => {
+            println!("✅ Queued {} webhook summaries", queued);
+        }
+        Err(e) => {
+            eprintln!("❌ Failed to queue webhook summaries: {}", e);
+        }
+    }
+}
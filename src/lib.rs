@@ -5,8 +5,16 @@ pub mod api;
 pub mod service;
 pub mod models;
 pub mod db;
+pub mod repo;
 pub mod config;
 pub mod errors;
 pub mod utils;
 pub mod path;
 pub mod middleware;
+pub mod state;
+pub mod jobs;
+pub mod i18n;
+pub mod app;
+pub mod analytics;
+pub mod integrations;
+pub mod grpc;
\ No newline at end of file
@@ -1,49 +1,41 @@
-use axum::Router;
 use dotenv::dotenv;
 use std::env;
 use std::net::SocketAddr;
-use tower_http::cors::{CorsLayer}; 
-use axum::http::{HeaderValue, Method}; 
-use tokio::time::{sleep, Duration};
-use axum::http::header::{AUTHORIZATION, CONTENT_TYPE, ACCEPT};
-use diesel::r2d2;
-use diesel::pg::PgConnection;
-
-mod api;
-mod service;
-mod models;
-mod db;
-mod path;
-mod config;
-mod errors;
-mod utils;
-mod schema;
-mod middleware;
-
-// Background task untuk cleanup expired tokens
-async fn token_cleanup_task(pool: r2d2::Pool<r2d2::ConnectionManager<PgConnection>>) {
-    loop {
-        // Jalankan setiap 24 jam
-        sleep(Duration::from_secs(24 * 60 * 60)).await;
-        
-        let cutoff_date = chrono::Utc::now().naive_utc() - chrono::Duration::days(7);
-        
-        match pool.get() {
-            Ok(mut conn) => {
-                match db::token_blacklist_query::cleanup_expired_tokens(&mut conn, cutoff_date) {
-                    Ok(deleted_count) => {
-                        println!("✅ Cleaned up {} expired tokens", deleted_count);
-                    }
-                    Err(e) => {
-                        eprintln!("❌ Failed to cleanup expired tokens: {}", e);
-                    }
-                }
-            }
-            Err(e) => {
-                eprintln!("❌ Failed to get DB connection for cleanup: {}", e);
-            }
-        }
+use std::time::Duration;
+use tokio::signal;
+use tokio_util::sync::CancellationToken;
+use mindmate_be::{app, db, grpc, jobs};
+use mindmate_be::config::app_config::app_config;
+use mindmate_be::config::startup_check;
+use mindmate_be::middleware::panic;
+use mindmate_be::state::AppState;
+
+/// Tunggu SIGINT (Ctrl+C) atau SIGTERM, lalu batalkan semua background job
+async fn shutdown_signal(token: CancellationToken) {
+    let ctrl_c = async {
+        signal::ctrl_c()
+            .await
+            .expect("Failed to install Ctrl+C handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        signal::unix::signal(signal::unix::SignalKind::terminate())
+            .expect("Failed to install SIGTERM handler")
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
     }
+
+    println!("🛑 Shutdown signal received, stopping server...");
+    token.cancel();
 }
 
 #[tokio::main]
@@ -54,38 +46,119 @@ async fn main() {
     // Initialize logger (make sure RUST_LOG is set, e.g. to "debug")
     env_logger::init();
 
+    // Panic dicatat beserta backtrace dan request id; respons 500-nya dibuat oleh CatchPanicLayer
+    panic::install_hook();
+
+    // Hentikan proses sebelum menerima request jika konfigurasi wajib tidak lengkap
+    let problems = startup_check::check(app_config());
+    if !problems.is_empty() {
+        eprintln!("{}", startup_check::report(&problems));
+        std::process::exit(1);
+    }
+
     // Get the database URL from environment
-    let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let database_url = app_config().database_url.clone().expect("DATABASE_URL must be set");
+
+    // Create the database connection pools (primary + optional read replica)
+    let pool = db::pool::DbPools::from_urls(database_url, app_config().database_replica_url.clone());
+    let config = app_config();
+    if !pool.wait_for_primary(config.db_startup_retries, Duration::from_secs(config.db_startup_retry_secs)) {
+        eprintln!("⚠️ Starting without a database connection; /ready reports unavailable until it recovers");
+    }
+
+    // Background job dijalankan lewat supervisor agar bisa di-restart dan dihentikan saat shutdown
+    let job_backend = jobs::backend::from_config(app_config(), &pool).expect("Failed to initialize job backend");
+    println!("🗓️ Scheduled jobs coordinated by the {} backend", job_backend.name());
+    let mut supervisor = jobs::JobSupervisor::new(CancellationToken::new(), job_backend);
+
+    let state = AppState::new(pool).expect("Failed to initialize application state");
+
+    let cleanup_pool = state.pool.clone();
+    supervisor.spawn("token_cleanup", move |ctx| {
+        jobs::token_cleanup::run(cleanup_pool.clone(), ctx)
+    });
+
+    let trash_pool = state.pool.clone();
+    supervisor.spawn("mood_trash", move |ctx| {
+        jobs::mood_trash::run(trash_pool.clone(), ctx)
+    });
+
+    let insight_pool = state.pool.clone();
+    let insight_mailer = state.mailer.clone();
+    supervisor.spawn("insight_alerts", move |ctx| {
+        jobs::insight_alerts::run(insight_pool.clone(), insight_mailer.clone(), ctx)
+    });
+
+    let push_pool = state.pool.clone();
+    let push_sender = state.push_sender.clone();
+    supervisor.spawn("push_delivery", move |ctx| {
+        jobs::push_delivery::run(push_pool.clone(), push_sender.clone(), ctx)
+    });
+
+    let reminder_pool = state.pool.clone();
+    let reminder_mailer = state.mailer.clone();
+    supervisor.spawn("reminders", move |ctx| {
+        jobs::reminders::run(reminder_pool.clone(), reminder_mailer.clone(), ctx)
+    });
+
+    let appointment_pool = state.pool.clone();
+    let appointment_mailer = state.mailer.clone();
+    supervisor.spawn("appointment_reminders", move |ctx| {
+        jobs::appointment_reminders::run(appointment_pool.clone(), appointment_mailer.clone(), ctx)
+    });
+
+    let webhook_summary_pool = state.pool.clone();
+    supervisor.spawn("webhook_summaries", move |ctx| {
+        jobs::webhook_summaries::run(webhook_summary_pool.clone(), ctx)
+    });
+
+    let webhook_pool = state.pool.clone();
+    let webhook_client = state.http_client.clone();
+    supervisor.spawn("webhook_delivery", move |ctx| {
+        jobs::webhook_delivery::run(webhook_pool.clone(), webhook_client.clone(), ctx)
+    });
+
+    let job_pool = state.pool.clone();
+    let job_storage = state.storage.clone();
+    let job_cache = state.stats_cache.clone();
+    supervisor.spawn("job_worker", move |ctx| {
+        jobs::job_worker::run(job_pool.clone(), job_storage.clone(), job_cache.clone(), ctx)
+    });
 
-    // Create the database connection pool
-    let pool = db::pool::create_pool(database_url);
+    let backup_pool = state.pool.clone();
+    let backup_storage = state.storage.clone();
+    supervisor.spawn("backup", move |ctx| {
+        jobs::backup::run(backup_pool.clone(), backup_storage.clone(), ctx)
+    });
+
+    let retention_pool = state.pool.clone();
+    let retention_cache = state.stats_cache.clone();
+    let retention_mailer = state.mailer.clone();
+    supervisor.spawn("retention", move |ctx| {
+        jobs::retention::run(retention_pool.clone(), retention_cache.clone(), retention_mailer.clone(), ctx)
+    });
 
-    // Clone pool untuk background task
-    let cleanup_pool = pool.clone();
-    
-    // Jalankan background task untuk cleanup
-    tokio::spawn(async move {
-        token_cleanup_task(cleanup_pool).await;
+    let usage_pool = state.pool.clone();
+    let usage_counter = state.api_usage.clone();
+    supervisor.spawn("usage_flush", move |ctx| {
+        jobs::usage_flush::run(usage_pool.clone(), usage_counter.clone(), ctx)
+    });
+
+    // Server gRPC untuk konsumen internal di port kedua, berhenti bersama server HTTP
+    let grpc_server = app_config().grpc_port.map(|grpc_port| {
+        let grpc_addr = SocketAddr::from(([0, 0, 0, 0], grpc_port));
+        let auth_token = app_config().grpc_auth_token.clone().expect("GRPC_PORT requires GRPC_AUTH_TOKEN");
+        let grpc_state = state.clone();
+        let grpc_shutdown = supervisor.token();
+        println!("📡 gRPC server listening on {}", grpc_addr);
+        tokio::spawn(async move {
+            if let Err(e) = grpc::serve(grpc_state, grpc_addr, auth_token, grpc_shutdown).await {
+                eprintln!("❌ gRPC server failed: {}", e);
+            }
+        })
     });
 
-    // Create API routes dengan prefix /api
-    let api_routes = Router::new()
-        .merge(path::init_routes())
-        .with_state(pool);
-
-    // CORS configuration untuk development
-    let local_origin = "http://localhost:5173".parse::<HeaderValue>().unwrap();
-    let vercel_origin = "https://mindmate-project.vercel.app".parse::<HeaderValue>().unwrap();
-    let cors = CorsLayer::new()
-        .allow_origin([local_origin, vercel_origin])
-        .allow_methods([Method::GET, Method::POST, Method::PUT, Method::DELETE])
-        .allow_headers([AUTHORIZATION, CONTENT_TYPE, ACCEPT])
-        .allow_credentials(true);
-
-    // Create the main app dengan prefix /api
-    let app = Router::new()
-        .nest("/api", api_routes)
-        .layer(cors);
+    let app = app::build_router(state);
 
     // Railway memberikan PORT lewat environment variable
     let port: u16 = env::var("PORT")
@@ -98,8 +171,23 @@ async fn main() {
 
     println!("🚀 Server listening on {}", addr);
 
-    // Run the Axum server
-    axum::serve(tokio::net::TcpListener::bind(&addr).await.unwrap(), app)
+    // Run the Axum server, berhenti menerima koneksi baru saat ada sinyal shutdown
+    axum::serve(
+        tokio::net::TcpListener::bind(&addr).await.unwrap(),
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+        .with_graceful_shutdown(shutdown_signal(supervisor.token()))
         .await
         .expect("Server failed to start");
+
+    if let Some(grpc_server) = grpc_server {
+        if tokio::time::timeout(Duration::from_secs(10), grpc_server).await.is_err() {
+            eprintln!("⚠️ gRPC server did not stop within 10s");
+        }
+    }
+
+    // Tunggu background job selesai sebelum proses keluar
+    supervisor.shutdown(Duration::from_secs(10)).await;
+
+    println!("👋 Server stopped");
 }
\ No newline at end of file
@@ -0,0 +1,45 @@
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::request::Parts,
+};
+use crate::db::user_query;
+use crate::errors::app_error::AppError;
+use crate::state::AppState;
+use crate::middleware::auth_middleware::AuthenticatedUser;
+
+/// Pengguna terautentikasi yang email-nya terdaftar di ADMIN_EMAILS
+#[derive(Clone)]
+pub struct AdminUser(pub AuthenticatedUser);
+
+impl AdminUser {
+    pub fn user_id(&self) -> &str {
+        self.0.user_id()
+    }
+}
+
+#[async_trait]
+impl FromRequestParts<AppState> for AdminUser
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState
+    ) -> Result<Self, Self::Rejection> {
+        let user = AuthenticatedUser::from_request_parts(parts, state).await?;
+        let user_id: i32 = user
+            .user_id()
+            .parse()
+            .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+        let mut conn = state.pool.conn_write()?;
+
+        let account = user_query::find_user_by_id(&mut conn, user_id)?;
+        if !state.config.is_admin_email(&account.email) {
+            return Err(AppError::Forbidden("Admin access required".to_string()));
+        }
+
+        Ok(AdminUser(user))
+    }
+}
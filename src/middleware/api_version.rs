@@ -0,0 +1,104 @@
+use axum::{
+    extract::Request,
+    http::{HeaderName, HeaderValue, Uri},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use crate::errors::app_error::AppError;
+
+/// Prefix tempat semua route API dipasang
+pub const API_PREFIX: &str = "/api";
+
+/// Header untuk memilih versi pada path tanpa versi, dan yang dikembalikan di respons
+pub static API_VERSION_HEADER: HeaderName = HeaderName::from_static("x-api-version");
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ApiVersion {
+    #[default]
+    V1,
+    V2,
+}
+
+impl ApiVersion {
+    /// Terima "1", "v1", "2" atau "v2"
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().trim_start_matches(['v', 'V']) {
+            "1" => Some(ApiVersion::V1),
+            "2" => Some(ApiVersion::V2),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApiVersion::V1 => "1",
+            ApiVersion::V2 => "2",
+        }
+    }
+
+    fn path_segment(&self) -> &'static str {
+        match self {
+            ApiVersion::V1 => "/v1",
+            ApiVersion::V2 => "/v2",
+        }
+    }
+
+    /// Versi dari segmen pertama path setelah `/api`, misalnya `/api/v2/moods`
+    fn from_path(path: &str) -> Option<Self> {
+        let rest = path.strip_prefix(API_PREFIX)?;
+        [ApiVersion::V1, ApiVersion::V2].into_iter().find(|version| {
+            rest.strip_prefix(version.path_segment())
+                .is_some_and(|tail| tail.is_empty() || tail.starts_with('/'))
+        })
+    }
+}
+
+/// Negosiasi versi API. Versi di path selalu menang; path tanpa versi memakai
+/// header `X-Api-Version` dan default ke v1. Harus dipasang di luar router `/api`
+/// karena URI diubah sebelum routing di dalamnya.
+pub async fn negotiate(mut request: Request, next: Next) -> Response {
+    let path = request.uri().path().to_string();
+    let in_api = path == API_PREFIX || path.starts_with(&format!("{}/", API_PREFIX));
+
+    let version = match ApiVersion::from_path(&path) {
+        Some(version) => version,
+        None if in_api => {
+            let requested = match request.headers().get(&API_VERSION_HEADER) {
+                Some(value) => {
+                    let raw = value.to_str().unwrap_or_default();
+                    match ApiVersion::parse(raw) {
+                        Some(version) => version,
+                        None => {
+                            return AppError::BadRequest(format!("Unsupported API version: {}", raw))
+                                .into_response()
+                        }
+                    }
+                }
+                None => ApiVersion::default(),
+            };
+            // Path tanpa versi sudah dilayani v1, jadi hanya versi lain yang perlu ditulis ulang
+            if requested != ApiVersion::default() {
+                if let Some(uri) = versioned_uri(request.uri(), requested) {
+                    *request.uri_mut() = uri;
+                }
+            }
+            requested
+        }
+        None => return next.run(request).await,
+    };
+
+    let mut response = next.run(request).await;
+    response
+        .headers_mut()
+        .insert(API_VERSION_HEADER.clone(), HeaderValue::from_static(version.as_str()));
+    response
+}
+
+/// `/api/moods?x=1` menjadi `/api/v2/moods?x=1`
+fn versioned_uri(uri: &Uri, version: ApiVersion) -> Option<Uri> {
+    let rest = uri.path().strip_prefix(API_PREFIX)?;
+    let query = uri.query().map(|q| format!("?{}", q)).unwrap_or_default();
+    format!("{}{}{}{}", API_PREFIX, version.path_segment(), rest, query)
+        .parse()
+        .ok()
+}
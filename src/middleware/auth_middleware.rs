@@ -1,57 +1,207 @@
 use axum::{
     async_trait,
-    extract::{FromRequestParts},
-    http::{request::Parts},
+    extract::{FromRequestParts, OriginalUri},
+    http::{header, request::Parts, HeaderMap, Method},
 };
-use diesel::{r2d2, PgConnection};
-use crate::utils::jwt::validate_token;
+use std::marker::PhantomData;
+use crate::config::app_config::AppConfig;
+use crate::utils::auth_cookie::{csrf_cookie_name, csrf_matches, read_cookie, CSRF_HEADER};
+use crate::utils::jwt::{validate_token, TokenScope};
 use crate::errors::app_error::AppError;
+use crate::models::audit::{AuditAction, NewAuditLog};
+use crate::service::audit_service;
+use crate::state::AppState;
 
+/// Syarat scope token untuk sebuah route, dipakai sebagai parameter `AuthenticatedUser`
+pub trait ScopeRequirement: Send + Sync + 'static {
+    /// `scopes` bernilai `None` untuk token akses penuh
+    fn allows(scopes: Option<&[TokenScope]>) -> bool;
+
+    /// Nama scope untuk pesan error
+    fn name() -> &'static str;
+}
+
+/// Hanya token akses penuh (hasil login); default untuk semua route
+#[derive(Clone)]
+pub struct FullAccess;
+
+/// Token akses penuh atau token dengan scope `export:read`
+#[derive(Clone)]
+pub struct ExportScope;
+
+/// Token akses penuh atau token dengan scope `share:read`
 #[derive(Clone)]
-pub struct AuthenticatedUser(pub String);
+pub struct ShareScope;
+
+/// Token apa pun yang valid. Hanya untuk extractor pendukung seperti `UserTimezone`
+/// yang tidak memberi akses ke data, jangan dipakai langsung di handler.
+#[derive(Clone)]
+pub struct AnyScope;
+
+impl ScopeRequirement for FullAccess {
+    fn allows(scopes: Option<&[TokenScope]>) -> bool {
+        scopes.is_none()
+    }
+
+    fn name() -> &'static str {
+        "full access"
+    }
+}
+
+impl ScopeRequirement for ExportScope {
+    fn allows(scopes: Option<&[TokenScope]>) -> bool {
+        scopes.is_none_or(|scopes| scopes.contains(&TokenScope::ExportRead))
+    }
+
+    fn name() -> &'static str {
+        TokenScope::ExportRead.as_str()
+    }
+}
+
+impl ScopeRequirement for ShareScope {
+    fn allows(scopes: Option<&[TokenScope]>) -> bool {
+        scopes.is_none_or(|scopes| scopes.contains(&TokenScope::ShareRead))
+    }
+
+    fn name() -> &'static str {
+        TokenScope::ShareRead.as_str()
+    }
+}
+
+impl ScopeRequirement for AnyScope {
+    fn allows(_scopes: Option<&[TokenScope]>) -> bool {
+        true
+    }
 
-impl AuthenticatedUser {
+    fn name() -> &'static str {
+        "any"
+    }
+}
+
+/// Token yang sudah divalidasi pada request ini, disimpan di extensions
+#[derive(Clone)]
+struct VerifiedToken {
+    sub: String,
+    scopes: Option<Vec<TokenScope>>,
+}
+
+#[derive(Clone)]
+pub struct AuthenticatedUser<S: ScopeRequirement = FullAccess>(pub String, PhantomData<S>);
+
+impl<S: ScopeRequirement> AuthenticatedUser<S> {
     pub fn user_id(&self) -> &str {
         &self.0
     }
 }
 
 #[async_trait]
-impl FromRequestParts<r2d2::Pool<r2d2::ConnectionManager<PgConnection>>> for AuthenticatedUser
+impl<S: ScopeRequirement> FromRequestParts<AppState> for AuthenticatedUser<S>
 {
     type Rejection = AppError;
 
     async fn from_request_parts(
         parts: &mut Parts, 
-        state: &r2d2::Pool<r2d2::ConnectionManager<PgConnection>>
+        state: &AppState
     ) -> Result<Self, Self::Rejection> {
-        let auth_header = parts.headers
-            .get("Authorization")
-            .ok_or_else(|| AppError::Unauthorized("Authorization header missing".to_string()))?;
+        let verified = match parts.extensions.get::<VerifiedToken>() {
+            // Sudah divalidasi oleh extractor lain pada request yang sama
+            Some(verified) => verified.clone(),
+            None => {
+                let verified = verify_request_token(parts, state)?;
+                parts.extensions.insert(verified.clone());
+                // Dihitung sekali per request, saat token pertama kali divalidasi
+                if let Ok(user_id) = verified.sub.parse() {
+                    state.api_usage.record(user_id);
+                }
+                verified
+            }
+        };
+
+        if !S::allows(verified.scopes.as_deref()) {
+            return Err(AppError::Forbidden(format!("Token does not have the required scope: {}", S::name())));
+        }
+
+        Ok(AuthenticatedUser(verified.sub, PhantomData))
+    }
+}
 
+/// Token login dari header `Authorization: Bearer`, atau dari cookie sesi jika mode cookie aktif.
+/// Request tulis yang memakai cookie wajib membawa header X-CSRF-Token yang sama dengan cookie CSRF,
+/// karena browser mengirim cookie otomatis termasuk dari situs lain.
+pub fn request_token<'a>(
+    headers: &'a HeaderMap,
+    method: &Method,
+    config: &AppConfig,
+) -> Result<&'a str, AppError> {
+    if let Some(auth_header) = headers.get(header::AUTHORIZATION) {
         let auth_str = auth_header.to_str()
             .map_err(|_| AppError::Unauthorized("Invalid Authorization header".to_string()))?;
+        return auth_str
+            .strip_prefix("Bearer ")
+            .ok_or_else(|| AppError::Unauthorized("Invalid Authorization scheme".to_string()));
+    }
+
+    let cookie_token = config
+        .auth_cookie_enabled
+        .then(|| read_cookie(headers, &config.auth_cookie_name))
+        .flatten()
+        .ok_or_else(|| AppError::Unauthorized("Authorization header missing".to_string()))?;
 
-        if !auth_str.starts_with("Bearer ") {
-            return Err(AppError::Unauthorized("Invalid Authorization scheme".to_string()));
+    if !method.is_safe() {
+        let expected = read_cookie(headers, &csrf_cookie_name(config));
+        let provided = headers.get(CSRF_HEADER).and_then(|value| value.to_str().ok());
+        match (expected, provided) {
+            (Some(expected), Some(provided)) if csrf_matches(expected, provided) => {}
+            _ => return Err(AppError::Forbidden("Missing or invalid CSRF token".to_string())),
         }
+    }
 
-        let token = &auth_str[7..];
+    Ok(cookie_token)
+}
 
-        let claims = validate_token(token)
-            .map_err(|_| AppError::Unauthorized("Invalid token".to_string()))?;
+fn verify_request_token(parts: &Parts, state: &AppState) -> Result<VerifiedToken, AppError> {
+    let token = request_token(&parts.headers, &parts.method, state.config)?;
 
-        let mut conn = state
-            .get()
-            .map_err(|_| AppError::InternalServerError("Failed to get DB connection".to_string()))?;
+    let claims = validate_token(token)
+        .map_err(|_| AppError::Unauthorized("Invalid token".to_string()))?;
 
-        let is_blacklisted = crate::db::token_blacklist_query::is_token_blacklisted(&mut conn, token)
-            .map_err(|_| AppError::InternalServerError("Failed to check token blacklist".to_string()))?;
+    let mut conn = state.pool.conn_write()?;
 
-        if is_blacklisted {
-            return Err(AppError::Unauthorized("Token is blacklisted".to_string()));
-        }
+    let is_blacklisted = crate::db::token_blacklist_query::is_token_blacklisted(&mut conn, token)
+        .map_err(|_| AppError::InternalServerError("Failed to check token blacklist".to_string()))?;
+
+    if is_blacklisted {
+        return Err(AppError::Unauthorized("Token is blacklisted".to_string()));
+    }
 
-        Ok(AuthenticatedUser(claims.sub))
+    // Token impersonasi hanya untuk melihat; setiap request dicatat di audit log
+    if let Some(ref impersonator) = claims.impersonator {
+        if !parts.method.is_safe() {
+            return Err(AppError::Forbidden("Impersonation tokens cannot modify data".to_string()));
+        }
+        audit_service::record(
+            &mut conn,
+            NewAuditLog::new(
+                AuditAction::ImpersonatedRequest,
+                impersonator.parse().ok(),
+                claims.sub.parse().ok(),
+                None,
+            )
+            .with_details(format!("{} {}", parts.method, original_path(parts))),
+        )?;
     }
-}
\ No newline at end of file
+
+    Ok(VerifiedToken {
+        scopes: claims.scopes(),
+        sub: claims.sub,
+    })
+}
+
+/// Path lengkap request, termasuk prefix router yang di-nest (`/api/v1`)
+fn original_path(parts: &Parts) -> &str {
+    parts
+        .extensions
+        .get::<OriginalUri>()
+        .map(|uri| uri.0.path())
+        .unwrap_or_else(|| parts.uri.path())
+}
@@ -0,0 +1,20 @@
+use axum::{
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+};
+use crate::errors::app_error::AppError;
+
+/// Respons 413 dari `RequestBodyLimitLayer` atau extractor berupa teks biasa;
+/// ubah menjadi body error JSON yang sama dengan `AppError` lainnya
+pub async fn payload_too_large_as_json(response: Response) -> Response {
+    let is_json = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("application/json"));
+
+    if response.status() == StatusCode::PAYLOAD_TOO_LARGE && !is_json {
+        return AppError::PayloadTooLarge("Request body is too large".to_string()).into_response();
+    }
+    response
+}
@@ -0,0 +1,31 @@
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::request::Parts,
+};
+use crate::errors::app_error::AppError;
+use crate::middleware::client_info::ClientInfo;
+use crate::state::AppState;
+
+/// Header tempat klien mengirim token CAPTCHA (hCaptcha/Turnstile)
+pub const CAPTCHA_TOKEN_HEADER: &str = "x-captcha-token";
+
+/// Extractor untuk endpoint publik yang rawan disalahgunakan. Lolos tanpa CAPTCHA
+/// sampai IP klien melewati batas percobaan, setelah itu header `X-Captcha-Token` wajib.
+pub struct CaptchaVerified;
+
+#[async_trait]
+impl FromRequestParts<AppState> for CaptchaVerified {
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self, Self::Rejection> {
+        let client = ClientInfo::from_request_parts(parts, state).await?;
+        let token = parts
+            .headers
+            .get(CAPTCHA_TOKEN_HEADER)
+            .and_then(|value| value.to_str().ok());
+
+        state.captcha.check(client.ip_address.as_deref(), token).await?;
+        Ok(CaptchaVerified)
+    }
+}
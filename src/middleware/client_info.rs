@@ -0,0 +1,160 @@
+use std::net::{IpAddr, SocketAddr};
+use axum::{
+    async_trait,
+    extract::{ConnectInfo, FromRequestParts},
+    http::{header::{FORWARDED, USER_AGENT}, request::Parts, HeaderMap},
+};
+use ipnet::IpNet;
+use crate::config::app_config::app_config;
+use crate::errors::app_error::AppError;
+
+/// Alamat IP dan user agent klien, dipakai untuk audit login, lockout dan CAPTCHA
+#[derive(Clone, Debug, Default)]
+pub struct ClientInfo {
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+}
+
+impl ClientInfo {
+    pub fn from_parts(parts: &Parts) -> Self {
+        let peer = parts
+            .extensions
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| addr.ip());
+        let ip_address = client_ip(&parts.headers, peer, &app_config().trusted_proxies)
+            .map(|ip| ip.to_string());
+
+        let user_agent = parts
+            .headers
+            .get(USER_AGENT)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.chars().take(512).collect());
+
+        ClientInfo { ip_address, user_agent }
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for ClientInfo
+where
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(ClientInfo::from_parts(parts))
+    }
+}
+
+/// IP klien asli. Header proxy hanya dibaca jika koneksi datang dari proxy tepercaya;
+/// rantai alamat ditelusuri dari kanan dan alamat pertama yang bukan proxy tepercaya
+/// dianggap klien. Tanpa alamat socket (test in-process) header langsung dipakai.
+pub fn client_ip(headers: &HeaderMap, peer: Option<IpAddr>, trusted: &[IpNet]) -> Option<IpAddr> {
+    let is_trusted = |ip: &IpAddr| trusted.iter().any(|net| net.contains(ip));
+    if peer.is_some_and(|ip| !is_trusted(&ip)) {
+        return peer;
+    }
+
+    let chain = forwarded_chain(headers);
+    let mut client = peer;
+    for hop in chain.iter().rev() {
+        // Alamat yang tidak bisa dibaca (misalnya `unknown`) mengakhiri rantai
+        let Some(ip) = parse_hop(hop) else {
+            break;
+        };
+        client = Some(ip);
+        if !is_trusted(&ip) {
+            break;
+        }
+    }
+    client
+}
+
+/// Alamat dari header `Forwarded` (RFC 7239), atau `X-Forwarded-For` jika tidak ada
+fn forwarded_chain(headers: &HeaderMap) -> Vec<String> {
+    let forwarded: Vec<String> = headers
+        .get_all(FORWARDED)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .filter_map(|element| {
+            element.split(';').find_map(|pair| {
+                let (key, value) = pair.split_once('=')?;
+                key.trim()
+                    .eq_ignore_ascii_case("for")
+                    .then(|| value.trim().trim_matches('"').to_string())
+            })
+        })
+        .collect();
+    if !forwarded.is_empty() {
+        return forwarded;
+    }
+
+    headers
+        .get_all("X-Forwarded-For")
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(|hop| hop.trim().to_string())
+        .filter(|hop| !hop.is_empty())
+        .collect()
+}
+
+/// Satu hop: `1.2.3.4`, `1.2.3.4:80`, `2001:db8::1` atau `[2001:db8::1]:80`
+fn parse_hop(hop: &str) -> Option<IpAddr> {
+    if let Ok(ip) = hop.parse::<IpAddr>() {
+        return Some(ip);
+    }
+    if let Ok(addr) = hop.parse::<SocketAddr>() {
+        return Some(addr.ip());
+    }
+    hop.strip_prefix('[')
+        .and_then(|rest| rest.split(']').next())
+        .and_then(|ip| ip.parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn headers(name: &'static str, value: &'static str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(name, HeaderValue::from_static(value));
+        headers
+    }
+
+    fn ip(value: &str) -> Option<IpAddr> {
+        Some(value.parse().unwrap())
+    }
+
+    fn trusted() -> Vec<IpNet> {
+        vec!["10.0.0.0/8".parse().unwrap()]
+    }
+
+    #[test]
+    fn untrusted_peer_ignores_spoofed_forwarded_for() {
+        let spoofed = headers("x-forwarded-for", "1.2.3.4");
+        assert_eq!(client_ip(&spoofed, ip("203.0.113.9"), &trusted()), ip("203.0.113.9"));
+    }
+
+    #[test]
+    fn trusted_chain_is_walked_from_the_right() {
+        // Klien bisa memalsukan hop paling kiri; hanya hop yang ditambahkan proxy tepercaya dipakai
+        let chain = headers("x-forwarded-for", "6.6.6.6, 198.51.100.7, 10.0.0.2");
+        assert_eq!(client_ip(&chain, ip("10.0.0.1"), &trusted()), ip("198.51.100.7"));
+    }
+
+    #[test]
+    fn unknown_hop_stops_the_chain() {
+        let chain = headers("forwarded", "for=198.51.100.7, for=unknown");
+        assert_eq!(client_ip(&chain, ip("10.0.0.1"), &trusted()), ip("10.0.0.1"));
+    }
+
+    #[test]
+    fn bracketed_ipv6_with_port_is_parsed() {
+        let chain = headers("forwarded", "for=\"[2001:db8::7]:4711\";proto=https");
+        assert_eq!(client_ip(&chain, ip("10.0.0.1"), &trusted()), ip("2001:db8::7"));
+        assert_eq!(parse_hop("198.51.100.7:80"), ip("198.51.100.7"));
+    }
+}
@@ -0,0 +1,29 @@
+use axum::http::{header, Extensions, HeaderMap, StatusCode, Version};
+use tower_http::compression::predicate::{NotForContentType, Predicate, SizeAbove};
+use tower_http::compression::CompressionLayer;
+
+/// Respons yang lebih kecil dari ini tidak sebanding dengan biaya kompresi
+const MIN_COMPRESS_BYTES: u16 = 1024;
+
+/// Kompresi gzip atau brotli sesuai `Accept-Encoding` untuk respons JSON/teks.
+/// Respons yang di-stream (ukuran tidak diketahui) selalu dikompresi, kecuali SSE
+/// yang harus sampai ke klien per event.
+pub fn compression_layer() -> CompressionLayer<impl Predicate> {
+    let predicate = SizeAbove::new(MIN_COMPRESS_BYTES)
+        .and(NotForContentType::SSE)
+        .and(is_json_or_text);
+
+    CompressionLayer::new()
+        .gzip(true)
+        .br(true)
+        .compress_when(predicate)
+}
+
+/// Hanya JSON dan teks; PDF dan gambar sudah terkompresi
+fn is_json_or_text(_status: StatusCode, _version: Version, headers: &HeaderMap, _extensions: &Extensions) -> bool {
+    let content_type = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+    content_type.starts_with("application/json") || content_type.starts_with("text/")
+}
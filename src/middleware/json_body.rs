@@ -0,0 +1,146 @@
+use axum::{
+    async_trait,
+    body::Bytes,
+    extract::{FromRequest, Request},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+};
+use serde::{de::DeserializeOwned, Serialize};
+use crate::errors::app_error::{AppError, FieldError};
+
+/// Pengganti `axum::Json`: body yang rusak atau tidak cocok dengan tipe tujuan menjadi
+/// `AppError::ValidationError` (body error JSON biasa, dengan field yang bermasalah),
+/// bukan teks polos bawaan Axum. Sebagai respons sama persis dengan `axum::Json`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Json<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequest<S> for Json<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request(request: Request, state: &S) -> Result<Self, Self::Rejection> {
+        if !has_json_content_type(request.headers()) {
+            return Err(AppError::ValidationError {
+                message: "Expected request with Content-Type: application/json".to_string(),
+                fields: Vec::new(),
+            });
+        }
+
+        let bytes = Bytes::from_request(request, state).await.map_err(|rejection| {
+            if rejection.status() == StatusCode::PAYLOAD_TOO_LARGE {
+                AppError::PayloadTooLarge("Request body is too large".to_string())
+            } else {
+                AppError::BadRequest(rejection.body_text())
+            }
+        })?;
+        parse_json(&bytes).map(Json)
+    }
+}
+
+impl<T: Serialize> IntoResponse for Json<T> {
+    fn into_response(self) -> Response {
+        axum::Json(self.0).into_response()
+    }
+}
+
+fn has_json_content_type(headers: &HeaderMap) -> bool {
+    headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.split(';').next().unwrap_or_default().trim().to_ascii_lowercase())
+        .is_some_and(|mime| mime == "application/json" || (mime.starts_with("application/") && mime.ends_with("+json")))
+}
+
+/// Deserialisasi body JSON dengan lokasi error (`details.sleep_hours`, `items[0].name`)
+pub fn parse_json<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, AppError> {
+    let mut deserializer = serde_json::Deserializer::from_slice(bytes);
+    let value = serde_path_to_error::deserialize(&mut deserializer).map_err(|e| {
+        let path = e.path().to_string();
+        validation_error(e.into_inner(), &path)
+    })?;
+    deserializer.end().map_err(|e| validation_error(e, "."))?;
+    Ok(value)
+}
+
+fn validation_error(error: serde_json::Error, path: &str) -> AppError {
+    if error.is_syntax() || error.is_eof() {
+        return AppError::ValidationError {
+            message: "Malformed JSON body".to_string(),
+            fields: vec![FieldError { field: None, message: error.to_string() }],
+        };
+    }
+
+    // Serde melaporkan field yang hilang pada objek induknya, jadi nama field diambil dari pesan
+    let message = strip_position(&error.to_string());
+    let missing = message
+        .strip_prefix("missing field `")
+        .and_then(|rest| rest.split('`').next());
+    let field = match (path, missing) {
+        (".", Some(name)) => name.to_string(),
+        (parent, Some(name)) => format!("{}.{}", parent, name),
+        (path, None) => path.to_string(),
+    };
+
+    AppError::ValidationError {
+        message: "Invalid request body".to_string(),
+        fields: vec![FieldError { field: Some(field), message }],
+    }
+}
+
+/// Pesan serde diakhiri " at line X column Y", yang tidak berguna bila field-nya sudah disebut
+fn strip_position(message: &str) -> String {
+    match message.rfind(" at line ") {
+        Some(index) => message[..index].to_string(),
+        None => message.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize)]
+    struct Details {
+        sleep_hours: f32,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Body {
+        mood: String,
+        details: Option<Details>,
+    }
+
+    fn field_error(body: &str) -> (String, Option<String>, String) {
+        match parse_json::<Body>(body.as_bytes()) {
+            Err(AppError::ValidationError { message, mut fields }) => {
+                let field = fields.remove(0);
+                (message, field.field, field.message)
+            }
+            other => panic!("expected validation error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn reports_the_failing_field() {
+        let body: Body = parse_json(br#"{"mood":"happy","details":{"sleep_hours":7.5}}"#).unwrap();
+        assert_eq!((body.mood.as_str(), body.details.map(|details| details.sleep_hours)), ("happy", Some(7.5)));
+
+        assert_eq!(
+            field_error(r#"{"details":null}"#),
+            ("Invalid request body".to_string(), Some("mood".to_string()), "missing field `mood`".to_string())
+        );
+        assert_eq!(
+            field_error(r#"{"mood":"happy","details":{"sleep_hours":"eight"}}"#).1.as_deref(),
+            Some("details.sleep_hours")
+        );
+        assert_eq!(field_error(r#"{"mood":"happy","details":{}}"#).1.as_deref(), Some("details.sleep_hours"));
+
+        let (message, field, _) = field_error(r#"{"mood":"#);
+        assert_eq!((message.as_str(), field), ("Malformed JSON body", None));
+    }
+}
@@ -0,0 +1,51 @@
+use axum::{
+    extract::{Request, State},
+    http::{header, HeaderMap, HeaderValue, Method},
+    middleware::Next,
+    response::Response,
+};
+use crate::db::user_query;
+use crate::middleware::auth_middleware::request_token;
+use crate::i18n::{with_locale, Locale};
+use crate::models::user::UserSettings;
+use crate::state::AppState;
+use crate::utils::jwt::validate_token;
+
+/// Tentukan bahasa pesan untuk setiap request.
+/// Urutan: pengaturan bahasa pengguna (jika login), lalu Accept-Language, lalu `en`.
+pub async fn resolve_locale(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let locale = user_locale(&state, request.headers())
+        .or_else(|| {
+            request
+                .headers()
+                .get(header::ACCEPT_LANGUAGE)
+                .and_then(|value| value.to_str().ok())
+                .and_then(Locale::from_accept_language)
+        })
+        .unwrap_or_default();
+
+    let mut response = with_locale(locale, next.run(request)).await;
+    response
+        .headers_mut()
+        .insert(header::CONTENT_LANGUAGE, HeaderValue::from_static(locale.as_str()));
+    response
+}
+
+/// Bahasa dari settings pengguna. Token hanya didekode di sini; validasi lengkap
+/// (blacklist) tetap dilakukan oleh extractor `AuthenticatedUser`.
+fn user_locale(state: &AppState, headers: &HeaderMap) -> Option<Locale> {
+    let token = request_token(headers, &Method::GET, state.config).ok()?;
+    let user_id: i32 = validate_token(token).ok()?.sub.parse().ok()?;
+
+    let mut conn = state.pool.conn_read().ok()?;
+    let user = user_query::find_user_by_id(&mut conn, user_id).ok()?;
+
+    UserSettings::parse(user.settings.as_deref())
+        .language
+        .as_deref()
+        .and_then(Locale::parse)
+}
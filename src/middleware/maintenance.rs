@@ -0,0 +1,63 @@
+use axum::{
+    extract::{Request, State},
+    http::Method,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use crate::errors::app_error::AppError;
+use crate::state::AppState;
+
+/// Endpoint untuk mematikan maintenance; tetap menerima request tulis agar admin tidak terkunci
+const MAINTENANCE_TOGGLE_PATH: &str = "/admin/maintenance";
+
+/// Endpoint GET yang menulis ke database (check-in dari link, konfirmasi email dari link,
+/// callback OAuth, dan token kalender yang dibuat saat pertama diminta)
+const WRITING_GET_PATHS: &[&str] = &[
+    "/checkin",
+    "/user/email/confirm",
+    "/auth/google/callback",
+    "/calendar/token",
+];
+
+/// `true` jika request bisa mengubah data
+fn writes_data(method: &Method, path: &str) -> bool {
+    if !matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS) {
+        return true;
+    }
+    *method == Method::GET && WRITING_GET_PATHS.iter().any(|writing| path.ends_with(writing))
+}
+
+/// Saat mode maintenance aktif, tolak request yang bisa mengubah data dengan 503 dan
+/// `Retry-After`. GET, HEAD dan OPTIONS tetap dilayani, kecuali GET di `WRITING_GET_PATHS`.
+pub async fn reject_writes(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if !writes_data(request.method(), request.uri().path())
+        || !state.maintenance.is_enabled()
+        || request.uri().path().ends_with(MAINTENANCE_TOGGLE_PATH)
+    {
+        return next.run(request).await;
+    }
+
+    AppError::ServiceUnavailable {
+        message: "The service is in read-only maintenance mode, please try again later".to_string(),
+        retry_after_secs: state.maintenance.retry_after_secs(),
+    }
+    .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gets_that_write_are_treated_as_writes() {
+        assert!(!writes_data(&Method::GET, "/moods"));
+        assert!(!writes_data(&Method::OPTIONS, "/checkin"));
+        assert!(writes_data(&Method::GET, "/checkin"));
+        assert!(writes_data(&Method::GET, "/api/user/email/confirm"));
+        assert!(writes_data(&Method::POST, "/moods"));
+    }
+}
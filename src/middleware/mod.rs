@@ -1 +1,15 @@
-pub mod auth_middleware;
\ No newline at end of file
+pub mod auth_middleware;
+pub mod timezone_middleware;
+pub mod admin_middleware;
+pub mod client_info;
+pub mod locale_middleware;
+pub mod body_limit;
+pub mod compression;
+pub mod api_version;
+pub mod captcha;
+pub mod request_log;
+pub mod maintenance;
+pub mod request_id;
+pub mod json_body;
+pub mod panic;
+pub mod request_limits;
\ No newline at end of file
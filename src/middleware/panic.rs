@@ -0,0 +1,67 @@
+use std::any::Any;
+use std::backtrace::Backtrace;
+use axum::response::{IntoResponse, Response};
+use crate::errors::app_error::AppError;
+use crate::middleware::request_id;
+
+/// Pasang panic hook yang mencatat lokasi dan backtrace beserta request id. Payload yang
+/// diterima `CatchPanicLayer` tidak membawa backtrace, jadi harus diambil di sini.
+pub fn install_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        if !log::log_enabled!(log::Level::Error) {
+            return default_hook(info);
+        }
+        let location = info
+            .location()
+            .map(|location| location.to_string())
+            .unwrap_or_else(|| "unknown location".to_string());
+        log::error!(
+            "[{}] Panicked at {}: {}\n{}",
+            request_id::current().unwrap_or_else(|| "-".to_string()),
+            location,
+            panic_message(info.payload()),
+            Backtrace::force_capture()
+        );
+    }));
+}
+
+/// Responder untuk `CatchPanicLayer`: panic di handler menjadi 500 dengan format error standar,
+/// bukan koneksi yang diputus tanpa respons
+pub fn panic_response(payload: Box<dyn Any + Send + 'static>) -> Response {
+    AppError::InternalServerError(format!("Handler panicked: {}", panic_message(payload.as_ref()))).into_response()
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    payload
+        .downcast_ref::<String>()
+        .map(String::as_str)
+        .or_else(|| payload.downcast_ref::<&str>().copied())
+        .unwrap_or("non-string panic payload")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::{Request, StatusCode}, routing::get, Router};
+    use tower::ServiceExt;
+    use tower_http::catch_panic::CatchPanicLayer;
+
+    #[tokio::test]
+    async fn handler_panic_becomes_json_500() {
+        let app = Router::new()
+            .route("/boom", get(|| async { if true { panic!("boom") } }))
+            .layer(CatchPanicLayer::custom(panic_response));
+
+        let response = app
+            .oneshot(Request::get("/boom").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["error"], "Internal server error");
+        assert_eq!(json["code"], "internal_error");
+    }
+}
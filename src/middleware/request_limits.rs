@@ -0,0 +1,102 @@
+use std::sync::Arc;
+use std::time::Duration;
+use axum::{
+    extract::{Request, State},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use tokio::sync::Semaphore;
+use crate::config::app_config::AppConfig;
+use crate::errors::app_error::AppError;
+
+/// Saran `Retry-After` (detik) untuk request yang ditolak karena server penuh
+const BUSY_RETRY_AFTER_SECS: u64 = 1;
+
+/// Batas waktu dan jumlah request bersamaan. Handler memakai koneksi Diesel yang sinkron,
+/// jadi tanpa batas ini request lambat bisa menghabiskan pool dan membuat semua request menunggu.
+pub struct RequestLimits {
+    permits: Option<Arc<Semaphore>>,
+    timeout: Option<Duration>,
+}
+
+impl RequestLimits {
+    /// `0` pada salah satu argumen berarti batas tersebut nonaktif
+    pub fn new(max_concurrent: usize, timeout: Duration) -> Self {
+        RequestLimits {
+            permits: (max_concurrent > 0).then(|| Arc::new(Semaphore::new(max_concurrent))),
+            timeout: (!timeout.is_zero()).then_some(timeout),
+        }
+    }
+
+    pub fn from_config(config: &AppConfig) -> Self {
+        RequestLimits::new(
+            config.max_concurrent_requests,
+            Duration::from_secs(config.request_timeout_secs),
+        )
+    }
+}
+
+/// Tolak request dengan 503 jika slot penuh (tanpa antre), dan 408 jika handler melewati batas waktu.
+/// Slot dilepas begitu respons dibuat, jadi stream seperti SSE tidak menahannya.
+pub async fn enforce(
+    State(limits): State<Arc<RequestLimits>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let _permit = match limits.permits.clone().map(Semaphore::try_acquire_owned) {
+        Some(Err(_)) => {
+            return AppError::ServiceUnavailable {
+                message: "The server is busy, please try again later".to_string(),
+                retry_after_secs: BUSY_RETRY_AFTER_SECS,
+            }
+            .into_response();
+        }
+        Some(Ok(permit)) => Some(permit),
+        None => None,
+    };
+
+    let Some(timeout) = limits.timeout else {
+        return next.run(request).await;
+    };
+    match tokio::time::timeout(timeout, next.run(request)).await {
+        Ok(response) => response,
+        Err(_) => AppError::RequestTimeout("The request took too long to process".to_string()).into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::StatusCode, routing::get, Router};
+    use tower::ServiceExt;
+
+    fn app(limits: Arc<RequestLimits>) -> Router {
+        Router::new()
+            .route("/fast", get(|| async { "ok" }))
+            .route("/slow", get(|| async {
+                tokio::time::sleep(Duration::from_millis(200)).await;
+                "ok"
+            }))
+            .layer(axum::middleware::from_fn_with_state(limits, enforce))
+    }
+
+    async fn status(limits: &Arc<RequestLimits>, path: &str) -> StatusCode {
+        let request = Request::get(path).body(Body::empty()).unwrap();
+        app(limits.clone()).oneshot(request).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn sheds_load_and_times_out_slow_handlers() {
+        let limits = Arc::new(RequestLimits::new(1, Duration::from_millis(50)));
+        assert_eq!(status(&limits, "/fast").await, StatusCode::OK);
+        assert_eq!(status(&limits, "/slow").await, StatusCode::REQUEST_TIMEOUT);
+
+        let held = limits.permits.clone().unwrap().try_acquire_owned().unwrap();
+        assert_eq!(status(&limits, "/fast").await, StatusCode::SERVICE_UNAVAILABLE);
+        drop(held);
+        assert_eq!(status(&limits, "/fast").await, StatusCode::OK);
+
+        let unlimited = Arc::new(RequestLimits::new(0, Duration::ZERO));
+        assert_eq!(status(&unlimited, "/slow").await, StatusCode::OK);
+    }
+}
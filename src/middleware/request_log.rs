@@ -0,0 +1,30 @@
+use std::time::Instant;
+use axum::{
+    extract::Request,
+    middleware::Next,
+    response::Response,
+};
+use crate::middleware::client_info::ClientInfo;
+use crate::middleware::request_id;
+
+/// Log satu baris per request dengan IP klien asli (lihat `client_info::client_ip`) dan request id
+pub async fn log_request(request: Request, next: Next) -> Response {
+    let (parts, body) = request.into_parts();
+    let client = ClientInfo::from_parts(&parts);
+    let method = parts.method.clone();
+    let path = parts.uri.path().to_string();
+    let started = Instant::now();
+
+    let response = next.run(Request::from_parts(parts, body)).await;
+
+    log::info!(
+        "[{}] {} {} {} {} {}ms",
+        request_id::current().as_deref().unwrap_or("-"),
+        client.ip_address.as_deref().unwrap_or("-"),
+        method,
+        path,
+        response.status().as_u16(),
+        started.elapsed().as_millis()
+    );
+    response
+}
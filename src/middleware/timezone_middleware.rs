@@ -0,0 +1,61 @@
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::request::Parts,
+};
+use chrono_tz::Tz;
+use crate::db::user_query;
+use crate::errors::app_error::AppError;
+use crate::state::AppState;
+use crate::middleware::auth_middleware::{AnyScope, AuthenticatedUser};
+use crate::models::user::UserSettings;
+use crate::utils::timezone::parse_timezone;
+
+pub const TIMEZONE_HEADER: &str = "x-timezone";
+
+/// Zona waktu efektif pengguna untuk menghitung "hari ini".
+/// Urutan: header X-Timezone, lalu settings pengguna, lalu UTC.
+#[derive(Clone, Copy, Debug)]
+pub struct UserTimezone(pub Tz);
+
+impl UserTimezone {
+    pub fn tz(&self) -> Tz {
+        self.0
+    }
+}
+
+#[async_trait]
+impl FromRequestParts<AppState> for UserTimezone
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState
+    ) -> Result<Self, Self::Rejection> {
+        if let Some(header) = parts.headers.get(TIMEZONE_HEADER) {
+            let name = header.to_str()
+                .map_err(|_| AppError::BadRequest("Invalid X-Timezone header".to_string()))?;
+            let tz = parse_timezone(name)
+                .ok_or_else(|| AppError::BadRequest(format!("Unknown timezone: {}", name)))?;
+            return Ok(UserTimezone(tz));
+        }
+
+        let user = AuthenticatedUser::<AnyScope>::from_request_parts(parts, state).await?;
+        let user_id: i32 = user
+            .user_id()
+            .parse()
+            .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+
+        let mut conn = state.pool.conn_write()?;
+
+        let user = user_query::find_user_by_id(&mut conn, user_id)?;
+        let tz = UserSettings::parse(user.settings.as_deref())
+            .timezone
+            .as_deref()
+            .and_then(parse_timezone)
+            .unwrap_or(Tz::UTC);
+
+        Ok(UserTimezone(tz))
+    }
+}
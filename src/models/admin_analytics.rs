@@ -0,0 +1,36 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Deserialize, IntoParams)]
+pub struct AnalyticsQuery {
+    /// Jumlah minggu terakhir yang dihitung, termasuk minggu berjalan (default 12, maksimum 104)
+    pub weeks: Option<i64>,
+}
+
+/// Aktivitas satu minggu (Senin-Minggu, UTC); aktivitas = mencatat mood atau menulis jurnal
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WeeklyActivity {
+    #[serde(with = "crate::utils::api_dates")]
+    #[schema(value_type = String, format = Date, example = "2025-07-21")]
+    pub week_start: NaiveDate,
+    pub active_users: i64,
+    /// Jumlah mood dan jurnal yang dibuat
+    pub entries: i64,
+    pub avg_entries_per_active_user: f64,
+}
+
+/// Retensi pengguna yang mendaftar pada minggu yang sama. `dN` adalah persentase pengguna
+/// yang aktif tepat N hari setelah mendaftar, dihitung dari pengguna yang sudah melewati
+/// hari ke-N; `null` jika belum ada.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RetentionCohort {
+    #[serde(with = "crate::utils::api_dates")]
+    #[schema(value_type = String, format = Date, example = "2025-07-21")]
+    pub signup_week: NaiveDate,
+    pub users: i64,
+    #[schema(example = 42.5)]
+    pub d1: Option<f64>,
+    pub d7: Option<f64>,
+    pub d30: Option<f64>,
+}
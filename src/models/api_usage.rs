@@ -0,0 +1,47 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Insertable, Debug, Clone, PartialEq, Eq)]
+#[diesel(table_name = crate::schema::api_usage_daily)]
+pub struct NewApiUsageDay {
+    pub user_id: i32,
+    pub day: NaiveDate,
+    pub request_count: i64,
+}
+
+#[derive(Deserialize, IntoParams)]
+pub struct UsageQuery {
+    /// Jumlah hari terakhir yang ditampilkan, termasuk hari ini (default 30, maksimum API_USAGE_RETENTION_DAYS)
+    pub days: Option<i64>,
+}
+
+/// Jumlah request API pada satu hari (UTC)
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DailyUsage {
+    #[serde(with = "crate::utils::api_dates")]
+    #[schema(value_type = String, format = Date, example = "2025-07-21")]
+    pub date: NaiveDate,
+    pub requests: i64,
+}
+
+/// Pemakaian kuota harian satu operasi hari ini
+#[derive(Debug, Serialize, ToSchema)]
+pub struct QuotaUsage {
+    /// `export`, `report_pdf` atau `import`
+    #[schema(example = "export")]
+    pub operation: String,
+    pub used: i32,
+    /// `null` berarti tanpa batas
+    pub limit: Option<i32>,
+    pub reset_at: NaiveDateTime,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ApiUsageResponse {
+    /// Satu entri per hari, dari yang terlama sampai hari ini; hari tanpa request bernilai 0
+    pub days: Vec<DailyUsage>,
+    pub total_requests: i64,
+    pub quotas: Vec<QuotaUsage>,
+}
@@ -0,0 +1,118 @@
+use diesel::prelude::*;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Status janji temu; janji yang sudah lewat tetap `scheduled`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AppointmentStatus {
+    Scheduled,
+    Cancelled,
+}
+
+impl AppointmentStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AppointmentStatus::Scheduled => "scheduled",
+            AppointmentStatus::Cancelled => "cancelled",
+        }
+    }
+}
+
+/// Pengingat yang dikirim sebelum janji temu dimulai
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AppointmentReminder {
+    DayBefore,
+    HourBefore,
+}
+
+impl AppointmentReminder {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AppointmentReminder::DayBefore => "24h",
+            AppointmentReminder::HourBefore => "1h",
+        }
+    }
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = crate::schema::appointments)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Appointment {
+    pub id: i32,
+    pub user_id: i32,
+    pub psychologist_id: i32,
+    pub request_id: Option<i32>,
+    pub starts_at: NaiveDateTime,
+    pub ends_at: NaiveDateTime,
+    pub status: String,
+    pub meeting_link: Option<String>,
+    pub reminder_24h_sent_at: Option<NaiveDateTime>,
+    pub reminder_1h_sent_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl Appointment {
+    pub fn is_scheduled(&self) -> bool {
+        self.status == AppointmentStatus::Scheduled.as_str()
+    }
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::appointments)]
+pub struct NewAppointment<'a> {
+    pub user_id: i32,
+    pub psychologist_id: i32,
+    pub request_id: Option<i32>,
+    pub starts_at: NaiveDateTime,
+    pub ends_at: NaiveDateTime,
+    pub meeting_link: Option<&'a str>,
+}
+
+/// Konfirmasi pengajuan menjadi janji temu. Tanpa waktu, jadwal yang dipesan pengguna dipakai.
+#[derive(Deserialize, ToSchema)]
+pub struct ConfirmAppointmentRequest {
+    /// Waktu mulai (UTC); wajib jika pengajuan tidak memesan jadwal
+    pub starts_at: Option<NaiveDateTime>,
+    pub ends_at: Option<NaiveDateTime>,
+    #[schema(example = "https://meet.example.com/abc-defg-hij")]
+    pub meeting_link: Option<String>,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct RescheduleAppointmentRequest {
+    /// Jadwal kosong lain milik psikolog yang sama
+    pub slot_id: i32,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AppointmentResponse {
+    pub id: i32,
+    pub psychologist_id: i32,
+    pub psychologist_name: String,
+    pub starts_at: NaiveDateTime,
+    pub ends_at: NaiveDateTime,
+    /// `scheduled` atau `cancelled`
+    #[schema(example = "scheduled")]
+    pub status: String,
+    pub meeting_link: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl AppointmentResponse {
+    pub fn new(appointment: Appointment, psychologist_name: String) -> Self {
+        AppointmentResponse {
+            id: appointment.id,
+            psychologist_id: appointment.psychologist_id,
+            psychologist_name,
+            starts_at: appointment.starts_at,
+            ends_at: appointment.ends_at,
+            status: appointment.status,
+            meeting_link: appointment.meeting_link,
+            created_at: appointment.created_at,
+            updated_at: appointment.updated_at,
+        }
+    }
+}
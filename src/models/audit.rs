@@ -0,0 +1,119 @@
+use diesel::prelude::*;
+use chrono::{NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+/// Aksi sensitif yang dicatat di audit log
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditAction {
+    PasswordChange,
+    PasswordReset,
+    EmailChange,
+    DataExport,
+    AccountDeletion,
+    AdminAction,
+    ScopedTokenIssued,
+    ConsentChange,
+    Impersonation,
+    ImpersonatedRequest,
+    RetentionPurge,
+}
+
+impl AuditAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditAction::PasswordChange => "password_change",
+            AuditAction::PasswordReset => "password_reset",
+            AuditAction::EmailChange => "email_change",
+            AuditAction::DataExport => "data_export",
+            AuditAction::AccountDeletion => "account_deletion",
+            AuditAction::AdminAction => "admin_action",
+            AuditAction::ScopedTokenIssued => "scoped_token_issued",
+            AuditAction::ConsentChange => "consent_change",
+            AuditAction::Impersonation => "impersonation",
+            AuditAction::ImpersonatedRequest => "impersonated_request",
+            AuditAction::RetentionPurge => "retention_purge",
+        }
+    }
+}
+
+#[derive(Queryable, Selectable, Debug)]
+#[diesel(table_name = crate::schema::audit_logs)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct AuditLog {
+    pub id: i32,
+    pub actor_user_id: Option<i32>,
+    pub action: String,
+    pub target_user_id: Option<i32>,
+    pub details: Option<String>,
+    pub ip_address: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::audit_logs)]
+pub struct NewAuditLog {
+    pub actor_user_id: Option<i32>,
+    pub action: String,
+    pub target_user_id: Option<i32>,
+    pub details: Option<String>,
+    pub ip_address: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+impl NewAuditLog {
+    /// Entri audit untuk aksi yang dilakukan `actor_user_id` terhadap `target_user_id`
+    pub fn new(
+        action: AuditAction,
+        actor_user_id: Option<i32>,
+        target_user_id: Option<i32>,
+        ip_address: Option<&str>,
+    ) -> Self {
+        NewAuditLog {
+            actor_user_id,
+            action: action.as_str().to_string(),
+            target_user_id,
+            details: None,
+            ip_address: ip_address.map(str::to_string),
+            created_at: Utc::now().naive_utc(),
+        }
+    }
+
+    pub fn with_details(mut self, details: impl Into<String>) -> Self {
+        self.details = Some(details.into());
+        self
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct AuditLogResponse {
+    pub id: i32,
+    pub actor_user_id: Option<i32>,
+    pub action: String,
+    pub target_user_id: Option<i32>,
+    pub details: Option<String>,
+    pub ip_address: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+impl From<AuditLog> for AuditLogResponse {
+    fn from(log: AuditLog) -> Self {
+        AuditLogResponse {
+            id: log.id,
+            actor_user_id: log.actor_user_id,
+            action: log.action,
+            target_user_id: log.target_user_id,
+            details: log.details,
+            ip_address: log.ip_address,
+            created_at: log.created_at,
+        }
+    }
+}
+
+#[derive(Deserialize, IntoParams)]
+pub struct AuditLogQuery {
+    /// Filter entri yang melibatkan pengguna ini (sebagai pelaku atau target)
+    pub user_id: Option<i32>,
+    /// Jumlah entri maksimum (default 50, maksimum 500)
+    pub limit: Option<i64>,
+}
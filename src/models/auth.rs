@@ -1,7 +1,9 @@
 use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use chrono::NaiveDateTime;
 use crate::models::user::UserResponse;
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct RegisterRequest {
     pub username: String,
     pub email: String,
@@ -10,25 +12,122 @@ pub struct RegisterRequest {
     pub password: String,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct LoginRequest {
+    /// Username atau email, dideteksi otomatis dan tidak membedakan huruf besar/kecil
+    #[schema(example = "budi_santoso")]
+    pub identifier: Option<String>,
+    /// Nama field lama, tetap diterima untuk klien yang belum mengirim `identifier`
+    pub email: Option<String>,
+    pub password: String,
+}
+
+impl LoginRequest {
+    pub fn identifier(&self) -> Option<&str> {
+        self.identifier
+            .as_deref()
+            .or(self.email.as_deref())
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+    }
+}
+
+/// Masuk sebagai tamu. Perangkat baru cukup mengirim `device_id`; perangkat yang
+/// sudah punya akun tamu wajib menyertakan `device_secret` dari respons pertama.
+#[derive(Deserialize, ToSchema)]
+pub struct GuestLoginRequest {
+    /// ID acak yang dibuat aplikasi dan disimpan di perangkat
+    pub device_id: String,
+    pub device_secret: Option<String>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct GuestLoginResponse {
+    pub token: String,
+    pub user: UserResponse,
+    /// Hanya dikembalikan saat akun tamu baru dibuat; simpan di perangkat
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub device_secret: Option<String>,
+}
+
+/// Ubah akun tamu menjadi akun penuh; semua data tetap milik akun yang sama
+#[derive(Deserialize, ToSchema)]
+pub struct UpgradeAccountRequest {
     pub email: String,
     pub password: String,
+    /// Username baru; jika kosong username tamu tetap dipakai
+    pub username: Option<String>,
+}
+
+#[derive(Deserialize, IntoParams)]
+pub struct GoogleAuthUrlRequest {
+    /// Path frontend (mis. `/journal/5`) atau URL absolut dengan origin yang diizinkan, tujuan setelah login
+    pub redirect_to: Option<String>,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
 pub struct GoogleCallbackRequest {
     pub code: String,
     pub state: Option<String>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct LoginResponse {
     pub token: String,
     pub user: UserResponse,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
+pub struct CsrfTokenResponse {
+    /// Kirim di header X-CSRF-Token pada request tulis yang memakai cookie sesi
+    pub csrf_token: String,
+}
+
+/// Kode sekali pakai dari redirect callback Google, ditukar dengan JWT
+#[derive(Deserialize, ToSchema)]
+pub struct ExchangeCodeRequest {
+    pub code: String,
+}
+
+#[derive(Serialize, ToSchema)]
 pub struct GoogleAuthUrlResponse {
     pub auth_url: String,
-}
\ No newline at end of file
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct TokenCleanupResponse {
+    pub deleted: usize,
+}
+
+/// Permintaan token terbatas untuk share link atau integrasi
+#[derive(Deserialize, ToSchema)]
+pub struct ScopedTokenRequest {
+    /// Scope yang diberikan, misalnya `export:read` atau `share:read`
+    #[schema(example = json!(["share:read"]))]
+    pub scopes: Vec<String>,
+    /// Masa berlaku dalam jam (1-168), default 24
+    pub expires_in_hours: Option<i64>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ScopedTokenResponse {
+    pub token: String,
+    pub scopes: Vec<String>,
+    pub expires_at: NaiveDateTime,
+}
+
+/// Permintaan impersonasi oleh admin; alasan wajib dan dicatat di audit log
+#[derive(Deserialize, ToSchema)]
+pub struct ImpersonateRequest {
+    #[schema(example = "Reproduksi laporan grafik mood kosong, tiket #123")]
+    pub reason: String,
+}
+
+/// Token hanya-baca berumur pendek untuk melihat aplikasi sebagai pengguna lain
+#[derive(Serialize, ToSchema)]
+pub struct ImpersonationResponse {
+    pub token: String,
+    pub user_id: i32,
+    pub impersonator_id: i32,
+    pub expires_at: NaiveDateTime,
+}
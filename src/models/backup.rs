@@ -0,0 +1,38 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct CreateBackupRequest {
+    /// Tabel yang di-backup; kosong berarti semua tabel
+    #[schema(example = json!(["users", "moods", "journals"]))]
+    pub tables: Option<Vec<String>>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct BackupResponse {
+    /// Nama file backup di storage, dipakai saat restore
+    #[schema(example = "mindmate-backup-20261016T030000000Z.json.gz")]
+    pub name: String,
+    pub tables: Vec<String>,
+    pub rows: i64,
+    pub size_bytes: usize,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct RestoreBackupRequest {
+    /// Nama file backup
+    pub name: String,
+    /// Harus sama persis dengan `name`; restore menimpa data yang ada
+    pub confirm: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct RestoreBackupResponse {
+    pub name: String,
+    pub tables: Vec<String>,
+    pub rows: usize,
+    /// Backup otomatis dari data sebelum restore, untuk membatalkan restore
+    pub safety_backup: String,
+}
@@ -0,0 +1,30 @@
+use diesel::prelude::*;
+use chrono::NaiveDateTime;
+use serde::Serialize;
+use utoipa::ToSchema;
+
+#[derive(Queryable, Selectable, Debug)]
+#[diesel(table_name = crate::schema::calendar_feed_tokens)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct CalendarFeedToken {
+    pub id: i32,
+    pub user_id: i32,
+    pub token: String,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::calendar_feed_tokens)]
+pub struct NewCalendarFeedToken {
+    pub user_id: i32,
+    pub token: String,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct CalendarTokenResponse {
+    pub token: String,
+    /// URL feed iCalendar untuk dilanggan dari aplikasi kalender
+    pub feed_url: String,
+    pub created_at: NaiveDateTime,
+}
@@ -0,0 +1,11 @@
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+#[derive(Deserialize, IntoParams)]
+pub struct CheckinQuery {
+    /// Token bertanda tangan dari email pengingat
+    pub token: String,
+    /// Jenis mood yang dipilih
+    #[param(example = "happy")]
+    pub mood: String,
+}
@@ -0,0 +1,138 @@
+use diesel::prelude::*;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+/// Reaksi dukungan yang tersedia; balasan bebas sengaja belum didukung
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReactionKind {
+    Support,
+    Hug,
+    Relate,
+}
+
+impl ReactionKind {
+    pub const ALL: [ReactionKind; 3] = [ReactionKind::Support, ReactionKind::Hug, ReactionKind::Relate];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReactionKind::Support => "support",
+            ReactionKind::Hug => "hug",
+            ReactionKind::Relate => "relate",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.as_str() == value)
+    }
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = crate::schema::community_groups)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct CommunityGroup {
+    pub id: i32,
+    pub slug: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub is_active: bool,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::community_groups)]
+pub struct NewCommunityGroup<'a> {
+    pub slug: &'a str,
+    pub name: &'a str,
+    pub description: Option<&'a str>,
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = crate::schema::community_posts)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct CommunityPost {
+    pub id: i32,
+    pub group_id: i32,
+    pub user_id: i32,
+    pub body: String,
+    pub held: bool,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::community_posts)]
+pub struct NewCommunityPost<'a> {
+    pub group_id: i32,
+    pub user_id: i32,
+    pub body: &'a str,
+    pub held: bool,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct CreateCommunityGroupRequest {
+    /// Huruf kecil, angka dan tanda hubung
+    #[schema(example = "anxiety-support")]
+    pub slug: String,
+    pub name: String,
+    pub description: Option<String>,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct CreateCommunityPostRequest {
+    pub body: String,
+}
+
+#[derive(Deserialize, IntoParams)]
+pub struct CommunityPostListQuery {
+    /// Ambil kiriman yang lebih lama dari id ini (halaman berikutnya)
+    pub before_id: Option<i32>,
+    /// Jumlah kiriman maksimum (default 20, maksimum 100)
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CommunityGroupResponse {
+    pub id: i32,
+    pub slug: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub member_count: i64,
+    /// Pemanggil sudah bergabung dengan grup ini
+    pub joined: bool,
+}
+
+impl CommunityGroupResponse {
+    pub fn new(group: CommunityGroup, member_count: i64, joined: bool) -> Self {
+        CommunityGroupResponse {
+            id: group.id,
+            slug: group.slug,
+            name: group.name,
+            description: group.description,
+            member_count,
+            joined,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReactionSummary {
+    /// `support`, `hug` atau `relate`
+    #[schema(example = "support")]
+    pub kind: String,
+    pub count: i64,
+    /// Pemanggil memberikan reaksi ini
+    pub reacted: bool,
+}
+
+/// Kiriman anonim; penulis tidak pernah ditampilkan, hanya `is_own` untuk pemanggil
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CommunityPostResponse {
+    pub id: i32,
+    pub group_id: i32,
+    pub body: String,
+    pub is_own: bool,
+    /// Ditahan moderasi; hanya terlihat oleh penulis sampai disetujui admin
+    pub held: bool,
+    pub reactions: Vec<ReactionSummary>,
+    pub created_at: NaiveDateTime,
+}
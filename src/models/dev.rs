@@ -0,0 +1,27 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Permintaan pembuatan riwayat buatan untuk pengguna yang login
+#[derive(Deserialize, ToSchema)]
+pub struct GenerateHistoryRequest {
+    /// Jumlah hari ke belakang mulai hari ini (1-730), default 90
+    #[schema(example = 90)]
+    pub days: Option<i64>,
+    /// Rata-rata jurnal per minggu (0-7), default 2
+    #[schema(example = 2.0)]
+    pub journals_per_week: Option<f64>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct GenerateHistoryResponse {
+    #[serde(with = "crate::utils::api_dates")]
+    #[schema(value_type = String, format = Date, example = "2025-04-24")]
+    pub start_date: NaiveDate,
+    #[serde(with = "crate::utils::api_dates")]
+    #[schema(value_type = String, format = Date, example = "2025-07-23")]
+    pub end_date: NaiveDate,
+    /// Tanggal yang sudah punya mood tidak ditimpa
+    pub moods_created: usize,
+    pub journals_created: usize,
+}
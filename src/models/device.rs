@@ -0,0 +1,106 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Layanan push yang menerbitkan token perangkat
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DevicePlatform {
+    /// Firebase Cloud Messaging (Android dan web)
+    Fcm,
+    /// Apple Push Notification service (iOS)
+    Apns,
+}
+
+impl DevicePlatform {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DevicePlatform::Fcm => "fcm",
+            DevicePlatform::Apns => "apns",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "fcm" => Some(DevicePlatform::Fcm),
+            "apns" => Some(DevicePlatform::Apns),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = crate::schema::devices)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Device {
+    pub id: i32,
+    pub user_id: i32,
+    pub platform: String,
+    pub push_token: String,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::devices)]
+pub struct NewDevice<'a> {
+    pub user_id: i32,
+    pub platform: &'a str,
+    pub push_token: &'a str,
+    pub updated_at: NaiveDateTime,
+}
+
+/// Token push tidak dikembalikan agar tidak bocor lewat log klien
+#[derive(Serialize, ToSchema)]
+pub struct DeviceResponse {
+    pub id: i32,
+    /// "fcm" atau "apns"
+    pub platform: String,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl From<Device> for DeviceResponse {
+    fn from(device: Device) -> Self {
+        DeviceResponse {
+            id: device.id,
+            platform: device.platform,
+            created_at: device.created_at,
+            updated_at: device.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct RegisterDeviceRequest {
+    /// "fcm" (Android/web) atau "apns" (iOS)
+    #[schema(example = "fcm")]
+    pub platform: String,
+    /// Registration token FCM atau device token APNs
+    pub token: String,
+}
+
+#[derive(Queryable, Selectable, Debug)]
+#[diesel(table_name = crate::schema::push_outbox)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct PushOutboxEntry {
+    pub id: i32,
+    pub device_id: i32,
+    pub category: String,
+    pub title: String,
+    pub body: String,
+    pub attempts: i32,
+    pub next_attempt_at: NaiveDateTime,
+    pub last_error: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::push_outbox)]
+pub struct NewPushOutboxEntry<'a> {
+    pub device_id: i32,
+    pub category: &'a str,
+    pub title: &'a str,
+    pub body: &'a str,
+    pub next_attempt_at: NaiveDateTime,
+}
@@ -0,0 +1,46 @@
+use diesel::prelude::*;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Queryable, Selectable, Debug)]
+#[diesel(table_name = crate::schema::email_change_requests)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct EmailChangeRequest {
+    pub id: i32,
+    pub user_id: i32,
+    pub new_email: String,
+    pub token: String,
+    pub expires_at: NaiveDateTime,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::email_change_requests)]
+pub struct NewEmailChangeRequest {
+    pub user_id: i32,
+    pub new_email: String,
+    pub token: String,
+    pub expires_at: NaiveDateTime,
+    pub created_at: NaiveDateTime,
+}
+
+/// Request body untuk meminta ganti email
+#[derive(Deserialize, ToSchema)]
+pub struct RequestEmailChangeRequest {
+    pub new_email: String,
+    /// Password saat ini, untuk memastikan pemilik akun yang meminta
+    pub password: String,
+}
+
+/// Request body / query untuk konfirmasi ganti email dari link di email
+#[derive(Deserialize, ToSchema, IntoParams)]
+pub struct ConfirmEmailChangeRequest {
+    pub token: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct PendingEmailChangeResponse {
+    pub new_email: String,
+    pub expires_at: NaiveDateTime,
+}
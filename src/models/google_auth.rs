@@ -1,9 +1,15 @@
-use serde::{Deserialize, Serialize};
+use diesel::prelude::*;
+use chrono::NaiveDateTime;
+use serde::Deserialize;
 
 #[derive(Deserialize)]
 pub struct GoogleTokenResponse {
     pub access_token: String,
-    // Removed unused fields: expires_in, refresh_token, scope, token_type, id_token
+    /// Umur access token (detik)
+    pub expires_in: Option<i64>,
+    /// Hanya dikirim saat pengguna memberi izin (`prompt=consent`), dan kadang saat refresh
+    pub refresh_token: Option<String>,
+    pub scope: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -16,9 +22,28 @@ pub struct GoogleUserInfo {
     pub picture: Option<String>,
 }
 
-#[derive(Serialize)]
+/// Hasil callback Google: kode sekali pakai untuk ditukar dengan JWT lewat `POST /auth/exchange`
 pub struct GoogleLoginResponse {
-    pub token: String,
-    pub user: crate::models::user::UserResponse,
+    pub exchange_code: String,
     pub is_new_user: bool,
+}
+
+#[derive(Queryable, Selectable, Debug)]
+#[diesel(table_name = crate::schema::auth_exchange_codes)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct AuthExchangeCode {
+    pub id: i32,
+    pub code: String,
+    pub user_id: i32,
+    pub expires_at: NaiveDateTime,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::auth_exchange_codes)]
+pub struct NewAuthExchangeCode {
+    pub code: String,
+    pub user_id: i32,
+    pub expires_at: NaiveDateTime,
+    pub created_at: NaiveDateTime,
 }
\ No newline at end of file
@@ -0,0 +1,106 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Aplikasi kesehatan asal data
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HealthSource {
+    GoogleFit,
+    AppleHealth,
+}
+
+impl HealthSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HealthSource::GoogleFit => "google_fit",
+            HealthSource::AppleHealth => "apple_health",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "google_fit" => Some(HealthSource::GoogleFit),
+            "apple_health" => Some(HealthSource::AppleHealth),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HealthSampleKind {
+    Steps,
+    Sleep,
+}
+
+impl HealthSampleKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HealthSampleKind::Steps => "steps",
+            HealthSampleKind::Sleep => "sleep",
+        }
+    }
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = crate::schema::health_samples)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct HealthSample {
+    pub id: i32,
+    pub user_id: i32,
+    pub kind: String,
+    pub source: String,
+    pub start_at: NaiveDateTime,
+    pub end_at: NaiveDateTime,
+    /// Jumlah langkah; NULL untuk segmen tidur
+    pub value: Option<f64>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::health_samples)]
+pub struct NewHealthSample<'a> {
+    pub user_id: i32,
+    pub kind: &'a str,
+    pub source: &'a str,
+    pub start_at: NaiveDateTime,
+    pub end_at: NaiveDateTime,
+    pub value: Option<f64>,
+}
+
+/// Jumlah langkah dalam satu interval (UTC)
+#[derive(Deserialize, ToSchema)]
+pub struct StepSample {
+    pub start_at: NaiveDateTime,
+    pub end_at: NaiveDateTime,
+    #[schema(example = 1200)]
+    pub count: i64,
+}
+
+/// Segmen tidur (UTC); kirim hanya segmen tertidur, bukan waktu di tempat tidur
+#[derive(Deserialize, ToSchema)]
+pub struct SleepSegment {
+    pub start_at: NaiveDateTime,
+    pub end_at: NaiveDateTime,
+}
+
+/// Data yang diekspor aplikasi mobile. Sampel yang sama boleh dikirim ulang;
+/// interval yang sudah tersimpan diperbarui, bukan digandakan.
+#[derive(Deserialize, ToSchema)]
+pub struct HealthSamplesRequest {
+    /// `google_fit` atau `apple_health`
+    #[schema(example = "google_fit")]
+    pub source: String,
+    #[serde(default)]
+    pub steps: Vec<StepSample>,
+    #[serde(default)]
+    pub sleep: Vec<SleepSegment>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct HealthSamplesResponse {
+    /// Jumlah sampel langkah yang disimpan atau diperbarui
+    pub steps: usize,
+    /// Jumlah segmen tidur yang disimpan
+    pub sleep: usize,
+}
@@ -0,0 +1,198 @@
+use diesel::prelude::*;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+/// Status penanganan permintaan bantuan; hanya boleh maju open → in_progress → resolved
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HelpRequestStatus {
+    Open,
+    InProgress,
+    Resolved,
+}
+
+impl HelpRequestStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HelpRequestStatus::Open => "open",
+            HelpRequestStatus::InProgress => "in_progress",
+            HelpRequestStatus::Resolved => "resolved",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "open" => Some(HelpRequestStatus::Open),
+            "in_progress" => Some(HelpRequestStatus::InProgress),
+            "resolved" => Some(HelpRequestStatus::Resolved),
+            _ => None,
+        }
+    }
+
+    /// Status berikutnya yang sah, `None` jika sudah selesai
+    pub fn next(&self) -> Option<Self> {
+        match self {
+            HelpRequestStatus::Open => Some(HelpRequestStatus::InProgress),
+            HelpRequestStatus::InProgress => Some(HelpRequestStatus::Resolved),
+            HelpRequestStatus::Resolved => None,
+        }
+    }
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = crate::schema::help_requests)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct HelpRequest {
+    pub id: i32,
+    pub user_id: i32,
+    pub name: String,
+    pub email: String,
+    pub message: String,
+    pub created_at: NaiveDateTime,
+    pub status: String,
+    pub updated_at: NaiveDateTime,
+    pub held: bool,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::help_requests)]
+pub struct NewHelpRequest<'a> {
+    pub user_id: i32,
+    pub name: &'a str,
+    pub email: &'a str,
+    pub message: &'a str,
+    pub held: bool,
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = crate::schema::help_request_comments)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct HelpRequestComment {
+    pub id: i32,
+    pub help_request_id: i32,
+    pub author_id: Option<i32>,
+    pub is_staff: bool,
+    pub body: String,
+    pub created_at: NaiveDateTime,
+    pub held: bool,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::help_request_comments)]
+pub struct NewHelpRequestComment<'a> {
+    pub help_request_id: i32,
+    pub author_id: Option<i32>,
+    pub is_staff: bool,
+    pub body: &'a str,
+    pub held: bool,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct CreateHelpRequest {
+    /// Default: username akun
+    pub name: Option<String>,
+    /// Default: email akun
+    pub email: Option<String>,
+    #[schema(example = "I have been feeling overwhelmed lately and would like to talk to someone.")]
+    pub message: String,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct UpdateHelpRequestStatus {
+    /// `in_progress` atau `resolved`; status hanya boleh maju satu langkah
+    #[schema(example = "in_progress")]
+    pub status: String,
+    /// Komentar admin yang ikut ditambahkan ke percakapan
+    pub comment: Option<String>,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct CreateHelpCommentRequest {
+    pub body: String,
+}
+
+#[derive(Deserialize, IntoParams)]
+pub struct HelpRequestListQuery {
+    /// Filter status: `open`, `in_progress`, atau `resolved`
+    pub status: Option<String>,
+    /// Jumlah maksimum (1-200, default 50)
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct HelpRequestResponse {
+    pub id: i32,
+    pub user_id: i32,
+    pub name: String,
+    pub email: String,
+    pub message: String,
+    #[schema(example = "open")]
+    pub status: String,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+    /// Ditahan moderasi sampai ditinjau admin
+    pub held: bool,
+}
+
+impl From<HelpRequest> for HelpRequestResponse {
+    fn from(request: HelpRequest) -> Self {
+        HelpRequestResponse {
+            id: request.id,
+            user_id: request.user_id,
+            name: request.name,
+            email: request.email,
+            message: request.message,
+            status: request.status,
+            created_at: request.created_at,
+            updated_at: request.updated_at,
+            held: request.held,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct HelpCommentResponse {
+    pub id: i32,
+    pub author_id: Option<i32>,
+    /// true jika ditulis oleh admin
+    pub is_staff: bool,
+    pub body: String,
+    pub created_at: NaiveDateTime,
+    /// Ditahan moderasi sampai ditinjau admin
+    pub held: bool,
+}
+
+impl From<HelpRequestComment> for HelpCommentResponse {
+    fn from(comment: HelpRequestComment) -> Self {
+        HelpCommentResponse {
+            id: comment.id,
+            author_id: comment.author_id,
+            is_staff: comment.is_staff,
+            body: comment.body,
+            created_at: comment.created_at,
+            held: comment.held,
+        }
+    }
+}
+
+/// Permintaan bantuan beserta seluruh percakapannya
+#[derive(Debug, Serialize, ToSchema)]
+pub struct HelpRequestDetail {
+    #[serde(flatten)]
+    pub request: HelpRequestResponse,
+    pub comments: Vec<HelpCommentResponse>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_only_moves_forward_one_step() {
+        assert_eq!(HelpRequestStatus::Open.next(), Some(HelpRequestStatus::InProgress));
+        assert_eq!(HelpRequestStatus::InProgress.next(), Some(HelpRequestStatus::Resolved));
+        assert_eq!(HelpRequestStatus::Resolved.next(), None);
+        assert_eq!(HelpRequestStatus::parse(" In_Progress "), Some(HelpRequestStatus::InProgress));
+        assert_eq!(HelpRequestStatus::parse("closed"), None);
+    }
+}
@@ -0,0 +1,45 @@
+use chrono::NaiveDate;
+use serde::Serialize;
+use utoipa::ToSchema;
+
+#[derive(ToSchema)]
+pub struct DaylioImportForm {
+    /// File CSV hasil export Daylio
+    #[schema(value_type = String, format = Binary)]
+    pub file: Vec<u8>,
+}
+
+/// Mood yang dibuat (atau akan dibuat saat dry run) dari satu baris CSV
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ImportedMood {
+    /// Nomor baris di file CSV, dimulai dari 1 untuk header
+    pub line: usize,
+    #[serde(with = "crate::utils::api_dates")]
+    #[schema(value_type = String, format = Date, example = "2025-07-23")]
+    pub date: NaiveDate,
+    #[schema(example = "happy")]
+    pub mood: String,
+    pub emoji: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SkippedImportRow {
+    pub line: usize,
+    #[serde(skip_serializing_if = "Option::is_none", with = "crate::utils::api_dates::option")]
+    #[schema(value_type = Option<String>, format = Date)]
+    pub date: Option<NaiveDate>,
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ImportSummary {
+    /// true jika tidak ada data yang disimpan
+    pub dry_run: bool,
+    /// Jumlah baris data di file, tanpa header
+    pub total_rows: usize,
+    pub created: Vec<ImportedMood>,
+    pub skipped: Vec<SkippedImportRow>,
+}
@@ -0,0 +1,285 @@
+use diesel::prelude::*;
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use crate::models::mood::{MoodCount, ScoreInterpretation};
+
+/// Jenis pola penurunan mood yang dideteksi
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlertKind {
+    /// Beberapa hari berturut-turut dengan skor di bawah ambang batas
+    SustainedLow,
+    /// Rata-rata beberapa hari terakhir turun tajam dibanding baseline pengguna
+    SharpDrop,
+}
+
+impl AlertKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AlertKind::SustainedLow => "sustained_low",
+            AlertKind::SharpDrop => "sharp_drop",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct MoodAlert {
+    #[schema(example = "sustained_low")]
+    pub kind: String,
+    pub message: String,
+    #[serde(with = "crate::utils::api_dates")]
+    #[schema(value_type = String, format = Date, example = "2025-03-10")]
+    pub start_date: NaiveDate,
+    #[serde(with = "crate::utils::api_dates")]
+    #[schema(value_type = String, format = Date, example = "2025-03-14")]
+    pub end_date: NaiveDate,
+    /// Jumlah catatan mood yang memicu alert
+    pub entries: i64,
+    pub recent_average: f64,
+    /// Rata-rata periode sebelumnya; hanya untuk `sharp_drop`
+    pub baseline_average: Option<f64>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::insight_notifications)]
+pub struct NewInsightNotification<'a> {
+    pub user_id: i32,
+    pub kind: &'a str,
+    pub start_date: NaiveDate,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WeekSummary {
+    #[serde(with = "crate::utils::api_dates")]
+    #[schema(value_type = String, format = Date, example = "2025-03-10")]
+    pub start_date: NaiveDate,
+    #[serde(with = "crate::utils::api_dates")]
+    #[schema(value_type = String, format = Date, example = "2025-03-16")]
+    pub end_date: NaiveDate,
+    pub entries: i64,
+    pub average_score: Option<f64>,
+    pub mood_distribution: Vec<MoodCount>,
+}
+
+/// Hari dengan kenaikan skor terbesar dibanding hari yang sama minggu lalu
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ImprovedDay {
+    #[schema(example = "monday")]
+    pub weekday: String,
+    #[serde(with = "crate::utils::api_dates")]
+    #[schema(value_type = String, format = Date, example = "2025-03-10")]
+    pub date: NaiveDate,
+    pub score: i32,
+    pub last_week_score: i32,
+    pub delta: i32,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MoodCountChange {
+    #[schema(example = "happy")]
+    pub mood: String,
+    pub this_week: i64,
+    pub last_week: i64,
+    pub change: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WeekOverWeekInsight {
+    pub this_week: WeekSummary,
+    pub last_week: WeekSummary,
+    /// Rata-rata minggu ini dikurangi rata-rata minggu lalu
+    pub average_delta: Option<f64>,
+    pub most_improved_day: Option<ImprovedDay>,
+    pub distribution_changes: Vec<MoodCountChange>,
+    /// Key i18n ringkasan: `insight.week.improved`, `declined`, `stable` atau `not_enough_data`
+    #[schema(example = "insight.week.improved")]
+    pub summary_key: String,
+    pub summary: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WeekdayAverage {
+    #[schema(example = "monday")]
+    pub weekday: String,
+    pub entries: i64,
+    pub average_score: Option<f64>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DayOfWeekInsight {
+    #[serde(with = "crate::utils::api_dates")]
+    #[schema(value_type = String, format = Date, example = "2025-01-01")]
+    pub start_date: NaiveDate,
+    #[serde(with = "crate::utils::api_dates")]
+    #[schema(value_type = String, format = Date, example = "2025-03-31")]
+    pub end_date: NaiveDate,
+    /// Senin sampai Minggu, termasuk hari tanpa catatan
+    pub weekdays: Vec<WeekdayAverage>,
+    #[schema(example = "saturday")]
+    pub best_weekday: Option<String>,
+    #[schema(example = "monday")]
+    pub worst_weekday: Option<String>,
+}
+
+/// Rata-rata mood di satu tempat; `label` terisi untuk tempat berlabel, koordinat untuk sisanya
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PlaceAverage {
+    #[schema(example = "Kantor")]
+    pub label: Option<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub entries: i64,
+    pub average_score: Option<f64>,
+    /// Selisih dengan rata-rata semua mood berlokasi dalam periode yang sama
+    pub difference_from_average: Option<f64>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PlacesInsight {
+    #[serde(with = "crate::utils::api_dates")]
+    #[schema(value_type = String, format = Date, example = "2025-01-01")]
+    pub start_date: NaiveDate,
+    #[serde(with = "crate::utils::api_dates")]
+    #[schema(value_type = String, format = Date, example = "2025-03-31")]
+    pub end_date: NaiveDate,
+    /// Rata-rata semua mood yang punya lokasi
+    pub average_score: Option<f64>,
+    /// Urut dari tempat dengan catatan terbanyak
+    pub places: Vec<PlaceAverage>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ForecastPoint {
+    #[serde(with = "crate::utils::api_dates")]
+    #[schema(value_type = String, format = Date, example = "2025-03-15")]
+    pub date: NaiveDate,
+    pub predicted_score: f64,
+    /// Batas bawah interval prediksi 95%
+    pub lower: f64,
+    /// Batas atas interval prediksi 95%
+    pub upper: f64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MoodForecast {
+    /// `linear_trend`, `linear_trend_weekday`, atau `not_enough_data` jika catatan terlalu sedikit
+    #[schema(example = "linear_trend_weekday")]
+    pub method: String,
+    /// Jumlah catatan mood yang dipakai untuk model
+    pub data_points: i64,
+    /// Perubahan skor per hari menurut tren
+    pub trend_per_day: Option<f64>,
+    pub forecast: Vec<ForecastPoint>,
+}
+
+/// Query grafik tren (`GET /insights/trend`)
+#[derive(Deserialize, IntoParams)]
+pub struct TrendQuery {
+    /// Start date (YYYY-MM-DD); default 30 hari sebelum end_date
+    #[serde(default, with = "crate::utils::api_dates::option")]
+    #[param(value_type = Option<String>, format = Date, example = "2025-07-01")]
+    pub start_date: Option<NaiveDate>,
+    /// End date (YYYY-MM-DD), inklusif; default hari ini
+    #[serde(default, with = "crate::utils::api_dates::option")]
+    #[param(value_type = Option<String>, format = Date, example = "2025-07-31")]
+    pub end_date: Option<NaiveDate>,
+    /// Satuan periode: `day` (default), `week` (minggu ISO), `month`, atau `quarter`
+    #[param(example = "week")]
+    pub group: Option<String>,
+    /// Lebar jendela rolling average dalam hari (2-31), hanya untuk `group=day`; tanpa parameter ini tidak ada smoothing
+    #[param(example = 7)]
+    pub smoothing: Option<u32>,
+    /// Posisi jendela smoothing: `right` (default, hanya data sebelumnya) atau `center`
+    #[param(example = "center")]
+    pub align: Option<String>,
+    /// `previous`: sertakan seri rentang sebelumnya dengan panjang yang sama beserta selisihnya
+    #[param(example = "previous")]
+    pub compare: Option<String>,
+}
+
+/// Rata-rata skor mood dalam satu periode grafik tren
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TrendPoint {
+    #[serde(with = "crate::utils::api_dates")]
+    #[schema(value_type = String, format = Date, example = "2025-03-10")]
+    pub period_start: NaiveDate,
+    /// Hari terakhir periode (inklusif); sama dengan `period_start` untuk grouping harian
+    #[serde(with = "crate::utils::api_dates")]
+    #[schema(value_type = String, format = Date, example = "2025-03-16")]
+    pub period_end: NaiveDate,
+    pub score: f64,
+    /// Jumlah hari dengan catatan mood dalam periode
+    pub entries: i64,
+    /// Jarak `period_start` dari awal rentang (hari), untuk menumpuk seri pembanding
+    pub offset_days: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SmoothedPoint {
+    #[serde(with = "crate::utils::api_dates")]
+    #[schema(value_type = String, format = Date, example = "2025-03-15")]
+    pub date: NaiveDate,
+    pub score: f64,
+    /// Jarak `date` dari awal rentang (hari)
+    pub offset_days: i64,
+}
+
+/// Seri rentang sebelumnya (panjang sama, tepat sebelum `start_date`) dan selisihnya
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TrendComparison {
+    #[serde(with = "crate::utils::api_dates")]
+    #[schema(value_type = String, format = Date)]
+    pub previous_start_date: NaiveDate,
+    #[serde(with = "crate::utils::api_dates")]
+    #[schema(value_type = String, format = Date)]
+    pub previous_end_date: NaiveDate,
+    pub previous_points: Vec<TrendPoint>,
+    pub previous_smoothed: Vec<SmoothedPoint>,
+    /// Rata-rata skor harian rentang sekarang dan sebelumnya
+    pub current_average: Option<f64>,
+    pub previous_average: Option<f64>,
+    /// `current_average - previous_average`
+    pub average_delta: Option<f64>,
+    pub current_entries: i64,
+    pub previous_entries: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MoodTrend {
+    #[serde(with = "crate::utils::api_dates")]
+    #[schema(value_type = String, format = Date)]
+    pub start_date: NaiveDate,
+    #[serde(with = "crate::utils::api_dates")]
+    #[schema(value_type = String, format = Date)]
+    pub end_date: NaiveDate,
+    /// Satuan periode: `day`, `week` (minggu ISO), `month`, atau `quarter`
+    #[schema(example = "week")]
+    pub group: String,
+    /// Lebar jendela rolling average (hari), null jika tanpa smoothing
+    pub smoothing: Option<u32>,
+    /// Posisi jendela smoothing: `right` atau `center`
+    #[schema(example = "right")]
+    pub alignment: Option<String>,
+    /// Rata-rata skor per periode yang punya catatan
+    pub points: Vec<TrendPoint>,
+    /// Rolling average harian; kosong tanpa smoothing
+    pub smoothed: Vec<SmoothedPoint>,
+    /// Hanya ada jika `compare=previous`
+    pub comparison: Option<TrendComparison>,
+}
+
+/// Ringkasan insight dalam satu dokumen untuk widget/dashboard, di-cache maksimal satu jam
+#[derive(Debug, Serialize, ToSchema)]
+pub struct InsightSnapshot {
+    /// Waktu snapshot dihitung (UTC); bisa tertinggal sampai satu jam dari data terbaru
+    #[schema(value_type = String, format = DateTime)]
+    pub generated_at: NaiveDateTime,
+    pub total_entries: i64,
+    pub average_score: Option<f64>,
+    pub interpretation: ScoreInterpretation,
+    pub mood_distribution: Vec<MoodCount>,
+    pub current_streak: i32,
+    pub longest_streak: i32,
+    pub day_of_week: DayOfWeekInsight,
+    pub alerts: Vec<MoodAlert>,
+}
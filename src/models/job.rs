@@ -0,0 +1,134 @@
+use diesel::prelude::*;
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Jenis operasi panjang yang dikerjakan worker job
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobKind {
+    DaylioImport,
+    JournalsMarkdown,
+    MonthlyReportPdf,
+    AccountDeletion,
+}
+
+impl JobKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JobKind::DaylioImport => "daylio_import",
+            JobKind::JournalsMarkdown => "journals_markdown",
+            JobKind::MonthlyReportPdf => "monthly_report_pdf",
+            JobKind::AccountDeletion => "account_deletion",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "daylio_import" => Some(JobKind::DaylioImport),
+            "journals_markdown" => Some(JobKind::JournalsMarkdown),
+            "monthly_report_pdf" => Some(JobKind::MonthlyReportPdf),
+            "account_deletion" => Some(JobKind::AccountDeletion),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobStatus {
+    Queued,
+    Running,
+    Succeeded,
+    Failed,
+}
+
+impl JobStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JobStatus::Queued => "queued",
+            JobStatus::Running => "running",
+            JobStatus::Succeeded => "succeeded",
+            JobStatus::Failed => "failed",
+        }
+    }
+}
+
+/// Parameter job, disimpan sebagai JSON di kolom `params`; field yang dipakai bergantung jenis job
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct JobParams {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub month: Option<NaiveDate>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dry_run: Option<bool>,
+    /// IP pemohon, untuk audit log yang ditulis saat job dikerjakan
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ip_address: Option<String>,
+}
+
+impl JobParams {
+    pub fn parse(value: &str) -> Self {
+        serde_json::from_str(value).unwrap_or_default()
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_else(|_| "{}".to_string())
+    }
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = crate::schema::jobs)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Job {
+    pub id: i32,
+    pub user_id: Option<i32>,
+    pub kind: String,
+    pub status: String,
+    pub params: String,
+    pub result_content_type: Option<String>,
+    pub result_filename: Option<String>,
+    pub error: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub started_at: Option<NaiveDateTime>,
+    pub finished_at: Option<NaiveDateTime>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::jobs)]
+pub struct NewJob<'a> {
+    pub user_id: i32,
+    pub kind: &'a str,
+    pub params: String,
+}
+
+/// Hasil job yang berhasil, disimpan di storage
+#[derive(Debug)]
+pub struct JobOutput {
+    pub content_type: &'static str,
+    pub filename: Option<String>,
+    pub data: Vec<u8>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct JobResponse {
+    pub id: i32,
+    /// `daylio_import`, `journals_markdown`, `monthly_report_pdf` atau `account_deletion`
+    #[schema(example = "journals_markdown")]
+    pub kind: String,
+    /// `queued`, `running`, `succeeded` atau `failed`
+    #[schema(example = "queued")]
+    pub status: String,
+    /// Alasan kegagalan, jika status `failed`
+    pub error: Option<String>,
+    /// URL untuk mengunduh hasil setelah job selesai, jika job punya hasil
+    pub result_url: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub started_at: Option<NaiveDateTime>,
+    pub finished_at: Option<NaiveDateTime>,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct DeleteAccountRequest {
+    /// Password saat ini sebagai konfirmasi; tidak diperlukan untuk akun tamu
+    pub password: Option<String>,
+}
@@ -1,8 +1,11 @@
 use diesel::prelude::*;
-use chrono::NaiveDateTime;
+use chrono::{NaiveDate, NaiveDateTime};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use crate::utils::markdown_preview::{preview, PREVIEW_MAX_CHARS};
+use crate::utils::patch::Patch;
 
-#[derive(Queryable, Selectable, Debug, Serialize)]
+#[derive(Queryable, Selectable, Debug, Clone, Serialize)]
 #[diesel(table_name = crate::schema::journals)]
 #[diesel(check_for_backend(diesel::pg::Pg))]
 pub struct Journal {
@@ -12,6 +15,7 @@ pub struct Journal {
     pub content: String,
     pub created_at: NaiveDateTime,
     pub updated_at: Option<NaiveDateTime>,
+    pub is_pinned: bool,
 }
 
 #[derive(Insertable, Debug, Deserialize)]
@@ -24,28 +28,210 @@ pub struct NewJournal {
     pub updated_at: Option<NaiveDateTime>,
 }
 
-#[derive(Serialize)]
+/// Metadata jurnal tanpa isi, untuk list ringkas; `word_count` dihitung di database
+#[derive(Queryable, Debug, Clone)]
+pub struct JournalSummary {
+    pub id: i32,
+    pub title: String,
+    pub is_pinned: bool,
+    pub created_at: NaiveDateTime,
+    pub updated_at: Option<NaiveDateTime>,
+    pub word_count: i64,
+}
+
+/// Field yang bisa dipilih lewat parameter `fields` pada list jurnal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JournalField {
+    Id,
+    Title,
+    Content,
+    IsPinned,
+    CreatedAt,
+    UpdatedAt,
+    WordCount,
+}
+
+impl JournalField {
+    pub const ALL: [JournalField; 7] = [
+        JournalField::Id,
+        JournalField::Title,
+        JournalField::Content,
+        JournalField::IsPinned,
+        JournalField::CreatedAt,
+        JournalField::UpdatedAt,
+        JournalField::WordCount,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JournalField::Id => "id",
+            JournalField::Title => "title",
+            JournalField::Content => "content",
+            JournalField::IsPinned => "is_pinned",
+            JournalField::CreatedAt => "created_at",
+            JournalField::UpdatedAt => "updated_at",
+            JournalField::WordCount => "word_count",
+        }
+    }
+}
+
+/// Pilihan field dari `fields=id,title,...`. Tanpa `content`, list memakai proyeksi ringkas.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JournalFields(Vec<JournalField>);
+
+impl JournalFields {
+    /// `None` jika parameter kosong, artinya respons lengkap seperti biasa
+    pub fn parse(value: Option<&str>) -> Result<Option<Self>, String> {
+        let Some(value) = value.filter(|value| !value.trim().is_empty()) else {
+            return Ok(None);
+        };
+        let mut fields = Vec::new();
+        for name in value.split(',').map(str::trim).filter(|name| !name.is_empty()) {
+            let field = JournalField::ALL
+                .into_iter()
+                .find(|field| field.as_str() == name)
+                .ok_or_else(|| {
+                    let allowed: Vec<&str> = JournalField::ALL.iter().map(JournalField::as_str).collect();
+                    format!("Unknown field: {}. Allowed fields: {}", name, allowed.join(", "))
+                })?;
+            if !fields.contains(&field) {
+                fields.push(field);
+            }
+        }
+        Ok(Some(JournalFields(fields)))
+    }
+
+    pub fn contains(&self, field: JournalField) -> bool {
+        self.0.contains(&field)
+    }
+}
+
+/// Jurnal dengan field yang dipilih saja; field yang tidak diminta tidak ikut di JSON
+#[derive(Debug, Default, Serialize, ToSchema)]
+pub struct SparseJournalResponse {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_pinned: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<NaiveDateTime>,
+    /// `null` jika jurnal belum pernah diubah
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<NaiveDateTime>)]
+    pub updated_at: Option<Option<NaiveDateTime>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub word_count: Option<i64>,
+}
+
+impl SparseJournalResponse {
+    pub fn from_summary(summary: JournalSummary, fields: &JournalFields) -> Self {
+        let pick = |field| fields.contains(field);
+        SparseJournalResponse {
+            id: pick(JournalField::Id).then_some(summary.id),
+            title: pick(JournalField::Title).then_some(summary.title),
+            content: None,
+            is_pinned: pick(JournalField::IsPinned).then_some(summary.is_pinned),
+            created_at: pick(JournalField::CreatedAt).then_some(summary.created_at),
+            updated_at: pick(JournalField::UpdatedAt).then_some(summary.updated_at),
+            word_count: pick(JournalField::WordCount).then_some(summary.word_count),
+        }
+    }
+
+    pub fn from_journal(journal: Journal, fields: &JournalFields) -> Self {
+        let summary = JournalSummary {
+            id: journal.id,
+            title: journal.title,
+            is_pinned: journal.is_pinned,
+            created_at: journal.created_at,
+            updated_at: journal.updated_at,
+            word_count: journal.content.split_whitespace().count() as i64,
+        };
+        SparseJournalResponse {
+            content: fields.contains(JournalField::Content).then_some(journal.content),
+            ..SparseJournalResponse::from_summary(summary, fields)
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
 pub struct JournalResponse {
     pub id: i32,
     pub user_id: i32,
     pub title: String,
     pub content: String,
+    pub is_pinned: bool,
+    pub created_at: NaiveDateTime,
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+/// Item list jurnal v2: `preview` (Markdown dibuang, maksimal 200 karakter) menggantikan isi lengkap;
+/// `content` hanya ada jika diminta lewat `include=content`
+#[derive(Serialize, ToSchema)]
+pub struct JournalListItem {
+    pub id: i32,
+    pub user_id: i32,
+    pub title: String,
+    pub preview: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    pub is_pinned: bool,
     pub created_at: NaiveDateTime,
     pub updated_at: Option<NaiveDateTime>,
 }
 
-#[derive(Debug, Deserialize)]
+impl JournalListItem {
+    pub fn from_response(journal: JournalResponse, include_content: bool) -> Self {
+        JournalListItem {
+            id: journal.id,
+            user_id: journal.user_id,
+            title: journal.title,
+            preview: preview(&journal.content, PREVIEW_MAX_CHARS),
+            content: include_content.then_some(journal.content),
+            is_pinned: journal.is_pinned,
+            created_at: journal.created_at,
+            updated_at: journal.updated_at,
+        }
+    }
+}
+
+/// Parse `include=...` pada list jurnal v2; saat ini hanya `content` yang didukung.
+/// Mengembalikan `true` jika isi lengkap diminta.
+pub fn includes_content(include: Option<&str>) -> Result<bool, String> {
+    let mut content = false;
+    for name in include.unwrap_or_default().split(',').map(str::trim).filter(|name| !name.is_empty()) {
+        match name {
+            "content" => content = true,
+            other => return Err(format!("Unknown include: {}. Allowed values: content", other)),
+        }
+    }
+    Ok(content)
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct CreateJournalRequest {
     pub title: String,
     pub content: String,
-    pub created_at: Option<String>, 
+    #[serde(default, with = "crate::utils::api_dates::option")]
+    #[schema(value_type = Option<String>, format = Date, example = "2025-07-23")]
+    pub created_at: Option<NaiveDate>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct UpdateJournalRequest {
-    pub title: Option<String>,
-    pub content: Option<String>,
-    pub created_at: Option<String>,
+    /// Tidak boleh `null`; field yang tidak dikirim tidak diubah
+    #[serde(default)]
+    #[schema(value_type = Option<String>)]
+    pub title: Patch<String>,
+    #[serde(default)]
+    #[schema(value_type = Option<String>)]
+    pub content: Patch<String>,
+    #[serde(default, with = "crate::utils::api_dates::option")]
+    #[schema(value_type = Option<String>, format = Date, example = "2025-07-23")]
+    pub created_at: Option<NaiveDate>,
 }
 
 #[derive(Debug, Serialize)]
@@ -63,4 +249,29 @@ pub struct JournalWordCount {
     pub title: String,
     pub word_count: usize,
     pub created_at: NaiveDateTime,
-}
\ No newline at end of file
+}
+#[derive(Queryable, Selectable, Insertable, Debug)]
+#[diesel(table_name = crate::schema::journal_drafts)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct JournalDraft {
+    pub user_id: i32,
+    pub title: String,
+    pub content: String,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct JournalDraftResponse {
+    pub title: String,
+    pub content: String,
+    pub updated_at: NaiveDateTime,
+}
+
+/// Isi draft yang sedang ditulis; boleh kosong karena disimpan otomatis
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SaveJournalDraftRequest {
+    #[serde(default)]
+    pub title: String,
+    #[serde(default)]
+    pub content: String,
+}
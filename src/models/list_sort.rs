@@ -0,0 +1,63 @@
+use crate::errors::app_error::AppError;
+
+/// Urutan hasil endpoint list (parameter `sort`). Setiap endpoint punya whitelist sendiri;
+/// nilai di luar whitelist ditolak, bukan diabaikan.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListSort {
+    DateAsc,
+    DateDesc,
+    /// Terakhir diubah lebih dulu; entri tanpa `updated_at` di akhir
+    UpdatedDesc,
+    Title,
+}
+
+impl ListSort {
+    pub const MOODS: [ListSort; 3] = [ListSort::DateAsc, ListSort::DateDesc, ListSort::UpdatedDesc];
+    pub const JOURNALS: [ListSort; 4] = [ListSort::DateAsc, ListSort::DateDesc, ListSort::UpdatedDesc, ListSort::Title];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ListSort::DateAsc => "date_asc",
+            ListSort::DateDesc => "date_desc",
+            ListSort::UpdatedDesc => "updated_desc",
+            ListSort::Title => "title",
+        }
+    }
+
+    /// Parse `sort` dari query string; kosong berarti `date_desc`
+    pub fn parse(value: Option<&str>, allowed: &[ListSort]) -> Result<Self, AppError> {
+        let Some(value) = value.map(str::trim).filter(|value| !value.is_empty()) else {
+            return Ok(ListSort::DateDesc);
+        };
+        allowed
+            .iter()
+            .find(|sort| sort.as_str() == value)
+            .copied()
+            .ok_or_else(|| invalid_sort(value, allowed))
+    }
+
+    /// Error untuk sort yang tidak didukung daftar `allowed`
+    pub fn unsupported(&self, allowed: &[ListSort]) -> AppError {
+        invalid_sort(self.as_str(), allowed)
+    }
+}
+
+fn invalid_sort(value: &str, allowed: &[ListSort]) -> AppError {
+    let names: Vec<&str> = allowed.iter().map(ListSort::as_str).collect();
+    AppError::BadRequest(format!("Invalid sort: {}. Allowed values: {}", value, names.join(", ")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_only_whitelisted_values() {
+        assert_eq!(ListSort::parse(None, &ListSort::MOODS).unwrap(), ListSort::DateDesc);
+        assert_eq!(ListSort::parse(Some("updated_desc"), &ListSort::MOODS).unwrap(), ListSort::UpdatedDesc);
+        assert_eq!(ListSort::parse(Some("title"), &ListSort::JOURNALS).unwrap(), ListSort::Title);
+        assert!(matches!(ListSort::parse(Some("title"), &ListSort::MOODS), Err(AppError::BadRequest(_))));
+        assert!(ListSort::parse(Some("id; drop table"), &ListSort::JOURNALS).is_err());
+        assert!(matches!(ListSort::Title.unsupported(&ListSort::MOODS), AppError::BadRequest(_)));
+    }
+}
@@ -0,0 +1,86 @@
+use diesel::prelude::*;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = crate::schema::messages)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Message {
+    pub id: i32,
+    pub user_id: i32,
+    pub psychologist_id: i32,
+    pub from_psychologist: bool,
+    pub body: String,
+    pub created_at: NaiveDateTime,
+    pub read_at: Option<NaiveDateTime>,
+    pub held: bool,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::messages)]
+pub struct NewMessage<'a> {
+    pub user_id: i32,
+    pub psychologist_id: i32,
+    pub from_psychologist: bool,
+    pub body: &'a str,
+    pub held: bool,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct SendMessageRequest {
+    pub body: String,
+}
+
+#[derive(Deserialize, IntoParams)]
+pub struct MessageListQuery {
+    /// Ambil pesan yang lebih lama dari id ini (halaman berikutnya)
+    pub before_id: Option<i32>,
+    /// Jumlah pesan maksimum (default 50, maksimum 200)
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct MessageResponse {
+    pub id: i32,
+    pub user_id: i32,
+    pub psychologist_id: i32,
+    pub from_psychologist: bool,
+    pub body: String,
+    pub created_at: NaiveDateTime,
+    pub read_at: Option<NaiveDateTime>,
+    /// Ditahan moderasi; hanya terlihat oleh pengirim sampai disetujui admin
+    pub held: bool,
+}
+
+impl From<Message> for MessageResponse {
+    fn from(message: Message) -> Self {
+        MessageResponse {
+            id: message.id,
+            user_id: message.user_id,
+            psychologist_id: message.psychologist_id,
+            from_psychologist: message.from_psychologist,
+            body: message.body,
+            created_at: message.created_at,
+            read_at: message.read_at,
+            held: message.held,
+        }
+    }
+}
+
+/// Percakapan antara pengguna (`user_id`) dan psikolog (`psychologist_id`)
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ConversationResponse {
+    pub user_id: i32,
+    pub psychologist_id: i32,
+    /// Nama psikolog bagi pengguna, atau username klien bagi psikolog
+    pub counterpart_name: String,
+    pub last_message: Option<MessageResponse>,
+    /// Pesan dari lawan bicara yang belum dibaca
+    pub unread_count: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MarkReadResponse {
+    pub marked_read: usize,
+}
@@ -2,4 +2,35 @@ pub mod auth;
 pub mod user;
 pub mod mood;
 pub mod journal;
-pub mod google_auth;
\ No newline at end of file
+pub mod google_auth;
+pub mod report;
+pub mod calendar;
+pub mod security;
+pub mod audit;
+pub mod email_change;
+pub mod onboarding;
+pub mod insight;
+pub mod device;
+pub mod notification;
+pub mod import;
+pub mod backup;
+pub mod organization;
+pub mod retention;
+pub mod admin_analytics;
+pub mod dev;
+pub mod password_reset;
+pub mod help;
+pub mod psychologist;
+pub mod appointment;
+pub mod message;
+pub mod moderation;
+pub mod community;
+pub mod checkin;
+pub mod webhook;
+pub mod health;
+pub mod quota;
+pub mod job;
+pub mod status;
+pub mod oauth_account;
+pub mod api_usage;
+pub mod list_sort;
\ No newline at end of file
@@ -0,0 +1,137 @@
+use diesel::prelude::*;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+/// Jenis konten yang dimoderasi
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModeratedContent {
+    Message,
+    HelpRequest,
+    HelpComment,
+    CommunityPost,
+}
+
+impl ModeratedContent {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ModeratedContent::Message => "message",
+            ModeratedContent::HelpRequest => "help_request",
+            ModeratedContent::HelpComment => "help_comment",
+            ModeratedContent::CommunityPost => "community_post",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        [
+            ModeratedContent::Message,
+            ModeratedContent::HelpRequest,
+            ModeratedContent::HelpComment,
+            ModeratedContent::CommunityPost,
+        ]
+        .into_iter()
+            .find(|content| content.as_str() == value)
+    }
+}
+
+/// Status tinjauan admin
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModerationStatus {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+impl ModerationStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ModerationStatus::Pending => "pending",
+            ModerationStatus::Approved => "approved",
+            ModerationStatus::Rejected => "rejected",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        [ModerationStatus::Pending, ModerationStatus::Approved, ModerationStatus::Rejected]
+            .into_iter()
+            .find(|status| status.as_str() == value)
+    }
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = crate::schema::moderation_verdicts)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct ModerationVerdictRecord {
+    pub id: i32,
+    pub content_type: String,
+    pub content_id: i32,
+    pub author_id: Option<i32>,
+    pub action: String,
+    pub source: String,
+    pub reasons: Vec<String>,
+    pub status: String,
+    pub reviewed_by: Option<i32>,
+    pub reviewed_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::moderation_verdicts)]
+pub struct NewModerationVerdict<'a> {
+    pub content_type: &'a str,
+    pub content_id: i32,
+    pub author_id: Option<i32>,
+    pub action: &'a str,
+    pub source: &'a str,
+    pub reasons: &'a [String],
+}
+
+#[derive(Deserialize, IntoParams)]
+pub struct ModerationListQuery {
+    /// `pending` (default), `approved` atau `rejected`
+    #[param(example = "pending")]
+    pub status: Option<String>,
+    /// Jumlah entri maksimum (default 50, maksimum 200)
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ModerationVerdictResponse {
+    pub id: i32,
+    /// `message`, `help_request`, `help_comment` atau `community_post`
+    #[schema(example = "message")]
+    pub content_type: String,
+    pub content_id: i32,
+    /// Isi konten saat ini; kosong jika konten sudah dihapus
+    pub content: Option<String>,
+    pub author_id: Option<i32>,
+    /// `flag` atau `hold`
+    #[schema(example = "hold")]
+    pub action: String,
+    /// Pemeriksa yang menentukan tindakan: `terms`, `api`, `community` (pra-moderasi) atau `error`
+    pub source: String,
+    pub reasons: Vec<String>,
+    pub status: String,
+    pub reviewed_by: Option<i32>,
+    pub reviewed_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+impl ModerationVerdictResponse {
+    pub fn new(verdict: ModerationVerdictRecord, content: Option<String>) -> Self {
+        ModerationVerdictResponse {
+            id: verdict.id,
+            content_type: verdict.content_type,
+            content_id: verdict.content_id,
+            content,
+            author_id: verdict.author_id,
+            action: verdict.action,
+            source: verdict.source,
+            reasons: verdict.reasons,
+            status: verdict.status,
+            reviewed_by: verdict.reviewed_by,
+            reviewed_at: verdict.reviewed_at,
+            created_at: verdict.created_at,
+        }
+    }
+}
@@ -1,8 +1,10 @@
 use diesel::prelude::*;
 use chrono::{NaiveDateTime}; 
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use crate::utils::patch::Patch;
 
-#[derive(Queryable, Selectable, Debug, Serialize)]
+#[derive(Queryable, Selectable, Insertable, Debug, Clone, Serialize)]
 #[diesel(table_name = crate::schema::moods)]
 #[diesel(check_for_backend(diesel::pg::Pg))]
 pub struct Mood {
@@ -14,6 +16,71 @@ pub struct Mood {
     pub notes: Option<String>,
     pub created_at: NaiveDateTime,
     pub updated_at: Option<NaiveDateTime>,
+    pub details: Option<serde_json::Value>,
+    pub is_pinned: bool,
+    pub place_label: Option<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+}
+
+/// Mood yang sudah dihapus tetapi masih bisa dikembalikan selama jendela undo
+#[derive(Queryable, Selectable, Insertable, Debug)]
+#[diesel(table_name = crate::schema::deleted_moods)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct DeletedMood {
+    pub id: i32,
+    pub user_id: i32,
+    pub date: chrono::NaiveDate,
+    pub mood: String,
+    pub emoji: String,
+    pub notes: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: Option<NaiveDateTime>,
+    pub details: Option<serde_json::Value>,
+    pub is_pinned: bool,
+    pub deleted_at: NaiveDateTime,
+    pub place_label: Option<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+}
+
+impl DeletedMood {
+    pub fn from_mood(mood: Mood, deleted_at: NaiveDateTime) -> Self {
+        DeletedMood {
+            id: mood.id,
+            user_id: mood.user_id,
+            date: mood.date,
+            mood: mood.mood,
+            emoji: mood.emoji,
+            notes: mood.notes,
+            created_at: mood.created_at,
+            updated_at: mood.updated_at,
+            details: mood.details,
+            is_pinned: mood.is_pinned,
+            deleted_at,
+            place_label: mood.place_label,
+            latitude: mood.latitude,
+            longitude: mood.longitude,
+        }
+    }
+
+    pub fn into_mood(self) -> Mood {
+        Mood {
+            id: self.id,
+            user_id: self.user_id,
+            date: self.date,
+            mood: self.mood,
+            emoji: self.emoji,
+            notes: self.notes,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            details: self.details,
+            is_pinned: self.is_pinned,
+            place_label: self.place_label,
+            latitude: self.latitude,
+            longitude: self.longitude,
+        }
+    }
 }
 
 #[derive(Insertable, Debug, Deserialize)]
@@ -25,44 +92,204 @@ pub struct NewMood {
     pub emoji: String,
     pub notes: Option<String>,
     pub created_at: NaiveDateTime,
-    pub updated_at: Option<NaiveDateTime>
+    pub updated_at: Option<NaiveDateTime>,
+    pub details: Option<serde_json::Value>,
+    pub place_label: Option<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+}
+
+/// Data terstruktur opsional yang bisa diisi bersama mood harian
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct MoodDetails {
+    /// Tingkat energi 1-5
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(minimum = 1, maximum = 5, example = 3)]
+    pub energy: Option<i32>,
+    /// Tingkat kecemasan 1-5
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(minimum = 1, maximum = 5, example = 2)]
+    pub anxiety: Option<i32>,
+    /// Lama tidur semalam (jam)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(minimum = 0, maximum = 24, example = 7.5)]
+    pub sleep_hours: Option<f64>,
+    /// Aktivitas atau label bebas, misalnya hasil import dari Daylio
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(example = json!(["friends", "reading"]))]
+    pub tags: Option<Vec<String>>,
+}
+
+impl MoodDetails {
+    pub const MAX_TAGS: usize = 20;
+    pub const TAG_MAX_LENGTH: usize = 50;
+
+    /// Nama seri yang dipakai di analitik laporan
+    pub const METRICS: [&'static str; 3] = ["energy", "anxiety", "sleep_hours"];
+
+    pub fn metric(&self, name: &str) -> Option<f64> {
+        match name {
+            "energy" => self.energy.map(f64::from),
+            "anxiety" => self.anxiety.map(f64::from),
+            "sleep_hours" => self.sleep_hours,
+            _ => None,
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        for (name, value) in [("energy", self.energy), ("anxiety", self.anxiety)] {
+            if let Some(value) = value {
+                if !(1..=5).contains(&value) {
+                    return Err(format!("{} must be between 1 and 5", name));
+                }
+            }
+        }
+        if let Some(hours) = self.sleep_hours {
+            if !(0.0..=24.0).contains(&hours) {
+                return Err("sleep_hours must be between 0 and 24".to_string());
+            }
+        }
+        if let Some(ref tags) = self.tags {
+            if tags.len() > Self::MAX_TAGS {
+                return Err(format!("At most {} tags are allowed", Self::MAX_TAGS));
+            }
+            if tags.iter().any(|tag| tag.trim().is_empty() || tag.chars().count() > Self::TAG_MAX_LENGTH) {
+                return Err(format!("Tags must be between 1 and {} characters", Self::TAG_MAX_LENGTH));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.energy.is_none()
+            && self.anxiety.is_none()
+            && self.sleep_hours.is_none()
+            && self.tags.as_ref().is_none_or(|tags| tags.is_empty())
+    }
+
+    /// Baca kolom JSONB; nilai yang tidak sesuai skema diabaikan
+    pub fn from_json(value: Option<serde_json::Value>) -> Option<MoodDetails> {
+        value
+            .and_then(|value| serde_json::from_value::<MoodDetails>(value).ok())
+            .filter(|details| !details.is_empty())
+    }
+
+    pub fn to_json(&self) -> Option<serde_json::Value> {
+        if self.is_empty() {
+            None
+        } else {
+            serde_json::to_value(self).ok()
+        }
+    }
 }
 
-#[derive(Serialize)]
+/// Lokasi kasar saat mood dicatat: label tempat pilihan pengguna, koordinat, atau keduanya.
+/// Koordinat dibulatkan ke 2 desimal (sekitar 1 km) sebelum disimpan.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct MoodLocation {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(example = "Kantor")]
+    pub label: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(minimum = -90, maximum = 90, example = -6.21)]
+    pub latitude: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(minimum = -180, maximum = 180, example = 106.85)]
+    pub longitude: Option<f64>,
+}
+
+impl MoodLocation {
+    pub const LABEL_MAX_LENGTH: usize = 100;
+    /// Jumlah desimal koordinat yang disimpan
+    pub const COORDINATE_DECIMALS: i32 = 2;
+
+    /// Validasi lalu rapikan lokasi: label di-trim, koordinat dibulatkan.
+    /// Lokasi tanpa label maupun koordinat menjadi `None`.
+    pub fn normalize(self) -> Result<Option<MoodLocation>, String> {
+        let label = self.label.map(|label| label.trim().to_string()).filter(|label| !label.is_empty());
+        if label.as_ref().is_some_and(|label| label.chars().count() > Self::LABEL_MAX_LENGTH) {
+            return Err(format!("Place label must be at most {} characters", Self::LABEL_MAX_LENGTH));
+        }
+
+        let (latitude, longitude) = match (self.latitude, self.longitude) {
+            (Some(latitude), Some(longitude)) => {
+                if !(-90.0..=90.0).contains(&latitude) || !(-180.0..=180.0).contains(&longitude) {
+                    return Err("Latitude must be between -90 and 90 and longitude between -180 and 180".to_string());
+                }
+                (Some(round_coordinate(latitude)), Some(round_coordinate(longitude)))
+            }
+            (None, None) => (None, None),
+            _ => return Err("Latitude and longitude must be sent together".to_string()),
+        };
+
+        if label.is_none() && latitude.is_none() {
+            return Ok(None);
+        }
+        Ok(Some(MoodLocation { label, latitude, longitude }))
+    }
+
+    pub fn from_columns(label: Option<String>, latitude: Option<f64>, longitude: Option<f64>) -> Option<MoodLocation> {
+        (label.is_some() || latitude.is_some()).then_some(MoodLocation { label, latitude, longitude })
+    }
+}
+
+fn round_coordinate(value: f64) -> f64 {
+    let factor = 10f64.powi(MoodLocation::COORDINATE_DECIMALS);
+    (value * factor).round() / factor
+}
+
+#[derive(Serialize, ToSchema)]
 pub struct MoodResponse {
     pub id: i32,
     pub user_id: i32,
-    #[serde(serialize_with = "serialize_date")]
+    #[serde(with = "crate::utils::api_dates")]
+    #[schema(value_type = String, format = Date, example = "2025-07-23")]
     pub date: chrono::NaiveDate,
     pub mood: String,
     pub emoji: String,
     pub notes: Option<String>,
+    pub details: Option<MoodDetails>,
+    pub location: Option<MoodLocation>,
+    pub is_pinned: bool,
     pub created_at: NaiveDateTime,
     pub updated_at: Option<NaiveDateTime>,
 }
 
-fn serialize_date<S>(date: &chrono::NaiveDate, serializer: S) -> Result<S::Ok, S::Error>
-where
-    S: serde::Serializer,
-{
-    let formatted = date.format("%m-%d-%Y").to_string();
-    serializer.serialize_str(&formatted)
-}
-
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct CreateMoodRequest {
+    #[schema(example = "happy")]
     pub mood: String,
-    pub emoji: String,
+    /// Harus ada di MOOD_EMOJI_WHITELIST; kosong berarti emoji bawaan jenis mood
+    #[schema(example = "🙂")]
+    pub emoji: Option<String>,
     pub notes: Option<String>,
-    pub date: Option<String>, // ✅ Changed from &str to String
+    pub details: Option<MoodDetails>,
+    pub location: Option<MoodLocation>,
+    #[serde(default, with = "crate::utils::api_dates::option")]
+    #[schema(value_type = Option<String>, format = Date, example = "2025-07-23")]
+    pub date: Option<chrono::NaiveDate>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct UpdateMoodRequest {
     pub mood: Option<String>,
+    /// Jika `mood` diubah tanpa emoji, emoji ikut diganti ke emoji bawaan mood baru
     pub emoji: Option<String>,
-    pub notes: Option<String>,
-    pub date: Option<String>, // ✅ Changed from &str to String
+    /// `null` menghapus catatan; field yang tidak dikirim tidak diubah
+    #[serde(default)]
+    #[schema(value_type = Option<String>)]
+    pub notes: Patch<String>,
+    /// `null` menghapus detail
+    #[serde(default)]
+    #[schema(value_type = Option<MoodDetails>)]
+    pub details: Patch<MoodDetails>,
+    /// `null` menghapus lokasi
+    #[serde(default)]
+    #[schema(value_type = Option<MoodLocation>)]
+    pub location: Patch<MoodLocation>,
+    #[serde(default, with = "crate::utils::api_dates::option")]
+    #[schema(value_type = Option<String>, format = Date, example = "2025-07-23")]
+    pub date: Option<chrono::NaiveDate>,
 }
 
 #[derive(Debug, Serialize)]
@@ -72,13 +299,30 @@ pub struct MoodStats {
     pub average_mood_score: f64,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct MoodCount {
     pub mood: String,
     pub count: i64,
     pub percentage: f64,
 }
 
+/// Ringkasan seluruh mood pengguna tanpa interpretasi yang dilokalkan, untuk konsumen internal (gRPC)
+#[derive(Debug)]
+pub struct MoodSummary {
+    pub total_entries: i64,
+    pub average_score: f64,
+    pub mood_distribution: Vec<MoodCount>,
+}
+
+/// Interpretasi skor rata-rata mood: kode stabil untuk frontend dan label sesuai bahasa request
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ScoreInterpretation {
+    /// Salah satu dari `no_data`, `low`, `moderate`, `good`
+    #[schema(example = "good")]
+    pub code: String,
+    pub label: String,
+}
+
 // Enum untuk validasi mood
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum MoodType {
@@ -95,6 +339,14 @@ pub enum MoodType {
 }
 
 impl MoodType {
+    pub const ALL: [MoodType; 5] = [
+        MoodType::VerySad,
+        MoodType::Sad,
+        MoodType::Neutral,
+        MoodType::Happy,
+        MoodType::VeryHappy,
+    ];
+
     pub fn as_str(&self) -> &'static str {
         match self {
             MoodType::VerySad => "very sad",
@@ -105,6 +357,17 @@ impl MoodType {
         }
     }
 
+    /// Emoji bawaan, dipakai saat request atau data dari sumber lain tidak membawa emoji
+    pub fn default_emoji(&self) -> &'static str {
+        match self {
+            MoodType::VerySad => "😢",
+            MoodType::Sad => "😔",
+            MoodType::Neutral => "😐",
+            MoodType::Happy => "🙂",
+            MoodType::VeryHappy => "😄",
+        }
+    }
+
     pub fn score(&self) -> i32 {
         match self {
             MoodType::VerySad => 1,
@@ -114,15 +377,54 @@ impl MoodType {
             MoodType::VeryHappy => 5,
         }
     }
+}
+
+impl std::str::FromStr for MoodType {
+    type Err = String;
 
-    pub fn from_str(s: &str) -> Option<Self> {
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
-            "very sad" => Some(MoodType::VerySad),
-            "sad" => Some(MoodType::Sad),
-            "neutral" => Some(MoodType::Neutral),
-            "happy" => Some(MoodType::Happy),
-            "very happy" => Some(MoodType::VeryHappy),
-            _ => None,
+            "very sad" => Ok(MoodType::VerySad),
+            "sad" => Ok(MoodType::Sad),
+            "neutral" => Ok(MoodType::Neutral),
+            "happy" => Ok(MoodType::Happy),
+            "very happy" => Ok(MoodType::VeryHappy),
+            _ => Err(format!("Invalid mood type: {}", s)),
         }
     }
-}
\ No newline at end of file
+}
+/// Kriteria pencarian mood berdasarkan catatan; filter `None` tidak dipakai
+#[derive(Debug, Clone)]
+pub struct MoodSearchFilter {
+    pub query: String,
+    pub mood: Option<MoodType>,
+    pub start_date: Option<chrono::NaiveDate>,
+    pub end_date: Option<chrono::NaiveDate>,
+    pub limit: Option<i32>,
+    pub offset: Option<i32>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn location_is_trimmed_and_rounded() {
+        let location = MoodLocation {
+            label: Some("  Kantor ".to_string()),
+            latitude: Some(-6.208763),
+            longitude: Some(106.845599),
+        };
+        assert_eq!(
+            location.normalize(),
+            Ok(Some(MoodLocation {
+                label: Some("Kantor".to_string()),
+                latitude: Some(-6.21),
+                longitude: Some(106.85),
+            }))
+        );
+        assert_eq!(MoodLocation { label: Some(" ".to_string()), ..Default::default() }.normalize(), Ok(None));
+        assert!(MoodLocation { latitude: Some(1.0), ..Default::default() }.normalize().is_err());
+        assert!(MoodLocation { latitude: Some(91.0), longitude: Some(0.0), label: None }.normalize().is_err());
+    }
+}
@@ -0,0 +1,127 @@
+use chrono::{NaiveDateTime, NaiveTime};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Jalur pengiriman notifikasi
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationChannel {
+    Email,
+    Push,
+}
+
+impl NotificationChannel {
+    pub const ALL: [NotificationChannel; 2] = [NotificationChannel::Email, NotificationChannel::Push];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationChannel::Email => "email",
+            NotificationChannel::Push => "push",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|channel| channel.as_str() == value)
+    }
+}
+
+/// Jenis notifikasi yang bisa dimatikan pengguna
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationCategory {
+    /// Pengingat harian mencatat mood
+    Reminder,
+    /// Alert penurunan mood dari job insight
+    InsightAlert,
+    /// Perubahan status permintaan bantuan oleh admin
+    HelpRequest,
+    /// Pengingat janji temu dengan psikolog
+    Appointment,
+}
+
+impl NotificationCategory {
+    pub const ALL: [NotificationCategory; 4] = [
+        NotificationCategory::Reminder,
+        NotificationCategory::InsightAlert,
+        NotificationCategory::HelpRequest,
+        NotificationCategory::Appointment,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationCategory::Reminder => "reminder",
+            NotificationCategory::InsightAlert => "insight_alert",
+            NotificationCategory::HelpRequest => "help_request",
+            NotificationCategory::Appointment => "appointment",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|category| category.as_str() == value)
+    }
+}
+
+/// Preferensi notifikasi di dalam `UserSettings`
+#[derive(Serialize, Deserialize, Default, Debug, Clone, ToSchema)]
+pub struct NotificationPreferences {
+    /// Channel yang boleh dipakai ("email", "push"); kosong berarti semua
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(example = json!(["push"]))]
+    pub channels: Option<Vec<String>>,
+    /// Kategori yang ingin diterima ("reminder", "insight_alert", "help_request", "appointment").
+    /// Jika tidak diisi, semua aktif kecuali alert insight yang mengikuti `insight_notifications`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(example = json!(["reminder", "insight_alert"]))]
+    pub categories: Option<Vec<String>>,
+    /// Rentang waktu tanpa notifikasi, dalam zona waktu pengguna
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quiet_hours: Option<QuietHours>,
+}
+
+/// Jam tenang (HH:MM); boleh melewati tengah malam, misalnya 22:00-07:00
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct QuietHours {
+    #[schema(example = "22:00")]
+    pub start: String,
+    #[schema(example = "07:00")]
+    pub end: String,
+}
+
+impl QuietHours {
+    pub fn parse_times(&self) -> Option<(NaiveTime, NaiveTime)> {
+        let start = NaiveTime::parse_from_str(&self.start, "%H:%M").ok()?;
+        let end = NaiveTime::parse_from_str(&self.end, "%H:%M").ok()?;
+        Some((start, end))
+    }
+
+    /// Jika `local_now` berada dalam jam tenang, waktu lokal saat jam tenang berakhir
+    pub fn ends_after(&self, local_now: NaiveDateTime) -> Option<NaiveDateTime> {
+        let (start, end) = self.parse_times()?;
+        let time = local_now.time();
+        let date = local_now.date();
+
+        if start < end {
+            (time >= start && time < end).then(|| date.and_time(end))
+        } else if start > end {
+            if time >= start {
+                date.succ_opt().map(|next| next.and_time(end))
+            } else if time < end {
+                Some(date.and_time(end))
+            } else {
+                None
+            }
+        } else {
+            None
+        }
+    }
+}
+
+/// Notifikasi yang dikirim lewat `notification_service::dispatch`
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub category: NotificationCategory,
+    /// Channel yang didukung notifikasi ini, sebelum disaring preferensi pengguna
+    pub channels: &'static [NotificationChannel],
+    pub title: String,
+    pub body: String,
+    /// Isi email; default sama dengan `body`
+    pub email_body: Option<String>,
+}
@@ -0,0 +1,46 @@
+use diesel::prelude::*;
+use chrono::NaiveDateTime;
+
+pub const PROVIDER_GOOGLE: &str = "google";
+
+#[derive(Queryable, Selectable, Debug)]
+#[diesel(table_name = crate::schema::oauth_accounts)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct OAuthAccount {
+    pub id: i32,
+    pub user_id: i32,
+    pub provider: String,
+    pub provider_user_id: String,
+    pub refresh_token_encrypted: Option<String>,
+    pub access_token_encrypted: Option<String>,
+    pub access_token_expires_at: Option<NaiveDateTime>,
+    pub scope: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+/// Dipakai untuk insert dan update saat login; field `None` tidak menimpa nilai yang sudah tersimpan,
+/// karena Google hanya mengirim refresh token saat pengguna memberi izin
+#[derive(Insertable, AsChangeset, Debug)]
+#[diesel(table_name = crate::schema::oauth_accounts)]
+pub struct NewOAuthAccount<'a> {
+    pub user_id: i32,
+    pub provider: &'a str,
+    pub provider_user_id: &'a str,
+    pub refresh_token_encrypted: Option<String>,
+    pub access_token_encrypted: Option<String>,
+    pub access_token_expires_at: Option<NaiveDateTime>,
+    pub scope: Option<String>,
+    pub updated_at: NaiveDateTime,
+}
+
+/// Hasil refresh access token
+#[derive(AsChangeset, Debug)]
+#[diesel(table_name = crate::schema::oauth_accounts)]
+pub struct OAuthTokenUpdate {
+    pub access_token_encrypted: Option<String>,
+    pub access_token_expires_at: Option<NaiveDateTime>,
+    /// Hanya diisi jika penyedia merotasi refresh token
+    pub refresh_token_encrypted: Option<String>,
+    pub updated_at: NaiveDateTime,
+}
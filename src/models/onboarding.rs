@@ -0,0 +1,70 @@
+use diesel::prelude::*;
+use chrono::NaiveDateTime;
+use serde::Serialize;
+use utoipa::ToSchema;
+
+/// Langkah checklist onboarding yang ditampilkan frontend
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OnboardingStep {
+    ProfileFilled,
+    FirstMood,
+    FirstJournal,
+    ReminderSet,
+}
+
+impl OnboardingStep {
+    pub const ALL: [OnboardingStep; 4] = [
+        OnboardingStep::ProfileFilled,
+        OnboardingStep::FirstMood,
+        OnboardingStep::FirstJournal,
+        OnboardingStep::ReminderSet,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OnboardingStep::ProfileFilled => "profile_filled",
+            OnboardingStep::FirstMood => "first_mood",
+            OnboardingStep::FirstJournal => "first_journal",
+            OnboardingStep::ReminderSet => "reminder_set",
+        }
+    }
+}
+
+#[derive(Queryable, Selectable, Insertable, Debug, Default)]
+#[diesel(table_name = crate::schema::user_onboarding)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct UserOnboarding {
+    pub user_id: i32,
+    pub profile_completed_at: Option<NaiveDateTime>,
+    pub first_mood_at: Option<NaiveDateTime>,
+    pub first_journal_at: Option<NaiveDateTime>,
+    pub reminder_set_at: Option<NaiveDateTime>,
+    pub updated_at: NaiveDateTime,
+}
+
+impl UserOnboarding {
+    pub fn completed_at(&self, step: OnboardingStep) -> Option<NaiveDateTime> {
+        match step {
+            OnboardingStep::ProfileFilled => self.profile_completed_at,
+            OnboardingStep::FirstMood => self.first_mood_at,
+            OnboardingStep::FirstJournal => self.first_journal_at,
+            OnboardingStep::ReminderSet => self.reminder_set_at,
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct OnboardingStepStatus {
+    #[schema(example = "first_mood")]
+    pub step: String,
+    pub completed: bool,
+    pub completed_at: Option<NaiveDateTime>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct OnboardingStatus {
+    pub steps: Vec<OnboardingStepStatus>,
+    pub completed_steps: usize,
+    pub total_steps: usize,
+    pub is_complete: bool,
+}
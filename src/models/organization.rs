@@ -0,0 +1,197 @@
+use diesel::prelude::*;
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use crate::models::mood::MoodCount;
+use crate::utils::api_dates;
+
+/// Peran anggota organisasi; admin (terapis) mengelola anggota dan melihat data agregat
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrganizationRole {
+    Admin,
+    Member,
+}
+
+impl OrganizationRole {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OrganizationRole::Admin => "admin",
+            OrganizationRole::Member => "member",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "admin" => Some(OrganizationRole::Admin),
+            "member" => Some(OrganizationRole::Member),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = crate::schema::organizations)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Organization {
+    pub id: i32,
+    pub name: String,
+    pub created_by: Option<i32>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::organizations)]
+pub struct NewOrganization<'a> {
+    pub name: &'a str,
+    pub created_by: Option<i32>,
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = crate::schema::organization_members)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct OrganizationMember {
+    pub organization_id: i32,
+    pub user_id: i32,
+    pub role: String,
+    pub data_sharing_consent: bool,
+    pub consent_updated_at: Option<NaiveDateTime>,
+    pub joined_at: NaiveDateTime,
+}
+
+impl OrganizationMember {
+    pub fn is_admin(&self) -> bool {
+        self.role == OrganizationRole::Admin.as_str()
+    }
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::organization_members)]
+pub struct NewOrganizationMember<'a> {
+    pub organization_id: i32,
+    pub user_id: i32,
+    pub role: &'a str,
+}
+
+#[derive(Queryable, Selectable, Debug)]
+#[diesel(table_name = crate::schema::organization_invitations)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct OrganizationInvitation {
+    pub id: i32,
+    pub organization_id: i32,
+    pub email: String,
+    pub role: String,
+    pub token: String,
+    pub invited_by: Option<i32>,
+    pub expires_at: NaiveDateTime,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::organization_invitations)]
+pub struct NewOrganizationInvitation<'a> {
+    pub organization_id: i32,
+    pub email: &'a str,
+    pub role: &'a str,
+    pub token: &'a str,
+    pub invited_by: Option<i32>,
+    pub expires_at: NaiveDateTime,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateOrganizationRequest {
+    #[schema(example = "Klinik Sehat Jiwa")]
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct InviteMemberRequest {
+    pub email: String,
+    /// "member" (default) atau "admin"
+    #[schema(example = "member")]
+    pub role: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AcceptInvitationRequest {
+    /// Kode undangan dari email
+    pub token: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateConsentRequest {
+    /// Izinkan admin organisasi melihat ringkasan mood kamu
+    pub data_sharing_consent: bool,
+}
+
+/// Organisasi dilihat dari sisi pengguna yang sedang login
+#[derive(Serialize, ToSchema)]
+pub struct OrganizationResponse {
+    pub id: i32,
+    pub name: String,
+    /// Peran pengguna di organisasi ini
+    #[schema(example = "member")]
+    pub role: String,
+    pub data_sharing_consent: bool,
+    pub joined_at: NaiveDateTime,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct OrganizationMemberResponse {
+    pub user_id: i32,
+    pub username: String,
+    pub role: String,
+    pub data_sharing_consent: bool,
+    pub joined_at: NaiveDateTime,
+}
+
+/// Token undangan tidak dikembalikan; hanya dikirim ke email yang diundang
+#[derive(Serialize, ToSchema)]
+pub struct InvitationResponse {
+    pub id: i32,
+    pub organization_id: i32,
+    pub email: String,
+    pub role: String,
+    pub expires_at: NaiveDateTime,
+}
+
+#[derive(Deserialize, IntoParams)]
+pub struct OrganizationSummaryQuery {
+    /// Start date (YYYY-MM-DD)
+    #[serde(with = "api_dates")]
+    #[param(value_type = String, format = Date, example = "2025-07-01")]
+    pub start_date: NaiveDate,
+    /// End date (YYYY-MM-DD)
+    #[serde(with = "api_dates")]
+    #[param(value_type = String, format = Date, example = "2025-07-31")]
+    pub end_date: NaiveDate,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct MemberMoodSummary {
+    pub user_id: i32,
+    pub username: String,
+    pub mood_entries: i64,
+    pub average_score: Option<f64>,
+    #[serde(with = "api_dates::option")]
+    #[schema(value_type = Option<String>, format = Date)]
+    pub last_mood_date: Option<NaiveDate>,
+}
+
+/// Ringkasan mood anggota organisasi. Hanya anggota yang memberi persetujuan
+/// yang dihitung; anggota lain hanya muncul di `member_count`.
+#[derive(Serialize, ToSchema)]
+pub struct OrganizationSummary {
+    pub organization_id: i32,
+    #[serde(with = "api_dates")]
+    #[schema(value_type = String, format = Date)]
+    pub start_date: NaiveDate,
+    #[serde(with = "api_dates")]
+    #[schema(value_type = String, format = Date)]
+    pub end_date: NaiveDate,
+    pub member_count: usize,
+    pub consenting_members: usize,
+    pub mood_entries: i64,
+    pub average_score: Option<f64>,
+    pub mood_distribution: Vec<MoodCount>,
+    pub members: Vec<MemberMoodSummary>,
+}
@@ -0,0 +1,44 @@
+use diesel::prelude::*;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Queryable, Selectable, Debug)]
+#[diesel(table_name = crate::schema::password_reset_tokens)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct PasswordResetToken {
+    pub id: i32,
+    pub user_id: i32,
+    pub token: String,
+    pub expires_at: NaiveDateTime,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::password_reset_tokens)]
+pub struct NewPasswordResetToken {
+    pub user_id: i32,
+    pub token: String,
+    pub expires_at: NaiveDateTime,
+    pub created_at: NaiveDateTime,
+}
+
+/// Request body untuk meminta link reset password (lupa password)
+#[derive(Deserialize, ToSchema)]
+pub struct CheckEmailRequest {
+    pub email: String,
+}
+
+/// Respons yang sama untuk email terdaftar maupun tidak, agar tidak bisa dipakai menebak akun
+#[derive(Serialize, ToSchema)]
+pub struct PasswordResetRequestedResponse {
+    pub message: String,
+}
+
+/// Request body untuk reset password dengan token dari email
+#[derive(Deserialize, ToSchema)]
+pub struct ResetPasswordRequest {
+    pub token: String,
+    pub new_password: String,
+    pub confirm_password: String,
+}
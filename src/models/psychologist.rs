@@ -0,0 +1,196 @@
+use diesel::prelude::*;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = crate::schema::psychologists)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Psychologist {
+    pub id: i32,
+    pub name: String,
+    pub specialization: String,
+    pub languages: Vec<String>,
+    pub bio: Option<String>,
+    pub is_active: bool,
+    pub created_at: NaiveDateTime,
+    pub user_id: Option<i32>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::psychologists)]
+pub struct NewPsychologist<'a> {
+    pub name: &'a str,
+    pub specialization: &'a str,
+    pub languages: &'a [String],
+    pub bio: Option<&'a str>,
+    pub user_id: Option<i32>,
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = crate::schema::psychologist_slots)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct PsychologistSlot {
+    pub id: i32,
+    pub psychologist_id: i32,
+    pub starts_at: NaiveDateTime,
+    pub ends_at: NaiveDateTime,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::psychologist_slots)]
+pub struct NewPsychologistSlot {
+    pub psychologist_id: i32,
+    pub starts_at: NaiveDateTime,
+    pub ends_at: NaiveDateTime,
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = crate::schema::psychologist_requests)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct PsychologistRequest {
+    pub id: i32,
+    pub user_id: i32,
+    pub name: String,
+    pub email: String,
+    pub message: String,
+    pub preferred_time: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub psychologist_id: Option<i32>,
+    pub slot_id: Option<i32>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::psychologist_requests)]
+pub struct NewPsychologistRequest<'a> {
+    pub user_id: i32,
+    pub name: &'a str,
+    pub email: &'a str,
+    pub message: &'a str,
+    pub preferred_time: Option<&'a str>,
+    pub psychologist_id: Option<i32>,
+    pub slot_id: Option<i32>,
+}
+
+#[derive(Deserialize, IntoParams)]
+pub struct PsychologistQuery {
+    /// Filter spesialisasi, tanpa membedakan huruf besar/kecil
+    #[param(example = "anxiety")]
+    pub specialization: Option<String>,
+    /// Filter bahasa (kode ISO 639-1)
+    #[param(example = "id")]
+    pub language: Option<String>,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct CreatePsychologistRequest {
+    pub name: String,
+    #[schema(example = "anxiety")]
+    pub specialization: String,
+    /// Kode bahasa ISO 639-1
+    #[schema(example = json!(["id", "en"]))]
+    pub languages: Vec<String>,
+    pub bio: Option<String>,
+    /// Akun pengguna milik psikolog, dipakai untuk membalas pesan klien
+    pub user_id: Option<i32>,
+}
+
+/// Waktu dalam UTC
+#[derive(Deserialize, ToSchema)]
+pub struct SlotInput {
+    pub starts_at: NaiveDateTime,
+    pub ends_at: NaiveDateTime,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct CreateSlotsRequest {
+    pub slots: Vec<SlotInput>,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct CreatePsychologistBooking {
+    pub psychologist_id: i32,
+    /// Jadwal yang dipilih; tanpa jadwal, `preferred_time` dipakai sebagai catatan
+    pub slot_id: Option<i32>,
+    pub message: String,
+    pub preferred_time: Option<String>,
+    /// Default: username akun
+    pub name: Option<String>,
+    /// Default: email akun
+    pub email: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PsychologistResponse {
+    pub id: i32,
+    pub name: String,
+    pub specialization: String,
+    pub languages: Vec<String>,
+    pub bio: Option<String>,
+}
+
+impl From<Psychologist> for PsychologistResponse {
+    fn from(psychologist: Psychologist) -> Self {
+        PsychologistResponse {
+            id: psychologist.id,
+            name: psychologist.name,
+            specialization: psychologist.specialization,
+            languages: psychologist.languages,
+            bio: psychologist.bio,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SlotResponse {
+    pub id: i32,
+    pub starts_at: NaiveDateTime,
+    pub ends_at: NaiveDateTime,
+}
+
+impl From<PsychologistSlot> for SlotResponse {
+    fn from(slot: PsychologistSlot) -> Self {
+        SlotResponse {
+            id: slot.id,
+            starts_at: slot.starts_at,
+            ends_at: slot.ends_at,
+        }
+    }
+}
+
+/// Profil psikolog beserta jadwal mendatang yang belum dipesan
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PsychologistDetail {
+    #[serde(flatten)]
+    pub psychologist: PsychologistResponse,
+    pub available_slots: Vec<SlotResponse>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PsychologistRequestResponse {
+    pub id: i32,
+    pub psychologist_id: Option<i32>,
+    pub name: String,
+    pub email: String,
+    pub message: String,
+    pub preferred_time: Option<String>,
+    /// Jadwal yang dipesan, jika ada
+    pub slot: Option<SlotResponse>,
+    pub created_at: NaiveDateTime,
+}
+
+impl PsychologistRequestResponse {
+    pub fn new(request: PsychologistRequest, slot: Option<PsychologistSlot>) -> Self {
+        PsychologistRequestResponse {
+            id: request.id,
+            psychologist_id: request.psychologist_id,
+            name: request.name,
+            email: request.email,
+            message: request.message,
+            preferred_time: request.preferred_time,
+            slot: slot.map(SlotResponse::from),
+            created_at: request.created_at,
+        }
+    }
+}
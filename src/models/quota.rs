@@ -0,0 +1,51 @@
+use chrono::NaiveDate;
+use diesel::prelude::*;
+use crate::config::app_config::AppConfig;
+
+/// Operasi berat yang dibatasi per pengguna per hari (UTC)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuotaOperation {
+    /// Export data (arsip Markdown jurnal, semua mood)
+    Export,
+    ReportPdf,
+    Import,
+}
+
+impl QuotaOperation {
+    pub const ALL: [QuotaOperation; 3] = [QuotaOperation::Export, QuotaOperation::ReportPdf, QuotaOperation::Import];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QuotaOperation::Export => "export",
+            QuotaOperation::ReportPdf => "report_pdf",
+            QuotaOperation::Import => "import",
+        }
+    }
+
+    /// Batas harian dari konfigurasi; 0 berarti tanpa batas
+    pub fn daily_limit(&self, config: &AppConfig) -> i32 {
+        match self {
+            QuotaOperation::Export => config.quota_exports_per_day,
+            QuotaOperation::ReportPdf => config.quota_report_pdfs_per_day,
+            QuotaOperation::Import => config.quota_imports_per_day,
+        }
+    }
+
+    /// Pesan 429 (bahasa Inggris, diterjemahkan lewat template)
+    pub fn exceeded_message(&self, limit: i32) -> String {
+        match self {
+            QuotaOperation::Export => format!("Daily export limit reached ({} per day)", limit),
+            QuotaOperation::ReportPdf => format!("Daily PDF report limit reached ({} per day)", limit),
+            QuotaOperation::Import => format!("Daily import limit reached ({} per day)", limit),
+        }
+    }
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::usage_quotas)]
+pub struct NewUsageQuota<'a> {
+    pub user_id: i32,
+    pub operation: &'a str,
+    pub period_start: NaiveDate,
+    pub used: i32,
+}
@@ -0,0 +1,123 @@
+use chrono::NaiveDate;
+use serde::Serialize;
+use utoipa::ToSchema;
+use crate::models::mood::{MoodCount, ScoreInterpretation};
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct DailyScore {
+    #[serde(with = "crate::utils::api_dates")]
+    #[schema(value_type = String, format = Date, example = "2025-03-14")]
+    pub date: NaiveDate,
+    pub mood: String,
+    pub score: i32,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WeeklyAverage {
+    #[serde(with = "crate::utils::api_dates")]
+    #[schema(value_type = String, format = Date)]
+    pub week_start: NaiveDate,
+    #[serde(with = "crate::utils::api_dates")]
+    #[schema(value_type = String, format = Date)]
+    pub week_end: NaiveDate,
+    pub entries: i64,
+    pub average_score: Option<f64>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MonthlyAverage {
+    #[schema(example = "2025-03")]
+    pub month: String,
+    pub entries: i64,
+    pub average_score: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct StreakSummary {
+    #[serde(with = "crate::utils::api_dates")]
+    #[schema(value_type = String, format = Date)]
+    pub start_date: NaiveDate,
+    #[serde(with = "crate::utils::api_dates")]
+    #[schema(value_type = String, format = Date)]
+    pub end_date: NaiveDate,
+    pub length: i32,
+}
+
+/// Statistik mood untuk rentang tanggal bebas (`GET /moods/stats?start_date=&end_date=`)
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MoodRangeStats {
+    #[serde(with = "crate::utils::api_dates")]
+    #[schema(value_type = String, format = Date)]
+    pub start_date: NaiveDate,
+    #[serde(with = "crate::utils::api_dates")]
+    #[schema(value_type = String, format = Date)]
+    pub end_date: NaiveDate,
+    pub total_entries: i64,
+    pub average_score: Option<f64>,
+    pub interpretation: ScoreInterpretation,
+    pub mood_distribution: Vec<MoodCount>,
+    pub best_day: Option<DailyScore>,
+    pub worst_day: Option<DailyScore>,
+}
+
+/// Ringkasan "month in review"
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MonthlyReport {
+    #[schema(example = "2025-03")]
+    pub month: String,
+    #[serde(with = "crate::utils::api_dates")]
+    #[schema(value_type = String, format = Date)]
+    pub start_date: NaiveDate,
+    #[serde(with = "crate::utils::api_dates")]
+    #[schema(value_type = String, format = Date)]
+    pub end_date: NaiveDate,
+    pub total_entries: i64,
+    pub average_score: Option<f64>,
+    pub interpretation: ScoreInterpretation,
+    pub daily_scores: Vec<DailyScore>,
+    pub weekly_averages: Vec<WeeklyAverage>,
+    pub best_day: Option<DailyScore>,
+    pub worst_day: Option<DailyScore>,
+    pub mood_distribution: Vec<MoodCount>,
+    /// Seri tambahan dari detail mood (energi, kecemasan, jam tidur) dan data kesehatan (langkah, tidur)
+    pub detail_series: Vec<DetailSeries>,
+    pub journal_count: i64,
+    pub longest_streak: Option<StreakSummary>,
+}
+
+/// Ringkasan "year in review"
+#[derive(Debug, Serialize, ToSchema)]
+pub struct YearlyReport {
+    pub year: i32,
+    pub total_entries: i64,
+    pub average_score: Option<f64>,
+    pub interpretation: ScoreInterpretation,
+    pub monthly_averages: Vec<MonthlyAverage>,
+    pub best_month: Option<String>,
+    pub worst_month: Option<String>,
+    pub mood_distribution: Vec<MoodCount>,
+    /// Seri tambahan dari detail mood (energi, kecemasan, jam tidur) dan data kesehatan (langkah, tidur)
+    pub detail_series: Vec<DetailSeries>,
+    pub journal_count: i64,
+    pub longest_streak: Option<StreakSummary>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct DetailPoint {
+    #[serde(with = "crate::utils::api_dates")]
+    #[schema(value_type = String, format = Date, example = "2025-03-14")]
+    pub date: NaiveDate,
+    pub value: f64,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct DetailSeries {
+    /// Salah satu dari `energy`, `anxiety`, `sleep_hours` (dari detail mood), atau `steps`,
+    /// `tracked_sleep_hours` (dari Google Fit / Apple Health)
+    #[schema(example = "energy")]
+    pub name: String,
+    pub points: Vec<DetailPoint>,
+    pub average: Option<f64>,
+    /// Korelasi Pearson dengan skor mood pada hari yang sama (-1 sampai 1); null jika data kurang
+    pub mood_correlation: Option<f64>,
+}
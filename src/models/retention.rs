@@ -0,0 +1,28 @@
+use diesel::prelude::*;
+use chrono::NaiveDateTime;
+use crate::schema::journal_retention_states;
+
+/// Status retensi jurnal pengguna; baris ada setelah email pemberitahuan terkirim
+#[derive(Debug, Queryable, Selectable)]
+#[diesel(table_name = journal_retention_states)]
+pub struct JournalRetentionState {
+    pub user_id: i32,
+    pub notice_sent_at: NaiveDateTime,
+    pub last_purged_at: Option<NaiveDateTime>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = journal_retention_states)]
+pub struct NewJournalRetentionState {
+    pub user_id: i32,
+    pub notice_sent_at: NaiveDateTime,
+}
+
+/// Hasil satu putaran job retensi
+#[derive(Debug, Default)]
+pub struct RetentionRun {
+    /// Pengguna yang menerima email pemberitahuan sebelum penghapusan pertama
+    pub notified: usize,
+    /// Jumlah jurnal yang dihapus
+    pub purged: usize,
+}
@@ -0,0 +1,47 @@
+use diesel::prelude::*;
+use chrono::NaiveDateTime;
+use serde::Serialize;
+use utoipa::ToSchema;
+
+#[derive(Queryable, Selectable, Debug)]
+#[diesel(table_name = crate::schema::login_attempts)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct LoginAttempt {
+    pub id: i32,
+    pub user_id: Option<i32>,
+    pub email: String,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub success: bool,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::login_attempts)]
+pub struct NewLoginAttempt {
+    pub user_id: Option<i32>,
+    pub email: String,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub success: bool,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct LoginAttemptResponse {
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub success: bool,
+    pub created_at: NaiveDateTime,
+}
+
+impl From<LoginAttempt> for LoginAttemptResponse {
+    fn from(attempt: LoginAttempt) -> Self {
+        LoginAttemptResponse {
+            ip_address: attempt.ip_address,
+            user_agent: attempt.user_agent,
+            success: attempt.success,
+            created_at: attempt.created_at,
+        }
+    }
+}
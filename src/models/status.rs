@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Kondisi satu pool koneksi database
+#[derive(Debug, Clone, Copy, Serialize, ToSchema)]
+pub struct PoolStats {
+    pub max_size: u32,
+    /// Koneksi yang sedang terbuka, termasuk yang idle
+    pub connections: u32,
+    pub idle_connections: u32,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DatabaseStatus {
+    /// Apakah primary bisa dihubungi saat pemeriksaan
+    pub primary_available: bool,
+    pub primary: PoolStats,
+    /// Kosong jika DATABASE_REPLICA_URL tidak diatur
+    pub replica: Option<PoolStats>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReadinessResponse {
+    /// `ready` atau `unavailable`
+    #[schema(example = "ready")]
+    pub status: String,
+    pub database: DatabaseStatus,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MaintenanceStatus {
+    /// Saat aktif, request yang mengubah data ditolak dengan 503; request baca tetap dilayani
+    pub enabled: bool,
+    /// Nilai header `Retry-After` untuk request yang ditolak, dalam detik
+    pub retry_after_secs: u64,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateMaintenanceRequest {
+    pub enabled: bool,
+    /// Kosong berarti tetap memakai nilai sebelumnya
+    pub retry_after_secs: Option<u64>,
+}
@@ -1,8 +1,11 @@
 use diesel::prelude::*;
 use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use crate::models::notification::NotificationPreferences;
+use crate::utils::patch::Patch;
 
-#[derive(Queryable, Selectable, Debug, Serialize)]
+#[derive(Queryable, Selectable, Debug, Clone, Serialize)]
 #[diesel(table_name = crate::schema::users)]
 #[diesel(check_for_backend(diesel::pg::Pg))]
 pub struct User {
@@ -16,6 +19,18 @@ pub struct User {
     pub avatar: Option<String>, 
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
+    pub is_guest: bool,
+    pub guest_device_id: Option<String>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::users)]
+pub struct NewGuestUser<'a> {
+    pub username: &'a str,
+    pub email: &'a str,
+    pub password: &'a str,
+    pub is_guest: bool,
+    pub guest_device_id: &'a str,
 }
 
 #[derive(Insertable, Debug, Deserialize)]
@@ -27,11 +42,12 @@ pub struct NewUser {
     pub settings: Option<String>,
     pub age: Option<i32>,
     pub gender: Option<String>,
+    pub avatar: Option<String>,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct UserResponse {
     pub id: i32,
     pub username: String,
@@ -43,4 +59,123 @@ pub struct UserResponse {
     pub settings: Option<String>,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
-}
\ No newline at end of file
+    /// Akun tamu tanpa email; upgrade lewat POST /auth/upgrade
+    pub is_guest: bool,
+}
+
+impl From<User> for UserResponse {
+    fn from(user: User) -> Self {
+        UserResponse {
+            id: user.id,
+            username: user.username,
+            email: user.email,
+            password: user.password,
+            age: user.age,
+            gender: user.gender,
+            avatar: user.avatar,
+            settings: user.settings,
+            created_at: user.created_at,
+            updated_at: user.updated_at,
+            is_guest: user.is_guest,
+        }
+    }
+}
+
+/// Pengaturan pengguna yang disimpan sebagai JSON di kolom `users.settings`
+#[derive(Serialize, Deserialize, Default, Debug, Clone, ToSchema)]
+pub struct UserSettings {
+    /// Zona waktu IANA, misalnya "Asia/Jakarta"
+    #[schema(example = "Asia/Jakarta")]
+    pub timezone: Option<String>,
+    /// Jam pengingat harian untuk mencatat mood (HH:MM, zona waktu pengguna)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(example = "20:00")]
+    pub reminder_time: Option<String>,
+    /// Kirim pengingat harian juga lewat email, berisi tombol check-in mood sekali klik
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email_checkin: Option<bool>,
+    /// Bahasa pesan API ("en" atau "id"); mengalahkan header Accept-Language
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(example = "id")]
+    pub language: Option<String>,
+    /// Kirim email saat terdeteksi penurunan mood yang berkelanjutan
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub insight_notifications: Option<bool>,
+    /// Channel, kategori dan jam tenang notifikasi
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notifications: Option<NotificationPreferences>,
+    /// Hapus otomatis jurnal yang lebih tua dari sekian tahun; jurnal yang disematkan tidak dihapus
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(example = 3)]
+    pub journal_retention_years: Option<u32>,
+}
+
+impl UserSettings {
+    /// Baca settings dari kolom database; nilai kosong atau bukan JSON dianggap default
+    pub fn parse(raw: Option<&str>) -> Self {
+        raw.and_then(|value| serde_json::from_str(value).ok())
+            .unwrap_or_default()
+    }
+}
+
+/// Request body untuk edit profil - ditambahkan avatar
+#[derive(Deserialize, ToSchema)]
+pub struct EditProfileRequest {
+    pub username: String,
+    pub email: String,
+    pub age: Option<i32>,
+    pub gender: Option<String>,
+    pub avatar: Option<String>, // Tambahan field avatar
+}
+
+/// Request body PATCH /user/profile: hanya field yang dikirim yang diubah,
+/// `null` mengosongkan age, gender atau avatar
+#[derive(Deserialize, ToSchema)]
+pub struct PatchProfileRequest {
+    #[serde(default)]
+    #[schema(value_type = Option<String>)]
+    pub username: Patch<String>,
+    /// Hanya boleh sama dengan email sekarang; ganti email lewat /user/email/change
+    #[serde(default)]
+    #[schema(value_type = Option<String>)]
+    pub email: Patch<String>,
+    #[serde(default)]
+    #[schema(value_type = Option<i32>)]
+    pub age: Patch<i32>,
+    #[serde(default)]
+    #[schema(value_type = Option<String>)]
+    pub gender: Patch<String>,
+    #[serde(default)]
+    #[schema(value_type = Option<String>)]
+    pub avatar: Patch<String>,
+}
+
+/// Kolom profil yang diubah; `None` berarti kolom tidak disentuh
+#[derive(AsChangeset, Debug, Default)]
+#[diesel(table_name = crate::schema::users)]
+pub struct UserProfileChanges {
+    pub username: Option<String>,
+    pub age: Option<Option<i32>>,
+    pub gender: Option<Option<String>>,
+    pub avatar: Option<Option<String>>,
+}
+
+impl UserProfileChanges {
+    pub fn is_empty(&self) -> bool {
+        self.username.is_none() && self.age.is_none() && self.gender.is_none() && self.avatar.is_none()
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct AvatarResponse {
+    /// URL avatar yang tersimpan di profil
+    pub avatar: String,
+}
+
+/// Form multipart untuk upload avatar
+#[derive(ToSchema)]
+pub struct AvatarUploadForm {
+    /// File gambar PNG, JPEG atau WebP
+    #[schema(value_type = String, format = Binary)]
+    pub avatar: Vec<u8>,
+}
@@ -0,0 +1,114 @@
+use diesel::prelude::*;
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Status entri antrian pengiriman webhook
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeliveryStatus {
+    Pending,
+    Delivered,
+    Failed,
+}
+
+impl DeliveryStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DeliveryStatus::Pending => "pending",
+            DeliveryStatus::Delivered => "delivered",
+            DeliveryStatus::Failed => "failed",
+        }
+    }
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = crate::schema::user_webhooks)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct UserWebhook {
+    pub id: i32,
+    pub user_id: i32,
+    pub platform: String,
+    pub url: String,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::user_webhooks)]
+pub struct NewUserWebhook<'a> {
+    pub user_id: i32,
+    pub platform: &'a str,
+    pub url: &'a str,
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = crate::schema::webhook_deliveries)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct WebhookDelivery {
+    pub id: i32,
+    pub webhook_id: i32,
+    pub period_start: NaiveDate,
+    pub payload: String,
+    pub status: String,
+    pub attempts: i32,
+    pub next_attempt_at: NaiveDateTime,
+    pub last_error: Option<String>,
+    pub delivered_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::webhook_deliveries)]
+pub struct NewWebhookDelivery {
+    pub webhook_id: i32,
+    pub period_start: NaiveDate,
+    pub payload: String,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct CreateWebhookRequest {
+    /// `slack` atau `discord`
+    #[schema(example = "slack")]
+    pub platform: String,
+    /// URL incoming webhook dari Slack atau Discord
+    #[schema(example = "https://hooks.slack.com/services/T000/B000/XXXX")]
+    pub url: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WebhookDeliveryResponse {
+    /// Awal minggu yang diringkas
+    #[serde(with = "crate::utils::api_dates")]
+    #[schema(value_type = String, format = Date, example = "2025-03-10")]
+    pub period_start: NaiveDate,
+    /// `pending`, `delivered` atau `failed`
+    #[schema(example = "delivered")]
+    pub status: String,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub delivered_at: Option<NaiveDateTime>,
+}
+
+impl From<WebhookDelivery> for WebhookDeliveryResponse {
+    fn from(delivery: WebhookDelivery) -> Self {
+        WebhookDeliveryResponse {
+            period_start: delivery.period_start,
+            status: delivery.status,
+            attempts: delivery.attempts,
+            last_error: delivery.last_error,
+            delivered_at: delivery.delivered_at,
+        }
+    }
+}
+
+/// URL webhook berisi rahasia, jadi hanya ditampilkan sebagian
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WebhookResponse {
+    pub id: i32,
+    #[schema(example = "slack")]
+    pub platform: String,
+    #[schema(example = "https://hooks.slack.com/…XXXX")]
+    pub url_preview: String,
+    pub created_at: NaiveDateTime,
+    /// Pengiriman ringkasan terakhir, jika ada
+    pub last_delivery: Option<WebhookDeliveryResponse>,
+}
@@ -0,0 +1,87 @@
+use axum::{Router, routing::{get, post, put}};
+use crate::state::AppState;
+use crate::api::admin_handler;
+
+pub fn admin_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/admin/cleanup-tokens",
+            post(admin_handler::cleanup_tokens_handler)
+        )
+        .route(
+            "/admin/cache-stats",
+            get(admin_handler::cache_stats_handler)
+        )
+        .route(
+            "/admin/audit-logs",
+            get(admin_handler::audit_logs_handler)
+        )
+        .route(
+            "/admin/backup",
+            post(admin_handler::create_backup_handler)
+        )
+        .route(
+            "/admin/restore",
+            post(admin_handler::restore_backup_handler)
+        )
+        .route(
+            "/admin/impersonate/:user_id",
+            post(admin_handler::impersonate_user_handler)
+        )
+        .route(
+            "/admin/analytics/active-users",
+            get(admin_handler::weekly_activity_handler)
+        )
+        .route(
+            "/admin/analytics/retention",
+            get(admin_handler::retention_cohorts_handler)
+        )
+        .route(
+            "/admin/help-requests",
+            get(admin_handler::list_help_requests_handler)
+        )
+        .route(
+            "/admin/help-requests/:id",
+            get(admin_handler::get_help_request_handler)
+        )
+        .route(
+            "/admin/help-requests/:id/status",
+            put(admin_handler::update_help_request_status_handler)
+        )
+        .route(
+            "/admin/help-requests/:id/comments",
+            post(admin_handler::add_help_comment_handler)
+        )
+        .route(
+            "/admin/psychologists",
+            post(admin_handler::create_psychologist_handler)
+        )
+        .route(
+            "/admin/psychologists/:id/slots",
+            post(admin_handler::create_psychologist_slots_handler)
+        )
+        .route(
+            "/admin/psychologist-requests/:id/confirm",
+            post(admin_handler::confirm_appointment_handler)
+        )
+        .route(
+            "/admin/community/groups",
+            post(admin_handler::create_community_group_handler)
+        )
+        .route(
+            "/admin/moderation",
+            get(admin_handler::list_moderation_handler)
+        )
+        .route(
+            "/admin/moderation/:id/approve",
+            post(admin_handler::approve_moderation_handler)
+        )
+        .route(
+            "/admin/moderation/:id/reject",
+            post(admin_handler::reject_moderation_handler)
+        )
+        .route(
+            "/admin/maintenance",
+            get(admin_handler::get_maintenance_handler).put(admin_handler::update_maintenance_handler)
+        )
+}
\ No newline at end of file
@@ -0,0 +1,19 @@
+use axum::{Router, routing::{get, post, put}};
+use crate::state::AppState;
+use crate::api::appointment_handler;
+
+pub fn appointment_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/appointments",
+            get(appointment_handler::get_appointments_handler)
+        )
+        .route(
+            "/appointments/:id/reschedule",
+            put(appointment_handler::reschedule_appointment_handler)
+        )
+        .route(
+            "/appointments/:id/cancel",
+            post(appointment_handler::cancel_appointment_handler)
+        )
+}
\ No newline at end of file
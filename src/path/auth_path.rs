@@ -1,14 +1,18 @@
 use axum::Router;
-use diesel::pg::PgConnection;
-use diesel::r2d2;
+use crate::state::AppState;
 use crate::api::auth_handler;
 
-pub fn auth_routes() -> Router<r2d2::Pool<diesel::r2d2::ConnectionManager<PgConnection>>> {
+pub fn auth_routes() -> Router<AppState> {
     Router::new()
         .route("/auth/register", axum::routing::post(auth_handler::register))
         .route("/auth/login", axum::routing::post(auth_handler::login))
         .route("/auth/logout", axum::routing::post(auth_handler::logout))
+        .route("/auth/scoped-token", axum::routing::post(auth_handler::issue_scoped_token_handler))
+        .route("/auth/guest", axum::routing::post(auth_handler::guest_login_handler))
+        .route("/auth/upgrade", axum::routing::post(auth_handler::upgrade_account_handler))
+        .route("/auth/csrf", axum::routing::get(auth_handler::csrf_token_handler))
         // Google OAuth routes
         .route("/auth/google", axum::routing::get(auth_handler::google_auth_url))
         .route("/auth/google/callback", axum::routing::get(auth_handler::google_callback))
+        .route("/auth/exchange", axum::routing::post(auth_handler::exchange_code_handler))
 }
\ No newline at end of file
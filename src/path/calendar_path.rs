@@ -0,0 +1,19 @@
+use axum::{Router, routing::{get, post}};
+use crate::state::AppState;
+use crate::api::calendar_handler;
+
+pub fn calendar_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/calendar.ics",
+            get(calendar_handler::calendar_feed_handler)
+        )
+        .route(
+            "/calendar/token",
+            get(calendar_handler::get_calendar_token_handler)
+        )
+        .route(
+            "/calendar/token",
+            post(calendar_handler::regenerate_calendar_token_handler)
+        )
+}
@@ -0,0 +1,35 @@
+use axum::{Router, routing::{delete, get, post, put}};
+use crate::state::AppState;
+use crate::api::community_handler;
+
+pub fn community_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/community/groups",
+            get(community_handler::get_groups_handler)
+        )
+        .route(
+            "/community/groups/:id/join",
+            post(community_handler::join_group_handler)
+        )
+        .route(
+            "/community/groups/:id/join",
+            delete(community_handler::leave_group_handler)
+        )
+        .route(
+            "/community/groups/:id/posts",
+            get(community_handler::get_posts_handler)
+        )
+        .route(
+            "/community/groups/:id/posts",
+            post(community_handler::create_post_handler)
+        )
+        .route(
+            "/community/posts/:id/reactions/:kind",
+            put(community_handler::add_reaction_handler)
+        )
+        .route(
+            "/community/posts/:id/reactions/:kind",
+            delete(community_handler::remove_reaction_handler)
+        )
+}
\ No newline at end of file
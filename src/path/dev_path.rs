@@ -0,0 +1,17 @@
+use axum::{Router, routing::post};
+use crate::config::app_config::app_config;
+use crate::state::AppState;
+use crate::api::dev_handler;
+
+/// Route development; tidak didaftarkan sama sekali jika DEV_TOOLS nonaktif
+pub fn dev_routes() -> Router<AppState> {
+    if !app_config().dev_tools {
+        return Router::new();
+    }
+
+    Router::new()
+        .route(
+            "/dev/generate",
+            post(dev_handler::generate_history_handler)
+        )
+}
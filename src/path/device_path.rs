@@ -0,0 +1,19 @@
+use axum::{Router, routing::{delete, get, post}};
+use crate::state::AppState;
+use crate::api::device_handler;
+
+pub fn device_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/devices",
+            post(device_handler::register_device_handler)
+        )
+        .route(
+            "/devices",
+            get(device_handler::get_devices_handler)
+        )
+        .route(
+            "/devices/:id",
+            delete(device_handler::delete_device_handler)
+        )
+}
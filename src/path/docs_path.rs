@@ -0,0 +1,15 @@
+use axum::{Router, routing::get};
+use crate::state::AppState;
+use crate::api::docs_handler;
+
+pub fn docs_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/openapi.json",
+            get(docs_handler::openapi_json_handler)
+        )
+        .route(
+            "/docs",
+            get(docs_handler::swagger_ui_handler)
+        )
+}
@@ -0,0 +1,20 @@
+use axum::{Router, routing::{get, post}};
+use crate::state::AppState;
+use crate::api::export_handler;
+
+pub fn export_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/export/markdown",
+            get(export_handler::export_markdown_handler)
+        )
+}
+
+/// Versi v2: export dikerjakan sebagai job latar belakang
+pub fn export_job_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/export/markdown",
+            post(export_handler::export_markdown_job_handler)
+        )
+}
@@ -0,0 +1,11 @@
+use axum::{Router, routing::post};
+use crate::state::AppState;
+use crate::api::health_handler;
+
+pub fn health_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/integrations/health/samples",
+            post(health_handler::save_samples_handler)
+        )
+}
\ No newline at end of file
@@ -0,0 +1,23 @@
+use axum::{Router, routing::{get, post}};
+use crate::state::AppState;
+use crate::api::help_handler;
+
+pub fn help_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/help-requests",
+            post(help_handler::create_help_request_handler)
+        )
+        .route(
+            "/help-requests",
+            get(help_handler::get_help_requests_handler)
+        )
+        .route(
+            "/help-requests/:id",
+            get(help_handler::get_help_request_handler)
+        )
+        .route(
+            "/help-requests/:id/comments",
+            post(help_handler::add_help_comment_handler)
+        )
+}
\ No newline at end of file
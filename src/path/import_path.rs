@@ -0,0 +1,24 @@
+use axum::{Router, extract::DefaultBodyLimit, routing::post};
+use crate::config::app_config::app_config;
+use crate::state::AppState;
+use crate::api::import_handler;
+
+/// Import file punya batas body sendiri (IMPORT_MAX_UPLOAD_BYTES), seperti upload avatar
+pub fn import_upload_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/import/daylio",
+            post(import_handler::import_daylio_handler)
+                .layer(DefaultBodyLimit::max(app_config().import_max_upload_bytes + 64 * 1024))
+        )
+}
+
+/// Versi v2: import dikerjakan sebagai job latar belakang
+pub fn import_job_upload_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/import/daylio",
+            post(import_handler::import_daylio_job_handler)
+                .layer(DefaultBodyLimit::max(app_config().import_max_upload_bytes + 64 * 1024))
+        )
+}
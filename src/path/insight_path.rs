@@ -0,0 +1,35 @@
+use axum::{Router, routing::get};
+use crate::state::AppState;
+use crate::api::insight_handler;
+
+pub fn insight_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/insights/alerts",
+            get(insight_handler::get_alerts_handler)
+        )
+        .route(
+            "/insights/week-over-week",
+            get(insight_handler::get_week_over_week_handler)
+        )
+        .route(
+            "/insights/day-of-week",
+            get(insight_handler::get_day_of_week_handler)
+        )
+        .route(
+            "/insights/places",
+            get(insight_handler::get_places_handler)
+        )
+        .route(
+            "/insights/forecast",
+            get(insight_handler::get_forecast_handler)
+        )
+        .route(
+            "/insights/trend",
+            get(insight_handler::get_trend_handler)
+        )
+        .route(
+            "/insights/snapshot",
+            get(insight_handler::get_snapshot_handler)
+        )
+}
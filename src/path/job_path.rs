@@ -0,0 +1,15 @@
+use axum::{Router, routing::get};
+use crate::state::AppState;
+use crate::api::job_handler;
+
+pub fn job_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/jobs/:id",
+            get(job_handler::get_job_handler)
+        )
+        .route(
+            "/jobs/:id/result",
+            get(job_handler::get_job_result_handler)
+        )
+}
\ No newline at end of file
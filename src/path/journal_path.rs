@@ -1,9 +1,8 @@
 use axum::{Router, routing::{get, post, put, delete}};
-use diesel::pg::PgConnection;
-use diesel::r2d2;
+use crate::state::AppState;
 use crate::api::journal_handler;
 
-pub fn journal_routes() -> Router<r2d2::Pool<diesel::r2d2::ConnectionManager<PgConnection>>> {
+pub fn journal_routes() -> Router<AppState> {
     Router::new()
         // Special Operations - put first to avoid path conflicts
         .route(
@@ -15,12 +14,20 @@ pub fn journal_routes() -> Router<r2d2::Pool<diesel::r2d2::ConnectionManager<PgC
             get(journal_handler::get_journal_stats_handler)
         )
         .route(
-            "/journals/search",
-            get(journal_handler::search_journals_handler)
+            "/journals/streak",
+            get(journal_handler::get_journal_streak_handler)
         )
         .route(
-            "/journals/recent",
-            get(journal_handler::get_recent_journals_handler)
+            "/journals/draft",
+            get(journal_handler::get_journal_draft_handler)
+        )
+        .route(
+            "/journals/draft",
+            put(journal_handler::save_journal_draft_handler)
+        )
+        .route(
+            "/journals/draft",
+            delete(journal_handler::delete_journal_draft_handler)
         )
 
         // CRUD Operations
@@ -28,10 +35,6 @@ pub fn journal_routes() -> Router<r2d2::Pool<diesel::r2d2::ConnectionManager<PgC
             "/journals",
             post(journal_handler::create_journal_handler)
         )
-        .route(
-            "/journals",
-            get(journal_handler::get_user_journals_handler)
-        )
         .route(
             "/journals/:id",
             get(journal_handler::get_journal_by_id_handler)
@@ -44,14 +47,60 @@ pub fn journal_routes() -> Router<r2d2::Pool<diesel::r2d2::ConnectionManager<PgC
             "/journals/:id",
             delete(journal_handler::delete_journal_handler)
         )
+        .route(
+            "/journals/:id/pin",
+            post(journal_handler::pin_journal_handler)
+        )
+        .route(
+            "/journals/:id/pin",
+            delete(journal_handler::unpin_journal_handler)
+        )
 
         // Query Operations
         .route(
             "/journals/date/:date",
             get(journal_handler::get_journal_by_date_handler)
         )
+}
+
+/// List journal v1: isi lengkap setiap entri
+pub fn journal_list_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/journals/search",
+            get(journal_handler::search_journals_handler)
+        )
+        .route(
+            "/journals/recent",
+            get(journal_handler::get_recent_journals_handler)
+        )
+        .route(
+            "/journals",
+            get(journal_handler::get_user_journals_handler)
+        )
         .route(
             "/journals/range",
             get(journal_handler::get_journals_by_date_range_handler)
         )
+}
+
+/// List journal v2: `preview` tanpa Markdown, isi lengkap hanya dengan `?include=content`
+pub fn journal_preview_list_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/journals/search",
+            get(journal_handler::search_journal_previews_handler)
+        )
+        .route(
+            "/journals/recent",
+            get(journal_handler::get_recent_journal_previews_handler)
+        )
+        .route(
+            "/journals",
+            get(journal_handler::get_user_journal_previews_handler)
+        )
+        .route(
+            "/journals/range",
+            get(journal_handler::get_journal_previews_by_date_range_handler)
+        )
 }
\ No newline at end of file
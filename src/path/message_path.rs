@@ -0,0 +1,27 @@
+use axum::{Router, routing::{get, post}};
+use crate::state::AppState;
+use crate::api::message_handler;
+
+pub fn message_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/conversations",
+            get(message_handler::get_conversations_handler)
+        )
+        .route(
+            "/conversations/events",
+            get(message_handler::conversation_events_handler)
+        )
+        .route(
+            "/conversations/:psychologist_id/:user_id/messages",
+            get(message_handler::get_messages_handler)
+        )
+        .route(
+            "/conversations/:psychologist_id/:user_id/messages",
+            post(message_handler::send_message_handler)
+        )
+        .route(
+            "/conversations/:psychologist_id/:user_id/read",
+            post(message_handler::mark_read_handler)
+        )
+}
\ No newline at end of file
@@ -1,16 +1,39 @@
 use axum::Router;
-use diesel::pg::PgConnection;
-use diesel::r2d2;
+use crate::state::AppState;
 
 pub mod auth_path;
 pub mod user_path;
 pub mod mood_path;
 pub mod journal_path;
+pub mod docs_path;
+pub mod report_path;
+pub mod calendar_path;
+pub mod admin_path;
+pub mod security_path;
+pub mod insight_path;
+pub mod device_path;
+pub mod export_path;
+pub mod import_path;
+pub mod organization_path;
+pub mod help_path;
+pub mod psychologist_path;
+pub mod appointment_path;
+pub mod message_path;
+pub mod community_path;
+pub mod webhook_path;
+pub mod health_path;
+pub mod job_path;
+pub mod status_path;
+pub mod dev_path;
+pub mod v1;
+pub mod v2;
 
-pub fn init_routes() -> Router<r2d2::Pool<diesel::r2d2::ConnectionManager<PgConnection>>> {
+/// Route API per versi. Path tanpa versi tetap dilayani v1 agar klien lama
+/// tidak rusak; versi lain dipilih lewat header `X-Api-Version`
+/// (lihat `middleware::api_version`).
+pub fn init_routes() -> Router<AppState> {
     Router::new()
-        .merge(auth_path::auth_routes())
-        .merge(user_path::user_routes())
-        .merge(mood_path::mood_routes())
-        .merge(journal_path::journal_routes())
-}
\ No newline at end of file
+        .nest("/v1", v1::routes())
+        .nest("/v2", v2::routes())
+        .merge(v1::routes())
+}
@@ -1,9 +1,8 @@
 use axum::{Router, routing::{get, post, put, delete}};
-use diesel::pg::PgConnection;
-use diesel::r2d2;
-use crate::api::mood_handler;
+use crate::state::AppState;
+use crate::api::{checkin_handler, mood_handler};
 
-pub fn mood_routes() -> Router<r2d2::Pool<diesel::r2d2::ConnectionManager<PgConnection>>> {
+pub fn mood_routes() -> Router<AppState> {
     Router::new()
         // CRUD Operations
         .route(
@@ -22,6 +21,10 @@ pub fn mood_routes() -> Router<r2d2::Pool<diesel::r2d2::ConnectionManager<PgConn
             "/moods",
             get(mood_handler::get_user_moods_handler)
         )
+        .route(
+            "/moods/search",
+            get(mood_handler::search_moods_handler)
+        )
         .route(
             "/moods/:id",
             get(mood_handler::get_mood_by_id_handler)
@@ -34,6 +37,18 @@ pub fn mood_routes() -> Router<r2d2::Pool<diesel::r2d2::ConnectionManager<PgConn
             "/moods/:id",
             delete(mood_handler::delete_mood_handler)
         )
+        .route(
+            "/moods/:id/restore",
+            post(mood_handler::restore_mood_handler)
+        )
+        .route(
+            "/moods/:id/pin",
+            post(mood_handler::pin_mood_handler)
+        )
+        .route(
+            "/moods/:id/pin",
+            delete(mood_handler::unpin_mood_handler)
+        )
         
         // Query Operations
         .route(
@@ -58,4 +73,9 @@ pub fn mood_routes() -> Router<r2d2::Pool<diesel::r2d2::ConnectionManager<PgConn
             "/moods/streak",
             get(mood_handler::get_mood_streak_handler)
         )
+        // Check-in sekali klik dari email pengingat, tanpa login
+        .route(
+            "/checkin",
+            get(checkin_handler::checkin_handler)
+        )
 }
\ No newline at end of file
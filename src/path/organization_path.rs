@@ -0,0 +1,39 @@
+use axum::{Router, routing::{delete, get, post, put}};
+use crate::state::AppState;
+use crate::api::organization_handler;
+
+pub fn organization_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/organizations",
+            post(organization_handler::create_organization_handler)
+        )
+        .route(
+            "/organizations",
+            get(organization_handler::get_organizations_handler)
+        )
+        .route(
+            "/organizations/invitations/accept",
+            post(organization_handler::accept_invitation_handler)
+        )
+        .route(
+            "/organizations/:id/members",
+            get(organization_handler::get_members_handler)
+        )
+        .route(
+            "/organizations/:id/members/:user_id",
+            delete(organization_handler::remove_member_handler)
+        )
+        .route(
+            "/organizations/:id/invitations",
+            post(organization_handler::invite_member_handler)
+        )
+        .route(
+            "/organizations/:id/consent",
+            put(organization_handler::update_consent_handler)
+        )
+        .route(
+            "/organizations/:id/summary",
+            get(organization_handler::get_summary_handler)
+        )
+}
@@ -0,0 +1,23 @@
+use axum::{Router, routing::{get, post}};
+use crate::state::AppState;
+use crate::api::psychologist_handler;
+
+pub fn psychologist_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/psychologists",
+            get(psychologist_handler::get_psychologists_handler)
+        )
+        .route(
+            "/psychologists/:id",
+            get(psychologist_handler::get_psychologist_handler)
+        )
+        .route(
+            "/psychologist-requests",
+            post(psychologist_handler::create_psychologist_request_handler)
+        )
+        .route(
+            "/psychologist-requests",
+            get(psychologist_handler::get_psychologist_requests_handler)
+        )
+}
\ No newline at end of file
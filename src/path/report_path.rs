@@ -0,0 +1,32 @@
+use axum::{Router, routing::{get, post}};
+use crate::state::AppState;
+use crate::api::report_handler;
+
+pub fn report_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/reports/monthly",
+            get(report_handler::get_monthly_report_handler)
+        )
+        .route(
+            "/reports/yearly",
+            get(report_handler::get_yearly_report_handler)
+        )
+}
+
+pub fn report_pdf_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/reports/monthly.pdf",
+            get(report_handler::get_monthly_report_pdf_handler)
+        )
+}
+
+/// Versi v2: PDF dikerjakan sebagai job latar belakang
+pub fn report_pdf_job_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/reports/monthly.pdf",
+            post(report_handler::monthly_report_pdf_job_handler)
+        )
+}
@@ -0,0 +1,15 @@
+use axum::{Router, routing::get};
+use crate::state::AppState;
+use crate::api::security_handler;
+
+pub fn security_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/user/security/logins",
+            get(security_handler::get_login_history_handler)
+        )
+        .route(
+            "/user/security/audit",
+            get(security_handler::get_audit_log_handler)
+        )
+}
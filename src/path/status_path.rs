@@ -0,0 +1,15 @@
+use axum::{Router, routing::get};
+use crate::state::AppState;
+use crate::api::status_handler;
+
+pub fn status_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/ready",
+            get(status_handler::ready_handler)
+        )
+        .route(
+            "/metrics",
+            get(status_handler::metrics_handler)
+        )
+}
\ No newline at end of file
@@ -1,9 +1,9 @@
-use axum::{Router, routing::{get, put, post}};
-use diesel::pg::PgConnection;
-use diesel::r2d2;
+use axum::{Router, extract::DefaultBodyLimit, routing::{delete, get, patch, put, post}};
+use crate::config::app_config::app_config;
+use crate::state::AppState;
 use crate::api::user_handler;
 
-pub fn user_routes() -> Router<r2d2::Pool<diesel::r2d2::ConnectionManager<PgConnection>>> {
+pub fn user_routes() -> Router<AppState> {
     Router::new()
         .route(
             "/user/profile",
@@ -13,6 +13,26 @@ pub fn user_routes() -> Router<r2d2::Pool<diesel::r2d2::ConnectionManager<PgConn
             "/user/profile",
             put(user_handler::edit_profile_handler)
         )
+        .route(
+            "/user/profile",
+            patch(user_handler::patch_profile_handler)
+        )
+        .route(
+            "/user/onboarding",
+            get(user_handler::get_onboarding_handler)
+        )
+        .route(
+            "/user/settings",
+            get(user_handler::get_settings_handler)
+        )
+        .route(
+            "/user/usage",
+            get(user_handler::get_usage_handler)
+        )
+        .route(
+            "/user/settings",
+            put(user_handler::update_settings_handler)
+        )
         .route(
             "/user/password",
             put(user_handler::change_password_handler)
@@ -23,14 +43,54 @@ pub fn user_routes() -> Router<r2d2::Pool<diesel::r2d2::ConnectionManager<PgConn
         )
         .route(
             "/user/check-email",
-            get(user_handler::check_email_handler_get)
+            post(user_handler::check_email_handler_post)
         )
         .route(
-            "/user/check-email",
-            post(user_handler::check_email_handler_post)
+            "/user/check-username",
+            get(user_handler::check_username_handler)
         )
         .route(
             "/user/reset-password",
             post(user_handler::reset_password_handler)
         )
+        .route(
+            "/user/email/change",
+            get(user_handler::get_pending_email_change_handler)
+        )
+        .route(
+            "/user/email/change",
+            post(user_handler::request_email_change_handler)
+        )
+        .route(
+            "/user/email/change",
+            delete(user_handler::cancel_email_change_handler)
+        )
+        .route(
+            "/user/avatar",
+            get(user_handler::get_avatar_handler)
+        )
+        .route(
+            "/user/account",
+            delete(user_handler::delete_account_handler)
+        )
+        .route(
+            "/user/email/confirm",
+            get(user_handler::confirm_email_change_handler_get)
+        )
+        .route(
+            "/user/email/confirm",
+            post(user_handler::confirm_email_change_handler_post)
+        )
+}
+
+/// Upload avatar dipisah dari `user_routes` karena batas body-nya lebih besar
+/// dari batas umum MAX_REQUEST_BODY_BYTES
+pub fn avatar_upload_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/user/avatar",
+            // Sisakan ruang untuk header multipart di atas batas ukuran file
+            post(user_handler::upload_avatar_handler)
+                .layer(DefaultBodyLimit::max(app_config().avatar_max_upload_bytes + 64 * 1024))
+        )
 }
\ No newline at end of file
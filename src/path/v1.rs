@@ -0,0 +1,46 @@
+use axum::{Router, extract::DefaultBodyLimit};
+use tower_http::limit::RequestBodyLimitLayer;
+use crate::config::app_config::app_config;
+use crate::state::AppState;
+use super::{
+    admin_path, appointment_path, auth_path, calendar_path, community_path, dev_path, device_path, docs_path,
+    export_path, health_path, help_path, import_path, insight_path, job_path, journal_path, message_path,
+    mood_path, organization_path, psychologist_path, report_path, security_path, status_path, user_path,
+    webhook_path,
+};
+
+/// Route API v1. Handler di sini tidak boleh berubah secara breaking;
+/// perubahan seperti itu masuk ke `v2`.
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .merge(auth_path::auth_routes())
+        .merge(user_path::user_routes())
+        .merge(mood_path::mood_routes())
+        .merge(journal_path::journal_routes())
+        .merge(journal_path::journal_list_routes())
+        .merge(docs_path::docs_routes())
+        .merge(report_path::report_routes())
+        .merge(report_path::report_pdf_routes())
+        .merge(calendar_path::calendar_routes())
+        .merge(admin_path::admin_routes())
+        .merge(security_path::security_routes())
+        .merge(insight_path::insight_routes())
+        .merge(device_path::device_routes())
+        .merge(export_path::export_routes())
+        .merge(organization_path::organization_routes())
+        .merge(help_path::help_routes())
+        .merge(psychologist_path::psychologist_routes())
+        .merge(appointment_path::appointment_routes())
+        .merge(message_path::message_routes())
+        .merge(community_path::community_routes())
+        .merge(webhook_path::webhook_routes())
+        .merge(health_path::health_routes())
+        .merge(job_path::job_routes())
+        .merge(status_path::status_routes())
+        .merge(dev_path::dev_routes())
+        // Batas body untuk semua route di atas; upload avatar dan import punya batas sendiri
+        .layer(DefaultBodyLimit::disable())
+        .layer(RequestBodyLimitLayer::new(app_config().max_request_body_bytes))
+        .merge(user_path::avatar_upload_routes())
+        .merge(import_path::import_upload_routes())
+}
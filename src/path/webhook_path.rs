@@ -0,0 +1,19 @@
+use axum::{Router, routing::{delete, get, post}};
+use crate::state::AppState;
+use crate::api::webhook_handler;
+
+pub fn webhook_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/user/settings/webhooks",
+            get(webhook_handler::get_webhooks_handler)
+        )
+        .route(
+            "/user/settings/webhooks",
+            post(webhook_handler::register_webhook_handler)
+        )
+        .route(
+            "/user/settings/webhooks/:id",
+            delete(webhook_handler::delete_webhook_handler)
+        )
+}
\ No newline at end of file
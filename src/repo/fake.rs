@@ -0,0 +1,514 @@
+//! Repository di memori untuk unit test service; perilakunya mengikuti query Diesel
+//! (urutan, default limit, dan pesan error NotFound).
+
+use std::cmp::Reverse;
+use std::sync::Mutex;
+use chrono::{Duration, NaiveDate, NaiveDateTime, Utc};
+use chrono_tz::Tz;
+use crate::errors::app_error::AppError;
+use crate::models::journal::{Journal, JournalSummary};
+use crate::models::list_sort::ListSort;
+use crate::models::mood::{Mood, MoodSearchFilter, MoodType, NewMood, UpdateMoodRequest};
+use crate::models::user::User;
+use crate::utils::patch::Patch;
+use crate::utils::timezone::{day_range_utc, day_start_utc, today_in};
+use super::{JournalRepo, MoodRepo, UserRepo};
+
+fn not_found(message: &str) -> AppError {
+    AppError::NotFound(message.to_string())
+}
+
+fn page<T>(items: Vec<T>, limit: Option<i32>, offset: Option<i32>) -> Vec<T> {
+    items
+        .into_iter()
+        .skip(offset.unwrap_or(0).max(0) as usize)
+        .take(limit.unwrap_or(50).max(0) as usize)
+        .collect()
+}
+
+#[derive(Default)]
+pub struct FakeMoodRepo {
+    pub moods: Mutex<Vec<Mood>>,
+    /// Mood yang dihapus beserta waktu penghapusannya
+    pub trash: Mutex<Vec<(Mood, NaiveDateTime)>>,
+}
+
+impl FakeMoodRepo {
+    pub fn with(moods: Vec<Mood>) -> Self {
+        FakeMoodRepo { moods: Mutex::new(moods), ..Default::default() }
+    }
+
+    /// Mood minimal untuk test; field lain bisa diubah setelahnya
+    pub fn mood(id: i32, user_id: i32, date: NaiveDate, mood: &str) -> Mood {
+        Mood {
+            id,
+            user_id,
+            date,
+            mood: mood.to_string(),
+            emoji: String::new(),
+            notes: None,
+            created_at: Utc::now().naive_utc(),
+            updated_at: None,
+            details: None,
+            is_pinned: false,
+            place_label: None,
+            latitude: None,
+            longitude: None,
+        }
+    }
+
+    fn filtered(&self, filter: impl Fn(&Mood) -> bool) -> Vec<Mood> {
+        self.moods.lock().unwrap().iter().filter(|mood| filter(mood)).cloned().collect()
+    }
+
+    fn in_range(mood: &Mood, user_id: i32, range: Option<(NaiveDate, NaiveDate)>) -> bool {
+        mood.user_id == user_id && range.is_none_or(|(start, end)| mood.date >= start && mood.date <= end)
+    }
+}
+
+impl MoodRepo for FakeMoodRepo {
+    fn create(&self, new_mood: NewMood) -> Result<Mood, AppError> {
+        let mut moods = self.moods.lock().unwrap();
+        if moods.iter().any(|mood| mood.user_id == new_mood.user_id && mood.date == new_mood.date) {
+            return Err(AppError::Conflict("Mood already exists for this date".to_string()));
+        }
+        let mood = Mood {
+            id: moods.iter().map(|mood| mood.id).max().unwrap_or(0) + 1,
+            user_id: new_mood.user_id,
+            date: new_mood.date,
+            mood: new_mood.mood,
+            emoji: new_mood.emoji,
+            notes: new_mood.notes,
+            created_at: new_mood.created_at,
+            updated_at: new_mood.updated_at,
+            details: new_mood.details,
+            is_pinned: false,
+            place_label: new_mood.place_label,
+            latitude: new_mood.latitude,
+            longitude: new_mood.longitude,
+        };
+        moods.push(mood.clone());
+        Ok(mood)
+    }
+
+    fn find_by_id(&self, mood_id: i32) -> Result<Mood, AppError> {
+        self.filtered(|mood| mood.id == mood_id).pop().ok_or_else(|| not_found("Mood not found"))
+    }
+
+    fn find_by_user(
+        &self,
+        user_id: i32,
+        limit: Option<i32>,
+        offset: Option<i32>,
+        pinned: Option<bool>,
+        sort: ListSort,
+    ) -> Result<Vec<Mood>, AppError> {
+        let mut moods = self.filtered(|mood| mood.user_id == user_id && pinned.is_none_or(|pinned| mood.is_pinned == pinned));
+        match sort {
+            ListSort::DateAsc => moods.sort_by_key(|mood| (mood.date, mood.id)),
+            ListSort::UpdatedDesc => moods.sort_by_key(|mood| (mood.updated_at.is_none(), Reverse((mood.updated_at, mood.id)))),
+            ListSort::DateDesc => moods.sort_by_key(|mood| Reverse((mood.date, mood.id))),
+            ListSort::Title => return Err(sort.unsupported(&ListSort::MOODS)),
+        }
+        Ok(page(moods, limit, offset))
+    }
+
+    fn find_by_date(&self, user_id: i32, date: NaiveDate) -> Result<Mood, AppError> {
+        self.filtered(|mood| mood.user_id == user_id && mood.date == date)
+            .pop()
+            .ok_or_else(|| not_found("Mood not found for this date"))
+    }
+
+    fn find_by_date_range(&self, user_id: i32, start_date: NaiveDate, end_date: NaiveDate) -> Result<Vec<Mood>, AppError> {
+        let mut moods = self.filtered(|mood| mood.user_id == user_id && mood.date >= start_date && mood.date <= end_date);
+        moods.sort_by_key(|mood| mood.date);
+        Ok(moods)
+    }
+
+    fn search(&self, user_id: i32, filter: &MoodSearchFilter) -> Result<Vec<Mood>, AppError> {
+        let query = filter.query.to_lowercase();
+        let mut moods = self.filtered(|mood| {
+            mood.user_id == user_id
+                && mood.notes.as_ref().is_some_and(|notes| notes.to_lowercase().contains(&query))
+                && filter.mood.as_ref().is_none_or(|wanted| mood.mood == wanted.as_str())
+                && filter.start_date.is_none_or(|start| mood.date >= start)
+                && filter.end_date.is_none_or(|end| mood.date <= end)
+        });
+        moods.sort_by_key(|mood| Reverse(mood.date));
+        Ok(page(moods, filter.limit, filter.offset))
+    }
+
+    fn find_recent(&self, user_id: i32, days: i32, today: NaiveDate) -> Result<Vec<Mood>, AppError> {
+        let cutoff = today - Duration::days(days as i64);
+        let mut moods = self.filtered(|mood| mood.user_id == user_id && mood.date >= cutoff);
+        moods.sort_by_key(|mood| Reverse(mood.date));
+        Ok(moods)
+    }
+
+    fn update(&self, mood_id: i32, user_id: i32, changes: UpdateMoodRequest) -> Result<Mood, AppError> {
+        let mut moods = self.moods.lock().unwrap();
+        if let Some(date) = changes.date {
+            if moods.iter().any(|mood| mood.user_id == user_id && mood.date == date && mood.id != mood_id) {
+                return Err(AppError::Conflict("Another mood already exists for this date".to_string()));
+            }
+        }
+        let mood = moods
+            .iter_mut()
+            .find(|mood| mood.id == mood_id && mood.user_id == user_id)
+            .ok_or_else(|| not_found("Mood not found"))?;
+
+        if let Some(new_mood) = changes.mood {
+            mood.mood = new_mood;
+        }
+        if let Some(emoji) = changes.emoji {
+            mood.emoji = emoji;
+        }
+        if let Some(date) = changes.date {
+            mood.date = date;
+        }
+        mood.notes = changes.notes.apply(mood.notes.take());
+        match changes.details {
+            Patch::Absent => {}
+            Patch::Null => mood.details = None,
+            Patch::Value(details) => mood.details = details.to_json(),
+        }
+        match changes.location {
+            Patch::Absent => {}
+            Patch::Null => (mood.place_label, mood.latitude, mood.longitude) = (None, None, None),
+            Patch::Value(location) => {
+                (mood.place_label, mood.latitude, mood.longitude) = (location.label, location.latitude, location.longitude)
+            }
+        }
+        mood.updated_at = Some(Utc::now().naive_utc());
+        Ok(mood.clone())
+    }
+
+    fn set_pinned(&self, mood_id: i32, user_id: i32, pinned: bool) -> Result<Mood, AppError> {
+        let mut moods = self.moods.lock().unwrap();
+        let mood = moods
+            .iter_mut()
+            .find(|mood| mood.id == mood_id && mood.user_id == user_id)
+            .ok_or_else(|| not_found("Mood not found"))?;
+        mood.is_pinned = pinned;
+        Ok(mood.clone())
+    }
+
+    fn move_to_trash(&self, mood_id: i32, user_id: i32, deleted_at: NaiveDateTime) -> Result<bool, AppError> {
+        let mut moods = self.moods.lock().unwrap();
+        let Some(index) = moods.iter().position(|mood| mood.id == mood_id && mood.user_id == user_id) else {
+            return Ok(false);
+        };
+        self.trash.lock().unwrap().push((moods.remove(index), deleted_at));
+        Ok(true)
+    }
+
+    fn restore(&self, mood_id: i32, user_id: i32, deleted_after: NaiveDateTime) -> Result<Mood, AppError> {
+        let mut trash = self.trash.lock().unwrap();
+        let index = trash
+            .iter()
+            .position(|(mood, deleted_at)| mood.id == mood_id && mood.user_id == user_id && *deleted_at >= deleted_after)
+            .ok_or_else(|| not_found("Deleted mood not found or undo window has passed"))?;
+
+        let mut moods = self.moods.lock().unwrap();
+        let date = trash[index].0.date;
+        if moods.iter().any(|mood| mood.user_id == user_id && mood.date == date) {
+            return Err(AppError::Conflict("Mood already exists for this date".to_string()));
+        }
+        let (mood, _) = trash.remove(index);
+        moods.push(mood.clone());
+        Ok(mood)
+    }
+
+    fn count(&self, user_id: i32) -> Result<i64, AppError> {
+        Ok(self.filtered(|mood| mood.user_id == user_id).len() as i64)
+    }
+
+    fn latest_update(&self, user_id: i32) -> Result<Option<NaiveDateTime>, AppError> {
+        Ok(self.filtered(|mood| mood.user_id == user_id).iter().filter_map(|mood| mood.updated_at).max())
+    }
+
+    fn count_by_type(&self, user_id: i32, range: Option<(NaiveDate, NaiveDate)>) -> Result<Vec<(String, i64)>, AppError> {
+        let mut counts: Vec<(String, i64)> = Vec::new();
+        for mood in self.filtered(|mood| Self::in_range(mood, user_id, range)) {
+            match counts.iter_mut().find(|(name, _)| *name == mood.mood) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((mood.mood, 1)),
+            }
+        }
+        Ok(counts)
+    }
+
+    fn average_score(&self, user_id: i32, range: Option<(NaiveDate, NaiveDate)>) -> Result<Option<f64>, AppError> {
+        let scores: Vec<i32> = self
+            .filtered(|mood| Self::in_range(mood, user_id, range))
+            .iter()
+            .filter_map(|mood| mood.mood.parse::<MoodType>().ok())
+            .map(|mood_type| mood_type.score())
+            .collect();
+        if scores.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(scores.iter().sum::<i32>() as f64 / scores.len() as f64))
+    }
+
+    fn dates_until(&self, user_id: i32, end_date: NaiveDate) -> Result<Vec<NaiveDate>, AppError> {
+        Ok(self
+            .filtered(|mood| mood.user_id == user_id && mood.date <= end_date)
+            .into_iter()
+            .map(|mood| mood.date)
+            .collect())
+    }
+
+    fn for_each_by_user(&self, user_id: i32, f: &mut dyn FnMut(Mood) -> Result<(), AppError>) -> Result<(), AppError> {
+        let mut moods = self.filtered(|mood| mood.user_id == user_id);
+        moods.sort_by_key(|mood| Reverse(mood.date));
+        moods.into_iter().try_for_each(f)
+    }
+}
+
+#[derive(Default)]
+pub struct FakeJournalRepo {
+    pub journals: Mutex<Vec<Journal>>,
+}
+
+impl FakeJournalRepo {
+    pub fn with(journals: Vec<Journal>) -> Self {
+        FakeJournalRepo { journals: Mutex::new(journals) }
+    }
+
+    pub fn journal(id: i32, user_id: i32, created_at: NaiveDateTime, title: &str) -> Journal {
+        Journal {
+            id,
+            user_id,
+            title: title.to_string(),
+            content: String::new(),
+            created_at,
+            updated_at: None,
+            is_pinned: false,
+        }
+    }
+
+    fn filtered(&self, filter: impl Fn(&Journal) -> bool) -> Vec<Journal> {
+        self.journals.lock().unwrap().iter().filter(|journal| filter(journal)).cloned().collect()
+    }
+}
+
+impl JournalRepo for FakeJournalRepo {
+    fn create(
+        &self,
+        user_id: i32,
+        title: &str,
+        content: &str,
+        created_at: Option<NaiveDate>,
+        tz: Tz,
+    ) -> Result<Journal, AppError> {
+        let mut journals = self.journals.lock().unwrap();
+        let created_at = match created_at {
+            Some(date) => day_start_utc(date, tz),
+            None => Utc::now().naive_utc(),
+        };
+        let mut journal = Self::journal(journals.iter().map(|journal| journal.id).max().unwrap_or(0) + 1, user_id, created_at, title);
+        journal.content = content.to_string();
+        journals.push(journal.clone());
+        Ok(journal)
+    }
+
+    fn find_by_id(&self, journal_id: i32) -> Result<Journal, AppError> {
+        self.filtered(|journal| journal.id == journal_id)
+            .pop()
+            .ok_or_else(|| not_found("Journal not found"))
+    }
+
+    fn find_by_user(
+        &self,
+        user_id: i32,
+        limit: Option<i32>,
+        offset: Option<i32>,
+        pinned: Option<bool>,
+        sort: ListSort,
+    ) -> Result<Vec<Journal>, AppError> {
+        let mut journals = self.filtered(|journal| {
+            journal.user_id == user_id && pinned.is_none_or(|pinned| journal.is_pinned == pinned)
+        });
+        match sort {
+            ListSort::DateAsc => journals.sort_by_key(|journal| (journal.created_at, journal.id)),
+            ListSort::DateDesc => journals.sort_by_key(|journal| Reverse((journal.created_at, journal.id))),
+            ListSort::UpdatedDesc => {
+                journals.sort_by_key(|journal| (journal.updated_at.is_none(), Reverse((journal.updated_at, journal.id))))
+            }
+            ListSort::Title => journals.sort_by_key(|journal| (journal.title.to_lowercase(), journal.id)),
+        }
+        Ok(page(journals, limit, offset))
+    }
+
+    fn find_summaries_by_user(
+        &self,
+        user_id: i32,
+        limit: Option<i32>,
+        offset: Option<i32>,
+        pinned: Option<bool>,
+        sort: ListSort,
+    ) -> Result<Vec<JournalSummary>, AppError> {
+        let journals = self.find_by_user(user_id, limit, offset, pinned, sort)?;
+        Ok(journals
+            .into_iter()
+            .map(|journal| JournalSummary {
+                id: journal.id,
+                title: journal.title,
+                is_pinned: journal.is_pinned,
+                created_at: journal.created_at,
+                updated_at: journal.updated_at,
+                word_count: journal.content.split_whitespace().count() as i64,
+            })
+            .collect())
+    }
+
+    fn find_by_date(&self, user_id: i32, date: NaiveDate, tz: Tz) -> Result<Journal, AppError> {
+        let (start, end) = day_range_utc(date, date, tz);
+        self.filtered(|journal| journal.user_id == user_id && journal.created_at >= start && journal.created_at < end)
+            .pop()
+            .ok_or_else(|| not_found("Journal not found for this date"))
+    }
+
+    fn find_by_date_range(
+        &self,
+        user_id: i32,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+        tz: Tz,
+    ) -> Result<Vec<Journal>, AppError> {
+        let (start, end) = day_range_utc(start_date, end_date, tz);
+        let mut journals = self.filtered(|journal| {
+            journal.user_id == user_id && journal.created_at >= start && journal.created_at < end
+        });
+        journals.sort_by_key(|journal| journal.created_at);
+        Ok(journals)
+    }
+
+    fn find_recent(&self, user_id: i32, days: i32, tz: Tz) -> Result<Vec<Journal>, AppError> {
+        let cutoff = day_start_utc(today_in(tz) - Duration::days(days as i64), tz);
+        let mut journals = self.filtered(|journal| journal.user_id == user_id && journal.created_at >= cutoff);
+        journals.sort_by_key(|journal| Reverse(journal.created_at));
+        Ok(journals)
+    }
+
+    fn update(
+        &self,
+        journal_id: i32,
+        user_id: i32,
+        title: Option<String>,
+        content: Option<String>,
+        created_at: Option<NaiveDate>,
+        tz: Tz,
+    ) -> Result<Journal, AppError> {
+        let mut journals = self.journals.lock().unwrap();
+        let journal = journals
+            .iter_mut()
+            .find(|journal| journal.id == journal_id && journal.user_id == user_id)
+            .ok_or_else(|| not_found("Journal not found"))?;
+        if let Some(title) = title {
+            journal.title = title;
+        }
+        if let Some(content) = content {
+            journal.content = content;
+        }
+        if let Some(date) = created_at {
+            journal.created_at = day_start_utc(date, tz);
+        }
+        journal.updated_at = Some(Utc::now().naive_utc());
+        Ok(journal.clone())
+    }
+
+    fn set_pinned(&self, journal_id: i32, user_id: i32, pinned: bool) -> Result<Journal, AppError> {
+        let mut journals = self.journals.lock().unwrap();
+        let journal = journals
+            .iter_mut()
+            .find(|journal| journal.id == journal_id && journal.user_id == user_id)
+            .ok_or_else(|| not_found("Journal not found"))?;
+        journal.is_pinned = pinned;
+        Ok(journal.clone())
+    }
+
+    fn delete(&self, journal_id: i32, user_id: i32) -> Result<bool, AppError> {
+        let mut journals = self.journals.lock().unwrap();
+        let before = journals.len();
+        journals.retain(|journal| !(journal.id == journal_id && journal.user_id == user_id));
+        Ok(journals.len() < before)
+    }
+
+    fn count(&self, user_id: i32) -> Result<i64, AppError> {
+        Ok(self.filtered(|journal| journal.user_id == user_id).len() as i64)
+    }
+
+    fn latest_update(&self, user_id: i32) -> Result<Option<NaiveDateTime>, AppError> {
+        Ok(self.filtered(|journal| journal.user_id == user_id).iter().filter_map(|journal| journal.updated_at).max())
+    }
+
+    fn timestamps(&self, user_id: i32) -> Result<Vec<NaiveDateTime>, AppError> {
+        Ok(self
+            .filtered(|journal| journal.user_id == user_id)
+            .into_iter()
+            .map(|journal| journal.created_at)
+            .collect())
+    }
+
+    fn for_each_by_user(&self, user_id: i32, f: &mut dyn FnMut(Journal) -> Result<(), AppError>) -> Result<(), AppError> {
+        let mut journals = self.filtered(|journal| journal.user_id == user_id);
+        journals.sort_by_key(|journal| Reverse(journal.created_at));
+        journals.into_iter().try_for_each(f)
+    }
+}
+
+#[derive(Default)]
+pub struct FakeUserRepo {
+    pub users: Mutex<Vec<User>>,
+}
+
+impl FakeUserRepo {
+    pub fn with(users: Vec<User>) -> Self {
+        FakeUserRepo { users: Mutex::new(users) }
+    }
+
+    pub fn user(id: i32, username: &str) -> User {
+        let now = Utc::now().naive_utc();
+        User {
+            id,
+            username: username.to_string(),
+            email: format!("{}@example.com", username),
+            password: String::new(),
+            settings: None,
+            age: None,
+            gender: None,
+            avatar: None,
+            created_at: now,
+            updated_at: now,
+            is_guest: false,
+            guest_device_id: None,
+        }
+    }
+}
+
+impl UserRepo for FakeUserRepo {
+    fn find_by_id(&self, user_id: i32) -> Result<User, AppError> {
+        self.users
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|user| user.id == user_id)
+            .cloned()
+            .ok_or_else(|| not_found("User not found"))
+    }
+
+    fn find_all(&self) -> Result<Vec<User>, AppError> {
+        Ok(self.users.lock().unwrap().clone())
+    }
+
+    fn username_exists(&self, username: &str) -> Result<bool, AppError> {
+        Ok(self
+            .users
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|user| user.username.eq_ignore_ascii_case(username)))
+    }
+}
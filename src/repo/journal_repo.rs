@@ -0,0 +1,207 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use chrono_tz::Tz;
+use crate::db::{journal_query, onboarding_query};
+use crate::db::pool::DbPools;
+use crate::db::transaction::run_in_transaction;
+use crate::errors::app_error::AppError;
+use crate::models::list_sort::ListSort;
+use crate::models::journal::{Journal, JournalSummary};
+use crate::models::onboarding::OnboardingStep;
+
+/// Tanggal pada jurnal dihitung di zona waktu `tz` pengguna
+pub trait JournalRepo: Send + Sync {
+    /// `created_at` kosong berarti sekarang; sekaligus menandai langkah onboarding `FirstJournal`
+    fn create(
+        &self,
+        user_id: i32,
+        title: &str,
+        content: &str,
+        created_at: Option<NaiveDate>,
+        tz: Tz,
+    ) -> Result<Journal, AppError>;
+
+    fn find_by_id(&self, journal_id: i32) -> Result<Journal, AppError>;
+
+    fn find_by_user(
+        &self,
+        user_id: i32,
+        limit: Option<i32>,
+        offset: Option<i32>,
+        pinned: Option<bool>,
+        sort: ListSort,
+    ) -> Result<Vec<Journal>, AppError>;
+
+    /// Seperti `find_by_user`, tanpa memuat isi jurnal
+    fn find_summaries_by_user(
+        &self,
+        user_id: i32,
+        limit: Option<i32>,
+        offset: Option<i32>,
+        pinned: Option<bool>,
+        sort: ListSort,
+    ) -> Result<Vec<JournalSummary>, AppError>;
+
+    fn find_by_date(&self, user_id: i32, date: NaiveDate, tz: Tz) -> Result<Journal, AppError>;
+
+    fn find_by_date_range(
+        &self,
+        user_id: i32,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+        tz: Tz,
+    ) -> Result<Vec<Journal>, AppError>;
+
+    /// Jurnal sejak `days` hari terakhir, terbaru lebih dulu
+    fn find_recent(&self, user_id: i32, days: i32, tz: Tz) -> Result<Vec<Journal>, AppError>;
+
+    /// Field `None` tidak diubah
+    fn update(
+        &self,
+        journal_id: i32,
+        user_id: i32,
+        title: Option<String>,
+        content: Option<String>,
+        created_at: Option<NaiveDate>,
+        tz: Tz,
+    ) -> Result<Journal, AppError>;
+
+    fn set_pinned(&self, journal_id: i32, user_id: i32, pinned: bool) -> Result<Journal, AppError>;
+
+    /// `false` jika jurnal tidak ada atau bukan milik pengguna
+    fn delete(&self, journal_id: i32, user_id: i32) -> Result<bool, AppError>;
+
+    fn count(&self, user_id: i32) -> Result<i64, AppError>;
+
+    /// `updated_at` terbaru, dipakai sebagai versi cache statistik
+    fn latest_update(&self, user_id: i32) -> Result<Option<NaiveDateTime>, AppError>;
+
+    /// Waktu pembuatan (UTC) semua jurnal, untuk menghitung streak
+    fn timestamps(&self, user_id: i32) -> Result<Vec<NaiveDateTime>, AppError>;
+
+    /// Semua jurnal pengguna, terbaru lebih dulu, diteruskan satu per satu ke `f`
+    fn for_each_by_user(&self, user_id: i32, f: &mut dyn FnMut(Journal) -> Result<(), AppError>) -> Result<(), AppError>;
+}
+
+pub struct DieselJournalRepo {
+    pool: DbPools,
+}
+
+impl DieselJournalRepo {
+    pub fn new(pool: DbPools) -> Self {
+        DieselJournalRepo { pool }
+    }
+}
+
+impl JournalRepo for DieselJournalRepo {
+    fn create(
+        &self,
+        user_id: i32,
+        title: &str,
+        content: &str,
+        created_at: Option<NaiveDate>,
+        tz: Tz,
+    ) -> Result<Journal, AppError> {
+        let mut conn = self.pool.conn_write()?;
+        run_in_transaction(&mut conn, |conn| {
+            let journal = journal_query::create_journal(conn, user_id, title, content, created_at, tz)?;
+            onboarding_query::mark_step_completed(conn, user_id, OnboardingStep::FirstJournal)?;
+            Ok(journal)
+        })
+    }
+
+    fn find_by_id(&self, journal_id: i32) -> Result<Journal, AppError> {
+        let mut conn = self.pool.conn_write()?;
+        journal_query::find_journal_by_id(&mut conn, journal_id)
+    }
+
+    fn find_by_user(
+        &self,
+        user_id: i32,
+        limit: Option<i32>,
+        offset: Option<i32>,
+        pinned: Option<bool>,
+        sort: ListSort,
+    ) -> Result<Vec<Journal>, AppError> {
+        let mut conn = self.pool.conn_read()?;
+        journal_query::find_journals_by_user(&mut conn, user_id, limit, offset, pinned, sort)
+    }
+
+    fn find_summaries_by_user(
+        &self,
+        user_id: i32,
+        limit: Option<i32>,
+        offset: Option<i32>,
+        pinned: Option<bool>,
+        sort: ListSort,
+    ) -> Result<Vec<JournalSummary>, AppError> {
+        let mut conn = self.pool.conn_read()?;
+        journal_query::find_journal_summaries_by_user(&mut conn, user_id, limit, offset, pinned, sort)
+    }
+
+    fn find_by_date(&self, user_id: i32, date: NaiveDate, tz: Tz) -> Result<Journal, AppError> {
+        let mut conn = self.pool.conn_write()?;
+        journal_query::find_journal_by_user_and_date(&mut conn, user_id, date, tz)
+    }
+
+    fn find_by_date_range(
+        &self,
+        user_id: i32,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+        tz: Tz,
+    ) -> Result<Vec<Journal>, AppError> {
+        let mut conn = self.pool.conn_read()?;
+        journal_query::find_journals_by_date_range(&mut conn, user_id, start_date, end_date, tz)
+    }
+
+    fn find_recent(&self, user_id: i32, days: i32, tz: Tz) -> Result<Vec<Journal>, AppError> {
+        let mut conn = self.pool.conn_read()?;
+        journal_query::get_recent_journals(&mut conn, user_id, days, tz)
+    }
+
+    fn update(
+        &self,
+        journal_id: i32,
+        user_id: i32,
+        title: Option<String>,
+        content: Option<String>,
+        created_at: Option<NaiveDate>,
+        tz: Tz,
+    ) -> Result<Journal, AppError> {
+        let mut conn = self.pool.conn_write()?;
+        // update_journal membaca lalu menulis, jalankan dalam satu transaksi
+        run_in_transaction(&mut conn, |conn| {
+            journal_query::update_journal(conn, journal_id, user_id, title, content, created_at, tz)
+        })
+    }
+
+    fn set_pinned(&self, journal_id: i32, user_id: i32, pinned: bool) -> Result<Journal, AppError> {
+        let mut conn = self.pool.conn_write()?;
+        journal_query::set_journal_pinned(&mut conn, journal_id, user_id, pinned)
+    }
+
+    fn delete(&self, journal_id: i32, user_id: i32) -> Result<bool, AppError> {
+        let mut conn = self.pool.conn_write()?;
+        journal_query::delete_journal(&mut conn, journal_id, user_id)
+    }
+
+    fn count(&self, user_id: i32) -> Result<i64, AppError> {
+        let mut conn = self.pool.conn_read()?;
+        journal_query::get_journal_stats_simple(&mut conn, user_id)
+    }
+
+    fn latest_update(&self, user_id: i32) -> Result<Option<NaiveDateTime>, AppError> {
+        let mut conn = self.pool.conn_read()?;
+        journal_query::latest_journal_update(&mut conn, user_id)
+    }
+
+    fn timestamps(&self, user_id: i32) -> Result<Vec<NaiveDateTime>, AppError> {
+        let mut conn = self.pool.conn_read()?;
+        journal_query::find_journal_timestamps(&mut conn, user_id)
+    }
+
+    fn for_each_by_user(&self, user_id: i32, f: &mut dyn FnMut(Journal) -> Result<(), AppError>) -> Result<(), AppError> {
+        let mut conn = self.pool.conn_read()?;
+        journal_query::for_each_journal_by_user(&mut conn, user_id, f)
+    }
+}
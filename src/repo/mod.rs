@@ -0,0 +1,13 @@
+//! Repository untuk data inti (mood, jurnal, pengguna). Service bergantung pada trait di sini,
+//! bukan langsung pada `db::*_query`, sehingga bisa diuji dengan test double tanpa database.
+//! Operasi yang butuh transaksi lintas tabel tetap memakai query modul `db` dengan koneksi pemanggil.
+
+pub mod mood_repo;
+pub mod journal_repo;
+pub mod user_repo;
+#[cfg(test)]
+pub mod fake;
+
+pub use journal_repo::{DieselJournalRepo, JournalRepo};
+pub use mood_repo::{DieselMoodRepo, MoodRepo};
+pub use user_repo::{DieselUserRepo, UserRepo};
\ No newline at end of file
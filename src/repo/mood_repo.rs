@@ -0,0 +1,192 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use crate::db::{mood_query, onboarding_query};
+use crate::db::pool::DbPools;
+use crate::db::transaction::run_in_transaction;
+use crate::errors::app_error::AppError;
+use crate::models::list_sort::ListSort;
+use crate::models::mood::{Mood, MoodSearchFilter, NewMood, UpdateMoodRequest};
+use crate::models::onboarding::OnboardingStep;
+
+pub trait MoodRepo: Send + Sync {
+    /// Conflict jika tanggal tersebut sudah punya mood; sekaligus menandai langkah onboarding `FirstMood`
+    fn create(&self, mood: NewMood) -> Result<Mood, AppError>;
+
+    fn find_by_id(&self, mood_id: i32) -> Result<Mood, AppError>;
+
+    fn find_by_user(
+        &self,
+        user_id: i32,
+        limit: Option<i32>,
+        offset: Option<i32>,
+        pinned: Option<bool>,
+        sort: ListSort,
+    ) -> Result<Vec<Mood>, AppError>;
+
+    fn find_by_date(&self, user_id: i32, date: NaiveDate) -> Result<Mood, AppError>;
+
+    fn find_by_date_range(&self, user_id: i32, start_date: NaiveDate, end_date: NaiveDate) -> Result<Vec<Mood>, AppError>;
+
+    /// Mood yang catatannya cocok dengan `filter`, terbaru lebih dulu
+    fn search(&self, user_id: i32, filter: &MoodSearchFilter) -> Result<Vec<Mood>, AppError>;
+
+    /// Mood sejak `days` hari sebelum `today`, terbaru lebih dulu
+    fn find_recent(&self, user_id: i32, days: i32, today: NaiveDate) -> Result<Vec<Mood>, AppError>;
+
+    /// Terapkan `changes` yang sudah divalidasi; Conflict jika tanggal baru sudah dipakai mood lain
+    fn update(&self, mood_id: i32, user_id: i32, changes: UpdateMoodRequest) -> Result<Mood, AppError>;
+
+    fn set_pinned(&self, mood_id: i32, user_id: i32, pinned: bool) -> Result<Mood, AppError>;
+
+    /// `false` jika mood tidak ada atau bukan milik pengguna
+    fn move_to_trash(&self, mood_id: i32, user_id: i32, deleted_at: NaiveDateTime) -> Result<bool, AppError>;
+
+    /// Kembalikan mood yang dihapus setelah `deleted_after`; Conflict jika tanggalnya sudah terisi lagi
+    fn restore(&self, mood_id: i32, user_id: i32, deleted_after: NaiveDateTime) -> Result<Mood, AppError>;
+
+    fn count(&self, user_id: i32) -> Result<i64, AppError>;
+
+    /// `updated_at` terbaru, dipakai sebagai versi cache statistik
+    fn latest_update(&self, user_id: i32) -> Result<Option<NaiveDateTime>, AppError>;
+
+    /// Jumlah mood per jenis, seluruh riwayat atau dalam rentang tanggal (inklusif)
+    fn count_by_type(&self, user_id: i32, range: Option<(NaiveDate, NaiveDate)>) -> Result<Vec<(String, i64)>, AppError>;
+
+    /// Rata-rata skor mood; `None` jika tidak ada mood
+    fn average_score(&self, user_id: i32, range: Option<(NaiveDate, NaiveDate)>) -> Result<Option<f64>, AppError>;
+
+    /// Semua tanggal yang punya mood sampai `end_date`, untuk menghitung streak
+    fn dates_until(&self, user_id: i32, end_date: NaiveDate) -> Result<Vec<NaiveDate>, AppError>;
+
+    /// Semua mood pengguna, terbaru lebih dulu, diteruskan satu per satu ke `f`
+    fn for_each_by_user(&self, user_id: i32, f: &mut dyn FnMut(Mood) -> Result<(), AppError>) -> Result<(), AppError>;
+}
+
+pub struct DieselMoodRepo {
+    pool: DbPools,
+}
+
+impl DieselMoodRepo {
+    pub fn new(pool: DbPools) -> Self {
+        DieselMoodRepo { pool }
+    }
+}
+
+impl MoodRepo for DieselMoodRepo {
+    fn create(&self, mood: NewMood) -> Result<Mood, AppError> {
+        let mut conn = self.pool.conn_write()?;
+        run_in_transaction(&mut conn, |conn| {
+            if mood_query::check_mood_exists_for_date(conn, mood.user_id, mood.date)? {
+                return Err(AppError::Conflict("Mood already exists for this date".to_string()));
+            }
+            let created = mood_query::create_mood(conn, &mood)?;
+            onboarding_query::mark_step_completed(conn, mood.user_id, OnboardingStep::FirstMood)?;
+            Ok(created)
+        })
+    }
+
+    fn find_by_id(&self, mood_id: i32) -> Result<Mood, AppError> {
+        let mut conn = self.pool.conn_write()?;
+        mood_query::find_mood_by_id(&mut conn, mood_id)
+    }
+
+    fn find_by_user(
+        &self,
+        user_id: i32,
+        limit: Option<i32>,
+        offset: Option<i32>,
+        pinned: Option<bool>,
+        sort: ListSort,
+    ) -> Result<Vec<Mood>, AppError> {
+        let mut conn = self.pool.conn_read()?;
+        mood_query::find_moods_by_user(&mut conn, user_id, limit, offset, pinned, sort)
+    }
+
+    fn find_by_date(&self, user_id: i32, date: NaiveDate) -> Result<Mood, AppError> {
+        let mut conn = self.pool.conn_write()?;
+        mood_query::find_mood_by_user_and_date(&mut conn, user_id, date)
+    }
+
+    fn find_by_date_range(&self, user_id: i32, start_date: NaiveDate, end_date: NaiveDate) -> Result<Vec<Mood>, AppError> {
+        let mut conn = self.pool.conn_read()?;
+        mood_query::find_moods_by_date_range(&mut conn, user_id, start_date, end_date)
+    }
+
+    fn search(&self, user_id: i32, filter: &MoodSearchFilter) -> Result<Vec<Mood>, AppError> {
+        let mut conn = self.pool.conn_read()?;
+        mood_query::search_moods(&mut conn, user_id, filter)
+    }
+
+    fn find_recent(&self, user_id: i32, days: i32, today: NaiveDate) -> Result<Vec<Mood>, AppError> {
+        let mut conn = self.pool.conn_read()?;
+        mood_query::get_recent_moods(&mut conn, user_id, days, today)
+    }
+
+    fn update(&self, mood_id: i32, user_id: i32, changes: UpdateMoodRequest) -> Result<Mood, AppError> {
+        let mut conn = self.pool.conn_write()?;
+        run_in_transaction(&mut conn, |conn| {
+            if let Some(date) = changes.date {
+                if mood_query::check_mood_exists_for_date_excluding(conn, user_id, date, mood_id)? {
+                    return Err(AppError::Conflict("Another mood already exists for this date".to_string()));
+                }
+            }
+            mood_query::update_mood_with_date(conn, mood_id, user_id, changes)
+        })
+    }
+
+    fn set_pinned(&self, mood_id: i32, user_id: i32, pinned: bool) -> Result<Mood, AppError> {
+        let mut conn = self.pool.conn_write()?;
+        mood_query::set_mood_pinned(&mut conn, mood_id, user_id, pinned)
+    }
+
+    fn move_to_trash(&self, mood_id: i32, user_id: i32, deleted_at: NaiveDateTime) -> Result<bool, AppError> {
+        let mut conn = self.pool.conn_write()?;
+        run_in_transaction(&mut conn, |conn| {
+            mood_query::move_mood_to_trash(conn, mood_id, user_id, deleted_at)
+        })
+    }
+
+    fn restore(&self, mood_id: i32, user_id: i32, deleted_after: NaiveDateTime) -> Result<Mood, AppError> {
+        let mut conn = self.pool.conn_write()?;
+        run_in_transaction(&mut conn, |conn| {
+            let trashed = mood_query::find_trashed_mood(conn, mood_id, user_id, deleted_after)?
+                .ok_or_else(|| AppError::NotFound("Deleted mood not found or undo window has passed".to_string()))?;
+
+            // Tanggal yang sama bisa saja sudah diisi mood baru setelah penghapusan
+            if mood_query::check_mood_exists_for_date(conn, user_id, trashed.date)? {
+                return Err(AppError::Conflict("Mood already exists for this date".to_string()));
+            }
+
+            mood_query::restore_trashed_mood(conn, trashed)
+        })
+    }
+
+    fn count(&self, user_id: i32) -> Result<i64, AppError> {
+        let mut conn = self.pool.conn_read()?;
+        mood_query::get_mood_stats_simple(&mut conn, user_id)
+    }
+
+    fn latest_update(&self, user_id: i32) -> Result<Option<NaiveDateTime>, AppError> {
+        let mut conn = self.pool.conn_read()?;
+        mood_query::latest_mood_update(&mut conn, user_id)
+    }
+
+    fn count_by_type(&self, user_id: i32, range: Option<(NaiveDate, NaiveDate)>) -> Result<Vec<(String, i64)>, AppError> {
+        let mut conn = self.pool.conn_read()?;
+        mood_query::count_moods_by_type(&mut conn, user_id, range)
+    }
+
+    fn average_score(&self, user_id: i32, range: Option<(NaiveDate, NaiveDate)>) -> Result<Option<f64>, AppError> {
+        let mut conn = self.pool.conn_read()?;
+        mood_query::average_mood_score(&mut conn, user_id, range)
+    }
+
+    fn dates_until(&self, user_id: i32, end_date: NaiveDate) -> Result<Vec<NaiveDate>, AppError> {
+        let mut conn = self.pool.conn_read()?;
+        mood_query::find_mood_dates_until(&mut conn, user_id, end_date)
+    }
+
+    fn for_each_by_user(&self, user_id: i32, f: &mut dyn FnMut(Mood) -> Result<(), AppError>) -> Result<(), AppError> {
+        let mut conn = self.pool.conn_read()?;
+        mood_query::for_each_mood_by_user(&mut conn, user_id, f)
+    }
+}
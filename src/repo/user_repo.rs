@@ -0,0 +1,40 @@
+use crate::db::pool::DbPools;
+use crate::db::user_query;
+use crate::errors::app_error::AppError;
+use crate::models::user::User;
+
+pub trait UserRepo: Send + Sync {
+    fn find_by_id(&self, user_id: i32) -> Result<User, AppError>;
+
+    fn find_all(&self) -> Result<Vec<User>, AppError>;
+
+    /// Pembandingan username tidak membedakan huruf besar/kecil
+    fn username_exists(&self, username: &str) -> Result<bool, AppError>;
+}
+
+pub struct DieselUserRepo {
+    pool: DbPools,
+}
+
+impl DieselUserRepo {
+    pub fn new(pool: DbPools) -> Self {
+        DieselUserRepo { pool }
+    }
+}
+
+impl UserRepo for DieselUserRepo {
+    fn find_by_id(&self, user_id: i32) -> Result<User, AppError> {
+        let mut conn = self.pool.conn_write()?;
+        user_query::find_user_by_id(&mut conn, user_id)
+    }
+
+    fn find_all(&self) -> Result<Vec<User>, AppError> {
+        let mut conn = self.pool.conn_read()?;
+        user_query::get_all_users(&mut conn)
+    }
+
+    fn username_exists(&self, username: &str) -> Result<bool, AppError> {
+        let mut conn = self.pool.conn_read()?;
+        user_query::username_exists(&mut conn, username)
+    }
+}
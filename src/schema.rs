@@ -1,5 +1,183 @@
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    api_usage_daily (user_id, day) {
+        user_id -> Int4,
+        day -> Date,
+        request_count -> Int8,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    appointments (id) {
+        id -> Int4,
+        user_id -> Int4,
+        psychologist_id -> Int4,
+        request_id -> Nullable<Int4>,
+        starts_at -> Timestamp,
+        ends_at -> Timestamp,
+        #[max_length = 20]
+        status -> Varchar,
+        meeting_link -> Nullable<Text>,
+        reminder_24h_sent_at -> Nullable<Timestamp>,
+        reminder_1h_sent_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    audit_logs (id) {
+        id -> Int4,
+        actor_user_id -> Nullable<Int4>,
+        #[max_length = 64]
+        action -> Varchar,
+        target_user_id -> Nullable<Int4>,
+        details -> Nullable<Text>,
+        #[max_length = 64]
+        ip_address -> Nullable<Varchar>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    auth_exchange_codes (id) {
+        id -> Int4,
+        #[max_length = 64]
+        code -> Varchar,
+        user_id -> Int4,
+        expires_at -> Timestamp,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    calendar_feed_tokens (id) {
+        id -> Int4,
+        user_id -> Int4,
+        #[max_length = 64]
+        token -> Varchar,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    community_groups (id) {
+        id -> Int4,
+        #[max_length = 50]
+        slug -> Varchar,
+        #[max_length = 100]
+        name -> Varchar,
+        description -> Nullable<Text>,
+        is_active -> Bool,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    community_memberships (group_id, user_id) {
+        group_id -> Int4,
+        user_id -> Int4,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    community_posts (id) {
+        id -> Int4,
+        group_id -> Int4,
+        user_id -> Int4,
+        body -> Text,
+        held -> Bool,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    community_reactions (post_id, user_id, kind) {
+        post_id -> Int4,
+        user_id -> Int4,
+        #[max_length = 20]
+        kind -> Varchar,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    deleted_moods (id) {
+        id -> Int4,
+        user_id -> Int4,
+        date -> Date,
+        #[max_length = 50]
+        mood -> Varchar,
+        #[max_length = 10]
+        emoji -> Varchar,
+        notes -> Nullable<Text>,
+        created_at -> Timestamp,
+        updated_at -> Nullable<Timestamp>,
+        details -> Nullable<Jsonb>,
+        is_pinned -> Bool,
+        deleted_at -> Timestamp,
+        #[max_length = 100]
+        place_label -> Nullable<Varchar>,
+        latitude -> Nullable<Float8>,
+        longitude -> Nullable<Float8>,
+    }
+}
+
+diesel::table! {
+    devices (id) {
+        id -> Int4,
+        user_id -> Int4,
+        #[max_length = 10]
+        platform -> Varchar,
+        push_token -> Text,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    email_change_requests (id) {
+        id -> Int4,
+        user_id -> Int4,
+        #[max_length = 255]
+        new_email -> Varchar,
+        #[max_length = 64]
+        token -> Varchar,
+        expires_at -> Timestamp,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    health_samples (id) {
+        id -> Int4,
+        user_id -> Int4,
+        #[max_length = 16]
+        kind -> Varchar,
+        #[max_length = 32]
+        source -> Varchar,
+        start_at -> Timestamp,
+        end_at -> Timestamp,
+        value -> Nullable<Float8>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    help_request_comments (id) {
+        id -> Int4,
+        help_request_id -> Int4,
+        author_id -> Nullable<Int4>,
+        is_staff -> Bool,
+        body -> Text,
+        created_at -> Timestamp,
+        held -> Bool,
+    }
+}
+
 diesel::table! {
     help_requests (id) {
         id -> Int4,
@@ -10,6 +188,59 @@ diesel::table! {
         email -> Varchar,
         message -> Text,
         created_at -> Timestamp,
+        #[max_length = 20]
+        status -> Varchar,
+        updated_at -> Timestamp,
+        held -> Bool,
+    }
+}
+
+diesel::table! {
+    insight_notifications (id) {
+        id -> Int4,
+        user_id -> Int4,
+        #[max_length = 50]
+        kind -> Varchar,
+        start_date -> Date,
+        notified_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    jobs (id) {
+        id -> Int4,
+        user_id -> Nullable<Int4>,
+        #[max_length = 32]
+        kind -> Varchar,
+        #[max_length = 16]
+        status -> Varchar,
+        params -> Text,
+        #[max_length = 100]
+        result_content_type -> Nullable<Varchar>,
+        #[max_length = 255]
+        result_filename -> Nullable<Varchar>,
+        error -> Nullable<Text>,
+        created_at -> Timestamp,
+        started_at -> Nullable<Timestamp>,
+        finished_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    journal_drafts (user_id) {
+        user_id -> Int4,
+        #[max_length = 500]
+        title -> Varchar,
+        content -> Text,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    journal_retention_states (user_id) {
+        user_id -> Int4,
+        notice_sent_at -> Timestamp,
+        last_purged_at -> Nullable<Timestamp>,
     }
 }
 
@@ -22,6 +253,54 @@ diesel::table! {
         content -> Text,
         created_at -> Timestamp,
         updated_at -> Nullable<Timestamp>,
+        is_pinned -> Bool,
+    }
+}
+
+diesel::table! {
+    login_attempts (id) {
+        id -> Int4,
+        user_id -> Nullable<Int4>,
+        #[max_length = 255]
+        email -> Varchar,
+        #[max_length = 64]
+        ip_address -> Nullable<Varchar>,
+        user_agent -> Nullable<Text>,
+        success -> Bool,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    messages (id) {
+        id -> Int4,
+        user_id -> Int4,
+        psychologist_id -> Int4,
+        from_psychologist -> Bool,
+        body -> Text,
+        created_at -> Timestamp,
+        read_at -> Nullable<Timestamp>,
+        held -> Bool,
+    }
+}
+
+diesel::table! {
+    moderation_verdicts (id) {
+        id -> Int4,
+        #[max_length = 30]
+        content_type -> Varchar,
+        content_id -> Int4,
+        author_id -> Nullable<Int4>,
+        #[max_length = 10]
+        action -> Varchar,
+        #[max_length = 20]
+        source -> Varchar,
+        reasons -> Array<Text>,
+        #[max_length = 20]
+        status -> Varchar,
+        reviewed_by -> Nullable<Int4>,
+        reviewed_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
     }
 }
 
@@ -37,6 +316,78 @@ diesel::table! {
         notes -> Nullable<Text>,
         created_at -> Timestamp,
         updated_at -> Nullable<Timestamp>,
+        details -> Nullable<Jsonb>,
+        is_pinned -> Bool,
+        #[max_length = 100]
+        place_label -> Nullable<Varchar>,
+        latitude -> Nullable<Float8>,
+        longitude -> Nullable<Float8>,
+    }
+}
+
+diesel::table! {
+    oauth_accounts (id) {
+        id -> Int4,
+        user_id -> Int4,
+        #[max_length = 32]
+        provider -> Varchar,
+        #[max_length = 255]
+        provider_user_id -> Varchar,
+        refresh_token_encrypted -> Nullable<Text>,
+        access_token_encrypted -> Nullable<Text>,
+        access_token_expires_at -> Nullable<Timestamp>,
+        scope -> Nullable<Text>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    organization_invitations (id) {
+        id -> Int4,
+        organization_id -> Int4,
+        #[max_length = 255]
+        email -> Varchar,
+        #[max_length = 20]
+        role -> Varchar,
+        #[max_length = 64]
+        token -> Varchar,
+        invited_by -> Nullable<Int4>,
+        expires_at -> Timestamp,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    organization_members (organization_id, user_id) {
+        organization_id -> Int4,
+        user_id -> Int4,
+        #[max_length = 20]
+        role -> Varchar,
+        data_sharing_consent -> Bool,
+        consent_updated_at -> Nullable<Timestamp>,
+        joined_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    organizations (id) {
+        id -> Int4,
+        #[max_length = 100]
+        name -> Varchar,
+        created_by -> Nullable<Int4>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    password_reset_tokens (id) {
+        id -> Int4,
+        user_id -> Int4,
+        #[max_length = 64]
+        token -> Varchar,
+        expires_at -> Timestamp,
+        created_at -> Timestamp,
     }
 }
 
@@ -52,6 +403,57 @@ diesel::table! {
         #[max_length = 255]
         preferred_time -> Nullable<Varchar>,
         created_at -> Timestamp,
+        psychologist_id -> Nullable<Int4>,
+        slot_id -> Nullable<Int4>,
+    }
+}
+
+diesel::table! {
+    psychologist_slots (id) {
+        id -> Int4,
+        psychologist_id -> Int4,
+        starts_at -> Timestamp,
+        ends_at -> Timestamp,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    psychologists (id) {
+        id -> Int4,
+        #[max_length = 255]
+        name -> Varchar,
+        #[max_length = 100]
+        specialization -> Varchar,
+        languages -> Array<Text>,
+        bio -> Nullable<Text>,
+        is_active -> Bool,
+        created_at -> Timestamp,
+        user_id -> Nullable<Int4>,
+    }
+}
+
+diesel::table! {
+    push_outbox (id) {
+        id -> Int4,
+        device_id -> Int4,
+        #[max_length = 50]
+        category -> Varchar,
+        title -> Text,
+        body -> Text,
+        attempts -> Int4,
+        next_attempt_at -> Timestamp,
+        last_error -> Nullable<Text>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    scheduled_job_runs (job_name) {
+        #[max_length = 64]
+        job_name -> Varchar,
+        last_scheduled_at -> Timestamp,
+        claimed_at -> Timestamp,
     }
 }
 
@@ -63,6 +465,48 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    usage_quotas (user_id, operation, period_start) {
+        user_id -> Int4,
+        #[max_length = 32]
+        operation -> Varchar,
+        period_start -> Date,
+        used -> Int4,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    used_checkin_tokens (jti) {
+        #[max_length = 64]
+        jti -> Varchar,
+        user_id -> Int4,
+        used_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    user_onboarding (user_id) {
+        user_id -> Int4,
+        profile_completed_at -> Nullable<Timestamp>,
+        first_mood_at -> Nullable<Timestamp>,
+        first_journal_at -> Nullable<Timestamp>,
+        reminder_set_at -> Nullable<Timestamp>,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    user_webhooks (id) {
+        id -> Int4,
+        user_id -> Int4,
+        #[max_length = 20]
+        platform -> Varchar,
+        url -> Text,
+        created_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     users (id) {
         id -> Int4,
@@ -79,19 +523,114 @@ diesel::table! {
         avatar -> Nullable<Text>,
         created_at -> Timestamp,
         updated_at -> Timestamp,
+        is_guest -> Bool,
+        #[max_length = 255]
+        guest_device_id -> Nullable<Varchar>,
+    }
+}
+
+diesel::table! {
+    webhook_deliveries (id) {
+        id -> Int4,
+        webhook_id -> Int4,
+        period_start -> Date,
+        payload -> Text,
+        #[max_length = 20]
+        status -> Varchar,
+        attempts -> Int4,
+        next_attempt_at -> Timestamp,
+        last_error -> Nullable<Text>,
+        delivered_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
     }
 }
 
+diesel::joinable!(api_usage_daily -> users (user_id));
+diesel::joinable!(appointments -> psychologist_requests (request_id));
+diesel::joinable!(appointments -> psychologists (psychologist_id));
+diesel::joinable!(appointments -> users (user_id));
+diesel::joinable!(auth_exchange_codes -> users (user_id));
+diesel::joinable!(calendar_feed_tokens -> users (user_id));
+diesel::joinable!(community_memberships -> community_groups (group_id));
+diesel::joinable!(community_memberships -> users (user_id));
+diesel::joinable!(community_posts -> community_groups (group_id));
+diesel::joinable!(community_posts -> users (user_id));
+diesel::joinable!(community_reactions -> community_posts (post_id));
+diesel::joinable!(community_reactions -> users (user_id));
+diesel::joinable!(deleted_moods -> users (user_id));
+diesel::joinable!(devices -> users (user_id));
+diesel::joinable!(email_change_requests -> users (user_id));
+diesel::joinable!(health_samples -> users (user_id));
+diesel::joinable!(help_request_comments -> help_requests (help_request_id));
+diesel::joinable!(help_request_comments -> users (author_id));
 diesel::joinable!(help_requests -> users (user_id));
+diesel::joinable!(insight_notifications -> users (user_id));
+diesel::joinable!(jobs -> users (user_id));
+diesel::joinable!(journal_drafts -> users (user_id));
+diesel::joinable!(journal_retention_states -> users (user_id));
 diesel::joinable!(journals -> users (user_id));
+diesel::joinable!(login_attempts -> users (user_id));
+diesel::joinable!(messages -> psychologists (psychologist_id));
+diesel::joinable!(messages -> users (user_id));
 diesel::joinable!(moods -> users (user_id));
+diesel::joinable!(oauth_accounts -> users (user_id));
+diesel::joinable!(organization_invitations -> organizations (organization_id));
+diesel::joinable!(organization_members -> organizations (organization_id));
+diesel::joinable!(organization_members -> users (user_id));
+diesel::joinable!(organizations -> users (created_by));
+diesel::joinable!(password_reset_tokens -> users (user_id));
+diesel::joinable!(psychologist_requests -> psychologist_slots (slot_id));
+diesel::joinable!(psychologist_requests -> psychologists (psychologist_id));
 diesel::joinable!(psychologist_requests -> users (user_id));
+diesel::joinable!(psychologist_slots -> psychologists (psychologist_id));
+diesel::joinable!(psychologists -> users (user_id));
+diesel::joinable!(push_outbox -> devices (device_id));
+diesel::joinable!(usage_quotas -> users (user_id));
+diesel::joinable!(used_checkin_tokens -> users (user_id));
+diesel::joinable!(user_onboarding -> users (user_id));
+diesel::joinable!(user_webhooks -> users (user_id));
+diesel::joinable!(webhook_deliveries -> user_webhooks (webhook_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
+    api_usage_daily,
+    appointments,
+    audit_logs,
+    auth_exchange_codes,
+    calendar_feed_tokens,
+    community_groups,
+    community_memberships,
+    community_posts,
+    community_reactions,
+    deleted_moods,
+    devices,
+    email_change_requests,
+    health_samples,
+    help_request_comments,
     help_requests,
+    insight_notifications,
+    jobs,
+    journal_drafts,
+    journal_retention_states,
     journals,
+    login_attempts,
+    messages,
+    moderation_verdicts,
     moods,
+    oauth_accounts,
+    organization_invitations,
+    organization_members,
+    organizations,
+    password_reset_tokens,
     psychologist_requests,
+    psychologist_slots,
+    psychologists,
+    push_outbox,
+    scheduled_job_runs,
     token_blacklist,
+    usage_quotas,
+    used_checkin_tokens,
+    user_onboarding,
+    user_webhooks,
     users,
+    webhook_deliveries,
 );
@@ -0,0 +1,84 @@
+use chrono::{Datelike, Duration, NaiveDate, Utc};
+use crate::db::admin_analytics_query;
+use crate::db::pool::DbPools;
+use crate::errors::app_error::AppError;
+use crate::models::admin_analytics::{RetentionCohort, WeeklyActivity};
+
+const DEFAULT_WEEKS: i64 = 12;
+const MAX_WEEKS: i64 = 104;
+
+/// Pengguna aktif mingguan dan rata-rata entri per pengguna aktif, termasuk minggu tanpa aktivitas
+pub fn get_weekly_activity(
+    pool: &DbPools,
+    weeks: Option<i64>,
+) -> Result<Vec<WeeklyActivity>, AppError> {
+    let since = first_week_start(weeks)?;
+    let mut conn = pool.conn_read()?;
+
+    let rows = admin_analytics_query::weekly_activity(&mut conn, since)?;
+
+    let current_week = week_start(Utc::now().date_naive());
+    let mut result = Vec::new();
+    let mut week = since;
+    while week <= current_week {
+        let row = rows.iter().find(|row| row.week_start == week);
+        let active_users = row.map_or(0, |row| row.active_users);
+        let entries = row.map_or(0, |row| row.entries);
+        result.push(WeeklyActivity {
+            week_start: week,
+            active_users,
+            entries,
+            avg_entries_per_active_user: if active_users > 0 {
+                round2(entries as f64 / active_users as f64)
+            } else {
+                0.0
+            },
+        });
+        week += Duration::weeks(1);
+    }
+
+    Ok(result)
+}
+
+/// Retensi D1/D7/D30 per kohort minggu pendaftaran
+pub fn get_retention_cohorts(
+    pool: &DbPools,
+    weeks: Option<i64>,
+) -> Result<Vec<RetentionCohort>, AppError> {
+    let since = first_week_start(weeks)?;
+    let mut conn = pool.conn_read()?;
+
+    let rows = admin_analytics_query::retention_cohorts(&mut conn, since, Utc::now().date_naive())?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| RetentionCohort {
+            signup_week: row.signup_week,
+            users: row.users,
+            d1: percentage(row.d1_retained, row.d1_eligible),
+            d7: percentage(row.d7_retained, row.d7_eligible),
+            d30: percentage(row.d30_retained, row.d30_eligible),
+        })
+        .collect())
+}
+
+/// Senin dari minggu pertama yang dihitung
+fn first_week_start(weeks: Option<i64>) -> Result<NaiveDate, AppError> {
+    let weeks = weeks.unwrap_or(DEFAULT_WEEKS);
+    if !(1..=MAX_WEEKS).contains(&weeks) {
+        return Err(AppError::BadRequest(format!("Weeks must be between 1 and {}", MAX_WEEKS)));
+    }
+    Ok(week_start(Utc::now().date_naive()) - Duration::weeks(weeks - 1))
+}
+
+fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+fn percentage(count: i64, total: i64) -> Option<f64> {
+    (total > 0).then(|| round2(count as f64 * 100.0 / total as f64))
+}
+
+fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
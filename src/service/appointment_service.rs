@@ -0,0 +1,244 @@
+use chrono::{NaiveDateTime, Utc};
+use chrono_tz::Tz;
+use diesel::pg::PgConnection;
+use crate::db::{appointment_query, psychologist_query, user_query};
+use crate::db::pool::DbPools;
+use crate::db::transaction::run_in_transaction;
+use crate::errors::app_error::AppError;
+use crate::i18n::{t_in, t_in_args, Locale};
+use crate::models::appointment::{
+    Appointment, AppointmentReminder, AppointmentResponse, ConfirmAppointmentRequest, NewAppointment,
+    RescheduleAppointmentRequest,
+};
+use crate::models::audit::{AuditAction, NewAuditLog};
+use crate::models::notification::{Notification, NotificationCategory, NotificationChannel};
+use crate::models::user::UserSettings;
+use crate::service::{audit_service, notification_service};
+use crate::utils::mailer::Mailer;
+use crate::utils::text_limits::ensure_max_length;
+use crate::utils::timezone::parse_timezone;
+
+const MEETING_LINK_MAX_LENGTH: usize = 2048;
+
+fn meeting_link(link: Option<&str>) -> Result<Option<&str>, AppError> {
+    let Some(link) = link.map(str::trim).filter(|link| !link.is_empty()) else {
+        return Ok(None);
+    };
+    ensure_max_length("Meeting link", link, MEETING_LINK_MAX_LENGTH)?;
+    if !link.starts_with("https://") || link.len() == "https://".len() {
+        return Err(AppError::BadRequest("Meeting link must be an https URL".to_string()));
+    }
+    Ok(Some(link))
+}
+
+/// Janji milik pengguna yang masih bisa diubah: terjadwal dan belum dimulai
+fn require_changeable(
+    conn: &mut PgConnection,
+    user_id: i32,
+    appointment_id: i32,
+    now: NaiveDateTime,
+) -> Result<Appointment, AppError> {
+    let appointment = appointment_query::find_user_appointment_for_update(conn, user_id, appointment_id)?
+        .ok_or_else(|| AppError::NotFound("Appointment not found".to_string()))?;
+    if !appointment.is_scheduled() || appointment.starts_at <= now {
+        return Err(AppError::Conflict("Appointment can no longer be changed".to_string()));
+    }
+    Ok(appointment)
+}
+
+/// Konfirmasi pengajuan psikolog menjadi janji temu (admin). Tanpa waktu di body,
+/// jadwal yang dipesan pengguna dipakai.
+pub fn confirm_appointment(
+    pool: &DbPools,
+    admin_id: i32,
+    request_id: i32,
+    data: ConfirmAppointmentRequest,
+    ip_address: Option<&str>,
+) -> Result<AppointmentResponse, AppError> {
+    let meeting_link = meeting_link(data.meeting_link.as_deref())?;
+
+    let mut conn = pool.conn_write()?;
+    run_in_transaction(&mut conn, |conn| {
+        let request = psychologist_query::find_request_for_update(conn, request_id)?
+            .ok_or_else(|| AppError::NotFound("Psychologist request not found".to_string()))?;
+        let psychologist_id = request
+            .psychologist_id
+            .ok_or_else(|| AppError::BadRequest("Request is not for a specific psychologist".to_string()))?;
+
+        let (starts_at, ends_at) = match (data.starts_at, data.ends_at) {
+            (Some(starts_at), Some(ends_at)) => (starts_at, ends_at),
+            (None, None) => {
+                let slot = match request.slot_id {
+                    Some(slot_id) => psychologist_query::find_slot(conn, slot_id)?,
+                    None => None,
+                }
+                .ok_or_else(|| {
+                    AppError::BadRequest("Appointment time is required when no slot was booked".to_string())
+                })?;
+                (slot.starts_at, slot.ends_at)
+            }
+            _ => {
+                return Err(AppError::BadRequest("Both starts_at and ends_at are required".to_string()));
+            }
+        };
+        if ends_at <= starts_at {
+            return Err(AppError::BadRequest("Appointment must end after it starts".to_string()));
+        }
+        if starts_at <= Utc::now().naive_utc() {
+            return Err(AppError::BadRequest("Appointment must start in the future".to_string()));
+        }
+
+        let appointment = appointment_query::create_appointment(
+            conn,
+            &NewAppointment {
+                user_id: request.user_id,
+                psychologist_id,
+                request_id: Some(request.id),
+                starts_at,
+                ends_at,
+                meeting_link,
+            },
+        )?;
+
+        audit_service::record(
+            conn,
+            NewAuditLog::new(AuditAction::AdminAction, Some(admin_id), Some(request.user_id), ip_address)
+                .with_details(format!("appointment {} confirmed for psychologist_request {}", appointment.id, request.id)),
+        )?;
+
+        let name = psychologist_query::find_psychologist_name(conn, psychologist_id)?;
+        Ok(AppointmentResponse::new(appointment, name))
+    })
+}
+
+pub fn get_appointments(
+    pool: &DbPools,
+    user_id: i32,
+) -> Result<Vec<AppointmentResponse>, AppError> {
+    let mut conn = pool.conn_read()?;
+
+    let appointments = appointment_query::find_appointments_by_user(&mut conn, user_id)?;
+    Ok(appointments
+        .into_iter()
+        .map(|(appointment, name)| AppointmentResponse::new(appointment, name))
+        .collect())
+}
+
+/// Pindahkan janji ke jadwal kosong lain milik psikolog yang sama. Jadwal lama dilepas
+/// dan pengingat dikirim ulang sesuai waktu yang baru.
+pub fn reschedule_appointment(
+    pool: &DbPools,
+    user_id: i32,
+    appointment_id: i32,
+    data: RescheduleAppointmentRequest,
+) -> Result<AppointmentResponse, AppError> {
+    let mut conn = pool.conn_write()?;
+    run_in_transaction(&mut conn, |conn| {
+        let now = Utc::now().naive_utc();
+        let appointment = require_changeable(conn, user_id, appointment_id, now)?;
+        let request_id = appointment
+            .request_id
+            .ok_or_else(|| AppError::Conflict("Appointment can no longer be changed".to_string()))?;
+
+        let slot = psychologist_query::find_slot_for_update(conn, data.slot_id)?
+            .filter(|slot| slot.psychologist_id == appointment.psychologist_id)
+            .ok_or_else(|| AppError::NotFound("Slot not found".to_string()))?;
+        if slot.starts_at <= now {
+            return Err(AppError::BadRequest("Slot has already started".to_string()));
+        }
+        if psychologist_query::is_slot_booked(conn, slot.id)? {
+            return Err(AppError::Conflict("Slot is already booked".to_string()));
+        }
+
+        psychologist_query::update_request_slot(conn, request_id, Some(slot.id))?;
+        let appointment =
+            appointment_query::reschedule_appointment(conn, appointment.id, slot.starts_at, slot.ends_at, now)?;
+
+        let name = psychologist_query::find_psychologist_name(conn, appointment.psychologist_id)?;
+        Ok(AppointmentResponse::new(appointment, name))
+    })
+}
+
+/// Batalkan janji; jadwalnya kembali bisa dipesan pengguna lain
+pub fn cancel_appointment(
+    pool: &DbPools,
+    user_id: i32,
+    appointment_id: i32,
+) -> Result<AppointmentResponse, AppError> {
+    let mut conn = pool.conn_write()?;
+    run_in_transaction(&mut conn, |conn| {
+        let now = Utc::now().naive_utc();
+        let appointment = require_changeable(conn, user_id, appointment_id, now)?;
+
+        if let Some(request_id) = appointment.request_id {
+            psychologist_query::update_request_slot(conn, request_id, None)?;
+        }
+        let appointment = appointment_query::cancel_appointment(conn, appointment.id, now)?;
+
+        let name = psychologist_query::find_psychologist_name(conn, appointment.psychologist_id)?;
+        Ok(AppointmentResponse::new(appointment, name))
+    })
+}
+
+/// Kirim pengingat 24 jam dan 1 jam sebelum janji, dengan waktu dalam zona waktu pengguna.
+/// Setiap pengingat ditandai lebih dulu sehingga tidak terkirim dua kali.
+pub fn send_appointment_reminders(pool: &DbPools, mailer: &dyn Mailer) -> Result<usize, AppError> {
+    let mut conn = pool.conn_write()?;
+    let now = Utc::now().naive_utc();
+
+    let mut sent = 0;
+    for reminder in [AppointmentReminder::HourBefore, AppointmentReminder::DayBefore] {
+        for (appointment, psychologist_name) in appointment_query::find_due_reminders(&mut conn, reminder, now)? {
+            if !appointment_query::claim_reminder(&mut conn, appointment.id, reminder, now)? {
+                continue;
+            }
+
+            let user = user_query::find_user_by_id(&mut conn, appointment.user_id)?;
+            let settings = UserSettings::parse(user.settings.as_deref());
+            let locale = settings.language.as_deref().and_then(Locale::parse).unwrap_or_default();
+            let tz = settings.timezone.as_deref().and_then(parse_timezone).unwrap_or(Tz::UTC);
+            let local_start = appointment.starts_at.and_utc().with_timezone(&tz);
+            let time = format!("{} ({})", local_start.format("%Y-%m-%d %H:%M"), tz.name());
+
+            let body = t_in_args(
+                locale,
+                &format!("appointment.reminder.{}", reminder.as_str()),
+                &[&psychologist_name, &time],
+            );
+            let email_body = match appointment.meeting_link {
+                Some(ref link) => format!("{}\n\n{}", body, link),
+                None => body.clone(),
+            };
+            let result = notification_service::dispatch(
+                &mut conn,
+                mailer,
+                &user,
+                &Notification {
+                    category: NotificationCategory::Appointment,
+                    channels: &NotificationChannel::ALL,
+                    title: t_in(locale, "appointment.reminder.title"),
+                    body,
+                    email_body: Some(email_body),
+                },
+            )?;
+            if result.delivered() {
+                sent += 1;
+            }
+        }
+    }
+
+    Ok(sent)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn meeting_link_must_be_https() {
+        assert_eq!(meeting_link(Some(" https://meet.example.com/x ")).unwrap(), Some("https://meet.example.com/x"));
+        assert_eq!(meeting_link(Some("  ")).unwrap(), None);
+        assert!(meeting_link(Some("http://meet.example.com/x")).is_err());
+        assert!(meeting_link(Some("https://")).is_err());
+    }
+}
@@ -0,0 +1,41 @@
+use crate::models::audit::{AuditLogResponse, NewAuditLog};
+use crate::db::audit_log_query;
+use crate::errors::app_error::AppError;
+use crate::db::pool::DbPools;
+use diesel::pg::PgConnection;
+
+const DEFAULT_AUDIT_LIMIT: i64 = 50;
+const MAX_AUDIT_LIMIT: i64 = 500;
+
+/// Catat aksi sensitif. Memakai koneksi pemanggil agar bisa ikut transaksi yang sama.
+pub fn record(conn: &mut PgConnection, entry: NewAuditLog) -> Result<(), AppError> {
+    audit_log_query::insert_audit_log(conn, &entry)
+}
+
+/// Audit log milik pengguna sendiri
+pub fn get_user_audit_log(
+    pool: &DbPools,
+    user_id: i32,
+) -> Result<Vec<AuditLogResponse>, AppError> {
+    let mut conn = pool.conn_read()?;
+
+    let logs = audit_log_query::find_audit_logs(&mut conn, Some(user_id), DEFAULT_AUDIT_LIMIT)?;
+    Ok(logs.into_iter().map(AuditLogResponse::from).collect())
+}
+
+/// Audit log untuk admin, opsional difilter per pengguna
+pub fn get_audit_logs(
+    pool: &DbPools,
+    user_id: Option<i32>,
+    limit: Option<i64>,
+) -> Result<Vec<AuditLogResponse>, AppError> {
+    let limit = limit.unwrap_or(DEFAULT_AUDIT_LIMIT);
+    if limit <= 0 || limit > MAX_AUDIT_LIMIT {
+        return Err(AppError::BadRequest(format!("Limit must be between 1 and {}", MAX_AUDIT_LIMIT)));
+    }
+
+    let mut conn = pool.conn_read()?;
+
+    let logs = audit_log_query::find_audit_logs(&mut conn, user_id, limit)?;
+    Ok(logs.into_iter().map(AuditLogResponse::from).collect())
+}
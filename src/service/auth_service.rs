@@ -1,13 +1,49 @@
-use crate::models::{user::User, auth::LoginResponse};
-use crate::db::{user_query, token_blacklist_query};
+use crate::models::{
+    user::{NewGuestUser, User, UserResponse},
+    auth::{GuestLoginResponse, ImpersonationResponse, LoginResponse, ScopedTokenResponse, UpgradeAccountRequest},
+};
+use crate::models::security::NewLoginAttempt;
+use crate::models::audit::{AuditAction, NewAuditLog};
+use crate::service::audit_service;
+use crate::db::{login_attempt_query, user_query, token_blacklist_query};
+use crate::config::app_config::app_config;
 use crate::errors::app_error::AppError;
-use crate::utils::jwt::{generate_token, validate_token};
-use diesel::r2d2;
-use diesel::pg::PgConnection;
+use crate::utils::jwt::{generate_impersonation_token, generate_scoped_token, generate_token, validate_token, TokenScope};
+use crate::utils::email::parse_email;
+use crate::utils::password_policy::PasswordPolicy;
+use crate::utils::username::{normalize_username, validate_username};
+use crate::db::pool::DbPools;
+use crate::db::transaction::run_in_transaction;
 use bcrypt::{hash, verify, DEFAULT_COST};
+use chrono::{Duration, Utc};
+use rand::Rng;
+
+/// JWT berlaku 24 jam, token blacklist disimpan sedikit lebih lama agar aman
+const BLACKLIST_RETENTION_DAYS: i64 = 7;
+
+/// Token terbatas tidak boleh berlaku lebih lama dari retensi blacklist,
+/// agar token yang sudah di-logout tidak bisa dipakai lagi setelah cleanup
+const SCOPED_TOKEN_MAX_HOURS: i64 = BLACKLIST_RETENTION_DAYS * 24;
+const SCOPED_TOKEN_DEFAULT_HOURS: i64 = 24;
+
+/// Domain email pengganti untuk akun tamu; `.invalid` tidak pernah bisa menerima email
+const GUEST_EMAIL_DOMAIN: &str = "guest.mindmate.invalid";
+const GUEST_DEVICE_ID_MIN_LENGTH: usize = 8;
+const GUEST_DEVICE_ID_MAX_LENGTH: usize = 255;
+
+/// Token impersonasi sengaja berumur pendek, apa pun nilai konfigurasinya
+const IMPERSONATION_MAX_MINUTES: i64 = 60;
+const IMPERSONATION_REASON_MAX_LENGTH: usize = 500;
+
+fn random_alphanumeric(length: usize) -> String {
+    let mut rng = rand::thread_rng();
+    (0..length)
+        .map(|_| rng.sample(rand::distributions::Alphanumeric) as char)
+        .collect()
+}
 
 pub fn register_user(
-    pool: &r2d2::Pool<r2d2::ConnectionManager<PgConnection>>,
+    pool: &DbPools,
     username: &str,
     email: &str,
     password: &str,
@@ -23,51 +59,107 @@ pub fn register_user(
         return Err(AppError::BadRequest("Gender must be provided".to_string()));
     }
 
-    let mut conn = pool
-        .get()
-        .map_err(|_| AppError::InternalServerError("Failed to get DB connection".to_string()))?;
+    let username = normalize_username(username);
+    validate_username(&username)?;
+    let username = username.as_str();
+    let email = parse_email(email)?;
+    let email = email.as_str();
 
-    // Check if email already exists
-    if user_query::find_user_by_email(&mut conn, email).is_ok() {
-        return Err(AppError::BadRequest("Email already exists".to_string()));
-    }
+    PasswordPolicy::from_config(app_config()).validate(password, &[username, email])?;
 
-    // Check if username already exists
-    if user_query::find_user_by_username(&mut conn, username).is_ok() {
-        return Err(AppError::BadRequest("Username already exists".to_string()));
-    }
-
-    // Hash password
+    // Hash password sebelum transaksi agar transaksi tetap singkat
     let hashed_password = hash(password, DEFAULT_COST)
         .map_err(|_| AppError::InternalServerError("Failed to hash password".to_string()))?;
 
-    // Gunakan create_user yang sudah diupdate dengan semua parameter
-    let user = user_query::create_user(&mut conn, username, email, &hashed_password, age, gender, settings)?;
-    
-    Ok(user)
+    let mut conn = pool.conn_write()?;
+
+    run_in_transaction(&mut conn, |conn| {
+        // Check if email already exists
+        if user_query::find_user_by_email(conn, email).is_ok() {
+            return Err(AppError::Conflict("Email already exists".to_string()));
+        }
+
+        // Check if username already exists
+        if user_query::username_exists(conn, username)? {
+            return Err(AppError::Conflict("Username already exists".to_string()));
+        }
+
+        // Gunakan create_user yang sudah diupdate dengan semua parameter
+        user_query::create_user(conn, username, email, &hashed_password, age, gender, settings)
+    })
 }
 
+/// Login dengan username atau email (`identifier`)
 pub fn login_user(
-    pool: &r2d2::Pool<r2d2::ConnectionManager<PgConnection>>,
-    email: &str,
+    pool: &DbPools,
+    identifier: &str,
     password: &str,
+    ip_address: Option<&str>,
+    user_agent: Option<&str>,
 ) -> Result<LoginResponse, AppError> {
-    let mut conn = pool
-        .get()
-        .map_err(|_| AppError::InternalServerError("Failed to get DB connection".to_string()))?;
+    let mut conn = pool.conn_write()?;
+
+    let config = app_config();
+    let window_start = Utc::now().naive_utc() - Duration::minutes(config.login_lockout_minutes);
 
-    // Find user by email
-    let user = user_query::find_user_by_email(&mut conn, email)
-        .map_err(|_| AppError::Unauthorized("Invalid email or password".to_string()))?;
+    // Batasi IP yang mencoba banyak akun sekaligus
+    if let Some(ip) = ip_address {
+        if login_attempt_query::count_failures_for_ip_since(&mut conn, ip, window_start)? >= config.login_max_failures_per_ip {
+            return Err(AppError::TooManyRequests("Too many failed login attempts, try again later".to_string()));
+        }
+    }
+
+    let attempt = |user_id: Option<i32>, success: bool| NewLoginAttempt {
+        user_id,
+        email: identifier.to_lowercase(),
+        ip_address: ip_address.map(str::to_string),
+        user_agent: user_agent.map(str::to_string),
+        success,
+        created_at: Utc::now().naive_utc(),
+    };
+
+    // Identifier yang tidak terdaftar juga dikunci setelah jumlah kegagalan yang sama,
+    // agar respons lockout tidak membocorkan akun mana yang ada
+    let locked = || {
+        AppError::TooManyRequests(
+            "Account temporarily locked due to too many failed login attempts, try again later".to_string(),
+        )
+    };
+
+    let user = match user_query::find_user_by_login_identifier(&mut conn, identifier) {
+        Ok(user) => user,
+        Err(_) => {
+            let failures = login_attempt_query::count_unknown_identifier_failures_since(
+                &mut conn,
+                &identifier.to_lowercase(),
+                window_start,
+            )?;
+            if failures >= config.login_max_failures {
+                return Err(locked());
+            }
+            login_attempt_query::insert_login_attempt(&mut conn, &attempt(None, false))?;
+            return Err(AppError::Unauthorized("Invalid username/email or password".to_string()));
+        }
+    };
+
+    // Akun dikunci jika gagal berkali-kali sejak login berhasil terakhir dalam jendela waktu
+    let since = login_attempt_query::last_successful_login(&mut conn, user.id)?
+        .map_or(window_start, |last_success| last_success.max(window_start));
+    if login_attempt_query::count_failures_for_user_since(&mut conn, user.id, since)? >= config.login_max_failures {
+        return Err(locked());
+    }
 
     // Verify password
     let is_valid = verify(password, &user.password)
         .map_err(|_| AppError::InternalServerError("Failed to verify password".to_string()))?;
 
     if !is_valid {
-        return Err(AppError::Unauthorized("Invalid email or password".to_string()));
+        login_attempt_query::insert_login_attempt(&mut conn, &attempt(Some(user.id), false))?;
+        return Err(AppError::Unauthorized("Invalid username/email or password".to_string()));
     }
 
+    login_attempt_query::insert_login_attempt(&mut conn, &attempt(Some(user.id), true))?;
+
     // Generate JWT token with user ID
     let token = generate_token(&user.id.to_string())
         .map_err(|_| AppError::InternalServerError("Failed to generate token".to_string()))?;
@@ -85,17 +177,113 @@ pub fn login_user(
             settings: user.settings.clone(),
             created_at: user.created_at,
             updated_at: user.updated_at,
+            is_guest: user.is_guest,
         },
     })
 }
 
+/// Masuk sebagai tamu dari sebuah perangkat. Perangkat baru mendapat akun tamu
+/// dengan `device_secret` acak (disimpan sebagai hash password) yang dipakai untuk masuk lagi.
+pub fn login_guest(
+    pool: &DbPools,
+    device_id: &str,
+    device_secret: Option<&str>,
+) -> Result<GuestLoginResponse, AppError> {
+    let device_id = device_id.trim();
+    if !(GUEST_DEVICE_ID_MIN_LENGTH..=GUEST_DEVICE_ID_MAX_LENGTH).contains(&device_id.len()) {
+        return Err(AppError::BadRequest(format!(
+            "Device id must be between {} and {} characters",
+            GUEST_DEVICE_ID_MIN_LENGTH, GUEST_DEVICE_ID_MAX_LENGTH
+        )));
+    }
+
+    let mut conn = pool.conn_write()?;
+
+    let (user, new_secret) = match user_query::find_guest_by_device(&mut conn, device_id)? {
+        Some(user) => {
+            let is_valid = match device_secret {
+                Some(secret) => verify(secret, &user.password)
+                    .map_err(|_| AppError::InternalServerError("Failed to verify password".to_string()))?,
+                None => false,
+            };
+            if !is_valid {
+                return Err(AppError::Unauthorized("Invalid guest credentials".to_string()));
+            }
+            (user, None)
+        }
+        None => {
+            let secret = random_alphanumeric(48);
+            let hashed_secret = hash(&secret, DEFAULT_COST)
+                .map_err(|_| AppError::InternalServerError("Failed to hash password".to_string()))?;
+            let username = format!("guest-{}", random_alphanumeric(10).to_lowercase());
+            let email = format!("{}@{}", username, GUEST_EMAIL_DOMAIN);
+
+            let user = user_query::create_guest_user(
+                &mut conn,
+                &NewGuestUser {
+                    username: &username,
+                    email: &email,
+                    password: &hashed_secret,
+                    is_guest: true,
+                    guest_device_id: device_id,
+                },
+            )?;
+            (user, Some(secret))
+        }
+    };
+
+    let token = generate_token(&user.id.to_string())
+        .map_err(|_| AppError::InternalServerError("Failed to generate token".to_string()))?;
+
+    Ok(GuestLoginResponse {
+        token,
+        user: user.into(),
+        device_secret: new_secret,
+    })
+}
+
+/// Upgrade akun tamu menjadi akun penuh dengan email dan password.
+/// ID pengguna tidak berubah sehingga semua mood dan jurnal tetap ada.
+pub fn upgrade_guest(
+    pool: &DbPools,
+    user_id: i32,
+    data: UpgradeAccountRequest,
+) -> Result<UserResponse, AppError> {
+    let email = parse_email(&data.email)?;
+    let username = data.username.as_deref().map(normalize_username);
+    if let Some(ref username) = username {
+        validate_username(username)?;
+    }
+
+    let mut conn = pool.conn_write()?;
+    let user = user_query::find_user_by_id(&mut conn, user_id)?;
+    if !user.is_guest {
+        return Err(AppError::BadRequest("Account is not a guest account".to_string()));
+    }
+    let username = username.unwrap_or_else(|| user.username.clone());
+
+    PasswordPolicy::from_config(app_config()).validate(&data.password, &[&username, &email])?;
+    let hashed_password = hash(&data.password, DEFAULT_COST)
+        .map_err(|_| AppError::InternalServerError("Failed to hash password".to_string()))?;
+
+    run_in_transaction(&mut conn, |conn| {
+        if user_query::find_user_by_email(conn, &email).is_ok() {
+            return Err(AppError::Conflict("Email already exists".to_string()));
+        }
+        if username != user.username && user_query::username_exists(conn, &username)? {
+            return Err(AppError::Conflict("Username already exists".to_string()));
+        }
+
+        let user = user_query::upgrade_guest_user(conn, user_id, &username, &email, &hashed_password)?;
+        Ok(user.into())
+    })
+}
+
 pub fn logout_user(
-    pool: &r2d2::Pool<r2d2::ConnectionManager<PgConnection>>,
+    pool: &DbPools,
     token: &str,
 ) -> Result<(), AppError> {
-    let mut conn = pool
-        .get()
-        .map_err(|_| AppError::InternalServerError("Failed to get DB connection".to_string()))?;
+    let mut conn = pool.conn_write()?;
 
     // Validate token first
     validate_token(token)
@@ -113,4 +301,127 @@ pub fn logout_user(
     token_blacklist_query::insert_blacklisted_token(&mut conn, token)?;
 
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Buat token dengan scope terbatas untuk share link atau integrasi export.
+/// Hanya bisa diminta dengan token akses penuh (dicek oleh extractor).
+pub fn issue_scoped_token(
+    pool: &DbPools,
+    user_id: i32,
+    scopes: &[String],
+    expires_in_hours: Option<i64>,
+    ip_address: Option<&str>,
+) -> Result<ScopedTokenResponse, AppError> {
+    if scopes.is_empty() {
+        return Err(AppError::BadRequest("At least one scope is required".to_string()));
+    }
+    let mut parsed: Vec<TokenScope> = Vec::new();
+    for scope in scopes {
+        let scope = TokenScope::parse(scope.trim())
+            .ok_or_else(|| AppError::BadRequest(format!("Unknown scope: {}", scope)))?;
+        if !parsed.contains(&scope) {
+            parsed.push(scope);
+        }
+    }
+
+    let hours = expires_in_hours.unwrap_or(SCOPED_TOKEN_DEFAULT_HOURS);
+    if !(1..=SCOPED_TOKEN_MAX_HOURS).contains(&hours) {
+        return Err(AppError::BadRequest(format!(
+            "Token expiry must be between 1 and {} hours",
+            SCOPED_TOKEN_MAX_HOURS
+        )));
+    }
+
+    let ttl = Duration::hours(hours);
+    let token = generate_scoped_token(&user_id.to_string(), &parsed, ttl)
+        .map_err(|_| AppError::InternalServerError("Failed to generate token".to_string()))?;
+    let scope_names: Vec<String> = parsed.iter().map(|scope| scope.as_str().to_string()).collect();
+
+    let mut conn = pool.conn_write()?;
+    audit_service::record(
+        &mut conn,
+        NewAuditLog::new(AuditAction::ScopedTokenIssued, Some(user_id), Some(user_id), ip_address)
+            .with_details(format!("scopes={} hours={}", scope_names.join(","), hours)),
+    )?;
+
+    Ok(ScopedTokenResponse {
+        token,
+        scopes: scope_names,
+        expires_at: (Utc::now() + ttl).naive_utc(),
+    })
+}
+
+/// Token impersonasi untuk admin support. Admin lain dan diri sendiri tidak bisa
+/// diimpersonasi; penerbitan dan setiap request dengan token ini masuk audit log.
+pub fn impersonate_user(
+    pool: &DbPools,
+    admin_id: i32,
+    user_id: i32,
+    reason: &str,
+    ip_address: Option<&str>,
+) -> Result<ImpersonationResponse, AppError> {
+    let reason = reason.trim();
+    if reason.is_empty() || reason.chars().count() > IMPERSONATION_REASON_MAX_LENGTH {
+        return Err(AppError::BadRequest(format!(
+            "Reason is required (max {} characters)",
+            IMPERSONATION_REASON_MAX_LENGTH
+        )));
+    }
+    if admin_id == user_id {
+        return Err(AppError::BadRequest("Cannot impersonate yourself".to_string()));
+    }
+
+    let mut conn = pool.conn_write()?;
+
+    let user = user_query::find_user_by_id(&mut conn, user_id)?;
+    if app_config().is_admin_email(&user.email) {
+        return Err(AppError::Forbidden("Cannot impersonate another admin".to_string()));
+    }
+
+    let ttl = Duration::minutes(app_config().impersonation_token_minutes.clamp(1, IMPERSONATION_MAX_MINUTES));
+    let token = generate_impersonation_token(&user_id.to_string(), &admin_id.to_string(), ttl)
+        .map_err(|_| AppError::InternalServerError("Failed to generate token".to_string()))?;
+
+    audit_service::record(
+        &mut conn,
+        NewAuditLog::new(AuditAction::Impersonation, Some(admin_id), Some(user_id), ip_address)
+            .with_details(format!("minutes={} reason={}", ttl.num_minutes(), reason)),
+    )?;
+
+    Ok(ImpersonationResponse {
+        token,
+        user_id,
+        impersonator_id: admin_id,
+        expires_at: (Utc::now() + ttl).naive_utc(),
+    })
+}
+
+/// Hapus token blacklist yang lebih lama dari masa berlaku JWT
+pub fn cleanup_expired_tokens(
+    pool: &DbPools,
+) -> Result<usize, AppError> {
+    let mut conn = pool.conn_write()?;
+
+    let cutoff_date = Utc::now().naive_utc() - Duration::days(BLACKLIST_RETENTION_DAYS);
+
+    token_blacklist_query::cleanup_expired_tokens(&mut conn, cutoff_date)
+        .map_err(AppError::from)
+}
+
+/// Cleanup token blacklist yang dipicu admin, dicatat di audit log
+pub fn cleanup_expired_tokens_by_admin(
+    pool: &DbPools,
+    admin_user_id: i32,
+    ip_address: Option<&str>,
+) -> Result<usize, AppError> {
+    let deleted = cleanup_expired_tokens(pool)?;
+
+    let mut conn = pool.conn_write()?;
+    audit_service::record(
+        &mut conn,
+        NewAuditLog::new(AuditAction::AdminAction, Some(admin_user_id), None, ip_address)
+            .with_details(format!("cleanup_tokens deleted={}", deleted)),
+    )?;
+
+    Ok(deleted)
+}
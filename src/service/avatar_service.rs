@@ -0,0 +1,108 @@
+use crate::models::user::{AvatarResponse, User};
+use crate::db::user_query;
+use crate::errors::app_error::AppError;
+use crate::config::app_config::app_config;
+use crate::db::pool::DbPools;
+use axum::body::Bytes;
+use crate::utils::image_processing::resize_avatar_blocking;
+use crate::utils::storage::Storage;
+use crate::service::onboarding_service;
+use rand::Rng;
+
+/// Path avatar yang tersimpan di kolom `users.avatar`; versi berubah setiap upload
+const AVATAR_URL_PATH: &str = "/api/user/avatar?v=";
+
+fn avatar_key(user_id: i32, version: &str) -> String {
+    format!("avatars/{}/{}.jpg", user_id, version)
+}
+
+fn generate_version() -> String {
+    let mut rng = rand::thread_rng();
+    (0..16)
+        .map(|_| rng.sample(rand::distributions::Alphanumeric) as char)
+        .collect()
+}
+
+/// Versi avatar dari nilai kolom `users.avatar`, jika avatar tersebut hasil upload
+/// (bukan URL eksternal atau base64 lama)
+fn stored_version(avatar: &str) -> Option<&str> {
+    avatar
+        .split_once(AVATAR_URL_PATH)
+        .map(|(_, version)| version)
+        .filter(|version| !version.is_empty() && version.chars().all(|c| c.is_ascii_alphanumeric()))
+}
+
+/// Resize avatar yang di-upload, simpan ke storage, dan simpan URL-nya di profil
+pub async fn upload_avatar(
+    pool: &DbPools,
+    storage: &dyn Storage,
+    user_id: i32,
+    data: Bytes,
+) -> Result<AvatarResponse, AppError> {
+    let config = app_config();
+    if data.len() > config.avatar_max_upload_bytes {
+        return Err(AppError::BadRequest(format!(
+            "Image too large. Maximum size is {} bytes",
+            config.avatar_max_upload_bytes
+        )));
+    }
+
+    let resized = resize_avatar_blocking(data, config.avatar_size).await?;
+
+    let mut conn = pool.conn_write()?;
+
+    let user = user_query::find_user_by_id(&mut conn, user_id)
+        .map_err(|_| AppError::NotFound("User not found".to_string()))?;
+
+    let version = generate_version();
+    storage.put(&avatar_key(user_id, &version), &resized)?;
+
+    let avatar_url = format!("{}{}{}", config.public_api_url, AVATAR_URL_PATH, version);
+    user_query::update_user_avatar(&mut conn, user_id, Some(&avatar_url))?;
+
+    let updated_user = user_query::find_user_by_id(&mut conn, user_id)?;
+    onboarding_service::complete_profile_step_if_filled(&mut conn, &updated_user)?;
+
+    // File lama tidak lagi direferensikan
+    if let Some(old_version) = user.avatar.as_deref().and_then(stored_version) {
+        storage.delete(&avatar_key(user_id, old_version))?;
+    }
+
+    Ok(AvatarResponse { avatar: avatar_url })
+}
+
+/// Ambil file avatar hasil upload beserta versinya (dipakai sebagai ETag)
+pub fn get_avatar(
+    pool: &DbPools,
+    storage: &dyn Storage,
+    user_id: i32,
+) -> Result<(Vec<u8>, String), AppError> {
+    let mut conn = pool.conn_read()?;
+
+    let user = user_query::find_user_by_id(&mut conn, user_id)
+        .map_err(|_| AppError::NotFound("User not found".to_string()))?;
+
+    let version = user
+        .avatar
+        .as_deref()
+        .and_then(stored_version)
+        .ok_or_else(|| AppError::NotFound("No uploaded avatar".to_string()))?
+        .to_string();
+
+    let data = storage
+        .get(&avatar_key(user_id, &version))?
+        .ok_or_else(|| AppError::NotFound("No uploaded avatar".to_string()))?;
+
+    Ok((data, version))
+}
+
+/// Hapus file avatar hasil upload milik `user`, misalnya saat akun dihapus
+pub fn delete_stored_avatar(
+    storage: &dyn Storage,
+    user: &User,
+) -> Result<(), AppError> {
+    match user.avatar.as_deref().and_then(stored_version) {
+        Some(version) => storage.delete(&avatar_key(user.id, version)),
+        None => Ok(()),
+    }
+}
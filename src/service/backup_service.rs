@@ -0,0 +1,357 @@
+use std::io::{Read, Write};
+use chrono::Utc;
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use serde::Deserialize;
+use crate::db::backup_query;
+use crate::db::pool::DbPools;
+use crate::db::transaction::run_in_transaction;
+use crate::errors::app_error::AppError;
+use crate::models::audit::{AuditAction, NewAuditLog};
+use crate::models::backup::{BackupResponse, CreateBackupRequest, RestoreBackupRequest, RestoreBackupResponse};
+use crate::service::audit_service;
+use crate::utils::stats_cache::StatsCache;
+use crate::utils::storage::Storage;
+
+/// Versi format file backup; naikkan jika struktur file berubah
+const BACKUP_FORMAT_VERSION: u32 = 1;
+const BACKUP_DIR: &str = "backups";
+const BACKUP_PREFIX: &str = "mindmate-backup-";
+const BACKUP_SUFFIX: &str = ".json.gz";
+
+/// Tabel yang bisa di-backup beserta tabel induknya (foreign key), diurutkan
+/// agar induk selalu dipulihkan lebih dulu
+const BACKUP_TABLES: &[(&str, &[&str])] = &[
+    ("users", &[]),
+    ("moods", &["users"]),
+    ("deleted_moods", &["users"]),
+    ("journals", &["users"]),
+    ("journal_drafts", &["users"]),
+    ("journal_retention_states", &["users"]),
+    ("user_onboarding", &["users"]),
+    ("calendar_feed_tokens", &["users"]),
+    ("email_change_requests", &["users"]),
+    ("password_reset_tokens", &["users"]),
+    ("auth_exchange_codes", &["users"]),
+    // Token di sini terenkripsi dengan OAUTH_TOKEN_ENCRYPTION_KEY; restore butuh kunci yang sama
+    ("oauth_accounts", &["users"]),
+    ("help_requests", &["users"]),
+    ("help_request_comments", &["help_requests", "users"]),
+    ("psychologists", &["users"]),
+    ("psychologist_slots", &["psychologists"]),
+    ("psychologist_requests", &["users", "psychologists", "psychologist_slots"]),
+    ("appointments", &["users", "psychologists", "psychologist_requests"]),
+    ("messages", &["users", "psychologists"]),
+    ("moderation_verdicts", &["users"]),
+    ("community_groups", &[]),
+    ("community_memberships", &["community_groups", "users"]),
+    ("community_posts", &["community_groups", "users"]),
+    ("community_reactions", &["community_posts", "users"]),
+    ("user_webhooks", &["users"]),
+    ("webhook_deliveries", &["user_webhooks"]),
+    ("health_samples", &["users"]),
+    ("usage_quotas", &["users"]),
+    ("api_usage_daily", &["users"]),
+    ("insight_notifications", &["users"]),
+    ("jobs", &["users"]),
+    ("login_attempts", &["users"]),
+    ("devices", &["users"]),
+    ("push_outbox", &["devices"]),
+    ("organizations", &["users"]),
+    ("organization_members", &["organizations", "users"]),
+    ("organization_invitations", &["organizations", "users"]),
+    ("audit_logs", &[]),
+    ("token_blacklist", &[]),
+    ("used_checkin_tokens", &["users"]),
+];
+
+/// Tabel yang sengaja tidak di-backup. `scheduled_job_runs` hanya mencatat kapan job
+/// terjadwal terakhir diklaim oleh instance yang berjalan; memulihkannya bisa membuat
+/// job terlewat atau berjalan ulang.
+const EXCLUDED_TABLES: &[&str] = &["scheduled_job_runs"];
+
+/// Tabel tanpa kolom `id` berbasis sequence
+const TABLES_WITHOUT_ID_SEQUENCE: &[&str] = &[
+    "deleted_moods",
+    "journal_drafts",
+    "journal_retention_states",
+    "user_onboarding",
+    "usage_quotas",
+    "api_usage_daily",
+    "community_memberships",
+    "community_reactions",
+    "organization_members",
+    "used_checkin_tokens",
+];
+
+#[derive(Deserialize)]
+struct BackupFile {
+    version: u32,
+    tables: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Dump tabel yang dipilih ke file JSON ter-gzip di storage. Semua tabel dibaca dalam
+/// satu snapshot agar relasi antar tabel konsisten.
+pub fn create_backup(
+    pool: &DbPools,
+    storage: &dyn Storage,
+    data: CreateBackupRequest,
+) -> Result<BackupResponse, AppError> {
+    let tables = select_tables(data.tables.as_deref())?;
+    let created_at = Utc::now().naive_utc();
+    let name = format!("{}{}{}", BACKUP_PREFIX, created_at.format("%Y%m%dT%H%M%S%3fZ"), BACKUP_SUFFIX);
+
+    let mut conn = pool.conn_write()?;
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    let rows = conn
+        .build_transaction()
+        .repeatable_read()
+        .read_only()
+        .run(|conn| {
+            let write_err = |e: std::io::Error| AppError::InternalServerError(format!("Failed to write backup: {}", e));
+            write!(
+                encoder,
+                "{{\"version\":{},\"created_at\":\"{}\",\"tables\":{{",
+                BACKUP_FORMAT_VERSION,
+                created_at.format("%Y-%m-%dT%H:%M:%SZ")
+            )
+            .map_err(write_err)?;
+
+            let mut total = 0;
+            for (index, table) in tables.iter().enumerate() {
+                let (count, rows) = backup_query::dump_table(conn, table)?;
+                let separator = if index == 0 { "" } else { "," };
+                write!(encoder, "{}\"{}\":{}", separator, table, rows).map_err(write_err)?;
+                total += count;
+            }
+
+            encoder.write_all(b"}}").map_err(write_err)?;
+            Ok::<_, AppError>(total)
+        })?;
+
+    let archive = encoder
+        .finish()
+        .map_err(|e| AppError::InternalServerError(format!("Failed to write backup: {}", e)))?;
+    storage.put(&backup_key(&name)?, &archive)?;
+
+    Ok(BackupResponse {
+        name,
+        tables: tables.iter().map(|table| table.to_string()).collect(),
+        rows,
+        size_bytes: archive.len(),
+        created_at,
+    })
+}
+
+/// Backup manual oleh admin, dicatat di audit log
+pub fn create_backup_by_admin(
+    pool: &DbPools,
+    storage: &dyn Storage,
+    admin_user_id: i32,
+    data: CreateBackupRequest,
+    ip_address: Option<&str>,
+) -> Result<BackupResponse, AppError> {
+    let backup = create_backup(pool, storage, data)?;
+
+    let mut conn = pool.conn_write()?;
+    audit_service::record(
+        &mut conn,
+        NewAuditLog::new(AuditAction::AdminAction, Some(admin_user_id), None, ip_address)
+            .with_details(format!("backup name={} rows={}", backup.name, backup.rows)),
+    )?;
+
+    Ok(backup)
+}
+
+/// Ganti isi tabel yang ada di file backup dengan isi backup tersebut. Sebelum itu
+/// data saat ini di-backup dulu, dan semuanya berjalan dalam satu transaksi.
+pub fn restore_backup(
+    pool: &DbPools,
+    storage: &dyn Storage,
+    cache: &StatsCache,
+    admin_user_id: i32,
+    data: RestoreBackupRequest,
+    ip_address: Option<&str>,
+) -> Result<RestoreBackupResponse, AppError> {
+    if data.confirm != data.name {
+        return Err(AppError::BadRequest(
+            "Confirmation must match the backup name".to_string(),
+        ));
+    }
+
+    let archive = storage
+        .get(&backup_key(&data.name)?)?
+        .ok_or_else(|| AppError::NotFound("Backup not found".to_string()))?;
+    let mut json = Vec::new();
+    GzDecoder::new(archive.as_slice())
+        .read_to_end(&mut json)
+        .map_err(|e| AppError::BadRequest(format!("Invalid backup file: {}", e)))?;
+    let backup: BackupFile = serde_json::from_slice(&json)
+        .map_err(|e| AppError::BadRequest(format!("Invalid backup file: {}", e)))?;
+    if backup.version != BACKUP_FORMAT_VERSION {
+        return Err(AppError::BadRequest(format!(
+            "Unsupported backup version: {}",
+            backup.version
+        )));
+    }
+
+    let names: Vec<String> = backup.tables.keys().cloned().collect();
+    let tables = select_tables(Some(&names))?;
+
+    let safety_backup = create_backup(
+        pool,
+        storage,
+        CreateBackupRequest {
+            tables: Some(names),
+        },
+    )?;
+
+    let mut conn = pool.conn_write()?;
+    let rows = run_in_transaction(&mut conn, |conn| {
+        backup_query::truncate_tables(conn, &tables)?;
+
+        let mut total = 0;
+        for table in &tables {
+            let rows = backup.tables[*table].to_string();
+            total += backup_query::restore_table(conn, table, &rows)?;
+            if !TABLES_WITHOUT_ID_SEQUENCE.contains(table) {
+                backup_query::reset_id_sequence(conn, table)?;
+            }
+        }
+        Ok(total)
+    })?;
+    cache.invalidate_all();
+
+    audit_service::record(
+        &mut conn,
+        NewAuditLog::new(AuditAction::AdminAction, Some(admin_user_id), None, ip_address).with_details(
+            format!("restore name={} rows={} safety_backup={}", data.name, rows, safety_backup.name),
+        ),
+    )?;
+
+    Ok(RestoreBackupResponse {
+        name: data.name,
+        tables: tables.iter().map(|table| table.to_string()).collect(),
+        rows,
+        safety_backup: safety_backup.name,
+    })
+}
+
+/// Tabel terpilih dalam urutan BACKUP_TABLES. Tabel yang mereferensikan tabel terpilih
+/// wajib ikut dipilih, karena restore mengosongkan tabel induknya.
+fn select_tables(requested: Option<&[String]>) -> Result<Vec<&'static str>, AppError> {
+    let Some(requested) = requested.filter(|tables| !tables.is_empty()) else {
+        return Ok(BACKUP_TABLES.iter().map(|(table, _)| *table).collect());
+    };
+
+    if let Some(unknown) = requested
+        .iter()
+        .find(|name| !BACKUP_TABLES.iter().any(|(table, _)| table == name))
+    {
+        if EXCLUDED_TABLES.contains(&unknown.as_str()) {
+            return Err(AppError::BadRequest(format!("Table {} is not included in backups", unknown)));
+        }
+        return Err(AppError::BadRequest(format!("Unknown backup table: {}", unknown)));
+    }
+
+    let selected = |table: &str| requested.iter().any(|name| name == table);
+    for (table, parents) in BACKUP_TABLES {
+        for parent in parents.iter() {
+            if selected(parent) && !selected(table) {
+                return Err(AppError::BadRequest(format!(
+                    "Table {} references {} and must be included",
+                    table, parent
+                )));
+            }
+        }
+    }
+
+    Ok(BACKUP_TABLES
+        .iter()
+        .map(|(table, _)| *table)
+        .filter(|table| selected(table))
+        .collect())
+}
+
+/// Key storage untuk nama file backup; nama di luar pola backup ditolak
+fn backup_key(name: &str) -> Result<String, AppError> {
+    let valid = name
+        .strip_prefix(BACKUP_PREFIX)
+        .and_then(|rest| rest.strip_suffix(BACKUP_SUFFIX))
+        .is_some_and(|stamp| !stamp.is_empty() && stamp.chars().all(|c| c.is_ascii_alphanumeric()));
+    if !valid {
+        return Err(AppError::BadRequest("Invalid backup name".to_string()));
+    }
+    Ok(format!("{}/{}", BACKUP_DIR, name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Nama tabel dan primary key dari setiap `diesel::table!` di schema.rs
+    fn schema_tables() -> Vec<(&'static str, &'static str)> {
+        let mut lines = include_str!("../schema.rs").lines();
+        let mut tables = Vec::new();
+        while let Some(line) = lines.next() {
+            if line.starts_with("diesel::table!") {
+                let (name, key) = lines.next().unwrap().trim().split_once(" (").unwrap();
+                tables.push((name, key.trim_end_matches(") {")));
+            }
+        }
+        tables
+    }
+
+    #[test]
+    fn every_schema_table_is_backed_up_or_excluded() {
+        let tables = schema_tables();
+        assert!(!tables.is_empty());
+        for (table, _) in &tables {
+            let backed_up = BACKUP_TABLES.iter().any(|(name, _)| name == table);
+            assert!(
+                backed_up != EXCLUDED_TABLES.contains(table),
+                "{} must be in exactly one of BACKUP_TABLES or EXCLUDED_TABLES",
+                table
+            );
+        }
+        for (table, _) in BACKUP_TABLES {
+            assert!(tables.iter().any(|(name, _)| name == table), "{} is not in schema.rs", table);
+        }
+    }
+
+    #[test]
+    fn tables_without_id_key_skip_sequence_reset() {
+        for (table, key) in schema_tables() {
+            if key != "id" && !EXCLUDED_TABLES.contains(&table) {
+                assert!(TABLES_WITHOUT_ID_SEQUENCE.contains(&table), "{} has no id sequence", table);
+            }
+        }
+    }
+
+    #[test]
+    fn foreign_keys_are_listed_as_parents() {
+        for line in include_str!("../schema.rs").lines() {
+            let Some(relation) = line.strip_prefix("diesel::joinable!(") else {
+                continue;
+            };
+            let (child, rest) = relation.split_once(" -> ").unwrap();
+            let parent = rest.split_once(' ').unwrap().0;
+            let parents = BACKUP_TABLES.iter().find(|(name, _)| *name == child).map(|(_, parents)| *parents);
+            assert!(
+                parents.is_some_and(|parents| parents.contains(&parent)),
+                "{} references {} but does not list it as a parent",
+                child,
+                parent
+            );
+        }
+    }
+
+    #[test]
+    fn parents_come_before_children() {
+        for (index, (table, parents)) in BACKUP_TABLES.iter().enumerate() {
+            for parent in parents.iter() {
+                let parent_index = BACKUP_TABLES.iter().position(|(name, _)| name == parent);
+                assert!(parent_index.is_some_and(|i| i < index), "{} must come after {}", table, parent);
+            }
+        }
+    }
+}
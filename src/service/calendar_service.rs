@@ -0,0 +1,133 @@
+use crate::models::calendar::CalendarTokenResponse;
+use crate::models::user::UserSettings;
+use crate::db::{appointment_query, calendar_query, journal_query, mood_query, user_query};
+use crate::errors::app_error::AppError;
+use crate::config::app_config::app_config;
+use crate::utils::ical::{build_calendar, AllDayEvent, TimedEvent};
+use crate::utils::timezone::{local_date, parse_timezone};
+use crate::db::pool::DbPools;
+use chrono::{NaiveDate, Utc};
+use chrono_tz::Tz;
+use rand::Rng;
+use std::collections::BTreeMap;
+
+fn generate_feed_token() -> String {
+    let mut rng = rand::thread_rng();
+    (0..48)
+        .map(|_| rng.sample(rand::distributions::Alphanumeric) as char)
+        .collect()
+}
+
+fn to_response(token: String, created_at: chrono::NaiveDateTime) -> CalendarTokenResponse {
+    CalendarTokenResponse {
+        feed_url: format!("{}/api/calendar.ics?token={}", app_config().public_api_url, token),
+        token,
+        created_at,
+    }
+}
+
+/// Ambil token feed kalender user, buat baru jika belum ada
+pub fn get_or_create_feed_token(
+    pool: &DbPools,
+    user_id: i32,
+) -> Result<CalendarTokenResponse, AppError> {
+    let mut conn = pool.conn_write()?;
+
+    let feed_token = match calendar_query::find_token_by_user(&mut conn, user_id)? {
+        Some(existing) => existing,
+        None => calendar_query::upsert_token(&mut conn, user_id, &generate_feed_token())?,
+    };
+
+    Ok(to_response(feed_token.token, feed_token.created_at))
+}
+
+/// Buat token baru; link feed lama langsung tidak berlaku
+pub fn regenerate_feed_token(
+    pool: &DbPools,
+    user_id: i32,
+) -> Result<CalendarTokenResponse, AppError> {
+    let mut conn = pool.conn_write()?;
+
+    let feed_token = calendar_query::upsert_token(&mut conn, user_id, &generate_feed_token())?;
+
+    Ok(to_response(feed_token.token, feed_token.created_at))
+}
+
+#[derive(Default)]
+struct DayActivity {
+    mood: Option<String>,
+    journal_titles: Vec<String>,
+}
+
+/// Susun feed iCalendar berisi event sepanjang hari untuk setiap hari yang punya catatan,
+/// ditambah janji temu dengan psikolog (yang dibatalkan tetap ada dengan status CANCELLED)
+pub fn build_feed(
+    pool: &DbPools,
+    token: &str,
+) -> Result<String, AppError> {
+    let mut conn = pool.conn_read()?;
+
+    let feed_token = calendar_query::find_token(&mut conn, token)?;
+    let user_id = feed_token.user_id;
+
+    let user = user_query::find_user_by_id(&mut conn, user_id)?;
+    let tz = UserSettings::parse(user.settings.as_deref())
+        .timezone
+        .as_deref()
+        .and_then(parse_timezone)
+        .unwrap_or(Tz::UTC);
+
+    let mut days: BTreeMap<NaiveDate, DayActivity> = BTreeMap::new();
+
+    for mood in mood_query::get_all_moods_by_user(&mut conn, user_id)? {
+        days.entry(mood.date).or_default().mood = Some(mood.mood);
+    }
+
+    for journal in journal_query::get_all_journals_by_user(&mut conn, user_id)? {
+        days.entry(local_date(journal.created_at, tz))
+            .or_default()
+            .journal_titles
+            .push(journal.title);
+    }
+
+    let events: Vec<AllDayEvent> = days
+        .into_iter()
+        .map(|(date, activity)| {
+            let mut parts = Vec::new();
+            if let Some(ref mood) = activity.mood {
+                parts.push(format!("Mood: {}", mood));
+            }
+            match activity.journal_titles.len() {
+                0 => {}
+                1 => parts.push("1 journal entry".to_string()),
+                n => parts.push(format!("{} journal entries", n)),
+            }
+
+            AllDayEvent {
+                uid: format!("mindmate-{}-{}@mindmate", user_id, date.format("%Y%m%d")),
+                date,
+                summary: parts.join(" · "),
+                description: if activity.journal_titles.is_empty() {
+                    None
+                } else {
+                    Some(activity.journal_titles.join("\n"))
+                },
+            }
+        })
+        .collect();
+
+    let appointments: Vec<TimedEvent> = appointment_query::find_appointments_by_user(&mut conn, user_id)?
+        .into_iter()
+        .map(|(appointment, psychologist_name)| TimedEvent {
+            uid: format!("mindmate-appointment-{}@mindmate", appointment.id),
+            cancelled: !appointment.is_scheduled(),
+            starts_at: appointment.starts_at,
+            ends_at: appointment.ends_at,
+            summary: format!("Session with {}", psychologist_name),
+            description: appointment.meeting_link.clone(),
+            url: appointment.meeting_link,
+        })
+        .collect();
+
+    Ok(build_calendar("MindMate", &events, &appointments, Utc::now().naive_utc()))
+}
@@ -0,0 +1,142 @@
+use chrono::{Duration, NaiveDate, Utc};
+use chrono_tz::Tz;
+use rand::Rng;
+use url::Url;
+use crate::config::app_config::app_config;
+use crate::db::{checkin_query, user_query};
+use crate::db::pool::DbPools;
+use crate::db::transaction::run_in_transaction;
+use crate::errors::app_error::AppError;
+use crate::i18n::{t_in, t_in_args, Locale};
+use crate::models::mood::{CreateMoodRequest, MoodResponse, MoodType};
+use crate::models::user::UserSettings;
+use crate::service::mood_service;
+use crate::utils::jwt::{generate_checkin_token, validate_checkin_token};
+use crate::utils::stats_cache::StatsCache;
+use crate::utils::timezone::parse_timezone;
+
+fn generate_token_id() -> String {
+    let mut rng = rand::thread_rng();
+    (0..32)
+        .map(|_| rng.sample(rand::distributions::Alphanumeric) as char)
+        .collect()
+}
+
+fn mood_label(locale: Locale, mood: &MoodType) -> String {
+    t_in(locale, &format!("checkin.mood.{}", mood.as_str().replace(' ', "_")))
+}
+
+/// Link check-in untuk setiap jenis mood. Semua link memakai token yang sama,
+/// jadi setelah satu pilihan diklik link lainnya ikut tidak berlaku.
+pub fn checkin_links(user_id: i32, date: NaiveDate) -> Result<Vec<(MoodType, String)>, AppError> {
+    let ttl = Duration::hours(app_config().checkin_token_hours);
+    let token = generate_checkin_token(user_id, date, &generate_token_id(), ttl)
+        .map_err(|_| AppError::InternalServerError("Failed to create check-in token".to_string()))?;
+    let base = format!("{}/api/checkin", app_config().public_api_url);
+
+    MoodType::ALL
+        .into_iter()
+        .map(|mood| {
+            let url = Url::parse_with_params(&base, &[("token", token.as_str()), ("mood", mood.as_str())])
+                .map_err(|e| AppError::InternalServerError(format!("Invalid PUBLIC_API_URL: {}", e)))?;
+            Ok((mood, url.to_string()))
+        })
+        .collect()
+}
+
+/// Isi email pengingat: satu baris per jenis mood, dari yang paling baik
+pub fn checkin_email_body(locale: Locale, username: &str, links: &[(MoodType, String)]) -> String {
+    let mut body = t_in_args(locale, "checkin.email.intro", &[username]);
+    body.push_str("\n\n");
+    for (mood, url) in links.iter().rev() {
+        body.push_str(&format!("{} {}: {}\n", mood.default_emoji(), mood_label(locale, mood), url));
+    }
+    body.push('\n');
+    let hours = app_config().checkin_token_hours.to_string();
+    body.push_str(&t_in_args(locale, "checkin.email.footer", &[&hours]));
+    body
+}
+
+/// Catat mood dari link email tanpa login. Token dicatat dalam transaksi yang sama
+/// dengan mood, sehingga link hanya bisa dipakai sekali.
+pub fn check_in(
+    pool: &DbPools,
+    cache: &StatsCache,
+    token: &str,
+    mood: &str,
+) -> Result<(MoodResponse, Locale), AppError> {
+    let claims = validate_checkin_token(token)
+        .map_err(|_| AppError::Unauthorized("Invalid or expired check-in link".to_string()))?;
+
+    let mut conn = pool.conn_write()?;
+    let (response, locale) = run_in_transaction(&mut conn, |conn| {
+        let user = user_query::find_user_by_id(conn, claims.uid)?;
+        let settings = UserSettings::parse(user.settings.as_deref());
+        let locale = settings.language.as_deref().and_then(Locale::parse).unwrap_or_default();
+        let tz = settings.timezone.as_deref().and_then(parse_timezone).unwrap_or(Tz::UTC);
+
+        if !checkin_query::claim_token(conn, &claims.jti, user.id)? {
+            return Err(AppError::Conflict("This check-in link has already been used".to_string()));
+        }
+
+        let response = mood_service::insert_mood(
+            conn,
+            user.id,
+            CreateMoodRequest {
+                mood: mood.to_string(),
+                emoji: None,
+                notes: None,
+                details: None,
+                location: None,
+                date: Some(claims.date),
+            },
+            tz,
+        )?;
+        Ok((response, locale))
+    })?;
+    cache.invalidate_user(response.user_id);
+
+    Ok((response, locale))
+}
+
+/// Hapus catatan token yang sudah lewat masa berlakunya
+pub fn cleanup_used_tokens(pool: &DbPools) -> Result<usize, AppError> {
+    let mut conn = pool.conn_write()?;
+
+    let cutoff = Utc::now().naive_utc() - Duration::hours(app_config().checkin_token_hours);
+    checkin_query::delete_used_before(&mut conn, cutoff)
+}
+
+fn escape_html(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+/// Halaman sederhana yang dibuka browser setelah tombol di email diklik
+pub fn render_page(locale: Locale, heading: &str, message: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"{}\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         <title>MindMate</title>\n</head>\n<body style=\"font-family: sans-serif; text-align: center; padding: 3em 1em;\">\n\
+         <h1>{}</h1>\n<p>{}</p>\n</body>\n</html>\n",
+        locale.as_str(),
+        escape_html(heading),
+        escape_html(message),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn page_escapes_user_controlled_text() {
+        let page = render_page(Locale::En, "Oops", "Invalid mood type: <script>alert('x')</script>");
+        assert!(page.contains("&lt;script&gt;alert(&#39;x&#39;)&lt;/script&gt;"));
+        assert!(!page.contains("<script>"));
+    }
+}
@@ -0,0 +1,355 @@
+use std::collections::{HashMap, HashSet};
+use chrono::{Duration, Utc};
+use diesel::pg::PgConnection;
+use crate::config::app_config::app_config;
+use crate::db::{community_query, user_query};
+use crate::db::pool::DbPools;
+use crate::db::transaction::run_in_transaction;
+use crate::errors::app_error::AppError;
+use crate::models::audit::{AuditAction, NewAuditLog};
+use crate::models::community::{
+    CommunityGroup, CommunityGroupResponse, CommunityPost, CommunityPostListQuery, CommunityPostResponse,
+    CreateCommunityGroupRequest, CreateCommunityPostRequest, NewCommunityGroup, NewCommunityPost, ReactionKind,
+    ReactionSummary,
+};
+use crate::models::moderation::ModeratedContent;
+use crate::service::{audit_service, moderation_service};
+use crate::utils::moderation::{ModerationAction, ModerationVerdict};
+use crate::utils::text_limits::ensure_max_length;
+
+const POST_MAX_LENGTH: usize = 2000;
+const GROUP_SLUG_MAX_LENGTH: usize = 50;
+const GROUP_NAME_MAX_LENGTH: usize = 100;
+const GROUP_DESCRIPTION_MAX_LENGTH: usize = 1000;
+const DEFAULT_POST_LIMIT: i64 = 20;
+const MAX_POST_LIMIT: i64 = 100;
+
+/// Dengan pra-moderasi, setiap kiriman ditahan sampai disetujui admin
+/// apa pun hasil pemeriksa otomatis
+pub fn community_verdict(verdict: &ModerationVerdict, premoderation: bool) -> ModerationVerdict {
+    if !premoderation || verdict.is_held() {
+        return verdict.clone();
+    }
+
+    let mut verdict = verdict.clone();
+    if verdict.action == ModerationAction::Allow {
+        verdict.source = "community";
+    }
+    verdict.action = ModerationAction::Hold;
+    verdict.reasons.push("premoderation".to_string());
+    verdict
+}
+
+/// Susun kiriman beserta ringkasan reaksi; semua jenis reaksi selalu dicantumkan
+pub fn post_responses(
+    posts: Vec<CommunityPost>,
+    viewer_id: i32,
+    counts: Vec<(i32, String, i64)>,
+    own_reactions: Vec<(i32, String)>,
+) -> Vec<CommunityPostResponse> {
+    let counts: HashMap<(i32, String), i64> =
+        counts.into_iter().map(|(post_id, kind, count)| ((post_id, kind), count)).collect();
+    let own_reactions: HashSet<(i32, String)> = own_reactions.into_iter().collect();
+
+    posts
+        .into_iter()
+        .map(|post| {
+            let reactions = ReactionKind::ALL
+                .into_iter()
+                .map(|kind| {
+                    let key = (post.id, kind.as_str().to_string());
+                    ReactionSummary {
+                        kind: kind.as_str().to_string(),
+                        count: counts.get(&key).copied().unwrap_or(0),
+                        reacted: own_reactions.contains(&key),
+                    }
+                })
+                .collect();
+            CommunityPostResponse {
+                id: post.id,
+                group_id: post.group_id,
+                body: post.body,
+                is_own: post.user_id == viewer_id,
+                held: post.held,
+                reactions,
+                created_at: post.created_at,
+            }
+        })
+        .collect()
+}
+
+fn load_post_responses(
+    conn: &mut PgConnection,
+    viewer_id: i32,
+    posts: Vec<CommunityPost>,
+) -> Result<Vec<CommunityPostResponse>, AppError> {
+    let post_ids: Vec<i32> = posts.iter().map(|post| post.id).collect();
+    let counts = community_query::count_reactions(conn, &post_ids)?;
+    let own_reactions = community_query::find_user_reactions(conn, viewer_id, &post_ids)?;
+    Ok(post_responses(posts, viewer_id, counts, own_reactions))
+}
+
+fn find_group(conn: &mut PgConnection, group_id: i32) -> Result<CommunityGroup, AppError> {
+    community_query::find_active_group(conn, group_id)?
+        .ok_or_else(|| AppError::NotFound("Community group not found".to_string()))
+}
+
+/// Grup hanya bisa dibaca dan ditulisi anggotanya
+fn ensure_member(conn: &mut PgConnection, group_id: i32, user_id: i32) -> Result<(), AppError> {
+    find_group(conn, group_id)?;
+    if !community_query::is_member(conn, group_id, user_id)? {
+        return Err(AppError::Forbidden("Join the group to see and share posts".to_string()));
+    }
+    Ok(())
+}
+
+/// Kiriman yang boleh dilihat pemanggil: grupnya diikuti dan kiriman tidak ditahan (kecuali milik sendiri)
+fn find_visible_post(conn: &mut PgConnection, post_id: i32, user_id: i32) -> Result<CommunityPost, AppError> {
+    let post = community_query::find_post(conn, post_id)?
+        .filter(|post| !post.held || post.user_id == user_id)
+        .ok_or_else(|| AppError::NotFound("Community post not found".to_string()))?;
+    ensure_member(conn, post.group_id, user_id)?;
+    Ok(post)
+}
+
+fn ensure_group_slug(slug: &str) -> Result<(), AppError> {
+    let valid = !slug.is_empty()
+        && !slug.starts_with('-')
+        && !slug.ends_with('-')
+        && slug.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+    if !valid {
+        return Err(AppError::BadRequest(
+            "Group slug may only contain lowercase letters, digits and hyphens".to_string(),
+        ));
+    }
+    ensure_max_length("Group slug", slug, GROUP_SLUG_MAX_LENGTH)
+}
+
+pub fn get_groups(
+    pool: &DbPools,
+    user_id: i32,
+) -> Result<Vec<CommunityGroupResponse>, AppError> {
+    let mut conn = pool.conn_read()?;
+
+    let members: HashMap<i32, i64> = community_query::count_members(&mut conn)?.into_iter().collect();
+    let joined: HashSet<i32> = community_query::find_joined_group_ids(&mut conn, user_id)?.into_iter().collect();
+    let groups = community_query::find_active_groups(&mut conn)?;
+    Ok(groups
+        .into_iter()
+        .map(|group| {
+            let member_count = members.get(&group.id).copied().unwrap_or(0);
+            let is_joined = joined.contains(&group.id);
+            CommunityGroupResponse::new(group, member_count, is_joined)
+        })
+        .collect())
+}
+
+pub fn create_group(
+    pool: &DbPools,
+    admin_id: i32,
+    data: CreateCommunityGroupRequest,
+    ip_address: Option<&str>,
+) -> Result<CommunityGroupResponse, AppError> {
+    let slug = data.slug.trim();
+    let name = data.name.trim();
+    let description = data.description.as_deref().map(str::trim).filter(|value| !value.is_empty());
+    ensure_group_slug(slug)?;
+    if name.is_empty() {
+        return Err(AppError::BadRequest("Group name cannot be empty".to_string()));
+    }
+    ensure_max_length("Group name", name, GROUP_NAME_MAX_LENGTH)?;
+    if let Some(description) = description {
+        ensure_max_length("Group description", description, GROUP_DESCRIPTION_MAX_LENGTH)?;
+    }
+
+    let mut conn = pool.conn_write()?;
+    run_in_transaction(&mut conn, |conn| {
+        let group = community_query::insert_group(conn, &NewCommunityGroup { slug, name, description })?;
+        audit_service::record(
+            conn,
+            NewAuditLog::new(AuditAction::AdminAction, Some(admin_id), None, ip_address)
+                .with_details(format!("community group {} created", group.slug)),
+        )?;
+        Ok(CommunityGroupResponse::new(group, 0, false))
+    })
+}
+
+pub fn join_group(
+    pool: &DbPools,
+    user_id: i32,
+    group_id: i32,
+) -> Result<CommunityGroupResponse, AppError> {
+    let mut conn = pool.conn_write()?;
+
+    let group = find_group(&mut conn, group_id)?;
+    community_query::insert_membership(&mut conn, group_id, user_id)?;
+    let member_count = community_query::count_members(&mut conn)?
+        .into_iter()
+        .find_map(|(id, count)| (id == group_id).then_some(count))
+        .unwrap_or(0);
+    Ok(CommunityGroupResponse::new(group, member_count, true))
+}
+
+/// Keluar dari grup; kiriman yang sudah dibuat tetap ada
+pub fn leave_group(
+    pool: &DbPools,
+    user_id: i32,
+    group_id: i32,
+) -> Result<(), AppError> {
+    let mut conn = pool.conn_write()?;
+
+    find_group(&mut conn, group_id)?;
+    if community_query::delete_membership(&mut conn, group_id, user_id)? == 0 {
+        return Err(AppError::NotFound("You are not a member of this group".to_string()));
+    }
+    Ok(())
+}
+
+pub fn get_posts(
+    pool: &DbPools,
+    user_id: i32,
+    group_id: i32,
+    query: &CommunityPostListQuery,
+) -> Result<Vec<CommunityPostResponse>, AppError> {
+    let limit = query.limit.unwrap_or(DEFAULT_POST_LIMIT);
+    if limit <= 0 || limit > MAX_POST_LIMIT {
+        return Err(AppError::BadRequest(format!("Limit must be between 1 and {}", MAX_POST_LIMIT)));
+    }
+
+    let mut conn = pool.conn_read()?;
+
+    ensure_member(&mut conn, group_id, user_id)?;
+    let posts = community_query::find_posts(&mut conn, group_id, user_id, query.before_id, limit)?;
+    load_post_responses(&mut conn, user_id, posts)
+}
+
+/// Buat kiriman anonim. Batas per jam dan per hari dihitung dari database dengan baris
+/// pengguna dikunci, sehingga request yang bersamaan tidak bisa melewatinya.
+pub fn create_post(
+    pool: &DbPools,
+    user_id: i32,
+    group_id: i32,
+    data: CreateCommunityPostRequest,
+    verdict: &ModerationVerdict,
+) -> Result<CommunityPostResponse, AppError> {
+    let body = data.body.trim();
+    if body.is_empty() {
+        return Err(AppError::BadRequest("Post cannot be empty".to_string()));
+    }
+    ensure_max_length("Post", body, POST_MAX_LENGTH)?;
+
+    let config = app_config();
+    let verdict = community_verdict(verdict, config.community_premoderation);
+
+    let mut conn = pool.conn_write()?;
+    run_in_transaction(&mut conn, |conn| {
+        ensure_member(conn, group_id, user_id)?;
+        user_query::lock_user(conn, user_id)?;
+
+        let now = Utc::now().naive_utc();
+        if community_query::count_posts_since(conn, user_id, now - Duration::hours(1))?
+            >= config.community_posts_per_hour
+            || community_query::count_posts_since(conn, user_id, now - Duration::days(1))?
+                >= config.community_posts_per_day
+        {
+            return Err(AppError::TooManyRequests("You are posting too often, please try again later".to_string()));
+        }
+
+        let post = community_query::insert_post(
+            conn,
+            &NewCommunityPost {
+                group_id,
+                user_id,
+                body,
+                held: verdict.is_held(),
+            },
+        )?;
+        moderation_service::record(conn, ModeratedContent::CommunityPost, post.id, Some(user_id), &verdict)?;
+        Ok(post_responses(vec![post], user_id, Vec::new(), Vec::new()).remove(0))
+    })
+}
+
+pub fn add_reaction(
+    pool: &DbPools,
+    user_id: i32,
+    post_id: i32,
+    kind: &str,
+) -> Result<CommunityPostResponse, AppError> {
+    let kind = ReactionKind::parse(kind)
+        .ok_or_else(|| AppError::BadRequest(format!("Unknown reaction: {}", kind)))?;
+
+    let mut conn = pool.conn_write()?;
+    run_in_transaction(&mut conn, |conn| {
+        let post = find_visible_post(conn, post_id, user_id)?;
+        user_query::lock_user(conn, user_id)?;
+
+        let since = Utc::now().naive_utc() - Duration::hours(1);
+        if community_query::count_reactions_since(conn, user_id, since)? >= app_config().community_reactions_per_hour {
+            return Err(AppError::TooManyRequests("You are reacting too often, please try again later".to_string()));
+        }
+
+        community_query::insert_reaction(conn, post.id, user_id, kind.as_str())?;
+        Ok(load_post_responses(conn, user_id, vec![post])?.remove(0))
+    })
+}
+
+pub fn remove_reaction(
+    pool: &DbPools,
+    user_id: i32,
+    post_id: i32,
+    kind: &str,
+) -> Result<CommunityPostResponse, AppError> {
+    let kind = ReactionKind::parse(kind)
+        .ok_or_else(|| AppError::BadRequest(format!("Unknown reaction: {}", kind)))?;
+
+    let mut conn = pool.conn_write()?;
+
+    let post = find_visible_post(&mut conn, post_id, user_id)?;
+    community_query::delete_reaction(&mut conn, post.id, user_id, kind.as_str())?;
+    Ok(load_post_responses(&mut conn, user_id, vec![post])?.remove(0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn premoderation_holds_every_post() {
+        let verdict = community_verdict(&ModerationVerdict::allow(), true);
+        assert_eq!(verdict.action, ModerationAction::Hold);
+        assert_eq!(verdict.source, "community");
+        assert_eq!(verdict.reasons, vec!["premoderation".to_string()]);
+
+        assert_eq!(community_verdict(&ModerationVerdict::allow(), false), ModerationVerdict::allow());
+    }
+
+    #[test]
+    fn post_responses_hide_author_and_list_every_reaction() {
+        let created_at = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap().and_hms_opt(9, 0, 0).unwrap();
+        let post = |id, user_id| CommunityPost {
+            id,
+            group_id: 1,
+            user_id,
+            body: "hello".to_string(),
+            held: false,
+            created_at,
+        };
+
+        let responses = post_responses(
+            vec![post(1, 7), post(2, 8)],
+            7,
+            vec![(1, "hug".to_string(), 2), (2, "support".to_string(), 1)],
+            vec![(1, "hug".to_string())],
+        );
+
+        assert!(responses[0].is_own);
+        assert!(!responses[1].is_own);
+        let kinds: Vec<(&str, i64, bool)> = responses[0]
+            .reactions
+            .iter()
+            .map(|reaction| (reaction.kind.as_str(), reaction.count, reaction.reacted))
+            .collect();
+        assert_eq!(kinds, vec![("support", 0, false), ("hug", 2, true), ("relate", 0, false)]);
+    }
+}
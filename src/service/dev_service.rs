@@ -0,0 +1,52 @@
+use chrono::Duration;
+use chrono_tz::Tz;
+use crate::db::{journal_query, mood_query};
+use crate::db::pool::DbPools;
+use crate::db::transaction::run_in_transaction;
+use crate::errors::app_error::AppError;
+use crate::models::dev::{GenerateHistoryRequest, GenerateHistoryResponse};
+use crate::utils::stats_cache::StatsCache;
+use crate::utils::synthetic_data::generate_history;
+use crate::utils::timezone::today_in;
+
+const DEFAULT_HISTORY_DAYS: i64 = 90;
+const MAX_HISTORY_DAYS: i64 = 730;
+const DEFAULT_JOURNALS_PER_WEEK: f64 = 2.0;
+
+/// Isi riwayat mood dan jurnal buatan untuk pengguna (hanya jika DEV_TOOLS aktif)
+pub fn generate_user_history(
+    pool: &DbPools,
+    cache: &StatsCache,
+    user_id: i32,
+    data: GenerateHistoryRequest,
+    tz: Tz,
+) -> Result<GenerateHistoryResponse, AppError> {
+    let days = data.days.unwrap_or(DEFAULT_HISTORY_DAYS);
+    if !(1..=MAX_HISTORY_DAYS).contains(&days) {
+        return Err(AppError::BadRequest(format!("Days must be between 1 and {}", MAX_HISTORY_DAYS)));
+    }
+    let journals_per_week = data.journals_per_week.unwrap_or(DEFAULT_JOURNALS_PER_WEEK);
+    if !(0.0..=7.0).contains(&journals_per_week) {
+        return Err(AppError::BadRequest("Journals per week must be between 0 and 7".to_string()));
+    }
+
+    let end_date = today_in(tz);
+    let start_date = end_date - Duration::days(days - 1);
+    let history = generate_history(&mut rand::thread_rng(), user_id, start_date, end_date, journals_per_week);
+
+    let mut conn = pool.conn_write()?;
+    let (moods_created, journals_created) = run_in_transaction(&mut conn, |conn| {
+        Ok((
+            mood_query::insert_moods(conn, &history.moods)?,
+            journal_query::insert_journals(conn, &history.journals)?,
+        ))
+    })?;
+    cache.invalidate_user(user_id);
+
+    Ok(GenerateHistoryResponse {
+        start_date,
+        end_date,
+        moods_created,
+        journals_created,
+    })
+}
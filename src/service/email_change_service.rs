@@ -0,0 +1,140 @@
+use crate::models::audit::{AuditAction, NewAuditLog};
+use crate::models::email_change::{NewEmailChangeRequest, PendingEmailChangeResponse, RequestEmailChangeRequest};
+use crate::db::{email_change_query, user_query};
+use crate::errors::app_error::AppError;
+use crate::config::app_config::app_config;
+use crate::db::pool::DbPools;
+use crate::db::transaction::run_in_transaction;
+use crate::service::audit_service;
+use crate::utils::email::{normalize_email, parse_email};
+use crate::utils::mailer::{EmailMessage, Mailer};
+use bcrypt::verify;
+use chrono::{Duration, Utc};
+use rand::Rng;
+
+/// Link konfirmasi ganti email berlaku 24 jam
+const EMAIL_CHANGE_TOKEN_HOURS: i64 = 24;
+
+fn generate_change_token() -> String {
+    let mut rng = rand::thread_rng();
+    (0..48)
+        .map(|_| rng.sample(rand::distributions::Alphanumeric) as char)
+        .collect()
+}
+
+/// Langkah pertama ganti email: simpan permintaan dan kirim link konfirmasi ke alamat baru.
+/// Email akun belum berubah sampai link dikonfirmasi.
+pub fn request_email_change(
+    pool: &DbPools,
+    mailer: &dyn Mailer,
+    user_id: i32,
+    data: RequestEmailChangeRequest,
+) -> Result<PendingEmailChangeResponse, AppError> {
+    let new_email = parse_email(&data.new_email)?;
+
+    let mut conn = pool.conn_write()?;
+
+    let user = user_query::find_user_by_id(&mut conn, user_id)
+        .map_err(|_| AppError::NotFound("User not found".to_string()))?;
+
+    let is_valid = verify(&data.password, &user.password)
+        .map_err(|_| AppError::InternalServerError("Failed to verify password".to_string()))?;
+    if !is_valid {
+        return Err(AppError::BadRequest("Invalid password".to_string()));
+    }
+
+    if new_email == normalize_email(&user.email) {
+        return Err(AppError::BadRequest("New email is the same as the current email".to_string()));
+    }
+    if user_query::find_user_by_email(&mut conn, &new_email).is_ok() {
+        return Err(AppError::Conflict("Email already exists".to_string()));
+    }
+
+    let now = Utc::now().naive_utc();
+    let request = email_change_query::upsert_request(&mut conn, &NewEmailChangeRequest {
+        user_id,
+        new_email,
+        token: generate_change_token(),
+        expires_at: now + Duration::hours(EMAIL_CHANGE_TOKEN_HOURS),
+        created_at: now,
+    })?;
+
+    mailer.send(&EmailMessage {
+        to: request.new_email.clone(),
+        subject: "Confirm your new MindMate email".to_string(),
+        body: format!(
+            "Hi {},\n\nOpen this link to use this address for your MindMate account:\n{}/api/user/email/confirm?token={}\n\nThe link expires in {} hours. If you did not request this, ignore this email.",
+            user.username,
+            app_config().public_api_url,
+            request.token,
+            EMAIL_CHANGE_TOKEN_HOURS
+        ),
+    })?;
+
+    Ok(PendingEmailChangeResponse {
+        new_email: request.new_email,
+        expires_at: request.expires_at,
+    })
+}
+
+/// Langkah kedua: terapkan email baru jika token valid dan belum kedaluwarsa
+pub fn confirm_email_change(
+    pool: &DbPools,
+    token: &str,
+    ip_address: Option<&str>,
+) -> Result<(), AppError> {
+    let mut conn = pool.conn_write()?;
+
+    run_in_transaction(&mut conn, |conn| {
+        let request = email_change_query::find_request_by_token(conn, token)?;
+
+        if request.expires_at < Utc::now().naive_utc() {
+            email_change_query::delete_request_by_user(conn, request.user_id)?;
+            return Err(AppError::BadRequest("Invalid or expired email change token".to_string()));
+        }
+
+        let user = user_query::find_user_by_id(conn, request.user_id)
+            .map_err(|_| AppError::NotFound("User not found".to_string()))?;
+
+        // Alamat bisa saja sudah dipakai akun lain sejak permintaan dibuat
+        if user_query::find_user_by_email(conn, &request.new_email).is_ok() {
+            return Err(AppError::Conflict("Email already exists".to_string()));
+        }
+
+        user_query::update_user_email(conn, user.id, &request.new_email)?;
+        email_change_query::delete_request_by_user(conn, user.id)?;
+
+        audit_service::record(
+            conn,
+            NewAuditLog::new(AuditAction::EmailChange, Some(user.id), Some(user.id), ip_address)
+                .with_details(format!("{} -> {}", user.email, request.new_email)),
+        )
+    })
+}
+
+/// Permintaan ganti email yang masih menunggu konfirmasi, jika ada
+pub fn get_pending_email_change(
+    pool: &DbPools,
+    user_id: i32,
+) -> Result<Option<PendingEmailChangeResponse>, AppError> {
+    let mut conn = pool.conn_write()?;
+
+    let pending = email_change_query::find_request_by_user(&mut conn, user_id)?
+        .filter(|request| request.expires_at >= Utc::now().naive_utc())
+        .map(|request| PendingEmailChangeResponse {
+            new_email: request.new_email,
+            expires_at: request.expires_at,
+        });
+
+    Ok(pending)
+}
+
+pub fn cancel_email_change(
+    pool: &DbPools,
+    user_id: i32,
+) -> Result<(), AppError> {
+    let mut conn = pool.conn_write()?;
+
+    email_change_query::delete_request_by_user(&mut conn, user_id)?;
+    Ok(())
+}
@@ -0,0 +1,112 @@
+use std::collections::HashMap;
+use axum::body::Body;
+use chrono::NaiveDate;
+use chrono_tz::Tz;
+use crate::db::{journal_query, mood_query};
+use crate::db::pool::DbPools;
+use crate::errors::app_error::AppError;
+use crate::models::audit::{AuditAction, NewAuditLog};
+use crate::models::journal::Journal;
+use crate::models::quota::QuotaOperation;
+use crate::service::{audit_service, quota_service};
+use crate::utils::timezone::local_date;
+use crate::utils::zip_stream::stream_zip;
+
+/// Panjang maksimum slug judul di nama file
+const SLUG_MAX_LENGTH: usize = 50;
+
+/// Arsip ZIP berisi satu file Markdown per jurnal, dengan front-matter YAML
+/// (tanggal, mood hari itu, tag) agar bisa dibuka langsung di Obsidian/Notion
+pub fn export_journals_markdown(
+    pool: &DbPools,
+    user_id: i32,
+    tz: Tz,
+    ip_address: Option<&str>,
+) -> Result<Body, AppError> {
+    quota_service::consume(pool, user_id, QuotaOperation::Export)?;
+    journals_markdown_archive(pool, user_id, tz, ip_address)
+}
+
+/// Arsip jurnal tanpa memakai kuota; dipanggil worker untuk job yang kuotanya sudah dipakai saat diantrikan
+pub fn journals_markdown_archive(
+    pool: &DbPools,
+    user_id: i32,
+    tz: Tz,
+    ip_address: Option<&str>,
+) -> Result<Body, AppError> {
+    let mut conn = pool.conn_write()?;
+    audit_service::record(
+        &mut conn,
+        NewAuditLog::new(AuditAction::DataExport, Some(user_id), Some(user_id), ip_address)
+            .with_details("journals_markdown".to_string()),
+    )?;
+
+    let mut conn = pool.conn_read()?;
+    let moods: HashMap<NaiveDate, String> = mood_query::get_all_moods_by_user(&mut conn, user_id)?
+        .into_iter()
+        .map(|mood| (mood.date, mood.mood))
+        .collect();
+
+    Ok(stream_zip(move |zip| {
+        journal_query::for_each_journal_by_user(&mut conn, user_id, |journal| {
+            let date = local_date(journal.created_at, tz);
+            let name = format!("journals/{}-{}-{}.md", date, slugify(&journal.title), journal.id);
+            let markdown = journal_markdown(&journal, date, moods.get(&date).map(String::as_str));
+            zip.add_file(&name, journal.updated_at.unwrap_or(journal.created_at), markdown.as_bytes())
+        })
+    }))
+}
+
+fn journal_markdown(journal: &Journal, date: NaiveDate, mood: Option<&str>) -> String {
+    // String JSON juga string YAML yang valid, jadi judul aman dari karakter khusus
+    let quote = |value: &str| serde_json::to_string(value).unwrap_or_else(|_| "\"\"".to_string());
+
+    let mut tags = vec!["mindmate".to_string()];
+    if let Some(mood) = mood {
+        tags.push(format!("mood/{}", mood.replace(' ', "-")));
+    }
+    if journal.is_pinned {
+        tags.push("pinned".to_string());
+    }
+
+    let mut out = String::from("---\n");
+    out.push_str(&format!("title: {}\n", quote(&journal.title)));
+    out.push_str(&format!("date: {}\n", date));
+    out.push_str(&format!("created: {}\n", journal.created_at.format("%Y-%m-%dT%H:%M:%SZ")));
+    if let Some(updated_at) = journal.updated_at {
+        out.push_str(&format!("updated: {}\n", updated_at.format("%Y-%m-%dT%H:%M:%SZ")));
+    }
+    if let Some(mood) = mood {
+        out.push_str(&format!("mood: {}\n", quote(mood)));
+    }
+    out.push_str("tags:\n");
+    for tag in tags {
+        out.push_str(&format!("  - {}\n", tag));
+    }
+    out.push_str("---\n\n");
+    out.push_str(&format!("# {}\n\n", journal.title.trim()));
+    out.push_str(journal.content.trim_end());
+    out.push('\n');
+    out
+}
+
+/// Slug ASCII untuk nama file; judul tanpa huruf/angka menjadi "journal"
+fn slugify(title: &str) -> String {
+    let mut slug = String::new();
+    for c in title.chars().flat_map(char::to_lowercase) {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+        if slug.len() >= SLUG_MAX_LENGTH {
+            break;
+        }
+    }
+    let slug = slug.trim_end_matches('-');
+    if slug.is_empty() {
+        "journal".to_string()
+    } else {
+        slug.to_string()
+    }
+}
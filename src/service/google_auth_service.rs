@@ -1,14 +1,28 @@
-use crate::models::google_auth::{GoogleTokenResponse, GoogleUserInfo, GoogleLoginResponse};
-use crate::db::user_query;
+use crate::config::app_config::{app_config, AppConfig};
+use crate::models::google_auth::{GoogleTokenResponse, GoogleUserInfo, GoogleLoginResponse, NewAuthExchangeCode};
+use crate::models::auth::LoginResponse;
+use crate::db::{auth_exchange_query, oauth_account_query, user_query};
+use crate::models::oauth_account::{NewOAuthAccount, OAuthTokenUpdate, PROVIDER_GOOGLE};
+use crate::utils::token_crypto::token_cipher;
+use diesel::pg::PgConnection;
+use crate::models::user::{NewUser, UserResponse};
 use crate::errors::app_error::AppError;
 use crate::utils::jwt::generate_token;
-use diesel::r2d2;
-use diesel::pg::PgConnection;
-use reqwest;
+use crate::db::pool::DbPools;
+use crate::db::transaction::run_in_transaction;
+use crate::utils::http_client::HttpClient;
+use crate::utils::redirect::RedirectWhitelist;
+use chrono::{Duration, Utc};
 use url::Url;
+use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
 use rand::Rng;
+use crate::utils::email::normalize_email;
+use crate::utils::username::{normalize_username, USERNAME_MAX_LENGTH};
 use bcrypt;
 
+/// Access token yang tersisa kurang dari ini dianggap kedaluwarsa dan di-refresh
+const ACCESS_TOKEN_EXPIRY_MARGIN_SECS: i64 = 60;
+
 pub struct GoogleOAuthConfig {
     pub client_id: String,
     pub client_secret: String,
@@ -16,25 +30,22 @@ pub struct GoogleOAuthConfig {
 }
 
 impl GoogleOAuthConfig {
-    pub fn from_env() -> Result<Self, AppError> {
-        let client_id = std::env::var("GOOGLE_CLIENT_ID")
-            .map_err(|_| AppError::InternalServerError("GOOGLE_CLIENT_ID not set".to_string()))?;
-        let client_secret = std::env::var("GOOGLE_CLIENT_SECRET")
-            .map_err(|_| AppError::InternalServerError("GOOGLE_CLIENT_SECRET not set".to_string()))?;
-        let redirect_uri = std::env::var("GOOGLE_REDIRECT_URI")
-            .map_err(|_| AppError::InternalServerError("GOOGLE_REDIRECT_URI not set".to_string()))?;
+    pub fn from_config(config: &AppConfig) -> Result<Self, AppError> {
+        let required = |value: &Option<String>, name: &str| {
+            value
+                .clone()
+                .ok_or_else(|| AppError::InternalServerError(format!("{} not set", name)))
+        };
 
         Ok(GoogleOAuthConfig {
-            client_id,
-            client_secret,
-            redirect_uri,
+            client_id: required(&config.google_client_id, "GOOGLE_CLIENT_ID")?,
+            client_secret: required(&config.google_client_secret, "GOOGLE_CLIENT_SECRET")?,
+            redirect_uri: required(&config.google_redirect_uri, "GOOGLE_REDIRECT_URI")?,
         })
     }
 }
 
-pub fn generate_google_auth_url(config: &GoogleOAuthConfig) -> Result<String, AppError> {
-    let state = generate_random_state();
-    
+pub fn generate_google_auth_url(config: &GoogleOAuthConfig, state: &str) -> Result<String, AppError> {
     let mut url = Url::parse("https://accounts.google.com/o/oauth2/auth")
         .map_err(|_| AppError::InternalServerError("Failed to parse Google OAuth URL".to_string()))?;
 
@@ -45,17 +56,16 @@ pub fn generate_google_auth_url(config: &GoogleOAuthConfig) -> Result<String, Ap
         .append_pair("response_type", "code")
         .append_pair("access_type", "offline")
         .append_pair("prompt", "consent")
-        .append_pair("state", &state);
+        .append_pair("state", state);
 
     Ok(url.to_string())
 }
 
 pub async fn exchange_code_for_token(
+    client: &HttpClient,
     config: &GoogleOAuthConfig,
     code: &str,
 ) -> Result<GoogleTokenResponse, AppError> {
-    let client = reqwest::Client::new();
-    
     let params = [
         ("client_id", config.client_id.as_str()),
         ("client_secret", config.client_secret.as_str()),
@@ -64,10 +74,12 @@ pub async fn exchange_code_for_token(
         ("redirect_uri", config.redirect_uri.as_str()),
     ];
 
-    let response = client
+    let request = client
         .post("https://oauth2.googleapis.com/token")
-        .form(&params)
-        .send()
+        .form(&params);
+
+    let response = client
+        .send(request)
         .await
         .map_err(|e| AppError::InternalServerError(format!("Failed to exchange code for token: {}", e)))?;
 
@@ -84,13 +96,13 @@ pub async fn exchange_code_for_token(
     Ok(token_response)
 }
 
-pub async fn get_user_info(access_token: &str) -> Result<GoogleUserInfo, AppError> {
-    let client = reqwest::Client::new();
-    
-    let response = client
+pub async fn get_user_info(client: &HttpClient, access_token: &str) -> Result<GoogleUserInfo, AppError> {
+    let request = client
         .get("https://www.googleapis.com/oauth2/v2/userinfo")
-        .bearer_auth(access_token)
-        .send()
+        .bearer_auth(access_token);
+
+    let response = client
+        .send(request)
         .await
         .map_err(|e| AppError::InternalServerError(format!("Failed to get user info: {}", e)))?;
 
@@ -108,57 +120,222 @@ pub async fn get_user_info(access_token: &str) -> Result<GoogleUserInfo, AppErro
 }
 
 pub async fn google_login(
-    pool: &r2d2::Pool<r2d2::ConnectionManager<PgConnection>>,
+    pool: &DbPools,
+    client: &HttpClient,
     code: &str,
     _state: Option<&str>,
 ) -> Result<GoogleLoginResponse, AppError> {
-    let config = GoogleOAuthConfig::from_env()?;
+    let config = GoogleOAuthConfig::from_config(app_config())?;
     
-    let token_response = exchange_code_for_token(&config, code).await?;
-    let google_user = get_user_info(&token_response.access_token).await?;
+    let token_response = exchange_code_for_token(client, &config, code).await?;
+    let google_user = get_user_info(client, &token_response.access_token).await?;
     
     println!("Google user info: ID={}, Name={}, Email={}, Verified={}", 
              google_user.id, google_user.name, google_user.email, google_user.verified_email);
     
-    let mut conn = pool
-        .get()
-        .map_err(|_| AppError::InternalServerError("Failed to get DB connection".to_string()))?;
-
-    let (user, is_new_user) = match user_query::find_user_by_email(&mut conn, &google_user.email) {
-        Ok(existing_user) => {
-            if let Some(_picture) = &google_user.picture {
-                println!("User {} has profile picture: {}", google_user.email, _picture);
+    let email = normalize_email(&google_user.email);
+    let mut conn = pool.conn_write()?;
+
+    // Find-or-create dalam satu transaksi agar dua callback bersamaan tidak membuat user ganda
+    let (user, is_new_user) = run_in_transaction(&mut conn, |conn| {
+        match user_query::find_user_by_email(conn, &email) {
+            Ok(mut existing_user) => {
+                // Foto Google hanya menggantikan avatar kosong atau foto Google sebelumnya,
+                // bukan avatar yang dipilih pengguna sendiri
+                let picture = google_user.picture.as_deref();
+                let refresh_avatar = picture.is_some()
+                    && existing_user.avatar.as_deref() != picture
+                    && existing_user.avatar.as_deref().is_none_or(is_google_picture);
+                if refresh_avatar {
+                    user_query::update_user_avatar(conn, existing_user.id, picture)?;
+                    existing_user.avatar = picture.map(str::to_string);
+                }
+                Ok((existing_user, false))
+            },
+            Err(_) => {
+                let username = generate_username_from_google_user(&google_user);
+                let random_password = generate_random_password();
+                
+                let hashed_password = bcrypt::hash(&random_password, bcrypt::DEFAULT_COST)
+                    .map_err(|_| AppError::InternalServerError("Failed to hash password".to_string()))?;
+                
+                let now = Utc::now().naive_utc();
+                let new_user = user_query::insert_user(
+                    conn,
+                    &NewUser {
+                        username: username.clone(),
+                        email: email.clone(),
+                        password: hashed_password,
+                        settings: None,
+                        age: None,
+                        gender: None,
+                        avatar: google_user.picture.clone(),
+                        created_at: now,
+                        updated_at: now,
+                    },
+                )?;
+                
+                println!("Created new user: {} with username: {}", email, username);
+                Ok((new_user, true))
             }
-            (existing_user, false)
+        }
+    })?;
+
+    // Gagal menyimpan token Google tidak boleh menggagalkan login
+    if let Err(e) = save_google_account(&mut conn, user.id, &google_user.id, &token_response) {
+        eprintln!("❌ Failed to store Google tokens for user {}: {}", user.id, e);
+    }
+
+    // Token tidak dikirim lewat URL redirect; frontend menukar kode ini lewat POST /auth/exchange
+    let now = Utc::now().naive_utc();
+    let exchange_code = auth_exchange_query::insert_code(
+        &mut conn,
+        &NewAuthExchangeCode {
+            code: generate_exchange_code(),
+            user_id: user.id,
+            expires_at: now + Duration::seconds(app_config().auth_exchange_code_ttl_secs),
+            created_at: now,
+        },
+    )?;
+
+    Ok(GoogleLoginResponse {
+        exchange_code: exchange_code.code,
+        is_new_user,
+    })
+}
+
+/// Simpan akun Google pengguna beserta tokennya (terenkripsi). Tanpa OAUTH_TOKEN_ENCRYPTION_KEY
+/// hanya tautan akunnya yang disimpan.
+fn save_google_account(
+    conn: &mut PgConnection,
+    user_id: i32,
+    google_user_id: &str,
+    tokens: &GoogleTokenResponse,
+) -> Result<(), AppError> {
+    let encrypt = |token: &str| token_cipher().map(|cipher| cipher.encrypt(token)).transpose();
+    let now = Utc::now().naive_utc();
+
+    oauth_account_query::upsert_account(
+        conn,
+        &NewOAuthAccount {
+            user_id,
+            provider: PROVIDER_GOOGLE,
+            provider_user_id: google_user_id,
+            refresh_token_encrypted: tokens.refresh_token.as_deref().map(encrypt).transpose()?.flatten(),
+            access_token_encrypted: encrypt(&tokens.access_token)?,
+            access_token_expires_at: tokens.expires_in.map(|secs| now + Duration::seconds(secs)),
+            scope: tokens.scope.clone(),
+            updated_at: now,
         },
-        Err(_) => {
-            let username = generate_username_from_google_user(&google_user);
-            let random_password = generate_random_password();
-            
-            let hashed_password = bcrypt::hash(&random_password, bcrypt::DEFAULT_COST)
-                .map_err(|_| AppError::InternalServerError("Failed to hash password".to_string()))?;
-            
-            let new_user = user_query::create_user(
-                &mut conn,
-                &username,
-                &google_user.email,
-                &hashed_password,
-                None,
-                None,
-                None,
-            )?;
-            
-            println!("Created new user: {} with username: {}", google_user.email, username);
-            (new_user, true)
+    )?;
+    Ok(())
+}
+
+/// Access token Google yang masih berlaku untuk pengguna, di-refresh dengan refresh token tersimpan
+/// bila perlu. Untuk integrasi Google API (Calendar, Fit) tanpa meminta izin ulang.
+pub async fn google_access_token(
+    pool: &DbPools,
+    client: &HttpClient,
+    user_id: i32,
+) -> Result<String, AppError> {
+    let cipher = token_cipher()
+        .ok_or_else(|| AppError::InternalServerError("OAUTH_TOKEN_ENCRYPTION_KEY not set".to_string()))?;
+    let not_connected = || AppError::BadRequest("Google account is not connected".to_string());
+
+    let account = {
+        let mut conn = pool.conn_read()?;
+        oauth_account_query::find_user_account(&mut conn, user_id, PROVIDER_GOOGLE)?.ok_or_else(not_connected)?
+    };
+
+    let refresh_after = Utc::now().naive_utc() + Duration::seconds(ACCESS_TOKEN_EXPIRY_MARGIN_SECS);
+    if let (Some(ref access_token), Some(expires_at)) = (&account.access_token_encrypted, account.access_token_expires_at) {
+        if expires_at > refresh_after {
+            return cipher.decrypt(access_token);
+        }
+    }
+
+    let refresh_token = cipher.decrypt(account.refresh_token_encrypted.as_deref().ok_or_else(not_connected)?)?;
+    let config = GoogleOAuthConfig::from_config(app_config())?;
+    let tokens = match refresh_access_token(client, &config, &refresh_token).await? {
+        Some(tokens) => tokens,
+        None => {
+            // Izin dicabut pengguna di akun Google; token lama tidak berguna lagi
+            let mut conn = pool.conn_write()?;
+            oauth_account_query::clear_tokens(&mut conn, account.id, Utc::now().naive_utc())?;
+            return Err(AppError::BadRequest("Google access was revoked, sign in with Google again".to_string()));
         }
     };
 
-    let jwt_token = generate_token(&user.id.to_string())
+    let now = Utc::now().naive_utc();
+    let mut conn = pool.conn_write()?;
+    oauth_account_query::update_tokens(
+        &mut conn,
+        account.id,
+        &OAuthTokenUpdate {
+            access_token_encrypted: Some(cipher.encrypt(&tokens.access_token)?),
+            access_token_expires_at: tokens.expires_in.map(|secs| now + Duration::seconds(secs)),
+            refresh_token_encrypted: tokens.refresh_token.as_deref().map(|token| cipher.encrypt(token)).transpose()?,
+            updated_at: now,
+        },
+    )?;
+
+    Ok(tokens.access_token)
+}
+
+/// `None` jika Google menolak refresh token (`invalid_grant`: dicabut atau kedaluwarsa)
+async fn refresh_access_token(
+    client: &HttpClient,
+    config: &GoogleOAuthConfig,
+    refresh_token: &str,
+) -> Result<Option<GoogleTokenResponse>, AppError> {
+    let params = [
+        ("client_id", config.client_id.as_str()),
+        ("client_secret", config.client_secret.as_str()),
+        ("refresh_token", refresh_token),
+        ("grant_type", "refresh_token"),
+    ];
+
+    let request = client
+        .post("https://oauth2.googleapis.com/token")
+        .form(&params);
+
+    let response = client
+        .send_idempotent(request)
+        .await
+        .map_err(|e| AppError::InternalServerError(format!("Failed to refresh Google token: {}", e)))?;
+
+    if !response.status().is_success() {
+        let error_text = response.text().await.unwrap_or_default();
+        if error_text.contains("invalid_grant") {
+            return Ok(None);
+        }
+        return Err(AppError::InternalServerError(format!("Google OAuth error: {}", error_text)));
+    }
+
+    response
+        .json()
+        .await
+        .map(Some)
+        .map_err(|e| AppError::InternalServerError(format!("Failed to parse token response: {}", e)))
+}
+
+/// Tukar kode dari callback Google dengan JWT. Kode langsung dihapus, jadi hanya bisa dipakai sekali.
+pub fn redeem_exchange_code(pool: &DbPools, code: &str) -> Result<LoginResponse, AppError> {
+    let invalid = || AppError::BadRequest("Invalid or expired exchange code".to_string());
+    let mut conn = pool.conn_write()?;
+
+    let exchange_code = auth_exchange_query::take_code(&mut conn, code.trim())?.ok_or_else(invalid)?;
+    if exchange_code.expires_at <= Utc::now().naive_utc() {
+        return Err(invalid());
+    }
+
+    let user = user_query::find_user_by_id(&mut conn, exchange_code.user_id)?;
+    let token = generate_token(&user.id.to_string())
         .map_err(|_| AppError::InternalServerError("Failed to generate token".to_string()))?;
 
-    Ok(GoogleLoginResponse {
-        token: jwt_token,
-        user: crate::models::user::UserResponse {
+    Ok(LoginResponse {
+        token,
+        user: UserResponse {
             id: user.id,
             username: user.username,
             email: user.email,
@@ -169,14 +346,53 @@ pub async fn google_login(
             settings: user.settings,
             created_at: user.created_at,
             updated_at: user.updated_at,
+            is_guest: user.is_guest,
         },
-        is_new_user,
     })
 }
 
-pub fn get_google_auth_url() -> Result<String, AppError> {
-    let config = GoogleOAuthConfig::from_env()?;
-    generate_google_auth_url(&config)
+/// Hapus kode tukar yang kedaluwarsa tanpa pernah ditukar
+pub fn cleanup_expired_exchange_codes(pool: &DbPools) -> Result<usize, AppError> {
+    let mut conn = pool.conn_write()?;
+    auth_exchange_query::delete_expired(&mut conn, Utc::now().naive_utc())
+}
+
+/// URL login Google. Tujuan `redirect_to` divalidasi terhadap whitelist lalu dibawa di parameter
+/// `state` (`<acak>.<base64url(redirect_to)>`), karena Google hanya mengembalikan `code` dan `state`.
+pub fn get_google_auth_url(redirect_to: Option<&str>) -> Result<String, AppError> {
+    let config = GoogleOAuthConfig::from_config(app_config())?;
+    let mut state = generate_random_state();
+    if let Some(target) = redirect_to.map(str::trim).filter(|target| !target.is_empty()) {
+        redirect_whitelist()?.resolve(Some(target))?;
+        state = format!("{}.{}", state, URL_SAFE_NO_PAD.encode(target));
+    }
+    generate_google_auth_url(&config, &state)
+}
+
+/// Tujuan redirect frontend setelah callback. `state` yang rusak atau tujuan yang tidak lagi
+/// diizinkan kembali ke halaman default, agar login tetap berhasil.
+pub fn frontend_redirect(state: Option<&str>) -> Result<Url, AppError> {
+    let whitelist = redirect_whitelist()?;
+    let target = state
+        .and_then(|state| state.split_once('.'))
+        .and_then(|(_, encoded)| URL_SAFE_NO_PAD.decode(encoded).ok())
+        .and_then(|target| String::from_utf8(target).ok());
+
+    Ok(whitelist
+        .resolve(target.as_deref())
+        .unwrap_or_else(|_| whitelist.default_target()))
+}
+
+fn redirect_whitelist() -> Result<RedirectWhitelist, AppError> {
+    RedirectWhitelist::from_config(app_config()).map_err(AppError::InternalServerError)
+}
+
+/// Foto profil Google (lh3.googleusercontent.com dan sejenisnya)
+fn is_google_picture(avatar: &str) -> bool {
+    Url::parse(avatar)
+        .ok()
+        .and_then(|url| url.host_str().map(|host| host.ends_with(".googleusercontent.com")))
+        .unwrap_or(false)
 }
 
 fn generate_random_state() -> String {
@@ -186,6 +402,14 @@ fn generate_random_state() -> String {
         .collect()
 }
 
+/// 48 karakter alfanumerik (~285 bit); cukup karena kode juga hanya berlaku sebentar dan sekali pakai
+fn generate_exchange_code() -> String {
+    let mut rng = rand::thread_rng();
+    (0..48)
+        .map(|_| rng.sample(rand::distributions::Alphanumeric) as char)
+        .collect()
+}
+
 fn generate_username_from_google_user(google_user: &GoogleUserInfo) -> String {
     let base_username = if let Some(given_name) = &google_user.given_name {
         given_name.to_lowercase().replace(' ', "")
@@ -193,6 +417,14 @@ fn generate_username_from_google_user(google_user: &GoogleUserInfo) -> String {
         google_user.email.split('@').next().unwrap_or("user").to_string()
     };
     
+    // Samakan dengan aturan username registrasi biasa
+    let base_username: String = normalize_username(&base_username)
+        .chars()
+        .filter(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '_' | '.' | '-'))
+        .take(USERNAME_MAX_LENGTH - 4)
+        .collect();
+    let base_username = if base_username.is_empty() { "user".to_string() } else { base_username };
+
     let random_suffix: u32 = rand::thread_rng().gen_range(1000..9999);
     format!("{}{}", base_username, random_suffix)
 }
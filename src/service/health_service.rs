@@ -0,0 +1,241 @@
+use std::collections::{BTreeMap, HashSet};
+use chrono::{Duration, NaiveDate, NaiveDateTime, Utc};
+use chrono_tz::Tz;
+use diesel::pg::PgConnection;
+use crate::db::health_query;
+use crate::db::pool::DbPools;
+use crate::db::transaction::run_in_transaction;
+use crate::errors::app_error::AppError;
+use crate::models::health::{
+    HealthSample, HealthSampleKind, HealthSamplesRequest, HealthSamplesResponse, HealthSource, NewHealthSample,
+};
+use crate::utils::timezone::{day_range_utc, local_date};
+
+const MAX_SAMPLES_PER_REQUEST: usize = 5000;
+const MAX_SAMPLE_HOURS: i64 = 24;
+const MAX_STEPS_PER_SAMPLE: i64 = 100_000;
+/// Toleransi jam perangkat yang sedikit lebih cepat dari server
+const CLOCK_SKEW_MINUTES: i64 = 10;
+
+/// Langkah dan jam tidur per tanggal lokal pengguna
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct DailyHealth {
+    pub steps: Option<f64>,
+    pub sleep_hours: Option<f64>,
+}
+
+impl DailyHealth {
+    /// Nama seri yang dipakai di analitik laporan
+    pub const METRICS: [&'static str; 2] = ["steps", "tracked_sleep_hours"];
+
+    pub fn metric(&self, name: &str) -> Option<f64> {
+        match name {
+            "steps" => self.steps,
+            "tracked_sleep_hours" => self.sleep_hours,
+            _ => None,
+        }
+    }
+}
+
+fn validate_interval(start_at: NaiveDateTime, end_at: NaiveDateTime, latest: NaiveDateTime) -> Result<(), AppError> {
+    if end_at <= start_at || end_at - start_at > Duration::hours(MAX_SAMPLE_HOURS) {
+        return Err(AppError::BadRequest(format!(
+            "Each sample must end after it starts and last at most {} hours",
+            MAX_SAMPLE_HOURS
+        )));
+    }
+    if end_at > latest {
+        return Err(AppError::BadRequest("Health samples cannot end in the future".to_string()));
+    }
+    Ok(())
+}
+
+/// Buang sampel dengan interval yang sama (jenis, sumber, awal, akhir) dalam satu request;
+/// nilai yang terakhir dikirim yang dipakai, sama seperti upsert ke baris yang sudah ada.
+/// Tanpa ini PostgreSQL menolak `ON CONFLICT DO UPDATE` yang mengenai baris yang sama dua kali.
+fn dedupe_samples(samples: Vec<NewHealthSample<'_>>) -> Vec<NewHealthSample<'_>> {
+    let mut seen = HashSet::new();
+    let mut unique: Vec<_> = samples
+        .into_iter()
+        .rev()
+        .filter(|sample| seen.insert((sample.kind, sample.source, sample.start_at, sample.end_at)))
+        .collect();
+    unique.reverse();
+    unique
+}
+
+pub fn save_samples(
+    pool: &DbPools,
+    user_id: i32,
+    data: HealthSamplesRequest,
+) -> Result<HealthSamplesResponse, AppError> {
+    let source = HealthSource::parse(&data.source)
+        .ok_or_else(|| AppError::BadRequest(format!("Unsupported health data source: {}", data.source)))?;
+    if data.steps.len() + data.sleep.len() > MAX_SAMPLES_PER_REQUEST {
+        return Err(AppError::BadRequest(format!(
+            "At most {} samples can be sent per request",
+            MAX_SAMPLES_PER_REQUEST
+        )));
+    }
+
+    let latest = Utc::now().naive_utc() + Duration::minutes(CLOCK_SKEW_MINUTES);
+    let mut samples = Vec::with_capacity(data.steps.len() + data.sleep.len());
+    for sample in &data.steps {
+        validate_interval(sample.start_at, sample.end_at, latest)?;
+        if !(0..=MAX_STEPS_PER_SAMPLE).contains(&sample.count) {
+            return Err(AppError::BadRequest(format!(
+                "Step count must be between 0 and {}",
+                MAX_STEPS_PER_SAMPLE
+            )));
+        }
+        samples.push(NewHealthSample {
+            user_id,
+            kind: HealthSampleKind::Steps.as_str(),
+            source: source.as_str(),
+            start_at: sample.start_at,
+            end_at: sample.end_at,
+            value: Some(sample.count as f64),
+        });
+    }
+    for segment in &data.sleep {
+        validate_interval(segment.start_at, segment.end_at, latest)?;
+        samples.push(NewHealthSample {
+            user_id,
+            kind: HealthSampleKind::Sleep.as_str(),
+            source: source.as_str(),
+            start_at: segment.start_at,
+            end_at: segment.end_at,
+            value: None,
+        });
+    }
+
+    let samples = dedupe_samples(samples);
+    let mut conn = pool.conn_write()?;
+    run_in_transaction(&mut conn, |conn| health_query::upsert_samples(conn, &samples))?;
+
+    Ok(HealthSamplesResponse {
+        steps: data.steps.len(),
+        sleep: data.sleep.len(),
+    })
+}
+
+/// Ringkasan harian antara `start_date` dan `end_date` menurut zona waktu pengguna
+pub fn get_daily_health(
+    conn: &mut PgConnection,
+    user_id: i32,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+    tz: Tz,
+) -> Result<BTreeMap<NaiveDate, DailyHealth>, AppError> {
+    let (from, to) = day_range_utc(start_date, end_date, tz);
+    let samples = health_query::find_samples_ending_between(conn, user_id, from, to)?;
+    Ok(daily_health(&samples, tz))
+}
+
+/// Gabungkan sampel per tanggal lokal tempat sampel berakhir, jadi tidur semalam dihitung
+/// untuk hari bangun. Langkah dari beberapa sumber tidak dijumlahkan (ponsel dan jam tangan
+/// mencatat langkah yang sama); dipakai total sumber tertinggi. Segmen tidur yang tumpang
+/// tindih digabung lebih dulu.
+pub fn daily_health(samples: &[HealthSample], tz: Tz) -> BTreeMap<NaiveDate, DailyHealth> {
+    let mut steps: BTreeMap<(NaiveDate, &str), f64> = BTreeMap::new();
+    let mut sleep: BTreeMap<NaiveDate, Vec<(NaiveDateTime, NaiveDateTime)>> = BTreeMap::new();
+
+    for sample in samples {
+        let date = local_date(sample.end_at, tz);
+        if sample.kind == HealthSampleKind::Steps.as_str() {
+            *steps.entry((date, sample.source.as_str())).or_default() += sample.value.unwrap_or(0.0);
+        } else if sample.kind == HealthSampleKind::Sleep.as_str() {
+            sleep.entry(date).or_default().push((sample.start_at, sample.end_at));
+        }
+    }
+
+    let mut days: BTreeMap<NaiveDate, DailyHealth> = BTreeMap::new();
+    for ((date, _), total) in steps {
+        let day = days.entry(date).or_default();
+        day.steps = Some(day.steps.map_or(total, |current| current.max(total)));
+    }
+    for (date, mut segments) in sleep {
+        segments.sort();
+        let mut seconds = 0;
+        let mut current: Option<(NaiveDateTime, NaiveDateTime)> = None;
+        for (start, end) in segments {
+            current = match current {
+                Some((current_start, current_end)) if start <= current_end => Some((current_start, current_end.max(end))),
+                Some((current_start, current_end)) => {
+                    seconds += (current_end - current_start).num_seconds();
+                    Some((start, end))
+                }
+                None => Some((start, end)),
+            };
+        }
+        if let Some((start, end)) = current {
+            seconds += (end - start).num_seconds();
+        }
+        days.entry(date).or_default().sleep_hours = Some(seconds as f64 / 3600.0);
+    }
+    days
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(kind: HealthSampleKind, source: HealthSource, start: &str, end: &str, value: Option<f64>) -> HealthSample {
+        let parse = |value: &str| NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M").unwrap();
+        HealthSample {
+            id: 0,
+            user_id: 1,
+            kind: kind.as_str().to_string(),
+            source: source.as_str().to_string(),
+            start_at: parse(start),
+            end_at: parse(end),
+            value,
+            created_at: parse(start),
+        }
+    }
+
+    #[test]
+    fn duplicate_intervals_keep_the_last_value() {
+        let parse = |value: &str| NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M").unwrap();
+        let new_sample = |source: HealthSource, value: f64| NewHealthSample {
+            user_id: 1,
+            kind: HealthSampleKind::Steps.as_str(),
+            source: source.as_str(),
+            start_at: parse("2026-10-14 01:00"),
+            end_at: parse("2026-10-14 02:00"),
+            value: Some(value),
+        };
+        let samples = dedupe_samples(vec![
+            new_sample(HealthSource::GoogleFit, 100.0),
+            new_sample(HealthSource::AppleHealth, 200.0),
+            new_sample(HealthSource::GoogleFit, 300.0),
+        ]);
+        let values: Vec<_> = samples.iter().map(|sample| (sample.source, sample.value)).collect();
+        assert_eq!(
+            values,
+            [
+                (HealthSource::AppleHealth.as_str(), Some(200.0)),
+                (HealthSource::GoogleFit.as_str(), Some(300.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn overlapping_sources_and_segments_are_not_double_counted() {
+        use HealthSampleKind::{Sleep, Steps};
+        use HealthSource::{AppleHealth, GoogleFit};
+        let samples = [
+            sample(Steps, GoogleFit, "2026-10-14 01:00", "2026-10-14 02:00", Some(3000.0)),
+            sample(Steps, GoogleFit, "2026-10-14 05:00", "2026-10-14 06:00", Some(2000.0)),
+            sample(Steps, AppleHealth, "2026-10-14 01:00", "2026-10-14 06:00", Some(4500.0)),
+            // 22:00-05:00 WIB, terpotong dua segmen yang tumpang tindih
+            sample(Sleep, AppleHealth, "2026-10-13 15:00", "2026-10-13 20:00", None),
+            sample(Sleep, GoogleFit, "2026-10-13 19:00", "2026-10-13 22:00", None),
+        ];
+
+        let days = daily_health(&samples, chrono_tz::Asia::Jakarta);
+        let day = &days[&NaiveDate::from_ymd_opt(2026, 10, 14).unwrap()];
+        assert_eq!(day.steps, Some(5000.0));
+        assert_eq!(day.sleep_hours, Some(7.0));
+    }
+}
@@ -0,0 +1,282 @@
+use chrono::Utc;
+use diesel::pg::PgConnection;
+use crate::db::{help_query, user_query};
+use crate::db::pool::DbPools;
+use crate::db::transaction::run_in_transaction;
+use crate::errors::app_error::AppError;
+use crate::i18n::{t_in, Locale};
+use crate::models::audit::{AuditAction, NewAuditLog};
+use crate::models::help::{
+    CreateHelpCommentRequest, CreateHelpRequest, HelpCommentResponse, HelpRequest, HelpRequestDetail,
+    HelpRequestResponse, HelpRequestStatus, NewHelpRequest, NewHelpRequestComment, UpdateHelpRequestStatus,
+};
+use crate::models::moderation::ModeratedContent;
+use crate::models::notification::{Notification, NotificationCategory, NotificationChannel};
+use crate::models::user::UserSettings;
+use crate::service::{audit_service, moderation_service, notification_service};
+use crate::utils::email::parse_email;
+use crate::utils::mailer::Mailer;
+use crate::utils::moderation::ModerationVerdict;
+use crate::utils::text_limits::ensure_max_length;
+
+const HELP_MESSAGE_MAX_LENGTH: usize = 5000;
+const HELP_COMMENT_MAX_LENGTH: usize = 2000;
+const HELP_NAME_MAX_LENGTH: usize = 255;
+const DEFAULT_HELP_LIST_LIMIT: i64 = 50;
+const MAX_HELP_LIST_LIMIT: i64 = 200;
+
+/// Permintaan milik pengguna; milik orang lain diperlakukan seolah tidak ada
+fn require_own_request(
+    conn: &mut PgConnection,
+    user_id: i32,
+    request_id: i32,
+) -> Result<HelpRequest, AppError> {
+    help_query::find_help_request(conn, request_id)?
+        .filter(|request| request.user_id == user_id)
+        .ok_or_else(|| AppError::NotFound("Help request not found".to_string()))
+}
+
+fn comment_body(body: &str) -> Result<&str, AppError> {
+    let body = body.trim();
+    if body.is_empty() {
+        return Err(AppError::BadRequest("Comment cannot be empty".to_string()));
+    }
+    ensure_max_length("Comment", body, HELP_COMMENT_MAX_LENGTH)?;
+    Ok(body)
+}
+
+fn detail(conn: &mut PgConnection, request: HelpRequest) -> Result<HelpRequestDetail, AppError> {
+    let comments = help_query::find_comments(conn, request.id)?;
+    Ok(HelpRequestDetail {
+        request: request.into(),
+        comments: comments.into_iter().map(HelpCommentResponse::from).collect(),
+    })
+}
+
+/// Kirim permintaan bantuan; nama dan email default diambil dari akun.
+/// Permintaan yang ditahan moderasi tidak masuk antrian admin sampai disetujui.
+pub fn create_help_request(
+    pool: &DbPools,
+    user_id: i32,
+    data: CreateHelpRequest,
+    verdict: &ModerationVerdict,
+) -> Result<HelpRequestResponse, AppError> {
+    let message = data.message.trim();
+    if message.is_empty() {
+        return Err(AppError::BadRequest("Message cannot be empty".to_string()));
+    }
+    ensure_max_length("Message", message, HELP_MESSAGE_MAX_LENGTH)?;
+
+    let mut conn = pool.conn_write()?;
+
+    let user = user_query::find_user_by_id(&mut conn, user_id)?;
+    let name = data.name.as_deref().map(str::trim).filter(|name| !name.is_empty()).unwrap_or(&user.username);
+    ensure_max_length("Name", name, HELP_NAME_MAX_LENGTH)?;
+    let email = match data.email.as_deref() {
+        Some(email) => parse_email(email)?,
+        None => user.email.clone(),
+    };
+
+    run_in_transaction(&mut conn, |conn| {
+        let request = help_query::create_help_request(
+            conn,
+            &NewHelpRequest {
+                user_id,
+                name,
+                email: &email,
+                message,
+                held: verdict.is_held(),
+            },
+        )?;
+        moderation_service::record(conn, ModeratedContent::HelpRequest, request.id, Some(user_id), verdict)?;
+        Ok(request.into())
+    })
+}
+
+pub fn get_help_requests(
+    pool: &DbPools,
+    user_id: i32,
+) -> Result<Vec<HelpRequestResponse>, AppError> {
+    let mut conn = pool.conn_read()?;
+
+    let requests = help_query::find_help_requests_by_user(&mut conn, user_id)?;
+    Ok(requests.into_iter().map(HelpRequestResponse::from).collect())
+}
+
+pub fn get_help_request(
+    pool: &DbPools,
+    user_id: i32,
+    request_id: i32,
+) -> Result<HelpRequestDetail, AppError> {
+    let mut conn = pool.conn_read()?;
+
+    let request = require_own_request(&mut conn, user_id, request_id)?;
+    detail(&mut conn, request)
+}
+
+/// Balasan pengguna di percakapan permintaannya sendiri
+pub fn add_user_comment(
+    pool: &DbPools,
+    user_id: i32,
+    request_id: i32,
+    data: CreateHelpCommentRequest,
+    verdict: &ModerationVerdict,
+) -> Result<HelpCommentResponse, AppError> {
+    let body = comment_body(&data.body)?;
+
+    let mut conn = pool.conn_write()?;
+    run_in_transaction(&mut conn, |conn| {
+        let request = require_own_request(conn, user_id, request_id)?;
+        if request.status == HelpRequestStatus::Resolved.as_str() {
+            return Err(AppError::Conflict("Help request is already resolved".to_string()));
+        }
+
+        let comment = help_query::insert_comment(
+            conn,
+            &NewHelpRequestComment {
+                help_request_id: request.id,
+                author_id: Some(user_id),
+                is_staff: false,
+                body,
+                held: verdict.is_held(),
+            },
+        )?;
+        moderation_service::record(conn, ModeratedContent::HelpComment, comment.id, Some(user_id), verdict)?;
+        help_query::touch(conn, request.id, comment.created_at)?;
+        Ok(comment.into())
+    })
+}
+
+/// Antrian permintaan bantuan untuk admin
+pub fn list_help_requests(
+    pool: &DbPools,
+    status: Option<&str>,
+    limit: Option<i64>,
+) -> Result<Vec<HelpRequestResponse>, AppError> {
+    let status = status
+        .map(|value| {
+            HelpRequestStatus::parse(value)
+                .ok_or_else(|| AppError::BadRequest(format!("Unknown help request status: {}", value)))
+        })
+        .transpose()?;
+    let limit = limit.unwrap_or(DEFAULT_HELP_LIST_LIMIT);
+    if limit <= 0 || limit > MAX_HELP_LIST_LIMIT {
+        return Err(AppError::BadRequest(format!("Limit must be between 1 and {}", MAX_HELP_LIST_LIMIT)));
+    }
+
+    let mut conn = pool.conn_read()?;
+
+    let requests = help_query::find_help_requests(&mut conn, status.map(|status| status.as_str()), limit)?;
+    Ok(requests.into_iter().map(HelpRequestResponse::from).collect())
+}
+
+pub fn get_help_request_for_admin(
+    pool: &DbPools,
+    request_id: i32,
+) -> Result<HelpRequestDetail, AppError> {
+    let mut conn = pool.conn_read()?;
+
+    let request = help_query::find_help_request(&mut conn, request_id)?
+        .ok_or_else(|| AppError::NotFound("Help request not found".to_string()))?;
+    detail(&mut conn, request)
+}
+
+/// Majukan status permintaan (open → in_progress → resolved), opsional dengan komentar.
+/// Notifikasi ke pengguna masuk antrian push di transaksi yang sama dengan perubahan status.
+pub fn update_help_request_status(
+    pool: &DbPools,
+    mailer: &dyn Mailer,
+    admin_id: i32,
+    request_id: i32,
+    data: UpdateHelpRequestStatus,
+    ip_address: Option<&str>,
+) -> Result<HelpRequestDetail, AppError> {
+    let status = HelpRequestStatus::parse(&data.status)
+        .ok_or_else(|| AppError::BadRequest(format!("Unknown help request status: {}", data.status)))?;
+    let comment = data.comment.as_deref().map(comment_body).transpose()?;
+
+    let mut conn = pool.conn_write()?;
+    run_in_transaction(&mut conn, |conn| {
+        let request = help_query::find_help_request_for_update(conn, request_id)?
+            .ok_or_else(|| AppError::NotFound("Help request not found".to_string()))?;
+        let current = HelpRequestStatus::parse(&request.status).unwrap_or(HelpRequestStatus::Open);
+        if current.next() != Some(status) {
+            return Err(AppError::Conflict(format!(
+                "Cannot change help request status from {} to {}",
+                current.as_str(),
+                status.as_str()
+            )));
+        }
+
+        let now = Utc::now().naive_utc();
+        let request = help_query::update_status(conn, request.id, status.as_str(), now)?;
+        if let Some(body) = comment {
+            help_query::insert_comment(
+                conn,
+                &NewHelpRequestComment {
+                    help_request_id: request.id,
+                    author_id: Some(admin_id),
+                    is_staff: true,
+                    body,
+                    held: false,
+                },
+            )?;
+        }
+
+        audit_service::record(
+            conn,
+            NewAuditLog::new(AuditAction::AdminAction, Some(admin_id), Some(request.user_id), ip_address)
+                .with_details(format!("help_request {}: {} -> {}", request.id, current.as_str(), status.as_str())),
+        )?;
+
+        let user = user_query::find_user_by_id(conn, request.user_id)?;
+        let locale = UserSettings::parse(user.settings.as_deref())
+            .language
+            .as_deref()
+            .and_then(Locale::parse)
+            .unwrap_or_default();
+        notification_service::dispatch(
+            conn,
+            mailer,
+            &user,
+            &Notification {
+                category: NotificationCategory::HelpRequest,
+                channels: &[NotificationChannel::Push],
+                title: t_in(locale, "help.push.title"),
+                body: t_in(locale, &format!("help.push.{}", status.as_str())),
+                email_body: None,
+            },
+        )?;
+
+        detail(conn, request)
+    })
+}
+
+/// Balasan admin tanpa mengubah status
+pub fn add_staff_comment(
+    pool: &DbPools,
+    admin_id: i32,
+    request_id: i32,
+    data: CreateHelpCommentRequest,
+) -> Result<HelpCommentResponse, AppError> {
+    let body = comment_body(&data.body)?;
+
+    let mut conn = pool.conn_write()?;
+    run_in_transaction(&mut conn, |conn| {
+        let request = help_query::find_help_request(conn, request_id)?
+            .ok_or_else(|| AppError::NotFound("Help request not found".to_string()))?;
+
+        let comment = help_query::insert_comment(
+            conn,
+            &NewHelpRequestComment {
+                help_request_id: request.id,
+                author_id: Some(admin_id),
+                is_staff: true,
+                body,
+                held: false,
+            },
+        )?;
+        help_query::touch(conn, request.id, comment.created_at)?;
+        Ok(comment.into())
+    })
+}
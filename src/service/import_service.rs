@@ -0,0 +1,166 @@
+use std::collections::{HashMap, HashSet};
+use chrono::Utc;
+use crate::config::app_config::app_config;
+use crate::db::mood_query;
+use crate::db::pool::DbPools;
+use crate::db::transaction::run_in_transaction;
+use crate::errors::app_error::AppError;
+use crate::models::import::{ImportSummary, ImportedMood, SkippedImportRow};
+use crate::models::mood::{MoodDetails, NewMood};
+use crate::models::onboarding::OnboardingStep;
+use crate::models::quota::QuotaOperation;
+use crate::service::{onboarding_service, quota_service};
+use crate::utils::daylio::{parse_daylio_csv, DaylioEntry};
+use crate::utils::stats_cache::StatsCache;
+
+/// Import mood dari CSV export Daylio. MindMate hanya menyimpan satu mood per hari, jadi
+/// untuk tanggal dengan beberapa entri dipakai entri terakhir; tanggal yang sudah punya
+/// mood dilewati. Dengan `dry_run` hasilnya dihitung tanpa menyimpan apa pun.
+pub fn import_daylio(
+    pool: &DbPools,
+    cache: &StatsCache,
+    user_id: i32,
+    data: &[u8],
+    dry_run: bool,
+) -> Result<ImportSummary, AppError> {
+    quota_service::consume(pool, user_id, QuotaOperation::Import)?;
+    run_daylio_import(pool, cache, user_id, data, dry_run)
+}
+
+/// Import Daylio tanpa memakai kuota; dipanggil worker untuk job yang kuotanya sudah dipakai saat diantrikan
+pub fn run_daylio_import(
+    pool: &DbPools,
+    cache: &StatsCache,
+    user_id: i32,
+    data: &[u8],
+    dry_run: bool,
+) -> Result<ImportSummary, AppError> {
+    let rows = parse_daylio_csv(data)?;
+    let total_rows = rows.len();
+
+    let mut skipped = Vec::new();
+    let mut latest: HashMap<_, DaylioEntry> = HashMap::new();
+    for row in rows {
+        let entry = match row {
+            Ok(entry) => entry,
+            Err(error) => {
+                skipped.push(SkippedImportRow {
+                    line: error.line,
+                    date: error.date,
+                    reason: error.reason,
+                });
+                continue;
+            }
+        };
+
+        if let Some(ref notes) = entry.notes {
+            let max = app_config().mood_notes_max_length;
+            if notes.chars().count() > max {
+                skipped.push(SkippedImportRow {
+                    line: entry.line,
+                    date: Some(entry.date),
+                    reason: format!("Notes must be at most {} characters", max),
+                });
+                continue;
+            }
+        }
+
+        // Export Daylio terurut dari yang terbaru; bandingkan jam agar tidak bergantung urutan
+        match latest.remove(&entry.date) {
+            Some(previous) if previous.time >= entry.time => {
+                skipped.push(superseded(&entry));
+                latest.insert(previous.date, previous);
+            }
+            Some(previous) => {
+                skipped.push(superseded(&previous));
+                latest.insert(entry.date, entry);
+            }
+            None => {
+                latest.insert(entry.date, entry);
+            }
+        }
+    }
+
+    let mut entries: Vec<DaylioEntry> = latest.into_values().collect();
+    entries.sort_by_key(|entry| entry.date);
+
+    let mut conn = pool.conn_write()?;
+    let created = run_in_transaction(&mut conn, |conn| {
+        let existing: HashSet<_> = match (entries.first(), entries.last()) {
+            (Some(first), Some(last)) => mood_query::find_mood_dates_in_range(conn, user_id, first.date, last.date)?
+                .into_iter()
+                .collect(),
+            _ => HashSet::new(),
+        };
+
+        let mut created = Vec::new();
+        for entry in &entries {
+            if existing.contains(&entry.date) {
+                skipped.push(SkippedImportRow {
+                    line: entry.line,
+                    date: Some(entry.date),
+                    reason: "Mood already exists for this date".to_string(),
+                });
+                continue;
+            }
+
+            let mood = ImportedMood {
+                line: entry.line,
+                date: entry.date,
+                mood: entry.mood.as_str().to_string(),
+                emoji: entry.mood.default_emoji().to_string(),
+                notes: entry.notes.clone(),
+                tags: entry.tags.clone(),
+            };
+            if !dry_run {
+                let details = MoodDetails {
+                    tags: Some(mood.tags.clone()),
+                    ..Default::default()
+                };
+                let now = Utc::now().naive_utc();
+                mood_query::create_mood(
+                    conn,
+                    &NewMood {
+                        user_id,
+                        date: mood.date,
+                        mood: mood.mood.clone(),
+                        emoji: mood.emoji.clone(),
+                        notes: mood.notes.clone(),
+                        created_at: now,
+                        updated_at: Some(now),
+                        details: details.to_json(),
+                        place_label: None,
+                        latitude: None,
+                        longitude: None,
+                    },
+                )?;
+            }
+            created.push(mood);
+        }
+
+        if !dry_run && !created.is_empty() {
+            onboarding_service::complete_step(conn, user_id, OnboardingStep::FirstMood)?;
+        }
+        Ok(created)
+    })?;
+
+    if !dry_run && !created.is_empty() {
+        cache.invalidate_user(user_id);
+    }
+
+    skipped.sort_by_key(|row| row.line);
+    Ok(ImportSummary {
+        dry_run,
+        total_rows,
+        created,
+        skipped,
+    })
+}
+
+fn superseded(entry: &DaylioEntry) -> SkippedImportRow {
+    SkippedImportRow {
+        line: entry.line,
+        date: Some(entry.date),
+        reason: "A later entry exists for this date".to_string(),
+    }
+}
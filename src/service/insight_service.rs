@@ -0,0 +1,815 @@
+use crate::analytics::forecast;
+use crate::analytics::trend::{self, Alignment, Grouping};
+use crate::config::app_config::{app_config, AppConfig};
+use crate::db::{insight_query, mood_query, user_query};
+use crate::db::pool::DbPools;
+use crate::errors::app_error::AppError;
+use crate::i18n::{current_locale, t, t_in, Locale};
+use crate::models::insight::{
+    AlertKind, DayOfWeekInsight, ForecastPoint, ImprovedDay, InsightSnapshot, MoodAlert, MoodCountChange, MoodForecast,
+    MoodTrend, NewInsightNotification, PlaceAverage, PlacesInsight, SmoothedPoint, TrendComparison, TrendPoint, TrendQuery, WeekOverWeekInsight, WeekSummary, WeekdayAverage,
+};
+use crate::models::mood::{Mood, MoodType};
+use crate::models::user::UserSettings;
+use crate::repo::MoodRepo;
+use crate::service::report_service::mood_distribution;
+use crate::models::notification::{Notification, NotificationCategory, NotificationChannel};
+use crate::service::{mood_service, notification_service};
+use crate::utils::mailer::Mailer;
+use crate::utils::mood_interpretation::interpret_average_score;
+use crate::utils::stats_cache::{StatsCache, StatsKind};
+use crate::utils::timezone::{parse_timezone, today_in};
+use chrono::{Datelike, Duration, NaiveDate, Utc, Weekday};
+use chrono_tz::Tz;
+use diesel::pg::PgConnection;
+
+/// Jumlah catatan minimum agar perbandingan dengan baseline bermakna
+const MIN_RECENT_ENTRIES: usize = 3;
+const MIN_BASELINE_ENTRIES: usize = 7;
+
+/// Riwayat yang dipakai model forecast (8 minggu agar pola per hari punya cukup data)
+const FORECAST_HISTORY_DAYS: i64 = 56;
+const FORECAST_HORIZON_DAYS: usize = 7;
+
+/// Rentang default dan maksimum grafik tren
+const DEFAULT_TREND_DAYS: i64 = 30;
+const MAX_TREND_DAYS: i64 = 3 * 366;
+/// Batas lebar jendela rolling average (hari)
+const MIN_SMOOTHING_DAYS: u32 = 2;
+const MAX_SMOOTHING_DAYS: u32 = 31;
+
+/// Selisih rata-rata mingguan yang masih dianggap stabil
+const STABLE_WEEK_DELTA: f64 = 0.25;
+
+/// Aturan deteksi penurunan mood
+#[derive(Debug, Clone)]
+pub struct AlertRules {
+    /// Skor harian di bawah nilai ini dianggap rendah
+    pub low_threshold: f64,
+    pub low_streak_days: i64,
+    pub recent_days: i64,
+    pub baseline_days: i64,
+    pub drop_threshold: f64,
+}
+
+impl AlertRules {
+    pub fn from_config(config: &AppConfig) -> Self {
+        AlertRules {
+            low_threshold: config.mood_score_low_threshold,
+            low_streak_days: config.insight_low_streak_days.max(1),
+            recent_days: config.insight_recent_days.max(1),
+            baseline_days: config.insight_baseline_days.max(1),
+            drop_threshold: config.insight_drop_threshold,
+        }
+    }
+
+    /// Rentang hari yang perlu dibaca untuk mendeteksi semua alert
+    pub fn lookback_days(&self) -> i64 {
+        std::cmp::max(self.recent_days + self.baseline_days, self.low_streak_days)
+    }
+}
+
+/// Alert penurunan mood pengguna per hari ini (zona waktu pengguna)
+pub fn get_alerts(
+    pool: &DbPools,
+    user_id: i32,
+    tz: Tz,
+) -> Result<Vec<MoodAlert>, AppError> {
+    let mut conn = pool.conn_read()?;
+
+    let rules = AlertRules::from_config(app_config());
+    let today = today_in(tz);
+    let moods = mood_query::find_moods_by_date_range(
+        &mut conn,
+        user_id,
+        today - Duration::days(rules.lookback_days()),
+        today,
+    )?;
+
+    Ok(detect_alerts(&daily_scores(&moods), today, &rules))
+}
+
+/// Kirim email untuk alert baru ke pengguna yang mengaktifkan `insight_notifications`.
+/// Setiap alert (jenis + tanggal mulai) hanya dikirim sekali.
+pub fn notify_alerts(
+    pool: &DbPools,
+    mailer: &dyn Mailer,
+) -> Result<usize, AppError> {
+    let mut conn = pool.conn_write()?;
+
+    let rules = AlertRules::from_config(app_config());
+    // Batas bawah dilebarkan satu hari untuk zona waktu yang sudah/belum berganti hari
+    let since = today_in(Tz::UTC) - Duration::days(rules.lookback_days() + 1);
+    let user_ids = mood_query::find_user_ids_with_moods_since(&mut conn, since)?;
+
+    let mut sent = 0;
+    for user_id in user_ids {
+        let user = user_query::find_user_by_id(&mut conn, user_id)?;
+        let settings = UserSettings::parse(user.settings.as_deref());
+        if !notification_service::wants(&settings, NotificationCategory::InsightAlert) {
+            continue;
+        }
+
+        let tz = settings.timezone.as_deref().and_then(parse_timezone).unwrap_or(Tz::UTC);
+        let locale = settings.language.as_deref().and_then(Locale::parse).unwrap_or_default();
+        let today = today_in(tz);
+        let moods = mood_query::find_moods_by_date_range(
+            &mut conn,
+            user_id,
+            today - Duration::days(rules.lookback_days()),
+            today,
+        )?;
+
+        for alert in detect_alerts(&daily_scores(&moods), today, &rules) {
+            let notification = NewInsightNotification {
+                user_id,
+                kind: &alert.kind,
+                start_date: alert.start_date,
+            };
+            if !insight_query::record_notification(&mut conn, &notification)? {
+                continue;
+            }
+
+            let message = t_in(locale, &format!("insight.alert.{}", alert.kind));
+            let result = notification_service::dispatch(
+                &mut conn,
+                mailer,
+                &user,
+                &Notification {
+                    category: NotificationCategory::InsightAlert,
+                    channels: &NotificationChannel::ALL,
+                    title: t_in(locale, "insight.email.subject"),
+                    email_body: Some(format!("{}\n\n{}", message, t_in(locale, "insight.email.footer"))),
+                    body: message,
+                },
+            )?;
+            if result.delivered() {
+                sent += 1;
+            }
+        }
+    }
+
+    Ok(sent)
+}
+
+/// Perbandingan minggu ini (Senin sampai hari ini) dengan minggu lalu (Senin-Minggu)
+pub fn get_week_over_week(
+    pool: &DbPools,
+    user_id: i32,
+    tz: Tz,
+) -> Result<WeekOverWeekInsight, AppError> {
+    let mut conn = pool.conn_read()?;
+
+    let today = today_in(tz);
+    let this_week_start = today - Duration::days(today.weekday().num_days_from_monday() as i64);
+    let last_week_start = this_week_start - Duration::days(7);
+    let last_week_end = this_week_start - Duration::days(1);
+
+    let moods = mood_query::find_moods_by_date_range(&mut conn, user_id, last_week_start, today)?;
+    let (this_week_moods, last_week_moods): (Vec<Mood>, Vec<Mood>) =
+        moods.into_iter().partition(|mood| mood.date >= this_week_start);
+
+    let this_week = week_summary(&this_week_moods, this_week_start, today);
+    let last_week = week_summary(&last_week_moods, last_week_start, last_week_end);
+
+    let average_delta = match (this_week.average_score, last_week.average_score) {
+        (Some(this_avg), Some(last_avg)) => Some(this_avg - last_avg),
+        _ => None,
+    };
+    let summary_key = match average_delta {
+        None => "insight.week.not_enough_data",
+        Some(delta) if delta >= STABLE_WEEK_DELTA => "insight.week.improved",
+        Some(delta) if delta <= -STABLE_WEEK_DELTA => "insight.week.declined",
+        Some(_) => "insight.week.stable",
+    };
+
+    let distribution_changes = this_week
+        .mood_distribution
+        .iter()
+        .zip(&last_week.mood_distribution)
+        .map(|(this_count, last_count)| MoodCountChange {
+            mood: this_count.mood.clone(),
+            this_week: this_count.count,
+            last_week: last_count.count,
+            change: this_count.count - last_count.count,
+        })
+        .collect();
+
+    Ok(WeekOverWeekInsight {
+        most_improved_day: most_improved_day(&daily_scores(&this_week_moods), &daily_scores(&last_week_moods)),
+        this_week,
+        last_week,
+        average_delta,
+        distribution_changes,
+        summary_key: summary_key.to_string(),
+        summary: t(summary_key),
+    })
+}
+
+/// Ringkasan satu minggu (atau rentang tanggal lain) untuk pengguna, misalnya untuk webhook mingguan
+pub fn summarize_range(
+    conn: &mut PgConnection,
+    user_id: i32,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+) -> Result<WeekSummary, AppError> {
+    let moods = mood_query::find_moods_by_date_range(conn, user_id, start_date, end_date)?;
+    Ok(week_summary(&moods, start_date, end_date))
+}
+
+fn week_summary(moods: &[Mood], start_date: NaiveDate, end_date: NaiveDate) -> WeekSummary {
+    let scores: Vec<i32> = daily_scores(moods).into_iter().map(|(_, score)| score).collect();
+    WeekSummary {
+        start_date,
+        end_date,
+        entries: scores.len() as i64,
+        average_score: (!scores.is_empty()).then(|| mean(&scores)),
+        mood_distribution: mood_distribution(moods),
+    }
+}
+
+/// Hari dengan kenaikan terbesar; hanya hari yang tercatat di kedua minggu yang dibandingkan
+fn most_improved_day(this_week: &[(NaiveDate, i32)], last_week: &[(NaiveDate, i32)]) -> Option<ImprovedDay> {
+    this_week
+        .iter()
+        .filter_map(|&(date, score)| {
+            let &(_, last_week_score) = last_week.iter().find(|(last_date, _)| last_date.weekday() == date.weekday())?;
+            Some(ImprovedDay {
+                weekday: weekday_name(date.weekday()).to_string(),
+                date,
+                score,
+                last_week_score,
+                delta: score - last_week_score,
+            })
+        })
+        .filter(|day| day.delta > 0)
+        .fold(None, |best: Option<ImprovedDay>, day| match best {
+            Some(b) if b.delta >= day.delta => Some(b),
+            _ => Some(day),
+        })
+}
+
+/// Rata-rata skor per hari dalam minggu untuk `days` hari terakhir (default 90)
+pub fn get_day_of_week(
+    pool: &DbPools,
+    user_id: i32,
+    days: Option<i32>,
+    tz: Tz,
+) -> Result<DayOfWeekInsight, AppError> {
+    let days = days.unwrap_or(90);
+    if days <= 0 || days > 365 {
+        return Err(AppError::BadRequest("Days must be between 1 and 365".to_string()));
+    }
+
+    let mut conn = pool.conn_read()?;
+
+    let end_date = today_in(tz);
+    let start_date = end_date - Duration::days(days as i64 - 1);
+    let rows = mood_query::average_score_by_weekday(&mut conn, user_id, start_date, end_date)?;
+
+    let weekdays: Vec<WeekdayAverage> = [
+        Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Weekday::Fri, Weekday::Sat, Weekday::Sun,
+    ]
+    .iter()
+    .map(|weekday| {
+        let dow = weekday.num_days_from_sunday() as i32;
+        let (entries, average_score) = rows
+            .iter()
+            .find(|(row_dow, _, _)| *row_dow == dow)
+            .map(|(_, entries, average)| (*entries, *average))
+            .unwrap_or((0, None));
+        WeekdayAverage {
+            weekday: weekday_name(*weekday).to_string(),
+            entries,
+            average_score,
+        }
+    })
+    .collect();
+
+    let scored = || weekdays.iter().filter_map(|w| w.average_score.map(|avg| (w, avg)));
+    let best_weekday = scored()
+        .fold(None, |best: Option<(&WeekdayAverage, f64)>, (w, avg)| match best {
+            Some((_, best_avg)) if best_avg >= avg => best,
+            _ => Some((w, avg)),
+        })
+        .map(|(w, _)| w.weekday.clone());
+    let worst_weekday = scored()
+        .fold(None, |worst: Option<(&WeekdayAverage, f64)>, (w, avg)| match worst {
+            Some((_, worst_avg)) if worst_avg <= avg => worst,
+            _ => Some((w, avg)),
+        })
+        .map(|(w, _)| w.weekday.clone());
+
+    Ok(DayOfWeekInsight {
+        start_date,
+        end_date,
+        weekdays,
+        best_weekday,
+        worst_weekday,
+    })
+}
+
+/// Rata-rata skor per tempat untuk `days` hari terakhir (default 90)
+pub fn get_places(
+    pool: &DbPools,
+    user_id: i32,
+    days: Option<i32>,
+    tz: Tz,
+) -> Result<PlacesInsight, AppError> {
+    let days = days.unwrap_or(90);
+    if days <= 0 || days > 365 {
+        return Err(AppError::BadRequest("Days must be between 1 and 365".to_string()));
+    }
+
+    let mut conn = pool.conn_read()?;
+
+    let end_date = today_in(tz);
+    let start_date = end_date - Duration::days(days as i64 - 1);
+    let rows = mood_query::average_score_by_place(&mut conn, user_id, start_date, end_date)?;
+
+    let (total, entries) = rows
+        .iter()
+        .filter_map(|(_, _, _, entries, average)| Some(((*average)? * *entries as f64, *entries)))
+        .fold((0.0, 0), |(total, count), (sum, entries)| (total + sum, count + entries));
+    let average_score = (entries > 0).then(|| total / entries as f64);
+
+    let mut places: Vec<PlaceAverage> = rows
+        .into_iter()
+        .map(|(label, latitude, longitude, entries, average)| PlaceAverage {
+            label,
+            latitude,
+            longitude,
+            entries,
+            average_score: average,
+            difference_from_average: average.zip(average_score).map(|(place, overall)| place - overall),
+        })
+        .collect();
+    places.sort_by(|a, b| b.entries.cmp(&a.entries).then_with(|| a.label.cmp(&b.label)));
+
+    Ok(PlacesInsight {
+        start_date,
+        end_date,
+        average_score,
+        places,
+    })
+}
+
+/// Prediksi skor mood 7 hari ke depan dari tren 8 minggu terakhir
+pub fn get_forecast(
+    pool: &DbPools,
+    user_id: i32,
+    seasonal: bool,
+    tz: Tz,
+) -> Result<MoodForecast, AppError> {
+    let mut conn = pool.conn_read()?;
+
+    let today = today_in(tz);
+    let moods = mood_query::find_moods_by_date_range(
+        &mut conn,
+        user_id,
+        today - Duration::days(FORECAST_HISTORY_DAYS - 1),
+        today,
+    )?;
+    let history: Vec<(NaiveDate, f64)> = daily_scores(&moods)
+        .into_iter()
+        .map(|(date, score)| (date, f64::from(score)))
+        .collect();
+
+    let result = forecast::forecast(&history, today, FORECAST_HORIZON_DAYS, seasonal);
+    let method = match &result {
+        None => "not_enough_data",
+        Some(result) if result.seasonal => "linear_trend_weekday",
+        Some(_) => "linear_trend",
+    };
+
+    Ok(MoodForecast {
+        method: method.to_string(),
+        data_points: history.len() as i64,
+        trend_per_day: result.as_ref().map(|result| result.slope_per_day),
+        forecast: result
+            .map(|result| result.predictions)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|prediction| ForecastPoint {
+                date: prediction.date,
+                predicted_score: prediction.value,
+                lower: prediction.lower,
+                upper: prediction.upper,
+            })
+            .collect(),
+    })
+}
+
+/// Nama hari dalam bahasa Inggris huruf kecil, stabil untuk dipakai frontend
+pub fn weekday_name(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "monday",
+        Weekday::Tue => "tuesday",
+        Weekday::Wed => "wednesday",
+        Weekday::Thu => "thursday",
+        Weekday::Fri => "friday",
+        Weekday::Sat => "saturday",
+        Weekday::Sun => "sunday",
+    }
+}
+
+/// Rata-rata skor per hari/minggu/bulan/kuartal untuk grafik tren, opsional dengan rolling
+/// average `smoothing` hari (hanya untuk grouping harian). Data di luar rentang ikut dibaca
+/// agar titik di tepi rentang dihaluskan dengan jendela penuh.
+pub fn get_mood_trend(
+    pool: &DbPools,
+    user_id: i32,
+    query: &TrendQuery,
+    tz: Tz,
+) -> Result<MoodTrend, AppError> {
+    let smoothing = query.smoothing;
+    let end_date = query.end_date.unwrap_or_else(|| today_in(tz));
+    let start_date = query.start_date.unwrap_or(end_date - Duration::days(DEFAULT_TREND_DAYS - 1));
+    if start_date > end_date {
+        return Err(AppError::BadRequest("Start date cannot be after end date".to_string()));
+    }
+    if (end_date - start_date).num_days() >= MAX_TREND_DAYS {
+        return Err(AppError::BadRequest(format!("Date range must be at most {} days", MAX_TREND_DAYS)));
+    }
+    if let Some(window) = smoothing {
+        if !(MIN_SMOOTHING_DAYS..=MAX_SMOOTHING_DAYS).contains(&window) {
+            return Err(AppError::BadRequest(format!(
+                "Smoothing must be between {} and {} days",
+                MIN_SMOOTHING_DAYS, MAX_SMOOTHING_DAYS
+            )));
+        }
+    }
+    let alignment = match query.align.as_deref() {
+        Some(value) => Alignment::parse(value)
+            .ok_or_else(|| AppError::BadRequest(format!("Unknown smoothing alignment: {}", value)))?,
+        None => Alignment::Right,
+    };
+    let grouping = match query.group.as_deref() {
+        Some(value) => Grouping::parse(value)
+            .ok_or_else(|| AppError::BadRequest(format!("Unknown trend grouping: {}", value)))?,
+        None => Grouping::Day,
+    };
+    if smoothing.is_some() && grouping != Grouping::Day {
+        return Err(AppError::BadRequest("Smoothing is only available for daily grouping".to_string()));
+    }
+
+    let compare_previous = match query.compare.as_deref() {
+        Some("previous") => true,
+        Some(value) => return Err(AppError::BadRequest(format!("Unknown trend comparison: {}", value))),
+        None => false,
+    };
+
+    let mut conn = pool.conn_read()?;
+
+    // Rentang sebelumnya sama panjang dan berakhir tepat sehari sebelum start_date
+    let length = end_date - start_date + Duration::days(1);
+    let previous_start = start_date - length;
+    let (before, after) = smoothing.map_or((0, 0), |window| alignment.span(window));
+    let moods = mood_query::find_moods_by_date_range(
+        &mut conn,
+        user_id,
+        if compare_previous { previous_start } else { start_date } - Duration::days(before),
+        end_date + Duration::days(after),
+    )?;
+    let mut scores: Vec<(NaiveDate, f64)> = daily_scores(&moods)
+        .into_iter()
+        .map(|(date, score)| (date, f64::from(score)))
+        .collect();
+    scores.sort_by_key(|(date, _)| *date);
+    let smoothed = match smoothing {
+        Some(window) => trend::rolling_average(&scores, window, alignment),
+        None => Vec::new(),
+    };
+
+    let current = TrendSeries::collect(&scores, &smoothed, grouping, start_date, end_date);
+    let comparison = compare_previous.then(|| {
+        let previous = TrendSeries::collect(&scores, &smoothed, grouping, previous_start, start_date - Duration::days(1));
+        TrendComparison {
+            previous_start_date: previous_start,
+            previous_end_date: start_date - Duration::days(1),
+            current_average: current.average,
+            previous_average: previous.average,
+            average_delta: current.average.zip(previous.average).map(|(current, previous)| current - previous),
+            current_entries: current.entries,
+            previous_entries: previous.entries,
+            previous_points: previous.points,
+            previous_smoothed: previous.smoothed,
+        }
+    });
+
+    Ok(MoodTrend {
+        start_date,
+        end_date,
+        group: grouping.as_str().to_string(),
+        smoothing,
+        alignment: smoothing.map(|_| alignment.as_str().to_string()),
+        points: current.points,
+        smoothed: current.smoothed,
+        comparison,
+    })
+}
+
+/// Titik tren satu rentang tanggal, dihitung dari skor harian yang sudah terurut
+struct TrendSeries {
+    points: Vec<TrendPoint>,
+    smoothed: Vec<SmoothedPoint>,
+    average: Option<f64>,
+    entries: i64,
+}
+
+impl TrendSeries {
+    fn collect(
+        scores: &[(NaiveDate, f64)],
+        smoothed: &[(NaiveDate, f64)],
+        grouping: Grouping,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Self {
+        let in_range = |(date, _): &&(NaiveDate, f64)| *date >= start_date && *date <= end_date;
+        let window: Vec<(NaiveDate, f64)> = scores.iter().filter(in_range).copied().collect();
+        let entries = window.len() as i64;
+
+        TrendSeries {
+            points: trend::group_averages(&window, grouping)
+                .into_iter()
+                .map(|period| TrendPoint {
+                    period_start: period.start,
+                    period_end: period.end,
+                    score: period.average,
+                    entries: period.entries as i64,
+                    offset_days: (period.start - start_date).num_days(),
+                })
+                .collect(),
+            smoothed: smoothed
+                .iter()
+                .filter(in_range)
+                .map(|(date, score)| SmoothedPoint {
+                    date: *date,
+                    score: *score,
+                    offset_days: (*date - start_date).num_days(),
+                })
+                .collect(),
+            average: (entries > 0).then(|| window.iter().map(|(_, score)| score).sum::<f64>() / entries as f64),
+            entries,
+        }
+    }
+}
+
+/// Ringkasan insight (rata-rata, distribusi, streak, pola harian, alert) dalam satu dokumen.
+/// Tidak di-invalidate saat data berubah; cache menghitung ulang paling sering sekali per jam.
+pub fn get_snapshot(
+    pool: &DbPools,
+    moods: &dyn MoodRepo,
+    cache: &StatsCache,
+    user_id: i32,
+    tz: Tz,
+) -> Result<serde_json::Value, AppError> {
+    cache.get_or_compute(user_id, StatsKind::InsightSnapshot(current_locale(), tz), None, || {
+        let mood_counts = moods.count_by_type(user_id, None)?;
+        let average_score = moods.average_score(user_id, None)?;
+        let streaks = mood_service::get_mood_streak(moods, user_id, tz)?;
+
+        let snapshot = InsightSnapshot {
+            generated_at: Utc::now().naive_utc(),
+            total_entries: mood_counts.iter().map(|(_, count)| count).sum(),
+            average_score,
+            interpretation: interpret_average_score(average_score, app_config()),
+            mood_distribution: mood_service::distribution_from_counts(&mood_counts),
+            current_streak: streaks.current_length(),
+            longest_streak: streaks.longest_length(),
+            day_of_week: get_day_of_week(pool, user_id, None, tz)?,
+            alerts: get_alerts(pool, user_id, tz)?,
+        };
+        serde_json::to_value(snapshot)
+            .map_err(|e| AppError::InternalServerError(format!("Failed to serialize insight snapshot: {}", e)))
+    })
+}
+
+fn daily_scores(moods: &[Mood]) -> Vec<(NaiveDate, i32)> {
+    moods
+        .iter()
+        .filter_map(|mood| Some((mood.date, mood.mood.parse::<MoodType>().ok()?.score())))
+        .collect()
+}
+
+/// Deteksi alert dari skor harian (tanggal, skor 1-5). Urutan input tidak harus terurut.
+pub fn detect_alerts(scores: &[(NaiveDate, i32)], today: NaiveDate, rules: &AlertRules) -> Vec<MoodAlert> {
+    let mut scores: Vec<(NaiveDate, i32)> = scores.iter().copied().filter(|(date, _)| *date <= today).collect();
+    scores.sort_by_key(|(date, _)| *date);
+    scores.dedup_by_key(|(date, _)| *date);
+
+    sustained_low(&scores, today, rules)
+        .into_iter()
+        .chain(sharp_drop(&scores, today, rules))
+        .collect()
+}
+
+/// Hari-hari berturut-turut (tanpa hari kosong) dengan skor rendah yang masih berlangsung,
+/// yaitu catatan terakhirnya hari ini atau kemarin
+fn sustained_low(scores: &[(NaiveDate, i32)], today: NaiveDate, rules: &AlertRules) -> Option<MoodAlert> {
+    let &(end_date, _) = scores.last()?;
+    if end_date < today - Duration::days(1) {
+        return None;
+    }
+
+    let mut run: Vec<i32> = Vec::new();
+    let mut start_date = end_date;
+    for &(date, score) in scores.iter().rev() {
+        let consecutive = run.is_empty() || date.succ_opt() == Some(start_date);
+        if !consecutive || f64::from(score) >= rules.low_threshold {
+            break;
+        }
+        run.push(score);
+        start_date = date;
+    }
+
+    if (run.len() as i64) < rules.low_streak_days {
+        return None;
+    }
+
+    Some(MoodAlert {
+        kind: AlertKind::SustainedLow.as_str().to_string(),
+        message: t("insight.alert.sustained_low"),
+        start_date,
+        end_date,
+        entries: run.len() as i64,
+        recent_average: mean(&run),
+        baseline_average: None,
+    })
+}
+
+/// Rata-rata periode terbaru dibandingkan periode baseline tepat sebelumnya
+fn sharp_drop(scores: &[(NaiveDate, i32)], today: NaiveDate, rules: &AlertRules) -> Option<MoodAlert> {
+    let recent_start = today - Duration::days(rules.recent_days - 1);
+    let baseline_start = recent_start - Duration::days(rules.baseline_days);
+
+    let recent: Vec<&(NaiveDate, i32)> = scores.iter().filter(|(date, _)| *date >= recent_start).collect();
+    let baseline: Vec<i32> = scores
+        .iter()
+        .filter(|(date, _)| *date >= baseline_start && *date < recent_start)
+        .map(|(_, score)| *score)
+        .collect();
+
+    let min_recent = MIN_RECENT_ENTRIES.min(rules.recent_days as usize);
+    if recent.len() < min_recent || baseline.len() < MIN_BASELINE_ENTRIES {
+        return None;
+    }
+
+    let recent_scores: Vec<i32> = recent.iter().map(|(_, score)| *score).collect();
+    let recent_average = mean(&recent_scores);
+    let baseline_average = mean(&baseline);
+    if baseline_average - recent_average < rules.drop_threshold {
+        return None;
+    }
+
+    Some(MoodAlert {
+        kind: AlertKind::SharpDrop.as_str().to_string(),
+        message: t("insight.alert.sharp_drop"),
+        start_date: recent.first()?.0,
+        end_date: recent.last()?.0,
+        entries: recent.len() as i64,
+        recent_average,
+        baseline_average: Some(baseline_average),
+    })
+}
+
+fn mean(scores: &[i32]) -> f64 {
+    if scores.is_empty() {
+        return 0.0;
+    }
+    scores.iter().map(|score| f64::from(*score)).sum::<f64>() / scores.len() as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules() -> AlertRules {
+        AlertRules {
+            low_threshold: 2.5,
+            low_streak_days: 5,
+            recent_days: 7,
+            baseline_days: 28,
+            drop_threshold: 1.0,
+        }
+    }
+
+    fn day(offset: i64) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 3, 31).unwrap() + Duration::days(offset)
+    }
+
+    /// Skor untuk hari-hari berturut-turut yang berakhir pada `day(end_offset)`
+    fn series(end_offset: i64, scores: &[i32]) -> Vec<(NaiveDate, i32)> {
+        let start = end_offset - scores.len() as i64 + 1;
+        scores.iter().enumerate().map(|(i, score)| (day(start + i as i64), *score)).collect()
+    }
+
+    fn kinds(alerts: &[MoodAlert]) -> Vec<&str> {
+        alerts.iter().map(|alert| alert.kind.as_str()).collect()
+    }
+
+    #[test]
+    fn no_data_has_no_alerts() {
+        assert!(detect_alerts(&[], day(0), &rules()).is_empty());
+    }
+
+    #[test]
+    fn five_low_days_trigger_sustained_low() {
+        let alerts = detect_alerts(&series(0, &[2, 1, 2, 2, 1]), day(0), &rules());
+
+        assert_eq!(kinds(&alerts), vec!["sustained_low"]);
+        assert_eq!(alerts[0].start_date, day(-4));
+        assert_eq!(alerts[0].end_date, day(0));
+        assert_eq!(alerts[0].entries, 5);
+        assert!((alerts[0].recent_average - 1.6).abs() < 1e-9);
+    }
+
+    #[test]
+    fn four_low_days_are_not_enough() {
+        let alerts = detect_alerts(&series(0, &[4, 2, 1, 2, 2]), day(0), &rules());
+        assert!(alerts.is_empty());
+    }
+
+    #[test]
+    fn missing_day_breaks_the_streak() {
+        let mut scores = series(-4, &[1, 1, 1]);
+        scores.extend(series(0, &[1, 1, 1]));
+        assert!(detect_alerts(&scores, day(0), &rules()).is_empty());
+    }
+
+    #[test]
+    fn score_at_threshold_is_not_low() {
+        let rules = AlertRules { low_threshold: 2.0, ..rules() };
+        let alerts = detect_alerts(&series(0, &[1, 1, 2, 1, 1]), day(0), &rules);
+        assert!(alerts.is_empty());
+    }
+
+    #[test]
+    fn streak_ending_yesterday_is_still_ongoing() {
+        let alerts = detect_alerts(&series(-1, &[1, 1, 1, 1, 1]), day(0), &rules());
+        assert_eq!(kinds(&alerts), vec!["sustained_low"]);
+    }
+
+    #[test]
+    fn old_streak_is_ignored() {
+        let alerts = detect_alerts(&series(-2, &[1, 1, 1, 1, 1]), day(0), &rules());
+        assert!(alerts.is_empty());
+    }
+
+    #[test]
+    fn future_entries_are_ignored() {
+        let mut scores = series(0, &[1, 1, 1, 1, 1]);
+        scores.push((day(1), 5));
+        let alerts = detect_alerts(&scores, day(0), &rules());
+        assert_eq!(kinds(&alerts), vec!["sustained_low"]);
+    }
+
+    #[test]
+    fn unsorted_input_is_handled() {
+        let mut scores = series(0, &[2, 2, 1, 1, 2]);
+        scores.reverse();
+        let alerts = detect_alerts(&scores, day(0), &rules());
+        assert_eq!(kinds(&alerts), vec!["sustained_low"]);
+    }
+
+    #[test]
+    fn drop_against_baseline_triggers_sharp_drop() {
+        let mut scores = series(-7, &[5; 14]);
+        scores.extend(series(0, &[3, 3, 3]));
+        let alerts = detect_alerts(&scores, day(0), &rules());
+
+        assert_eq!(kinds(&alerts), vec!["sharp_drop"]);
+        assert_eq!(alerts[0].start_date, day(-2));
+        assert_eq!(alerts[0].baseline_average, Some(5.0));
+        assert_eq!(alerts[0].recent_average, 3.0);
+    }
+
+    #[test]
+    fn small_drop_is_ignored() {
+        let mut scores = series(-7, &[4; 14]);
+        scores.extend(series(0, &[4, 3, 4]));
+        assert!(detect_alerts(&scores, day(0), &rules()).is_empty());
+    }
+
+    #[test]
+    fn sharp_drop_needs_enough_baseline() {
+        let mut scores = series(-7, &[5; 6]);
+        scores.extend(series(0, &[2, 3, 3]));
+        assert!(detect_alerts(&scores, day(0), &rules()).is_empty());
+    }
+
+    #[test]
+    fn sharp_drop_needs_enough_recent_entries() {
+        let mut scores = series(-7, &[5; 14]);
+        scores.extend(series(0, &[2, 3]));
+        assert!(detect_alerts(&scores, day(0), &rules()).is_empty());
+    }
+
+    #[test]
+    fn both_alerts_can_fire_together() {
+        let mut scores = series(-7, &[5; 14]);
+        scores.extend(series(0, &[1, 2, 1, 1, 2]));
+        let alerts = detect_alerts(&scores, day(0), &rules());
+        assert_eq!(kinds(&alerts), vec!["sustained_low", "sharp_drop"]);
+    }
+}
@@ -0,0 +1,408 @@
+use chrono::{Duration, NaiveDate, Utc};
+use chrono_tz::Tz;
+use bcrypt::verify;
+use crate::config::app_config::app_config;
+use crate::db::{job_query, user_query};
+use crate::db::pool::DbPools;
+use crate::db::transaction::run_in_transaction;
+use crate::errors::app_error::AppError;
+use crate::i18n::localize_message;
+use crate::models::audit::{AuditAction, NewAuditLog};
+use crate::models::job::{Job, JobKind, JobOutput, JobParams, JobResponse, JobStatus, NewJob};
+use crate::models::quota::QuotaOperation;
+use crate::service::{audit_service, avatar_service, export_service, import_service, quota_service, report_service};
+use crate::utils::stats_cache::StatsCache;
+use crate::utils::storage::Storage;
+use crate::utils::timezone::{parse_timezone, today_in};
+
+/// Jumlah job yang dikerjakan per putaran worker
+const JOBS_PER_RUN: usize = 10;
+/// Batas ukuran arsip export yang dikumpulkan di memori sebelum disimpan
+const MAX_RESULT_BYTES: usize = 512 * 1024 * 1024;
+
+fn input_key(job_id: i32) -> String {
+    format!("jobs/{}/input", job_id)
+}
+
+fn result_key(job_id: i32) -> String {
+    format!("jobs/{}/result", job_id)
+}
+
+pub fn job_response(job: Job) -> JobResponse {
+    let result_url = (job.status == JobStatus::Succeeded.as_str() && job.result_content_type.is_some())
+        .then(|| format!("{}/api/jobs/{}/result", app_config().public_api_url, job.id));
+
+    JobResponse {
+        id: job.id,
+        kind: job.kind,
+        status: job.status,
+        error: job.error.as_deref().map(localize_message),
+        result_url,
+        created_at: job.created_at,
+        started_at: job.started_at,
+        finished_at: job.finished_at,
+    }
+}
+
+fn insert_job(
+    pool: &DbPools,
+    user_id: i32,
+    kind: JobKind,
+    params: &JobParams,
+) -> Result<JobResponse, AppError> {
+    let mut conn = pool.conn_write()?;
+
+    let job = job_query::insert_job(
+        &mut conn,
+        &NewJob {
+            user_id,
+            kind: kind.as_str(),
+            params: params.to_json(),
+        },
+    )?;
+    Ok(job_response(job))
+}
+
+/// Antrikan export jurnal Markdown; kuota dipakai saat diantrikan, bukan saat dikerjakan
+pub fn enqueue_journals_markdown(
+    pool: &DbPools,
+    user_id: i32,
+    tz: Tz,
+    ip_address: Option<&str>,
+) -> Result<JobResponse, AppError> {
+    quota_service::consume(pool, user_id, QuotaOperation::Export)?;
+
+    let params = JobParams {
+        timezone: Some(tz.name().to_string()),
+        ip_address: ip_address.map(str::to_string),
+        ..Default::default()
+    };
+    insert_job(pool, user_id, JobKind::JournalsMarkdown, &params)
+}
+
+pub fn enqueue_monthly_report_pdf(
+    pool: &DbPools,
+    user_id: i32,
+    month_start: NaiveDate,
+    tz: Tz,
+    ip_address: Option<&str>,
+) -> Result<JobResponse, AppError> {
+    quota_service::consume(pool, user_id, QuotaOperation::ReportPdf)?;
+
+    let params = JobParams {
+        timezone: Some(tz.name().to_string()),
+        month: Some(month_start),
+        ip_address: ip_address.map(str::to_string),
+        ..Default::default()
+    };
+    insert_job(pool, user_id, JobKind::MonthlyReportPdf, &params)
+}
+
+/// Antrikan import Daylio. File disimpan ke storage sebelum transaksi di-commit,
+/// jadi worker tidak pernah melihat job tanpa file input.
+pub fn enqueue_daylio_import(
+    pool: &DbPools,
+    storage: &dyn Storage,
+    user_id: i32,
+    data: &[u8],
+    dry_run: bool,
+) -> Result<JobResponse, AppError> {
+    quota_service::consume(pool, user_id, QuotaOperation::Import)?;
+
+    let params = JobParams {
+        dry_run: Some(dry_run),
+        ..Default::default()
+    };
+
+    let mut conn = pool.conn_write()?;
+    let job = run_in_transaction(&mut conn, |conn| {
+        let job = job_query::insert_job(
+            conn,
+            &NewJob {
+                user_id,
+                kind: JobKind::DaylioImport.as_str(),
+                params: params.to_json(),
+            },
+        )?;
+        storage.put(&input_key(job.id), data)?;
+        Ok(job)
+    })?;
+    Ok(job_response(job))
+}
+
+/// Minta akun dihapus. Password dicek di sini; penghapusan dikerjakan worker.
+/// Jika permintaan sebelumnya belum selesai, job itu yang dikembalikan.
+pub fn request_account_deletion(
+    pool: &DbPools,
+    user_id: i32,
+    password: Option<&str>,
+    ip_address: Option<&str>,
+) -> Result<JobResponse, AppError> {
+    let mut conn = pool.conn_write()?;
+
+    let user = user_query::find_user_by_id(&mut conn, user_id)?;
+    if !user.is_guest {
+        let password = password.ok_or_else(|| AppError::BadRequest("Password is required".to_string()))?;
+        let is_valid = verify(password, &user.password)
+            .map_err(|_| AppError::InternalServerError("Failed to verify password".to_string()))?;
+        if !is_valid {
+            return Err(AppError::BadRequest("Invalid password".to_string()));
+        }
+    }
+
+    if let Some(job) = job_query::find_active_job(&mut conn, user_id, JobKind::AccountDeletion.as_str())? {
+        return Ok(job_response(job));
+    }
+
+    let params = JobParams {
+        ip_address: ip_address.map(str::to_string),
+        ..Default::default()
+    };
+    let job = job_query::insert_job(
+        &mut conn,
+        &NewJob {
+            user_id,
+            kind: JobKind::AccountDeletion.as_str(),
+            params: params.to_json(),
+        },
+    )?;
+    Ok(job_response(job))
+}
+
+fn find_job(
+    pool: &DbPools,
+    user_id: i32,
+    job_id: i32,
+) -> Result<Job, AppError> {
+    let mut conn = pool.conn_read()?;
+
+    job_query::find_user_job(&mut conn, job_id, user_id)?
+        .ok_or_else(|| AppError::NotFound("Job not found".to_string()))
+}
+
+pub fn get_job(
+    pool: &DbPools,
+    user_id: i32,
+    job_id: i32,
+) -> Result<JobResponse, AppError> {
+    find_job(pool, user_id, job_id).map(job_response)
+}
+
+/// Isi hasil job beserta content type dan nama file untuk diunduh
+pub fn get_job_result(
+    pool: &DbPools,
+    storage: &dyn Storage,
+    user_id: i32,
+    job_id: i32,
+) -> Result<(String, Option<String>, Vec<u8>), AppError> {
+    let job = find_job(pool, user_id, job_id)?;
+    if job.status == JobStatus::Queued.as_str() || job.status == JobStatus::Running.as_str() {
+        return Err(AppError::Conflict("Job has not finished yet".to_string()));
+    }
+
+    let content_type = job
+        .result_content_type
+        .filter(|_| job.status == JobStatus::Succeeded.as_str())
+        .ok_or_else(|| AppError::NotFound("Job result not found".to_string()))?;
+    let data = storage
+        .get(&result_key(job.id))?
+        .ok_or_else(|| AppError::NotFound("Job result not found".to_string()))?;
+    Ok((content_type, job.result_filename, data))
+}
+
+fn job_timezone(params: &JobParams) -> Tz {
+    params.timezone.as_deref().and_then(parse_timezone).unwrap_or(Tz::UTC)
+}
+
+/// Hapus akun beserta semua datanya, termasuk file avatar dan hasil job lain milik pengguna
+fn delete_account(
+    pool: &DbPools,
+    storage: &dyn Storage,
+    user_id: i32,
+    ip_address: Option<&str>,
+) -> Result<(), AppError> {
+    let mut conn = pool.conn_write()?;
+
+    let (user, job_ids) = run_in_transaction(&mut conn, |conn| {
+        let user = user_query::find_user_by_id(conn, user_id)?;
+        let job_ids = job_query::find_job_ids_by_user(conn, user_id)?;
+        user_query::delete_user(conn, user_id)?;
+        audit_service::record(
+            conn,
+            NewAuditLog::new(AuditAction::AccountDeletion, Some(user_id), Some(user_id), ip_address),
+        )?;
+        Ok((user, job_ids))
+    })?;
+
+    avatar_service::delete_stored_avatar(storage, &user)?;
+    for job_id in job_ids {
+        storage.delete(&input_key(job_id))?;
+        storage.delete(&result_key(job_id))?;
+    }
+    Ok(())
+}
+
+async fn execute(
+    pool: &DbPools,
+    storage: &dyn Storage,
+    cache: &StatsCache,
+    job: &Job,
+) -> Result<Option<JobOutput>, AppError> {
+    let user_id = job.user_id.ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+    let kind = JobKind::parse(&job.kind)
+        .ok_or_else(|| AppError::InternalServerError(format!("Unknown job kind: {}", job.kind)))?;
+    let params = JobParams::parse(&job.params);
+    let tz = job_timezone(&params);
+    let ip_address = params.ip_address.as_deref();
+
+    match kind {
+        JobKind::JournalsMarkdown => {
+            let archive = export_service::journals_markdown_archive(pool, user_id, tz, ip_address)?;
+            let data = axum::body::to_bytes(archive, MAX_RESULT_BYTES)
+                .await
+                .map_err(|e| AppError::InternalServerError(format!("Failed to build archive: {}", e)))?;
+            Ok(Some(JobOutput {
+                content_type: "application/zip",
+                filename: Some(format!("mindmate-journals-{}.zip", today_in(tz))),
+                data: data.to_vec(),
+            }))
+        }
+        JobKind::MonthlyReportPdf => {
+            let month_start = params
+                .month
+                .ok_or_else(|| AppError::BadRequest("Month is required".to_string()))?;
+            let pdf = report_service::render_monthly_report_pdf(pool, user_id, month_start, tz, ip_address)?;
+            Ok(Some(JobOutput {
+                content_type: "application/pdf",
+                filename: Some(format!("mindmate-report-{}.pdf", month_start.format("%Y-%m"))),
+                data: pdf,
+            }))
+        }
+        JobKind::DaylioImport => {
+            let data = storage
+                .get(&input_key(job.id))?
+                .ok_or_else(|| AppError::NotFound("Import file not found".to_string()))?;
+            let summary =
+                import_service::run_daylio_import(pool, cache, user_id, &data, params.dry_run.unwrap_or(false))?;
+            let data = serde_json::to_vec(&summary)
+                .map_err(|e| AppError::InternalServerError(format!("Failed to serialize import summary: {}", e)))?;
+            Ok(Some(JobOutput {
+                content_type: "application/json",
+                filename: None,
+                data,
+            }))
+        }
+        JobKind::AccountDeletion => {
+            delete_account(pool, storage, user_id, ip_address)?;
+            Ok(None)
+        }
+    }
+}
+
+/// Simpan hasil job; job yang gagal menyimpan pesan error (bahasa Inggris, diterjemahkan saat dibaca).
+/// File input tidak dibutuhkan lagi setelah job selesai, berhasil atau tidak.
+fn finish(
+    pool: &DbPools,
+    storage: &dyn Storage,
+    job: &Job,
+    outcome: Result<Option<JobOutput>, AppError>,
+) -> Result<bool, AppError> {
+    storage.delete(&input_key(job.id))?;
+    let outcome = outcome.and_then(|output| {
+        if let Some(ref output) = output {
+            storage.put(&result_key(job.id), &output.data)?;
+        }
+        Ok(output)
+    });
+
+    let mut conn = pool.conn_write()?;
+
+    let now = Utc::now().naive_utc();
+    match outcome {
+        Ok(output) => {
+            job_query::mark_succeeded(
+                &mut conn,
+                job.id,
+                output.as_ref().map(|output| output.content_type),
+                output.as_ref().and_then(|output| output.filename.as_deref()),
+                now,
+            )?;
+            Ok(true)
+        }
+        Err(e) => {
+            let (_, message) = e.into_parts();
+            job_query::mark_failed(&mut conn, job.id, &message, now)?;
+            Ok(false)
+        }
+    }
+}
+
+/// Kerjakan job dalam antrian, paling banyak JOBS_PER_RUN per putaran. Job yang terlalu lama
+/// `running` (misalnya karena server restart) ditandai gagal lebih dulu.
+/// Mengembalikan jumlah job yang berhasil dan yang gagal.
+pub async fn run_queued(
+    pool: &DbPools,
+    storage: &dyn Storage,
+    cache: &StatsCache,
+) -> Result<(usize, usize), AppError> {
+    {
+        let mut conn = pool.conn_write()?;
+
+        let now = Utc::now().naive_utc();
+        let timeout = Duration::minutes(app_config().job_timeout_minutes);
+        job_query::fail_stale_jobs(&mut conn, now - timeout, "Job was interrupted", now)?;
+    }
+
+    let (mut succeeded, mut failed) = (0, 0);
+    for _ in 0..JOBS_PER_RUN {
+        let claimed = job_query::claim_next_job(&mut *pool.conn_write()?, Utc::now().naive_utc())?;
+        let Some(job) = claimed else {
+            break;
+        };
+
+        let outcome = execute(pool, storage, cache, &job).await;
+        if finish(pool, storage, &job, outcome)? {
+            succeeded += 1;
+        } else {
+            failed += 1;
+        }
+    }
+
+    Ok((succeeded, failed))
+}
+
+/// Hapus job yang sudah selesai lebih dari JOB_RESULT_TTL_HOURS beserta file-nya
+pub fn cleanup_finished_jobs(
+    pool: &DbPools,
+    storage: &dyn Storage,
+) -> Result<usize, AppError> {
+    let mut conn = pool.conn_write()?;
+
+    let cutoff = Utc::now().naive_utc() - Duration::hours(app_config().job_result_ttl_hours);
+    let job_ids = job_query::find_finished_before(&mut conn, cutoff)?;
+    for &job_id in &job_ids {
+        storage.delete(&input_key(job_id))?;
+        storage.delete(&result_key(job_id))?;
+    }
+    job_query::delete_jobs(&mut conn, &job_ids)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn job_params_round_trip_and_tolerate_bad_json() {
+        let params = JobParams {
+            timezone: Some("Asia/Jakarta".to_string()),
+            month: NaiveDate::from_ymd_opt(2026, 10, 1),
+            ..Default::default()
+        };
+        let parsed = JobParams::parse(&params.to_json());
+        assert_eq!(job_timezone(&parsed), chrono_tz::Asia::Jakarta);
+        assert_eq!(parsed.month, params.month);
+        assert_eq!(parsed.dry_run, None);
+
+        assert_eq!(job_timezone(&JobParams::parse("not json")), Tz::UTC);
+    }
+}
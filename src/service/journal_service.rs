@@ -1,21 +1,27 @@
-use crate::models::journal::JournalResponse; 
-use crate::db::journal_query;
+use crate::models::journal::{JournalDraft, JournalDraftResponse, JournalField, JournalFields, JournalResponse, SaveJournalDraftRequest, SparseJournalResponse, UpdateJournalRequest};
+use crate::db::{journal_draft_query, journal_query};
 use crate::errors::app_error::AppError;
-use diesel::r2d2;
-use diesel::pg::PgConnection;
-use chrono::NaiveDate;
+use crate::models::list_sort::ListSort;
+use crate::db::pool::DbPools;
+use crate::repo::JournalRepo;
+use crate::utils::stats_cache::{StatsCache, StatsKind};
+use crate::config::app_config::app_config;
+use crate::utils::json_stream::{stream_json_array, JsonArrayStream};
+use crate::utils::streaks::{StreakEngine, StreakStats};
+use crate::utils::text_limits::ensure_max_length;
+use std::sync::Arc;
+use chrono::{NaiveDate, Utc};
+use chrono_tz::Tz;
 
 pub fn create_journal(
-    pool: &r2d2::Pool<r2d2::ConnectionManager<PgConnection>>,
+    journals: &dyn JournalRepo,
+    cache: &StatsCache,
     user_id: i32,
     title: &str,
     content: &str,
-    created_at: Option<String>, // Changed from NaiveDate to String
+    created_at: Option<NaiveDate>,
+    tz: Tz,
 ) -> Result<JournalResponse, AppError> {
-    let mut conn = pool
-        .get()
-        .map_err(|_| AppError::InternalServerError("Failed to get DB connection".to_string()))?;
-
     // Validate input
     if title.trim().is_empty() {
         return Err(AppError::BadRequest("Title cannot be empty".to_string()));
@@ -25,36 +31,30 @@ pub fn create_journal(
         return Err(AppError::BadRequest("Content cannot be empty".to_string()));
     }
 
-    // Parse the date from MM-DD-YYYY format if provided
-    let parsed_date = if let Some(date_str) = created_at {
-        Some(NaiveDate::parse_from_str(&date_str, "%m-%d-%Y")
-            .map_err(|_| AppError::BadRequest("Invalid date format. Use MM-DD-YYYY".to_string()))?)
-    } else {
-        None
-    };
+    ensure_max_length("Title", title, app_config().journal_title_max_length)?;
+    ensure_max_length("Content", content, app_config().journal_content_max_length)?;
 
-    let journal_data = journal_query::create_journal(&mut conn, user_id, title, content, parsed_date)?;
+    let journal_data = journals.create(user_id, title, content, created_at, tz)?;
+    cache.invalidate_user(user_id);
 
     Ok(JournalResponse {
         id: journal_data.id,
         user_id: journal_data.user_id,
         title: journal_data.title,
         content: journal_data.content,
+        is_pinned: journal_data.is_pinned,
         created_at: journal_data.created_at,
         updated_at: journal_data.updated_at,
     })
 }
 
 pub fn get_journal_by_id(
-    pool: &r2d2::Pool<r2d2::ConnectionManager<PgConnection>>,
+    journals: &dyn JournalRepo,
     journal_id: i32,
     user_id: i32,
 ) -> Result<JournalResponse, AppError> {
-    let mut conn = pool
-        .get()
-        .map_err(|_| AppError::InternalServerError("Failed to get DB connection".to_string()))?;
-
-    let journal = journal_query::find_journal_by_id(&mut conn, journal_id)
+    let journal = journals
+        .find_by_id(journal_id)
         .map_err(|_| AppError::NotFound("Journal not found".to_string()))?;
 
     // Check if user owns this journal
@@ -67,28 +67,28 @@ pub fn get_journal_by_id(
         user_id: journal.user_id,
         title: journal.title,
         content: journal.content,
+        is_pinned: journal.is_pinned,
         created_at: journal.created_at,
         updated_at: journal.updated_at,
     })
 }
 
 pub fn get_user_journals(
-    pool: &r2d2::Pool<r2d2::ConnectionManager<PgConnection>>,
+    journals: &dyn JournalRepo,
     user_id: i32,
     limit: Option<i32>,
     offset: Option<i32>,
+    pinned: Option<bool>,
+    sort: ListSort,
 ) -> Result<Vec<JournalResponse>, AppError> {
-    let mut conn = pool
-        .get()
-        .map_err(|_| AppError::InternalServerError("Failed to get DB connection".to_string()))?;
-
-    let journals = journal_query::find_journals_by_user(&mut conn, user_id, limit, offset)?;
+    let journals = journals.find_by_user(user_id, limit, offset, pinned, sort)?;
 
     let journal_responses = journals.into_iter().map(|journal| JournalResponse {
         id: journal.id,
         user_id: journal.user_id,
         title: journal.title,
         content: journal.content,
+        is_pinned: journal.is_pinned,
         created_at: journal.created_at,
         updated_at: journal.updated_at,
     }).collect();
@@ -96,48 +96,63 @@ pub fn get_user_journals(
     Ok(journal_responses)
 }
 
+/// List jurnal dengan field pilihan saja. Tanpa `content`, isi jurnal tidak dimuat dari database.
+pub fn get_user_journal_fields(
+    journals: &dyn JournalRepo,
+    user_id: i32,
+    limit: Option<i32>,
+    offset: Option<i32>,
+    pinned: Option<bool>,
+    sort: ListSort,
+    fields: &JournalFields,
+) -> Result<Vec<SparseJournalResponse>, AppError> {
+    if fields.contains(JournalField::Content) {
+        let journals = journals.find_by_user(user_id, limit, offset, pinned, sort)?;
+        return Ok(journals.into_iter().map(|journal| SparseJournalResponse::from_journal(journal, fields)).collect());
+    }
+
+    let summaries = journals.find_summaries_by_user(user_id, limit, offset, pinned, sort)?;
+    Ok(summaries.into_iter().map(|summary| SparseJournalResponse::from_summary(summary, fields)).collect())
+}
+
 pub fn get_journal_by_date(
-    pool: &r2d2::Pool<r2d2::ConnectionManager<PgConnection>>,
+    journals: &dyn JournalRepo,
     user_id: i32,
     date: NaiveDate,
+    tz: Tz,
 ) -> Result<JournalResponse, AppError> {
-    let mut conn = pool
-        .get()
-        .map_err(|_| AppError::InternalServerError("Failed to get DB connection".to_string()))?;
-
-    let journal = journal_query::find_journal_by_user_and_date(&mut conn, user_id, date)?;
+    let journal = journals.find_by_date(user_id, date, tz)?;
 
     Ok(JournalResponse {
         id: journal.id,
         user_id: journal.user_id,
         title: journal.title,  
         content: journal.content,
+        is_pinned: journal.is_pinned,
         created_at: journal.created_at,
         updated_at: journal.updated_at,
     })
 }
 
 pub fn get_journals_by_date_range(
-    pool: &r2d2::Pool<r2d2::ConnectionManager<PgConnection>>,
+    journals: &dyn JournalRepo,
     user_id: i32,
     start_date: NaiveDate,
     end_date: NaiveDate,
+    tz: Tz,
 ) -> Result<Vec<JournalResponse>, AppError> {
-    let mut conn = pool
-        .get()
-        .map_err(|_| AppError::InternalServerError("Failed to get DB connection".to_string()))?;
-
     if start_date > end_date {
         return Err(AppError::BadRequest("Start date cannot be after end date".to_string()));
     }
 
-    let journals = journal_query::find_journals_by_date_range(&mut conn, user_id, start_date, end_date)?;
+    let journals = journals.find_by_date_range(user_id, start_date, end_date, tz)?;
 
     let journal_responses = journals.into_iter().map(|journal| JournalResponse {
         id: journal.id,
         user_id: journal.user_id,
         title: journal.title,
         content: journal.content,
+        is_pinned: journal.is_pinned,
         created_at: journal.created_at,
         updated_at: journal.updated_at,
     }).collect();
@@ -146,96 +161,100 @@ pub fn get_journals_by_date_range(
 }
 
 pub fn update_journal(
-    pool: &r2d2::Pool<r2d2::ConnectionManager<PgConnection>>,
+    journals: &dyn JournalRepo,
+    cache: &StatsCache,
     journal_id: i32,
     user_id: i32,
-    new_title: Option<String>,
-    new_content: Option<String>,
-    new_created_at: Option<String>,
+    data: UpdateJournalRequest,
+    tz: Tz,
 ) -> Result<JournalResponse, AppError> {
-    let mut conn = pool
-        .get()
-        .map_err(|_| AppError::InternalServerError("Failed to get DB connection".to_string()))?;
+    let UpdateJournalRequest { title, content, created_at: new_created_at } = data;
+    let new_title = title.required("Title")?;
+    let new_content = content.required("Content")?;
 
     // Validate input if provided
     if let Some(ref title) = new_title {
         if title.trim().is_empty() {
             return Err(AppError::BadRequest("Title cannot be empty".to_string()));
         }
+        ensure_max_length("Title", title, app_config().journal_title_max_length)?;
     }
 
     if let Some(ref content) = new_content {
         if content.trim().is_empty() {
             return Err(AppError::BadRequest("Content cannot be empty".to_string()));
         }
+        ensure_max_length("Content", content, app_config().journal_content_max_length)?;
     }
 
-    // Parse the date from MM-DD-YYYY format if provided
-    let parsed_date = if let Some(date_str) = new_created_at {
-        Some(NaiveDate::parse_from_str(&date_str, "%m-%d-%Y")
-            .map_err(|_| AppError::BadRequest("Invalid date format. Use MM-DD-YYYY".to_string()))?)
-    } else {
-        None
-    };
-
-    let updated_journal = journal_query::update_journal(
-        &mut conn, 
-        journal_id, 
-        user_id, 
-        new_title, 
-        new_content,
-        parsed_date 
-    )?;
+    let updated_journal = journals.update(journal_id, user_id, new_title, new_content, new_created_at, tz)?;
+    cache.invalidate_user(user_id);
 
     Ok(JournalResponse {
         id: updated_journal.id,
         user_id: updated_journal.user_id,
         title: updated_journal.title,
         content: updated_journal.content,
+        is_pinned: updated_journal.is_pinned,
         created_at: updated_journal.created_at,
         updated_at: updated_journal.updated_at,
     })
 }
 
 pub fn delete_journal(
-    pool: &r2d2::Pool<r2d2::ConnectionManager<PgConnection>>,
+    journals: &dyn JournalRepo,
+    cache: &StatsCache,
     journal_id: i32,
     user_id: i32,
 ) -> Result<(), AppError> {
-    let mut conn = pool
-        .get()
-        .map_err(|_| AppError::InternalServerError("Failed to get DB connection".to_string()))?;
-
-    let deleted = journal_query::delete_journal(&mut conn, journal_id, user_id)?;
+    let deleted = journals.delete(journal_id, user_id)?;
     if !deleted {
         return Err(AppError::NotFound("Journal not found".to_string()));
     }
+    cache.invalidate_user(user_id);
 
     Ok(())
 }
 
+pub fn set_journal_pinned(
+    journals: &dyn JournalRepo,
+    journal_id: i32,
+    user_id: i32,
+    pinned: bool,
+) -> Result<JournalResponse, AppError> {
+    let journal = journals.set_pinned(journal_id, user_id, pinned)?;
+
+    Ok(JournalResponse {
+        id: journal.id,
+        user_id: journal.user_id,
+        title: journal.title,
+        content: journal.content,
+        is_pinned: journal.is_pinned,
+        created_at: journal.created_at,
+        updated_at: journal.updated_at,
+    })
+}
+
 pub fn get_recent_journals(
-    pool: &r2d2::Pool<r2d2::ConnectionManager<PgConnection>>,
+    journals: &dyn JournalRepo,
     user_id: i32,
     days: Option<i32>,
+    tz: Tz,
 ) -> Result<Vec<JournalResponse>, AppError> {
-    let mut conn = pool
-        .get()
-        .map_err(|_| AppError::InternalServerError("Failed to get DB connection".to_string()))?;
-
     let days = days.unwrap_or(7);
     
     if days <= 0 || days > 365 {
         return Err(AppError::BadRequest("Days must be between 1 and 365".to_string()));
     }
 
-    let journals = journal_query::get_recent_journals(&mut conn, user_id, days)?;
+    let journals = journals.find_recent(user_id, days, tz)?;
 
     let journal_responses = journals.into_iter().map(|journal| JournalResponse {
         id: journal.id,
         user_id: journal.user_id,
         title: journal.title,
         content: journal.content,
+        is_pinned: journal.is_pinned,
         created_at: journal.created_at,
         updated_at: journal.updated_at,
     }).collect();
@@ -244,48 +263,52 @@ pub fn get_recent_journals(
 }
 
 pub fn get_journal_stats_count(
-    pool: &r2d2::Pool<r2d2::ConnectionManager<PgConnection>>,
+    journals: &dyn JournalRepo,
+    cache: &StatsCache,
     user_id: i32,
 ) -> Result<i64, AppError> {
-    let mut conn = pool
-        .get()
-        .map_err(|_| AppError::InternalServerError("Failed to get DB connection".to_string()))?;
+    let version = journals.latest_update(user_id)?;
+    cache.get_or_compute(user_id, StatsKind::JournalCount, version, || journals.count(user_id))
+}
 
-    journal_query::get_journal_stats_simple(&mut conn, user_id)
+/// Streak jurnal per tanggal lokal pengguna
+pub fn get_journal_streak(
+    journals: &dyn JournalRepo,
+    user_id: i32,
+    tz: Tz,
+) -> Result<StreakStats, AppError> {
+    let timestamps = journals.timestamps(user_id)?;
+    Ok(StreakEngine::new(tz).summarize_timestamps(timestamps))
 }
 
+/// Semua jurnal pengguna sebagai array JSON yang di-stream dari database
 pub fn get_all_user_journals(
-    pool: &r2d2::Pool<r2d2::ConnectionManager<PgConnection>>,
+    journals: Arc<dyn JournalRepo>,
     user_id: i32,
-) -> Result<Vec<JournalResponse>, AppError> {
-    let mut conn = pool
-        .get()
-        .map_err(|_| AppError::InternalServerError("Failed to get DB connection".to_string()))?;
-
-    let journals = journal_query::get_all_journals_by_user(&mut conn, user_id)?;
-
-    let journal_responses = journals.into_iter().map(|journal| JournalResponse {
-        id: journal.id,
-        user_id: journal.user_id,
-        title: journal.title,
-        content: journal.content,
-        created_at: journal.created_at,
-        updated_at: journal.updated_at,
-    }).collect();
-
-    Ok(journal_responses)
+) -> Result<JsonArrayStream, AppError> {
+    Ok(stream_json_array(move |out| {
+        journals.for_each_by_user(user_id, &mut |journal| {
+            out.push(&JournalResponse {
+                id: journal.id,
+                user_id: journal.user_id,
+                title: journal.title,
+                content: journal.content,
+                is_pinned: journal.is_pinned,
+                created_at: journal.created_at,
+                updated_at: journal.updated_at,
+            })
+        })
+    }))
 }
 
 pub fn search_journals(
-    pool: &r2d2::Pool<r2d2::ConnectionManager<PgConnection>>,
+    pool: &DbPools,
     user_id: i32,
     search_query: &str,
     limit: Option<i32>,
     offset: Option<i32>,
 ) -> Result<Vec<JournalResponse>, AppError> {
-    let mut conn = pool
-        .get()
-        .map_err(|_| AppError::InternalServerError("Failed to get DB connection".to_string()))?;
+    let mut conn = pool.conn_read()?;
 
     if search_query.trim().is_empty() {
         return Err(AppError::BadRequest("Search query cannot be empty".to_string()));
@@ -298,9 +321,147 @@ pub fn search_journals(
         user_id: journal.user_id,
         title: journal.title,
         content: journal.content,
+        is_pinned: journal.is_pinned,
         created_at: journal.created_at,
         updated_at: journal.updated_at,
     }).collect();
 
     Ok(journal_responses)
-}
\ No newline at end of file
+}
+
+/// Seperti `search_journals`, dengan field pilihan saja
+pub fn search_journal_fields(
+    pool: &DbPools,
+    user_id: i32,
+    search_query: &str,
+    limit: Option<i32>,
+    offset: Option<i32>,
+    fields: &JournalFields,
+) -> Result<Vec<SparseJournalResponse>, AppError> {
+    if search_query.trim().is_empty() {
+        return Err(AppError::BadRequest("Search query cannot be empty".to_string()));
+    }
+
+    let mut conn = pool.conn_read()?;
+
+    if fields.contains(JournalField::Content) {
+        let journals = journal_query::search_journals(&mut conn, user_id, search_query, limit, offset)?;
+        return Ok(journals.into_iter().map(|journal| SparseJournalResponse::from_journal(journal, fields)).collect());
+    }
+
+    let summaries = journal_query::search_journal_summaries(&mut conn, user_id, search_query, limit, offset)?;
+    Ok(summaries.into_iter().map(|summary| SparseJournalResponse::from_summary(summary, fields)).collect())
+}
+
+pub fn get_journal_draft(
+    pool: &DbPools,
+    user_id: i32,
+) -> Result<JournalDraftResponse, AppError> {
+    let mut conn = pool.conn_write()?;
+
+    let draft = journal_draft_query::find_draft_by_user(&mut conn, user_id)?
+        .ok_or_else(|| AppError::NotFound("No journal draft".to_string()))?;
+
+    Ok(JournalDraftResponse {
+        title: draft.title,
+        content: draft.content,
+        updated_at: draft.updated_at,
+    })
+}
+
+/// Simpan draft (autosave); tidak membuat jurnal dan tidak memengaruhi statistik
+pub fn save_journal_draft(
+    pool: &DbPools,
+    user_id: i32,
+    data: SaveJournalDraftRequest,
+) -> Result<JournalDraftResponse, AppError> {
+    let mut conn = pool.conn_write()?;
+
+    ensure_max_length("Title", &data.title, app_config().journal_title_max_length)?;
+    ensure_max_length("Content", &data.content, app_config().journal_content_max_length)?;
+
+    let draft = journal_draft_query::upsert_draft(
+        &mut conn,
+        &JournalDraft {
+            user_id,
+            title: data.title,
+            content: data.content,
+            updated_at: Utc::now().naive_utc(),
+        },
+    )?;
+
+    Ok(JournalDraftResponse {
+        title: draft.title,
+        content: draft.content,
+        updated_at: draft.updated_at,
+    })
+}
+
+pub fn delete_journal_draft(
+    pool: &DbPools,
+    user_id: i32,
+) -> Result<(), AppError> {
+    let mut conn = pool.conn_write()?;
+
+    if journal_draft_query::delete_draft(&mut conn, user_id)? == 0 {
+        return Err(AppError::NotFound("No journal draft".to_string()));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repo::fake::FakeJournalRepo;
+    use crate::utils::patch::Patch;
+
+    #[test]
+    fn journal_dates_follow_the_user_timezone() {
+        // 2026-10-01 20:00 UTC sudah tanggal 2 Oktober di Jakarta (UTC+7)
+        let created_at = NaiveDate::from_ymd_opt(2026, 10, 1).unwrap().and_hms_opt(20, 0, 0).unwrap();
+        let repo = FakeJournalRepo::with(vec![FakeJournalRepo::journal(1, 7, created_at, "Malam")]);
+        let jakarta: Tz = "Asia/Jakarta".parse().unwrap();
+        let oct = |day| NaiveDate::from_ymd_opt(2026, 10, day).unwrap();
+
+        assert_eq!(get_journal_by_date(&repo, 7, oct(2), jakarta).unwrap().id, 1);
+        assert!(matches!(get_journal_by_date(&repo, 7, oct(1), jakarta), Err(AppError::NotFound(_))));
+        assert_eq!(get_journal_by_date(&repo, 7, oct(1), Tz::UTC).unwrap().id, 1);
+        assert!(matches!(set_journal_pinned(&repo, 1, 8, true), Err(AppError::NotFound(_))));
+        assert!(set_journal_pinned(&repo, 1, 7, true).unwrap().is_pinned);
+    }
+
+    #[test]
+    fn sparse_list_returns_only_requested_fields() {
+        let created_at = NaiveDate::from_ymd_opt(2026, 10, 1).unwrap().and_hms_opt(8, 0, 0).unwrap();
+        let mut journal = FakeJournalRepo::journal(1, 7, created_at, "Pagi");
+        journal.content = " Hari ini\ncerah  sekali ".to_string();
+        let repo = FakeJournalRepo::with(vec![journal]);
+
+        let fields = JournalFields::parse(Some("id, title,word_count")).unwrap().unwrap();
+        let list = get_user_journal_fields(&repo, 7, None, None, None, ListSort::DateDesc, &fields).unwrap();
+        assert_eq!(
+            serde_json::to_value(&list).unwrap(),
+            serde_json::json!([{ "id": 1, "title": "Pagi", "word_count": 4 }])
+        );
+
+        assert_eq!(JournalFields::parse(Some(" ")).unwrap(), None);
+        assert!(JournalFields::parse(Some("id,secret")).is_err());
+    }
+
+    #[test]
+    fn writes_and_stats_go_through_the_repo() {
+        let repo = FakeJournalRepo::default();
+        let cache = StatsCache::new(10, std::time::Duration::from_secs(60));
+        let oct = |day| NaiveDate::from_ymd_opt(2026, 10, day).unwrap();
+
+        let created = create_journal(&repo, &cache, 7, "Pagi", "Cerah", Some(oct(1)), Tz::UTC).unwrap();
+        assert!(matches!(create_journal(&repo, &cache, 7, " ", "Cerah", None, Tz::UTC), Err(AppError::BadRequest(_))));
+        let changes = UpdateJournalRequest { title: Patch::Value("Sore".to_string()), content: Patch::Absent, created_at: Some(oct(2)) };
+        let updated = update_journal(&repo, &cache, created.id, 7, changes, Tz::UTC).unwrap();
+        assert_eq!((updated.title.as_str(), updated.content.as_str()), ("Sore", "Cerah"));
+
+        assert_eq!(get_journal_stats_count(&repo, &cache, 7).unwrap(), 1);
+        assert_eq!(get_journal_streak(&repo, 8, Tz::UTC).unwrap().longest_length(), 0);
+    }
+}
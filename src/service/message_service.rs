@@ -0,0 +1,230 @@
+use std::collections::HashMap;
+use chrono::Utc;
+use diesel::pg::PgConnection;
+use crate::db::{appointment_query, message_query, psychologist_query};
+use crate::db::pool::DbPools;
+use crate::db::transaction::run_in_transaction;
+use crate::errors::app_error::AppError;
+use crate::models::message::{
+    ConversationResponse, MarkReadResponse, Message, MessageListQuery, MessageResponse, NewMessage,
+    SendMessageRequest,
+};
+use crate::models::moderation::ModeratedContent;
+use crate::service::moderation_service;
+use crate::utils::event_bus::{AppEvent, EventBus};
+use crate::utils::moderation::ModerationVerdict;
+use crate::utils::text_limits::ensure_max_length;
+
+const MESSAGE_MAX_LENGTH: usize = 5000;
+const DEFAULT_MESSAGE_LIMIT: i64 = 50;
+const MAX_MESSAGE_LIMIT: i64 = 200;
+
+/// Peserta percakapan: pengguna (klien) atau psikolog lewat akun yang ditautkan
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Participant {
+    Client,
+    Psychologist,
+}
+
+impl Participant {
+    fn is_psychologist(&self) -> bool {
+        *self == Participant::Psychologist
+    }
+}
+
+/// Pastikan pemanggil ikut dalam percakapan dan hubungan dengan psikolog sudah dikonfirmasi.
+/// Percakapan yang tidak boleh diakses diperlakukan seolah tidak ada.
+fn authorize(
+    conn: &mut PgConnection,
+    caller_id: i32,
+    user_id: i32,
+    psychologist_id: i32,
+) -> Result<Participant, AppError> {
+    let participant = if caller_id == user_id {
+        Some(Participant::Client)
+    } else if psychologist_query::find_psychologist_id_by_user(conn, caller_id)? == Some(psychologist_id) {
+        Some(Participant::Psychologist)
+    } else {
+        None
+    };
+
+    match participant {
+        Some(participant) if appointment_query::has_confirmed_relationship(conn, user_id, psychologist_id)? => {
+            Ok(participant)
+        }
+        _ => Err(AppError::NotFound("Conversation not found".to_string())),
+    }
+}
+
+/// Apakah event bus perlu diteruskan ke pemanggil ini (klien atau psikolog percakapannya)
+pub fn is_recipient(caller_id: i32, caller_psychologist_id: Option<i32>, user_id: i32, psychologist_id: i32) -> bool {
+    caller_id == user_id || caller_psychologist_id == Some(psychologist_id)
+}
+
+fn conversations(
+    counterparts: Vec<(i32, String)>,
+    last_messages: Vec<Message>,
+    unread: Vec<(i32, i64)>,
+    conversation: impl Fn(i32) -> (i32, i32),
+    counterpart_of: impl Fn(&Message) -> i32,
+) -> Vec<ConversationResponse> {
+    let mut last_messages: HashMap<i32, Message> =
+        last_messages.into_iter().map(|message| (counterpart_of(&message), message)).collect();
+    let unread: HashMap<i32, i64> = unread.into_iter().collect();
+
+    counterparts
+        .into_iter()
+        .map(|(counterpart_id, counterpart_name)| {
+            let (user_id, psychologist_id) = conversation(counterpart_id);
+            ConversationResponse {
+                user_id,
+                psychologist_id,
+                counterpart_name,
+                last_message: last_messages.remove(&counterpart_id).map(MessageResponse::from),
+                unread_count: unread.get(&counterpart_id).copied().unwrap_or(0),
+            }
+        })
+        .collect()
+}
+
+/// Percakapan pemanggil sebagai klien maupun sebagai psikolog, yang terbaru lebih dulu
+pub fn get_conversations(
+    pool: &DbPools,
+    caller_id: i32,
+) -> Result<Vec<ConversationResponse>, AppError> {
+    let mut conn = pool.conn_read()?;
+
+    let mut result = conversations(
+        appointment_query::find_confirmed_psychologists(&mut conn, caller_id)?,
+        message_query::find_last_messages_for_user(&mut conn, caller_id)?,
+        message_query::count_unread_for_user(&mut conn, caller_id)?,
+        |psychologist_id| (caller_id, psychologist_id),
+        |message| message.psychologist_id,
+    );
+
+    if let Some(psychologist_id) = psychologist_query::find_psychologist_id_by_user(&mut conn, caller_id)? {
+        result.extend(conversations(
+            appointment_query::find_confirmed_clients(&mut conn, psychologist_id)?,
+            message_query::find_last_messages_for_psychologist(&mut conn, psychologist_id)?,
+            message_query::count_unread_for_psychologist(&mut conn, psychologist_id)?,
+            |user_id| (user_id, psychologist_id),
+            |message| message.user_id,
+        ));
+    }
+
+    result.sort_by(|a, b| {
+        let a_last = a.last_message.as_ref().map(|message| message.created_at);
+        let b_last = b.last_message.as_ref().map(|message| message.created_at);
+        b_last.cmp(&a_last).then_with(|| a.counterpart_name.cmp(&b.counterpart_name))
+    });
+    Ok(result)
+}
+
+pub fn get_messages(
+    pool: &DbPools,
+    caller_id: i32,
+    user_id: i32,
+    psychologist_id: i32,
+    query: &MessageListQuery,
+) -> Result<Vec<MessageResponse>, AppError> {
+    let limit = query.limit.unwrap_or(DEFAULT_MESSAGE_LIMIT);
+    if limit <= 0 || limit > MAX_MESSAGE_LIMIT {
+        return Err(AppError::BadRequest(format!("Limit must be between 1 and {}", MAX_MESSAGE_LIMIT)));
+    }
+
+    let mut conn = pool.conn_read()?;
+
+    let participant = authorize(&mut conn, caller_id, user_id, psychologist_id)?;
+    let messages = message_query::find_messages(
+        &mut conn,
+        user_id,
+        psychologist_id,
+        participant.is_psychologist(),
+        query.before_id,
+        limit,
+    )?;
+    Ok(messages.into_iter().map(MessageResponse::from).collect())
+}
+
+/// Kirim pesan dan teruskan ke kedua peserta yang sedang terhubung lewat event bus.
+/// Pesan yang ditahan moderasi baru diteruskan setelah disetujui admin.
+pub fn send_message(
+    pool: &DbPools,
+    event_bus: &EventBus,
+    caller_id: i32,
+    user_id: i32,
+    psychologist_id: i32,
+    data: SendMessageRequest,
+    verdict: &ModerationVerdict,
+) -> Result<MessageResponse, AppError> {
+    let body = data.body.trim();
+    if body.is_empty() {
+        return Err(AppError::BadRequest("Message cannot be empty".to_string()));
+    }
+    ensure_max_length("Message", body, MESSAGE_MAX_LENGTH)?;
+
+    let mut conn = pool.conn_write()?;
+    let message = run_in_transaction(&mut conn, |conn| {
+        let participant = authorize(conn, caller_id, user_id, psychologist_id)?;
+        let message = message_query::insert_message(
+            conn,
+            &NewMessage {
+                user_id,
+                psychologist_id,
+                from_psychologist: participant.is_psychologist(),
+                body,
+                held: verdict.is_held(),
+            },
+        )?;
+        moderation_service::record(conn, ModeratedContent::Message, message.id, Some(caller_id), verdict)?;
+        Ok(MessageResponse::from(message))
+    })?;
+
+    if !message.held {
+        event_bus.publish(AppEvent::MessageCreated { message: message.clone() });
+    }
+    Ok(message)
+}
+
+/// Tandai dibaca semua pesan dari lawan bicara pemanggil
+pub fn mark_read(
+    pool: &DbPools,
+    event_bus: &EventBus,
+    caller_id: i32,
+    user_id: i32,
+    psychologist_id: i32,
+) -> Result<MarkReadResponse, AppError> {
+    let mut conn = pool.conn_write()?;
+
+    let participant = authorize(&mut conn, caller_id, user_id, psychologist_id)?;
+    let from_psychologist = !participant.is_psychologist();
+    let marked_read =
+        message_query::mark_read(&mut conn, user_id, psychologist_id, from_psychologist, Utc::now().naive_utc())?;
+
+    if marked_read > 0 {
+        event_bus.publish(AppEvent::MessagesRead { user_id, psychologist_id, from_psychologist });
+    }
+    Ok(MarkReadResponse { marked_read })
+}
+
+/// Id psikolog milik pemanggil, untuk menyaring event percakapan
+pub fn find_caller_psychologist_id(
+    pool: &DbPools,
+    caller_id: i32,
+) -> Result<Option<i32>, AppError> {
+    let mut conn = pool.conn_read()?;
+    psychologist_query::find_psychologist_id_by_user(&mut conn, caller_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn events_reach_both_participants_only() {
+        assert!(is_recipient(7, None, 7, 3));
+        assert!(is_recipient(9, Some(3), 7, 3));
+        assert!(!is_recipient(9, Some(4), 7, 3));
+        assert!(!is_recipient(8, None, 7, 3));
+    }
+}
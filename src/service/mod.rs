@@ -2,4 +2,35 @@ pub mod auth_service;
 pub mod user_service;
 pub mod mood_service;
 pub mod journal_service;
-pub mod google_auth_service;
\ No newline at end of file
+pub mod google_auth_service;
+pub mod report_service;
+pub mod calendar_service;
+pub mod security_service;
+pub mod audit_service;
+pub mod email_change_service;
+pub mod avatar_service;
+pub mod onboarding_service;
+pub mod insight_service;
+pub mod push_service;
+pub mod notification_service;
+pub mod export_service;
+pub mod import_service;
+pub mod backup_service;
+pub mod organization_service;
+pub mod retention_service;
+pub mod admin_analytics_service;
+pub mod dev_service;
+pub mod password_reset_service;
+pub mod help_service;
+pub mod psychologist_service;
+pub mod appointment_service;
+pub mod message_service;
+pub mod moderation_service;
+pub mod community_service;
+pub mod checkin_service;
+pub mod webhook_service;
+pub mod health_service;
+pub mod quota_service;
+pub mod job_service;
+pub mod status_service;
+pub mod usage_service;
\ No newline at end of file
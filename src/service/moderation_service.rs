@@ -0,0 +1,152 @@
+use chrono::Utc;
+use diesel::pg::PgConnection;
+use crate::db::{community_query, help_query, moderation_query};
+use crate::db::pool::DbPools;
+use crate::db::transaction::run_in_transaction;
+use crate::errors::app_error::AppError;
+use crate::models::audit::{AuditAction, NewAuditLog};
+use crate::models::message::MessageResponse;
+use crate::models::moderation::{
+    ModeratedContent, ModerationStatus, ModerationVerdictRecord, ModerationVerdictResponse, NewModerationVerdict,
+};
+use crate::service::audit_service;
+use crate::utils::event_bus::{AppEvent, EventBus};
+use crate::utils::moderation::{ModerationAction, ModerationVerdict};
+
+const DEFAULT_MODERATION_LIST_LIMIT: i64 = 50;
+const MAX_MODERATION_LIST_LIMIT: i64 = 200;
+
+/// Simpan hasil moderasi yang perlu ditinjau; konten yang lolos tidak dicatat
+pub fn record(
+    conn: &mut PgConnection,
+    content: ModeratedContent,
+    content_id: i32,
+    author_id: Option<i32>,
+    verdict: &ModerationVerdict,
+) -> Result<(), AppError> {
+    if verdict.action == ModerationAction::Allow {
+        return Ok(());
+    }
+
+    moderation_query::insert_verdict(
+        conn,
+        &NewModerationVerdict {
+            content_type: content.as_str(),
+            content_id,
+            author_id,
+            action: verdict.action.as_str(),
+            source: verdict.source,
+            reasons: &verdict.reasons,
+        },
+    )?;
+    Ok(())
+}
+
+fn content_text(conn: &mut PgConnection, verdict: &ModerationVerdictRecord) -> Result<Option<String>, AppError> {
+    Ok(match ModeratedContent::parse(&verdict.content_type) {
+        Some(ModeratedContent::Message) => {
+            moderation_query::find_message(conn, verdict.content_id)?.map(|message| message.body)
+        }
+        Some(ModeratedContent::HelpRequest) => {
+            help_query::find_help_request(conn, verdict.content_id)?.map(|request| request.message)
+        }
+        Some(ModeratedContent::HelpComment) => {
+            moderation_query::find_help_comment(conn, verdict.content_id)?.map(|comment| comment.body)
+        }
+        Some(ModeratedContent::CommunityPost) => {
+            community_query::find_post(conn, verdict.content_id)?.map(|post| post.body)
+        }
+        None => None,
+    })
+}
+
+/// Antrian tinjauan moderasi untuk admin
+pub fn list_verdicts(
+    pool: &DbPools,
+    status: Option<&str>,
+    limit: Option<i64>,
+) -> Result<Vec<ModerationVerdictResponse>, AppError> {
+    let status = match status {
+        Some(value) => ModerationStatus::parse(value)
+            .ok_or_else(|| AppError::BadRequest(format!("Unknown moderation status: {}", value)))?,
+        None => ModerationStatus::Pending,
+    };
+    let limit = limit.unwrap_or(DEFAULT_MODERATION_LIST_LIMIT);
+    if limit <= 0 || limit > MAX_MODERATION_LIST_LIMIT {
+        return Err(AppError::BadRequest(format!("Limit must be between 1 and {}", MAX_MODERATION_LIST_LIMIT)));
+    }
+
+    let mut conn = pool.conn_read()?;
+
+    let verdicts = moderation_query::find_verdicts(&mut conn, status.as_str(), limit)?;
+    verdicts
+        .into_iter()
+        .map(|verdict| {
+            let content = content_text(&mut conn, &verdict)?;
+            Ok(ModerationVerdictResponse::new(verdict, content))
+        })
+        .collect()
+}
+
+/// Setujui (konten ditampilkan) atau tolak (konten disembunyikan) hasil moderasi.
+/// Pesan yang baru dilepas diteruskan ke peserta percakapan lewat event bus.
+pub fn review_verdict(
+    pool: &DbPools,
+    event_bus: &EventBus,
+    admin_id: i32,
+    verdict_id: i32,
+    approve: bool,
+    ip_address: Option<&str>,
+) -> Result<ModerationVerdictResponse, AppError> {
+    let status = if approve { ModerationStatus::Approved } else { ModerationStatus::Rejected };
+
+    let mut conn = pool.conn_write()?;
+    let (response, released) = run_in_transaction(&mut conn, |conn| {
+        let verdict = moderation_query::find_verdict_for_update(conn, verdict_id)?
+            .ok_or_else(|| AppError::NotFound("Moderation verdict not found".to_string()))?;
+        if verdict.status != ModerationStatus::Pending.as_str() {
+            return Err(AppError::Conflict("Moderation verdict has already been reviewed".to_string()));
+        }
+
+        let held = !approve;
+        let mut released = None;
+        match ModeratedContent::parse(&verdict.content_type) {
+            Some(ModeratedContent::Message) => {
+                let before = moderation_query::find_message(conn, verdict.content_id)?;
+                let after = moderation_query::set_message_held(conn, verdict.content_id, held)?;
+                if let (Some(before), Some(after)) = (before, after) {
+                    if before.held && !after.held {
+                        released = Some(MessageResponse::from(after));
+                    }
+                }
+            }
+            Some(ModeratedContent::HelpRequest) => {
+                moderation_query::set_help_request_held(conn, verdict.content_id, held)?;
+            }
+            Some(ModeratedContent::HelpComment) => {
+                moderation_query::set_help_comment_held(conn, verdict.content_id, held)?;
+            }
+            Some(ModeratedContent::CommunityPost) => {
+                community_query::set_post_held(conn, verdict.content_id, held)?;
+            }
+            None => {}
+        }
+
+        let verdict =
+            moderation_query::update_verdict_status(conn, verdict.id, status.as_str(), admin_id, Utc::now().naive_utc())?;
+        audit_service::record(
+            conn,
+            NewAuditLog::new(AuditAction::AdminAction, Some(admin_id), verdict.author_id, ip_address).with_details(
+                format!("moderation {} {}: {} {}", verdict.id, status.as_str(), verdict.content_type, verdict.content_id),
+            ),
+        )?;
+
+        let content = content_text(conn, &verdict)?;
+        Ok((ModerationVerdictResponse::new(verdict, content), released))
+    })?;
+
+    if let Some(message) = released {
+        event_bus.publish(AppEvent::MessageCreated { message });
+    }
+    Ok(response)
+}
@@ -1,36 +1,68 @@
-use crate::models::mood::{Mood, MoodResponse, MoodType}; // Now Mood will be used
+use crate::models::mood::{CreateMoodRequest, MoodCount, MoodDetails, MoodLocation, MoodResponse, MoodSearchFilter, MoodSummary, MoodType, NewMood, UpdateMoodRequest};
+use crate::models::report::{DailyScore, MoodRangeStats};
+use crate::utils::mood_interpretation::interpret_average_score;
+use crate::config::app_config::app_config;
+use crate::models::onboarding::OnboardingStep;
+use crate::service::onboarding_service;
 use crate::db::mood_query;
 use crate::errors::app_error::AppError;
-use diesel::r2d2;
+use crate::models::list_sort::ListSort;
+use crate::db::pool::DbPools;
+use crate::repo::MoodRepo;
+use crate::utils::stats_cache::{StatsCache, StatsKind};
+use std::sync::Arc;
+use chrono::{Duration, NaiveDate, Utc};
+use chrono_tz::Tz;
 use diesel::pg::PgConnection;
-use chrono::NaiveDate;
+use crate::utils::json_stream::{stream_json_array, JsonArrayStream};
+use crate::utils::text_limits::ensure_max_length;
+use crate::utils::patch::Patch;
+use crate::utils::streaks::{StreakEngine, StreakStats};
+use crate::utils::timezone::today_in;
+
+/// Batas hasil pencarian mood per halaman
+const MAX_SEARCH_LIMIT: i32 = 200;
 
 pub fn create_mood(
-    pool: &r2d2::Pool<r2d2::ConnectionManager<PgConnection>>,
+    moods: &dyn MoodRepo,
+    cache: &StatsCache,
     user_id: i32,
-    mood: &str,
-    emoji: &str,
-    notes: Option<String>,
-    date: Option<NaiveDate>,
+    data: CreateMoodRequest,
+    tz: Tz,
 ) -> Result<MoodResponse, AppError> {
-    let mut conn = pool
-        .get()
-        .map_err(|_| AppError::InternalServerError("Failed to get DB connection".to_string()))?;
+    let mood_data = moods.create(new_mood(user_id, data, tz)?)?;
+    cache.invalidate_user(user_id);
 
-    // Validate mood type and USE as_str() method
-    let mood_type = MoodType::from_str(mood)
-        .ok_or_else(|| AppError::BadRequest(format!("Invalid mood type: {}", mood)))?;
-    
-    // Now USE as_str() method to ensure consistency
-    let validated_mood = mood_type.as_str();
+    Ok(MoodResponse {
+        id: mood_data.id,
+        user_id: mood_data.user_id,
+        date: mood_data.date,
+        mood: mood_data.mood,
+        emoji: mood_data.emoji,
+        notes: mood_data.notes,
+        details: MoodDetails::from_json(mood_data.details),
+        location: MoodLocation::from_columns(mood_data.place_label, mood_data.latitude, mood_data.longitude),
+        is_pinned: mood_data.is_pinned,
+        created_at: mood_data.created_at,
+        updated_at: mood_data.updated_at,
+    })
+}
 
+/// Validasi dan simpan mood di dalam transaksi pemanggil; cache statistik diurus pemanggil
+pub fn insert_mood(
+    conn: &mut PgConnection,
+    user_id: i32,
+    data: CreateMoodRequest,
+    tz: Tz,
+) -> Result<MoodResponse, AppError> {
+    let new_mood = new_mood(user_id, data, tz)?;
     // Check if mood already exists for the date
-    let mood_date = date.unwrap_or_else(|| chrono::Utc::now().date_naive());
-    if mood_query::check_mood_exists_for_date(&mut conn, user_id, mood_date)? {
-        return Err(AppError::BadRequest("Mood already exists for this date".to_string()));
+    if mood_query::check_mood_exists_for_date(conn, user_id, new_mood.date)? {
+        return Err(AppError::Conflict("Mood already exists for this date".to_string()));
     }
 
-    let mood_data = mood_query::create_mood(&mut conn, user_id, validated_mood, emoji, notes, date)?;
+    let mood_data = mood_query::create_mood(conn, &new_mood)?;
+    onboarding_service::complete_step(conn, user_id, OnboardingStep::FirstMood)?;
 
     Ok(MoodResponse {
         id: mood_data.id,
@@ -39,21 +71,61 @@ pub fn create_mood(
         mood: mood_data.mood,
         emoji: mood_data.emoji,
         notes: mood_data.notes,
+        details: MoodDetails::from_json(mood_data.details),
+        location: MoodLocation::from_columns(mood_data.place_label, mood_data.latitude, mood_data.longitude),
+        is_pinned: mood_data.is_pinned,
         created_at: mood_data.created_at,
         updated_at: mood_data.updated_at,
     })
 }
 
+/// Validasi request dan ubah menjadi baris mood baru; tanggal kosong berarti hari ini di zona waktu pengguna
+fn new_mood(user_id: i32, data: CreateMoodRequest, tz: Tz) -> Result<NewMood, AppError> {
+    let CreateMoodRequest { mood, emoji, notes, details, location, date } = data;
+
+    // Validate mood type and USE as_str() method
+    let mood_type: MoodType = mood.parse().map_err(AppError::BadRequest)?;
+    
+    // Now USE as_str() method to ensure consistency
+    let validated_mood = mood_type.as_str();
+    let emoji = resolve_emoji(&mood_type, emoji.as_deref())?;
+
+    if let Some(ref details) = details {
+        details.validate().map_err(AppError::BadRequest)?;
+    }
+    if let Some(ref notes) = notes {
+        ensure_max_length("Notes", notes, app_config().mood_notes_max_length)?;
+    }
+    let details = details.and_then(|details| details.to_json());
+    let location = match location {
+        Some(location) => location.normalize().map_err(AppError::BadRequest)?,
+        None => None,
+    };
+
+    let now = Utc::now().naive_utc();
+    let location = location.unwrap_or_default();
+    Ok(NewMood {
+        user_id,
+        date: date.unwrap_or_else(|| today_in(tz)),
+        mood: validated_mood.to_string(),
+        emoji,
+        notes,
+        created_at: now,
+        updated_at: Some(now),
+        details,
+        place_label: location.label,
+        latitude: location.latitude,
+        longitude: location.longitude,
+    })
+}
+
 pub fn get_mood_by_id(
-    pool: &r2d2::Pool<r2d2::ConnectionManager<PgConnection>>,
+    moods: &dyn MoodRepo,
     mood_id: i32,
     user_id: i32,
 ) -> Result<MoodResponse, AppError> {
-    let mut conn = pool
-        .get()
-        .map_err(|_| AppError::InternalServerError("Failed to get DB connection".to_string()))?;
-
-    let mood = mood_query::find_mood_by_id(&mut conn, mood_id)
+    let mood = moods
+        .find_by_id(mood_id)
         .map_err(|_| AppError::NotFound("Mood not found".to_string()))?;
 
     // Check if user owns this mood
@@ -68,22 +140,23 @@ pub fn get_mood_by_id(
         mood: mood.mood,
         emoji: mood.emoji,
         notes: mood.notes,
+        details: MoodDetails::from_json(mood.details),
+        location: MoodLocation::from_columns(mood.place_label, mood.latitude, mood.longitude),
+        is_pinned: mood.is_pinned,
         created_at: mood.created_at,
         updated_at: mood.updated_at,
     })
 }
 
 pub fn get_user_moods(
-    pool: &r2d2::Pool<r2d2::ConnectionManager<PgConnection>>,
+    moods: &dyn MoodRepo,
     user_id: i32,
     limit: Option<i32>,
     offset: Option<i32>,
+    pinned: Option<bool>,
+    sort: ListSort,
 ) -> Result<Vec<MoodResponse>, AppError> {
-    let mut conn = pool
-        .get()
-        .map_err(|_| AppError::InternalServerError("Failed to get DB connection".to_string()))?;
-
-    let moods = mood_query::find_moods_by_user(&mut conn, user_id, limit, offset)?;
+    let moods = moods.find_by_user(user_id, limit, offset, pinned, sort)?;
 
     let mood_responses = moods.into_iter().map(|mood| MoodResponse {
         id: mood.id,
@@ -92,6 +165,9 @@ pub fn get_user_moods(
         mood: mood.mood,
         emoji: mood.emoji,
         notes: mood.notes,
+        details: MoodDetails::from_json(mood.details),
+        location: MoodLocation::from_columns(mood.place_label, mood.latitude, mood.longitude),
+        is_pinned: mood.is_pinned,
         created_at: mood.created_at,
         updated_at: mood.updated_at,
     }).collect();
@@ -100,15 +176,11 @@ pub fn get_user_moods(
 }
 
 pub fn get_mood_by_date(
-    pool: &r2d2::Pool<r2d2::ConnectionManager<PgConnection>>,
+    moods: &dyn MoodRepo,
     user_id: i32,
     date: NaiveDate,
 ) -> Result<MoodResponse, AppError> {
-    let mut conn = pool
-        .get()
-        .map_err(|_| AppError::InternalServerError("Failed to get DB connection".to_string()))?;
-
-    let mood = mood_query::find_mood_by_user_and_date(&mut conn, user_id, date)?;
+    let mood = moods.find_by_date(user_id, date)?;
 
     Ok(MoodResponse {
         id: mood.id,
@@ -117,26 +189,25 @@ pub fn get_mood_by_date(
         mood: mood.mood,
         emoji: mood.emoji,
         notes: mood.notes,
+        details: MoodDetails::from_json(mood.details),
+        location: MoodLocation::from_columns(mood.place_label, mood.latitude, mood.longitude),
+        is_pinned: mood.is_pinned,
         created_at: mood.created_at,
         updated_at: mood.updated_at,
     })
 }
 
 pub fn get_moods_by_date_range(
-    pool: &r2d2::Pool<r2d2::ConnectionManager<PgConnection>>,
+    moods: &dyn MoodRepo,
     user_id: i32,
     start_date: NaiveDate,
     end_date: NaiveDate,
 ) -> Result<Vec<MoodResponse>, AppError> {
-    let mut conn = pool
-        .get()
-        .map_err(|_| AppError::InternalServerError("Failed to get DB connection".to_string()))?;
-
     if start_date > end_date {
         return Err(AppError::BadRequest("Start date cannot be after end date".to_string()));
     }
 
-    let moods = mood_query::find_moods_by_date_range(&mut conn, user_id, start_date, end_date)?;
+    let moods = moods.find_by_date_range(user_id, start_date, end_date)?;
 
     let mood_responses = moods.into_iter().map(|mood| MoodResponse {
         id: mood.id,
@@ -145,6 +216,49 @@ pub fn get_moods_by_date_range(
         mood: mood.mood,
         emoji: mood.emoji,
         notes: mood.notes,
+        details: MoodDetails::from_json(mood.details),
+        location: MoodLocation::from_columns(mood.place_label, mood.latitude, mood.longitude),
+        is_pinned: mood.is_pinned,
+        created_at: mood.created_at,
+        updated_at: mood.updated_at,
+    }).collect();
+
+    Ok(mood_responses)
+}
+
+/// Cari mood berdasarkan isi catatan, opsional dibatasi jenis mood dan rentang tanggal
+pub fn search_moods(
+    moods: &dyn MoodRepo,
+    user_id: i32,
+    filter: &MoodSearchFilter,
+) -> Result<Vec<MoodResponse>, AppError> {
+    if filter.query.trim().is_empty() {
+        return Err(AppError::BadRequest("Search query cannot be empty".to_string()));
+    }
+    if filter.limit.is_some_and(|limit| limit <= 0 || limit > MAX_SEARCH_LIMIT) {
+        return Err(AppError::BadRequest(format!("Limit must be between 1 and {}", MAX_SEARCH_LIMIT)));
+    }
+    if filter.offset.is_some_and(|offset| offset < 0) {
+        return Err(AppError::BadRequest("Offset cannot be negative".to_string()));
+    }
+    if let (Some(start_date), Some(end_date)) = (filter.start_date, filter.end_date) {
+        if start_date > end_date {
+            return Err(AppError::BadRequest("Start date cannot be after end date".to_string()));
+        }
+    }
+
+    let moods = moods.search(user_id, filter)?;
+
+    let mood_responses = moods.into_iter().map(|mood| MoodResponse {
+        id: mood.id,
+        user_id: mood.user_id,
+        date: mood.date,
+        mood: mood.mood,
+        emoji: mood.emoji,
+        notes: mood.notes,
+        details: MoodDetails::from_json(mood.details),
+        location: MoodLocation::from_columns(mood.place_label, mood.latitude, mood.longitude),
+        is_pinned: mood.is_pinned,
         created_at: mood.created_at,
         updated_at: mood.updated_at,
     }).collect();
@@ -153,44 +267,40 @@ pub fn get_moods_by_date_range(
 }
 
 pub fn update_mood_with_date(
-    pool: &r2d2::Pool<r2d2::ConnectionManager<PgConnection>>,
+    moods: &dyn MoodRepo,
+    cache: &StatsCache,
     mood_id: i32,
     user_id: i32,
-    new_mood: Option<String>,
-    new_emoji: Option<String>,
-    new_notes: Option<String>,
-    new_date: Option<NaiveDate>, // ✅ TAMBAH PARAMETER DATE
+    data: UpdateMoodRequest,
 ) -> Result<MoodResponse, AppError> {
-    let mut conn = pool
-        .get()
-        .map_err(|_| AppError::InternalServerError("Failed to get DB connection".to_string()))?;
-
     // Validate mood type if provided
-    let validated_mood = if let Some(ref mood) = new_mood {
-        let mood_type = MoodType::from_str(mood)
-            .ok_or_else(|| AppError::BadRequest(format!("Invalid mood type: {}", mood)))?;
-        Some(mood_type.as_str().to_string())
-    } else {
-        None
+    let mood_type = match data.mood {
+        Some(ref mood) => Some(mood.parse::<MoodType>().map_err(AppError::BadRequest)?),
+        None => None,
+    };
+    let validated_mood = mood_type.as_ref().map(|mood_type| mood_type.as_str().to_string());
+    let emoji = match (data.emoji.as_deref(), mood_type.as_ref()) {
+        (Some(emoji), _) => Some(validate_emoji(emoji)?),
+        (None, Some(mood_type)) => Some(mood_type.default_emoji().to_string()),
+        (None, None) => None,
     };
 
-    // ✅ JIKA ADA DATE BARU, CEK DUPLIKASI
-    if let Some(date) = new_date {
-        // Check if another mood exists for this date (excluding current mood)
-        if mood_query::check_mood_exists_for_date_excluding(&mut conn, user_id, date, mood_id)? {
-            return Err(AppError::BadRequest("Another mood already exists for this date".to_string()));
-        }
+    if let Some(details) = data.details.value() {
+        details.validate().map_err(AppError::BadRequest)?;
+    }
+    if let Some(notes) = data.notes.value() {
+        ensure_max_length("Notes", notes, app_config().mood_notes_max_length)?;
     }
 
-    let updated_mood = mood_query::update_mood_with_date(
-        &mut conn, 
-        mood_id, 
-        user_id, 
-        validated_mood, 
-        new_emoji, 
-        new_notes,
-        new_date 
-    )?;
+    let location = match data.location {
+        Patch::Value(location) => location.normalize().map_err(AppError::BadRequest)?.into(),
+        location => location,
+    };
+
+    let changes = UpdateMoodRequest { mood: validated_mood, emoji, location, ..data };
+
+    let updated_mood = moods.update(mood_id, user_id, changes)?;
+    cache.invalidate_user(user_id);
 
     Ok(MoodResponse {
         id: updated_mood.id,
@@ -199,44 +309,101 @@ pub fn update_mood_with_date(
         mood: updated_mood.mood,
         emoji: updated_mood.emoji,
         notes: updated_mood.notes,
+        details: MoodDetails::from_json(updated_mood.details),
+        location: MoodLocation::from_columns(updated_mood.place_label, updated_mood.latitude, updated_mood.longitude),
+        is_pinned: updated_mood.is_pinned,
         created_at: updated_mood.created_at,
         updated_at: updated_mood.updated_at,
     })
 }
 
+/// Hapus mood ke tempat sampah; masih bisa dikembalikan lewat `restore_mood`
+/// selama `mood_undo_window_minutes`
 pub fn delete_mood(
-    pool: &r2d2::Pool<r2d2::ConnectionManager<PgConnection>>,
+    moods: &dyn MoodRepo,
+    cache: &StatsCache,
     mood_id: i32,
     user_id: i32,
 ) -> Result<(), AppError> {
-    let mut conn = pool
-        .get()
-        .map_err(|_| AppError::InternalServerError("Failed to get DB connection".to_string()))?;
-
-    let deleted = mood_query::delete_mood(&mut conn, mood_id, user_id)?;
+    let deleted = moods.move_to_trash(mood_id, user_id, Utc::now().naive_utc())?;
     if !deleted {
         return Err(AppError::NotFound("Mood not found".to_string()));
     }
+    cache.invalidate_user(user_id);
 
     Ok(())
 }
 
+/// Batalkan penghapusan mood yang masih dalam jendela undo
+pub fn restore_mood(
+    moods: &dyn MoodRepo,
+    cache: &StatsCache,
+    mood_id: i32,
+    user_id: i32,
+) -> Result<MoodResponse, AppError> {
+    let deleted_after = Utc::now().naive_utc() - Duration::minutes(app_config().mood_undo_window_minutes);
+    let mood = moods.restore(mood_id, user_id, deleted_after)?;
+    cache.invalidate_user(user_id);
+
+    Ok(MoodResponse {
+        id: mood.id,
+        user_id: mood.user_id,
+        date: mood.date,
+        mood: mood.mood,
+        emoji: mood.emoji,
+        notes: mood.notes,
+        details: MoodDetails::from_json(mood.details),
+        location: MoodLocation::from_columns(mood.place_label, mood.latitude, mood.longitude),
+        is_pinned: mood.is_pinned,
+        created_at: mood.created_at,
+        updated_at: mood.updated_at,
+    })
+}
+
+/// Hapus permanen mood di tempat sampah yang jendela undo-nya sudah lewat
+pub fn purge_expired_trash(pool: &DbPools) -> Result<usize, AppError> {
+    let mut conn = pool.conn_write()?;
+
+    let deleted_before = Utc::now().naive_utc() - Duration::minutes(app_config().mood_undo_window_minutes);
+    mood_query::purge_trashed_moods(&mut conn, deleted_before)
+}
+
+pub fn set_mood_pinned(
+    moods: &dyn MoodRepo,
+    mood_id: i32,
+    user_id: i32,
+    pinned: bool,
+) -> Result<MoodResponse, AppError> {
+    let mood = moods.set_pinned(mood_id, user_id, pinned)?;
+
+    Ok(MoodResponse {
+        id: mood.id,
+        user_id: mood.user_id,
+        date: mood.date,
+        mood: mood.mood,
+        emoji: mood.emoji,
+        notes: mood.notes,
+        details: MoodDetails::from_json(mood.details),
+        location: MoodLocation::from_columns(mood.place_label, mood.latitude, mood.longitude),
+        is_pinned: mood.is_pinned,
+        created_at: mood.created_at,
+        updated_at: mood.updated_at,
+    })
+}
+
 pub fn get_recent_moods(
-    pool: &r2d2::Pool<r2d2::ConnectionManager<PgConnection>>,
+    moods: &dyn MoodRepo,
     user_id: i32,
     days: Option<i32>,
+    tz: Tz,
 ) -> Result<Vec<MoodResponse>, AppError> {
-    let mut conn = pool
-        .get()
-        .map_err(|_| AppError::InternalServerError("Failed to get DB connection".to_string()))?;
-
     let days = days.unwrap_or(7);
     
     if days <= 0 || days > 365 {
         return Err(AppError::BadRequest("Days must be between 1 and 365".to_string()));
     }
 
-    let moods = mood_query::get_recent_moods(&mut conn, user_id, days)?;
+    let moods = moods.find_recent(user_id, days, today_in(tz))?;
 
     let mood_responses = moods.into_iter().map(|mood| MoodResponse {
         id: mood.id,
@@ -245,6 +412,9 @@ pub fn get_recent_moods(
         mood: mood.mood,
         emoji: mood.emoji,
         notes: mood.notes,
+        details: MoodDetails::from_json(mood.details),
+        location: MoodLocation::from_columns(mood.place_label, mood.latitude, mood.longitude),
+        is_pinned: mood.is_pinned,
         created_at: mood.created_at,
         updated_at: mood.updated_at,
     }).collect();
@@ -253,110 +423,279 @@ pub fn get_recent_moods(
 }
 
 pub fn get_mood_stats_count(
-    pool: &r2d2::Pool<r2d2::ConnectionManager<PgConnection>>,
+    moods: &dyn MoodRepo,
+    cache: &StatsCache,
     user_id: i32,
 ) -> Result<i64, AppError> {
-    let mut conn = pool
-        .get()
-        .map_err(|_| AppError::InternalServerError("Failed to get DB connection".to_string()))?;
-
-    mood_query::get_mood_stats_simple(&mut conn, user_id)
+    let version = moods.latest_update(user_id)?;
+    cache.get_or_compute(user_id, StatsKind::MoodCount, version, || moods.count(user_id))
 }
 
 pub fn get_mood_streak(
-    pool: &r2d2::Pool<r2d2::ConnectionManager<PgConnection>>,
+    moods: &dyn MoodRepo,
     user_id: i32,
-) -> Result<i32, AppError> {
-    let mut conn = pool
-        .get()
-        .map_err(|_| AppError::InternalServerError("Failed to get DB connection".to_string()))?;
+    tz: Tz,
+) -> Result<StreakStats, AppError> {
+    let engine = StreakEngine::new(tz);
+    let dates = moods.dates_until(user_id, today_in(tz))?;
 
-    let recent_moods = mood_query::get_recent_moods(&mut conn, user_id, 30)?;
-    
-    if recent_moods.is_empty() {
-        return Ok(0);
-    }
+    Ok(engine.summarize(dates))
+}
 
-    let today = chrono::Utc::now().date_naive();
-    let mut streak = 0;
-    let mut current_date = today;
-
-    for mood in recent_moods {
-        if mood.date == current_date {
-            streak += 1;
-            current_date = current_date.pred_opt().unwrap_or(current_date);
-        } else if mood.date < current_date {
-            // Gap in streak, break
-            break;
+/// Semua mood pengguna sebagai array JSON yang di-stream dari database
+pub fn get_all_user_moods(
+    moods: Arc<dyn MoodRepo>,
+    user_id: i32,
+) -> Result<JsonArrayStream, AppError> {
+    Ok(stream_json_array(move |out| {
+        moods.for_each_by_user(user_id, &mut |mood| {
+            out.push(&MoodResponse {
+                id: mood.id,
+                user_id: mood.user_id,
+                date: mood.date,
+                mood: mood.mood,
+                emoji: mood.emoji,
+                notes: mood.notes,
+                details: MoodDetails::from_json(mood.details),
+                location: MoodLocation::from_columns(mood.place_label, mood.latitude, mood.longitude),
+                is_pinned: mood.is_pinned,
+                created_at: mood.created_at,
+                updated_at: mood.updated_at,
+            })
+        })
+    }))
+}
+
+/// Statistik mood dengan skor; agregasi dilakukan di database agar tetap cepat saat riwayat bertambah
+pub fn get_mood_stats_with_scores(
+    moods: &dyn MoodRepo,
+    cache: &StatsCache,
+    user_id: i32,
+) -> Result<serde_json::Value, AppError> {
+    let version = moods.latest_update(user_id)?;
+    let mut stats = cache.get_or_compute(user_id, StatsKind::MoodScores, version, || {
+        let mood_counts: std::collections::HashMap<String, i64> =
+            moods.count_by_type(user_id, None)?.into_iter().collect();
+        let total_entries: i64 = mood_counts.values().sum();
+
+        if total_entries == 0 {
+            return Ok(serde_json::json!({
+                "total_entries": 0,
+                "average_score": 0.0,
+                "mood_distribution": {}
+            }));
         }
+
+        let average_score = moods.average_score(user_id, None)?.unwrap_or(0.0);
+
+        Ok(serde_json::json!({
+            "total_entries": total_entries,
+            "average_score": average_score,
+            "mood_distribution": mood_counts
+        }))
+    })?;
+
+    // Label bergantung bahasa request, jadi tidak ikut disimpan di cache
+    let average_score = stats
+        .get("total_entries")
+        .and_then(|total| total.as_i64())
+        .filter(|total| *total > 0)
+        .and_then(|_| stats.get("average_score"))
+        .and_then(|score| score.as_f64());
+    let interpretation = interpret_average_score(average_score, app_config());
+    if let Some(object) = stats.as_object_mut() {
+        object.insert("interpretation".to_string(), serde_json::json!(interpretation));
     }
 
-    Ok(streak)
+    Ok(stats)
 }
 
-// NEW: Function to get ALL user moods (uses get_all_moods_by_user)
-pub fn get_all_user_moods(
-    pool: &r2d2::Pool<r2d2::ConnectionManager<PgConnection>>,
+/// Jumlah, rata-rata skor dan distribusi seluruh mood, dengan agregasi SQL yang sama dengan `/moods/stats/advanced`
+pub fn get_mood_summary(
+    moods: &dyn MoodRepo,
     user_id: i32,
-) -> Result<Vec<MoodResponse>, AppError> {
-    let mut conn = pool
-        .get()
-        .map_err(|_| AppError::InternalServerError("Failed to get DB connection".to_string()))?;
+) -> Result<MoodSummary, AppError> {
+    let mood_counts = moods.count_by_type(user_id, None)?;
+    let total_entries: i64 = mood_counts.iter().map(|(_, count)| count).sum();
+    let average_score = moods.average_score(user_id, None)?.unwrap_or(0.0);
+
+    Ok(MoodSummary {
+        total_entries,
+        average_score,
+        mood_distribution: distribution_from_counts(&mood_counts),
+    })
+}
 
-    // NOW USING get_all_moods_by_user function
-    let moods = mood_query::get_all_moods_by_user(&mut conn, user_id)?;
+/// Statistik mood untuk rentang tanggal bebas, memakai agregasi SQL yang sama dengan `/moods/stats/advanced`
+pub fn get_mood_stats_for_range(
+    pool: &DbPools,
+    user_id: i32,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+) -> Result<MoodRangeStats, AppError> {
+    if start_date > end_date {
+        return Err(AppError::BadRequest("Start date cannot be after end date".to_string()));
+    }
 
-    let mood_responses = moods.into_iter().map(|mood| MoodResponse {
-        id: mood.id,
-        user_id: mood.user_id,
-        date: mood.date,
-        mood: mood.mood,
-        emoji: mood.emoji,
-        notes: mood.notes,
-        created_at: mood.created_at,
-        updated_at: mood.updated_at,
-    }).collect();
+    let mut conn = pool.conn_read()?;
 
-    Ok(mood_responses)
+    let range = Some((start_date, end_date));
+    let mood_counts = mood_query::count_moods_by_type(&mut conn, user_id, range)?;
+    let total_entries: i64 = mood_counts.iter().map(|(_, count)| count).sum();
+    let average_score = mood_query::average_mood_score(&mut conn, user_id, range)?;
+    let day_score = |(date, mood): (NaiveDate, String)| {
+        let score = mood.parse::<MoodType>().ok()?.score();
+        Some(DailyScore { date, mood, score })
+    };
+    let best_day = mood_query::find_extreme_mood_day(&mut conn, user_id, range, true)?.and_then(day_score);
+    let worst_day = mood_query::find_extreme_mood_day(&mut conn, user_id, range, false)?.and_then(day_score);
+
+
+    Ok(MoodRangeStats {
+        start_date,
+        end_date,
+        total_entries,
+        average_score,
+        interpretation: interpret_average_score(average_score, app_config()),
+        mood_distribution: distribution_from_counts(&mood_counts),
+        best_day,
+        worst_day,
+    })
 }
 
-// NEW: Function to get mood statistics with scores (uses score() method)
-pub fn get_mood_stats_with_scores(
-    pool: &r2d2::Pool<r2d2::ConnectionManager<PgConnection>>,
-    user_id: i32,
-) -> Result<serde_json::Value, AppError> {
-    let mut conn = pool
-        .get()
-        .map_err(|_| AppError::InternalServerError("Failed to get DB connection".to_string()))?;
-
-    // Use get_all_moods_by_user to get all moods
-    let moods: Vec<Mood> = mood_query::get_all_moods_by_user(&mut conn, user_id)?; // NOW Mood is used!
-
-    if moods.is_empty() {
-        return Ok(serde_json::json!({
-            "total_entries": 0,
-            "average_score": 0.0,
-            "mood_distribution": {}
-        }));
+/// Distribusi semua jenis mood (termasuk yang nol) dari hasil `count_moods_by_type`
+pub fn distribution_from_counts(mood_counts: &[(String, i64)]) -> Vec<MoodCount> {
+    let total_entries: i64 = mood_counts.iter().map(|(_, count)| count).sum();
+    [MoodType::VeryHappy, MoodType::Happy, MoodType::Neutral, MoodType::Sad, MoodType::VerySad]
+        .iter()
+        .map(|mood_type| {
+            let count = mood_counts
+                .iter()
+                .find(|(mood, _)| mood == mood_type.as_str())
+                .map_or(0, |(_, count)| *count);
+            MoodCount {
+                mood: mood_type.as_str().to_string(),
+                count,
+                percentage: if total_entries > 0 { count as f64 * 100.0 / total_entries as f64 } else { 0.0 },
+            }
+        })
+        .collect()
+}
+
+/// Emoji dari request, atau emoji bawaan jenis mood jika kosong
+fn resolve_emoji(mood_type: &MoodType, emoji: Option<&str>) -> Result<String, AppError> {
+    match emoji.map(str::trim).filter(|emoji| !emoji.is_empty()) {
+        Some(emoji) => validate_emoji(emoji),
+        None => Ok(mood_type.default_emoji().to_string()),
     }
+}
 
-    // Calculate statistics using score() method
-    let mut total_score = 0i32;
-    let mut mood_counts: std::collections::HashMap<String, i32> = std::collections::HashMap::new();
+fn validate_emoji(emoji: &str) -> Result<String, AppError> {
+    let emoji = emoji.trim();
+    let is_default = MoodType::ALL.iter().any(|mood_type| mood_type.default_emoji() == emoji);
+    if is_default || app_config().mood_emoji_whitelist.iter().any(|allowed| allowed == emoji) {
+        Ok(emoji.to_string())
+    } else {
+        Err(AppError::BadRequest(format!("Unsupported emoji: {}", emoji)))
+    }
+}
 
-    for mood in &moods {
-        // USE score() method here!
-        if let Some(mood_type) = MoodType::from_str(&mood.mood) {
-            total_score += mood_type.score(); // NOW score() method is used!
-            *mood_counts.entry(mood.mood.clone()).or_insert(0) += 1;
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repo::fake::FakeMoodRepo;
+
+    #[test]
+    fn moods_are_scoped_to_their_owner() {
+        let date = NaiveDate::from_ymd_opt(2026, 10, 1).unwrap();
+        let repo = FakeMoodRepo::with(vec![
+            FakeMoodRepo::mood(1, 7, date, "happy"),
+            FakeMoodRepo::mood(2, 8, date, "sad"),
+        ]);
+        let cache = StatsCache::new(10, std::time::Duration::from_secs(60));
+
+        assert_eq!(get_mood_by_id(&repo, 1, 7).unwrap().mood, "happy");
+        assert!(matches!(get_mood_by_id(&repo, 2, 7), Err(AppError::BadRequest(_))));
+        assert!(matches!(get_mood_by_id(&repo, 3, 7), Err(AppError::NotFound(_))));
+        assert!(matches!(get_moods_by_date_range(&repo, 7, date, date.pred_opt().unwrap()), Err(AppError::BadRequest(_))));
+
+        assert!(matches!(delete_mood(&repo, &cache, 2, 7), Err(AppError::NotFound(_))));
+        delete_mood(&repo, &cache, 1, 7).unwrap();
+        assert!(get_user_moods(&repo, 7, None, None, None, ListSort::DateDesc).unwrap().is_empty());
+        assert_eq!(get_user_moods(&repo, 8, None, None, None, ListSort::DateDesc).unwrap().len(), 1);
     }
 
-    let average_score = total_score as f64 / moods.len() as f64;
+    #[test]
+    fn writes_and_stats_go_through_the_repo() {
+        let date = NaiveDate::from_ymd_opt(2026, 10, 1).unwrap();
+        let repo = FakeMoodRepo::default();
+        let cache = StatsCache::new(10, std::time::Duration::from_secs(60));
+        let request = |mood: &str, date| CreateMoodRequest {
+            mood: mood.to_string(),
+            emoji: None,
+            notes: None,
+            details: None,
+            location: None,
+            date: Some(date),
+        };
+
+        let first = create_mood(&repo, &cache, 7, request("happy", date), Tz::UTC).unwrap();
+        assert!(matches!(create_mood(&repo, &cache, 7, request("sad", date), Tz::UTC), Err(AppError::Conflict(_))));
+        let second = create_mood(&repo, &cache, 7, request("sad", date.succ_opt().unwrap()), Tz::UTC).unwrap();
+        let move_to_first_date = UpdateMoodRequest {
+            mood: None,
+            emoji: None,
+            notes: Patch::Absent,
+            details: Patch::Absent,
+            location: Patch::Absent,
+            date: Some(date),
+        };
+        assert!(matches!(
+            update_mood_with_date(&repo, &cache, second.id, 7, move_to_first_date),
+            Err(AppError::Conflict(_))
+        ));
+        assert_eq!(get_mood_stats_count(&repo, &cache, 7).unwrap(), 2);
+        assert_eq!(get_mood_summary(&repo, 7).unwrap().average_score, 3.0);
+
+        delete_mood(&repo, &cache, first.id, 7).unwrap();
+        assert_eq!(get_mood_stats_count(&repo, &cache, 7).unwrap(), 1);
+        assert_eq!(restore_mood(&repo, &cache, first.id, 7).unwrap().date, date);
+        assert!(matches!(restore_mood(&repo, &cache, first.id, 7), Err(AppError::NotFound(_))));
+        assert_eq!(get_mood_stats_count(&repo, &cache, 7).unwrap(), 2);
+    }
 
-    Ok(serde_json::json!({
-        "total_entries": moods.len(),
-        "average_score": average_score,
-        "mood_distribution": mood_counts
-    }))
-}
\ No newline at end of file
+    #[test]
+    fn search_matches_notes_case_insensitively_within_filters() {
+        let date = NaiveDate::from_ymd_opt(2026, 10, 1).unwrap();
+        let mut first = FakeMoodRepo::mood(1, 7, date, "happy");
+        first.notes = Some("Jalan pagi di Taman".to_string());
+        let mut second = FakeMoodRepo::mood(2, 7, date.succ_opt().unwrap(), "sad");
+        second.notes = Some("hujan, tidak jadi ke taman".to_string());
+        let repo = FakeMoodRepo::with(vec![first, second, FakeMoodRepo::mood(3, 7, date, "happy")]);
+
+        let filter = |mood: Option<MoodType>| MoodSearchFilter {
+            query: "taman".to_string(),
+            mood,
+            start_date: None,
+            end_date: None,
+            limit: None,
+            offset: None,
+        };
+        let ids: Vec<i32> = search_moods(&repo, 7, &filter(None)).unwrap().iter().map(|mood| mood.id).collect();
+        assert_eq!(ids, vec![2, 1]);
+        assert_eq!(search_moods(&repo, 7, &filter(Some(MoodType::Happy))).unwrap().len(), 1);
+        assert!(search_moods(&repo, 8, &filter(None)).unwrap().is_empty());
+        assert!(matches!(
+            search_moods(&repo, 7, &MoodSearchFilter { query: " ".to_string(), ..filter(None) }),
+            Err(AppError::BadRequest(_))
+        ));
+        assert!(matches!(
+            search_moods(&repo, 7, &MoodSearchFilter { limit: Some(MAX_SEARCH_LIMIT + 1), ..filter(None) }),
+            Err(AppError::BadRequest(_))
+        ));
+        assert!(matches!(
+            search_moods(&repo, 7, &MoodSearchFilter { offset: Some(-1), ..filter(None) }),
+            Err(AppError::BadRequest(_))
+        ));
+    }
+}
@@ -0,0 +1,223 @@
+use std::collections::HashSet;
+use chrono::{Duration, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use diesel::pg::PgConnection;
+use crate::db::{device_query, insight_query, mood_query, user_query};
+use crate::db::pool::DbPools;
+use crate::errors::app_error::AppError;
+use crate::i18n::{t_in, Locale};
+use crate::models::insight::NewInsightNotification;
+use crate::models::notification::{
+    Notification, NotificationCategory, NotificationChannel, NotificationPreferences,
+};
+use crate::models::user::{User, UserSettings};
+use crate::service::{checkin_service, push_service};
+use crate::utils::mailer::{EmailMessage, Mailer};
+use crate::utils::push::PushMessage;
+use crate::utils::timezone::parse_timezone;
+
+/// Pengingat tetap dikirim jika job terlambat (restart/deploy) selama masih dalam jendela ini
+const REMINDER_WINDOW_MINUTES: i64 = 15;
+/// Kind di insight_notifications untuk mencegah pengingat terkirim dua kali sehari
+const REMINDER_NOTIFICATION_KIND: &str = "daily_reminder";
+
+#[derive(Debug, Default)]
+pub struct DispatchResult {
+    pub email_sent: bool,
+    pub push_queued: usize,
+}
+
+impl DispatchResult {
+    pub fn delivered(&self) -> bool {
+        self.email_sent || self.push_queued > 0
+    }
+}
+
+/// Apakah pengguna ingin menerima kategori ini, tanpa melihat channel dan jam tenang
+pub fn wants(settings: &UserSettings, category: NotificationCategory) -> bool {
+    match settings.notifications.as_ref().and_then(|prefs| prefs.categories.as_ref()) {
+        Some(categories) => categories.iter().any(|name| name == category.as_str()),
+        None => match category {
+            NotificationCategory::Reminder
+            | NotificationCategory::HelpRequest
+            | NotificationCategory::Appointment => true,
+            NotificationCategory::InsightAlert => settings.insight_notifications == Some(true),
+        },
+    }
+}
+
+/// Validasi preferensi notifikasi sebelum disimpan ke settings
+pub fn validate_preferences(prefs: &NotificationPreferences) -> Result<(), AppError> {
+    for channel in prefs.channels.iter().flatten() {
+        if NotificationChannel::parse(channel).is_none() {
+            return Err(AppError::BadRequest(format!("Unknown notification channel: {}", channel)));
+        }
+    }
+    for category in prefs.categories.iter().flatten() {
+        if NotificationCategory::parse(category).is_none() {
+            return Err(AppError::BadRequest(format!("Unknown notification category: {}", category)));
+        }
+    }
+    if let Some(ref quiet_hours) = prefs.quiet_hours {
+        match quiet_hours.parse_times() {
+            Some((start, end)) if start != end => {}
+            _ => {
+                return Err(AppError::BadRequest(
+                    "Invalid quiet hours (expected different HH:MM start and end)".to_string(),
+                ))
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Satu-satunya jalur pengiriman email/push ke pengguna. Kategori dan channel disaring
+/// sesuai preferensi; saat jam tenang push ditunda sampai jam tenang berakhir, sedangkan
+/// email (tanpa antrian) dilewati.
+pub fn dispatch(
+    conn: &mut PgConnection,
+    mailer: &dyn Mailer,
+    user: &User,
+    notification: &Notification,
+) -> Result<DispatchResult, AppError> {
+    let settings = UserSettings::parse(user.settings.as_deref());
+    let mut result = DispatchResult::default();
+    if !wants(&settings, notification.category) {
+        return Ok(result);
+    }
+
+    let tz = settings.timezone.as_deref().and_then(parse_timezone).unwrap_or(Tz::UTC);
+    let now = Utc::now();
+    let quiet_until = settings
+        .notifications
+        .as_ref()
+        .and_then(|prefs| prefs.quiet_hours.as_ref())
+        .and_then(|quiet_hours| quiet_hours.ends_after(now.with_timezone(&tz).naive_local()))
+        .map(|local_end| local_to_utc(tz, local_end));
+
+    for channel in enabled_channels(&settings, notification.channels) {
+        match channel {
+            NotificationChannel::Email => {
+                // Akun tamu hanya punya alamat pengganti
+                if quiet_until.is_some() || user.is_guest {
+                    continue;
+                }
+                mailer.send(&EmailMessage {
+                    to: user.email.clone(),
+                    subject: notification.title.clone(),
+                    body: notification.email_body.clone().unwrap_or_else(|| notification.body.clone()),
+                })?;
+                result.email_sent = true;
+            }
+            NotificationChannel::Push => {
+                result.push_queued += push_service::enqueue_for_user(
+                    conn,
+                    user.id,
+                    &PushMessage {
+                        category: notification.category.as_str().to_string(),
+                        title: notification.title.clone(),
+                        body: notification.body.clone(),
+                    },
+                    quiet_until.unwrap_or_else(|| now.naive_utc()),
+                )?;
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+/// Kirim pengingat harian untuk pengguna yang sudah mencapai `reminder_time`
+/// di zona waktunya dan belum mencatat mood hari ini. Push dikirim ke pengguna dengan
+/// perangkat terdaftar; email berisi link check-in hanya untuk yang mengaktifkan `email_checkin`.
+pub fn send_due_reminders(pool: &DbPools, mailer: &dyn Mailer) -> Result<usize, AppError> {
+    let mut conn = pool.conn_write()?;
+
+    let with_devices: HashSet<i32> = device_query::find_user_ids_with_devices(&mut conn)?.into_iter().collect();
+    let mut sent = 0;
+    for user in user_query::find_users_with_setting(&mut conn, "reminder_time")? {
+        let user_id = user.id;
+        let settings = UserSettings::parse(user.settings.as_deref());
+        let email_checkin = settings.email_checkin == Some(true) && !user.is_guest;
+        let channels: &[NotificationChannel] = match (with_devices.contains(&user_id), email_checkin) {
+            (true, true) => &[NotificationChannel::Push, NotificationChannel::Email],
+            (true, false) => &[NotificationChannel::Push],
+            (false, true) => &[NotificationChannel::Email],
+            (false, false) => continue,
+        };
+        let Some(reminder_time) = settings
+            .reminder_time
+            .as_deref()
+            .and_then(|time| NaiveTime::parse_from_str(time, "%H:%M").ok())
+        else {
+            continue;
+        };
+        if !wants(&settings, NotificationCategory::Reminder) {
+            continue;
+        }
+
+        let tz = settings.timezone.as_deref().and_then(parse_timezone).unwrap_or(Tz::UTC);
+        let local_now = Utc::now().with_timezone(&tz).naive_local();
+        let today = local_now.date();
+        let minutes_since = (local_now.time() - reminder_time).num_minutes();
+        if !(0..REMINDER_WINDOW_MINUTES).contains(&minutes_since) {
+            continue;
+        }
+
+        if mood_query::check_mood_exists_for_date(&mut conn, user_id, today)? {
+            continue;
+        }
+
+        let notification = NewInsightNotification {
+            user_id,
+            kind: REMINDER_NOTIFICATION_KIND,
+            start_date: today,
+        };
+        if !insight_query::record_notification(&mut conn, &notification)? {
+            continue;
+        }
+
+        let locale = settings.language.as_deref().and_then(Locale::parse).unwrap_or_default();
+        let email_body = if email_checkin {
+            let links = checkin_service::checkin_links(user_id, today)?;
+            Some(checkin_service::checkin_email_body(locale, &user.username, &links))
+        } else {
+            None
+        };
+        let result = dispatch(
+            &mut conn,
+            mailer,
+            &user,
+            &Notification {
+                category: NotificationCategory::Reminder,
+                channels,
+                title: t_in(locale, "reminder.push.title"),
+                body: t_in(locale, "reminder.push.body"),
+                email_body,
+            },
+        )?;
+        if result.delivered() {
+            sent += 1;
+        }
+    }
+
+    Ok(sent)
+}
+
+fn enabled_channels(settings: &UserSettings, supported: &[NotificationChannel]) -> Vec<NotificationChannel> {
+    let chosen = settings.notifications.as_ref().and_then(|prefs| prefs.channels.as_ref());
+    supported
+        .iter()
+        .copied()
+        .filter(|channel| chosen.is_none_or(|names| names.iter().any(|name| name == channel.as_str())))
+        .collect()
+}
+
+/// Waktu lokal ke UTC; jam yang hilang karena DST digeser satu jam ke depan
+fn local_to_utc(tz: Tz, local: NaiveDateTime) -> NaiveDateTime {
+    tz.from_local_datetime(&local)
+        .earliest()
+        .or_else(|| tz.from_local_datetime(&(local + Duration::hours(1))).earliest())
+        .map(|time| time.naive_utc())
+        .unwrap_or(local)
+}
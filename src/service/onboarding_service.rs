@@ -0,0 +1,61 @@
+use crate::models::onboarding::{OnboardingStatus, OnboardingStep, OnboardingStepStatus};
+use crate::models::user::User;
+use crate::db::onboarding_query;
+use crate::errors::app_error::AppError;
+use crate::db::pool::DbPools;
+use diesel::pg::PgConnection;
+
+/// Hook untuk service lain: tandai langkah onboarding selesai
+pub fn complete_step(
+    conn: &mut PgConnection,
+    user_id: i32,
+    step: OnboardingStep,
+) -> Result<(), AppError> {
+    onboarding_query::mark_step_completed(conn, user_id, step)
+}
+
+/// Profil dianggap lengkap jika umur, gender dan avatar sudah diisi
+pub fn complete_profile_step_if_filled(
+    conn: &mut PgConnection,
+    user: &User,
+) -> Result<(), AppError> {
+    let filled = user.age.is_some()
+        && user.gender.as_deref().is_some_and(|g| !g.trim().is_empty())
+        && user.avatar.as_deref().is_some_and(|a| !a.trim().is_empty());
+
+    if filled {
+        complete_step(conn, user.id, OnboardingStep::ProfileFilled)?;
+    }
+    Ok(())
+}
+
+pub fn get_onboarding_status(
+    pool: &DbPools,
+    user_id: i32,
+) -> Result<OnboardingStatus, AppError> {
+    let mut conn = pool.conn_read()?;
+
+    let onboarding = onboarding_query::find_onboarding_by_user(&mut conn, user_id)?;
+
+    let steps: Vec<OnboardingStepStatus> = OnboardingStep::ALL
+        .iter()
+        .map(|step| {
+            let completed_at = onboarding.as_ref().and_then(|o| o.completed_at(*step));
+            OnboardingStepStatus {
+                step: step.as_str().to_string(),
+                completed: completed_at.is_some(),
+                completed_at,
+            }
+        })
+        .collect();
+
+    let completed_steps = steps.iter().filter(|s| s.completed).count();
+    let total_steps = steps.len();
+
+    Ok(OnboardingStatus {
+        steps,
+        completed_steps,
+        total_steps,
+        is_complete: completed_steps == total_steps,
+    })
+}
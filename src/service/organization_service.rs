@@ -0,0 +1,370 @@
+use std::collections::HashMap;
+use chrono::{Duration, NaiveDate, Utc};
+use diesel::pg::PgConnection;
+use rand::Rng;
+use crate::db::{mood_query, organization_query, user_query};
+use crate::db::pool::DbPools;
+use crate::db::transaction::run_in_transaction;
+use crate::errors::app_error::AppError;
+use crate::models::audit::{AuditAction, NewAuditLog};
+use crate::models::mood::{Mood, MoodType};
+use crate::models::organization::{
+    AcceptInvitationRequest, CreateOrganizationRequest, InvitationResponse, InviteMemberRequest, MemberMoodSummary,
+    NewOrganization, NewOrganizationInvitation, NewOrganizationMember, OrganizationMember,
+    OrganizationMemberResponse, OrganizationResponse, OrganizationRole, OrganizationSummary,
+};
+use crate::service::audit_service;
+use crate::service::report_service::mood_distribution;
+use crate::utils::email::{normalize_email, parse_email};
+use crate::utils::mailer::{EmailMessage, Mailer};
+
+/// Undangan organisasi berlaku 7 hari
+const INVITATION_TOKEN_DAYS: i64 = 7;
+const ORGANIZATION_NAME_MAX_LENGTH: usize = 100;
+/// Rentang ringkasan paling panjang satu tahun
+const SUMMARY_MAX_DAYS: i64 = 366;
+
+fn generate_invitation_token() -> String {
+    let mut rng = rand::thread_rng();
+    (0..48)
+        .map(|_| rng.sample(rand::distributions::Alphanumeric) as char)
+        .collect()
+}
+
+/// Keanggotaan pengguna; bukan anggota diperlakukan seolah organisasi tidak ada
+fn require_member(
+    conn: &mut PgConnection,
+    organization_id: i32,
+    user_id: i32,
+) -> Result<OrganizationMember, AppError> {
+    organization_query::find_membership(conn, organization_id, user_id)?
+        .ok_or_else(|| AppError::NotFound("Organization not found".to_string()))
+}
+
+fn require_admin(
+    conn: &mut PgConnection,
+    organization_id: i32,
+    user_id: i32,
+) -> Result<OrganizationMember, AppError> {
+    let member = require_member(conn, organization_id, user_id)?;
+    if !member.is_admin() {
+        return Err(AppError::Forbidden("Organization admin access required".to_string()));
+    }
+    Ok(member)
+}
+
+/// Buat organisasi baru; pembuatnya otomatis menjadi admin
+pub fn create_organization(
+    pool: &DbPools,
+    user_id: i32,
+    data: CreateOrganizationRequest,
+) -> Result<OrganizationResponse, AppError> {
+    let name = data.name.trim();
+    if name.is_empty() || name.chars().count() > ORGANIZATION_NAME_MAX_LENGTH {
+        return Err(AppError::BadRequest(format!(
+            "Organization name must be between 1 and {} characters",
+            ORGANIZATION_NAME_MAX_LENGTH
+        )));
+    }
+
+    let mut conn = pool.conn_write()?;
+    run_in_transaction(&mut conn, |conn| {
+        let organization = organization_query::create_organization(
+            conn,
+            &NewOrganization {
+                name,
+                created_by: Some(user_id),
+            },
+        )?;
+        let member = organization_query::add_member(
+            conn,
+            &NewOrganizationMember {
+                organization_id: organization.id,
+                user_id,
+                role: OrganizationRole::Admin.as_str(),
+            },
+        )?;
+
+        Ok(OrganizationResponse {
+            id: organization.id,
+            name: organization.name,
+            role: member.role,
+            data_sharing_consent: member.data_sharing_consent,
+            joined_at: member.joined_at,
+        })
+    })
+}
+
+pub fn get_organizations(
+    pool: &DbPools,
+    user_id: i32,
+) -> Result<Vec<OrganizationResponse>, AppError> {
+    let mut conn = pool.conn_read()?;
+
+    let organizations = organization_query::find_organizations_by_user(&mut conn, user_id)?;
+    Ok(organizations
+        .into_iter()
+        .map(|(organization, member)| OrganizationResponse {
+            id: organization.id,
+            name: organization.name,
+            role: member.role,
+            data_sharing_consent: member.data_sharing_consent,
+            joined_at: member.joined_at,
+        })
+        .collect())
+}
+
+/// Daftar anggota, hanya untuk admin organisasi
+pub fn get_members(
+    pool: &DbPools,
+    user_id: i32,
+    organization_id: i32,
+) -> Result<Vec<OrganizationMemberResponse>, AppError> {
+    let mut conn = pool.conn_read()?;
+    require_admin(&mut conn, organization_id, user_id)?;
+
+    let members = organization_query::find_members(&mut conn, organization_id)?;
+    Ok(members
+        .into_iter()
+        .map(|(member, username)| OrganizationMemberResponse {
+            user_id: member.user_id,
+            username,
+            role: member.role,
+            data_sharing_consent: member.data_sharing_consent,
+            joined_at: member.joined_at,
+        })
+        .collect())
+}
+
+/// Keluarkan anggota (admin) atau keluar dari organisasi (anggota sendiri).
+/// Admin terakhir tidak bisa dikeluarkan agar organisasi tetap bisa dikelola.
+pub fn remove_member(
+    pool: &DbPools,
+    user_id: i32,
+    organization_id: i32,
+    member_user_id: i32,
+) -> Result<(), AppError> {
+    let mut conn = pool.conn_write()?;
+
+    run_in_transaction(&mut conn, |conn| {
+        if member_user_id == user_id {
+            require_member(conn, organization_id, user_id)?;
+        } else {
+            require_admin(conn, organization_id, user_id)?;
+        }
+
+        let member = organization_query::find_membership(conn, organization_id, member_user_id)?
+            .ok_or_else(|| AppError::NotFound("Member not found".to_string()))?;
+        if member.is_admin() && organization_query::count_admins(conn, organization_id)? <= 1 {
+            return Err(AppError::BadRequest("An organization must keep at least one admin".to_string()));
+        }
+
+        organization_query::remove_member(conn, organization_id, member_user_id)?;
+        Ok(())
+    })
+}
+
+/// Undang pengguna lewat email. Kode undangan hanya dikirim ke email tersebut.
+pub fn invite_member(
+    pool: &DbPools,
+    mailer: &dyn Mailer,
+    user_id: i32,
+    organization_id: i32,
+    data: InviteMemberRequest,
+) -> Result<InvitationResponse, AppError> {
+    let email = parse_email(&data.email)?;
+    let role = match data.role.as_deref() {
+        Some(role) => OrganizationRole::parse(role)
+            .ok_or_else(|| AppError::BadRequest(format!("Unknown organization role: {}", role)))?,
+        None => OrganizationRole::Member,
+    };
+
+    let mut conn = pool.conn_write()?;
+    require_admin(&mut conn, organization_id, user_id)?;
+    let organization_name = organization_query::find_organization_by_id(&mut conn, organization_id)?.name;
+    let inviter = user_query::find_user_by_id(&mut conn, user_id)?;
+
+    let now = Utc::now().naive_utc();
+    organization_query::delete_expired_invitations(&mut conn, organization_id, now)?;
+    let token = generate_invitation_token();
+    let invitation = organization_query::create_invitation(
+        &mut conn,
+        &NewOrganizationInvitation {
+            organization_id,
+            email: &email,
+            role: role.as_str(),
+            token: &token,
+            invited_by: Some(user_id),
+            expires_at: now + Duration::days(INVITATION_TOKEN_DAYS),
+        },
+    )?;
+
+    mailer.send(&EmailMessage {
+        to: email.clone(),
+        subject: format!("You're invited to join {} on MindMate", organization_name),
+        body: format!(
+            "Hi,\n\n{} invited you to join {} on MindMate.\n\nSign in to MindMate with this email address and enter this invitation code:\n{}\n\nThe code expires in {} days. Joining does not share your data; you choose separately whether {} may see a summary of your moods.",
+            inviter.username,
+            organization_name,
+            invitation.token,
+            INVITATION_TOKEN_DAYS,
+            organization_name
+        ),
+    })?;
+
+    Ok(InvitationResponse {
+        id: invitation.id,
+        organization_id: invitation.organization_id,
+        email: invitation.email,
+        role: invitation.role,
+        expires_at: invitation.expires_at,
+    })
+}
+
+/// Terima undangan; email akun harus sama dengan email yang diundang.
+/// Anggota baru belum membagikan data sampai memberi persetujuan sendiri.
+pub fn accept_invitation(
+    pool: &DbPools,
+    user_id: i32,
+    data: AcceptInvitationRequest,
+) -> Result<OrganizationResponse, AppError> {
+    let mut conn = pool.conn_write()?;
+
+    run_in_transaction(&mut conn, |conn| {
+        let invitation = organization_query::find_invitation_by_token(conn, data.token.trim())?
+            .filter(|invitation| invitation.expires_at >= Utc::now().naive_utc())
+            .ok_or_else(|| AppError::BadRequest("Invalid or expired invitation".to_string()))?;
+
+        let user = user_query::find_user_by_id(conn, user_id)?;
+        if normalize_email(&user.email) != normalize_email(&invitation.email) {
+            return Err(AppError::Forbidden("This invitation was sent to a different email".to_string()));
+        }
+        if organization_query::find_membership(conn, invitation.organization_id, user_id)?.is_some() {
+            return Err(AppError::Conflict("Already a member of this organization".to_string()));
+        }
+
+        let member = organization_query::add_member(
+            conn,
+            &NewOrganizationMember {
+                organization_id: invitation.organization_id,
+                user_id,
+                role: &invitation.role,
+            },
+        )?;
+        organization_query::delete_invitation(conn, invitation.id)?;
+
+        let organization = organization_query::find_organization_by_id(conn, invitation.organization_id)?;
+
+        Ok(OrganizationResponse {
+            id: organization.id,
+            name: organization.name,
+            role: member.role,
+            data_sharing_consent: member.data_sharing_consent,
+            joined_at: member.joined_at,
+        })
+    })
+}
+
+/// Atur persetujuan berbagi data dengan admin organisasi; dicatat di audit log
+pub fn update_consent(
+    pool: &DbPools,
+    user_id: i32,
+    organization_id: i32,
+    consent: bool,
+    ip_address: Option<&str>,
+) -> Result<OrganizationMemberResponse, AppError> {
+    let mut conn = pool.conn_write()?;
+
+    run_in_transaction(&mut conn, |conn| {
+        require_member(conn, organization_id, user_id)?;
+        let member = organization_query::set_consent(conn, organization_id, user_id, consent)?;
+        let user = user_query::find_user_by_id(conn, user_id)?;
+
+        audit_service::record(
+            conn,
+            NewAuditLog::new(AuditAction::ConsentChange, Some(user_id), Some(user_id), ip_address)
+                .with_details(format!("organization={} data_sharing={}", organization_id, consent)),
+        )?;
+
+        Ok(OrganizationMemberResponse {
+            user_id: member.user_id,
+            username: user.username,
+            role: member.role,
+            data_sharing_consent: member.data_sharing_consent,
+            joined_at: member.joined_at,
+        })
+    })
+}
+
+/// Ringkasan mood anggota untuk admin organisasi. Data hanya diambil dari anggota
+/// yang memberi persetujuan; catatan dan isi jurnal tidak pernah ikut.
+pub fn get_summary(
+    pool: &DbPools,
+    user_id: i32,
+    organization_id: i32,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+) -> Result<OrganizationSummary, AppError> {
+    if start_date > end_date {
+        return Err(AppError::BadRequest("Start date cannot be after end date".to_string()));
+    }
+    if (end_date - start_date).num_days() >= SUMMARY_MAX_DAYS {
+        return Err(AppError::BadRequest(format!(
+            "Date range must be at most {} days",
+            SUMMARY_MAX_DAYS
+        )));
+    }
+
+    let mut conn = pool.conn_read()?;
+    require_admin(&mut conn, organization_id, user_id)?;
+
+    let members = organization_query::find_members(&mut conn, organization_id)?;
+    let member_count = members.len();
+    let consenting: Vec<(i32, String)> = members
+        .into_iter()
+        .filter(|(member, _)| member.data_sharing_consent)
+        .map(|(member, username)| (member.user_id, username))
+        .collect();
+    let user_ids: Vec<i32> = consenting.iter().map(|(id, _)| *id).collect();
+
+    let moods = mood_query::find_moods_by_users_in_range(&mut conn, &user_ids, start_date, end_date)?;
+    let score = |mood: &str| mood.parse::<MoodType>().map(|mood_type| mood_type.score()).ok();
+    let average = |scores: &[i32]| {
+        (!scores.is_empty()).then(|| scores.iter().sum::<i32>() as f64 / scores.len() as f64)
+    };
+
+    let mut by_member: HashMap<i32, Vec<&Mood>> = HashMap::new();
+    for mood in &moods {
+        by_member.entry(mood.user_id).or_default().push(mood);
+    }
+
+    let members = consenting
+        .into_iter()
+        .map(|(member_id, username)| {
+            let member_moods = by_member.get(&member_id).map(Vec::as_slice).unwrap_or_default();
+            let scores: Vec<i32> = member_moods.iter().filter_map(|mood| score(&mood.mood)).collect();
+            MemberMoodSummary {
+                user_id: member_id,
+                username,
+                mood_entries: member_moods.len() as i64,
+                average_score: average(&scores),
+                last_mood_date: member_moods.iter().map(|mood| mood.date).max(),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let scores: Vec<i32> = moods.iter().filter_map(|mood| score(&mood.mood)).collect();
+    let average_score = average(&scores);
+
+    Ok(OrganizationSummary {
+        organization_id,
+        start_date,
+        end_date,
+        member_count,
+        consenting_members: members.len(),
+        mood_entries: moods.len() as i64,
+        average_score,
+        mood_distribution: mood_distribution(&moods),
+        members,
+    })
+}
@@ -0,0 +1,129 @@
+use crate::models::audit::{AuditAction, NewAuditLog};
+use crate::models::password_reset::{NewPasswordResetToken, PasswordResetRequestedResponse};
+use crate::db::{password_reset_query, user_query};
+use crate::errors::app_error::AppError;
+use crate::config::app_config::app_config;
+use crate::db::pool::DbPools;
+use crate::db::transaction::run_in_transaction;
+use crate::i18n::t;
+use crate::service::audit_service;
+use crate::utils::email::parse_email;
+use crate::utils::mailer::{EmailMessage, Mailer};
+use crate::utils::password_policy::PasswordPolicy;
+use bcrypt::{hash, DEFAULT_COST};
+use std::sync::Arc;
+use chrono::{Duration, Utc};
+use rand::Rng;
+
+/// Token reset password berlaku 1 jam
+const PASSWORD_RESET_TOKEN_MINUTES: i64 = 60;
+
+fn generate_reset_token() -> String {
+    let mut rng = rand::thread_rng();
+    (0..48)
+        .map(|_| rng.sample(rand::distributions::Alphanumeric) as char)
+        .collect()
+}
+
+/// Langkah pertama lupa password: kirim token reset jika email terdaftar. Respons selalu
+/// sama agar endpoint tidak bisa dipakai untuk menebak email mana yang punya akun: email
+/// dikirim di thread blocking tanpa ditunggu, dan kegagalan menyimpan token atau mengirim
+/// email hanya dicatat di log.
+pub fn request_password_reset(
+    pool: &DbPools,
+    mailer: Arc<dyn Mailer>,
+    email: &str,
+) -> Result<PasswordResetRequestedResponse, AppError> {
+    let email = parse_email(email)?;
+    let response = PasswordResetRequestedResponse {
+        message: t("message.password_reset_requested"),
+    };
+
+    let mut conn = pool.conn_write()?;
+
+    let user = match user_query::find_user_by_email(&mut conn, &email) {
+        Ok(user) => user,
+        Err(AppError::NotFound(_)) => return Ok(response),
+        Err(e) => return Err(e),
+    };
+    // Akun tamu hanya punya alamat pengganti
+    if user.is_guest {
+        return Ok(response);
+    }
+
+    let now = Utc::now().naive_utc();
+    let token = match password_reset_query::upsert_token(&mut conn, &NewPasswordResetToken {
+        user_id: user.id,
+        token: generate_reset_token(),
+        expires_at: now + Duration::minutes(PASSWORD_RESET_TOKEN_MINUTES),
+        created_at: now,
+    }) {
+        Ok(token) => token,
+        Err(e) => {
+            eprintln!("❌ Failed to store password reset token for user {}: {}", user.id, e);
+            return Ok(response);
+        }
+    };
+
+    let message = EmailMessage {
+        to: user.email.clone(),
+        subject: "Reset your MindMate password".to_string(),
+        body: format!(
+            "Hi {},\n\nUse this code in the MindMate app to choose a new password:\n{}\n\nThe code expires in {} minutes. If you did not request this, ignore this email.",
+            user.username,
+            token.token,
+            PASSWORD_RESET_TOKEN_MINUTES
+        ),
+    };
+    tokio::task::spawn_blocking(move || {
+        if let Err(e) = mailer.send(&message) {
+            eprintln!("❌ Failed to send password reset email for user {}: {}", user.id, e);
+        }
+    });
+
+    Ok(response)
+}
+
+/// Langkah kedua: ganti password jika token valid dan belum kedaluwarsa. Token hanya bisa dipakai sekali.
+pub fn reset_password(
+    pool: &DbPools,
+    token: &str,
+    new_password: &str,
+    ip_address: Option<&str>,
+) -> Result<(), AppError> {
+    let mut conn = pool.conn_write()?;
+
+    // Token kedaluwarsa tetap dihapus: transaksi di-commit dulu, baru setelah itu
+    // kesalahan dikembalikan agar penghapusan tidak ikut di-rollback
+    let reset_done = run_in_transaction(&mut conn, |conn| {
+        let reset = password_reset_query::find_token(conn, token)?;
+
+        if reset.expires_at < Utc::now().naive_utc() {
+            password_reset_query::delete_token_by_user(conn, reset.user_id)?;
+            return Ok(false);
+        }
+
+        let user = user_query::find_user_by_id(conn, reset.user_id)
+            .map_err(|_| AppError::NotFound("User not found".to_string()))?;
+
+        PasswordPolicy::from_config(app_config()).validate(new_password, &[&user.username, &user.email])?;
+
+        let hashed_new_password = hash(new_password, DEFAULT_COST)
+            .map_err(|_| AppError::InternalServerError("Failed to hash password".to_string()))?;
+
+        user_query::update_user_password(conn, user.id, &hashed_new_password)?;
+        password_reset_query::delete_token_by_user(conn, user.id)?;
+
+        // Reset dilakukan tanpa login, jadi tidak ada pelaku yang terautentikasi
+        audit_service::record(
+            conn,
+            NewAuditLog::new(AuditAction::PasswordReset, None, Some(user.id), ip_address),
+        )?;
+        Ok(true)
+    })?;
+
+    if !reset_done {
+        return Err(AppError::BadRequest("Invalid or expired password reset token".to_string()));
+    }
+    Ok(())
+}
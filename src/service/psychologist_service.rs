@@ -0,0 +1,244 @@
+use chrono::{Duration, Utc};
+use crate::db::{psychologist_query, user_query};
+use crate::db::pool::DbPools;
+use crate::db::transaction::run_in_transaction;
+use crate::errors::app_error::AppError;
+use crate::models::psychologist::{
+    CreatePsychologistBooking, CreatePsychologistRequest, CreateSlotsRequest, NewPsychologist,
+    NewPsychologistRequest, NewPsychologistSlot, PsychologistDetail, PsychologistQuery,
+    PsychologistRequestResponse, PsychologistResponse, SlotResponse,
+};
+use crate::utils::email::parse_email;
+use crate::utils::text_limits::ensure_max_length;
+
+const PSYCHOLOGIST_NAME_MAX_LENGTH: usize = 255;
+const SPECIALIZATION_MAX_LENGTH: usize = 100;
+const BIO_MAX_LENGTH: usize = 2000;
+const REQUEST_MESSAGE_MAX_LENGTH: usize = 5000;
+const PREFERRED_TIME_MAX_LENGTH: usize = 255;
+/// Batas jadwal per request admin dan durasi satu sesi
+const MAX_SLOTS_PER_REQUEST: usize = 100;
+const MAX_SLOT_HOURS: i64 = 8;
+
+/// Kode bahasa ISO 639-1 huruf kecil, tanpa duplikat
+fn normalize_languages(languages: &[String]) -> Result<Vec<String>, AppError> {
+    let mut normalized: Vec<String> = Vec::with_capacity(languages.len());
+    for language in languages {
+        let code = language.trim().to_lowercase();
+        if code.len() != 2 || !code.chars().all(|c| c.is_ascii_lowercase()) {
+            return Err(AppError::BadRequest(format!("Invalid language code: {}", language)));
+        }
+        if !normalized.contains(&code) {
+            normalized.push(code);
+        }
+    }
+    Ok(normalized)
+}
+
+/// Direktori publik psikolog aktif
+pub fn get_psychologists(
+    pool: &DbPools,
+    query: &PsychologistQuery,
+) -> Result<Vec<PsychologistResponse>, AppError> {
+    let specialization = query
+        .specialization
+        .as_deref()
+        .map(|value| value.trim().to_lowercase())
+        .filter(|value| !value.is_empty());
+    let language = query
+        .language
+        .as_deref()
+        .map(|value| value.trim().to_lowercase())
+        .filter(|value| !value.is_empty());
+
+    let mut conn = pool.conn_read()?;
+
+    let psychologists =
+        psychologist_query::find_active_psychologists(&mut conn, specialization.as_deref(), language.as_deref())?;
+    Ok(psychologists.into_iter().map(PsychologistResponse::from).collect())
+}
+
+/// Profil psikolog beserta jadwal mendatang yang masih kosong
+pub fn get_psychologist(
+    pool: &DbPools,
+    psychologist_id: i32,
+) -> Result<PsychologistDetail, AppError> {
+    let mut conn = pool.conn_read()?;
+
+    let psychologist = psychologist_query::find_active_psychologist(&mut conn, psychologist_id)?
+        .ok_or_else(|| AppError::NotFound("Psychologist not found".to_string()))?;
+    let slots = psychologist_query::find_available_slots(&mut conn, psychologist.id, Utc::now().naive_utc())?;
+
+    Ok(PsychologistDetail {
+        psychologist: psychologist.into(),
+        available_slots: slots.into_iter().map(SlotResponse::from).collect(),
+    })
+}
+
+/// Tambah psikolog ke direktori (admin)
+pub fn create_psychologist(
+    pool: &DbPools,
+    data: CreatePsychologistRequest,
+) -> Result<PsychologistResponse, AppError> {
+    let name = data.name.trim();
+    let specialization = data.specialization.trim();
+    if name.is_empty() || specialization.is_empty() {
+        return Err(AppError::BadRequest("Name and specialization are required".to_string()));
+    }
+    ensure_max_length("Name", name, PSYCHOLOGIST_NAME_MAX_LENGTH)?;
+    ensure_max_length("Specialization", specialization, SPECIALIZATION_MAX_LENGTH)?;
+    let bio = data.bio.as_deref().map(str::trim).filter(|bio| !bio.is_empty());
+    if let Some(bio) = bio {
+        ensure_max_length("Bio", bio, BIO_MAX_LENGTH)?;
+    }
+    let languages = normalize_languages(&data.languages)?;
+
+    let mut conn = pool.conn_write()?;
+
+    if let Some(user_id) = data.user_id {
+        user_query::find_user_by_id(&mut conn, user_id)?;
+    }
+    let psychologist = psychologist_query::create_psychologist(
+        &mut conn,
+        &NewPsychologist {
+            name,
+            specialization,
+            languages: &languages,
+            bio,
+            user_id: data.user_id,
+        },
+    )?;
+    Ok(psychologist.into())
+}
+
+/// Tambah jadwal praktik (admin). Semua jadwal disimpan atau tidak sama sekali.
+pub fn create_slots(
+    pool: &DbPools,
+    psychologist_id: i32,
+    data: CreateSlotsRequest,
+) -> Result<Vec<SlotResponse>, AppError> {
+    if data.slots.is_empty() || data.slots.len() > MAX_SLOTS_PER_REQUEST {
+        return Err(AppError::BadRequest(format!(
+            "Between 1 and {} slots can be added at once",
+            MAX_SLOTS_PER_REQUEST
+        )));
+    }
+    let now = Utc::now().naive_utc();
+    for slot in &data.slots {
+        if slot.ends_at <= slot.starts_at || slot.ends_at - slot.starts_at > Duration::hours(MAX_SLOT_HOURS) {
+            return Err(AppError::BadRequest(format!(
+                "Slot must end after it starts and last at most {} hours",
+                MAX_SLOT_HOURS
+            )));
+        }
+        if slot.starts_at <= now {
+            return Err(AppError::BadRequest("Slot must start in the future".to_string()));
+        }
+    }
+
+    let mut conn = pool.conn_write()?;
+    run_in_transaction(&mut conn, |conn| {
+        psychologist_query::find_active_psychologist(conn, psychologist_id)?
+            .ok_or_else(|| AppError::NotFound("Psychologist not found".to_string()))?;
+
+        let slots: Vec<NewPsychologistSlot> = data
+            .slots
+            .iter()
+            .map(|slot| NewPsychologistSlot {
+                psychologist_id,
+                starts_at: slot.starts_at,
+                ends_at: slot.ends_at,
+            })
+            .collect();
+        let created = psychologist_query::insert_slots(conn, &slots)?;
+        Ok(created.into_iter().map(SlotResponse::from).collect())
+    })
+}
+
+/// Ajukan sesi dengan psikolog, opsional langsung memesan salah satu jadwalnya.
+/// Jadwal dikunci selama transaksi sehingga tidak bisa dipesan dua kali.
+pub fn book_psychologist(
+    pool: &DbPools,
+    user_id: i32,
+    data: CreatePsychologistBooking,
+) -> Result<PsychologistRequestResponse, AppError> {
+    let message = data.message.trim();
+    if message.is_empty() {
+        return Err(AppError::BadRequest("Message cannot be empty".to_string()));
+    }
+    ensure_max_length("Message", message, REQUEST_MESSAGE_MAX_LENGTH)?;
+    let preferred_time = data.preferred_time.as_deref().map(str::trim).filter(|time| !time.is_empty());
+    if let Some(preferred_time) = preferred_time {
+        ensure_max_length("Preferred time", preferred_time, PREFERRED_TIME_MAX_LENGTH)?;
+    }
+
+    let mut conn = pool.conn_write()?;
+
+    let user = user_query::find_user_by_id(&mut conn, user_id)?;
+    let name = data.name.as_deref().map(str::trim).filter(|name| !name.is_empty()).unwrap_or(&user.username);
+    ensure_max_length("Name", name, PSYCHOLOGIST_NAME_MAX_LENGTH)?;
+    let email = match data.email.as_deref() {
+        Some(email) => parse_email(email)?,
+        None => user.email.clone(),
+    };
+
+    run_in_transaction(&mut conn, |conn| {
+        let psychologist = psychologist_query::find_active_psychologist(conn, data.psychologist_id)?
+            .ok_or_else(|| AppError::NotFound("Psychologist not found".to_string()))?;
+
+        let slot = match data.slot_id {
+            Some(slot_id) => {
+                let slot = psychologist_query::find_slot_for_update(conn, slot_id)?
+                    .filter(|slot| slot.psychologist_id == psychologist.id)
+                    .ok_or_else(|| AppError::NotFound("Slot not found".to_string()))?;
+                if slot.starts_at <= Utc::now().naive_utc() {
+                    return Err(AppError::BadRequest("Slot has already started".to_string()));
+                }
+                if psychologist_query::is_slot_booked(conn, slot.id)? {
+                    return Err(AppError::Conflict("Slot is already booked".to_string()));
+                }
+                Some(slot)
+            }
+            None => None,
+        };
+
+        let request = psychologist_query::create_request(
+            conn,
+            &NewPsychologistRequest {
+                user_id,
+                name,
+                email: &email,
+                message,
+                preferred_time,
+                psychologist_id: Some(psychologist.id),
+                slot_id: slot.as_ref().map(|slot| slot.id),
+            },
+        )?;
+        Ok(PsychologistRequestResponse::new(request, slot))
+    })
+}
+
+pub fn get_psychologist_requests(
+    pool: &DbPools,
+    user_id: i32,
+) -> Result<Vec<PsychologistRequestResponse>, AppError> {
+    let mut conn = pool.conn_read()?;
+
+    let requests = psychologist_query::find_requests_by_user(&mut conn, user_id)?;
+    Ok(requests
+        .into_iter()
+        .map(|(request, slot)| PsychologistRequestResponse::new(request, slot))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn languages_are_lowercased_and_deduplicated() {
+        let languages = normalize_languages(&[" ID ".to_string(), "en".to_string(), "id".to_string()]).unwrap();
+        assert_eq!(languages, vec!["id".to_string(), "en".to_string()]);
+        assert!(normalize_languages(&["indonesian".to_string()]).is_err());
+    }
+}
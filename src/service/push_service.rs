@@ -0,0 +1,169 @@
+use chrono::{Duration, NaiveDateTime, Utc};
+use diesel::pg::PgConnection;
+use crate::config::app_config::app_config;
+use crate::db::{device_query, push_outbox_query};
+use crate::db::pool::DbPools;
+use crate::errors::app_error::AppError;
+use crate::models::device::{DevicePlatform, DeviceResponse, NewDevice, NewPushOutboxEntry, RegisterDeviceRequest};
+use crate::utils::push::{PushError, PushMessage, PushSender};
+
+/// Jumlah entri antrian yang diproses per putaran job
+const DELIVERY_BATCH_SIZE: i64 = 100;
+/// Jeda retry pertama, digandakan setiap percobaan sampai RETRY_MAX_DELAY
+const RETRY_BASE_DELAY_SECS: i64 = 30;
+const RETRY_MAX_DELAY_SECS: i64 = 60 * 60;
+/// Batas panjang token; token FCM/APNs jauh lebih pendek dari ini
+const PUSH_TOKEN_MAX_LENGTH: usize = 4096;
+
+pub fn register_device(
+    pool: &DbPools,
+    user_id: i32,
+    data: RegisterDeviceRequest,
+) -> Result<DeviceResponse, AppError> {
+    let platform = DevicePlatform::parse(&data.platform)
+        .ok_or_else(|| AppError::BadRequest(format!("Unsupported platform: {}", data.platform)))?;
+    let token = data.token.trim();
+    if token.is_empty() || token.len() > PUSH_TOKEN_MAX_LENGTH {
+        return Err(AppError::BadRequest("Invalid push token".to_string()));
+    }
+
+    let mut conn = pool.conn_write()?;
+
+    let device = device_query::upsert_device(
+        &mut conn,
+        &NewDevice {
+            user_id,
+            platform: platform.as_str(),
+            push_token: token,
+            updated_at: Utc::now().naive_utc(),
+        },
+    )?;
+
+    Ok(device.into())
+}
+
+pub fn get_devices(
+    pool: &DbPools,
+    user_id: i32,
+) -> Result<Vec<DeviceResponse>, AppError> {
+    let mut conn = pool.conn_read()?;
+
+    let devices = device_query::find_devices_by_user(&mut conn, user_id)?;
+    Ok(devices.into_iter().map(DeviceResponse::from).collect())
+}
+
+pub fn delete_device(
+    pool: &DbPools,
+    user_id: i32,
+    device_id: i32,
+) -> Result<(), AppError> {
+    let mut conn = pool.conn_write()?;
+
+    if device_query::delete_device(&mut conn, device_id, user_id)? == 0 {
+        return Err(AppError::NotFound("Device not found".to_string()));
+    }
+    Ok(())
+}
+
+/// Masukkan notifikasi ke antrian untuk semua perangkat pengguna, dikirim mulai `deliver_at`.
+/// Gunakan `notification_service::dispatch` agar preferensi pengguna diperhitungkan.
+pub fn enqueue_for_user(
+    conn: &mut PgConnection,
+    user_id: i32,
+    message: &PushMessage,
+    deliver_at: NaiveDateTime,
+) -> Result<usize, AppError> {
+    let devices = device_query::find_devices_by_user(conn, user_id)?;
+    if devices.is_empty() {
+        return Ok(0);
+    }
+
+    let entries: Vec<NewPushOutboxEntry> = devices
+        .iter()
+        .map(|device| NewPushOutboxEntry {
+            device_id: device.id,
+            category: &message.category,
+            title: &message.title,
+            body: &message.body,
+            next_attempt_at: deliver_at,
+        })
+        .collect();
+    push_outbox_query::insert_entries(conn, &entries)
+}
+
+/// Kirim entri antrian yang sudah jatuh tempo. Entri yang gagal dijadwalkan ulang
+/// dengan backoff eksponensial sampai PUSH_MAX_ATTEMPTS, perangkat dengan token
+/// yang ditolak provider dihapus.
+pub async fn deliver_due(
+    pool: &DbPools,
+    sender: &PushSender,
+) -> Result<usize, AppError> {
+    let mut conn = pool.conn_write()?;
+
+    let now = Utc::now().naive_utc();
+    let due = push_outbox_query::find_due_entries(&mut conn, now, DELIVERY_BATCH_SIZE)?;
+
+    let mut sent = 0;
+    for (entry, device) in due {
+        let Some(platform) = DevicePlatform::parse(&device.platform) else {
+            push_outbox_query::delete_entry(&mut conn, entry.id)?;
+            continue;
+        };
+        let message = PushMessage {
+            category: entry.category,
+            title: entry.title,
+            body: entry.body,
+        };
+
+        match sender.send(platform, &device.push_token, &message).await {
+            Ok(()) => {
+                push_outbox_query::delete_entry(&mut conn, entry.id)?;
+                sent += 1;
+            }
+            Err(PushError::InvalidToken) => {
+                device_query::delete_device_by_id(&mut conn, device.id)?;
+            }
+            Err(PushError::NotConfigured) => {
+                eprintln!("⚠️ Dropping push {}: {} push provider not configured", entry.id, platform.as_str());
+                push_outbox_query::delete_entry(&mut conn, entry.id)?;
+            }
+            Err(PushError::Failed(reason)) => {
+                let attempts = entry.attempts + 1;
+                if attempts >= app_config().push_max_attempts {
+                    eprintln!("❌ Giving up on push {} after {} attempts: {}", entry.id, attempts, reason);
+                    push_outbox_query::delete_entry(&mut conn, entry.id)?;
+                } else {
+                    let delay = (RETRY_BASE_DELAY_SECS << (attempts - 1).min(16)).min(RETRY_MAX_DELAY_SECS);
+                    push_outbox_query::reschedule_entry(
+                        &mut conn,
+                        entry.id,
+                        attempts,
+                        Utc::now().naive_utc() + Duration::seconds(delay),
+                        &reason,
+                    )?;
+                }
+            }
+        }
+    }
+
+    Ok(sent)
+}
+
+/// Kirim semua entri yang sudah jatuh tempo, batch demi batch. Dipakai saat shutdown agar
+/// antrian tidak tertinggal; entri yang gagal dijadwalkan ulang ke depan sehingga loop selalu berhenti.
+pub async fn deliver_all_due(
+    pool: &DbPools,
+    sender: &PushSender,
+) -> Result<usize, AppError> {
+    let mut sent = 0;
+    loop {
+        let due = {
+            let mut conn = pool.conn_write()?;
+            push_outbox_query::count_due_entries(&mut conn, Utc::now().naive_utc())?
+        };
+        if due == 0 {
+            return Ok(sent);
+        }
+        sent += deliver_due(pool, sender).await?;
+    }
+}
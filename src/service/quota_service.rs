@@ -0,0 +1,57 @@
+use chrono::{Duration, NaiveDate, NaiveDateTime, Utc};
+use crate::config::app_config::app_config;
+use crate::db::pool::DbPools;
+use crate::db::quota_query;
+use crate::errors::app_error::AppError;
+use crate::models::quota::QuotaOperation;
+
+/// Catatan pemakaian disimpan sebentar setelah periodenya lewat, untuk penelusuran
+const QUOTA_HISTORY_DAYS: i64 = 7;
+
+/// Saat kuota periode `today` (UTC) terisi kembali: tengah malam UTC berikutnya
+pub fn reset_at(today: NaiveDate) -> NaiveDateTime {
+    (today + Duration::days(1)).and_hms_opt(0, 0, 0).unwrap_or_default()
+}
+
+/// Pakai satu kuota harian `operation`; gagal dengan 429 beserta waktu reset jika sudah habis
+pub fn consume(
+    pool: &DbPools,
+    user_id: i32,
+    operation: QuotaOperation,
+) -> Result<(), AppError> {
+    let limit = operation.daily_limit(app_config());
+    if limit <= 0 {
+        return Ok(());
+    }
+
+    let mut conn = pool.conn_write()?;
+
+    let today = Utc::now().date_naive();
+    match quota_query::try_consume(&mut conn, user_id, operation.as_str(), today, limit)? {
+        Some(_) => Ok(()),
+        None => Err(AppError::QuotaExceeded {
+            message: operation.exceeded_message(limit),
+            limit,
+            reset_at: reset_at(today),
+        }),
+    }
+}
+
+/// Hapus catatan pemakaian yang sudah lama lewat
+pub fn cleanup_old_usage(pool: &DbPools) -> Result<usize, AppError> {
+    let mut conn = pool.conn_write()?;
+
+    let cutoff = Utc::now().date_naive() - Duration::days(QUOTA_HISTORY_DAYS);
+    quota_query::delete_before(&mut conn, cutoff)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quota_resets_at_next_utc_midnight() {
+        let today = NaiveDate::from_ymd_opt(2026, 12, 31).unwrap();
+        assert_eq!(reset_at(today), NaiveDate::from_ymd_opt(2027, 1, 1).unwrap().and_hms_opt(0, 0, 0).unwrap());
+    }
+}
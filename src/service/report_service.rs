@@ -0,0 +1,341 @@
+use crate::models::mood::{Mood, MoodCount, MoodDetails, MoodType};
+use crate::models::audit::{AuditAction, NewAuditLog};
+use crate::models::quota::QuotaOperation;
+use crate::models::report::{DailyScore, DetailPoint, DetailSeries, MonthlyAverage, MonthlyReport, StreakSummary, WeeklyAverage, YearlyReport};
+use crate::db::{journal_query, mood_query};
+use crate::service::{audit_service, quota_service};
+use crate::service::health_service::{self, DailyHealth};
+use crate::errors::app_error::AppError;
+use crate::db::pool::DbPools;
+use std::collections::BTreeMap;
+use chrono::{Datelike, Duration, NaiveDate};
+use chrono_tz::Tz;
+use crate::utils::pdf_report;
+use crate::utils::streaks::StreakEngine;
+use crate::utils::mood_interpretation::interpret_average_score;
+use crate::config::app_config::app_config;
+
+pub fn get_monthly_report(
+    pool: &DbPools,
+    user_id: i32,
+    month_start: NaiveDate,
+    tz: Tz,
+) -> Result<MonthlyReport, AppError> {
+    let mut conn = pool.conn_read()?;
+
+    let month_end = last_day_of_month(month_start);
+
+    let moods = mood_query::find_moods_by_date_range(&mut conn, user_id, month_start, month_end)?;
+    let journal_count = journal_query::count_journals_by_date_range(&mut conn, user_id, month_start, month_end, tz)?;
+    let health = health_service::get_daily_health(&mut conn, user_id, month_start, month_end, tz)?;
+
+    let daily_scores = daily_scores(&moods);
+    let mut detail_series = detail_series(&moods);
+    detail_series.extend(health_series(&daily_scores, &health));
+    let average_score = average(daily_scores.iter().map(|d| d.score));
+
+    Ok(MonthlyReport {
+        month: month_start.format("%Y-%m").to_string(),
+        start_date: month_start,
+        end_date: month_end,
+        total_entries: daily_scores.len() as i64,
+        average_score,
+        interpretation: interpret_average_score(average_score, app_config()),
+        weekly_averages: weekly_averages(&daily_scores, month_start, month_end),
+        best_day: best_day(&daily_scores),
+        worst_day: worst_day(&daily_scores),
+        mood_distribution: mood_distribution(&moods),
+        detail_series,
+        journal_count,
+        longest_streak: longest_streak(&daily_scores, tz),
+        daily_scores,
+    })
+}
+
+/// Laporan bulanan dalam bentuk PDF untuk dicetak atau diberikan ke psikolog
+pub fn get_monthly_report_pdf(
+    pool: &DbPools,
+    user_id: i32,
+    month_start: NaiveDate,
+    tz: Tz,
+    ip_address: Option<&str>,
+) -> Result<Vec<u8>, AppError> {
+    quota_service::consume(pool, user_id, QuotaOperation::ReportPdf)?;
+    render_monthly_report_pdf(pool, user_id, month_start, tz, ip_address)
+}
+
+/// PDF laporan bulanan tanpa memakai kuota; dipanggil worker untuk job yang kuotanya sudah dipakai saat diantrikan
+pub fn render_monthly_report_pdf(
+    pool: &DbPools,
+    user_id: i32,
+    month_start: NaiveDate,
+    tz: Tz,
+    ip_address: Option<&str>,
+) -> Result<Vec<u8>, AppError> {
+    let report = get_monthly_report(pool, user_id, month_start, tz)?;
+    let pdf = pdf_report::render_monthly_report(&report)?;
+
+    let mut conn = pool.conn_write()?;
+    audit_service::record(
+        &mut conn,
+        NewAuditLog::new(AuditAction::DataExport, Some(user_id), Some(user_id), ip_address)
+            .with_details(format!("monthly_report_pdf {}", report.month)),
+    )?;
+
+    Ok(pdf)
+}
+
+pub fn get_yearly_report(
+    pool: &DbPools,
+    user_id: i32,
+    year: i32,
+    tz: Tz,
+) -> Result<YearlyReport, AppError> {
+    let mut conn = pool.conn_read()?;
+
+    let year_start = NaiveDate::from_ymd_opt(year, 1, 1)
+        .ok_or_else(|| AppError::BadRequest(format!("Invalid year: {}", year)))?;
+    let year_end = NaiveDate::from_ymd_opt(year, 12, 31)
+        .ok_or_else(|| AppError::BadRequest(format!("Invalid year: {}", year)))?;
+
+    let moods = mood_query::find_moods_by_date_range(&mut conn, user_id, year_start, year_end)?;
+    let journal_count = journal_query::count_journals_by_date_range(&mut conn, user_id, year_start, year_end, tz)?;
+    let health = health_service::get_daily_health(&mut conn, user_id, year_start, year_end, tz)?;
+
+    let daily_scores = daily_scores(&moods);
+    let mut detail_series = detail_series(&moods);
+    detail_series.extend(health_series(&daily_scores, &health));
+
+    let monthly_averages: Vec<MonthlyAverage> = (1..=12)
+        .map(|month| {
+            let scores: Vec<i32> = daily_scores
+                .iter()
+                .filter(|d| d.date.month() == month)
+                .map(|d| d.score)
+                .collect();
+            MonthlyAverage {
+                month: format!("{}-{:02}", year, month),
+                entries: scores.len() as i64,
+                average_score: average(scores.into_iter()),
+            }
+        })
+        .collect();
+
+    let scored_months = || monthly_averages.iter().filter_map(|m| m.average_score.map(|avg| (m, avg)));
+    let best_month = scored_months()
+        .fold(None, |best: Option<(&MonthlyAverage, f64)>, (m, avg)| match best {
+            Some((_, best_avg)) if best_avg >= avg => best,
+            _ => Some((m, avg)),
+        })
+        .map(|(m, _)| m.month.clone());
+    let worst_month = scored_months()
+        .fold(None, |worst: Option<(&MonthlyAverage, f64)>, (m, avg)| match worst {
+            Some((_, worst_avg)) if worst_avg <= avg => worst,
+            _ => Some((m, avg)),
+        })
+        .map(|(m, _)| m.month.clone());
+
+    let average_score = average(daily_scores.iter().map(|d| d.score));
+
+    Ok(YearlyReport {
+        year,
+        total_entries: daily_scores.len() as i64,
+        average_score,
+        interpretation: interpret_average_score(average_score, app_config()),
+        best_month,
+        worst_month,
+        monthly_averages,
+        mood_distribution: mood_distribution(&moods),
+        detail_series,
+        journal_count,
+        longest_streak: longest_streak(&daily_scores, tz),
+    })
+}
+
+fn last_day_of_month(month_start: NaiveDate) -> NaiveDate {
+    let (year, month) = if month_start.month() == 12 {
+        (month_start.year() + 1, 1)
+    } else {
+        (month_start.year(), month_start.month() + 1)
+    };
+    NaiveDate::from_ymd_opt(year, month, 1)
+        .and_then(|next| next.pred_opt())
+        .unwrap_or(month_start)
+}
+
+fn daily_scores(moods: &[Mood]) -> Vec<DailyScore> {
+    let mut scores: Vec<DailyScore> = moods
+        .iter()
+        .filter_map(|mood| {
+            let mood_type = mood.mood.parse::<MoodType>().ok()?;
+            Some(DailyScore {
+                date: mood.date,
+                mood: mood.mood.clone(),
+                score: mood_type.score(),
+            })
+        })
+        .collect();
+    scores.sort_by_key(|d| d.date);
+    scores
+}
+
+fn average(scores: impl Iterator<Item = i32>) -> Option<f64> {
+    let (total, count) = scores.fold((0i64, 0i64), |(total, count), score| (total + score as i64, count + 1));
+    if count == 0 {
+        None
+    } else {
+        Some(total as f64 / count as f64)
+    }
+}
+
+/// Rata-rata per minggu (Senin-Minggu), dipotong pada batas bulan
+fn weekly_averages(scores: &[DailyScore], start: NaiveDate, end: NaiveDate) -> Vec<WeeklyAverage> {
+    let mut weeks = Vec::new();
+    let mut week_start = start;
+
+    while week_start <= end {
+        let days_to_sunday = 6 - week_start.weekday().num_days_from_monday() as i64;
+        let week_end = std::cmp::min(week_start + Duration::days(days_to_sunday), end);
+
+        let week_scores: Vec<i32> = scores
+            .iter()
+            .filter(|d| d.date >= week_start && d.date <= week_end)
+            .map(|d| d.score)
+            .collect();
+
+        weeks.push(WeeklyAverage {
+            week_start,
+            week_end,
+            entries: week_scores.len() as i64,
+            average_score: average(week_scores.into_iter()),
+        });
+
+        week_start = week_end + Duration::days(1);
+    }
+
+    weeks
+}
+
+fn best_day(scores: &[DailyScore]) -> Option<DailyScore> {
+    scores
+        .iter()
+        .fold(None, |best: Option<&DailyScore>, day| match best {
+            Some(b) if b.score >= day.score => Some(b),
+            _ => Some(day),
+        })
+        .cloned()
+}
+
+fn worst_day(scores: &[DailyScore]) -> Option<DailyScore> {
+    scores
+        .iter()
+        .fold(None, |worst: Option<&DailyScore>, day| match worst {
+            Some(w) if w.score <= day.score => Some(w),
+            _ => Some(day),
+        })
+        .cloned()
+}
+
+/// Jumlah dan persentase tiap jenis mood, selalu berisi kelima jenis
+pub fn mood_distribution(moods: &[Mood]) -> Vec<MoodCount> {
+    let total = moods.len() as f64;
+    [MoodType::VeryHappy, MoodType::Happy, MoodType::Neutral, MoodType::Sad, MoodType::VerySad]
+        .iter()
+        .map(|mood_type| {
+            let count = moods.iter().filter(|m| m.mood == mood_type.as_str()).count() as i64;
+            MoodCount {
+                mood: mood_type.as_str().to_string(),
+                count,
+                percentage: if total > 0.0 { count as f64 * 100.0 / total } else { 0.0 },
+            }
+        })
+        .collect()
+}
+
+/// Seri per detail mood beserta rata-rata dan korelasinya dengan skor mood harian
+fn detail_series(moods: &[Mood]) -> Vec<DetailSeries> {
+    let mut entries: Vec<(NaiveDate, i32, MoodDetails)> = moods
+        .iter()
+        .filter_map(|mood| {
+            let score = mood.mood.parse::<MoodType>().ok()?.score();
+            let details = MoodDetails::from_json(mood.details.clone())?;
+            Some((mood.date, score, details))
+        })
+        .collect();
+    entries.sort_by_key(|(date, _, _)| *date);
+
+    MoodDetails::METRICS
+        .iter()
+        .map(|name| {
+            let pairs: Vec<(NaiveDate, f64, f64)> = entries
+                .iter()
+                .filter_map(|(date, score, details)| Some((*date, details.metric(name)?, *score as f64)))
+                .collect();
+
+            let count = pairs.len() as f64;
+            DetailSeries {
+                name: name.to_string(),
+                average: (count > 0.0).then(|| pairs.iter().map(|(_, v, _)| v).sum::<f64>() / count),
+                mood_correlation: pearson(&pairs.iter().map(|(_, v, s)| (*v, *s)).collect::<Vec<_>>()),
+                points: pairs.into_iter().map(|(date, value, _)| DetailPoint { date, value }).collect(),
+            }
+        })
+        .collect()
+}
+
+/// Seri langkah dan tidur dari aplikasi kesehatan, dikorelasikan dengan skor mood hari yang sama
+fn health_series(scores: &[DailyScore], health: &BTreeMap<NaiveDate, DailyHealth>) -> Vec<DetailSeries> {
+    DailyHealth::METRICS
+        .iter()
+        .map(|name| {
+            let points: Vec<DetailPoint> = health
+                .iter()
+                .filter_map(|(date, day)| Some(DetailPoint { date: *date, value: day.metric(name)? }))
+                .collect();
+            let pairs: Vec<(f64, f64)> = scores
+                .iter()
+                .filter_map(|score| Some((health.get(&score.date)?.metric(name)?, score.score as f64)))
+                .collect();
+
+            let count = points.len() as f64;
+            DetailSeries {
+                name: name.to_string(),
+                average: (count > 0.0).then(|| points.iter().map(|point| point.value).sum::<f64>() / count),
+                mood_correlation: pearson(&pairs),
+                points,
+            }
+        })
+        .collect()
+}
+
+/// Korelasi Pearson; butuh minimal 3 pasangan dan variasi di kedua sisi
+fn pearson(pairs: &[(f64, f64)]) -> Option<f64> {
+    if pairs.len() < 3 {
+        return None;
+    }
+    let n = pairs.len() as f64;
+    let mean_x = pairs.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = pairs.iter().map(|(_, y)| y).sum::<f64>() / n;
+
+    let (mut covariance, mut variance_x, mut variance_y) = (0.0, 0.0, 0.0);
+    for (x, y) in pairs {
+        covariance += (x - mean_x) * (y - mean_y);
+        variance_x += (x - mean_x).powi(2);
+        variance_y += (y - mean_y).powi(2);
+    }
+
+    if variance_x == 0.0 || variance_y == 0.0 {
+        return None;
+    }
+    Some((covariance / (variance_x.sqrt() * variance_y.sqrt())).clamp(-1.0, 1.0))
+}
+
+/// Rangkaian hari berturut-turut terpanjang dengan catatan mood
+fn longest_streak(scores: &[DailyScore], tz: Tz) -> Option<StreakSummary> {
+    let stats = StreakEngine::new(tz).summarize(scores.iter().map(|day| day.date));
+    stats.longest.map(|streak| StreakSummary {
+        start_date: streak.start_date,
+        end_date: streak.end_date,
+        length: streak.length,
+    })
+}
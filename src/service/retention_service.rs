@@ -0,0 +1,117 @@
+use chrono::{Duration, Months, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use crate::config::app_config::app_config;
+use crate::db::{journal_query, retention_query, user_query};
+use crate::db::pool::DbPools;
+use crate::db::transaction::run_in_transaction;
+use crate::errors::app_error::AppError;
+use crate::models::audit::{AuditAction, NewAuditLog};
+use crate::models::retention::{NewJournalRetentionState, RetentionRun};
+use crate::models::user::{User, UserSettings};
+use crate::service::audit_service;
+use crate::utils::mailer::{EmailMessage, Mailer};
+use crate::utils::stats_cache::StatsCache;
+
+/// Batas atas `journal_retention_years`
+pub const MAX_RETENTION_YEARS: u32 = 50;
+const RETENTION_SETTING_KEY: &str = "journal_retention_years";
+
+/// Jalankan retensi jurnal untuk semua pengguna yang mengaturnya. Sebelum penghapusan
+/// pertama pengguna menerima email dan penghapusan ditunda RETENTION_NOTICE_DAYS hari;
+/// jurnal yang disematkan tidak pernah dihapus.
+pub fn apply_retention(
+    pool: &DbPools,
+    cache: &StatsCache,
+    mailer: &dyn Mailer,
+) -> Result<RetentionRun, AppError> {
+    let mut conn = pool.conn_write()?;
+
+    let now = Utc::now().naive_utc();
+    let notice_period = Duration::days(app_config().retention_notice_days);
+    let mut run = RetentionRun::default();
+
+    for user in user_query::find_users_with_setting(&mut conn, RETENTION_SETTING_KEY)? {
+        let Some(years) = UserSettings::parse(user.settings.as_deref()).journal_retention_years else {
+            continue;
+        };
+        let cutoff = retention_cutoff(now, years);
+        let due = journal_query::count_unpinned_journals_before(&mut conn, user.id, cutoff)?;
+        if due == 0 {
+            continue;
+        }
+
+        match retention_query::find_state(&mut conn, user.id)? {
+            None => {
+                send_notice(mailer, &user, years, due, now + notice_period)?;
+                retention_query::create_state(
+                    &mut conn,
+                    &NewJournalRetentionState {
+                        user_id: user.id,
+                        notice_sent_at: now,
+                    },
+                )?;
+                run.notified += 1;
+            }
+            Some(state) if state.notice_sent_at + notice_period <= now => {
+                let purged = run_in_transaction(&mut conn, |conn| {
+                    let purged = journal_query::delete_unpinned_journals_before(conn, user.id, cutoff)?;
+                    retention_query::mark_purged(conn, user.id, now)?;
+                    audit_service::record(
+                        conn,
+                        NewAuditLog::new(AuditAction::RetentionPurge, None, Some(user.id), None)
+                            .with_details(format!("journals={} before={}", purged, cutoff.date())),
+                    )?;
+                    Ok(purged)
+                })?;
+                cache.invalidate_user(user.id);
+                run.purged += purged;
+            }
+            Some(_) => {}
+        }
+    }
+
+    Ok(run)
+}
+
+/// Validasi `journal_retention_years` dari settings pengguna
+pub fn validate_retention_years(years: u32) -> Result<(), AppError> {
+    if !(1..=MAX_RETENTION_YEARS).contains(&years) {
+        return Err(AppError::BadRequest(format!(
+            "Journal retention must be between 1 and {} years",
+            MAX_RETENTION_YEARS
+        )));
+    }
+    Ok(())
+}
+
+/// Jurnal yang dibuat sebelum awal hari ini dikurangi `years` tahun dianggap kedaluwarsa
+fn retention_cutoff(now: NaiveDateTime, years: u32) -> NaiveDateTime {
+    now.date()
+        .checked_sub_months(Months::new(years * 12))
+        .unwrap_or(NaiveDate::MIN)
+        .and_time(NaiveTime::MIN)
+}
+
+fn send_notice(
+    mailer: &dyn Mailer,
+    user: &User,
+    years: u32,
+    due: i64,
+    purge_after: NaiveDateTime,
+) -> Result<(), AppError> {
+    // Akun tamu tidak punya alamat email; penghapusan tetap ditunda sama lamanya
+    if user.is_guest {
+        return Ok(());
+    }
+
+    mailer.send(&EmailMessage {
+        to: user.email.clone(),
+        subject: "Your older MindMate journals will be deleted soon".to_string(),
+        body: format!(
+            "Hi {},\n\nYou asked MindMate to keep journals for {} year(s). {} journal(s) are older than that and will be deleted on or after {}.\n\nPinned journals are never deleted. To keep these journals, pin or export them, or change the retention setting before then.",
+            user.username,
+            years,
+            due,
+            purge_after.format("%Y-%m-%d")
+        ),
+    })
+}
@@ -0,0 +1,18 @@
+use crate::models::security::LoginAttemptResponse;
+use crate::db::login_attempt_query;
+use crate::errors::app_error::AppError;
+use crate::db::pool::DbPools;
+
+const LOGIN_HISTORY_LIMIT: i64 = 20;
+
+/// Riwayat login terbaru (berhasil dan gagal) milik pengguna
+pub fn get_login_history(
+    pool: &DbPools,
+    user_id: i32,
+) -> Result<Vec<LoginAttemptResponse>, AppError> {
+    let mut conn = pool.conn_read()?;
+
+    let attempts = login_attempt_query::find_recent_attempts_by_user(&mut conn, user_id, LOGIN_HISTORY_LIMIT)?;
+
+    Ok(attempts.into_iter().map(LoginAttemptResponse::from).collect())
+}
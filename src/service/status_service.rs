@@ -0,0 +1,92 @@
+use std::fmt::Write;
+use std::time::Duration;
+use crate::db::pool::DbPools;
+use crate::models::audit::{AuditAction, NewAuditLog};
+use crate::models::status::{DatabaseStatus, MaintenanceStatus, PoolStats, ReadinessResponse, UpdateMaintenanceRequest};
+use crate::service::audit_service;
+use crate::utils::maintenance::MaintenanceMode;
+
+/// Probe readiness tidak boleh menunggu selama connection timeout biasa
+const READINESS_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Siap melayani jika primary bisa memberikan koneksi; replica yang mati tidak dihitung
+/// karena baca otomatis jatuh ke primary
+pub fn readiness(pool: &DbPools) -> ReadinessResponse {
+    let primary_available = pool.primary_available(READINESS_TIMEOUT);
+
+    ReadinessResponse {
+        status: if primary_available { "ready" } else { "unavailable" }.to_string(),
+        database: DatabaseStatus {
+            primary_available,
+            primary: pool.primary_stats(),
+            replica: pool.replica_stats(),
+        },
+    }
+}
+
+/// Statistik pool dalam format teks Prometheus
+pub fn metrics(pool: &DbPools) -> String {
+    let mut pools = vec![("primary", pool.primary_stats())];
+    if let Some(replica) = pool.replica_stats() {
+        pools.push(("replica", replica));
+    }
+    render_pool_metrics(&pools)
+}
+
+/// Ubah mode maintenance oleh admin. Perubahan tetap berlaku walaupun audit log gagal ditulis,
+/// karena database bisa saja sedang di-restore.
+pub fn set_maintenance_by_admin(
+    pool: &DbPools,
+    maintenance: &MaintenanceMode,
+    admin_user_id: i32,
+    data: UpdateMaintenanceRequest,
+    ip_address: Option<&str>,
+) -> MaintenanceStatus {
+    maintenance.set(data.enabled, data.retry_after_secs);
+    let status = maintenance.status();
+
+    let audit = pool.conn_write().and_then(|mut conn| {
+        audit_service::record(
+            &mut conn,
+            NewAuditLog::new(AuditAction::AdminAction, Some(admin_user_id), None, ip_address)
+                .with_details(format!("maintenance enabled={} retry_after={}", status.enabled, status.retry_after_secs)),
+        )
+    });
+    if let Err(e) = audit {
+        eprintln!("❌ Failed to record maintenance audit log: {}", e);
+    }
+
+    status
+}
+
+fn render_pool_metrics(pools: &[(&str, PoolStats)]) -> String {
+    let mut out = String::new();
+    write_gauge(&mut out, "mindmate_db_pool_max_connections", "Maximum connections in the pool", pools, |stats| stats.max_size);
+    write_gauge(&mut out, "mindmate_db_pool_connections", "Open connections, including idle ones", pools, |stats| stats.connections);
+    write_gauge(&mut out, "mindmate_db_pool_idle_connections", "Idle connections", pools, |stats| stats.idle_connections);
+    out
+}
+
+fn write_gauge(out: &mut String, name: &str, help: &str, pools: &[(&str, PoolStats)], value: fn(&PoolStats) -> u32) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} gauge", name);
+    for (label, stats) in pools {
+        let _ = writeln!(out, "{}{{pool=\"{}\"}} {}", name, label, value(stats));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_one_sample_per_pool() {
+        let stats = PoolStats { max_size: 10, connections: 4, idle_connections: 3 };
+        let text = render_pool_metrics(&[("primary", stats), ("replica", stats)]);
+
+        assert!(text.contains("# TYPE mindmate_db_pool_connections gauge\n"));
+        assert!(text.contains("mindmate_db_pool_connections{pool=\"primary\"} 4\n"));
+        assert!(text.contains("mindmate_db_pool_idle_connections{pool=\"replica\"} 3\n"));
+        assert_eq!(text.lines().filter(|line| line.starts_with("mindmate_db_pool_max_connections")).count(), 2);
+    }
+}
@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+use chrono::{Duration, NaiveDate, Utc};
+use crate::config::app_config::app_config;
+use crate::db::pool::DbPools;
+use crate::db::{api_usage_query, quota_query};
+use crate::errors::app_error::AppError;
+use crate::models::api_usage::{ApiUsageResponse, DailyUsage, QuotaUsage};
+use crate::models::quota::QuotaOperation;
+use crate::service::quota_service;
+use crate::utils::api_usage::ApiUsageCounter;
+
+const DEFAULT_DAYS: i64 = 30;
+
+/// Statistik request API pengguna beserta pemakaian kuota harian. Hitungan yang belum
+/// disimpan ke database ikut dijumlahkan, jadi angka hari ini langsung terlihat.
+pub fn get_usage(
+    pool: &DbPools,
+    counter: &ApiUsageCounter,
+    user_id: i32,
+    days: Option<i64>,
+) -> Result<ApiUsageResponse, AppError> {
+    let max_days = app_config().api_usage_retention_days;
+    let days = days.unwrap_or(DEFAULT_DAYS.min(max_days));
+    if !(1..=max_days).contains(&days) {
+        return Err(AppError::BadRequest(format!("Days must be between 1 and {}", max_days)));
+    }
+
+    let today = Utc::now().date_naive();
+    let since = today - Duration::days(days - 1);
+
+    let mut conn = pool.conn_read()?;
+    let mut counts: HashMap<NaiveDate, i64> = api_usage_query::find_user_days(&mut conn, user_id, since)?
+        .into_iter()
+        .collect();
+    for (day, pending) in counter.pending(user_id) {
+        *counts.entry(day).or_default() += pending;
+    }
+    let days = daily_series(since, today, &counts);
+
+    let used: HashMap<String, i32> = quota_query::find_user_period(&mut conn, user_id, today)?
+        .into_iter()
+        .collect();
+    let quotas = QuotaOperation::ALL
+        .iter()
+        .map(|operation| {
+            let limit = operation.daily_limit(app_config());
+            QuotaUsage {
+                operation: operation.as_str().to_string(),
+                used: used.get(operation.as_str()).copied().unwrap_or(0),
+                limit: (limit > 0).then_some(limit),
+                reset_at: quota_service::reset_at(today),
+            }
+        })
+        .collect();
+
+    Ok(ApiUsageResponse {
+        total_requests: days.iter().map(|day| day.requests).sum(),
+        days,
+        quotas,
+    })
+}
+
+/// Satu entri per hari dari `since` sampai `until`, hari tanpa catatan bernilai 0
+fn daily_series(since: NaiveDate, until: NaiveDate, counts: &HashMap<NaiveDate, i64>) -> Vec<DailyUsage> {
+    since
+        .iter_days()
+        .take_while(|date| *date <= until)
+        .map(|date| DailyUsage {
+            date,
+            requests: counts.get(&date).copied().unwrap_or(0),
+        })
+        .collect()
+}
+
+/// Simpan hitungan request dari memori ke database. Jika gagal, hitungan dikembalikan
+/// ke counter agar dicoba lagi pada penyimpanan berikutnya.
+pub fn flush(pool: &DbPools, counter: &ApiUsageCounter) -> Result<usize, AppError> {
+    let rows = counter.drain();
+    if rows.is_empty() {
+        return Ok(0);
+    }
+
+    let result = pool
+        .conn_write()
+        .and_then(|mut conn| api_usage_query::add_counts(&mut conn, &rows));
+    if result.is_err() {
+        counter.restore(rows);
+    }
+    result
+}
+
+/// Hapus statistik harian yang lebih lama dari API_USAGE_RETENTION_DAYS
+pub fn cleanup_old_usage(pool: &DbPools) -> Result<usize, AppError> {
+    let mut conn = pool.conn_write()?;
+
+    let cutoff = Utc::now().date_naive() - Duration::days(app_config().api_usage_retention_days);
+    api_usage_query::delete_before(&mut conn, cutoff)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn daily_series_fills_missing_days_with_zero() {
+        let since = NaiveDate::from_ymd_opt(2025, 7, 30).unwrap();
+        let until = NaiveDate::from_ymd_opt(2025, 8, 1).unwrap();
+        let counts = HashMap::from([(since, 4), (until, 2)]);
+
+        let series = daily_series(since, until, &counts);
+        let requests: Vec<i64> = series.iter().map(|day| day.requests).collect();
+        assert_eq!(requests, vec![4, 0, 2]);
+        assert_eq!(series[1].date, NaiveDate::from_ymd_opt(2025, 7, 31).unwrap());
+    }
+}
@@ -1,27 +1,38 @@
-use crate::models::user::{User, UserResponse};
-use crate::db::user_query;
+use crate::models::user::{EditProfileRequest, PatchProfileRequest, User, UserProfileChanges, UserResponse, UserSettings};
+use crate::models::audit::{AuditAction, NewAuditLog};
+use crate::models::onboarding::OnboardingStep;
+use crate::service::{audit_service, notification_service, onboarding_service, retention_service};
+use crate::utils::timezone::parse_timezone;
+use crate::utils::password_policy::PasswordPolicy;
+use crate::utils::email::normalize_email;
+use crate::utils::username::{normalize_username, validate_username};
+use crate::config::app_config::app_config;
+use crate::db::{retention_query, user_query};
 use crate::errors::app_error::AppError;
-use diesel::r2d2;
-use diesel::pg::PgConnection;
+use crate::db::pool::DbPools;
+use crate::repo::UserRepo;
+use crate::db::transaction::run_in_transaction;
 use bcrypt::{hash, verify, DEFAULT_COST};
+use chrono::NaiveTime;
+use crate::i18n::{localize_message, t, Locale};
 use serde::Serialize;
+use utoipa::ToSchema;
 
-// Response struct for email check
-#[derive(Serialize)]
-pub struct EmailCheckResponse {
-    pub exists: bool,
+// Response struct for username check
+#[derive(Serialize, ToSchema)]
+pub struct UsernameCheckResponse {
+    /// Username setelah dinormalisasi, yang akan tersimpan saat registrasi
+    pub username: String,
+    pub available: bool,
     pub message: String,
 }
 
 pub fn get_user_by_id(
-    pool: &r2d2::Pool<r2d2::ConnectionManager<PgConnection>>,
+    users: &dyn UserRepo,
     user_id: i32,
 ) -> Result<UserResponse, AppError> {
-    let mut conn = pool
-        .get()
-        .map_err(|_| AppError::InternalServerError("Failed to get DB connection".to_string()))?;
-
-    let user = user_query::find_user_by_id(&mut conn, user_id)
+    let user = users
+        .find_by_id(user_id)
         .map_err(|_| AppError::NotFound("User not found".to_string()))?;
 
     Ok(UserResponse {
@@ -35,46 +46,93 @@ pub fn get_user_by_id(
         settings: user.settings.clone(),
         created_at: user.created_at,
         updated_at: user.updated_at,
+        is_guest: user.is_guest,
     })
 }
 
 pub fn edit_profile(
-    pool: &r2d2::Pool<r2d2::ConnectionManager<PgConnection>>,
+    pool: &DbPools,
     user_id: i32,
-    new_username: &str,
-    new_email: &str,
-    new_age: Option<i32>,
-    new_gender: Option<String>,
-    new_avatar: Option<String>, // Tambahan parameter avatar
+    data: EditProfileRequest,
 ) -> Result<UserResponse, AppError> {
-    let mut conn = pool
-        .get()
-        .map_err(|_| AppError::InternalServerError("Failed to get DB connection".to_string()))?;
+    let EditProfileRequest {
+        username,
+        email,
+        age,
+        gender,
+        avatar, // Tambahan parameter avatar
+    } = data;
 
-    // Check if user exists
-    let existing_user = user_query::find_user_by_id(&mut conn, user_id)
-        .map_err(|_| AppError::NotFound("User not found".to_string()))?;
+    save_profile(
+        pool,
+        user_id,
+        Some(email),
+        UserProfileChanges {
+            username: Some(username),
+            age: Some(age),
+            gender: Some(gender),
+            avatar: Some(avatar),
+        },
+    )
+}
 
-    // Check if new email is already taken by another user
-    if new_email != existing_user.email {
-        if let Ok(other_user) = user_query::find_user_by_email(&mut conn, new_email) {
-            if other_user.id != user_id {
-                return Err(AppError::BadRequest("Email already exists".to_string()));
-            }
+/// Seperti `edit_profile`, tetapi hanya field yang dikirim yang divalidasi dan diubah
+pub fn patch_profile(
+    pool: &DbPools,
+    user_id: i32,
+    data: PatchProfileRequest,
+) -> Result<UserResponse, AppError> {
+    let email = data.email.required("Email")?;
+    let changes = UserProfileChanges {
+        username: data.username.required("Username")?,
+        age: data.age.into_change(),
+        gender: data.gender.into_change(),
+        avatar: data.avatar.into_change(),
+    };
+
+    save_profile(pool, user_id, email, changes)
+}
+
+fn save_profile(
+    pool: &DbPools,
+    user_id: i32,
+    email: Option<String>,
+    mut changes: UserProfileChanges,
+) -> Result<UserResponse, AppError> {
+    changes.username = changes.username.map(|username| normalize_username(&username));
+
+    let mut conn = pool.conn_write()?;
+
+    let updated_user = run_in_transaction(&mut conn, |conn| {
+        // Check if user exists
+        let existing_user = user_query::find_user_by_id(conn, user_id)
+            .map_err(|_| AppError::NotFound("User not found".to_string()))?;
+
+        // Ganti email harus lewat alur konfirmasi (/user/email/change)
+        if email.as_deref().is_some_and(|email| normalize_email(email) != normalize_email(&existing_user.email)) {
+            return Err(AppError::BadRequest(
+                "Email changes require confirmation, use /user/email/change".to_string(),
+            ));
         }
-    }
 
-    // Check if new username is already taken by another user
-    if new_username != existing_user.username {
-        if let Ok(other_user) = user_query::find_user_by_username(&mut conn, new_username) {
-            if other_user.id != user_id {
-                return Err(AppError::BadRequest("Username already exists".to_string()));
+        // Check if new username is already taken by another user
+        if let Some(ref new_username) = changes.username {
+            if !new_username.eq_ignore_ascii_case(&existing_user.username) {
+                validate_username(new_username)?;
+                if user_query::username_exists(conn, new_username)? {
+                    return Err(AppError::Conflict("Username already exists".to_string()));
+                }
             }
         }
-    }
 
-    // Update user dengan tambahan avatar parameter
-    let updated_user = user_query::update_user_profile(&mut conn, user_id, new_username, new_email, new_age, new_gender, new_avatar)?;
+        if changes.is_empty() {
+            return Ok(existing_user);
+        }
+
+        let updated_user = user_query::update_user_profile(conn, user_id, &changes)?;
+        onboarding_service::complete_profile_step_if_filled(conn, &updated_user)?;
+        Ok(updated_user)
+    })?;
 
     Ok(UserResponse {
         id: updated_user.id,
@@ -87,36 +145,32 @@ pub fn edit_profile(
         settings: updated_user.settings.clone(),
         created_at: updated_user.created_at,
         updated_at: updated_user.updated_at,
+        is_guest: updated_user.is_guest,
     })
 }
 
 // Function for internal use to get full user data including password hash
 pub fn get_user_full_data(
-    pool: &r2d2::Pool<r2d2::ConnectionManager<PgConnection>>,
+    users: &dyn UserRepo,
     user_id: i32,
 ) -> Result<User, AppError> {
-    let mut conn = pool
-        .get()
-        .map_err(|_| AppError::InternalServerError("Failed to get DB connection".to_string()))?;
-
-    let user = user_query::find_user_by_id(&mut conn, user_id)
+    let user = users
+        .find_by_id(user_id)
         .map_err(|_| AppError::NotFound("User not found".to_string()))?;
 
     Ok(user)
 }
 
 pub fn change_password(
-    pool: &r2d2::Pool<r2d2::ConnectionManager<PgConnection>>,
+    pool: &DbPools,
     user_id: i32,
     old_password: &str,
     new_password: &str,
+    ip_address: Option<&str>,
 ) -> Result<(), AppError> {
-    let mut conn = pool
-        .get()
-        .map_err(|_| AppError::InternalServerError("Failed to get DB connection".to_string()))?;
+    let mut conn = pool.conn_write()?;
 
-    // Find user - using get_user_full_data for consistency
-    let user = get_user_full_data(pool, user_id)?;
+    let user = user_query::find_user_by_id(&mut conn, user_id)?;
 
     // Verify old password
     let is_valid = verify(old_password, &user.password)
@@ -126,25 +180,84 @@ pub fn change_password(
         return Err(AppError::BadRequest("Invalid old password".to_string()));
     }
 
+    PasswordPolicy::from_config(app_config()).validate(new_password, &[&user.username, &user.email])?;
+
     // Hash new password
     let hashed_new_password = hash(new_password, DEFAULT_COST)
         .map_err(|_| AppError::InternalServerError("Failed to hash password".to_string()))?;
 
     // Update password
     user_query::update_user_password(&mut conn, user_id, &hashed_new_password)?;
+    audit_service::record(
+        &mut conn,
+        NewAuditLog::new(AuditAction::PasswordChange, Some(user_id), Some(user_id), ip_address),
+    )?;
 
     Ok(())
 }
 
+pub fn get_user_settings(
+    users: &dyn UserRepo,
+    user_id: i32,
+) -> Result<UserSettings, AppError> {
+    let user = get_user_full_data(users, user_id)?;
+    Ok(UserSettings::parse(user.settings.as_deref()))
+}
+
+pub fn update_user_settings(
+    pool: &DbPools,
+    user_id: i32,
+    mut settings: UserSettings,
+) -> Result<UserSettings, AppError> {
+    let mut conn = pool.conn_write()?;
+
+    if let Some(ref timezone) = settings.timezone {
+        if parse_timezone(timezone).is_none() {
+            return Err(AppError::BadRequest(format!("Unknown timezone: {}", timezone)));
+        }
+    }
+
+    if let Some(ref language) = settings.language {
+        let locale = Locale::parse(language)
+            .ok_or_else(|| AppError::BadRequest(format!("Unsupported language: {}", language)))?;
+        settings.language = Some(locale.as_str().to_string());
+    }
+
+    if let Some(ref reminder_time) = settings.reminder_time {
+        if NaiveTime::parse_from_str(reminder_time, "%H:%M").is_err() {
+            return Err(AppError::BadRequest(format!("Invalid reminder time: {} (expected HH:MM)", reminder_time)));
+        }
+    }
+
+    if let Some(ref notifications) = settings.notifications {
+        notification_service::validate_preferences(notifications)?;
+    }
+
+    if let Some(years) = settings.journal_retention_years {
+        retention_service::validate_retention_years(years)?;
+    }
+
+    let raw = serde_json::to_string(&settings)
+        .map_err(|_| AppError::InternalServerError("Failed to serialize settings".to_string()))?;
+
+    let previous = UserSettings::parse(user_query::find_user_by_id(&mut conn, user_id)?.settings.as_deref());
+    let updated_user = user_query::update_user_settings(&mut conn, user_id, &raw)?;
+    // Aturan retensi baru harus diberitahukan lagi sebelum menghapus jurnal
+    if previous.journal_retention_years != settings.journal_retention_years {
+        retention_query::delete_state(&mut conn, user_id)?;
+    }
+    if settings.reminder_time.is_some() {
+        onboarding_service::complete_step(&mut conn, user_id, OnboardingStep::ReminderSet)?;
+    }
+
+    Ok(UserSettings::parse(updated_user.settings.as_deref()))
+}
+
 // New function to get all users
 pub fn get_all_users(
-    pool: &r2d2::Pool<r2d2::ConnectionManager<PgConnection>>,
+    users: &dyn UserRepo,
 ) -> Result<Vec<UserResponse>, AppError> {
-    let mut conn = pool
-        .get()
-        .map_err(|_| AppError::InternalServerError("Failed to get DB connection".to_string()))?;
-
-    let users = user_query::get_all_users(&mut conn)?;
+    let users = users.find_all()?;
 
     // Map User to UserResponse dengan tambahan avatar
     let user_responses = users.into_iter().map(|user| UserResponse {
@@ -158,53 +271,55 @@ pub fn get_all_users(
         settings: user.settings.clone(),
         created_at: user.created_at,
         updated_at: user.updated_at,
+        is_guest: user.is_guest,
     }).collect();
 
     Ok(user_responses)
 }
 
-// Function to check if email exists - untuk forgot password flow
-pub fn check_email_exists(
-    pool: &r2d2::Pool<r2d2::ConnectionManager<PgConnection>>,
-    email: &str,
-) -> Result<EmailCheckResponse, AppError> {
-    let mut conn = pool
-        .get()
-        .map_err(|_| AppError::InternalServerError("Failed to get DB connection".to_string()))?;
-
-    match user_query::find_user_by_email(&mut conn, email) {
-        Ok(_) => Ok(EmailCheckResponse {
-            exists: true,
-            message: "Email exists in database - you can proceed to reset password".to_string(),
-        }),
-        Err(AppError::NotFound(_)) => Ok(EmailCheckResponse {
-            exists: false,
-            message: "Email not found in database".to_string(),
-        }),
-        Err(e) => Err(e),
+/// Cek ketersediaan username dengan aturan yang sama seperti registrasi
+pub fn check_username_available(
+    users: &dyn UserRepo,
+    username: &str,
+) -> Result<UsernameCheckResponse, AppError> {
+    let normalized = normalize_username(username);
+
+    if let Err(AppError::BadRequest(message)) = validate_username(&normalized) {
+        return Ok(UsernameCheckResponse {
+            username: normalized,
+            available: false,
+            message: localize_message(&message),
+        });
     }
-}
 
-// New function to reset password by email (for forgot password)
-pub fn reset_password(
-    pool: &r2d2::Pool<r2d2::ConnectionManager<PgConnection>>,
-    email: &str,
-    new_password: &str,
-) -> Result<(), AppError> {
-    let mut conn = pool
-        .get()
-        .map_err(|_| AppError::InternalServerError("Failed to get DB connection".to_string()))?;
+    if users.username_exists(&normalized)? {
+        return Ok(UsernameCheckResponse {
+            username: normalized,
+            available: false,
+            message: t("message.username_taken"),
+        });
+    }
 
-    // First, check if user exists with this email
-    let user = user_query::find_user_by_email(&mut conn, email)
-        .map_err(|_| AppError::NotFound("Email not found in database".to_string()))?;
+    Ok(UsernameCheckResponse {
+        username: normalized,
+        available: true,
+        message: t("message.username_available"),
+    })
+}
 
-    // Hash the new password
-    let hashed_new_password = hash(new_password, DEFAULT_COST)
-        .map_err(|_| AppError::InternalServerError("Failed to hash password".to_string()))?;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repo::fake::FakeUserRepo;
 
-    // Update password using user ID
-    user_query::update_user_password(&mut conn, user.id, &hashed_new_password)?;
+    #[test]
+    fn username_availability_uses_normalized_name() {
+        let repo = FakeUserRepo::with(vec![FakeUserRepo::user(1, "budi")]);
 
-    Ok(())
-}
\ No newline at end of file
+        let taken = check_username_available(&repo, "  Budi ").unwrap();
+        assert_eq!(taken.username, "budi");
+        assert!(!taken.available);
+        assert!(check_username_available(&repo, "sari").unwrap().available);
+        assert!(matches!(get_user_by_id(&repo, 2), Err(AppError::NotFound(_))));
+    }
+}
@@ -0,0 +1,254 @@
+use std::collections::HashMap;
+use chrono::{Datelike, Duration, NaiveDate, Utc};
+use chrono_tz::Tz;
+use url::Url;
+use crate::config::app_config::app_config;
+use crate::db::{user_query, webhook_query};
+use crate::db::pool::DbPools;
+use crate::errors::app_error::AppError;
+use crate::i18n::{t_in, t_in_args, Locale};
+use crate::integrations::{self, SummaryField, SummaryMessage, WebhookError, WebhookPlatform};
+use crate::models::insight::WeekSummary;
+use crate::models::mood::MoodType;
+use crate::models::user::UserSettings;
+use crate::models::webhook::{
+    CreateWebhookRequest, NewUserWebhook, NewWebhookDelivery, UserWebhook, WebhookDeliveryResponse, WebhookResponse,
+};
+use crate::service::insight_service;
+use crate::utils::http_client::HttpClient;
+use crate::utils::timezone::{parse_timezone, today_in};
+
+const MAX_WEBHOOKS_PER_USER: i64 = 5;
+/// Jumlah entri antrian yang diproses per putaran job
+const DELIVERY_BATCH_SIZE: i64 = 50;
+/// Jeda retry pertama, digandakan setiap percobaan sampai RETRY_MAX_DELAY
+const RETRY_BASE_DELAY_SECS: i64 = 60;
+const RETRY_MAX_DELAY_SECS: i64 = 6 * 60 * 60;
+
+/// `https://host/…` diikuti 4 karakter terakhir, cukup untuk mengenali webhook tanpa membocorkan token
+pub fn url_preview(url: &str) -> String {
+    let host = Url::parse(url).ok().and_then(|url| url.host_str().map(str::to_string)).unwrap_or_default();
+    let tail: String = url.chars().rev().take(4).collect::<Vec<_>>().into_iter().rev().collect();
+    format!("https://{}/…{}", host, tail)
+}
+
+fn to_response(webhook: UserWebhook, last_delivery: Option<WebhookDeliveryResponse>) -> WebhookResponse {
+    WebhookResponse {
+        id: webhook.id,
+        url_preview: url_preview(&webhook.url),
+        platform: webhook.platform,
+        created_at: webhook.created_at,
+        last_delivery,
+    }
+}
+
+pub fn get_webhooks(
+    pool: &DbPools,
+    user_id: i32,
+) -> Result<Vec<WebhookResponse>, AppError> {
+    let mut conn = pool.conn_read()?;
+
+    let webhooks = webhook_query::find_webhooks_by_user(&mut conn, user_id)?;
+    let ids: Vec<i32> = webhooks.iter().map(|webhook| webhook.id).collect();
+    let mut deliveries: HashMap<i32, WebhookDeliveryResponse> = webhook_query::find_last_deliveries(&mut conn, &ids)?
+        .into_iter()
+        .map(|delivery| (delivery.webhook_id, WebhookDeliveryResponse::from(delivery)))
+        .collect();
+
+    Ok(webhooks
+        .into_iter()
+        .map(|webhook| {
+            let last_delivery = deliveries.remove(&webhook.id);
+            to_response(webhook, last_delivery)
+        })
+        .collect())
+}
+
+pub fn register_webhook(
+    pool: &DbPools,
+    user_id: i32,
+    data: CreateWebhookRequest,
+) -> Result<WebhookResponse, AppError> {
+    let platform = WebhookPlatform::parse(&data.platform)
+        .ok_or_else(|| AppError::BadRequest(format!("Unsupported webhook platform: {}", data.platform)))?;
+    let url = platform.validate_url(&data.url)?;
+
+    let mut conn = pool.conn_write()?;
+
+    if webhook_query::count_webhooks_by_user(&mut conn, user_id)? >= MAX_WEBHOOKS_PER_USER {
+        return Err(AppError::BadRequest(format!("You can register at most {} webhooks", MAX_WEBHOOKS_PER_USER)));
+    }
+    let webhook = webhook_query::insert_webhook(
+        &mut conn,
+        &NewUserWebhook {
+            user_id,
+            platform: platform.as_str(),
+            url: &url,
+        },
+    )?;
+    Ok(to_response(webhook, None))
+}
+
+pub fn delete_webhook(
+    pool: &DbPools,
+    user_id: i32,
+    webhook_id: i32,
+) -> Result<(), AppError> {
+    let mut conn = pool.conn_write()?;
+
+    if webhook_query::delete_webhook(&mut conn, webhook_id, user_id)? == 0 {
+        return Err(AppError::NotFound("Webhook not found".to_string()));
+    }
+    Ok(())
+}
+
+/// Susun ringkasan minggu `summary` dibanding minggu sebelumnya dalam bahasa pengguna
+pub fn summary_message(locale: Locale, summary: &WeekSummary, previous: &WeekSummary) -> SummaryMessage {
+    let no_data = || t_in(locale, "webhook.summary.no_data");
+    let average = summary
+        .average_score
+        .map(|score| format!("{:.1} / 5", score))
+        .unwrap_or_else(no_data);
+    let change = match (summary.average_score, previous.average_score) {
+        (Some(this_week), Some(last_week)) => format!("{:+.1}", this_week - last_week),
+        _ => no_data(),
+    };
+    let top_mood = summary
+        .mood_distribution
+        .iter()
+        .filter(|count| count.count > 0)
+        .max_by_key(|count| count.count)
+        .map(|count| match count.mood.parse::<MoodType>() {
+            Ok(mood) => format!("{} {}", mood.default_emoji(), count.mood),
+            Err(_) => count.mood.clone(),
+        })
+        .unwrap_or_else(no_data);
+    let days = (summary.end_date - summary.start_date).num_days() + 1;
+
+    SummaryMessage {
+        title: t_in(locale, "webhook.summary.title"),
+        description: t_in_args(
+            locale,
+            "webhook.summary.period",
+            &[&summary.start_date.to_string(), &summary.end_date.to_string()],
+        ),
+        fields: vec![
+            SummaryField {
+                name: t_in(locale, "webhook.summary.entries"),
+                value: t_in_args(locale, "webhook.summary.entries_value", &[&summary.entries.to_string(), &days.to_string()]),
+            },
+            SummaryField { name: t_in(locale, "webhook.summary.average"), value: average },
+            SummaryField { name: t_in(locale, "webhook.summary.change"), value: change },
+            SummaryField { name: t_in(locale, "webhook.summary.top_mood"), value: top_mood },
+        ],
+        footer: t_in(locale, "webhook.summary.footer"),
+    }
+}
+
+/// Senin minggu lalu menurut tanggal lokal pengguna
+fn previous_week_start(today: NaiveDate) -> NaiveDate {
+    today - Duration::days(today.weekday().num_days_from_monday() as i64 + 7)
+}
+
+/// Antrikan ringkasan minggu lalu (Senin-Minggu) ke semua webhook. Aman dijalankan ulang:
+/// ringkasan untuk minggu yang sama tidak diantrikan dua kali.
+pub fn enqueue_weekly_summaries(pool: &DbPools) -> Result<usize, AppError> {
+    let mut conn = pool.conn_write()?;
+
+    let mut by_user: Vec<(i32, Vec<UserWebhook>)> = Vec::new();
+    for webhook in webhook_query::find_all_webhooks(&mut conn)? {
+        match by_user.last_mut() {
+            Some((user_id, webhooks)) if *user_id == webhook.user_id => webhooks.push(webhook),
+            _ => by_user.push((webhook.user_id, vec![webhook])),
+        }
+    }
+
+    let mut queued = 0;
+    for (user_id, webhooks) in by_user {
+        let user = user_query::find_user_by_id(&mut conn, user_id)?;
+        let settings = UserSettings::parse(user.settings.as_deref());
+        let locale = settings.language.as_deref().and_then(Locale::parse).unwrap_or_default();
+        let tz = settings.timezone.as_deref().and_then(parse_timezone).unwrap_or(Tz::UTC);
+
+        let start = previous_week_start(today_in(tz));
+        let summary = insight_service::summarize_range(&mut conn, user_id, start, start + Duration::days(6))?;
+        let previous = insight_service::summarize_range(
+            &mut conn,
+            user_id,
+            start - Duration::days(7),
+            start - Duration::days(1),
+        )?;
+        let message = summary_message(locale, &summary, &previous);
+
+        for webhook in webhooks {
+            let Some(platform) = WebhookPlatform::parse(&webhook.platform) else {
+                continue;
+            };
+            queued += webhook_query::insert_delivery(
+                &mut conn,
+                &NewWebhookDelivery {
+                    webhook_id: webhook.id,
+                    period_start: start,
+                    payload: platform.format(&message).to_string(),
+                },
+            )?;
+        }
+    }
+
+    Ok(queued)
+}
+
+/// Kirim antrian webhook yang sudah jatuh tempo. Kegagalan sementara dicoba lagi dengan
+/// backoff eksponensial sampai WEBHOOK_MAX_ATTEMPTS; penolakan permanen langsung ditandai gagal.
+pub async fn deliver_due(
+    pool: &DbPools,
+    http_client: &HttpClient,
+) -> Result<usize, AppError> {
+    let mut conn = pool.conn_write()?;
+
+    let due = webhook_query::find_due_deliveries(&mut conn, Utc::now().naive_utc(), DELIVERY_BATCH_SIZE)?;
+
+    let mut sent = 0;
+    for (delivery, webhook) in due {
+        let attempts = delivery.attempts + 1;
+        match integrations::send(http_client, &webhook.url, &delivery.payload).await {
+            Ok(()) => {
+                webhook_query::mark_delivered(&mut conn, delivery.id, attempts, Utc::now().naive_utc())?;
+                sent += 1;
+            }
+            Err(WebhookError::Rejected(reason)) => {
+                webhook_query::record_failure(&mut conn, delivery.id, attempts, None, &reason)?;
+            }
+            Err(WebhookError::Failed(reason)) => {
+                let next_attempt_at = (attempts < app_config().webhook_max_attempts).then(|| {
+                    let delay = (RETRY_BASE_DELAY_SECS << (attempts - 1).min(16)).min(RETRY_MAX_DELAY_SECS);
+                    Utc::now().naive_utc() + Duration::seconds(delay)
+                });
+                webhook_query::record_failure(&mut conn, delivery.id, attempts, next_attempt_at, &reason)?;
+            }
+        }
+    }
+
+    Ok(sent)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn preview_hides_the_webhook_secret() {
+        assert_eq!(
+            url_preview("https://hooks.slack.com/services/T000/B000/abcdWXYZ"),
+            "https://hooks.slack.com/…WXYZ"
+        );
+    }
+
+    #[test]
+    fn previous_week_starts_on_monday() {
+        let thursday = NaiveDate::from_ymd_opt(2026, 10, 15).unwrap();
+        let monday = NaiveDate::from_ymd_opt(2026, 10, 12).unwrap();
+        assert_eq!(previous_week_start(thursday), NaiveDate::from_ymd_opt(2026, 10, 5).unwrap());
+        assert_eq!(previous_week_start(monday), NaiveDate::from_ymd_opt(2026, 10, 5).unwrap());
+    }
+}
@@ -0,0 +1,70 @@
+use std::sync::Arc;
+use std::time::Duration;
+use crate::config::app_config::{app_config, AppConfig};
+use crate::db::pool::DbPools;
+use crate::errors::app_error::AppError;
+use crate::repo::{DieselJournalRepo, DieselMoodRepo, DieselUserRepo, JournalRepo, MoodRepo, UserRepo};
+use crate::utils::api_usage::ApiUsageCounter;
+use crate::utils::captcha::CaptchaGuard;
+use crate::utils::event_bus::EventBus;
+use crate::utils::http_client::HttpClient;
+use crate::utils::mailer::{mailer_from_config, Mailer};
+use crate::utils::maintenance::MaintenanceMode;
+use crate::utils::moderation::ModerationPipeline;
+use crate::utils::push::PushSender;
+use crate::utils::rate_limit::IpRateLimiter;
+use crate::utils::stats_cache::StatsCache;
+use crate::utils::storage::{LocalStorage, Storage};
+
+/// State bersama yang diteruskan ke semua handler lewat `with_state`
+#[derive(Clone)]
+pub struct AppState {
+    pub pool: DbPools,
+    pub moods: Arc<dyn MoodRepo>,
+    pub journals: Arc<dyn JournalRepo>,
+    pub users: Arc<dyn UserRepo>,
+    pub config: &'static AppConfig,
+    pub mailer: Arc<dyn Mailer>,
+    pub push_sender: Arc<PushSender>,
+    pub http_client: HttpClient,
+    pub event_bus: EventBus,
+    pub stats_cache: Arc<StatsCache>,
+    pub storage: Arc<dyn Storage>,
+    pub captcha: Arc<CaptchaGuard>,
+    pub moderation: Arc<ModerationPipeline>,
+    pub password_reset_limiter: Arc<IpRateLimiter>,
+    pub maintenance: Arc<MaintenanceMode>,
+    pub api_usage: Arc<ApiUsageCounter>,
+}
+
+impl AppState {
+    pub fn new(pool: DbPools) -> Result<Self, AppError> {
+        let config = app_config();
+        let http_client = HttpClient::from_config(config)?;
+
+        Ok(AppState {
+            moods: Arc::new(DieselMoodRepo::new(pool.clone())),
+            journals: Arc::new(DieselJournalRepo::new(pool.clone())),
+            users: Arc::new(DieselUserRepo::new(pool.clone())),
+            pool,
+            config,
+            mailer: mailer_from_config(config)?,
+            push_sender: Arc::new(PushSender::from_config(config, http_client.clone())?),
+            captcha: Arc::new(CaptchaGuard::from_config(config, http_client.clone())?),
+            moderation: Arc::new(ModerationPipeline::from_config(config, http_client.clone())?),
+            password_reset_limiter: Arc::new(IpRateLimiter::new(
+                config.password_reset_rate_limit,
+                Duration::from_secs(config.password_reset_window_minutes * 60),
+            )),
+            http_client,
+            event_bus: EventBus::new(),
+            stats_cache: Arc::new(StatsCache::new(
+                config.stats_cache_capacity,
+                Duration::from_secs(config.stats_cache_ttl_secs),
+            )),
+            storage: Arc::new(LocalStorage::new(&config.storage_dir)),
+            maintenance: Arc::new(MaintenanceMode::from_config(config)),
+            api_usage: Arc::new(ApiUsageCounter::new()),
+        })
+    }
+}
@@ -0,0 +1,164 @@
+//! Satu-satunya tempat format tanggal API ditentukan. Semua field tanggal di request
+//! dan response (body, query, path) lewat modul ini, lihat `tests/api_dates.rs`.
+use chrono::NaiveDate;
+use serde::{Deserialize, Deserializer, Serializer};
+use crate::config::app_config::app_config;
+use crate::errors::app_error::AppError;
+
+/// Format tanggal standar API (ISO-8601)
+pub const DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// Format tanggal lama yang hanya diterima jika ALLOW_LEGACY_DATE_FORMAT aktif
+pub const LEGACY_DATE_FORMAT: &str = "%m-%d-%Y";
+
+/// Parse dengan format persis: chrono menerima "2025-7-4" untuk `%m`/`%d`, padahal
+/// API hanya menerima angka dengan nol di depan
+fn parse_exact(value: &str, format: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(value, format)
+        .ok()
+        .filter(|date| date.format(format).to_string() == value)
+}
+
+fn parse(value: &str, allow_legacy: bool) -> Option<NaiveDate> {
+    let value = value.trim();
+    parse_exact(value, DATE_FORMAT).or_else(|| {
+        if allow_legacy {
+            parse_exact(value, LEGACY_DATE_FORMAT)
+        } else {
+            None
+        }
+    })
+}
+
+/// Parse tanggal dari request (path, query, body) dengan format standar API
+pub fn parse_date(value: &str) -> Result<NaiveDate, AppError> {
+    parse(value, app_config().allow_legacy_date_format)
+        .ok_or_else(|| AppError::BadRequest(format!("Invalid date '{}'. Use YYYY-MM-DD", value)))
+}
+
+/// Parse bulan dengan format YYYY-MM, mengembalikan tanggal 1 pada bulan tersebut
+pub fn parse_month(value: &str) -> Result<NaiveDate, AppError> {
+    parse_exact(&format!("{}-01", value.trim()), DATE_FORMAT)
+        .ok_or_else(|| AppError::BadRequest(format!("Invalid month '{}'. Use YYYY-MM", value)))
+}
+
+/// Format tanggal untuk response
+pub fn format_date(date: &NaiveDate) -> String {
+    date.format(DATE_FORMAT).to_string()
+}
+
+pub fn serialize<S>(date: &NaiveDate, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(&format_date(date))
+}
+
+pub fn deserialize<'de, D>(deserializer: D) -> Result<NaiveDate, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = String::deserialize(deserializer)?;
+    parse_date(&value).map_err(serde::de::Error::custom)
+}
+
+/// Varian untuk field `Option<NaiveDate>`, dipakai dengan `#[serde(default, with = "...::option")]`
+pub mod option {
+    use super::*;
+
+    pub fn serialize<S>(date: &Option<NaiveDate>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match date {
+            Some(date) => super::serialize(date, serializer),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<NaiveDate>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match Option::<String>::deserialize(deserializer)? {
+            Some(value) => parse_date(&value).map(Some).map_err(serde::de::Error::custom),
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Payload {
+        #[serde(with = "crate::utils::api_dates")]
+        date: NaiveDate,
+        #[serde(default, with = "crate::utils::api_dates::option")]
+        optional: Option<NaiveDate>,
+    }
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn parses_iso_dates() {
+        assert_eq!(parse("2025-07-23", false), Some(date(2025, 7, 23)));
+        assert_eq!(parse(" 2025-07-23 ", false), Some(date(2025, 7, 23)));
+        assert_eq!(parse("2024-02-29", false), Some(date(2024, 2, 29)));
+    }
+
+    #[test]
+    fn rejects_invalid_dates() {
+        for value in ["", "2025-7-4", "2025-07-4", "12025-07-04", "2025-02-30", "2023-02-29", "2025-13-01", "2025/07/23", "23-07-2025", "2025-07-23T10:00:00"] {
+            assert_eq!(parse(value, true), None, "{}", value);
+        }
+    }
+
+    #[test]
+    fn legacy_format_only_when_enabled() {
+        assert_eq!(parse("07-23-2025", false), None);
+        assert_eq!(parse("07-23-2025", true), Some(date(2025, 7, 23)));
+        assert_eq!(parse("7-23-2025", true), None);
+        // Format standar tetap didahulukan walau format lama aktif
+        assert_eq!(parse("2025-07-03", true), Some(date(2025, 7, 3)));
+    }
+
+    #[test]
+    fn parses_months() {
+        assert_eq!(parse_month("2025-03").unwrap(), date(2025, 3, 1));
+        assert!(parse_month("2025-3-1").is_err());
+        assert!(parse_month("2025-3").is_err());
+        assert!(parse_month("03-2025").is_err());
+        assert!(parse_month("2025-13").is_err());
+    }
+
+    #[test]
+    fn formats_zero_padded_iso() {
+        assert_eq!(format_date(&date(2025, 1, 5)), "2025-01-05");
+        assert_eq!(format_date(&date(999, 12, 31)), "0999-12-31");
+    }
+
+    #[test]
+    fn serde_round_trip() {
+        let payload = Payload {
+            date: date(2025, 7, 3),
+            optional: None,
+        };
+        let json = serde_json::to_value(&payload).unwrap();
+        assert_eq!(json, serde_json::json!({ "date": "2025-07-03", "optional": null }));
+        assert_eq!(serde_json::from_value::<Payload>(json).unwrap(), payload);
+
+        let parsed: Payload = serde_json::from_str(r#"{"date":"2025-07-03","optional":"2025-08-01"}"#).unwrap();
+        assert_eq!(parsed.optional, Some(date(2025, 8, 1)));
+
+        let missing: Payload = serde_json::from_str(r#"{"date":"2025-07-03"}"#).unwrap();
+        assert_eq!(missing.optional, None);
+
+        let error = serde_json::from_str::<Payload>(r#"{"date":"2025/07/03"}"#).unwrap_err();
+        assert!(error.to_string().contains("Use YYYY-MM-DD"));
+    }
+}
@@ -0,0 +1,86 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use chrono::{NaiveDate, Utc};
+use crate::models::api_usage::NewApiUsageDay;
+
+/// Penghitung request API per pengguna per hari (UTC) di memori. Angkanya disimpan ke
+/// `api_usage_daily` secara berkala oleh job `usage_flush`, jadi request tidak perlu menulis ke database.
+#[derive(Default)]
+pub struct ApiUsageCounter {
+    counts: Mutex<HashMap<(i32, NaiveDate), i64>>,
+}
+
+impl ApiUsageCounter {
+    pub fn new() -> Self {
+        ApiUsageCounter::default()
+    }
+
+    /// Catat satu request terautentikasi dari `user_id`
+    pub fn record(&self, user_id: i32) {
+        self.record_on(user_id, Utc::now().date_naive());
+    }
+
+    fn record_on(&self, user_id: i32, day: NaiveDate) {
+        if let Ok(mut counts) = self.counts.lock() {
+            *counts.entry((user_id, day)).or_default() += 1;
+        }
+    }
+
+    /// Request `user_id` yang belum disimpan ke database, per hari
+    pub fn pending(&self, user_id: i32) -> HashMap<NaiveDate, i64> {
+        let Ok(counts) = self.counts.lock() else {
+            return HashMap::new();
+        };
+        counts
+            .iter()
+            .filter(|((id, _), _)| *id == user_id)
+            .map(|((_, day), count)| (*day, *count))
+            .collect()
+    }
+
+    /// Ambil dan kosongkan semua hitungan untuk disimpan
+    pub fn drain(&self) -> Vec<NewApiUsageDay> {
+        let Ok(mut counts) = self.counts.lock() else {
+            return Vec::new();
+        };
+        counts
+            .drain()
+            .map(|((user_id, day), request_count)| NewApiUsageDay { user_id, day, request_count })
+            .collect()
+    }
+
+    /// Kembalikan hitungan yang gagal disimpan agar ikut pada penyimpanan berikutnya
+    pub fn restore(&self, rows: Vec<NewApiUsageDay>) {
+        if let Ok(mut counts) = self.counts.lock() {
+            for row in rows {
+                *counts.entry((row.user_id, row.day)).or_default() += row.request_count;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_per_user_and_day_until_drained() {
+        let counter = ApiUsageCounter::new();
+        let monday = NaiveDate::from_ymd_opt(2025, 7, 21).unwrap();
+        let tuesday = NaiveDate::from_ymd_opt(2025, 7, 22).unwrap();
+        counter.record_on(1, monday);
+        counter.record_on(1, monday);
+        counter.record_on(1, tuesday);
+        counter.record_on(2, tuesday);
+
+        assert_eq!(counter.pending(1), HashMap::from([(monday, 2), (tuesday, 1)]));
+
+        let drained = counter.drain();
+        assert_eq!(drained.iter().map(|row| row.request_count).sum::<i64>(), 4);
+        assert!(counter.pending(1).is_empty());
+
+        counter.restore(drained);
+        counter.record_on(2, tuesday);
+        assert_eq!(counter.pending(2), HashMap::from([(tuesday, 2)]));
+    }
+}
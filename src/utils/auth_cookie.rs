@@ -0,0 +1,117 @@
+use axum::http::{header, HeaderMap, HeaderName};
+use axum::response::AppendHeaders;
+use rand::Rng;
+use crate::config::app_config::AppConfig;
+use crate::utils::jwt::LOGIN_TOKEN_TTL_HOURS;
+
+/// Header yang harus berisi nilai cookie CSRF pada request tulis yang memakai cookie sesi
+pub const CSRF_HEADER: &str = "x-csrf-token";
+
+pub type SetCookies = AppendHeaders<Vec<(HeaderName, String)>>;
+
+pub fn csrf_cookie_name(config: &AppConfig) -> String {
+    format!("{}_csrf", config.auth_cookie_name)
+}
+
+pub fn generate_csrf_token() -> String {
+    let mut rng = rand::thread_rng();
+    (0..32)
+        .map(|_| rng.sample(rand::distributions::Alphanumeric) as char)
+        .collect()
+}
+
+fn cookie(config: &AppConfig, name: &str, value: &str, http_only: bool, max_age_secs: i64) -> String {
+    let mut cookie = format!(
+        "{}={}; Path=/; Max-Age={}; SameSite={}",
+        name, value, max_age_secs, config.auth_cookie_same_site
+    );
+    if let Some(ref domain) = config.auth_cookie_domain {
+        cookie.push_str(&format!("; Domain={}", domain));
+    }
+    if config.auth_cookie_secure {
+        cookie.push_str("; Secure");
+    }
+    if http_only {
+        cookie.push_str("; HttpOnly");
+    }
+    cookie
+}
+
+/// Cookie sesi (HttpOnly, berisi JWT) dan cookie CSRF untuk login. Kosong jika mode cookie tidak aktif.
+pub fn login_cookies(config: &AppConfig, token: &str) -> SetCookies {
+    if !config.auth_cookie_enabled {
+        return AppendHeaders(Vec::new());
+    }
+    let max_age = LOGIN_TOKEN_TTL_HOURS * 3600;
+    AppendHeaders(vec![
+        (header::SET_COOKIE, cookie(config, &config.auth_cookie_name, token, true, max_age)),
+        (header::SET_COOKIE, cookie(config, &csrf_cookie_name(config), &generate_csrf_token(), false, max_age)),
+    ])
+}
+
+/// Hapus cookie sesi dan CSRF saat logout
+pub fn clear_cookies(config: &AppConfig) -> SetCookies {
+    if !config.auth_cookie_enabled {
+        return AppendHeaders(Vec::new());
+    }
+    AppendHeaders(vec![
+        (header::SET_COOKIE, cookie(config, &config.auth_cookie_name, "", true, 0)),
+        (header::SET_COOKIE, cookie(config, &csrf_cookie_name(config), "", false, 0)),
+    ])
+}
+
+/// Nilai cookie `name` dari header `Cookie`
+pub fn read_cookie<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(';'))
+        .filter_map(|pair| pair.trim().split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value)
+        .filter(|value| !value.is_empty())
+}
+
+/// Bandingkan token CSRF tanpa berhenti di karakter pertama yang berbeda
+pub fn csrf_matches(expected: &str, provided: &str) -> bool {
+    expected.len() == provided.len()
+        && expected
+            .bytes()
+            .zip(provided.bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    #[test]
+    fn builds_and_reads_session_cookies() {
+        let config = AppConfig {
+            auth_cookie_enabled: true,
+            auth_cookie_name: "session".to_string(),
+            auth_cookie_same_site: "None".to_string(),
+            auth_cookie_secure: true,
+            auth_cookie_domain: None,
+            ..AppConfig::from_env()
+        };
+
+        let AppendHeaders(cookies) = login_cookies(&config, "jwt");
+        assert_eq!(cookies[0].1, "session=jwt; Path=/; Max-Age=86400; SameSite=None; Secure; HttpOnly");
+        assert!(cookies[1].1.starts_with("session_csrf="));
+        assert!(!cookies[1].1.contains("HttpOnly"));
+
+        let mut headers = HeaderMap::new();
+        headers.insert(header::COOKIE, HeaderValue::from_static("theme=dark; session=jwt; session_csrf=abc"));
+        assert_eq!(read_cookie(&headers, "session"), Some("jwt"));
+        assert_eq!(read_cookie(&headers, "session_csrf"), Some("abc"));
+        assert_eq!(read_cookie(&headers, "missing"), None);
+
+        assert!(csrf_matches("abc", "abc"));
+        assert!(!csrf_matches("abc", "abd"));
+        assert!(!csrf_matches("abc", "ab"));
+    }
+}
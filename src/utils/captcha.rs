@@ -0,0 +1,129 @@
+use std::time::Duration;
+use serde::Deserialize;
+use crate::config::app_config::AppConfig;
+use crate::errors::app_error::AppError;
+use crate::utils::http_client::HttpClient;
+use crate::utils::rate_limit::WindowCounter;
+
+const HCAPTCHA_VERIFY_URL: &str = "https://api.hcaptcha.com/siteverify";
+const TURNSTILE_VERIFY_URL: &str = "https://challenges.cloudflare.com/turnstile/v0/siteverify";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CaptchaProvider {
+    HCaptcha,
+    Turnstile,
+}
+
+impl CaptchaProvider {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "hcaptcha" => Some(CaptchaProvider::HCaptcha),
+            "turnstile" => Some(CaptchaProvider::Turnstile),
+            _ => None,
+        }
+    }
+
+    fn verify_url(self) -> &'static str {
+        match self {
+            CaptchaProvider::HCaptcha => HCAPTCHA_VERIFY_URL,
+            CaptchaProvider::Turnstile => TURNSTILE_VERIFY_URL,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct SiteVerifyResponse {
+    success: bool,
+}
+
+/// Verifikasi token CAPTCHA ke provider. hCaptcha dan Turnstile memakai API siteverify yang sama.
+struct CaptchaVerifier {
+    http_client: HttpClient,
+    provider: CaptchaProvider,
+    secret: String,
+}
+
+impl CaptchaVerifier {
+    async fn verify(&self, token: &str, ip_address: Option<&str>) -> Result<bool, AppError> {
+        let mut form = vec![("secret", self.secret.as_str()), ("response", token)];
+        if let Some(ip) = ip_address {
+            form.push(("remoteip", ip));
+        }
+
+        let response = self
+            .http_client
+            .send(self.http_client.post(self.provider.verify_url()).form(&form))
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("CAPTCHA verification failed: {}", e)))?;
+
+        if !response.status().is_success() {
+            return Err(AppError::InternalServerError(format!(
+                "CAPTCHA provider returned {}",
+                response.status()
+            )));
+        }
+
+        let result = response
+            .json::<SiteVerifyResponse>()
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("Invalid CAPTCHA response: {}", e)))?;
+        Ok(result.success)
+    }
+}
+
+/// Penjaga endpoint publik: setiap IP boleh melakukan `threshold` percobaan per jendela waktu,
+/// setelah itu request wajib membawa token CAPTCHA yang valid. Nonaktif jika provider atau
+/// secret tidak dikonfigurasi.
+pub struct CaptchaGuard {
+    verifier: Option<CaptchaVerifier>,
+    attempts: WindowCounter,
+    threshold: u32,
+}
+
+impl CaptchaGuard {
+    pub fn from_config(config: &AppConfig, http_client: HttpClient) -> Result<Self, AppError> {
+        let verifier = match (&config.captcha_provider, &config.captcha_secret) {
+            (Some(provider), Some(secret)) => Some(CaptchaVerifier {
+                http_client,
+                provider: CaptchaProvider::parse(provider).ok_or_else(|| {
+                    AppError::InternalServerError(format!("Unknown CAPTCHA_PROVIDER: {}", provider))
+                })?,
+                secret: secret.clone(),
+            }),
+            _ => None,
+        };
+
+        Ok(CaptchaGuard {
+            verifier,
+            attempts: WindowCounter::new(Duration::from_secs(config.captcha_window_minutes * 60)),
+            threshold: config.captcha_threshold,
+        })
+    }
+
+    /// Catat percobaan dari IP ini; `true` jika batas tanpa CAPTCHA sudah terlewati
+    fn record_attempt(&self, ip_address: &str) -> bool {
+        self.attempts.hit(ip_address) > self.threshold
+    }
+
+    /// Cek request dari `ip_address`; `token` adalah token CAPTCHA dari klien jika ada
+    pub async fn check(&self, ip_address: Option<&str>, token: Option<&str>) -> Result<(), AppError> {
+        let Some(ref verifier) = self.verifier else {
+            return Ok(());
+        };
+        let Some(ip) = ip_address else {
+            return Ok(());
+        };
+        if !self.record_attempt(ip) {
+            return Ok(());
+        }
+
+        let token = token
+            .map(str::trim)
+            .filter(|token| !token.is_empty())
+            .ok_or_else(|| AppError::Forbidden("CAPTCHA verification required".to_string()))?;
+        if !verifier.verify(token, Some(ip)).await? {
+            return Err(AppError::Forbidden("CAPTCHA verification failed".to_string()));
+        }
+        Ok(())
+    }
+}
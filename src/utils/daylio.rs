@@ -0,0 +1,202 @@
+use chrono::{NaiveDate, NaiveTime};
+use crate::errors::app_error::AppError;
+use crate::models::mood::{MoodDetails, MoodType};
+
+/// Pemisah aktivitas di kolom `activities` export Daylio
+const ACTIVITY_SEPARATOR: &str = " | ";
+
+/// Satu entri Daylio yang sudah diterjemahkan ke model MindMate
+#[derive(Debug)]
+pub struct DaylioEntry {
+    pub line: usize,
+    pub date: NaiveDate,
+    pub time: Option<NaiveTime>,
+    pub mood: MoodType,
+    pub tags: Vec<String>,
+    pub notes: Option<String>,
+}
+
+/// Baris yang tidak bisa diterjemahkan beserta alasannya
+#[derive(Debug)]
+pub struct DaylioRowError {
+    pub line: usize,
+    pub date: Option<NaiveDate>,
+    pub reason: String,
+}
+
+/// Posisi kolom di header; urutan kolom Daylio berbeda antar versi aplikasi
+struct Columns {
+    full_date: usize,
+    time: Option<usize>,
+    mood: usize,
+    activities: Option<usize>,
+    note_title: Option<usize>,
+    note: Option<usize>,
+}
+
+/// Parse CSV export Daylio (`full_date,date,weekday,time,mood,activities,note_title,note`).
+/// Error hanya dikembalikan untuk file yang tidak bisa dibaca sama sekali; masalah per baris
+/// dilaporkan sebagai `DaylioRowError`.
+pub fn parse_daylio_csv(data: &[u8]) -> Result<Vec<Result<DaylioEntry, DaylioRowError>>, AppError> {
+    let mut reader = csv::ReaderBuilder::new().flexible(true).from_reader(data);
+
+    let headers = reader
+        .headers()
+        .map_err(|e| AppError::BadRequest(format!("Invalid CSV file: {}", e)))?;
+    let find = |name: &str| {
+        headers
+            .iter()
+            .position(|header| header.trim_start_matches('\u{feff}').trim().eq_ignore_ascii_case(name))
+    };
+    let columns = match (find("full_date"), find("mood")) {
+        (Some(full_date), Some(mood)) => Columns {
+            full_date,
+            time: find("time"),
+            mood,
+            activities: find("activities"),
+            note_title: find("note_title"),
+            note: find("note"),
+        },
+        _ => {
+            return Err(AppError::BadRequest(
+                "Not a Daylio export: missing full_date or mood column".to_string(),
+            ))
+        }
+    };
+
+    let mut rows = Vec::new();
+    for record in reader.records() {
+        let record = record.map_err(|e| AppError::BadRequest(format!("Invalid CSV file: {}", e)))?;
+        let line = record.position().map(|pos| pos.line() as usize).unwrap_or_default();
+        let field = |index: Option<usize>| index.and_then(|i| record.get(i)).map(str::trim).unwrap_or("");
+
+        let raw_date = field(Some(columns.full_date));
+        let Ok(date) = NaiveDate::parse_from_str(raw_date, "%Y-%m-%d") else {
+            rows.push(Err(DaylioRowError {
+                line,
+                date: None,
+                reason: format!("Invalid date: {}", raw_date),
+            }));
+            continue;
+        };
+
+        let raw_mood = field(Some(columns.mood));
+        let Some(mood) = map_mood(raw_mood) else {
+            rows.push(Err(DaylioRowError {
+                line,
+                date: Some(date),
+                reason: format!("Unknown Daylio mood: {}", raw_mood),
+            }));
+            continue;
+        };
+
+        rows.push(Ok(DaylioEntry {
+            line,
+            date,
+            time: parse_time(field(columns.time)),
+            mood,
+            tags: parse_activities(field(columns.activities)),
+            notes: join_note(field(columns.note_title), field(columns.note)),
+        }));
+    }
+
+    Ok(rows)
+}
+
+/// Skala bawaan Daylio (rad → awful) ke MoodType; mood kustom tidak dikenali
+fn map_mood(value: &str) -> Option<MoodType> {
+    match value.to_lowercase().as_str() {
+        "rad" => Some(MoodType::VeryHappy),
+        "good" => Some(MoodType::Happy),
+        "meh" => Some(MoodType::Neutral),
+        "bad" => Some(MoodType::Sad),
+        "awful" => Some(MoodType::VerySad),
+        _ => None,
+    }
+}
+
+/// Daylio menulis jam sesuai pengaturan ponsel, "20:30" atau "8:30 PM"
+fn parse_time(value: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(value, "%H:%M")
+        .or_else(|_| NaiveTime::parse_from_str(&value.to_uppercase(), "%I:%M %p"))
+        .ok()
+}
+
+/// Aktivitas menjadi tag; tag yang terlalu panjang dipotong dan kelebihan tag dibuang
+fn parse_activities(value: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for activity in value.split(ACTIVITY_SEPARATOR) {
+        let tag: String = activity.trim().chars().take(MoodDetails::TAG_MAX_LENGTH).collect();
+        if !tag.is_empty() && !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    tags.truncate(MoodDetails::MAX_TAGS);
+    tags
+}
+
+/// Gabungkan judul dan isi catatan; Daylio menyimpan baris baru sebagai `<br>`
+fn join_note(title: &str, note: &str) -> Option<String> {
+    let note = note.replace("<br/>", "\n").replace("<br />", "\n").replace("<br>", "\n");
+    let note = note.trim();
+    match (title.is_empty(), note.is_empty()) {
+        (true, true) => None,
+        (false, true) => Some(title.to_string()),
+        (true, false) => Some(note.to_string()),
+        (false, false) => Some(format!("{}\n\n{}", title, note)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_rows_and_reports_bad_ones() {
+        let csv = "\u{feff}full_date,date,weekday,time,mood,activities,note_title,note\n\
+            2026-10-14,October 14,Wednesday,8:30 pm,rad,work | friends | work,Hari baik,Makan<br>malam\n\
+            2026-10-15,October 15,Thursday,07:05,meh,,,\n\
+            not-a-date,,,,good,,,\n\
+            2026-10-16,October 16,Friday,09:00,sleepy,,,\n";
+        let rows = parse_daylio_csv(csv.as_bytes()).unwrap();
+        assert_eq!(rows.len(), 4);
+
+        let first = rows[0].as_ref().unwrap();
+        assert_eq!(first.line, 2);
+        assert_eq!(first.date, NaiveDate::from_ymd_opt(2026, 10, 14).unwrap());
+        assert_eq!(first.time, NaiveTime::from_hms_opt(20, 30, 0));
+        assert_eq!(first.mood.as_str(), MoodType::VeryHappy.as_str());
+        assert_eq!(first.tags, ["work", "friends"]);
+        assert_eq!(first.notes.as_deref(), Some("Hari baik\n\nMakan\nmalam"));
+
+        let second = rows[1].as_ref().unwrap();
+        assert_eq!(second.time, NaiveTime::from_hms_opt(7, 5, 0));
+        assert_eq!(second.mood.as_str(), MoodType::Neutral.as_str());
+        assert!(second.tags.is_empty());
+        assert_eq!(second.notes, None);
+
+        let bad_date = rows[2].as_ref().unwrap_err();
+        assert_eq!((bad_date.line, bad_date.date), (4, None));
+        let bad_mood = rows[3].as_ref().unwrap_err();
+        assert_eq!(bad_mood.date, NaiveDate::from_ymd_opt(2026, 10, 16));
+        assert!(bad_mood.reason.contains("sleepy"));
+    }
+
+    #[test]
+    fn columns_are_found_by_name_and_required_ones_checked() {
+        let reordered = "mood,full_date\nawful,2026-01-02\n";
+        let rows = parse_daylio_csv(reordered.as_bytes()).unwrap();
+        let entry = rows[0].as_ref().unwrap();
+        assert_eq!((entry.mood.as_str(), entry.time), (MoodType::VerySad.as_str(), None));
+
+        assert!(matches!(parse_daylio_csv(b"date,mood\n2026-01-02,rad\n"), Err(AppError::BadRequest(_))));
+    }
+
+    #[test]
+    fn activities_are_trimmed_and_capped() {
+        let many: Vec<String> = (0..MoodDetails::MAX_TAGS + 3).map(|i| format!("tag{}", i)).collect();
+        assert_eq!(parse_activities(&many.join(ACTIVITY_SEPARATOR)).len(), MoodDetails::MAX_TAGS);
+        let long = "x".repeat(MoodDetails::TAG_MAX_LENGTH + 10);
+        assert_eq!(parse_activities(&long)[0].chars().count(), MoodDetails::TAG_MAX_LENGTH);
+    }
+}
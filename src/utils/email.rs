@@ -0,0 +1,16 @@
+use crate::errors::app_error::AppError;
+
+/// Bentuk baku email: tanpa spasi di awal/akhir dan huruf kecil semua, sehingga
+/// `User@X.com` dan `user@x.com` selalu dianggap akun yang sama
+pub fn normalize_email(raw: &str) -> String {
+    raw.trim().to_lowercase()
+}
+
+/// Normalisasi email yang akan disimpan dan cek formatnya secara sederhana
+pub fn parse_email(raw: &str) -> Result<String, AppError> {
+    let email = normalize_email(raw);
+    match email.split_once('@') {
+        Some((local, domain)) if !local.is_empty() && domain.contains('.') && !domain.contains('@') => Ok(email),
+        _ => Err(AppError::BadRequest("Invalid email format".to_string())),
+    }
+}
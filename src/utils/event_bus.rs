@@ -0,0 +1,44 @@
+use tokio::sync::broadcast;
+use crate::models::message::MessageResponse;
+
+const EVENT_BUS_CAPACITY: usize = 256;
+
+/// Event domain yang bisa didengarkan oleh background job atau integrasi lain
+#[derive(Debug, Clone)]
+pub enum AppEvent {
+    UserRegistered { user_id: i32 },
+    MoodCreated { user_id: i32, mood_id: i32 },
+    JournalCreated { user_id: i32, journal_id: i32 },
+    /// Pesan baru dalam percakapan pengguna dan psikolog
+    MessageCreated { message: MessageResponse },
+    /// Pesan dari satu sisi percakapan ditandai dibaca oleh sisi lainnya
+    MessagesRead { user_id: i32, psychologist_id: i32, from_psychologist: bool },
+}
+
+/// Event bus in-process berbasis broadcast channel
+#[derive(Clone)]
+pub struct EventBus {
+    sender: broadcast::Sender<AppEvent>,
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(EVENT_BUS_CAPACITY);
+        EventBus { sender }
+    }
+
+    /// Kirim event ke semua subscriber. Tidak error jika belum ada subscriber.
+    pub fn publish(&self, event: AppEvent) {
+        let _ = self.sender.send(event);
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<AppEvent> {
+        self.sender.subscribe()
+    }
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,118 @@
+use std::time::Duration;
+use reqwest::{Method, Request, RequestBuilder, Response};
+use crate::config::app_config::AppConfig;
+use crate::errors::app_error::AppError;
+
+/// Jeda awal sebelum retry, digandakan setiap percobaan
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(200);
+
+/// HTTP client bersama untuk semua panggilan API eksternal (Google OAuth, webhook, dll).
+/// Satu instance dipakai ulang agar connection pool reqwest bekerja.
+#[derive(Clone)]
+pub struct HttpClient {
+    client: reqwest::Client,
+    max_retries: u32,
+}
+
+impl HttpClient {
+    pub fn from_config(config: &AppConfig) -> Result<Self, AppError> {
+        Self::build(config, false)
+    }
+
+    /// Client khusus HTTP/2, untuk API yang tidak menerima HTTP/1.1 seperti APNs
+    pub fn http2_from_config(config: &AppConfig) -> Result<Self, AppError> {
+        Self::build(config, true)
+    }
+
+    fn build(config: &AppConfig, http2_only: bool) -> Result<Self, AppError> {
+        let mut builder = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.http_timeout_secs))
+            .connect_timeout(Duration::from_secs(config.http_connect_timeout_secs))
+            .user_agent(concat!("mindmate-be/", env!("CARGO_PKG_VERSION")));
+
+        if let Some(ref proxy_url) = config.http_proxy_url {
+            let proxy = reqwest::Proxy::all(proxy_url)
+                .map_err(|e| AppError::InternalServerError(format!("Invalid HTTP_PROXY_URL: {}", e)))?;
+            builder = builder.proxy(proxy);
+        }
+
+        if http2_only {
+            builder = builder.http2_prior_knowledge();
+        }
+
+        let client = builder
+            .build()
+            .map_err(|e| AppError::InternalServerError(format!("Failed to build HTTP client: {}", e)))?;
+
+        Ok(HttpClient {
+            client,
+            max_retries: config.http_max_retries,
+        })
+    }
+
+    pub fn get(&self, url: &str) -> RequestBuilder {
+        self.client.get(url)
+    }
+
+    pub fn post(&self, url: &str) -> RequestBuilder {
+        self.client.post(url)
+    }
+
+    /// Kirim request, retry pada error koneksi/timeout dan status 5xx hanya untuk method
+    /// idempoten (GET, HEAD, PUT, DELETE, OPTIONS). POST dikirim sekali: retry setelah timeout
+    /// bisa mengulang efek yang sudah terjadi di server, misalnya kode OAuth sekali pakai.
+    pub async fn send(&self, request: RequestBuilder) -> Result<Response, reqwest::Error> {
+        let request = request.build()?;
+        let retry = is_idempotent(request.method());
+        self.execute(request, retry).await
+    }
+
+    /// Seperti `send`, tetapi selalu retry. Hanya untuk POST yang aman diulang,
+    /// misalnya refresh token atau API klasifikasi tanpa efek samping.
+    pub async fn send_idempotent(&self, request: RequestBuilder) -> Result<Response, reqwest::Error> {
+        self.execute(request.build()?, true).await
+    }
+
+    async fn execute(&self, request: Request, retry: bool) -> Result<Response, reqwest::Error> {
+        let mut attempt = 0;
+
+        loop {
+            // Body streaming tidak bisa di-clone, kirim langsung tanpa retry
+            let retry_request = match request.try_clone() {
+                Some(retry_request) if retry => retry_request,
+                _ => return self.client.execute(request).await,
+            };
+
+            let result = self.client.execute(retry_request).await;
+            let retryable = match &result {
+                Ok(response) => response.status().is_server_error(),
+                Err(e) => e.is_connect() || e.is_timeout(),
+            };
+
+            if !retryable || attempt >= self.max_retries {
+                return result;
+            }
+
+            attempt += 1;
+            tokio::time::sleep(RETRY_BASE_DELAY * 2u32.pow(attempt - 1)).await;
+        }
+    }
+}
+
+fn is_idempotent(method: &Method) -> bool {
+    matches!(*method, Method::GET | Method::HEAD | Method::PUT | Method::DELETE | Method::OPTIONS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retries_only_idempotent_methods_by_default() {
+        assert!(is_idempotent(&Method::GET));
+        assert!(is_idempotent(&Method::PUT));
+        assert!(is_idempotent(&Method::DELETE));
+        assert!(!is_idempotent(&Method::POST));
+        assert!(!is_idempotent(&Method::PATCH));
+    }
+}
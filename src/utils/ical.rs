@@ -0,0 +1,106 @@
+use chrono::{NaiveDate, NaiveDateTime};
+
+/// Event sepanjang hari dalam feed iCalendar
+pub struct AllDayEvent {
+    pub uid: String,
+    pub date: NaiveDate,
+    pub summary: String,
+    pub description: Option<String>,
+}
+
+/// Event dengan jam mulai dan selesai (UTC)
+pub struct TimedEvent {
+    pub uid: String,
+    pub starts_at: NaiveDateTime,
+    pub ends_at: NaiveDateTime,
+    pub summary: String,
+    pub description: Option<String>,
+    pub url: Option<String>,
+    pub cancelled: bool,
+}
+
+/// Susun dokumen iCalendar (RFC 5545) dari daftar event
+pub fn build_calendar(
+    name: &str,
+    events: &[AllDayEvent],
+    timed_events: &[TimedEvent],
+    generated_at: NaiveDateTime,
+) -> String {
+    let stamp = generated_at.format("%Y%m%dT%H%M%SZ").to_string();
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//MindMate//MindMate Calendar//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        "METHOD:PUBLISH".to_string(),
+        format!("X-WR-CALNAME:{}", escape_text(name)),
+    ];
+
+    for event in events {
+        let end = event.date.succ_opt().unwrap_or(event.date);
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!("UID:{}", event.uid));
+        lines.push(format!("DTSTAMP:{}", stamp));
+        lines.push(format!("DTSTART;VALUE=DATE:{}", event.date.format("%Y%m%d")));
+        lines.push(format!("DTEND;VALUE=DATE:{}", end.format("%Y%m%d")));
+        lines.push(format!("SUMMARY:{}", escape_text(&event.summary)));
+        if let Some(ref description) = event.description {
+            lines.push(format!("DESCRIPTION:{}", escape_text(description)));
+        }
+        lines.push("TRANSP:TRANSPARENT".to_string());
+        lines.push("END:VEVENT".to_string());
+    }
+
+    for event in timed_events {
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!("UID:{}", event.uid));
+        lines.push(format!("DTSTAMP:{}", stamp));
+        lines.push(format!("DTSTART:{}", event.starts_at.format("%Y%m%dT%H%M%SZ")));
+        lines.push(format!("DTEND:{}", event.ends_at.format("%Y%m%dT%H%M%SZ")));
+        lines.push(format!("SUMMARY:{}", escape_text(&event.summary)));
+        if let Some(ref description) = event.description {
+            lines.push(format!("DESCRIPTION:{}", escape_text(description)));
+        }
+        if let Some(ref url) = event.url {
+            lines.push(format!("URL:{}", url));
+        }
+        lines.push(format!("STATUS:{}", if event.cancelled { "CANCELLED" } else { "CONFIRMED" }));
+        lines.push("END:VEVENT".to_string());
+    }
+
+    lines.push("END:VCALENDAR".to_string());
+
+    lines
+        .iter()
+        .map(|line| fold_line(line))
+        .collect::<Vec<_>>()
+        .join("\r\n")
+        + "\r\n"
+}
+
+fn escape_text(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+/// Lipat baris lebih dari 75 octet sesuai RFC 5545 tanpa memotong karakter UTF-8
+fn fold_line(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len());
+    let mut current_len = 0;
+
+    for ch in line.chars() {
+        let ch_len = ch.len_utf8();
+        if current_len + ch_len > 75 {
+            folded.push_str("\r\n ");
+            current_len = 1;
+        }
+        folded.push(ch);
+        current_len += ch_len;
+    }
+
+    folded
+}
@@ -0,0 +1,77 @@
+use std::io::Cursor;
+use axum::body::Bytes;
+use image::codecs::jpeg::JpegEncoder;
+use image::imageops::FilterType;
+use image::{ImageError, ImageReader, Limits};
+use crate::errors::app_error::AppError;
+
+const AVATAR_JPEG_QUALITY: u8 = 85;
+/// Batas lebar/tinggi gambar upload; file kecil bisa mengklaim dimensi sangat besar
+const MAX_IMAGE_DIMENSION: u32 = 8192;
+/// Batas memori untuk decode satu gambar
+const MAX_DECODE_ALLOC: u64 = 128 * 1024 * 1024;
+
+fn decode_limits() -> Limits {
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(MAX_IMAGE_DIMENSION);
+    limits.max_image_height = Some(MAX_IMAGE_DIMENSION);
+    limits.max_alloc = Some(MAX_DECODE_ALLOC);
+    limits
+}
+
+/// Decode gambar upload (PNG, JPEG, WebP), potong menjadi persegi di tengah,
+/// lalu resize ke `size` x `size` dan encode ulang sebagai JPEG
+pub fn resize_avatar(data: &[u8], size: u32) -> Result<Vec<u8>, AppError> {
+    let unsupported = || AppError::BadRequest("Unsupported or corrupt image. Use PNG, JPEG or WebP".to_string());
+
+    let mut reader = ImageReader::new(Cursor::new(data))
+        .with_guessed_format()
+        .map_err(|_| unsupported())?;
+    reader.limits(decode_limits());
+    let image = reader.decode().map_err(|e| match e {
+        ImageError::Limits(_) => AppError::BadRequest(format!(
+            "Image too large. Maximum dimensions are {}x{} pixels",
+            MAX_IMAGE_DIMENSION, MAX_IMAGE_DIMENSION
+        )),
+        _ => unsupported(),
+    })?;
+
+    let resized = image.resize_to_fill(size, size, FilterType::Lanczos3).to_rgb8();
+
+    let mut output = Cursor::new(Vec::new());
+    JpegEncoder::new_with_quality(&mut output, AVATAR_JPEG_QUALITY)
+        .encode_image(&resized)
+        .map_err(|e| AppError::InternalServerError(format!("Failed to encode avatar: {}", e)))?;
+
+    Ok(output.into_inner())
+}
+
+/// `resize_avatar` di thread blocking, agar decode dan resize tidak menahan runtime async
+pub async fn resize_avatar_blocking(data: Bytes, size: u32) -> Result<Vec<u8>, AppError> {
+    tokio::task::spawn_blocking(move || resize_avatar(&data, size))
+        .await
+        .map_err(|e| AppError::InternalServerError(format!("Avatar processing failed: {}", e)))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{ImageFormat, RgbImage};
+
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let mut output = Cursor::new(Vec::new());
+        RgbImage::new(width, height).write_to(&mut output, ImageFormat::Png).unwrap();
+        output.into_inner()
+    }
+
+    #[test]
+    fn resizes_images_within_limits_and_rejects_oversized_ones() {
+        let avatar = resize_avatar(&png(40, 20), 16).unwrap();
+        assert_eq!(image::load_from_memory(&avatar).unwrap().width(), 16);
+
+        let Err(AppError::BadRequest(message)) = resize_avatar(&png(MAX_IMAGE_DIMENSION + 1, 1), 16) else {
+            panic!("expected oversized image to be rejected");
+        };
+        assert!(message.starts_with("Image too large"));
+    }
+}
@@ -0,0 +1,86 @@
+use axum::{
+    body::{Body, Bytes},
+    http::header,
+    response::{IntoResponse, Response},
+};
+use serde::Serialize;
+use tokio::sync::mpsc;
+use crate::errors::app_error::AppError;
+
+/// Ukuran potongan body yang dikirim ke klien sekaligus
+const CHUNK_BYTES: usize = 64 * 1024;
+/// Jumlah potongan yang boleh mengantre sebelum producer menunggu klien
+const CHANNEL_CAPACITY: usize = 4;
+
+type Chunk = Result<Bytes, std::io::Error>;
+
+/// Penulis elemen array JSON untuk `stream_json_array`
+pub struct JsonArrayWriter {
+    sender: mpsc::Sender<Chunk>,
+    buffer: Vec<u8>,
+    empty: bool,
+}
+
+impl JsonArrayWriter {
+    pub fn push<T: Serialize>(&mut self, item: &T) -> Result<(), AppError> {
+        self.buffer.push(if self.empty { b'[' } else { b',' });
+        self.empty = false;
+        serde_json::to_writer(&mut self.buffer, item)
+            .map_err(|e| AppError::InternalServerError(format!("Failed to serialize response: {}", e)))?;
+
+        if self.buffer.len() >= CHUNK_BYTES {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), AppError> {
+        let chunk = Bytes::from(std::mem::take(&mut self.buffer));
+        self.sender
+            .blocking_send(Ok(chunk))
+            .map_err(|_| AppError::InternalServerError("Client disconnected".to_string()))
+    }
+
+    fn finish(mut self) -> Result<(), AppError> {
+        if self.empty {
+            self.buffer.push(b'[');
+        }
+        self.buffer.push(b']');
+        self.flush()
+    }
+}
+
+/// Respons array JSON yang diisi bertahap oleh `produce` di thread blocking, sehingga
+/// daftar besar tidak perlu ditampung seluruhnya di memori. Status 200 sudah terkirim
+/// saat `produce` berjalan; jika gagal di tengah jalan, koneksi diputus dan klien
+/// menerima JSON yang tidak lengkap.
+pub fn stream_json_array<F>(produce: F) -> JsonArrayStream
+where
+    F: FnOnce(&mut JsonArrayWriter) -> Result<(), AppError> + Send + 'static,
+{
+    let (sender, mut receiver) = mpsc::channel::<Chunk>(CHANNEL_CAPACITY);
+
+    tokio::task::spawn_blocking(move || {
+        let mut writer = JsonArrayWriter {
+            sender: sender.clone(),
+            buffer: Vec::with_capacity(CHUNK_BYTES),
+            empty: true,
+        };
+
+        if let Err(e) = produce(&mut writer).and_then(|_| writer.finish()) {
+            eprintln!("❌ Streaming response aborted: {}", e);
+            let _ = sender.blocking_send(Err(std::io::Error::other(e.to_string())));
+        }
+    });
+
+    let stream = futures_util::stream::poll_fn(move |cx| receiver.poll_recv(cx));
+    JsonArrayStream(Body::from_stream(stream))
+}
+
+pub struct JsonArrayStream(Body);
+
+impl IntoResponse for JsonArrayStream {
+    fn into_response(self) -> Response {
+        ([(header::CONTENT_TYPE, "application/json")], self.0).into_response()
+    }
+}
@@ -1,41 +1,306 @@
-use jsonwebtoken::{encode, decode, Header, Validation, EncodingKey, DecodingKey};
-use serde::{Deserialize, Serialize};
-use chrono::{Utc, Duration};
-use std::env;
+use std::sync::OnceLock;
+use jsonwebtoken::{encode, decode, decode_header, Header, Validation, EncodingKey, DecodingKey};
+use jsonwebtoken::errors::{Error, ErrorKind};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use chrono::{Utc, Duration, NaiveDate};
+use crate::config::app_config::{app_config, AppConfig};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Claims {
     pub sub: String, // Subject (user id)
     pub exp: usize,  // Expiration time
     pub iat: usize,  // Issued at
+    /// Scope dipisah spasi; token tanpa scope punya akses penuh ke akun
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+    /// ID admin yang memakai token ini untuk melihat akun `sub` (impersonasi)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub impersonator: Option<String>,
 }
 
-pub fn generate_token(user_id: &str) -> Result<String, jsonwebtoken::errors::Error> {
-    let secret = env::var("JWT_SECRET").unwrap_or_else(|_| "your-secret-key".to_string());
-    
+impl Claims {
+    /// `None` untuk token akses penuh. Scope yang tidak dikenal diabaikan.
+    pub fn scopes(&self) -> Option<Vec<TokenScope>> {
+        self.scope
+            .as_deref()
+            .map(|scope| scope.split_whitespace().filter_map(TokenScope::parse).collect())
+    }
+}
+
+/// Hak akses terbatas untuk token share link dan integrasi
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenScope {
+    ExportRead,
+    ShareRead,
+}
+
+impl TokenScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TokenScope::ExportRead => "export:read",
+            TokenScope::ShareRead => "share:read",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "export:read" => Some(TokenScope::ExportRead),
+            "share:read" => Some(TokenScope::ShareRead),
+            _ => None,
+        }
+    }
+}
+
+/// Dipakai hanya jika JWT_SECRET kosong (test dan tool lokal); server menolak start
+/// tanpa secret yang cukup panjang (lihat `config::startup_check`)
+const FALLBACK_JWT_SECRET: &str = "your-secret-key";
+
+/// Kunci HMAC beserta `kid` yang ditulis di header token
+#[derive(Debug, Clone)]
+struct JwtKey {
+    kid: String,
+    secret: String,
+}
+
+/// Kunci penandatangan saat ini (JWT_SECRET + JWT_KEY_ID) dan kunci lama yang masih diterima
+/// untuk verifikasi (JWT_PREVIOUS_KEYS / JWT_KEYS_FILE), agar secret bisa dirotasi tanpa
+/// membuat semua sesi langsung tidak berlaku.
+#[derive(Debug, Clone)]
+pub struct JwtKeyring {
+    current: JwtKey,
+    previous: Vec<JwtKey>,
+}
+
+impl JwtKeyring {
+    pub fn from_config(config: &AppConfig) -> Result<Self, String> {
+        let current = current_key(config);
+        if current.kid.is_empty() {
+            return Err("JWT_KEY_ID must not be empty".to_string());
+        }
+
+        let mut previous = Vec::new();
+        if let Some(ref keys) = config.jwt_previous_keys {
+            previous.extend(parse_keys("JWT_PREVIOUS_KEYS", keys.split(','))?);
+        }
+        if let Some(ref path) = config.jwt_keys_file {
+            let content = std::fs::read_to_string(path)
+                .map_err(|e| format!("JWT_KEYS_FILE cannot be read ({}): {}", path, e))?;
+            previous.extend(parse_keys("JWT_KEYS_FILE", content.lines())?);
+        }
+
+        let mut kids = vec![current.kid.as_str()];
+        for key in &previous {
+            if kids.contains(&key.kid.as_str()) {
+                return Err(format!("JWT key id '{}' is used more than once", key.kid));
+            }
+            kids.push(&key.kid);
+        }
+
+        Ok(JwtKeyring { current, previous })
+    }
+
+    /// Kunci yang dicoba untuk memverifikasi token. Token tanpa `kid` (dibuat sebelum rotasi
+    /// didukung) dicoba dengan semua kunci; `kid` yang tidak dikenal tidak punya kandidat.
+    fn candidates(&self, kid: Option<&str>) -> Vec<&JwtKey> {
+        std::iter::once(&self.current)
+            .chain(&self.previous)
+            .filter(|key| kid.is_none_or(|kid| key.kid == kid))
+            .collect()
+    }
+
+    /// Tanda tangani dengan kunci saat ini; `derive` mengubah secret untuk jenis token tertentu
+    fn sign<T: Serialize>(&self, claims: &T, derive: fn(&str) -> String) -> Result<String, Error> {
+        let header = Header {
+            kid: Some(self.current.kid.clone()),
+            ..Header::default()
+        };
+        encode(&header, claims, &EncodingKey::from_secret(derive(&self.current.secret).as_bytes()))
+    }
+
+    fn verify<T: DeserializeOwned>(&self, token: &str, derive: fn(&str) -> String) -> Result<T, Error> {
+        let header = decode_header(token)?;
+        let mut result = Err(Error::from(ErrorKind::InvalidSignature));
+        for key in self.candidates(header.kid.as_deref()) {
+            result = decode::<T>(token, &DecodingKey::from_secret(derive(&key.secret).as_bytes()), &Validation::default())
+                .map(|token_data| token_data.claims);
+            match result {
+                Err(ref e) if *e.kind() == ErrorKind::InvalidSignature => continue,
+                _ => break,
+            }
+        }
+        result
+    }
+}
+
+fn current_key(config: &AppConfig) -> JwtKey {
+    JwtKey {
+        kid: config.jwt_key_id.trim().to_string(),
+        secret: config.jwt_secret.clone().unwrap_or_else(|| FALLBACK_JWT_SECRET.to_string()),
+    }
+}
+
+/// Entri `kid:secret`; baris kosong dan yang diawali `#` diabaikan
+fn parse_keys<'a>(source: &str, entries: impl Iterator<Item = &'a str>) -> Result<Vec<JwtKey>, String> {
+    entries
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty() && !entry.starts_with('#'))
+        .map(|entry| match entry.split_once(':') {
+            Some((kid, secret)) if !kid.trim().is_empty() && !secret.trim().is_empty() => Ok(JwtKey {
+                kid: kid.trim().to_string(),
+                secret: secret.trim().to_string(),
+            }),
+            _ => Err(format!("{} entries must have the form kid:secret", source)),
+        })
+        .collect()
+}
+
+fn keyring() -> &'static JwtKeyring {
+    static KEYRING: OnceLock<JwtKeyring> = OnceLock::new();
+    KEYRING.get_or_init(|| {
+        let config = app_config();
+        JwtKeyring::from_config(config).unwrap_or_else(|e| {
+            eprintln!("❌ {}; verifying tokens with JWT_SECRET only", e);
+            JwtKeyring {
+                current: current_key(config),
+                previous: Vec::new(),
+            }
+        })
+    })
+}
+
+fn login_secret(secret: &str) -> String {
+    secret.to_string()
+}
+
+/// Masa berlaku token login (dan cookie sesi)
+pub const LOGIN_TOKEN_TTL_HOURS: i64 = 24;
+
+pub fn generate_token(user_id: &str) -> Result<String, Error> {
     let now = Utc::now();
-    let exp = now + Duration::hours(24); // Token expires in 24 hours
+    let exp = now + Duration::hours(LOGIN_TOKEN_TTL_HOURS);
     
     let claims = Claims {
         sub: user_id.to_string(),
         exp: exp.timestamp() as usize,
         iat: now.timestamp() as usize,
+        scope: None,
+        impersonator: None,
     };
 
-    encode(
-        &Header::default(),
-        &claims,
-        &EncodingKey::from_secret(secret.as_ref()),
-    )
+    keyring().sign(&claims, login_secret)
 }
 
-pub fn validate_token(token: &str) -> Result<Claims, jsonwebtoken::errors::Error> {
-    let secret = env::var("JWT_SECRET").unwrap_or_else(|_| "your-secret-key".to_string());
-    
-    decode::<Claims>(
-        token,
-        &DecodingKey::from_secret(secret.as_ref()),
-        &Validation::default(),
-    )
-    .map(|token_data| token_data.claims)
-}
\ No newline at end of file
+/// Token dengan scope terbatas, misalnya untuk share link atau integrasi export
+pub fn generate_scoped_token(
+    user_id: &str,
+    scopes: &[TokenScope],
+    ttl: Duration,
+) -> Result<String, Error> {
+    let now = Utc::now();
+    let scope = scopes.iter().map(TokenScope::as_str).collect::<Vec<_>>().join(" ");
+
+    let claims = Claims {
+        sub: user_id.to_string(),
+        exp: (now + ttl).timestamp() as usize,
+        iat: now.timestamp() as usize,
+        scope: Some(scope),
+        impersonator: None,
+    };
+
+    keyring().sign(&claims, login_secret)
+}
+
+/// Token berumur pendek bagi admin untuk melihat aplikasi sebagai pengguna lain.
+/// Hanya bisa dipakai untuk request baca (dicek oleh `AuthenticatedUser`).
+pub fn generate_impersonation_token(
+    user_id: &str,
+    impersonator_id: &str,
+    ttl: Duration,
+) -> Result<String, Error> {
+    let now = Utc::now();
+    let claims = Claims {
+        sub: user_id.to_string(),
+        exp: (now + ttl).timestamp() as usize,
+        iat: now.timestamp() as usize,
+        scope: None,
+        impersonator: Some(impersonator_id.to_string()),
+    };
+
+    keyring().sign(&claims, login_secret)
+}
+
+pub fn validate_token(token: &str) -> Result<Claims, Error> {
+    keyring().verify(token, login_secret)
+}
+
+/// Klaim link check-in mood dari email. Sengaja tanpa `sub` dan ditandatangani dengan kunci
+/// turunan agar tidak pernah bisa dipakai sebagai token login.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CheckinClaims {
+    pub uid: i32,
+    /// Tanggal mood yang dicatat (tanggal lokal pengguna saat email dikirim)
+    pub date: NaiveDate,
+    /// ID unik token; dicatat saat dipakai agar link hanya berlaku sekali
+    pub jti: String,
+    pub exp: usize,
+}
+
+fn checkin_secret(secret: &str) -> String {
+    format!("{}:checkin", secret)
+}
+
+pub fn generate_checkin_token(
+    user_id: i32,
+    date: NaiveDate,
+    jti: &str,
+    ttl: Duration,
+) -> Result<String, Error> {
+    let claims = CheckinClaims {
+        uid: user_id,
+        date,
+        jti: jti.to_string(),
+        exp: (Utc::now() + ttl).timestamp() as usize,
+    };
+
+    keyring().sign(&claims, checkin_secret)
+}
+
+pub fn validate_checkin_token(token: &str) -> Result<CheckinClaims, Error> {
+    keyring().verify(token, checkin_secret)
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(kid: &str, secret: &str, previous: Option<&str>) -> AppConfig {
+        AppConfig {
+            jwt_key_id: kid.to_string(),
+            jwt_secret: Some(secret.to_string()),
+            jwt_previous_keys: previous.map(str::to_string),
+            jwt_keys_file: None,
+            ..AppConfig::from_env()
+        }
+    }
+
+    #[test]
+    fn rotated_keyring_still_accepts_previous_key() {
+        let old = JwtKeyring::from_config(&config("2025", "old-secret", None)).unwrap();
+        let rotated = JwtKeyring::from_config(&config("2026", "new-secret", Some("2025:old-secret"))).unwrap();
+        let claims = Claims { sub: "7".to_string(), exp: usize::MAX, iat: 0, scope: None, impersonator: None };
+
+        let old_token = old.sign(&claims, login_secret).unwrap();
+        assert_eq!(rotated.verify::<Claims>(&old_token, login_secret).unwrap().sub, "7");
+        assert!(rotated.verify::<Claims>(&old_token, checkin_secret).is_err());
+
+        let new_token = rotated.sign(&claims, login_secret).unwrap();
+        assert_eq!(decode_header(&new_token).unwrap().kid.as_deref(), Some("2026"));
+        assert!(old.verify::<Claims>(&new_token, login_secret).is_err());
+
+        // Token tanpa kid (dibuat sebelum rotasi didukung) dicoba dengan semua kunci
+        let legacy = encode(&Header::default(), &claims, &EncodingKey::from_secret(b"old-secret")).unwrap();
+        assert!(rotated.verify::<Claims>(&legacy, login_secret).is_ok());
+
+        assert!(JwtKeyring::from_config(&config("2026", "new-secret", Some("2026:other"))).is_err());
+        assert!(JwtKeyring::from_config(&config("2026", "new-secret", Some("missing-secret"))).is_err());
+    }
+}
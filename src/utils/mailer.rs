@@ -0,0 +1,96 @@
+use std::sync::Arc;
+use std::time::Duration;
+use lettre::message::header::ContentType;
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{Message, SmtpTransport, Transport};
+use crate::config::app_config::AppConfig;
+use crate::errors::app_error::AppError;
+
+#[derive(Debug, Clone)]
+pub struct EmailMessage {
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+}
+
+/// Pengirim email. Implementasi bisa diganti (SMTP, API provider) tanpa mengubah service.
+pub trait Mailer: Send + Sync {
+    fn send(&self, message: &EmailMessage) -> Result<(), AppError>;
+}
+
+/// Pilih mailer dari konfigurasi: SMTP jika `SMTP_HOST` diisi, selain itu `LogMailer`
+pub fn mailer_from_config(config: &AppConfig) -> Result<Arc<dyn Mailer>, AppError> {
+    Ok(match SmtpMailer::from_config(config)? {
+        Some(mailer) => Arc::new(mailer),
+        None => Arc::new(LogMailer),
+    })
+}
+
+/// Mailer untuk development yang hanya mencatat penerima dan subjek. Isi email tidak ditulis
+/// ke log karena bisa berisi token reset password atau tautan konfirmasi.
+pub struct LogMailer;
+
+impl Mailer for LogMailer {
+    fn send(&self, message: &EmailMessage) -> Result<(), AppError> {
+        println!("📧 Email to {}: {} (body not logged, {} bytes)", message.to, message.subject, message.body.len());
+        Ok(())
+    }
+}
+
+/// Kirim email lewat server SMTP sebagai teks biasa
+pub struct SmtpMailer {
+    transport: SmtpTransport,
+    from: Mailbox,
+}
+
+impl SmtpMailer {
+    /// `None` jika SMTP belum dikonfigurasi
+    pub fn from_config(config: &AppConfig) -> Result<Option<Self>, AppError> {
+        let (Some(host), Some(from)) = (&config.smtp_host, &config.smtp_from) else {
+            return Ok(None);
+        };
+        let from = from
+            .parse::<Mailbox>()
+            .map_err(|e| AppError::InternalServerError(format!("Invalid SMTP_FROM: {}", e)))?;
+
+        let builder = match config.smtp_tls.as_str() {
+            "starttls" => SmtpTransport::starttls_relay(host),
+            "tls" => SmtpTransport::relay(host),
+            "none" => Ok(SmtpTransport::builder_dangerous(host)),
+            other => return Err(AppError::InternalServerError(format!("Unknown SMTP_TLS: {}", other))),
+        }
+        .map_err(|e| AppError::InternalServerError(format!("Invalid SMTP_HOST: {}", e)))?;
+
+        let mut builder = builder.timeout(Some(Duration::from_secs(config.smtp_timeout_secs)));
+        if let Some(port) = config.smtp_port {
+            builder = builder.port(port);
+        }
+        if let (Some(username), Some(password)) = (&config.smtp_username, &config.smtp_password) {
+            builder = builder.credentials(Credentials::new(username.clone(), password.clone()));
+        }
+
+        Ok(Some(SmtpMailer { transport: builder.build(), from }))
+    }
+}
+
+impl Mailer for SmtpMailer {
+    fn send(&self, message: &EmailMessage) -> Result<(), AppError> {
+        let to = message
+            .to
+            .parse::<Mailbox>()
+            .map_err(|e| AppError::BadRequest(format!("Invalid email address: {}", e)))?;
+        let email = Message::builder()
+            .from(self.from.clone())
+            .to(to)
+            .subject(&message.subject)
+            .header(ContentType::TEXT_PLAIN)
+            .body(message.body.clone())
+            .map_err(|e| AppError::InternalServerError(format!("Failed to build email: {}", e)))?;
+
+        self.transport
+            .send(&email)
+            .map_err(|e| AppError::InternalServerError(format!("Failed to send email: {}", e)))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,45 @@
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use crate::config::app_config::AppConfig;
+use crate::models::status::MaintenanceStatus;
+
+/// Saklar mode maintenance baca-saja. Nilai awal dari config, lalu bisa diubah admin
+/// saat server berjalan; perubahan hanya berlaku di instance yang menerimanya.
+pub struct MaintenanceMode {
+    enabled: AtomicBool,
+    retry_after_secs: AtomicU64,
+}
+
+impl MaintenanceMode {
+    pub fn new(enabled: bool, retry_after_secs: u64) -> Self {
+        MaintenanceMode {
+            enabled: AtomicBool::new(enabled),
+            retry_after_secs: AtomicU64::new(retry_after_secs),
+        }
+    }
+
+    pub fn from_config(config: &AppConfig) -> Self {
+        MaintenanceMode::new(config.maintenance_mode, config.maintenance_retry_after_secs)
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    pub fn retry_after_secs(&self) -> u64 {
+        self.retry_after_secs.load(Ordering::Relaxed)
+    }
+
+    pub fn set(&self, enabled: bool, retry_after_secs: Option<u64>) {
+        if let Some(secs) = retry_after_secs {
+            self.retry_after_secs.store(secs, Ordering::Relaxed);
+        }
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    pub fn status(&self) -> MaintenanceStatus {
+        MaintenanceStatus {
+            enabled: self.is_enabled(),
+            retry_after_secs: self.retry_after_secs(),
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use pulldown_cmark::{Event, Parser, TagEnd};
+
+/// Panjang maksimum preview jurnal, dalam karakter
+pub const PREVIEW_MAX_CHARS: usize = 200;
+
+/// Teks polos dari Markdown untuk preview: format, link, gambar dan HTML dibuang (teksnya tetap),
+/// spasi dirapikan, lalu dipotong di batas kata dengan "…" jika lebih dari `max_chars` karakter
+pub fn preview(markdown: &str, max_chars: usize) -> String {
+    let mut text = String::new();
+    for event in Parser::new(markdown) {
+        match event {
+            Event::Text(part) | Event::Code(part) => text.push_str(&part),
+            Event::SoftBreak | Event::HardBreak | Event::Rule => text.push(' '),
+            Event::End(tag) if ends_block(&tag) => text.push(' '),
+            _ => {}
+        }
+    }
+
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    truncate(text, max_chars)
+}
+
+fn ends_block(tag: &TagEnd) -> bool {
+    matches!(
+        tag,
+        TagEnd::Paragraph | TagEnd::Heading(_) | TagEnd::Item | TagEnd::CodeBlock | TagEnd::TableCell | TagEnd::TableRow
+    )
+}
+
+fn truncate(text: String, max_chars: usize) -> String {
+    let Some((cut, _)) = text.char_indices().nth(max_chars) else {
+        return text;
+    };
+    let head = &text[..cut];
+    // Potong di spasi terakhir, kecuali kata terakhir terlalu panjang
+    let head = match head.rfind(' ') {
+        Some(space) if space >= cut / 2 => &head[..space],
+        _ => head,
+    };
+    format!("{}…", head.trim_end())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_markdown_and_truncates_on_word_boundary() {
+        let markdown = "# Hari ini\n\nAku **senang** sekali, [baca ini](https://x.id) dan `kode`.\n\n- satu\n- dua\n\n![foto](a.png)";
+        assert_eq!(preview(markdown, 200), "Hari ini Aku senang sekali, baca ini dan kode. satu dua foto");
+        assert_eq!(preview(markdown, 20), "Hari ini Aku senang…");
+        assert_eq!(preview("Tanpa format", 200), "Tanpa format");
+        assert_eq!(preview("", 200), "");
+    }
+}
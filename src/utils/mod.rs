@@ -1 +1,32 @@
 pub mod jwt;
+pub mod api_dates;
+pub mod timezone;
+pub mod pdf_report;
+pub mod ical;
+pub mod mailer;
+pub mod event_bus;
+pub mod http_client;
+pub mod stats_cache;
+pub mod password_policy;
+pub mod username;
+pub mod storage;
+pub mod image_processing;
+pub mod mood_interpretation;
+pub mod text_limits;
+pub mod json_stream;
+pub mod push;
+pub mod zip_stream;
+pub mod daylio;
+pub mod captcha;
+pub mod synthetic_data;
+pub mod streaks;
+pub mod patch;
+pub mod email;
+pub mod rate_limit;
+pub mod moderation;
+pub mod maintenance;
+pub mod auth_cookie;
+pub mod redirect;
+pub mod token_crypto;
+pub mod api_usage;
+pub mod markdown_preview;
\ No newline at end of file
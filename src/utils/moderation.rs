@@ -0,0 +1,259 @@
+use axum::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::config::app_config::AppConfig;
+use crate::errors::app_error::AppError;
+use crate::utils::http_client::HttpClient;
+
+/// Tindakan moderasi, urut dari yang paling ringan
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ModerationAction {
+    Allow,
+    /// Konten tetap tampil, tetapi masuk antrian tinjauan admin
+    Flag,
+    /// Konten disembunyikan dari penerima sampai disetujui admin
+    Hold,
+}
+
+impl ModerationAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ModerationAction::Allow => "allow",
+            ModerationAction::Flag => "flag",
+            ModerationAction::Hold => "hold",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "allow" => Some(ModerationAction::Allow),
+            "flag" => Some(ModerationAction::Flag),
+            "hold" => Some(ModerationAction::Hold),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModerationVerdict {
+    pub action: ModerationAction,
+    /// Pemeriksa yang menentukan tindakan (`terms`, `api`)
+    pub source: &'static str,
+    pub reasons: Vec<String>,
+}
+
+impl ModerationVerdict {
+    pub fn allow() -> Self {
+        ModerationVerdict {
+            action: ModerationAction::Allow,
+            source: "none",
+            reasons: Vec::new(),
+        }
+    }
+
+    pub fn is_held(&self) -> bool {
+        self.action == ModerationAction::Hold
+    }
+}
+
+/// Satu tahap pipeline moderasi
+#[async_trait]
+pub trait ContentModerator: Send + Sync {
+    async fn review(&self, text: &str) -> Result<ModerationVerdict, AppError>;
+}
+
+/// Huruf kecil, selain huruf/angka jadi spasi, diapit spasi agar bisa dicocokkan per kata
+fn normalize(text: &str) -> String {
+    let words: Vec<String> = text
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_string)
+        .collect();
+    format!(" {} ", words.join(" "))
+}
+
+/// Daftar istilah terlarang; dicocokkan per kata utuh tanpa membedakan huruf besar/kecil
+pub struct BannedTermsModerator {
+    terms: Vec<String>,
+    action: ModerationAction,
+}
+
+impl BannedTermsModerator {
+    pub fn new(terms: &[String], action: ModerationAction) -> Self {
+        BannedTermsModerator {
+            terms: terms
+                .iter()
+                .map(|term| normalize(term))
+                .filter(|term| !term.trim().is_empty())
+                .collect(),
+            action,
+        }
+    }
+
+    fn matches(&self, text: &str) -> Vec<String> {
+        let text = normalize(text);
+        self.terms
+            .iter()
+            .filter(|term| text.contains(term.as_str()))
+            .map(|term| term.trim().to_string())
+            .collect()
+    }
+}
+
+#[async_trait]
+impl ContentModerator for BannedTermsModerator {
+    async fn review(&self, text: &str) -> Result<ModerationVerdict, AppError> {
+        let reasons = self.matches(text);
+        if reasons.is_empty() {
+            return Ok(ModerationVerdict::allow());
+        }
+        Ok(ModerationVerdict {
+            action: self.action,
+            source: "terms",
+            reasons,
+        })
+    }
+}
+
+#[derive(Serialize)]
+struct ApiModerationRequest<'a> {
+    text: &'a str,
+}
+
+#[derive(Deserialize)]
+struct ApiModerationResponse {
+    action: String,
+    #[serde(default)]
+    reasons: Vec<String>,
+}
+
+/// API moderasi eksternal: POST `{"text": ...}`, balasan `{"action": "allow|flag|hold", "reasons": [...]}`
+pub struct ApiModerator {
+    http_client: HttpClient,
+    url: String,
+    api_key: Option<String>,
+}
+
+#[async_trait]
+impl ContentModerator for ApiModerator {
+    async fn review(&self, text: &str) -> Result<ModerationVerdict, AppError> {
+        let mut request = self.http_client.post(&self.url).json(&ApiModerationRequest { text });
+        if let Some(ref api_key) = self.api_key {
+            request = request.bearer_auth(api_key);
+        }
+
+        let response = self
+            .http_client
+            .send_idempotent(request)
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("Moderation API request failed: {}", e)))?;
+        if !response.status().is_success() {
+            return Err(AppError::InternalServerError(format!(
+                "Moderation API returned {}",
+                response.status()
+            )));
+        }
+
+        let result = response
+            .json::<ApiModerationResponse>()
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("Invalid moderation API response: {}", e)))?;
+        let action = ModerationAction::parse(&result.action).ok_or_else(|| {
+            AppError::InternalServerError(format!("Unknown moderation action: {}", result.action))
+        })?;
+        Ok(ModerationVerdict {
+            action,
+            source: "api",
+            reasons: result.reasons,
+        })
+    }
+}
+
+/// Menjalankan semua pemeriksa dan mengambil tindakan yang paling berat.
+/// Jika sebuah pemeriksa gagal (misalnya API tidak bisa dihubungi), konten ditandai
+/// untuk ditinjau, bukan ditolak.
+pub struct ModerationPipeline {
+    moderators: Vec<Box<dyn ContentModerator>>,
+}
+
+impl ModerationPipeline {
+    pub fn new(moderators: Vec<Box<dyn ContentModerator>>) -> Self {
+        ModerationPipeline { moderators }
+    }
+
+    pub fn from_config(config: &AppConfig, http_client: HttpClient) -> Result<Self, AppError> {
+        let mut moderators: Vec<Box<dyn ContentModerator>> = Vec::new();
+
+        if !config.moderation_banned_terms.is_empty() {
+            let action = ModerationAction::parse(&config.moderation_banned_terms_action)
+                .filter(|action| *action != ModerationAction::Allow)
+                .ok_or_else(|| {
+                    AppError::InternalServerError(format!(
+                        "Invalid MODERATION_BANNED_TERMS_ACTION: {}",
+                        config.moderation_banned_terms_action
+                    ))
+                })?;
+            moderators.push(Box::new(BannedTermsModerator::new(&config.moderation_banned_terms, action)));
+        }
+
+        if let Some(ref url) = config.moderation_api_url {
+            moderators.push(Box::new(ApiModerator {
+                http_client,
+                url: url.clone(),
+                api_key: config.moderation_api_key.clone(),
+            }));
+        }
+
+        Ok(Self::new(moderators))
+    }
+
+    pub async fn review(&self, text: &str) -> ModerationVerdict {
+        let mut verdict = ModerationVerdict::allow();
+        for moderator in &self.moderators {
+            let result = moderator.review(text).await.unwrap_or_else(|e| {
+                eprintln!("❌ Moderation check failed: {}", e);
+                ModerationVerdict {
+                    action: ModerationAction::Flag,
+                    source: "error",
+                    reasons: vec!["moderation_unavailable".to_string()],
+                }
+            });
+            if result.action > verdict.action {
+                verdict.action = result.action;
+                verdict.source = result.source;
+            }
+            verdict.reasons.extend(result.reasons);
+        }
+        verdict
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn terms(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn banned_terms_match_whole_words_case_insensitively() {
+        let moderator = BannedTermsModerator::new(&terms(&["spam", "buy now"]), ModerationAction::Hold);
+        assert_eq!(moderator.matches("This is SPAM!"), vec!["spam".to_string()]);
+        assert_eq!(moderator.matches("Buy   now, cheap"), vec!["buy now".to_string()]);
+        assert!(moderator.matches("spammer").is_empty());
+    }
+
+    #[tokio::test]
+    async fn pipeline_keeps_the_most_severe_action() {
+        let pipeline = ModerationPipeline::new(vec![
+            Box::new(BannedTermsModerator::new(&terms(&["spam"]), ModerationAction::Flag)),
+            Box::new(BannedTermsModerator::new(&terms(&["scam"]), ModerationAction::Hold)),
+        ]);
+
+        let verdict = pipeline.review("spam and scam").await;
+        assert_eq!(verdict.action, ModerationAction::Hold);
+        assert_eq!(verdict.reasons, terms(&["spam", "scam"]));
+        assert_eq!(pipeline.review("hello").await, ModerationVerdict::allow());
+    }
+}
@@ -0,0 +1,22 @@
+use crate::config::app_config::AppConfig;
+use crate::i18n::t;
+use crate::models::mood::ScoreInterpretation;
+
+/// Kode interpretasi untuk skor rata-rata, berdasarkan ambang batas di konfigurasi
+pub fn interpretation_code(average_score: Option<f64>, config: &AppConfig) -> &'static str {
+    match average_score {
+        None => "no_data",
+        Some(score) if score < config.mood_score_low_threshold => "low",
+        Some(score) if score < config.mood_score_good_threshold => "moderate",
+        Some(_) => "good",
+    }
+}
+
+/// Interpretasi skor rata-rata dengan label dari katalog i18n sesuai bahasa request
+pub fn interpret_average_score(average_score: Option<f64>, config: &AppConfig) -> ScoreInterpretation {
+    let code = interpretation_code(average_score, config);
+    ScoreInterpretation {
+        code: code.to_string(),
+        label: t(&format!("mood.interpretation.{}", code)),
+    }
+}
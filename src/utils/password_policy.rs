@@ -0,0 +1,186 @@
+use std::collections::HashSet;
+use std::sync::OnceLock;
+use crate::config::app_config::AppConfig;
+use crate::errors::app_error::AppError;
+
+/// Daftar password yang paling sering dipakai, dicek tanpa membedakan huruf besar/kecil
+const COMMON_PASSWORDS: &str = include_str!("common_passwords.txt");
+
+fn common_passwords() -> &'static HashSet<&'static str> {
+    static LIST: OnceLock<HashSet<&'static str>> = OnceLock::new();
+    LIST.get_or_init(|| {
+        COMMON_PASSWORDS
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect()
+    })
+}
+
+/// Aturan password yang dipakai saat register, ganti password dan reset password
+#[derive(Debug, Clone)]
+pub struct PasswordPolicy {
+    pub min_length: usize,
+    pub require_lowercase: bool,
+    pub require_uppercase: bool,
+    pub require_digit: bool,
+    pub require_symbol: bool,
+    /// Skor kekuatan minimum 0-4 (gaya zxcvbn); 0 berarti tidak dicek
+    pub min_strength: u8,
+}
+
+impl PasswordPolicy {
+    pub fn from_config(config: &AppConfig) -> Self {
+        PasswordPolicy {
+            min_length: config.password_min_length,
+            require_lowercase: config.password_require_lowercase,
+            require_uppercase: config.password_require_uppercase,
+            require_digit: config.password_require_digit,
+            require_symbol: config.password_require_symbol,
+            min_strength: config.password_min_strength,
+        }
+    }
+
+    /// Validasi password baru. `user_inputs` berisi data akun (email, username)
+    /// yang tidak boleh dipakai sebagai password.
+    pub fn validate(&self, password: &str, user_inputs: &[&str]) -> Result<(), AppError> {
+        if password.chars().count() < self.min_length {
+            return Err(AppError::BadRequest(format!(
+                "Password must be at least {} characters long",
+                self.min_length
+            )));
+        }
+
+        if self.require_lowercase && !password.chars().any(|c| c.is_lowercase()) {
+            return Err(AppError::BadRequest("Password must contain a lowercase letter".to_string()));
+        }
+        if self.require_uppercase && !password.chars().any(|c| c.is_uppercase()) {
+            return Err(AppError::BadRequest("Password must contain an uppercase letter".to_string()));
+        }
+        if self.require_digit && !password.chars().any(|c| c.is_ascii_digit()) {
+            return Err(AppError::BadRequest("Password must contain a digit".to_string()));
+        }
+        if self.require_symbol && !password.chars().any(is_symbol) {
+            return Err(AppError::BadRequest("Password must contain a symbol".to_string()));
+        }
+
+        let lowered = password.to_lowercase();
+        if common_passwords().contains(lowered.as_str()) {
+            return Err(AppError::BadRequest("Password is too common".to_string()));
+        }
+
+        let contains_user_input = user_inputs
+            .iter()
+            .map(|input| input.split('@').next().unwrap_or(input).trim().to_lowercase())
+            .any(|input| input.len() >= 3 && lowered.contains(&input));
+        if contains_user_input {
+            return Err(AppError::BadRequest("Password must not contain your username or email".to_string()));
+        }
+
+        if self.min_strength > 0 && strength_score(password) < self.min_strength {
+            return Err(AppError::BadRequest("Password is too weak".to_string()));
+        }
+
+        Ok(())
+    }
+}
+
+fn is_symbol(c: char) -> bool {
+    !c.is_alphanumeric() && !c.is_whitespace()
+}
+
+/// Perkiraan kekuatan password dengan skala 0-4 seperti zxcvbn, berdasarkan
+/// entropi kasar (panjang x variasi karakter) dengan penalti untuk pengulangan
+pub fn strength_score(password: &str) -> u8 {
+    let chars: Vec<char> = password.chars().collect();
+    if chars.is_empty() {
+        return 0;
+    }
+
+    let mut pool = 0u32;
+    if chars.iter().any(|c| c.is_lowercase()) {
+        pool += 26;
+    }
+    if chars.iter().any(|c| c.is_uppercase()) {
+        pool += 26;
+    }
+    if chars.iter().any(|c| c.is_ascii_digit()) {
+        pool += 10;
+    }
+    if chars.iter().any(|c| is_symbol(*c)) {
+        pool += 33;
+    }
+
+    // Karakter yang sama dengan karakter sebelumnya hampir tidak menambah entropi
+    let effective_len = chars
+        .windows(2)
+        .filter(|pair| pair[0] != pair[1])
+        .count()
+        + 1;
+
+    let entropy = effective_len as f64 * f64::from(pool.max(1)).log2();
+    match entropy {
+        e if e < 28.0 => 0,
+        e if e < 36.0 => 1,
+        e if e < 60.0 => 2,
+        e if e < 80.0 => 3,
+        _ => 4,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strict() -> PasswordPolicy {
+        PasswordPolicy {
+            min_length: 10,
+            require_lowercase: true,
+            require_uppercase: true,
+            require_digit: true,
+            require_symbol: true,
+            min_strength: 3,
+        }
+    }
+
+    fn rejection(policy: &PasswordPolicy, password: &str, user_inputs: &[&str]) -> String {
+        match policy.validate(password, user_inputs) {
+            Err(AppError::BadRequest(message)) => message,
+            other => panic!("expected BadRequest for {}, got {:?}", password, other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn each_rule_is_enforced() {
+        let policy = strict();
+        assert!(rejection(&policy, "Short1!", &[]).contains("at least 10"));
+        assert!(rejection(&policy, "all-lower-2026", &[]).contains("uppercase"));
+        assert!(rejection(&policy, "ALL-UPPER-2026", &[]).contains("lowercase"));
+        assert!(rejection(&policy, "No-Digits-Here", &[]).contains("digit"));
+        assert!(rejection(&policy, "NoSymbols2026", &[]).contains("symbol"));
+        assert!(policy.validate("Quiet-Lantern-2026!", &[]).is_ok());
+    }
+
+    #[test]
+    fn common_passwords_and_account_data_are_rejected() {
+        let lenient = PasswordPolicy {
+            min_length: 8,
+            require_lowercase: false,
+            require_uppercase: false,
+            require_digit: false,
+            require_symbol: false,
+            min_strength: 0,
+        };
+        assert_eq!(rejection(&lenient, "PASSWORD123", &[]), "Password is too common");
+        assert!(rejection(&strict(), "Budi-Lantern-2026!", &["budi", "budi@example.com"]).contains("username"));
+        assert!(rejection(&strict(), "Lantern-Sari99!", &["sari", "sari.w@example.com"]).contains("username"));
+    }
+
+    #[test]
+    fn strength_score_penalizes_repetition() {
+        assert_eq!(strength_score(""), 0);
+        assert_eq!(strength_score("aaaaaaaaaaaaaaaa"), 0);
+        assert_eq!(strength_score("Quiet-Lantern-2026!"), 4);
+        assert!(strength_score("abcdefgh") < strength_score("Abcdefgh-2026"));
+    }
+}
@@ -0,0 +1,85 @@
+use serde::{Deserialize, Deserializer};
+use crate::errors::app_error::AppError;
+
+/// Field request PATCH/PUT yang membedakan "tidak dikirim", `null` dan nilai.
+/// Pakai bersama `#[serde(default)]` agar field yang tidak dikirim menjadi `Absent`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Patch<T> {
+    /// Field tidak ada di request: nilai lama dipertahankan
+    #[default]
+    Absent,
+    /// Field dikirim sebagai `null`: nilai dihapus
+    Null,
+    Value(T),
+}
+
+impl<T> Patch<T> {
+    pub fn is_absent(&self) -> bool {
+        matches!(self, Patch::Absent)
+    }
+
+    /// Nilai baru jika ada, untuk validasi sebelum disimpan
+    pub fn value(&self) -> Option<&T> {
+        match self {
+            Patch::Value(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn map<U, F: FnOnce(T) -> U>(self, f: F) -> Patch<U> {
+        match self {
+            Patch::Absent => Patch::Absent,
+            Patch::Null => Patch::Null,
+            Patch::Value(value) => Patch::Value(f(value)),
+        }
+    }
+
+    /// Nilai akhir kolom nullable setelah perubahan diterapkan
+    pub fn apply(self, existing: Option<T>) -> Option<T> {
+        match self {
+            Patch::Absent => existing,
+            Patch::Null => None,
+            Patch::Value(value) => Some(value),
+        }
+    }
+
+    /// Bentuk changeset Diesel untuk kolom nullable: `None` = kolom tidak diubah,
+    /// `Some(None)` = diset NULL
+    pub fn into_change(self) -> Option<Option<T>> {
+        match self {
+            Patch::Absent => None,
+            Patch::Null => Some(None),
+            Patch::Value(value) => Some(Some(value)),
+        }
+    }
+
+    /// Untuk kolom NOT NULL: `null` ditolak, `None` berarti tidak diubah
+    pub fn required(self, field: &str) -> Result<Option<T>, AppError> {
+        match self {
+            Patch::Absent => Ok(None),
+            Patch::Null => Err(AppError::BadRequest(format!("{} cannot be null", field))),
+            Patch::Value(value) => Ok(Some(value)),
+        }
+    }
+}
+
+impl<T> From<Option<T>> for Patch<T> {
+    fn from(value: Option<T>) -> Self {
+        match value {
+            Some(value) => Patch::Value(value),
+            None => Patch::Null,
+        }
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Patch<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Option::<T>::deserialize(deserializer).map(Patch::from)
+    }
+}
@@ -0,0 +1,139 @@
+use printpdf::{BuiltinFont, IndirectFontRef, Mm, PdfDocument, PdfDocumentReference, PdfLayerReference};
+use crate::errors::app_error::AppError;
+use crate::models::report::MonthlyReport;
+use crate::utils::api_dates::format_date;
+
+const PAGE_WIDTH: f32 = 210.0;
+const PAGE_HEIGHT: f32 = 297.0;
+const MARGIN: f32 = 20.0;
+const LINE_HEIGHT: f32 = 6.0;
+
+/// Penulis teks sederhana yang otomatis menambah halaman baru saat penuh
+struct PdfWriter {
+    doc: PdfDocumentReference,
+    layer: PdfLayerReference,
+    regular: IndirectFontRef,
+    bold: IndirectFontRef,
+    cursor_y: f32,
+}
+
+impl PdfWriter {
+    fn new(title: &str) -> Result<Self, AppError> {
+        let (doc, page, layer) = PdfDocument::new(title, Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "content");
+        let regular = doc
+            .add_builtin_font(BuiltinFont::Helvetica)
+            .map_err(|e| AppError::InternalServerError(format!("Failed to load PDF font: {}", e)))?;
+        let bold = doc
+            .add_builtin_font(BuiltinFont::HelveticaBold)
+            .map_err(|e| AppError::InternalServerError(format!("Failed to load PDF font: {}", e)))?;
+        let layer = doc.get_page(page).get_layer(layer);
+
+        Ok(PdfWriter {
+            doc,
+            layer,
+            regular,
+            bold,
+            cursor_y: PAGE_HEIGHT - MARGIN,
+        })
+    }
+
+    fn ensure_space(&mut self, height: f32) {
+        if self.cursor_y - height < MARGIN {
+            let (page, layer) = self.doc.add_page(Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "content");
+            self.layer = self.doc.get_page(page).get_layer(layer);
+            self.cursor_y = PAGE_HEIGHT - MARGIN;
+        }
+    }
+
+    fn heading(&mut self, text: &str, size: f32) {
+        self.ensure_space(size * 0.6 + LINE_HEIGHT);
+        self.cursor_y -= size * 0.4;
+        self.layer.use_text(text, size, Mm(MARGIN), Mm(self.cursor_y), &self.bold);
+        self.cursor_y -= LINE_HEIGHT;
+    }
+
+    fn line(&mut self, text: &str) {
+        self.ensure_space(LINE_HEIGHT);
+        self.layer.use_text(text, 11.0, Mm(MARGIN), Mm(self.cursor_y), &self.regular);
+        self.cursor_y -= LINE_HEIGHT;
+    }
+
+    fn spacer(&mut self) {
+        self.cursor_y -= LINE_HEIGHT / 2.0;
+    }
+
+    fn finish(self) -> Result<Vec<u8>, AppError> {
+        self.doc
+            .save_to_bytes()
+            .map_err(|e| AppError::InternalServerError(format!("Failed to render PDF: {}", e)))
+    }
+}
+
+fn format_score(score: Option<f64>) -> String {
+    score.map(|s| format!("{:.2}", s)).unwrap_or_else(|| "-".to_string())
+}
+
+/// Render laporan bulanan menjadi dokumen PDF yang bisa dicetak
+pub fn render_monthly_report(report: &MonthlyReport) -> Result<Vec<u8>, AppError> {
+    let mut pdf = PdfWriter::new(&format!("MindMate Monthly Report {}", report.month))?;
+
+    pdf.heading("MindMate - Monthly Mood Report", 18.0);
+    pdf.line(&format!(
+        "Period: {} to {}",
+        format_date(&report.start_date),
+        format_date(&report.end_date)
+    ));
+    pdf.spacer();
+
+    pdf.heading("Summary", 14.0);
+    pdf.line(&format!("Mood entries: {}", report.total_entries));
+    pdf.line(&format!(
+        "Average score (1-5): {} - {}",
+        format_score(report.average_score),
+        report.interpretation.label
+    ));
+    pdf.line(&format!("Journal entries: {}", report.journal_count));
+    if let Some(ref day) = report.best_day {
+        pdf.line(&format!("Best day: {} ({}, score {})", format_date(&day.date), day.mood, day.score));
+    }
+    if let Some(ref day) = report.worst_day {
+        pdf.line(&format!("Worst day: {} ({}, score {})", format_date(&day.date), day.mood, day.score));
+    }
+    if let Some(ref streak) = report.longest_streak {
+        pdf.line(&format!(
+            "Longest streak: {} days ({} to {})",
+            streak.length,
+            format_date(&streak.start_date),
+            format_date(&streak.end_date)
+        ));
+    }
+    pdf.spacer();
+
+    pdf.heading("Mood distribution", 14.0);
+    for item in &report.mood_distribution {
+        pdf.line(&format!("{:<12} {:>3} ({:.1}%)", item.mood, item.count, item.percentage));
+    }
+    pdf.spacer();
+
+    pdf.heading("Weekly averages", 14.0);
+    for week in &report.weekly_averages {
+        pdf.line(&format!(
+            "{} - {}: {} ({} entries)",
+            format_date(&week.week_start),
+            format_date(&week.week_end),
+            format_score(week.average_score),
+            week.entries
+        ));
+    }
+    pdf.spacer();
+
+    pdf.heading("Daily moods", 14.0);
+    if report.daily_scores.is_empty() {
+        pdf.line("No mood entries for this month.");
+    }
+    for day in &report.daily_scores {
+        pdf.line(&format!("{}  {} (score {})", format_date(&day.date), day.mood, day.score));
+    }
+
+    pdf.finish()
+}
@@ -0,0 +1,287 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use chrono::Utc;
+use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use crate::config::app_config::AppConfig;
+use crate::errors::app_error::AppError;
+use crate::models::device::DevicePlatform;
+use crate::utils::http_client::HttpClient;
+
+const FCM_SCOPE: &str = "https://www.googleapis.com/auth/firebase.messaging";
+const GOOGLE_TOKEN_URL: &str = "https://oauth2.googleapis.com/token";
+const APNS_PRODUCTION_URL: &str = "https://api.push.apple.com";
+const APNS_SANDBOX_URL: &str = "https://api.sandbox.push.apple.com";
+
+/// Token OAuth FCM berlaku 1 jam, provider token APNs maksimal 1 jam;
+/// keduanya diperbarui sebelum habis
+const AUTH_TOKEN_LIFETIME: Duration = Duration::from_secs(50 * 60);
+
+#[derive(Debug, Clone)]
+pub struct PushMessage {
+    /// Jenis notifikasi, misalnya "reminder" atau "insight_alert"; diteruskan ke aplikasi
+    pub category: String,
+    pub title: String,
+    pub body: String,
+}
+
+#[derive(Debug)]
+pub enum PushError {
+    /// Token perangkat sudah tidak berlaku; perangkat sebaiknya dihapus
+    InvalidToken,
+    /// Kredensial provider untuk platform ini tidak dikonfigurasi
+    NotConfigured,
+    /// Gagal sementara, boleh dicoba lagi
+    Failed(String),
+}
+
+impl std::fmt::Display for PushError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PushError::InvalidToken => write!(f, "invalid device token"),
+            PushError::NotConfigured => write!(f, "push provider not configured"),
+            PushError::Failed(reason) => write!(f, "{}", reason),
+        }
+    }
+}
+
+/// Pengirim push notification ke FCM dan APNs. Provider tanpa kredensial lengkap dinonaktifkan.
+pub struct PushSender {
+    fcm: Option<FcmClient>,
+    apns: Option<ApnsClient>,
+}
+
+impl PushSender {
+    pub fn from_config(config: &AppConfig, http_client: HttpClient) -> Result<Self, AppError> {
+        let fcm = match (&config.fcm_project_id, &config.fcm_client_email, &config.fcm_private_key) {
+            (Some(project_id), Some(client_email), Some(private_key)) => Some(FcmClient {
+                http_client,
+                project_id: project_id.clone(),
+                client_email: client_email.clone(),
+                key: EncodingKey::from_rsa_pem(private_key.as_bytes())
+                    .map_err(|e| AppError::InternalServerError(format!("Invalid FCM_PRIVATE_KEY: {}", e)))?,
+                access_token: Mutex::new(None),
+            }),
+            _ => None,
+        };
+
+        let apns = match (&config.apns_key_id, &config.apns_team_id, &config.apns_private_key, &config.apns_topic) {
+            (Some(key_id), Some(team_id), Some(private_key), Some(topic)) => Some(ApnsClient {
+                http_client: HttpClient::http2_from_config(config)?,
+                base_url: if config.apns_sandbox { APNS_SANDBOX_URL } else { APNS_PRODUCTION_URL },
+                key_id: key_id.clone(),
+                team_id: team_id.clone(),
+                topic: topic.clone(),
+                key: EncodingKey::from_ec_pem(private_key.as_bytes())
+                    .map_err(|e| AppError::InternalServerError(format!("Invalid APNS_PRIVATE_KEY: {}", e)))?,
+                provider_token: Mutex::new(None),
+            }),
+            _ => None,
+        };
+
+        Ok(PushSender { fcm, apns })
+    }
+
+    pub async fn send(
+        &self,
+        platform: DevicePlatform,
+        device_token: &str,
+        message: &PushMessage,
+    ) -> Result<(), PushError> {
+        match platform {
+            DevicePlatform::Fcm => match self.fcm {
+                Some(ref fcm) => fcm.send(device_token, message).await,
+                None => Err(PushError::NotConfigured),
+            },
+            DevicePlatform::Apns => match self.apns {
+                Some(ref apns) => apns.send(device_token, message).await,
+                None => Err(PushError::NotConfigured),
+            },
+        }
+    }
+}
+
+/// Token auth yang di-cache sampai mendekati masa berlakunya
+type CachedToken = Mutex<Option<(String, Instant)>>;
+
+fn cached(token: &CachedToken) -> Option<String> {
+    let guard = token.lock().ok()?;
+    guard
+        .as_ref()
+        .filter(|(_, created)| created.elapsed() < AUTH_TOKEN_LIFETIME)
+        .map(|(value, _)| value.clone())
+}
+
+fn store(token: &CachedToken, value: &str) {
+    if let Ok(mut guard) = token.lock() {
+        *guard = Some((value.to_string(), Instant::now()));
+    }
+}
+
+struct FcmClient {
+    http_client: HttpClient,
+    project_id: String,
+    client_email: String,
+    key: EncodingKey,
+    access_token: CachedToken,
+}
+
+#[derive(Serialize)]
+struct GoogleAssertionClaims<'a> {
+    iss: &'a str,
+    scope: &'a str,
+    aud: &'a str,
+    iat: i64,
+    exp: i64,
+}
+
+#[derive(Deserialize)]
+struct GoogleTokenResponse {
+    access_token: String,
+}
+
+impl FcmClient {
+    /// Tukar assertion service account dengan access token OAuth
+    async fn access_token(&self) -> Result<String, PushError> {
+        if let Some(token) = cached(&self.access_token) {
+            return Ok(token);
+        }
+
+        let now = Utc::now().timestamp();
+        let assertion = encode(
+            &Header::new(Algorithm::RS256),
+            &GoogleAssertionClaims {
+                iss: &self.client_email,
+                scope: FCM_SCOPE,
+                aud: GOOGLE_TOKEN_URL,
+                iat: now,
+                exp: now + 3600,
+            },
+            &self.key,
+        )
+        .map_err(|e| PushError::Failed(format!("Failed to sign FCM assertion: {}", e)))?;
+
+        let response = self
+            .http_client
+            .send_idempotent(self.http_client.post(GOOGLE_TOKEN_URL).form(&[
+                ("grant_type", "urn:ietf:params:oauth:grant-type:jwt-bearer"),
+                ("assertion", assertion.as_str()),
+            ]))
+            .await
+            .map_err(|e| PushError::Failed(format!("FCM token request failed: {}", e)))?;
+
+        if !response.status().is_success() {
+            return Err(PushError::Failed(format!("FCM token request returned {}", response.status())));
+        }
+
+        let token = response
+            .json::<GoogleTokenResponse>()
+            .await
+            .map_err(|e| PushError::Failed(format!("Invalid FCM token response: {}", e)))?
+            .access_token;
+        store(&self.access_token, &token);
+        Ok(token)
+    }
+
+    async fn send(&self, device_token: &str, message: &PushMessage) -> Result<(), PushError> {
+        let access_token = self.access_token().await?;
+        let url = format!("https://fcm.googleapis.com/v1/projects/{}/messages:send", self.project_id);
+        let payload = json!({
+            "message": {
+                "token": device_token,
+                "notification": { "title": message.title, "body": message.body },
+                "data": { "category": message.category },
+            }
+        });
+
+        let response = self
+            .http_client
+            .send(self.http_client.post(&url).bearer_auth(access_token).json(&payload))
+            .await
+            .map_err(|e| PushError::Failed(format!("FCM request failed: {}", e)))?;
+
+        let status = response.status();
+        if status.is_success() {
+            return Ok(());
+        }
+
+        let body = response.text().await.unwrap_or_default();
+        if status == StatusCode::NOT_FOUND || body.contains("UNREGISTERED") {
+            return Err(PushError::InvalidToken);
+        }
+        Err(PushError::Failed(format!("FCM returned {}: {}", status, body)))
+    }
+}
+
+struct ApnsClient {
+    http_client: HttpClient,
+    base_url: &'static str,
+    key_id: String,
+    team_id: String,
+    topic: String,
+    key: EncodingKey,
+    provider_token: CachedToken,
+}
+
+#[derive(Serialize)]
+struct ApnsProviderClaims<'a> {
+    iss: &'a str,
+    iat: i64,
+}
+
+impl ApnsClient {
+    fn provider_token(&self) -> Result<String, PushError> {
+        if let Some(token) = cached(&self.provider_token) {
+            return Ok(token);
+        }
+
+        let mut header = Header::new(Algorithm::ES256);
+        header.kid = Some(self.key_id.clone());
+        let token = encode(
+            &header,
+            &ApnsProviderClaims {
+                iss: &self.team_id,
+                iat: Utc::now().timestamp(),
+            },
+            &self.key,
+        )
+        .map_err(|e| PushError::Failed(format!("Failed to sign APNs token: {}", e)))?;
+
+        store(&self.provider_token, &token);
+        Ok(token)
+    }
+
+    async fn send(&self, device_token: &str, message: &PushMessage) -> Result<(), PushError> {
+        let url = format!("{}/3/device/{}", self.base_url, device_token);
+        let payload = json!({
+            "aps": { "alert": { "title": message.title, "body": message.body } },
+            "category": message.category,
+        });
+
+        let request = self
+            .http_client
+            .post(&url)
+            .bearer_auth(self.provider_token()?)
+            .header("apns-topic", &self.topic)
+            .header("apns-push-type", "alert")
+            .json(&payload);
+        let response = self
+            .http_client
+            .send(request)
+            .await
+            .map_err(|e| PushError::Failed(format!("APNs request failed: {}", e)))?;
+
+        let status = response.status();
+        if status.is_success() {
+            return Ok(());
+        }
+
+        let body = response.text().await.unwrap_or_default();
+        if status == StatusCode::GONE || body.contains("BadDeviceToken") || body.contains("Unregistered") {
+            return Err(PushError::InvalidToken);
+        }
+        Err(PushError::Failed(format!("APNs returned {}: {}", status, body)))
+    }
+}
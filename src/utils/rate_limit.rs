@@ -0,0 +1,96 @@
+use std::time::{Duration, Instant};
+use moka::sync::Cache;
+use crate::errors::app_error::AppError;
+
+/// Jumlah IP berbeda yang dilacak sekaligus
+const MAX_TRACKED_IPS: u64 = 100_000;
+
+/// Penghitung per key dengan jendela waktu tetap: hitungan kembali ke nol setelah
+/// `window` sejak hit pertama, meskipun key terus dipakai selama jendela berjalan.
+pub struct WindowCounter {
+    entries: Cache<String, (Instant, u32)>,
+    window: Duration,
+}
+
+impl WindowCounter {
+    pub fn new(window: Duration) -> Self {
+        WindowCounter {
+            // TTL hanya untuk membuang key yang tidak aktif; awal jendela disimpan sendiri
+            entries: Cache::builder()
+                .max_capacity(MAX_TRACKED_IPS)
+                .time_to_live(window)
+                .build(),
+            window,
+        }
+    }
+
+    /// Catat satu hit untuk `key` dan kembalikan jumlah hit di jendela saat ini
+    pub fn hit(&self, key: &str) -> u32 {
+        self.hit_at(key, Instant::now())
+    }
+
+    fn hit_at(&self, key: &str, now: Instant) -> u32 {
+        self.entries
+            .entry(key.to_string())
+            .and_upsert_with(|entry| match entry.map(|entry| entry.into_value()) {
+                Some((start, count)) if now.saturating_duration_since(start) < self.window => {
+                    (start, count.saturating_add(1))
+                }
+                _ => (now, 1),
+            })
+            .into_value()
+            .1
+    }
+}
+
+/// Batas request per IP dalam satu jendela waktu tetap, dihitung di memori per instance.
+/// Request tanpa IP (misalnya dari test in-process) tidak dibatasi.
+pub struct IpRateLimiter {
+    hits: WindowCounter,
+    limit: u32,
+}
+
+impl IpRateLimiter {
+    pub fn new(limit: u32, window: Duration) -> Self {
+        IpRateLimiter {
+            hits: WindowCounter::new(window),
+            limit,
+        }
+    }
+
+    /// Catat satu request dari `ip_address`; gagal dengan 429 jika batas sudah terlewati
+    pub fn check(&self, ip_address: Option<&str>) -> Result<(), AppError> {
+        let Some(ip) = ip_address else {
+            return Ok(());
+        };
+        if self.hits.hit(ip) > self.limit {
+            return Err(AppError::TooManyRequests("Too many requests, please try again later".to_string()));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blocks_after_limit_per_ip() {
+        let limiter = IpRateLimiter::new(2, Duration::from_secs(60));
+        assert!(limiter.check(Some("10.0.0.1")).is_ok());
+        assert!(limiter.check(Some("10.0.0.1")).is_ok());
+        assert!(matches!(limiter.check(Some("10.0.0.1")), Err(AppError::TooManyRequests(_))));
+        assert!(limiter.check(Some("10.0.0.2")).is_ok());
+        assert!(limiter.check(None).is_ok());
+    }
+
+    #[test]
+    fn window_resets_even_when_key_keeps_hitting() {
+        let counter = WindowCounter::new(Duration::from_secs(60));
+        let start = Instant::now();
+        assert_eq!(counter.hit_at("10.0.0.1", start), 1);
+        assert_eq!(counter.hit_at("10.0.0.1", start + Duration::from_secs(30)), 2);
+        assert_eq!(counter.hit_at("10.0.0.1", start + Duration::from_secs(59)), 3);
+        assert_eq!(counter.hit_at("10.0.0.1", start + Duration::from_secs(61)), 1);
+    }
+}
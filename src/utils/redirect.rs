@@ -0,0 +1,80 @@
+use url::{Origin, Url};
+use crate::config::app_config::AppConfig;
+use crate::errors::app_error::AppError;
+
+/// Halaman frontend default setelah login
+pub const DEFAULT_REDIRECT_PATH: &str = "/dashboard";
+
+/// Tujuan redirect ke frontend yang diizinkan: origin FRONTEND_URL ditambah FRONTEND_REDIRECT_WHITELIST.
+/// Mencegah open redirect lewat parameter `redirect_to`.
+pub struct RedirectWhitelist {
+    base: Url,
+    origins: Vec<Origin>,
+}
+
+impl RedirectWhitelist {
+    pub fn from_config(config: &AppConfig) -> Result<Self, String> {
+        let parse = |name: &str, value: &str| {
+            Url::parse(value)
+                .ok()
+                .filter(|url| matches!(url.scheme(), "http" | "https"))
+                .ok_or_else(|| format!("{} is not a valid http(s) URL: {}", name, value))
+        };
+
+        let base = parse("FRONTEND_URL", &config.frontend_url)?;
+        let mut origins = vec![base.origin()];
+        for entry in &config.frontend_redirect_whitelist {
+            origins.push(parse("FRONTEND_REDIRECT_WHITELIST entry", entry)?.origin());
+        }
+        Ok(RedirectWhitelist { base, origins })
+    }
+
+    /// URL tujuan redirect. `redirect_to` boleh path relatif terhadap FRONTEND_URL atau URL absolut
+    /// dengan origin yang diizinkan; kosong berarti halaman default.
+    pub fn resolve(&self, redirect_to: Option<&str>) -> Result<Url, AppError> {
+        let target = redirect_to
+            .map(str::trim)
+            .filter(|target| !target.is_empty())
+            .unwrap_or(DEFAULT_REDIRECT_PATH);
+
+        self.base
+            .join(target)
+            .ok()
+            .filter(|url| self.origins.contains(&url.origin()))
+            .ok_or_else(|| AppError::BadRequest("Redirect target is not allowed".to_string()))
+    }
+
+    pub fn default_target(&self) -> Url {
+        self.base.join(DEFAULT_REDIRECT_PATH).unwrap_or_else(|_| self.base.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_whitelisted_origins_are_allowed() {
+        let config = AppConfig {
+            frontend_url: "https://app.example.com".to_string(),
+            frontend_redirect_whitelist: vec!["http://localhost:5173".to_string()],
+            ..AppConfig::from_env()
+        };
+        let whitelist = RedirectWhitelist::from_config(&config).unwrap();
+
+        let resolve = |target| whitelist.resolve(target).map(|url| url.to_string()).ok();
+        assert_eq!(resolve(None).as_deref(), Some("https://app.example.com/dashboard"));
+        assert_eq!(resolve(Some("/journal/5?tab=mood")).as_deref(), Some("https://app.example.com/journal/5?tab=mood"));
+        assert_eq!(resolve(Some("http://localhost:5173/settings")).as_deref(), Some("http://localhost:5173/settings"));
+        assert_eq!(resolve(Some("https://evil.example.net/")), None);
+        assert_eq!(resolve(Some("//evil.example.net/dashboard")), None);
+        assert_eq!(resolve(Some("http://app.example.com/")), None);
+        assert_eq!(resolve(Some("javascript:alert(1)")), None);
+
+        let invalid = AppConfig {
+            frontend_redirect_whitelist: vec!["localhost".to_string()],
+            ..config
+        };
+        assert!(RedirectWhitelist::from_config(&invalid).is_err());
+    }
+}
@@ -0,0 +1,169 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use chrono::NaiveDateTime;
+use chrono_tz::Tz;
+use moka::{sync::Cache, Expiry};
+use serde::{de::DeserializeOwned, Serialize};
+use utoipa::ToSchema;
+use crate::errors::app_error::AppError;
+use crate::i18n::Locale;
+
+/// Jenis statistik yang di-cache per pengguna
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum StatsKind {
+    MoodCount,
+    MoodScores,
+    JournalCount,
+    /// Snapshot insight, per bahasa dan zona waktu karena berisi teks dan tanggal lokal.
+    /// Sengaja tidak ikut di-invalidate saat data berubah; dihitung ulang paling sering sekali per jam.
+    InsightSnapshot(Locale, Tz),
+}
+
+/// Umur snapshot insight di cache
+const INSIGHT_SNAPSHOT_TTL: Duration = Duration::from_secs(60 * 60);
+
+impl StatsKind {
+    /// Jenis yang bergantung langsung pada data pengguna dan di-invalidate saat data berubah
+    const ALL: [StatsKind; 3] = [StatsKind::MoodCount, StatsKind::MoodScores, StatsKind::JournalCount];
+
+    fn ttl(self, default_ttl: Duration) -> Duration {
+        match self {
+            StatsKind::InsightSnapshot(..) => INSIGHT_SNAPSHOT_TTL,
+            _ => default_ttl,
+        }
+    }
+}
+
+/// Masa berlaku entri per jenis statistik; menulis ulang entri memulai masa berlaku baru
+struct StatsExpiry {
+    default_ttl: Duration,
+}
+
+impl Expiry<(i32, StatsKind), CachedStats> for StatsExpiry {
+    fn expire_after_create(&self, key: &(i32, StatsKind), _value: &CachedStats, _created_at: Instant) -> Option<Duration> {
+        Some(key.1.ttl(self.default_ttl))
+    }
+
+    fn expire_after_update(
+        &self,
+        key: &(i32, StatsKind),
+        _value: &CachedStats,
+        _updated_at: Instant,
+        _duration_until_expiry: Option<Duration>,
+    ) -> Option<Duration> {
+        Some(key.1.ttl(self.default_ttl))
+    }
+}
+
+#[derive(Clone)]
+struct CachedStats {
+    /// `updated_at` terbaru saat nilai dihitung, dipakai untuk mendeteksi data yang berubah
+    version: Option<NaiveDateTime>,
+    value: serde_json::Value,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct CacheMetrics {
+    pub hits: u64,
+    pub misses: u64,
+    pub entries: u64,
+}
+
+/// Cache in-memory untuk endpoint statistik, dengan key (user id, jenis statistik).
+/// Entri dianggap basi jika `updated_at` terbaru pengguna berubah atau di-invalidate oleh service.
+pub struct StatsCache {
+    cache: Cache<(i32, StatsKind), CachedStats>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl StatsCache {
+    pub fn new(max_capacity: u64, ttl: Duration) -> Self {
+        StatsCache {
+            cache: Cache::builder()
+                .max_capacity(max_capacity)
+                .expire_after(StatsExpiry { default_ttl: ttl })
+                .build(),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Ambil statistik dari cache, atau hitung ulang dengan `compute` lalu simpan
+    pub fn get_or_compute<T, F>(
+        &self,
+        user_id: i32,
+        kind: StatsKind,
+        version: Option<NaiveDateTime>,
+        compute: F,
+    ) -> Result<T, AppError>
+    where
+        T: Serialize + DeserializeOwned,
+        F: FnOnce() -> Result<T, AppError>,
+    {
+        if let Some(cached) = self.cache.get(&(user_id, kind)) {
+            if cached.version == version {
+                if let Ok(value) = serde_json::from_value(cached.value) {
+                    self.hits.fetch_add(1, Ordering::Relaxed);
+                    return Ok(value);
+                }
+            }
+        }
+
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let value = compute()?;
+
+        if let Ok(json) = serde_json::to_value(&value) {
+            self.cache.insert((user_id, kind), CachedStats { version, value: json });
+        }
+
+        Ok(value)
+    }
+
+    /// Hapus semua statistik milik pengguna, dipanggil setelah create/update/delete
+    pub fn invalidate_user(&self, user_id: i32) {
+        for kind in StatsKind::ALL {
+            self.cache.invalidate(&(user_id, kind));
+        }
+    }
+
+    /// Hapus seluruh isi cache, misalnya setelah data dipulihkan dari backup
+    pub fn invalidate_all(&self) {
+        self.cache.invalidate_all();
+    }
+
+    pub fn metrics(&self) -> CacheMetrics {
+        // entry_count moka bersifat eventual, selesaikan dulu operasi yang tertunda
+        self.cache.run_pending_tasks();
+
+        CacheMetrics {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            entries: self.cache.entry_count(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalidate_user_keeps_insight_snapshot() {
+        let cache = StatsCache::new(100, Duration::from_secs(60));
+        let snapshot = StatsKind::InsightSnapshot(Locale::En, Tz::UTC);
+        cache.get_or_compute(1, StatsKind::MoodCount, None, || Ok(1)).unwrap();
+        cache.get_or_compute(1, snapshot, None, || Ok(1)).unwrap();
+
+        cache.invalidate_user(1);
+
+        assert_eq!(cache.get_or_compute(1, StatsKind::MoodCount, None, || Ok(2)).unwrap(), 2);
+        assert_eq!(cache.get_or_compute(1, snapshot, None, || Ok(2)).unwrap(), 1);
+    }
+
+    #[test]
+    fn insight_snapshot_lives_for_an_hour() {
+        assert_eq!(StatsKind::InsightSnapshot(Locale::Id, Tz::UTC).ttl(Duration::from_secs(60)), INSIGHT_SNAPSHOT_TTL);
+        assert_eq!(StatsKind::MoodScores.ttl(Duration::from_secs(60)), Duration::from_secs(60));
+    }
+}
@@ -0,0 +1,61 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use crate::errors::app_error::AppError;
+
+/// Penyimpanan file lampiran (avatar, export). Implementasi bisa diganti (S3, GCS) tanpa mengubah service.
+pub trait Storage: Send + Sync {
+    fn put(&self, key: &str, data: &[u8]) -> Result<(), AppError>;
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, AppError>;
+    fn delete(&self, key: &str) -> Result<(), AppError>;
+}
+
+/// Storage default yang menyimpan file di direktori lokal
+pub struct LocalStorage {
+    root: PathBuf,
+}
+
+impl LocalStorage {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        LocalStorage { root: root.into() }
+    }
+
+    /// Key berupa path relatif seperti `avatars/1/abc.jpg`; `..` dan path absolut ditolak
+    fn path_for(&self, key: &str) -> Result<PathBuf, AppError> {
+        let valid = !key.is_empty()
+            && !key.starts_with('/')
+            && key.split('/').all(|part| !part.is_empty() && part != "." && part != "..");
+        if !valid {
+            return Err(AppError::InternalServerError(format!("Invalid storage key: {}", key)));
+        }
+        Ok(self.root.join(key))
+    }
+}
+
+impl Storage for LocalStorage {
+    fn put(&self, key: &str, data: &[u8]) -> Result<(), AppError> {
+        let path = self.path_for(key)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| AppError::InternalServerError(format!("Failed to create storage directory: {}", e)))?;
+        }
+        fs::write(&path, data)
+            .map_err(|e| AppError::InternalServerError(format!("Failed to write file: {}", e)))
+    }
+
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, AppError> {
+        match fs::read(self.path_for(key)?) {
+            Ok(data) => Ok(Some(data)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(AppError::InternalServerError(format!("Failed to read file: {}", e))),
+        }
+    }
+
+    fn delete(&self, key: &str) -> Result<(), AppError> {
+        match fs::remove_file(self.path_for(key)?) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(AppError::InternalServerError(format!("Failed to delete file: {}", e))),
+        }
+    }
+}
@@ -0,0 +1,275 @@
+use std::collections::BTreeSet;
+use chrono::{NaiveDate, NaiveDateTime};
+use chrono_tz::Tz;
+use crate::config::app_config::app_config;
+use crate::utils::timezone::{local_date, today_in};
+
+/// Rangkaian hari berturut-turut yang punya catatan
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Streak {
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub length: i32,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StreakStats {
+    /// Streak yang masih berjalan, `None` jika sudah putus
+    pub current: Option<Streak>,
+    /// Streak terpanjang; jika ada yang sama panjang, yang paling awal dipilih
+    pub longest: Option<Streak>,
+}
+
+impl StreakStats {
+    pub fn current_length(&self) -> i32 {
+        self.current.map_or(0, |streak| streak.length)
+    }
+
+    pub fn longest_length(&self) -> i32 {
+        self.longest.map_or(0, |streak| streak.length)
+    }
+}
+
+/// Perhitungan streak untuk semua jenis catatan (mood, jurnal, kebiasaan) per tanggal
+/// lokal pengguna. Streak berjalan tetap hidup selama catatan terakhir tidak lebih dari
+/// `grace_days` hari sebelum hari ini; default 1 berarti hari ini yang belum dicatat
+/// tidak memutus streak. Tanggal duplikat digabung dan tanggal setelah hari ini diabaikan.
+#[derive(Debug, Clone, Copy)]
+pub struct StreakEngine {
+    tz: Tz,
+    today: NaiveDate,
+    grace_days: i64,
+}
+
+impl StreakEngine {
+    /// Engine untuk hari ini di zona waktu pengguna dengan STREAK_GRACE_DAYS
+    pub fn new(tz: Tz) -> Self {
+        Self::at(tz, today_in(tz), app_config().streak_grace_days)
+    }
+
+    pub fn at(tz: Tz, today: NaiveDate, grace_days: i64) -> Self {
+        StreakEngine {
+            tz,
+            today,
+            grace_days: grace_days.max(0),
+        }
+    }
+
+    /// Streak dari tanggal lokal, misalnya tanggal mood
+    pub fn summarize<I>(&self, dates: I) -> StreakStats
+    where
+        I: IntoIterator<Item = NaiveDate>,
+    {
+        let dates: BTreeSet<NaiveDate> = dates.into_iter().filter(|date| *date <= self.today).collect();
+        let runs = runs(&dates);
+
+        let current = runs
+            .last()
+            .copied()
+            .filter(|streak| (self.today - streak.end_date).num_days() <= self.grace_days);
+        let longest = runs.into_iter().reduce(|longest, streak| {
+            if streak.length > longest.length { streak } else { longest }
+        });
+
+        StreakStats { current, longest }
+    }
+
+    /// Streak dari timestamp UTC di database, misalnya `created_at` jurnal
+    pub fn summarize_timestamps<I>(&self, timestamps: I) -> StreakStats
+    where
+        I: IntoIterator<Item = NaiveDateTime>,
+    {
+        self.summarize(timestamps.into_iter().map(|timestamp| local_date(timestamp, self.tz)))
+    }
+}
+
+/// Pecah tanggal terurut menjadi rangkaian hari berturut-turut
+fn runs(dates: &BTreeSet<NaiveDate>) -> Vec<Streak> {
+    let mut runs: Vec<Streak> = Vec::new();
+    for &date in dates {
+        match runs.last_mut() {
+            Some(streak) if streak.end_date.succ_opt() == Some(date) => {
+                streak.end_date = date;
+                streak.length += 1;
+            }
+            _ => runs.push(Streak {
+                start_date: date,
+                end_date: date,
+                length: 1,
+            }),
+        }
+    }
+    runs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use proptest::prelude::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn today() -> NaiveDate {
+        date(2025, 3, 15)
+    }
+
+    fn days_ago(days: i64) -> NaiveDate {
+        today() - Duration::days(days)
+    }
+
+    fn engine() -> StreakEngine {
+        StreakEngine::at(Tz::UTC, today(), 1)
+    }
+
+    fn current<I: IntoIterator<Item = NaiveDate>>(dates: I) -> Option<Streak> {
+        engine().summarize(dates).current
+    }
+
+    fn longest<I: IntoIterator<Item = NaiveDate>>(dates: I) -> Option<Streak> {
+        engine().summarize(dates).longest
+    }
+
+    /// Tanggal dalam kurang lebih 60 hari sebelum dan 5 hari setelah `today`
+    fn dates() -> impl Strategy<Value = Vec<NaiveDate>> {
+        prop::collection::vec(-5i64..60, 0..80).prop_map(|offsets| offsets.into_iter().map(days_ago).collect())
+    }
+
+    /// Tanggal asli dan salinannya yang setiap tanggalnya muncul dua kali dalam urutan acak
+    fn duplicated_and_shuffled() -> impl Strategy<Value = (Vec<NaiveDate>, Vec<NaiveDate>)> {
+        dates().prop_flat_map(|dates| {
+            let doubled: Vec<NaiveDate> = dates.iter().chain(dates.iter()).copied().collect();
+            (Just(dates), Just(doubled).prop_shuffle())
+        })
+    }
+
+    /// Referensi sederhana: cari catatan terakhir dalam masa tenggang, lalu mundur hari demi hari
+    fn naive_current(dates: &[NaiveDate], today: NaiveDate, grace_days: i64) -> i32 {
+        let has = |day: NaiveDate| dates.contains(&day);
+        let Some(mut day) = (0..=grace_days).map(|days| today - Duration::days(days)).find(|day| has(*day)) else {
+            return 0;
+        };
+        let mut length = 0;
+        while has(day) {
+            length += 1;
+            day -= Duration::days(1);
+        }
+        length
+    }
+
+    #[test]
+    fn empty_has_no_streak() {
+        assert_eq!(engine().summarize(Vec::new()), StreakStats::default());
+    }
+
+    #[test]
+    fn streak_ending_today() {
+        let streak = current([days_ago(2), days_ago(1), days_ago(0)]).unwrap();
+        assert_eq!(streak.length, 3);
+        assert_eq!(streak.start_date, days_ago(2));
+        assert_eq!(streak.end_date, today());
+    }
+
+    #[test]
+    fn yesterday_keeps_the_streak_alive() {
+        let streak = current([days_ago(2), days_ago(1)]).unwrap();
+        assert_eq!(streak.length, 2);
+        assert_eq!(streak.end_date, days_ago(1));
+    }
+
+    #[test]
+    fn two_days_ago_breaks_the_streak() {
+        let stats = engine().summarize([days_ago(3), days_ago(2)]);
+        assert_eq!(stats.current, None);
+        assert_eq!(stats.longest_length(), 2);
+    }
+
+    #[test]
+    fn grace_days_are_configurable() {
+        let dates = [days_ago(3), days_ago(2)];
+        assert_eq!(StreakEngine::at(Tz::UTC, today(), 2).summarize(dates).current_length(), 2);
+        assert_eq!(StreakEngine::at(Tz::UTC, today(), 0).summarize([days_ago(1)]).current, None);
+        assert_eq!(StreakEngine::at(Tz::UTC, today(), -3).summarize([today()]).current_length(), 1);
+    }
+
+    #[test]
+    fn future_dates_are_ignored() {
+        assert_eq!(current([days_ago(-1), days_ago(0)]).unwrap().length, 1);
+        assert_eq!(engine().summarize([days_ago(-1), days_ago(-2)]), StreakStats::default());
+    }
+
+    #[test]
+    fn longest_prefers_earliest_on_tie() {
+        let streak = longest([days_ago(10), days_ago(9), days_ago(3), days_ago(2)]).unwrap();
+        assert_eq!(streak.start_date, days_ago(10));
+        assert_eq!(streak.length, 2);
+    }
+
+    #[test]
+    fn streak_crosses_month_and_leap_day() {
+        let dates = [date(2024, 2, 28), date(2024, 2, 29), date(2024, 3, 1)];
+        let stats = StreakEngine::at(Tz::UTC, date(2024, 3, 2), 1).summarize(dates);
+        assert_eq!(stats.current_length(), 3);
+        assert_eq!(stats.longest_length(), 3);
+    }
+
+    #[test]
+    fn timezone_decides_which_day_counts() {
+        // 23:30 UTC tanggal 14 sudah tanggal 15 di Jakarta (UTC+7)
+        let evening = date(2025, 3, 13).and_hms_opt(23, 30, 0).unwrap();
+        let late_evening = date(2025, 3, 14).and_hms_opt(23, 30, 0).unwrap();
+        let jakarta: Tz = "Asia/Jakarta".parse().unwrap();
+
+        let stats = StreakEngine::at(jakarta, today(), 0).summarize_timestamps([evening, late_evening]);
+        assert_eq!(stats.current_length(), 2);
+
+        let stats = StreakEngine::at(Tz::UTC, today(), 0).summarize_timestamps([evening, late_evening]);
+        assert_eq!(stats.current, None);
+        assert_eq!(stats.longest_length(), 2);
+    }
+
+    proptest! {
+        #[test]
+        fn current_matches_day_by_day_walk(dates in dates(), grace_days in 0i64..4) {
+            let expected = naive_current(&dates, today(), grace_days);
+            let actual = StreakEngine::at(Tz::UTC, today(), grace_days).summarize(dates).current_length();
+            prop_assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn duplicates_and_order_do_not_matter((dates, shuffled) in duplicated_and_shuffled()) {
+            prop_assert_eq!(engine().summarize(shuffled), engine().summarize(dates));
+        }
+
+        #[test]
+        fn streaks_are_maximal_runs(dates in dates()) {
+            let past: Vec<NaiveDate> = dates.iter().copied().filter(|date| *date <= today()).collect();
+            let stats = engine().summarize(dates);
+            prop_assert_eq!(stats.longest.is_none(), past.is_empty());
+
+            for streak in stats.current.into_iter().chain(stats.longest) {
+                prop_assert_eq!(streak.length as i64, (streak.end_date - streak.start_date).num_days() + 1);
+                let mut day = streak.start_date;
+                while day <= streak.end_date {
+                    prop_assert!(past.contains(&day));
+                    day += Duration::days(1);
+                }
+                prop_assert!(!past.contains(&(streak.start_date - Duration::days(1))));
+                prop_assert!(!past.contains(&(streak.end_date + Duration::days(1))));
+            }
+            prop_assert!(stats.current_length() <= stats.longest_length());
+        }
+
+        #[test]
+        fn timestamps_use_the_local_date(hours in 0i64..(24 * 60), offset_hours in -11i32..14) {
+            let tz: Tz = format!("Etc/GMT{:+}", -offset_hours).parse().unwrap();
+            let utc = NaiveDateTime::from(days_ago(60)) + Duration::hours(hours);
+            let local = (utc + Duration::hours(offset_hours.into())).date();
+
+            let stats = StreakEngine::at(tz, today(), 1).summarize_timestamps([utc]);
+            prop_assert_eq!(stats.longest.map(|streak| streak.end_date), Some(local).filter(|date| *date <= today()));
+        }
+    }
+}
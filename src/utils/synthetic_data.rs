@@ -0,0 +1,138 @@
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Weekday};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use crate::models::journal::NewJournal;
+use crate::models::mood::{MoodType, NewMood};
+
+const LOREM_WORDS: &[&str] = &[
+    "lorem", "ipsum", "dolor", "sit", "amet", "consectetur", "adipiscing", "elit", "sed", "do",
+    "eiusmod", "tempor", "incididunt", "ut", "labore", "et", "dolore", "magna", "aliqua", "enim",
+    "ad", "minim", "veniam", "quis", "nostrud", "exercitation", "ullamco", "laboris", "nisi",
+    "aliquip", "ex", "ea", "commodo", "consequat", "duis", "aute", "irure", "in", "reprehenderit",
+    "voluptate", "velit", "esse", "cillum", "fugiat", "nulla", "pariatur", "excepteur", "sint",
+    "occaecat", "cupidatat", "non", "proident", "sunt", "culpa", "qui", "officia", "deserunt",
+    "mollit", "anim", "id", "est", "laborum",
+];
+
+const JOURNAL_TITLES: &[&str] = &[
+    "Catatan hari ini",
+    "Refleksi malam",
+    "Hal yang aku syukuri",
+    "Minggu yang panjang",
+    "Pikiran acak",
+    "Tentang pekerjaan",
+    "Waktu bersama keluarga",
+    "Target minggu depan",
+];
+
+const NOTES: &[&str] = &[
+    "Tidur cukup semalam",
+    "Banyak tugas kantor",
+    "Olahraga pagi",
+    "Ngobrol dengan teman",
+    "Kurang tidur",
+    "Cuaca mendung seharian",
+    "Makan enak",
+    "Sedikit cemas soal besok",
+];
+
+/// Mood dan jurnal buatan untuk data development, belum disimpan ke database
+pub struct SyntheticHistory {
+    pub moods: Vec<NewMood>,
+    pub journals: Vec<NewJournal>,
+}
+
+/// Riwayat buatan untuk `user_id` dari `start` sampai `end` (inklusif). Setiap pengguna punya
+/// baseline mood, tingkat kedisiplinan mencatat dan naik-turun mood yang berkelanjutan
+/// (random walk), dengan mood akhir pekan sedikit lebih baik, agar grafik terlihat realistis.
+pub fn generate_history<R: Rng>(
+    rng: &mut R,
+    user_id: i32,
+    start: NaiveDate,
+    end: NaiveDate,
+    journals_per_week: f64,
+) -> SyntheticHistory {
+    let baseline: f64 = rng.gen_range(2.6..4.0);
+    let adherence: f64 = rng.gen_range(0.55..0.95);
+    let journal_chance = (journals_per_week / 7.0).clamp(0.0, 1.0);
+
+    let mut moods = Vec::new();
+    let mut journals = Vec::new();
+    let mut drift = 0.0;
+    let mut date = start;
+    while date <= end {
+        drift = drift * 0.8 + noise(rng) * 0.5;
+        let weekend_bonus = if matches!(date.weekday(), Weekday::Sat | Weekday::Sun) { 0.3 } else { 0.0 };
+
+        if rng.gen_bool(adherence) {
+            let score = (baseline + drift + weekend_bonus + noise(rng) * 0.4).round().clamp(1.0, 5.0) as usize;
+            let mood_type = &MoodType::ALL[score - 1];
+            let created_at = evening_time(rng, date);
+            moods.push(NewMood {
+                user_id,
+                date,
+                mood: mood_type.as_str().to_string(),
+                emoji: mood_type.default_emoji().to_string(),
+                notes: rng.gen_bool(0.3).then(|| NOTES.choose(rng).copied().unwrap_or_default().to_string()),
+                created_at,
+                updated_at: Some(created_at),
+                details: None,
+                place_label: None,
+                latitude: None,
+                longitude: None,
+            });
+        }
+
+        if rng.gen_bool(journal_chance) {
+            let created_at = evening_time(rng, date);
+            let paragraphs = rng.gen_range(1..=4);
+            journals.push(NewJournal {
+                user_id,
+                title: JOURNAL_TITLES.choose(rng).copied().unwrap_or_default().to_string(),
+                content: lorem_paragraphs(rng, paragraphs),
+                created_at,
+                updated_at: Some(created_at),
+            });
+        }
+
+        date += Duration::days(1);
+    }
+
+    SyntheticHistory { moods, journals }
+}
+
+/// Paragraf lorem ipsum dipisah baris kosong
+pub fn lorem_paragraphs<R: Rng>(rng: &mut R, paragraphs: usize) -> String {
+    (0..paragraphs)
+        .map(|_| {
+            (0..rng.gen_range(2..=5))
+                .map(|_| lorem_sentence(rng))
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+fn lorem_sentence<R: Rng>(rng: &mut R) -> String {
+    let words: Vec<&str> = (0..rng.gen_range(6..=14))
+        .map(|_| LOREM_WORDS.choose(rng).copied().unwrap_or("lorem"))
+        .collect();
+    let sentence = words.join(" ");
+    let mut chars = sentence.chars();
+    match chars.next() {
+        Some(first) => format!("{}{}.", first.to_uppercase(), chars.as_str()),
+        None => String::new(),
+    }
+}
+
+/// Kira-kira berdistribusi normal dengan rata-rata 0 dan simpangan baku 1
+fn noise<R: Rng>(rng: &mut R) -> f64 {
+    (0..12).map(|_| rng.gen::<f64>()).sum::<f64>() - 6.0
+}
+
+/// Waktu pencatatan acak antara 18:00 dan 23:59
+fn evening_time<R: Rng>(rng: &mut R, date: NaiveDate) -> NaiveDateTime {
+    let seconds = rng.gen_range(18 * 3600..24 * 3600);
+    date.and_time(NaiveTime::MIN) + Duration::seconds(seconds)
+}
@@ -0,0 +1,9 @@
+use crate::errors::app_error::AppError;
+
+/// Tolak teks yang lebih panjang dari `max` karakter sebelum sampai ke database
+pub fn ensure_max_length(field: &str, value: &str, max: usize) -> Result<(), AppError> {
+    if value.chars().count() > max {
+        return Err(AppError::PayloadTooLarge(format!("{} must be at most {} characters", field, max)));
+    }
+    Ok(())
+}
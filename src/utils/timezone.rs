@@ -0,0 +1,33 @@
+use chrono::{NaiveDate, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+
+/// Parse nama zona waktu IANA, misalnya "Asia/Jakarta"
+pub fn parse_timezone(name: &str) -> Option<Tz> {
+    name.trim().parse::<Tz>().ok()
+}
+
+/// Tanggal "hari ini" menurut zona waktu pengguna
+pub fn today_in(tz: Tz) -> NaiveDate {
+    Utc::now().with_timezone(&tz).date_naive()
+}
+
+/// Awal hari (00:00 waktu lokal) dalam UTC, sesuai format kolom timestamp di database
+pub fn day_start_utc(date: NaiveDate, tz: Tz) -> NaiveDateTime {
+    let local_midnight = date.and_hms_opt(0, 0, 0).unwrap_or_default();
+    tz.from_local_datetime(&local_midnight)
+        .earliest()
+        .map(|dt| dt.naive_utc())
+        .unwrap_or(local_midnight)
+}
+
+/// Rentang UTC [awal start_date, awal hari setelah end_date) untuk filter timestamp
+pub fn day_range_utc(start_date: NaiveDate, end_date: NaiveDate, tz: Tz) -> (NaiveDateTime, NaiveDateTime) {
+    let next_day = end_date.succ_opt().unwrap_or(end_date);
+    (day_start_utc(start_date, tz), day_start_utc(next_day, tz))
+}
+
+
+/// Tanggal lokal pengguna untuk timestamp UTC yang disimpan di database
+pub fn local_date(datetime: NaiveDateTime, tz: Tz) -> NaiveDate {
+    Utc.from_utc_datetime(&datetime).with_timezone(&tz).date_naive()
+}
@@ -0,0 +1,107 @@
+use std::sync::OnceLock;
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use base64::{Engine as _, engine::general_purpose::STANDARD};
+use crate::config::app_config::{app_config, AppConfig};
+use crate::errors::app_error::AppError;
+
+/// Prefix versi format, agar algoritma atau kunci bisa diganti tanpa kehilangan data lama
+const FORMAT_PREFIX: &str = "v1:";
+const NONCE_LENGTH: usize = 12;
+
+/// Enkripsi token penyedia OAuth (AES-256-GCM) sebelum disimpan di database.
+/// Hasilnya `v1:<base64(nonce || ciphertext)>`.
+pub struct TokenCipher {
+    cipher: Aes256Gcm,
+}
+
+impl TokenCipher {
+    /// `None` jika OAUTH_TOKEN_ENCRYPTION_KEY tidak diisi; kunci harus 32 byte dalam base64
+    pub fn from_config(config: &AppConfig) -> Result<Option<Self>, String> {
+        let Some(ref encoded) = config.oauth_token_encryption_key else {
+            return Ok(None);
+        };
+        let key = STANDARD
+            .decode(encoded.trim())
+            .ok()
+            .filter(|key| key.len() == 32)
+            .ok_or_else(|| "OAUTH_TOKEN_ENCRYPTION_KEY must be 32 bytes encoded as base64".to_string())?;
+        Ok(Some(TokenCipher {
+            cipher: Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key)),
+        }))
+    }
+
+    pub fn encrypt(&self, plaintext: &str) -> Result<String, AppError> {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .cipher
+            .encrypt(&nonce, plaintext.as_bytes())
+            .map_err(|_| AppError::InternalServerError("Failed to encrypt token".to_string()))?;
+
+        let mut payload = nonce.to_vec();
+        payload.extend_from_slice(&ciphertext);
+        Ok(format!("{}{}", FORMAT_PREFIX, STANDARD.encode(payload)))
+    }
+
+    pub fn decrypt(&self, value: &str) -> Result<String, AppError> {
+        let invalid = || AppError::InternalServerError("Failed to decrypt token".to_string());
+        let payload = value
+            .strip_prefix(FORMAT_PREFIX)
+            .and_then(|encoded| STANDARD.decode(encoded).ok())
+            .filter(|payload| payload.len() > NONCE_LENGTH)
+            .ok_or_else(invalid)?;
+
+        let (nonce, ciphertext) = payload.split_at(NONCE_LENGTH);
+        let plaintext = self
+            .cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| invalid())?;
+        String::from_utf8(plaintext).map_err(|_| invalid())
+    }
+}
+
+/// Cipher dari konfigurasi global; `None` berarti token penyedia tidak disimpan
+pub fn token_cipher() -> Option<&'static TokenCipher> {
+    static CIPHER: OnceLock<Option<TokenCipher>> = OnceLock::new();
+    CIPHER
+        .get_or_init(|| {
+            TokenCipher::from_config(app_config()).unwrap_or_else(|e| {
+                eprintln!("❌ {}; OAuth provider tokens will not be stored", e);
+                None
+            })
+        })
+        .as_ref()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encrypts_and_decrypts_tokens() {
+        let config = AppConfig {
+            oauth_token_encryption_key: Some(STANDARD.encode([7u8; 32])),
+            ..AppConfig::from_env()
+        };
+        let cipher = TokenCipher::from_config(&config).unwrap().unwrap();
+
+        let encrypted = cipher.encrypt("1//refresh-token").unwrap();
+        assert!(encrypted.starts_with(FORMAT_PREFIX));
+        assert!(!encrypted.contains("refresh-token"));
+        assert_ne!(encrypted, cipher.encrypt("1//refresh-token").unwrap());
+        assert_eq!(cipher.decrypt(&encrypted).unwrap(), "1//refresh-token");
+
+        let other_key = AppConfig {
+            oauth_token_encryption_key: Some(STANDARD.encode([8u8; 32])),
+            ..AppConfig::from_env()
+        };
+        let other = TokenCipher::from_config(&other_key).unwrap().unwrap();
+        assert!(other.decrypt(&encrypted).is_err());
+
+        let short_key = AppConfig {
+            oauth_token_encryption_key: Some(STANDARD.encode([7u8; 16])),
+            ..config
+        };
+        assert!(TokenCipher::from_config(&short_key).is_err());
+    }
+}
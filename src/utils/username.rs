@@ -0,0 +1,46 @@
+use crate::errors::app_error::AppError;
+
+pub const USERNAME_MIN_LENGTH: usize = 3;
+pub const USERNAME_MAX_LENGTH: usize = 30;
+
+/// Nama yang tidak boleh dipakai pengguna karena bisa disalahartikan sebagai akun resmi
+const RESERVED_USERNAMES: &[&str] = &[
+    "admin", "administrator", "root", "system", "support", "help", "helpdesk",
+    "mindmate", "official", "staff", "moderator", "mod", "security", "api",
+    "www", "mail", "email", "noreply", "no-reply", "null", "undefined",
+    "anonymous", "guest", "user", "me", "settings", "login", "logout", "register",
+    "psychologist", "counselor",
+];
+
+/// Bentuk baku username: tanpa spasi di awal/akhir dan huruf kecil semua
+pub fn normalize_username(raw: &str) -> String {
+    raw.trim().to_lowercase()
+}
+
+pub fn is_reserved_username(normalized: &str) -> bool {
+    RESERVED_USERNAMES.contains(&normalized)
+}
+
+/// Validasi username yang sudah dinormalisasi: panjang, karakter yang diizinkan dan nama terlarang
+pub fn validate_username(normalized: &str) -> Result<(), AppError> {
+    let length = normalized.chars().count();
+    if !(USERNAME_MIN_LENGTH..=USERNAME_MAX_LENGTH).contains(&length) {
+        return Err(AppError::BadRequest(format!(
+            "Username must be between {} and {} characters long",
+            USERNAME_MIN_LENGTH, USERNAME_MAX_LENGTH
+        )));
+    }
+
+    let allowed = |c: char| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '.' || c == '-';
+    if !normalized.chars().all(allowed) {
+        return Err(AppError::BadRequest(
+            "Username may only contain letters, digits, '_', '.' and '-'".to_string(),
+        ));
+    }
+
+    if is_reserved_username(normalized) {
+        return Err(AppError::BadRequest("Username is reserved".to_string()));
+    }
+
+    Ok(())
+}
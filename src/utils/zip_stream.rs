@@ -0,0 +1,127 @@
+use std::io::Write;
+use axum::body::{Body, Bytes};
+use chrono::NaiveDateTime;
+use tokio::sync::mpsc;
+use zip::write::{SimpleFileOptions, StreamWriter};
+use zip::{CompressionMethod, DateTime, ZipWriter};
+use crate::errors::app_error::AppError;
+
+/// Ukuran potongan body yang dikirim ke klien sekaligus
+const CHUNK_BYTES: usize = 64 * 1024;
+/// Jumlah potongan yang boleh mengantre sebelum producer menunggu klien
+const CHANNEL_CAPACITY: usize = 4;
+
+type Chunk = Result<Bytes, std::io::Error>;
+
+/// Penulis arsip ZIP untuk `stream_zip`. Arsip ditulis oleh crate `zip` (ZIP64 dipakai
+/// otomatis saat jumlah entri atau ukurannya melewati batas ZIP biasa) dan dikirim ke
+/// klien per potongan, jadi hanya satu file yang ditampung pada satu waktu.
+pub struct ZipStreamWriter {
+    zip: ZipWriter<StreamWriter<ChannelWriter>>,
+}
+
+impl ZipStreamWriter {
+    pub fn add_file(&mut self, name: &str, modified: NaiveDateTime, content: &[u8]) -> Result<(), AppError> {
+        let options = SimpleFileOptions::default()
+            .compression_method(CompressionMethod::Deflated)
+            .last_modified_time(DateTime::try_from(modified).unwrap_or_default())
+            .large_file(content.len() as u64 >= u32::MAX as u64);
+
+        self.zip
+            .start_file(name, options)
+            .map_err(|e| AppError::InternalServerError(format!("Failed to write ZIP entry: {}", e)))?;
+        self.zip
+            .write_all(content)
+            .map_err(|e| AppError::InternalServerError(format!("Failed to write ZIP entry: {}", e)))
+    }
+
+    /// Tulis central directory di akhir arsip lalu kirim sisa buffer
+    fn finish(self) -> Result<(), AppError> {
+        let mut channel = self
+            .zip
+            .finish()
+            .map_err(|e| AppError::InternalServerError(format!("Failed to finish ZIP archive: {}", e)))?
+            .into_inner();
+        channel
+            .send_buffer()
+            .map_err(|e| AppError::InternalServerError(e.to_string()))
+    }
+}
+
+/// `Write` yang menampung byte lalu mengirimnya ke body per `CHUNK_BYTES`
+struct ChannelWriter {
+    sender: mpsc::Sender<Chunk>,
+    buffer: Vec<u8>,
+}
+
+impl ChannelWriter {
+    fn send_buffer(&mut self) -> std::io::Result<()> {
+        let chunk = Bytes::from(std::mem::take(&mut self.buffer));
+        self.sender
+            .blocking_send(Ok(chunk))
+            .map_err(|_| std::io::Error::new(std::io::ErrorKind::BrokenPipe, "Client disconnected"))
+    }
+}
+
+impl Write for ChannelWriter {
+    fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
+        self.buffer.extend_from_slice(bytes);
+        if self.buffer.len() >= CHUNK_BYTES {
+            self.send_buffer()?;
+        }
+        Ok(bytes.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Body ZIP yang diisi bertahap oleh `produce` di thread blocking, dengan perilaku
+/// yang sama seperti `stream_json_array`: jika gagal di tengah jalan koneksi diputus
+/// dan klien menerima arsip yang tidak lengkap.
+pub fn stream_zip<F>(produce: F) -> Body
+where
+    F: FnOnce(&mut ZipStreamWriter) -> Result<(), AppError> + Send + 'static,
+{
+    let (sender, mut receiver) = mpsc::channel::<Chunk>(CHANNEL_CAPACITY);
+
+    tokio::task::spawn_blocking(move || {
+        let mut writer = ZipStreamWriter {
+            zip: ZipWriter::new_stream(ChannelWriter {
+                sender: sender.clone(),
+                buffer: Vec::with_capacity(CHUNK_BYTES),
+            }),
+        };
+
+        if let Err(e) = produce(&mut writer).and_then(|_| writer.finish()) {
+            eprintln!("❌ Streaming response aborted: {}", e);
+            let _ = sender.blocking_send(Err(std::io::Error::other(e.to_string())));
+        }
+    });
+
+    Body::from_stream(futures_util::stream::poll_fn(move |cx| receiver.poll_recv(cx)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn archive_with_more_than_u16_max_entries_is_readable() {
+        let modified = NaiveDateTime::parse_from_str("2025-01-02 03:04:05", "%Y-%m-%d %H:%M:%S").unwrap();
+        let body = stream_zip(move |zip| {
+            for i in 0..(u16::MAX as usize + 10) {
+                zip.add_file(&format!("{}.md", i), modified, b"isi")?;
+            }
+            Ok(())
+        });
+
+        let bytes = axum::body::to_bytes(body, usize::MAX).await.unwrap();
+        let mut archive = zip::ZipArchive::new(std::io::Cursor::new(bytes)).unwrap();
+        assert_eq!(archive.len(), u16::MAX as usize + 10);
+        let mut content = String::new();
+        std::io::Read::read_to_string(&mut archive.by_name("65540.md").unwrap(), &mut content).unwrap();
+        assert_eq!(content, "isi");
+    }
+}
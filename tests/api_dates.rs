@@ -0,0 +1,115 @@
+//! Kontrak format tanggal tiap endpoint: hanya YYYY-MM-DD (dan YYYY-MM untuk bulan)
+//! yang diterima, dan semua tanggal di response memakai format yang sama.
+//! Tanggal salah di body JSON ditolak extractor `Json` (422), di path/query 400.
+mod common;
+
+use axum::http::StatusCode;
+use serde_json::{json, Value};
+use common::TestApp;
+
+fn is_iso_date(value: &Value) -> bool {
+    value.as_str().is_some_and(|value| {
+        value.len() == 10 && chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d").is_ok()
+    })
+}
+
+#[tokio::test]
+async fn mood_dates() {
+    let app = TestApp::spawn().await;
+    let token = app.register_and_login("mood_dates").await;
+
+    let created = app
+        .post("/api/moods", Some(&token), json!({ "mood": "happy", "date": "2025-07-03" }))
+        .await;
+    assert_eq!(created.status, StatusCode::OK, "{}", created.body);
+    assert_eq!(created.body["date"], "2025-07-03");
+
+    for date in ["07-04-2025", "2025/07/04", "2025-7-4", "2025-02-30", "2025-07-04T00:00:00"] {
+        let rejected = app
+            .post("/api/moods", Some(&token), json!({ "mood": "happy", "date": date }))
+            .await;
+        assert_eq!(rejected.status, StatusCode::UNPROCESSABLE_ENTITY, "{}: {}", date, rejected.body);
+    }
+
+    let by_date = app.get("/api/moods/date/2025-07-03", Some(&token)).await;
+    assert_eq!(by_date.status, StatusCode::OK, "{}", by_date.body);
+    assert_eq!(by_date.body["date"], "2025-07-03");
+
+    let legacy_path = app.get("/api/moods/date/07-03-2025", Some(&token)).await;
+    assert_eq!(legacy_path.status, StatusCode::BAD_REQUEST);
+
+    let range = app
+        .get("/api/moods/range?start_date=2025-07-01&end_date=2025-07-31", Some(&token))
+        .await;
+    assert_eq!(range.status, StatusCode::OK, "{}", range.body);
+    let moods = range.body.as_array().expect("range returns an array");
+    assert_eq!(moods.len(), 1);
+    assert!(moods.iter().all(|mood| is_iso_date(&mood["date"])));
+
+    let legacy_range = app
+        .get("/api/moods/range?start_date=07-01-2025&end_date=07-31-2025", Some(&token))
+        .await;
+    assert_eq!(legacy_range.status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn journal_dates() {
+    let app = TestApp::spawn().await;
+    let token = app.register_and_login("journal_dates").await;
+
+    let created = app
+        .post(
+            "/api/journals",
+            Some(&token),
+            json!({ "title": "Dated", "content": "Backfilled entry", "created_at": "2025-07-03" }),
+        )
+        .await;
+    assert_eq!(created.status, StatusCode::OK, "{}", created.body);
+
+    let legacy = app
+        .post(
+            "/api/journals",
+            Some(&token),
+            json!({ "title": "Legacy", "content": "Old client", "created_at": "07-03-2025" }),
+        )
+        .await;
+    assert_eq!(legacy.status, StatusCode::UNPROCESSABLE_ENTITY, "{}", legacy.body);
+
+    let by_date = app.get("/api/journals/date/2025-07-03", Some(&token)).await;
+    assert_eq!(by_date.status, StatusCode::OK, "{}", by_date.body);
+
+    let range = app
+        .get("/api/journals/range?start_date=2025-07-01&end_date=2025-07-31", Some(&token))
+        .await;
+    assert_eq!(range.status, StatusCode::OK, "{}", range.body);
+
+    let legacy_range = app
+        .get("/api/journals/range?start_date=07-01-2025&end_date=2025-07-31", Some(&token))
+        .await;
+    assert_eq!(legacy_range.status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn report_months() {
+    let app = TestApp::spawn().await;
+    let token = app.register_and_login("report_months").await;
+
+    let created = app
+        .post("/api/moods", Some(&token), json!({ "mood": "neutral", "date": "2025-03-14" }))
+        .await;
+    assert_eq!(created.status, StatusCode::OK, "{}", created.body);
+
+    let report = app.get("/api/reports/monthly?month=2025-03", Some(&token)).await;
+    assert_eq!(report.status, StatusCode::OK, "{}", report.body);
+    assert_eq!(report.body["start_date"], "2025-03-01");
+    assert_eq!(report.body["end_date"], "2025-03-31");
+    let daily = report.body["daily_scores"].as_array().expect("daily_scores");
+    assert!(daily.iter().all(|point| is_iso_date(&point["date"])));
+
+    for month in ["03-2025", "2025-3", "2025-13"] {
+        let rejected = app
+            .get(&format!("/api/reports/monthly?month={}", month), Some(&token))
+            .await;
+        assert_eq!(rejected.status, StatusCode::BAD_REQUEST, "{}", month);
+    }
+}
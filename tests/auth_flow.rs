@@ -0,0 +1,187 @@
+mod common;
+
+use axum::http::StatusCode;
+use serde_json::json;
+use common::{TestApp, TEST_PASSWORD};
+
+#[tokio::test]
+async fn register_login_profile_and_logout() {
+    let app = TestApp::spawn().await;
+
+    let registered = app.register("harness_user", "harness@example.com", TEST_PASSWORD).await;
+    assert_eq!(registered.status, StatusCode::OK, "{}", registered.body);
+    assert_eq!(registered.body["user"]["email"], "harness@example.com");
+
+    let logged_in = app.login("harness@example.com", TEST_PASSWORD).await;
+    assert_eq!(logged_in.status, StatusCode::OK, "{}", logged_in.body);
+    let token = logged_in.body["token"].as_str().expect("token missing").to_string();
+
+    let profile = app.get("/api/user/profile", Some(&token)).await;
+    assert_eq!(profile.status, StatusCode::OK, "{}", profile.body);
+    assert_eq!(profile.body["username"], "harness_user");
+
+    let logout = app.post("/api/auth/logout", Some(&token), json!({})).await;
+    assert_eq!(logout.status, StatusCode::OK, "{}", logout.body);
+
+    let after_logout = app.get("/api/user/profile", Some(&token)).await;
+    assert_eq!(after_logout.status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn rejects_wrong_password_and_duplicate_email() {
+    let app = TestApp::spawn().await;
+
+    app.register_and_login("duplicate_user").await;
+
+    let duplicate = app.register("other_user", "duplicate_user@example.com", TEST_PASSWORD).await;
+    assert_eq!(duplicate.status, StatusCode::CONFLICT, "{}", duplicate.body);
+
+    let wrong_password = app.login("duplicate_user@example.com", "Not-The-Password-1!").await;
+    assert_eq!(wrong_password.status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn mood_endpoints_require_authentication() {
+    let app = TestApp::spawn().await;
+
+    let anonymous = app.get("/api/moods", None).await;
+    assert_eq!(anonymous.status, StatusCode::UNAUTHORIZED);
+
+    let token = app.register_and_login("mood_user").await;
+    let created = app
+        .post("/api/moods", Some(&token), json!({ "mood": "happy", "date": "2025-07-23" }))
+        .await;
+    assert_eq!(created.status, StatusCode::OK, "{}", created.body);
+    assert_eq!(created.body["emoji"], "🙂");
+
+    let moods = app.get("/api/moods", Some(&token)).await;
+    assert_eq!(moods.status, StatusCode::OK, "{}", moods.body);
+}
+
+#[tokio::test]
+async fn login_accepts_username_or_email_in_any_case() {
+    let app = TestApp::spawn().await;
+
+    app.register_and_login("casey").await;
+
+    for identifier in ["casey", "CASEY", "Casey@Example.com", " casey@example.com "] {
+        let logged_in = app
+            .post("/api/auth/login", None, json!({ "identifier": identifier, "password": TEST_PASSWORD }))
+            .await;
+        assert_eq!(logged_in.status, StatusCode::OK, "{}: {}", identifier, logged_in.body);
+    }
+
+    let missing = app.post("/api/auth/login", None, json!({ "password": TEST_PASSWORD })).await;
+    assert_eq!(missing.status, StatusCode::BAD_REQUEST);
+
+    let duplicate = app.register("CASEY", "other@example.com", TEST_PASSWORD).await;
+    assert_eq!(duplicate.status, StatusCode::CONFLICT, "{}", duplicate.body);
+}
+
+#[tokio::test]
+async fn emails_are_normalized() {
+    let app = TestApp::spawn().await;
+
+    let registered = app.register("mixed_case", "  Mixed.Case@Example.COM ", TEST_PASSWORD).await;
+    assert_eq!(registered.status, StatusCode::OK, "{}", registered.body);
+    assert_eq!(registered.body["user"]["email"], "mixed.case@example.com");
+
+    let duplicate = app.register("mixed_case_2", "mixed.case@example.com", TEST_PASSWORD).await;
+    assert_eq!(duplicate.status, StatusCode::CONFLICT, "{}", duplicate.body);
+
+    let logged_in = app.login("MIXED.CASE@example.com", TEST_PASSWORD).await;
+    assert_eq!(logged_in.status, StatusCode::OK, "{}", logged_in.body);
+
+    let check = app.post("/api/user/check-email", None, json!({ "email": "Mixed.Case@Example.com" })).await;
+    assert_eq!(check.status, StatusCode::OK, "{}", check.body);
+    let reset_tokens: i64 = {
+        use diesel::prelude::*;
+        mindmate_be::schema::password_reset_tokens::table
+            .count()
+            .get_result(&mut app.pool.conn_write().expect("connection"))
+            .expect("count reset tokens")
+    };
+    assert_eq!(reset_tokens, 1);
+
+    let invalid = app.register("no_at_sign", "not-an-email", TEST_PASSWORD).await;
+    assert_eq!(invalid.status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn password_reset_does_not_reveal_accounts_and_uses_emailed_token() {
+    use diesel::prelude::*;
+    use mindmate_be::schema::password_reset_tokens;
+
+    let app = TestApp::spawn().await;
+
+    app.register_and_login("reset_user").await;
+
+    let known = app.post("/api/user/check-email", None, json!({ "email": "reset_user@example.com" })).await;
+    let unknown = app.post("/api/user/check-email", None, json!({ "email": "nobody@example.com" })).await;
+    assert_eq!(known.status, StatusCode::OK, "{}", known.body);
+    assert_eq!(known.body, unknown.body);
+
+    let token: String = password_reset_tokens::table
+        .select(password_reset_tokens::token)
+        .first(&mut app.pool.conn_write().expect("connection"))
+        .expect("reset token stored");
+
+    let new_password = "Quiet-Lantern-2026!";
+    let body = json!({ "token": token, "new_password": new_password, "confirm_password": new_password });
+    let reset = app.post("/api/user/reset-password", None, body.clone()).await;
+    assert_eq!(reset.status, StatusCode::OK, "{}", reset.body);
+
+    let reused = app.post("/api/user/reset-password", None, body).await;
+    assert_eq!(reused.status, StatusCode::BAD_REQUEST, "{}", reused.body);
+
+    let old_login = app.login("reset_user@example.com", TEST_PASSWORD).await;
+    assert_eq!(old_login.status, StatusCode::UNAUTHORIZED);
+    let new_login = app.login("reset_user@example.com", new_password).await;
+    assert_eq!(new_login.status, StatusCode::OK, "{}", new_login.body);
+}
+
+#[tokio::test]
+async fn expired_password_reset_token_is_deleted() {
+    use diesel::prelude::*;
+    use mindmate_be::schema::password_reset_tokens;
+
+    let app = TestApp::spawn().await;
+
+    app.register_and_login("expired_reset").await;
+    let requested = app.post("/api/user/check-email", None, json!({ "email": "expired_reset@example.com" })).await;
+    assert_eq!(requested.status, StatusCode::OK, "{}", requested.body);
+
+    let mut conn = app.pool.conn_write().expect("connection");
+    let token: String = diesel::update(password_reset_tokens::table)
+        .set(password_reset_tokens::expires_at.eq(chrono::Utc::now().naive_utc() - chrono::Duration::minutes(1)))
+        .returning(password_reset_tokens::token)
+        .get_result(&mut conn)
+        .expect("expire reset token");
+
+    let new_password = "Quiet-Lantern-2026!";
+    let body = json!({ "token": token, "new_password": new_password, "confirm_password": new_password });
+    let reset = app.post("/api/user/reset-password", None, body).await;
+    assert_eq!(reset.status, StatusCode::BAD_REQUEST, "{}", reset.body);
+
+    let remaining: i64 = password_reset_tokens::table.count().get_result(&mut conn).expect("count reset tokens");
+    assert_eq!(remaining, 0);
+}
+
+#[tokio::test]
+async fn lockout_looks_the_same_for_unknown_identifiers() {
+    let app = TestApp::spawn().await;
+
+    app.register_and_login("locked_user").await;
+
+    let mut responses = Vec::new();
+    for email in ["locked_user@example.com", "nobody_here@example.com"] {
+        let mut last = None;
+        for _ in 0..=mindmate_be::config::app_config::app_config().login_max_failures {
+            last = Some(app.login(email, "Not-The-Password-1!").await);
+        }
+        let last = last.expect("at least one attempt");
+        assert_eq!(last.status, StatusCode::TOO_MANY_REQUESTS, "{}", last.body);
+        responses.push(last.body);
+    }
+    assert_eq!(responses[0], responses[1]);
+}
@@ -0,0 +1,194 @@
+//! Harness test integrasi: router lengkap dijalankan in-process dengan `tower::ServiceExt`
+//! terhadap database PostgreSQL sementara.
+//!
+//! Set `TEST_DATABASE_URL` ke server PostgreSQL yang boleh dipakai membuat database,
+//! misalnya `postgres://postgres@localhost:5432/postgres`. Tanpa variabel ini setiap `TestApp`
+//! menjalankan kontainer PostgreSQL sendiri lewat Docker (testcontainers); jika Docker juga
+//! tidak tersedia test gagal, bukan dilewati. Setiap `TestApp` membuat database baru,
+//! menjalankan semua migrasi lalu menghapusnya lagi.
+#![allow(dead_code)]
+
+use std::env;
+use std::fs;
+use std::path::Path;
+use axum::body::{to_bytes, Body};
+use axum::http::{header, Method, Request, StatusCode};
+use axum::Router;
+use diesel::connection::SimpleConnection;
+use diesel::pg::PgConnection;
+use diesel::Connection;
+use rand::Rng;
+use serde_json::{json, Value};
+use testcontainers_modules::postgres::Postgres;
+use testcontainers_modules::testcontainers::runners::AsyncRunner;
+use testcontainers_modules::testcontainers::{ContainerAsync, ImageExt};
+use tower::ServiceExt;
+use mindmate_be::app::build_router;
+use mindmate_be::db::pool::DbPools;
+use mindmate_be::state::AppState;
+
+pub const TEST_PASSWORD: &str = "Calm-Harbor-2025!";
+
+pub struct TestResponse {
+    pub status: StatusCode,
+    pub body: Value,
+}
+
+pub struct TestApp {
+    router: Router,
+    pub pool: DbPools,
+    // Urutan drop: pool ditutup, database dihapus, lalu kontainer dihentikan
+    database: TestDatabase,
+    _container: Option<ContainerAsync<Postgres>>,
+}
+
+impl TestApp {
+    /// Router dengan database baru di TEST_DATABASE_URL, atau di kontainer PostgreSQL jika tidak di-set
+    pub async fn spawn() -> TestApp {
+        let (admin_url, container) = match env::var("TEST_DATABASE_URL") {
+            Ok(admin_url) => (admin_url, None),
+            Err(_) => {
+                let container = Postgres::default()
+                    .with_tag("16-alpine")
+                    .start()
+                    .await
+                    .expect("TEST_DATABASE_URL is not set and a PostgreSQL container could not be started (is Docker running?)");
+                let host = container.get_host().await.expect("Failed to get container host");
+                let port = container.get_host_port_ipv4(5432).await.expect("Failed to get container port");
+                (format!("postgres://postgres:postgres@{}:{}/postgres", host, port), Some(container))
+            }
+        };
+
+        let database = TestDatabase::create(&admin_url);
+        let pool = DbPools::from_urls(database.url.clone(), None);
+        let state = AppState::new(pool.clone()).expect("Failed to initialize application state");
+
+        TestApp {
+            router: build_router(state),
+            pool,
+            database,
+            _container: container,
+        }
+    }
+
+    pub async fn request(
+        &self,
+        method: Method,
+        uri: &str,
+        token: Option<&str>,
+        body: Option<Value>,
+    ) -> TestResponse {
+        let mut builder = Request::builder().method(method).uri(uri);
+        if let Some(token) = token {
+            builder = builder.header(header::AUTHORIZATION, format!("Bearer {}", token));
+        }
+        let request = match body {
+            Some(body) => builder
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(body.to_string())),
+            None => builder.body(Body::empty()),
+        }
+        .expect("Failed to build request");
+
+        let response = self.router.clone().oneshot(request).await.expect("Router failed");
+        let status = response.status();
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.expect("Failed to read body");
+        let body = serde_json::from_slice(&bytes)
+            .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(&bytes).into_owned()));
+
+        TestResponse { status, body }
+    }
+
+    pub async fn get(&self, uri: &str, token: Option<&str>) -> TestResponse {
+        self.request(Method::GET, uri, token, None).await
+    }
+
+    pub async fn post(&self, uri: &str, token: Option<&str>, body: Value) -> TestResponse {
+        self.request(Method::POST, uri, token, Some(body)).await
+    }
+
+    pub async fn register(&self, username: &str, email: &str, password: &str) -> TestResponse {
+        self.post(
+            "/api/auth/register",
+            None,
+            json!({
+                "username": username,
+                "email": email,
+                "age": 24,
+                "gender": "female",
+                "password": password,
+            }),
+        )
+        .await
+    }
+
+    pub async fn login(&self, email: &str, password: &str) -> TestResponse {
+        self.post("/api/auth/login", None, json!({ "email": email, "password": password }))
+            .await
+    }
+
+    /// Daftarkan pengguna baru dengan TEST_PASSWORD lalu kembalikan token login-nya
+    pub async fn register_and_login(&self, username: &str) -> String {
+        let email = format!("{}@example.com", username);
+        let registered = self.register(username, &email, TEST_PASSWORD).await;
+        assert_eq!(registered.status, StatusCode::OK, "register failed: {}", registered.body);
+
+        let logged_in = self.login(&email, TEST_PASSWORD).await;
+        assert_eq!(logged_in.status, StatusCode::OK, "login failed: {}", logged_in.body);
+        logged_in.body["token"].as_str().expect("token missing").to_string()
+    }
+}
+
+struct TestDatabase {
+    admin_url: String,
+    name: String,
+    url: String,
+}
+
+impl TestDatabase {
+    fn create(admin_url: &str) -> TestDatabase {
+        let name = format!("mindmate_test_{}", rand::thread_rng().gen::<u64>());
+        let mut admin = PgConnection::establish(admin_url).expect("Failed to connect to TEST_DATABASE_URL");
+        admin
+            .batch_execute(&format!("CREATE DATABASE {}", name))
+            .expect("Failed to create test database");
+
+        let mut url = url::Url::parse(admin_url).expect("Invalid TEST_DATABASE_URL");
+        url.set_path(&name);
+        let url = url.to_string();
+
+        let mut conn = PgConnection::establish(&url).expect("Failed to connect to test database");
+        run_migrations(&mut conn);
+
+        TestDatabase {
+            admin_url: admin_url.to_string(),
+            name,
+            url,
+        }
+    }
+}
+
+impl Drop for TestDatabase {
+    fn drop(&mut self) {
+        if let Ok(mut admin) = PgConnection::establish(&self.admin_url) {
+            let _ = admin.batch_execute(&format!("DROP DATABASE IF EXISTS {} WITH (FORCE)", self.name));
+        }
+    }
+}
+
+/// Jalankan `up.sql` semua migrasi sesuai urutan nama direktori
+fn run_migrations(conn: &mut PgConnection) {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("migrations");
+    let mut migrations: Vec<_> = fs::read_dir(&root)
+        .expect("Failed to read migrations directory")
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.join("up.sql").is_file())
+        .collect();
+    migrations.sort();
+
+    for migration in migrations {
+        let sql = fs::read_to_string(migration.join("up.sql")).expect("Failed to read migration");
+        conn.batch_execute(&sql)
+            .unwrap_or_else(|e| panic!("Migration {} failed: {}", migration.display(), e));
+    }
+}